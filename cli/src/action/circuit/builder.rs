@@ -14,6 +14,8 @@
 
 #[cfg(feature = "circuit-auth-type")]
 use splinter::admin::messages::AuthorizationType;
+#[cfg(feature = "circuit-durability")]
+use splinter::admin::messages::DurabilityType;
 use splinter::admin::messages::{
     BuilderError, CreateCircuit, CreateCircuitBuilder, SplinterNode, SplinterNodeBuilder,
     SplinterServiceBuilder,
@@ -32,6 +34,8 @@ pub struct CreateCircuitMessageBuilder {
     management_type: Option<String>,
     #[cfg(feature = "circuit-auth-type")]
     authorization_type: Option<AuthorizationType>,
+    #[cfg(feature = "circuit-durability")]
+    durability: Option<DurabilityType>,
     application_metadata: Vec<u8>,
     comments: Option<String>,
     display_name: Option<String>,
@@ -47,6 +51,8 @@ impl CreateCircuitMessageBuilder {
             management_type: None,
             #[cfg(feature = "circuit-auth-type")]
             authorization_type: None,
+            #[cfg(feature = "circuit-durability")]
+            durability: None,
             application_metadata: vec![],
             comments: None,
             display_name: None,
@@ -216,6 +222,7 @@ impl CreateCircuitMessageBuilder {
     pub fn set_authorization_type(&mut self, authorization_type: &str) -> Result<(), CliError> {
         let auth_type = match authorization_type {
             "trust" => AuthorizationType::Trust,
+            "challenge" => AuthorizationType::Challenge,
             _ => {
                 return Err(CliError::ActionError(format!(
                     "Invalid authorization type {}",
@@ -228,6 +235,23 @@ impl CreateCircuitMessageBuilder {
         Ok(())
     }
 
+    #[cfg(feature = "circuit-durability")]
+    pub fn set_durability(&mut self, durability: &str) -> Result<(), CliError> {
+        let durability = match durability {
+            "none" => DurabilityType::NoDurability,
+            "store-and-forward" => DurabilityType::StoreAndForward,
+            _ => {
+                return Err(CliError::ActionError(format!(
+                    "Invalid durability {}",
+                    durability
+                )))
+            }
+        };
+
+        self.durability = Some(durability);
+        Ok(())
+    }
+
     pub fn set_application_metadata(&mut self, application_metadata: &[u8]) {
         self.application_metadata = application_metadata.into();
     }
@@ -323,6 +347,12 @@ impl CreateCircuitMessageBuilder {
             None => create_circuit_builder,
         };
 
+        #[cfg(feature = "circuit-durability")]
+        let create_circuit_builder = match self.durability {
+            Some(durability) => create_circuit_builder.with_durability(&durability),
+            None => create_circuit_builder,
+        };
+
         let create_circuit = create_circuit_builder.build().map_err(|err| {
             CliError::ActionError(format!(
                 "Failed to build circuit: {}",
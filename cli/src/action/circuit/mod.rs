@@ -34,7 +34,7 @@ use crate::template::CircuitTemplate;
 
 use super::api::SplinterRestClientBuilder;
 use super::{
-    msg_from_io_error, read_private_key, Action, DEFAULT_SPLINTER_REST_API_URL,
+    msg_from_io_error, print_table, read_private_key, Action, DEFAULT_SPLINTER_REST_API_URL,
     SPLINTER_REST_API_URL_ENV,
 };
 
@@ -111,6 +111,13 @@ impl Action for CircuitProposeAction {
             None => (),
         }
 
+        #[cfg(feature = "circuit-durability")]
+        {
+            if let Some(durability) = args.value_of("durability") {
+                builder.set_durability(durability)?;
+            }
+        }
+
         if let Some(management_type) = args.value_of("management_type") {
             builder.set_management_type(management_type);
         }
@@ -624,6 +631,96 @@ fn vote_on_circuit_proposal(
     }
 }
 
+#[cfg(feature = "circuit-disband")]
+struct CircuitDisband {
+    circuit_id: String,
+}
+
+#[cfg(feature = "circuit-disband")]
+pub struct CircuitDisbandAction;
+
+#[cfg(feature = "circuit-disband")]
+impl Action for CircuitDisbandAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+        let key = args.value_of("private_key_file");
+
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        disband_circuit(&url, key, circuit_id)
+    }
+}
+
+#[cfg(feature = "circuit-disband")]
+fn disband_circuit(url: &str, key: Option<&str>, circuit_id: &str) -> Result<(), CliError> {
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url.to_string())
+        .with_auth(create_cylinder_jwt_auth(key)?)
+        .build()?;
+
+    let private_key_hex = read_private_key(key.unwrap_or("splinter"))?;
+
+    let requester_node = client.get_node_status()?.node_id;
+
+    let circuit_disband = CircuitDisband {
+        circuit_id: circuit_id.into(),
+    };
+    let signed_payload = make_signed_payload(&requester_node, &private_key_hex, circuit_disband)?;
+    client.submit_admin_payload(signed_payload)
+}
+
+#[cfg(feature = "circuit-abandon")]
+struct CircuitAbandon {
+    circuit_id: String,
+}
+
+#[cfg(feature = "circuit-abandon")]
+pub struct CircuitAbandonAction;
+
+#[cfg(feature = "circuit-abandon")]
+impl Action for CircuitAbandonAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+        let key = args.value_of("private_key_file");
+
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+
+        abandon_circuit(&url, key, circuit_id)
+    }
+}
+
+#[cfg(feature = "circuit-abandon")]
+fn abandon_circuit(url: &str, key: Option<&str>, circuit_id: &str) -> Result<(), CliError> {
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url.to_string())
+        .with_auth(create_cylinder_jwt_auth(key)?)
+        .build()?;
+
+    let private_key_hex = read_private_key(key.unwrap_or("splinter"))?;
+
+    let requester_node = client.get_node_status()?.node_id;
+
+    let circuit_abandon = CircuitAbandon {
+        circuit_id: circuit_id.into(),
+    };
+    let signed_payload = make_signed_payload(&requester_node, &private_key_hex, circuit_abandon)?;
+    client.submit_admin_payload(signed_payload)
+}
+
 pub struct CircuitListAction;
 
 impl Action for CircuitListAction {
@@ -898,36 +995,3 @@ fn list_proposals(
 
     Ok(())
 }
-
-// Takes a vec of vecs of strings. The first vec should include the title of the columns.
-// The max length of each column is calculated and is used as the column with when printing the
-// table.
-fn print_table(table: Vec<Vec<String>>) {
-    let mut max_lengths = Vec::new();
-
-    // find the max lengths of the columns
-    for row in table.iter() {
-        for (i, col) in row.iter().enumerate() {
-            if let Some(length) = max_lengths.get_mut(i) {
-                if col.len() > *length {
-                    *length = col.len()
-                }
-            } else {
-                max_lengths.push(col.len())
-            }
-        }
-    }
-
-    // print each row with correct column size
-    for row in table.iter() {
-        let mut col_string = String::from("");
-        for (i, len) in max_lengths.iter().enumerate() {
-            if let Some(value) = row.get(i) {
-                col_string += &format!("{}{} ", value, " ".repeat(*len - value.len()),);
-            } else {
-                col_string += &" ".repeat(*len);
-            }
-        }
-        println!("{}", col_string);
-    }
-}
@@ -14,10 +14,10 @@
 
 use clap::ArgMatches;
 use reqwest::{blocking::Client, StatusCode};
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
 
 use super::create_cylinder_jwt_auth;
-use super::{Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
+use super::{print_table, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
 
 use crate::error::CliError;
 
@@ -35,6 +35,16 @@ impl Action for StatusAction {
             .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
             .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
 
+        let format = arg_matches
+            .and_then(|args| {
+                if let Some(val) = args.value_of("hidden_format") {
+                    Some(val)
+                } else {
+                    args.value_of("format")
+                }
+            })
+            .unwrap_or("human");
+
         let key = arg_matches.and_then(|args| args.value_of("private_key_file"));
 
         Client::new()
@@ -52,7 +62,8 @@ impl Action for StatusAction {
                 _ => CliError::ActionError(format!("Unable to contact the server at {}", url)),
             })
             .and_then(|res| match res.status() {
-                StatusCode::OK => res.json().map_err(|_| {
+                // The node responds with a report when it is unhealthy, as well
+                StatusCode::OK | StatusCode::SERVICE_UNAVAILABLE => res.json().map_err(|_| {
                     CliError::ActionError("The server failed to send a valid response".into())
                 }),
                 StatusCode::NOT_FOUND => Err(CliError::ActionError(
@@ -63,14 +74,49 @@ impl Action for StatusAction {
                     status_code.as_u16()
                 ))),
             })
-            .and_then(|status: Value| {
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&status).map_err(|_| CliError::ActionError(
-                        "Failed to serialize response".into()
-                    ))?
-                );
+            .and_then(|report: HealthReport| {
+                if format == "json" {
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&report).map_err(|_| {
+                            CliError::ActionError("Failed to serialize response".into())
+                        })?
+                    );
+                } else {
+                    println!("Status: {}\n", report.status);
+                    let mut data = vec![vec![
+                        "CHECK".to_string(),
+                        "STATUS".to_string(),
+                        "DETAILS".to_string(),
+                    ]];
+                    data.extend(
+                        std::iter::once(&report.liveness)
+                            .chain(report.readiness.iter())
+                            .map(|check| {
+                                vec![
+                                    check.name.clone(),
+                                    check.status.clone(),
+                                    check.details.clone(),
+                                ]
+                            }),
+                    );
+                    print_table(data);
+                }
                 Ok(())
             })
     }
 }
+
+#[derive(Deserialize, Serialize)]
+struct HealthReport {
+    status: String,
+    liveness: CheckReport,
+    readiness: Vec<CheckReport>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CheckReport {
+    name: String,
+    status: String,
+    details: String,
+}
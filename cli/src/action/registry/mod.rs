@@ -15,9 +15,18 @@
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+#[cfg(feature = "registry-signatures")]
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use clap::ArgMatches;
+#[cfg(feature = "registry-signatures")]
+use cylinder::{
+    current_user_search_path, load_key, load_key_from_path, secp256k1::Secp256k1Context, Context,
+    Signer,
+};
 use splinter::registry::Node;
+#[cfg(feature = "registry-signatures")]
+use splinter::registry::{sign_registry_file, REGISTRY_SIGNATURE_FILE_EXTENSION};
 
 use crate::error::CliError;
 
@@ -140,9 +149,11 @@ impl Action for RegistryGenerateAction {
 
         nodes.push(node);
 
-        let yaml = serde_yaml::to_vec(&nodes).map_err(|err| {
+        let mut yaml = serde_yaml::to_vec(&nodes).map_err(|err| {
             CliError::ActionError(format!("Cannot format node list into yaml: {}", err))
         })?;
+        // Append newline to file
+        yaml.push(b'\n');
 
         let mut file = File::create(output_file).map_err(|err| {
             CliError::ActionError(format!(
@@ -158,17 +169,76 @@ impl Action for RegistryGenerateAction {
                 msg_from_io_error(err)
             ))
         })?;
-        // Append newline to file
-        writeln!(file).map_err(|err| {
-            CliError::ActionError(format!(
-                "Failed to write to file '{}': {}",
-                output_file,
-                msg_from_io_error(err)
-            ))
-        })?;
 
         info!("Added node '{}' to '{}'", node_status.node_id, output_file);
 
+        #[cfg(feature = "registry-signatures")]
+        {
+            if let Some(signing_keys) = args.values_of("signing_keys") {
+                let signers = signing_keys
+                    .map(load_signer)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let version = match args.value_of("registry_version") {
+                    Some(version) => version.parse::<u64>().map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Registry version is not a valid integer: {}",
+                            version
+                        ))
+                    })?,
+                    None => SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map_err(|err| {
+                            CliError::ActionError(format!("Failed to get current time: {}", err))
+                        })?
+                        .as_secs(),
+                };
+                let signature_file = sign_registry_file(
+                    &yaml,
+                    version,
+                    &signers.iter().map(|signer| &**signer).collect::<Vec<_>>(),
+                )
+                .map_err(|err| {
+                    CliError::ActionError(format!("Failed to sign '{}': {}", output_file, err))
+                })?;
+
+                let signature_path =
+                    format!("{}.{}", output_file, REGISTRY_SIGNATURE_FILE_EXTENSION);
+                std::fs::write(&signature_path, signature_file).map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Failed to write to file '{}': {}",
+                        signature_path,
+                        msg_from_io_error(err)
+                    ))
+                })?;
+
+                info!(
+                    "Signed '{}' as version {} with {} key(s); signatures written to '{}'",
+                    output_file,
+                    version,
+                    signers.len(),
+                    signature_path
+                );
+            }
+        }
+
         Ok(())
     }
 }
+
+/// Loads the private key with the given name from the current user's key search path, or from
+/// the given path if the name contains a '/', and returns a signer for it.
+#[cfg(feature = "registry-signatures")]
+fn load_signer(key_name: &str) -> Result<Box<dyn Signer>, CliError> {
+    let private_key = if key_name.contains('/') {
+        load_key_from_path(Path::new(key_name))
+            .map_err(|err| CliError::ActionError(err.to_string()))?
+    } else {
+        load_key(key_name, &current_user_search_path())
+            .map_err(|err| CliError::ActionError(err.to_string()))?
+            .ok_or_else(|| {
+                CliError::ActionError(format!("No signing key found with name '{}'", key_name))
+            })?
+    };
+
+    Ok(Secp256k1Context::new().new_signer(private_key))
+}
@@ -256,12 +256,25 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
     let propose_circuit = propose_circuit.arg(
         Arg::with_name("authorization_type")
             .long("auth-type")
-            .possible_values(&["trust"])
+            .possible_values(&["trust", "challenge"])
             .default_value("trust")
             .takes_value(true)
             .help("Authorization type for the circuit"),
     );
 
+    #[cfg(feature = "circuit-durability")]
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("durability")
+            .long("durability")
+            .possible_values(&["none", "store-and-forward"])
+            .default_value("none")
+            .takes_value(true)
+            .help(
+                "Durability of the circuit's messages; with store-and-forward, messages to a \
+                 disconnected node are stored and delivered when it reconnects",
+            ),
+    );
+
     #[cfg(feature = "circuit-template")]
     let propose_circuit = propose_circuit
         .arg(
@@ -617,6 +630,62 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             .subcommand(cert_generate_subcommand),
     );
 
+    #[cfg(feature = "circuit-abandon")]
+    let circuit_command = circuit_command.subcommand(
+        SubCommand::with_name("abandon")
+            .about("Abandon an existing circuit on the local node, without a vote")
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of Splinter Daemon"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Path to private key file"),
+            )
+            .arg(
+                Arg::with_name("circuit_id")
+                    .value_name("circuit-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the circuit to be abandoned"),
+            ),
+    );
+
+    #[cfg(feature = "circuit-disband")]
+    let circuit_command = circuit_command.subcommand(
+        SubCommand::with_name("disband")
+            .about("Propose that an existing circuit be disbanded")
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true)
+                    .help("URL of Splinter Daemon"),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Path to private key file"),
+            )
+            .arg(
+                Arg::with_name("circuit_id")
+                    .value_name("circuit-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the circuit to be disbanded"),
+            ),
+    );
+
     #[cfg(feature = "circuit-template")]
     let circuit_command = circuit_command.subcommand(
         SubCommand::with_name("template")
@@ -659,51 +728,79 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
 
     app = app.subcommand(circuit_command);
 
+    #[allow(unused_mut)]
+    let mut registry_build_command = SubCommand::with_name("build")
+        .about("Add a node to a YAML file")
+        .arg(Arg::with_name("file").long("file").takes_value(true).help(
+            "Path of registry file to add node to; defaults to \
+                    './nodes.yaml'",
+        ))
+        .arg(
+            Arg::with_name("force")
+                .long("force")
+                .help("Overwrite node if it already exists"),
+        )
+        .arg(
+            Arg::with_name("status_url")
+                .takes_value(true)
+                .help("URL of splinter REST API to query for node data"),
+        )
+        .arg(
+            Arg::with_name("key_files")
+                .long("key-file")
+                .takes_value(true)
+                .multiple(true)
+                .required(true)
+                .help("Path of public key file to include with node"),
+        )
+        .arg(
+            Arg::with_name("metadata")
+                .long("metadata")
+                .takes_value(true)
+                .multiple(true)
+                .help("Metadata to include with node (<key>=<value>)"),
+        )
+        .arg(
+            Arg::with_name("private_key_file")
+                .value_name("private-key-file")
+                .short("k")
+                .long("key")
+                .takes_value(true)
+                .help("Name or path of private key"),
+        );
+
+    #[cfg(feature = "registry-signatures")]
+    {
+        registry_build_command = registry_build_command.arg(
+            Arg::with_name("signing_keys")
+                .value_name("signing-key")
+                .long("signing-key")
+                .takes_value(true)
+                .multiple(true)
+                .help(
+                    "Name or path of a publisher's private key to sign the registry file with; \
+                     the signatures are written to the registry file's path with '.sig' appended",
+                ),
+        )
+        .arg(
+            Arg::with_name("registry_version")
+                .value_name("version")
+                .long("registry-version")
+                .takes_value(true)
+                .requires("signing_keys")
+                .help(
+                    "Version to sign the registry file as, which must be greater than that of \
+                     any previously signed registry file; defaults to the current Unix time in \
+                     seconds",
+                ),
+        );
+    }
+
     app = app.subcommand(
         SubCommand::with_name("registry")
             .about("Splinter registry commands")
             .setting(AppSettings::SubcommandRequiredElseHelp)
-            .subcommand(
-                SubCommand::with_name("build")
-                    .about("Add a node to a YAML file")
-                    .arg(Arg::with_name("file").long("file").takes_value(true).help(
-                        "Path of registry file to add node to; defaults to \
-                                './nodes.yaml'",
-                    ))
-                    .arg(
-                        Arg::with_name("force")
-                            .long("force")
-                            .help("Overwrite node if it already exists"),
-                    )
-                    .arg(
-                        Arg::with_name("status_url")
-                            .takes_value(true)
-                            .help("URL of splinter REST API to query for node data"),
-                    )
-                    .arg(
-                        Arg::with_name("key_files")
-                            .long("key-file")
-                            .takes_value(true)
-                            .multiple(true)
-                            .required(true)
-                            .help("Path of public key file to include with node"),
-                    )
-                    .arg(
-                        Arg::with_name("metadata")
-                            .long("metadata")
-                            .takes_value(true)
-                            .multiple(true)
-                            .help("Metadata to include with node (<key>=<value>)"),
-                    )
-                    .arg(
-                        Arg::with_name("private_key_file")
-                            .value_name("private-key-file")
-                            .short("k")
-                            .long("key")
-                            .takes_value(true)
-                            .help("Name or path of private key"),
-                    ),
-            ),
+            .subcommand(registry_build_command),
     );
 
     #[cfg(feature = "health")]
@@ -715,8 +812,8 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 .subcommand(
                     SubCommand::with_name("status")
                         .about(
-                            "Displays a node's version, endpoint, node id, and a list\n\
-                             of endpoints of its connected peers",
+                            "Displays the result of a node's liveness and readiness\n\
+                             checks",
                         )
                         .arg(
                             Arg::with_name("url")
@@ -725,6 +822,23 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .help("URL of the Splinter daemon REST API")
                                 .takes_value(true),
                         )
+                        .arg(
+                            Arg::with_name("format")
+                                .short("F")
+                                .long("format")
+                                .help("Output format")
+                                .possible_values(&["human", "json"])
+                                .default_value("human")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("hidden_format")
+                                .short("f")
+                                .hidden(true)
+                                .help("Output format")
+                                .possible_values(&["human", "json"])
+                                .takes_value(true),
+                        )
                         .arg(
                             Arg::with_name("private_key_file")
                                 .value_name("private-key-file")
@@ -873,6 +987,12 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         .with_command("show", circuit::CircuitShowAction)
         .with_command("proposals", circuit::CircuitProposalsAction);
 
+    #[cfg(feature = "circuit-abandon")]
+    let circuit_command = circuit_command.with_command("abandon", circuit::CircuitAbandonAction);
+
+    #[cfg(feature = "circuit-disband")]
+    let circuit_command = circuit_command.with_command("disband", circuit::CircuitDisbandAction);
+
     #[cfg(feature = "circuit-template")]
     let circuit_command = circuit_command.with_command(
         "template",
@@ -36,7 +36,8 @@ use gameroom_database::{
 use scabbard::{protocol::SCABBARD_PROTOCOL_VERSION, service::StateChangeEvent};
 use splinter::{
     admin::messages::{
-        AdminServiceEvent, CircuitProposal, CreateCircuit, SplinterNode, SplinterService,
+        AdminServiceEvent, CircuitProposal, CreateCircuit, ProposalType, SplinterNode,
+        SplinterService,
     },
     events::{Igniter, ParseBytes, ParseError, WebSocketClient, WebSocketError, WsResponse},
 };
@@ -244,6 +245,25 @@ fn process_admin_event(
 ) -> Result<(), AppAuthHandlerError> {
     debug!("Received the event at {}", event.timestamp);
     let time: SystemTime = SystemTime::UNIX_EPOCH + Duration::from_millis(event.timestamp);
+
+    // Gamerooms are only created from circuit create proposals; events for any other proposal
+    // types, other than the circuit being disbanded, abandoned or having its application
+    // metadata updated, do not apply to gameroom.
+    match &event.admin_event {
+        AdminServiceEvent::CircuitDisbanded(_)
+        | AdminServiceEvent::CircuitAbandoned(_)
+        | AdminServiceEvent::CircuitMetadataUpdated(_) => (),
+        admin_event if admin_event.proposal().proposal_type != ProposalType::Create => {
+            debug!(
+                "Ignoring {:?} proposal for circuit {}",
+                admin_event.proposal().proposal_type,
+                admin_event.proposal().circuit_id
+            );
+            return Ok(());
+        }
+        _ => (),
+    }
+
     match event.admin_event {
         AdminServiceEvent::ProposalSubmitted(msg_proposal) => {
             // convert requester public key to hex
@@ -556,6 +576,72 @@ fn process_admin_event(
 
             igniter.start_ws(&xo_ws).map_err(AppAuthHandlerError::from)
         }
+        AdminServiceEvent::CircuitDisbanded(msg_proposal) => {
+            let conn = &*pool.get()?;
+
+            conn.transaction::<_, AppAuthHandlerError, _>(|| {
+                let notification = helpers::create_new_notification(
+                    "circuit_disbanded",
+                    &to_hex(&msg_proposal.requester),
+                    &msg_proposal.requester_node_id,
+                    &msg_proposal.circuit_id,
+                );
+                helpers::insert_gameroom_notification(conn, &[notification])?;
+                helpers::update_gameroom_status(
+                    conn,
+                    &msg_proposal.circuit_id,
+                    &time,
+                    "Disbanded",
+                )?;
+
+                debug!("Updated gameroom to status 'Disbanded'");
+
+                Ok(())
+            })
+        }
+        AdminServiceEvent::CircuitAbandoned(msg_proposal) => {
+            let conn = &*pool.get()?;
+
+            conn.transaction::<_, AppAuthHandlerError, _>(|| {
+                let notification = helpers::create_new_notification(
+                    "circuit_abandoned",
+                    &to_hex(&msg_proposal.requester),
+                    &msg_proposal.requester_node_id,
+                    &msg_proposal.circuit_id,
+                );
+                helpers::insert_gameroom_notification(conn, &[notification])?;
+                helpers::update_gameroom_status(
+                    conn,
+                    &msg_proposal.circuit_id,
+                    &time,
+                    "Abandoned",
+                )?;
+
+                debug!("Updated gameroom to status 'Abandoned'");
+
+                Ok(())
+            })
+        }
+        AdminServiceEvent::CircuitMetadataUpdated(msg_proposal) => {
+            let application_metadata =
+                ApplicationMetadata::from_bytes(&msg_proposal.circuit.application_metadata)?;
+
+            let conn = &*pool.get()?;
+
+            helpers::update_gameroom_alias(
+                conn,
+                &msg_proposal.circuit_id,
+                &time,
+                application_metadata.alias(),
+            )?;
+
+            debug!(
+                "Updated gameroom alias to '{}'",
+                application_metadata.alias()
+            );
+
+            Ok(())
+        }
     }
 }
 
@@ -0,0 +1,226 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A typed subscriber for the admin service's application registration websocket.
+//!
+//! An [`AdminEventSubscriber`] connects to `/ws/admin/register/{type}` for a single circuit
+//! management type and passes each [`AdminEvent`] it receives to an [`AdminEventHandler`]. After
+//! the handler has processed an event, the event's cursor is saved to an
+//! [`AdminEventCursorStore`]. When the subscriber is started again, or reconnects after losing
+//! its connection, it requests only the events after the saved cursor.
+//!
+//! ```no_run
+//! use splinter::admin::client::event::{
+//!     AdminEvent, AdminEventHandler, AdminEventSubscriberBuilder, FileAdminEventCursorStore,
+//! };
+//! use splinter::error::InternalError;
+//! use splinter::events::Reactor;
+//!
+//! struct LoggingHandler;
+//!
+//! impl AdminEventHandler for LoggingHandler {
+//!     fn handle_event(&mut self, event: &AdminEvent) -> Result<(), InternalError> {
+//!         println!("Received {:?}", event.admin_event());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let reactor = Reactor::new();
+//!
+//! let subscriber = AdminEventSubscriberBuilder::new()
+//!     .with_splinterd_url("http://localhost:8085")
+//!     .with_authorization("Bearer token")
+//!     .with_circuit_management_type("gameroom")
+//!     .with_cursor_store(Box::new(FileAdminEventCursorStore::new("/var/lib/app/cursors.json")))
+//!     .with_handler(Box::new(LoggingHandler))
+//!     .build()
+//!     .expect("Failed to build subscriber");
+//!
+//! subscriber
+//!     .start(&reactor.igniter())
+//!     .expect("Failed to start subscriber");
+//! ```
+//!
+//! [`AdminEventSubscriber`]: struct.AdminEventSubscriber.html
+//! [`AdminEvent`]: struct.AdminEvent.html
+//! [`AdminEventHandler`]: trait.AdminEventHandler.html
+//! [`AdminEventCursorStore`]: trait.AdminEventCursorStore.html
+
+mod store;
+mod subscriber;
+
+use crate::admin::messages::AdminServiceEvent;
+use crate::error::InternalError;
+use crate::events::{ParseBytes, ParseError};
+
+#[cfg(any(
+    feature = "admin-service-event-client-store-postgres",
+    feature = "admin-service-event-client-store-sqlite"
+))]
+pub use store::DieselAdminEventCursorStore;
+pub use store::{
+    AdminEventCursorStore, AdminEventCursorStoreError, FileAdminEventCursorStore,
+    MemoryAdminEventCursorStore,
+};
+pub use subscriber::{AdminEventSubscriber, AdminEventSubscriberBuilder};
+
+/// An admin service event, as delivered over the application registration websocket.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct AdminEvent {
+    timestamp: u64,
+    #[serde(default)]
+    event_id: Option<i64>,
+    #[serde(flatten)]
+    admin_event: AdminServiceEvent,
+}
+
+impl AdminEvent {
+    /// Returns the time the event was emitted, in milliseconds since the Unix epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Returns the event's ID, if the admin service is backed by an event store.
+    pub fn event_id(&self) -> Option<i64> {
+        self.event_id
+    }
+
+    /// Returns the event.
+    pub fn admin_event(&self) -> &AdminServiceEvent {
+        &self.admin_event
+    }
+
+    /// Returns the event, consuming the wrapper.
+    pub fn into_admin_event(self) -> AdminServiceEvent {
+        self.admin_event
+    }
+
+    /// Returns the value to resume from after this event.
+    ///
+    /// The admin service interprets the `last` query parameter as an event ID when it is backed
+    /// by an event store, and as a timestamp otherwise. The event ID is only sent in the former
+    /// case, so it is used when present and the timestamp is used when it is not.
+    pub fn cursor(&self) -> u64 {
+        match self.event_id {
+            Some(event_id) if event_id >= 0 => event_id as u64,
+            _ => self.timestamp,
+        }
+    }
+}
+
+impl ParseBytes<AdminEvent> for AdminEvent {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        serde_json::from_slice(bytes).map_err(|err| ParseError::MalformedMessage(Box::new(err)))
+    }
+}
+
+/// Processes the admin events received by an `AdminEventSubscriber`.
+pub trait AdminEventHandler: Send {
+    /// Handles a single event.
+    ///
+    /// The event is only marked as processed if this returns `Ok`. If an error is returned, the
+    /// subscriber closes its connection without advancing its cursor, so the event is delivered
+    /// again the next time the subscriber is started.
+    fn handle_event(&mut self, event: &AdminEvent) -> Result<(), InternalError>;
+}
+
+impl<F> AdminEventHandler for F
+where
+    F: FnMut(&AdminEvent) -> Result<(), InternalError> + Send,
+{
+    fn handle_event(&mut self, event: &AdminEvent) -> Result<(), InternalError> {
+        (*self)(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::admin::messages::{
+        AuthorizationType, CircuitProposal, CircuitStatus, CreateCircuit, DurabilityType,
+        PersistenceType, ProposalType, RouteType,
+    };
+
+    /// Verify that an event sent by an admin service backed by an event store is parsed, and
+    /// that its event ID is used as its cursor.
+    #[test]
+    fn parse_event_with_event_id() {
+        let mut json = serde_json::to_value(AdminServiceEvent::CircuitReady(proposal()))
+            .expect("Failed to serialize event");
+        json["timestamp"] = serde_json::json!(1_612_000_000_000u64);
+        json["event_id"] = serde_json::json!(42);
+
+        let event =
+            AdminEvent::from_bytes(json.to_string().as_bytes()).expect("Failed to parse event");
+
+        assert_eq!(event.event_id(), Some(42));
+        assert_eq!(event.cursor(), 42);
+        assert_eq!(
+            event.admin_event(),
+            &AdminServiceEvent::CircuitReady(proposal())
+        );
+    }
+
+    /// Verify that an event sent by an admin service without an event store is parsed, and that
+    /// its timestamp is used as its cursor.
+    #[test]
+    fn parse_event_without_event_id() {
+        let mut json = serde_json::to_value(AdminServiceEvent::ProposalSubmitted(proposal()))
+            .expect("Failed to serialize event");
+        json["timestamp"] = serde_json::json!(1_612_000_000_000u64);
+
+        let event =
+            AdminEvent::from_bytes(json.to_string().as_bytes()).expect("Failed to parse event");
+
+        assert_eq!(event.event_id(), None);
+        assert_eq!(event.cursor(), 1_612_000_000_000);
+        assert_eq!(
+            event.into_admin_event(),
+            AdminServiceEvent::ProposalSubmitted(proposal())
+        );
+    }
+
+    /// Verify that a message that is not an admin event is rejected.
+    #[test]
+    fn parse_malformed_event() {
+        assert!(AdminEvent::from_bytes(b"{\"timestamp\": 0}").is_err());
+    }
+
+    pub(super) fn proposal() -> CircuitProposal {
+        CircuitProposal {
+            proposal_type: ProposalType::Create,
+            circuit_id: "WBKLF-BBBBB".into(),
+            circuit_hash: "7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d".into(),
+            circuit: CreateCircuit {
+                circuit_id: "WBKLF-BBBBB".into(),
+                roster: vec![],
+                members: vec![],
+                authorization_type: AuthorizationType::Trust,
+                persistence: PersistenceType::Any,
+                durability: DurabilityType::NoDurability,
+                routes: RouteType::Any,
+                circuit_management_type: "gameroom".into(),
+                application_metadata: vec![],
+                comments: None,
+                display_name: None,
+                circuit_version: 1,
+                circuit_status: CircuitStatus::Active,
+            },
+            votes: vec![],
+            requester: vec![1, 2, 3],
+            requester_node_id: "acme-node-000".into(),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Diesel-backed AdminEventCursorStore implementation.
+
+mod models;
+mod operations;
+mod schema;
+
+use std::convert::TryFrom;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::error::InternalError;
+
+use super::{AdminEventCursorStore, AdminEventCursorStoreError};
+
+use models::AdminEventClientCursorModel;
+use operations::get_cursor::AdminEventCursorStoreGetCursorOperation as _;
+use operations::set_cursor::AdminEventCursorStoreSetCursorOperation as _;
+use operations::AdminEventCursorStoreOperations;
+
+/// A Diesel-backed AdminEventCursorStore
+pub struct DieselAdminEventCursorStore<C: diesel::Connection + 'static> {
+    connection_pool: Pool<ConnectionManager<C>>,
+}
+
+impl<C: diesel::Connection + 'static> DieselAdminEventCursorStore<C> {
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        Self { connection_pool }
+    }
+}
+
+#[cfg(feature = "admin-service-event-client-store-sqlite")]
+impl AdminEventCursorStore for DieselAdminEventCursorStore<diesel::sqlite::SqliteConnection> {
+    fn get_cursor(&self, subscriber_id: &str) -> Result<Option<u64>, AdminEventCursorStoreError> {
+        AdminEventCursorStoreOperations::new(&*self.connection_pool.get()?)
+            .get_cursor(subscriber_id)?
+            .map(cursor_from_model)
+            .transpose()
+    }
+
+    fn set_cursor(
+        &self,
+        subscriber_id: &str,
+        cursor: u64,
+    ) -> Result<(), AdminEventCursorStoreError> {
+        AdminEventCursorStoreOperations::new(&*self.connection_pool.get()?)
+            .set_cursor(cursor_into_model(subscriber_id, cursor)?)
+    }
+
+    fn clone_box(&self) -> Box<dyn AdminEventCursorStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "admin-service-event-client-store-postgres")]
+impl AdminEventCursorStore for DieselAdminEventCursorStore<diesel::pg::PgConnection> {
+    fn get_cursor(&self, subscriber_id: &str) -> Result<Option<u64>, AdminEventCursorStoreError> {
+        AdminEventCursorStoreOperations::new(&*self.connection_pool.get()?)
+            .get_cursor(subscriber_id)?
+            .map(cursor_from_model)
+            .transpose()
+    }
+
+    fn set_cursor(
+        &self,
+        subscriber_id: &str,
+        cursor: u64,
+    ) -> Result<(), AdminEventCursorStoreError> {
+        AdminEventCursorStoreOperations::new(&*self.connection_pool.get()?)
+            .set_cursor(cursor_into_model(subscriber_id, cursor)?)
+    }
+
+    fn clone_box(&self) -> Box<dyn AdminEventCursorStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+fn cursor_from_model(event_cursor: i64) -> Result<u64, AdminEventCursorStoreError> {
+    u64::try_from(event_cursor).map_err(|err| {
+        AdminEventCursorStoreError::InternalError(InternalError::from_source_with_prefix(
+            Box::new(err),
+            format!("Stored cursor {} is invalid", event_cursor),
+        ))
+    })
+}
+
+fn cursor_into_model(
+    subscriber_id: &str,
+    cursor: u64,
+) -> Result<AdminEventClientCursorModel, AdminEventCursorStoreError> {
+    let event_cursor = i64::try_from(cursor).map_err(|err| {
+        AdminEventCursorStoreError::InternalError(InternalError::from_source_with_prefix(
+            Box::new(err),
+            format!("Cursor {} is too large to be stored", cursor),
+        ))
+    })?;
+
+    Ok(AdminEventClientCursorModel {
+        subscriber_id: subscriber_id.to_string(),
+        event_cursor,
+    })
+}
+
+impl From<diesel::r2d2::PoolError> for AdminEventCursorStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        AdminEventCursorStoreError::InternalError(InternalError::from_source(Box::new(err)))
+    }
+}
+
+impl From<diesel::result::Error> for AdminEventCursorStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        AdminEventCursorStoreError::InternalError(InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(all(test, feature = "admin-service-event-client-store-sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::admin::client::event::store::tests::{
+        test_cursors_per_subscriber, test_set_and_get_cursor,
+    };
+    use crate::migrations::run_sqlite_migrations;
+
+    #[test]
+    fn sqlite_set_and_get_cursor() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselAdminEventCursorStore::new(pool);
+        test_set_and_get_cursor(&store);
+    }
+
+    #[test]
+    fn sqlite_cursors_per_subscriber() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselAdminEventCursorStore::new(pool);
+        test_cursors_per_subscriber(&store);
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection insures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
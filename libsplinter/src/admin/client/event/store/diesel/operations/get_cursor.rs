@@ -0,0 +1,40 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::prelude::*;
+
+use crate::admin::client::event::store::{
+    diesel::schema::admin_event_client_cursor, AdminEventCursorStoreError,
+};
+
+use super::AdminEventCursorStoreOperations;
+
+pub(in crate::admin::client::event::store::diesel) trait AdminEventCursorStoreGetCursorOperation {
+    fn get_cursor(&self, subscriber_id: &str) -> Result<Option<i64>, AdminEventCursorStoreError>;
+}
+
+impl<'a, C> AdminEventCursorStoreGetCursorOperation for AdminEventCursorStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn get_cursor(&self, subscriber_id: &str) -> Result<Option<i64>, AdminEventCursorStoreError> {
+        admin_event_client_cursor::table
+            .filter(admin_event_client_cursor::subscriber_id.eq(subscriber_id))
+            .select(admin_event_client_cursor::event_cursor)
+            .first::<i64>(self.conn)
+            .optional()
+            .map_err(AdminEventCursorStoreError::from)
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{
+    dsl::{insert_into, update},
+    prelude::*,
+};
+
+use crate::admin::client::event::store::{
+    diesel::{models::AdminEventClientCursorModel, schema::admin_event_client_cursor},
+    AdminEventCursorStoreError,
+};
+
+use super::AdminEventCursorStoreOperations;
+
+pub(in crate::admin::client::event::store::diesel) trait AdminEventCursorStoreSetCursorOperation {
+    fn set_cursor(
+        &self,
+        cursor: AdminEventClientCursorModel,
+    ) -> Result<(), AdminEventCursorStoreError>;
+}
+
+#[cfg(feature = "admin-service-event-client-store-sqlite")]
+impl<'a> AdminEventCursorStoreSetCursorOperation
+    for AdminEventCursorStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_cursor(
+        &self,
+        cursor: AdminEventClientCursorModel,
+    ) -> Result<(), AdminEventCursorStoreError> {
+        self.conn
+            .transaction::<_, AdminEventCursorStoreError, _>(|| {
+                let updated = update(admin_event_client_cursor::table.find(&cursor.subscriber_id))
+                    .set(admin_event_client_cursor::event_cursor.eq(cursor.event_cursor))
+                    .execute(self.conn)?;
+
+                if updated == 0 {
+                    insert_into(admin_event_client_cursor::table)
+                        .values(&cursor)
+                        .execute(self.conn)?;
+                }
+
+                Ok(())
+            })
+    }
+}
+
+#[cfg(feature = "admin-service-event-client-store-postgres")]
+impl<'a> AdminEventCursorStoreSetCursorOperation
+    for AdminEventCursorStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_cursor(
+        &self,
+        cursor: AdminEventClientCursorModel,
+    ) -> Result<(), AdminEventCursorStoreError> {
+        self.conn
+            .transaction::<_, AdminEventCursorStoreError, _>(|| {
+                let updated = update(admin_event_client_cursor::table.find(&cursor.subscriber_id))
+                    .set(admin_event_client_cursor::event_cursor.eq(cursor.event_cursor))
+                    .execute(self.conn)?;
+
+                if updated == 0 {
+                    insert_into(admin_event_client_cursor::table)
+                        .values(&cursor)
+                        .execute(self.conn)?;
+                }
+
+                Ok(())
+            })
+    }
+}
@@ -0,0 +1,193 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! File-backed AdminEventCursorStore implementation.
+
+use std::collections::BTreeMap;
+use std::fs::{rename, File};
+use std::io::{ErrorKind, Write};
+use std::sync::{Arc, Mutex};
+
+use crate::error::InternalError;
+
+use super::{AdminEventCursorStore, AdminEventCursorStoreError};
+
+/// A file-backed implementation of AdminEventCursorStore.
+///
+/// The cursors of all subscribers are kept in a single JSON file, which is created on the first
+/// call to `set_cursor`. Each update is written to a temporary file that is then moved into
+/// place, so a crash while writing never leaves the file partially written.
+#[derive(Clone)]
+pub struct FileAdminEventCursorStore {
+    file_path: String,
+    lock: Arc<Mutex<()>>,
+}
+
+impl FileAdminEventCursorStore {
+    /// Constructs a new instance that stores its cursors in the file at the given path.
+    pub fn new(file_path: &str) -> Self {
+        Self {
+            file_path: file_path.to_string(),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    fn read_cursors(&self) -> Result<BTreeMap<String, u64>, AdminEventCursorStoreError> {
+        let file = match File::open(&self.file_path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(err) => {
+                return Err(InternalError::from_source_with_prefix(
+                    Box::new(err),
+                    format!("Failed to open cursor file '{}'", self.file_path),
+                )
+                .into())
+            }
+        };
+
+        serde_json::from_reader(file).map_err(|err| {
+            AdminEventCursorStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                format!("Failed to read cursor file '{}'", self.file_path),
+            ))
+        })
+    }
+
+    fn write_cursors(
+        &self,
+        cursors: &BTreeMap<String, u64>,
+    ) -> Result<(), AdminEventCursorStoreError> {
+        let output = serde_json::to_vec(cursors).map_err(|err| {
+            AdminEventCursorStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                "Failed to serialize cursors".to_string(),
+            ))
+        })?;
+
+        // write the cursors to a temporary file to avoid corrupting them if an IO error occurs
+        // during write
+        let temp_file_path = format!("{}.temp", self.file_path);
+        let mut temp_file = File::create(&temp_file_path).map_err(|err| {
+            AdminEventCursorStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                format!("Failed to open temp cursor file '{}'", temp_file_path),
+            ))
+        })?;
+
+        temp_file
+            .write_all(&output)
+            .and_then(|_| temp_file.sync_all())
+            .map_err(|err| {
+                AdminEventCursorStoreError::InternalError(InternalError::from_source_with_prefix(
+                    Box::new(err),
+                    format!("Failed to write temp cursor file '{}'", temp_file_path),
+                ))
+            })?;
+
+        rename(&temp_file_path, &self.file_path).map_err(|err| {
+            AdminEventCursorStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                format!(
+                    "Failed to rename temp cursor file to final location '{}'",
+                    self.file_path
+                ),
+            ))
+        })
+    }
+}
+
+impl AdminEventCursorStore for FileAdminEventCursorStore {
+    fn get_cursor(&self, subscriber_id: &str) -> Result<Option<u64>, AdminEventCursorStoreError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| InternalError::with_message("cursor file lock was poisoned".into()))?;
+
+        Ok(self.read_cursors()?.get(subscriber_id).copied())
+    }
+
+    fn set_cursor(
+        &self,
+        subscriber_id: &str,
+        cursor: u64,
+    ) -> Result<(), AdminEventCursorStoreError> {
+        let _guard = self
+            .lock
+            .lock()
+            .map_err(|_| InternalError::with_message("cursor file lock was poisoned".into()))?;
+
+        let mut cursors = self.read_cursors()?;
+        cursors.insert(subscriber_id.to_string(), cursor);
+        self.write_cursors(&cursors)
+    }
+
+    fn clone_box(&self) -> Box<dyn AdminEventCursorStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    use crate::admin::client::event::store::tests::{
+        test_cursors_per_subscriber, test_set_and_get_cursor,
+    };
+
+    #[test]
+    fn file_set_and_get_cursor() {
+        let temp_dir = TempDir::new("file_set_and_get_cursor").expect("Failed to create temp dir");
+        let store = FileAdminEventCursorStore::new(&cursor_file_path(&temp_dir));
+        test_set_and_get_cursor(&store);
+    }
+
+    #[test]
+    fn file_cursors_per_subscriber() {
+        let temp_dir =
+            TempDir::new("file_cursors_per_subscriber").expect("Failed to create temp dir");
+        let store = FileAdminEventCursorStore::new(&cursor_file_path(&temp_dir));
+        test_cursors_per_subscriber(&store);
+    }
+
+    /// Verify that cursors written by one instance are read by a new instance using the same
+    /// file, as happens when an application restarts.
+    #[test]
+    fn file_cursor_survives_restart() {
+        let temp_dir =
+            TempDir::new("file_cursor_survives_restart").expect("Failed to create temp dir");
+        let path = cursor_file_path(&temp_dir);
+
+        FileAdminEventCursorStore::new(&path)
+            .set_cursor("gameroom", 7)
+            .expect("Failed to set cursor");
+
+        assert_eq!(
+            Some(7),
+            FileAdminEventCursorStore::new(&path)
+                .get_cursor("gameroom")
+                .expect("Failed to get cursor")
+        );
+    }
+
+    fn cursor_file_path(temp_dir: &TempDir) -> String {
+        temp_dir
+            .path()
+            .join("cursors.json")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string()
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory-backed AdminEventCursorStore implementation.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::error::InternalError;
+
+use super::{AdminEventCursorStore, AdminEventCursorStoreError};
+
+/// A memory-backed implementation of AdminEventCursorStore.
+///
+/// Cursors in this store are lost when the process exits, so it is only suitable for testing or
+/// for subscribers that do not need to resume after a restart.
+#[derive(Clone, Default)]
+pub struct MemoryAdminEventCursorStore {
+    cursors: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl MemoryAdminEventCursorStore {
+    /// Constructs a new instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl AdminEventCursorStore for MemoryAdminEventCursorStore {
+    fn get_cursor(&self, subscriber_id: &str) -> Result<Option<u64>, AdminEventCursorStoreError> {
+        Ok(self
+            .cursors
+            .lock()
+            .map_err(|_| InternalError::with_message("cursors lock was poisoned".into()))?
+            .get(subscriber_id)
+            .copied())
+    }
+
+    fn set_cursor(
+        &self,
+        subscriber_id: &str,
+        cursor: u64,
+    ) -> Result<(), AdminEventCursorStoreError> {
+        self.cursors
+            .lock()
+            .map_err(|_| InternalError::with_message("cursors lock was poisoned".into()))?
+            .insert(subscriber_id.to_string(), cursor);
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn AdminEventCursorStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::admin::client::event::store::tests::{
+        test_cursors_per_subscriber, test_set_and_get_cursor,
+    };
+
+    #[test]
+    fn memory_set_and_get_cursor() {
+        let store = MemoryAdminEventCursorStore::new();
+        test_set_and_get_cursor(&store);
+    }
+
+    #[test]
+    fn memory_cursors_per_subscriber() {
+        let store = MemoryAdminEventCursorStore::new();
+        test_cursors_per_subscriber(&store);
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines an API to persist the position of admin event subscribers.
+
+#[cfg(any(
+    feature = "admin-service-event-client-store-postgres",
+    feature = "admin-service-event-client-store-sqlite"
+))]
+mod diesel;
+mod error;
+mod file;
+mod memory;
+
+#[cfg(any(
+    feature = "admin-service-event-client-store-postgres",
+    feature = "admin-service-event-client-store-sqlite"
+))]
+pub use self::diesel::DieselAdminEventCursorStore;
+pub use error::AdminEventCursorStoreError;
+pub use file::FileAdminEventCursorStore;
+pub use memory::MemoryAdminEventCursorStore;
+
+/// A store for the cursor of the last admin event processed by each subscriber.
+///
+/// Subscribers are identified by a caller-provided ID, so a single store may be shared by several
+/// subscribers.
+pub trait AdminEventCursorStore: Send + Sync {
+    /// Returns the cursor of the last event processed by the given subscriber, if any.
+    fn get_cursor(&self, subscriber_id: &str) -> Result<Option<u64>, AdminEventCursorStoreError>;
+
+    /// Sets the cursor of the last event processed by the given subscriber, replacing any
+    /// existing value.
+    fn set_cursor(
+        &self,
+        subscriber_id: &str,
+        cursor: u64,
+    ) -> Result<(), AdminEventCursorStoreError>;
+
+    /// Clone the store for dynamic dispatch.
+    fn clone_box(&self) -> Box<dyn AdminEventCursorStore>;
+}
+
+impl Clone for Box<dyn AdminEventCursorStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// Verify that a store returns the cursor last set for a subscriber. It does the following:
+    /// 1. Verify that no cursor is returned for a subscriber that has not set one
+    /// 2. Set a cursor and verify that it is returned
+    /// 3. Set a new cursor and verify that it replaces the old one
+    pub fn test_set_and_get_cursor(store: &dyn AdminEventCursorStore) {
+        assert_eq!(
+            None,
+            store
+                .get_cursor("gameroom")
+                .expect("Failed to get unset cursor")
+        );
+
+        store
+            .set_cursor("gameroom", 10)
+            .expect("Failed to set cursor");
+        assert_eq!(
+            Some(10),
+            store.get_cursor("gameroom").expect("Failed to get cursor")
+        );
+
+        store
+            .set_cursor("gameroom", 25)
+            .expect("Failed to update cursor");
+        assert_eq!(
+            Some(25),
+            store
+                .get_cursor("gameroom")
+                .expect("Failed to get updated cursor")
+        );
+    }
+
+    /// Verify that the cursors of different subscribers are independent.
+    pub fn test_cursors_per_subscriber(store: &dyn AdminEventCursorStore) {
+        store
+            .set_cursor("gameroom", 10)
+            .expect("Failed to set gameroom cursor");
+        store
+            .set_cursor("scabbard", 3)
+            .expect("Failed to set scabbard cursor");
+
+        assert_eq!(
+            Some(10),
+            store
+                .get_cursor("gameroom")
+                .expect("Failed to get gameroom cursor")
+        );
+        assert_eq!(
+            Some(3),
+            store
+                .get_cursor("scabbard")
+                .expect("Failed to get scabbard cursor")
+        );
+    }
+}
@@ -0,0 +1,403 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The websocket subscriber that delivers admin events to an `AdminEventHandler`.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use crate::error::{InternalError, InvalidStateError};
+use crate::events::{Igniter, WebSocketClient, WsResponse};
+
+use super::{AdminEvent, AdminEventCursorStore, AdminEventHandler};
+
+/// The admin protocol version used when registering for events; the event format has not changed
+/// since the first version, so this is accepted by every admin service.
+const ADMIN_EVENT_PROTOCOL_VERSION: &str = "1";
+
+/// Default limit for the number of consecutive failed reconnection attempts
+const DEFAULT_RECONNECT_LIMIT: u64 = 10;
+
+/// Default timeout in seconds if no message is received from the server
+const DEFAULT_TIMEOUT: u64 = 60;
+
+/// Builds an [`AdminEventSubscriber`].
+///
+/// [`AdminEventSubscriber`]: struct.AdminEventSubscriber.html
+#[derive(Default)]
+pub struct AdminEventSubscriberBuilder {
+    splinterd_url: Option<String>,
+    authorization: Option<String>,
+    circuit_management_type: Option<String>,
+    subscriber_id: Option<String>,
+    cursor_store: Option<Box<dyn AdminEventCursorStore>>,
+    handler: Option<Box<dyn AdminEventHandler>>,
+    reconnect_limit: Option<u64>,
+    timeout: Option<u64>,
+}
+
+impl AdminEventSubscriberBuilder {
+    /// Constructs a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the base URL of the splinterd REST API.
+    pub fn with_splinterd_url(mut self, splinterd_url: &str) -> Self {
+        self.splinterd_url = Some(splinterd_url.to_string());
+        self
+    }
+
+    /// Sets the value of the `Authorization` header sent when connecting.
+    pub fn with_authorization(mut self, authorization: &str) -> Self {
+        self.authorization = Some(authorization.to_string());
+        self
+    }
+
+    /// Sets the circuit management type whose events will be received.
+    pub fn with_circuit_management_type(mut self, circuit_management_type: &str) -> Self {
+        self.circuit_management_type = Some(circuit_management_type.to_string());
+        self
+    }
+
+    /// Sets the ID under which the subscriber's cursor is stored.
+    ///
+    /// Defaults to the circuit management type. An ID must be set if more than one subscriber
+    /// for the same circuit management type shares a cursor store.
+    pub fn with_subscriber_id(mut self, subscriber_id: &str) -> Self {
+        self.subscriber_id = Some(subscriber_id.to_string());
+        self
+    }
+
+    /// Sets the store used to persist the cursor of the last processed event.
+    pub fn with_cursor_store(mut self, cursor_store: Box<dyn AdminEventCursorStore>) -> Self {
+        self.cursor_store = Some(cursor_store);
+        self
+    }
+
+    /// Sets the handler that processes received events.
+    pub fn with_handler(mut self, handler: Box<dyn AdminEventHandler>) -> Self {
+        self.handler = Some(handler);
+        self
+    }
+
+    /// Sets the number of consecutive failed reconnection attempts after which the subscriber
+    /// stops. Defaults to 10.
+    pub fn with_reconnect_limit(mut self, reconnect_limit: u64) -> Self {
+        self.reconnect_limit = Some(reconnect_limit);
+        self
+    }
+
+    /// Sets the number of seconds without a message from the server after which the connection
+    /// is considered lost. Defaults to 60.
+    pub fn with_timeout(mut self, timeout: u64) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Builds the `AdminEventSubscriber`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidStateError` if the splinterd URL, authorization, circuit management
+    /// type, cursor store or handler were not set.
+    pub fn build(self) -> Result<AdminEventSubscriber, InvalidStateError> {
+        let splinterd_url = self.splinterd_url.ok_or_else(|| {
+            InvalidStateError::with_message(
+                "A splinterd URL is required to build an AdminEventSubscriber".into(),
+            )
+        })?;
+        let authorization = self.authorization.ok_or_else(|| {
+            InvalidStateError::with_message(
+                "An authorization is required to build an AdminEventSubscriber".into(),
+            )
+        })?;
+        let circuit_management_type = self.circuit_management_type.ok_or_else(|| {
+            InvalidStateError::with_message(
+                "A circuit management type is required to build an AdminEventSubscriber".into(),
+            )
+        })?;
+        let cursor_store = self.cursor_store.ok_or_else(|| {
+            InvalidStateError::with_message(
+                "A cursor store is required to build an AdminEventSubscriber".into(),
+            )
+        })?;
+        let handler = self.handler.ok_or_else(|| {
+            InvalidStateError::with_message(
+                "A handler is required to build an AdminEventSubscriber".into(),
+            )
+        })?;
+
+        Ok(AdminEventSubscriber {
+            splinterd_url: splinterd_url.trim_end_matches('/').to_string(),
+            authorization,
+            subscriber_id: self
+                .subscriber_id
+                .unwrap_or_else(|| circuit_management_type.clone()),
+            circuit_management_type,
+            cursor_store,
+            handler: Arc::new(Mutex::new(handler)),
+            reconnect_limit: self.reconnect_limit.unwrap_or(DEFAULT_RECONNECT_LIMIT),
+            timeout: self.timeout.unwrap_or(DEFAULT_TIMEOUT),
+        })
+    }
+}
+
+/// Delivers the events for a circuit management type to an `AdminEventHandler`.
+///
+/// Each event is passed to the handler in the order it was emitted. Once the handler returns
+/// successfully, the event's cursor is saved, so that the next connection (whether a reconnect or
+/// a restart of the application) starts with the following event.
+///
+/// If the handler or the cursor store fails, the subscriber closes its connection and ignores
+/// any further events, leaving the cursor on the last event that was fully processed. The
+/// failed event is delivered again the next time the subscriber is started.
+pub struct AdminEventSubscriber {
+    splinterd_url: String,
+    authorization: String,
+    circuit_management_type: String,
+    subscriber_id: String,
+    cursor_store: Box<dyn AdminEventCursorStore>,
+    handler: Arc<Mutex<Box<dyn AdminEventHandler>>>,
+    reconnect_limit: u64,
+    timeout: u64,
+}
+
+impl AdminEventSubscriber {
+    /// Connects to the admin service and starts delivering events on the given igniter's
+    /// reactor.
+    ///
+    /// Events are requested starting after the cursor saved for this subscriber; if there is no
+    /// saved cursor, all events are requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the saved cursor cannot be read or the websocket cannot be
+    /// started.
+    pub fn start(&self, igniter: &Igniter) -> Result<(), InternalError> {
+        let cursor = self
+            .cursor_store
+            .get_cursor(&self.subscriber_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        let url = registration_url(&self.splinterd_url, &self.circuit_management_type, cursor);
+
+        let failed = Arc::new(AtomicBool::new(false));
+
+        let handler = self.handler.clone();
+        let cursor_store = self.cursor_store.clone();
+        let subscriber_id = self.subscriber_id.clone();
+        let message_failed = failed.clone();
+        let mut ws =
+            WebSocketClient::new(&url, &self.authorization, move |_, event: AdminEvent| {
+                if message_failed.load(Ordering::SeqCst) {
+                    return WsResponse::Close;
+                }
+
+                match process_event(&handler, &*cursor_store, &subscriber_id, &event) {
+                    Ok(()) => WsResponse::Empty,
+                    Err(err) => {
+                        error!(
+                            "Failed to process admin event for {}; stopping subscriber: {}",
+                            subscriber_id, err
+                        );
+                        message_failed.store(true, Ordering::SeqCst);
+                        WsResponse::Close
+                    }
+                }
+            });
+
+        ws.header(
+            "SplinterProtocolVersion",
+            ADMIN_EVENT_PROTOCOL_VERSION.to_string(),
+        );
+        ws.set_reconnect(true);
+        ws.set_reconnect_limit(self.reconnect_limit);
+        ws.set_timeout(self.timeout);
+
+        let splinterd_url = self.splinterd_url.clone();
+        let circuit_management_type = self.circuit_management_type.clone();
+        let cursor_store = self.cursor_store.clone();
+        let subscriber_id = self.subscriber_id.clone();
+        ws.on_reconnect(move |ws| {
+            if failed.load(Ordering::SeqCst) {
+                ws.set_reconnect(false);
+                return;
+            }
+
+            debug!(
+                "Admin event subscriber {} attempting reconnect",
+                subscriber_id
+            );
+            match cursor_store.get_cursor(&subscriber_id) {
+                Ok(cursor) => ws.set_url(&registration_url(
+                    &splinterd_url,
+                    &circuit_management_type,
+                    cursor,
+                )),
+                Err(err) => error!(
+                    "Failed to retrieve cursor for {}; reconnecting from previous cursor: {}",
+                    subscriber_id, err
+                ),
+            }
+        });
+
+        let subscriber_id = self.subscriber_id.clone();
+        ws.on_error(move |err, _| {
+            error!(
+                "Admin event subscriber {} has stopped: {}",
+                subscriber_id, err
+            );
+            Ok(())
+        });
+
+        igniter
+            .start_ws(&ws)
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+/// Returns the application registration URL, requesting the events after the given cursor.
+fn registration_url(
+    splinterd_url: &str,
+    circuit_management_type: &str,
+    cursor: Option<u64>,
+) -> String {
+    match cursor {
+        Some(cursor) => format!(
+            "{}/ws/admin/register/{}?last={}",
+            splinterd_url, circuit_management_type, cursor
+        ),
+        None => format!(
+            "{}/ws/admin/register/{}",
+            splinterd_url, circuit_management_type
+        ),
+    }
+}
+
+/// Passes the event to the handler and, if it succeeds, saves the event's cursor.
+fn process_event(
+    handler: &Mutex<Box<dyn AdminEventHandler>>,
+    cursor_store: &dyn AdminEventCursorStore,
+    subscriber_id: &str,
+    event: &AdminEvent,
+) -> Result<(), InternalError> {
+    handler
+        .lock()
+        .map_err(|_| InternalError::with_message("Admin event handler lock was poisoned".into()))?
+        .handle_event(event)?;
+
+    cursor_store
+        .set_cursor(subscriber_id, event.cursor())
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::admin::client::event::tests::proposal;
+    use crate::admin::client::event::MemoryAdminEventCursorStore;
+    use crate::admin::messages::AdminServiceEvent;
+
+    /// Verify that the builder requires a cursor store and a handler, and that the subscriber ID
+    /// defaults to the circuit management type.
+    #[test]
+    fn build_subscriber() {
+        assert!(AdminEventSubscriberBuilder::new()
+            .with_splinterd_url("http://localhost:8085")
+            .with_authorization("Bearer token")
+            .with_circuit_management_type("gameroom")
+            .with_handler(Box::new(|_: &AdminEvent| -> Result<(), InternalError> {
+                Ok(())
+            }))
+            .build()
+            .is_err());
+
+        assert!(AdminEventSubscriberBuilder::new()
+            .with_splinterd_url("http://localhost:8085")
+            .with_authorization("Bearer token")
+            .with_circuit_management_type("gameroom")
+            .with_cursor_store(Box::new(MemoryAdminEventCursorStore::new()))
+            .build()
+            .is_err());
+
+        let subscriber = AdminEventSubscriberBuilder::new()
+            .with_splinterd_url("http://localhost:8085/")
+            .with_authorization("Bearer token")
+            .with_circuit_management_type("gameroom")
+            .with_cursor_store(Box::new(MemoryAdminEventCursorStore::new()))
+            .with_handler(Box::new(|_: &AdminEvent| -> Result<(), InternalError> {
+                Ok(())
+            }))
+            .build()
+            .expect("Failed to build subscriber");
+
+        assert_eq!(subscriber.splinterd_url, "http://localhost:8085");
+        assert_eq!(subscriber.subscriber_id, "gameroom");
+        assert_eq!(subscriber.reconnect_limit, DEFAULT_RECONNECT_LIMIT);
+        assert_eq!(subscriber.timeout, DEFAULT_TIMEOUT);
+    }
+
+    /// Verify that the registration URL only requests events after the cursor when one is set.
+    #[test]
+    fn registration_url_with_cursor() {
+        assert_eq!(
+            registration_url("http://localhost:8085", "gameroom", None),
+            "http://localhost:8085/ws/admin/register/gameroom"
+        );
+        assert_eq!(
+            registration_url("http://localhost:8085", "gameroom", Some(12)),
+            "http://localhost:8085/ws/admin/register/gameroom?last=12"
+        );
+    }
+
+    /// Verify that an event's cursor is saved only after the handler processes it successfully.
+    /// It does the following:
+    /// 1. Process an event with a handler that succeeds and verify the cursor is saved
+    /// 2. Process a second event with a handler that fails and verify the cursor is unchanged
+    #[test]
+    fn process_event_saves_cursor_on_success() {
+        let store = MemoryAdminEventCursorStore::new();
+
+        let handler: Mutex<Box<dyn AdminEventHandler>> =
+            Mutex::new(Box::new(|_: &AdminEvent| -> Result<(), InternalError> {
+                Ok(())
+            }));
+        process_event(&handler, &store, "gameroom", &event(1)).expect("Failed to process event");
+        assert_eq!(
+            Some(1),
+            store.get_cursor("gameroom").expect("Failed to get cursor")
+        );
+
+        let handler: Mutex<Box<dyn AdminEventHandler>> =
+            Mutex::new(Box::new(|_: &AdminEvent| -> Result<(), InternalError> {
+                Err(InternalError::with_message("handler failed".into()))
+            }));
+        assert!(process_event(&handler, &store, "gameroom", &event(2)).is_err());
+        assert_eq!(
+            Some(1),
+            store.get_cursor("gameroom").expect("Failed to get cursor")
+        );
+    }
+
+    fn event(event_id: i64) -> AdminEvent {
+        AdminEvent {
+            timestamp: 0,
+            event_id: Some(event_id),
+            admin_event: AdminServiceEvent::CircuitReady(proposal()),
+        }
+    }
+}
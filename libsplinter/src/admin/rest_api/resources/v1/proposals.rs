@@ -44,6 +44,8 @@ impl<'a> From<&'a CircuitProposal> for ProposalResponse<'a> {
             ProposalType::AddNode => "AddNode",
             ProposalType::RemoveNode => "RemoveNode",
             ProposalType::Disband => "Disband",
+            ProposalType::Abandon => "Abandon",
+            ProposalType::UpdateApplicationMetadata => "UpdateApplicationMetadata",
         };
 
         Self {
@@ -162,7 +162,7 @@ impl ProposalManager for AdminProposalManager {
             .lock()
             .map_err(|_| ServiceError::PoisonedLock("the admin state lock was poisoned".into()))?;
         if let Some(circuit_payload) = shared.pop_pending_circuit_payload() {
-            let (expected_hash, circuit_proposal) = shared
+            let (expected_hash, _) = shared
                 .propose_change(circuit_payload.clone())
                 .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
 
@@ -180,13 +180,16 @@ impl ProposalManager for AdminProposalManager {
                 ..Default::default()
             };
 
+            // The verifiers are set by the pending change, as they are not always the members of
+            // the proposed circuit, such as when a node is removed from a circuit
+            let verifiers = shared.current_consensus_verifiers().clone();
             let mut required_verifiers = RequiredVerifiers::new();
-            let mut verifiers = vec![];
-            let members = circuit_proposal.get_circuit_proposal().get_members();
-            for member in members {
-                verifiers.push(admin_service_id(member.get_node_id()).as_bytes().to_vec());
-            }
-            required_verifiers.set_verifiers(RepeatedField::from_vec(verifiers));
+            required_verifiers.set_verifiers(RepeatedField::from_vec(
+                verifiers
+                    .iter()
+                    .map(|verifier| verifier.as_bytes().to_vec())
+                    .collect(),
+            ));
             let required_verifiers_bytes = required_verifiers
                 .write_to_bytes()
                 .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
@@ -202,16 +205,25 @@ impl ProposalManager for AdminProposalManager {
             proposed_circuit.set_circuit_payload(circuit_payload);
             proposed_circuit.set_expected_hash(expected_hash.as_bytes().into());
             proposed_circuit.set_required_verifiers(required_verifiers_bytes);
+            #[cfg(feature = "circuit-update")]
+            {
+                // a node that is being added to the circuit needs its current definition to
+                // verify the proposal
+                if let Some(circuit) = shared
+                    .pending_joining_circuit()
+                    .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?
+                {
+                    proposed_circuit.set_circuit(circuit);
+                }
+            }
             let mut msg = AdminMessage::new();
             msg.set_message_type(AdminMessage_Type::PROPOSED_CIRCUIT);
             msg.set_proposed_circuit(proposed_circuit);
 
             let envelope_bytes = msg.write_to_bytes().unwrap();
-            for member in members {
-                if member.get_node_id() != shared.node_id() {
-                    network_sender
-                        .send(&admin_service_id(member.get_node_id()), &envelope_bytes)
-                        .unwrap();
+            for verifier in verifiers.iter() {
+                if verifier != &admin_service_id(shared.node_id()) {
+                    network_sender.send(verifier, &envelope_bytes).unwrap();
                 }
             }
 
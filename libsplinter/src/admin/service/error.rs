@@ -18,6 +18,12 @@ use std::fmt;
 use crate::admin::store::error::AdminServiceStoreError;
 use crate::consensus::error::ProposalManagerError;
 use crate::orchestrator::InitializeServiceError;
+#[cfg(any(
+    feature = "circuit-abandon",
+    feature = "circuit-disband",
+    feature = "circuit-update"
+))]
+use crate::orchestrator::ShutdownServiceError;
 use crate::service::error::{ServiceError, ServiceSendError};
 
 use protobuf::error;
@@ -162,6 +168,15 @@ pub enum AdminSharedError {
         source: Option<InitializeServiceError>,
     },
     ServiceSendError(ServiceSendError),
+    #[cfg(any(
+        feature = "circuit-abandon",
+        feature = "circuit-disband",
+        feature = "circuit-update"
+    ))]
+    ServiceShutdownFailed {
+        context: String,
+        source: Option<ShutdownServiceError>,
+    },
     UnknownAction(String),
     ValidationFailed(String),
 
@@ -187,6 +202,18 @@ impl Error for AdminSharedError {
                 }
             }
             AdminSharedError::ServiceSendError(err) => Some(err),
+            #[cfg(any(
+                feature = "circuit-abandon",
+                feature = "circuit-disband",
+                feature = "circuit-update"
+            ))]
+            AdminSharedError::ServiceShutdownFailed { source, .. } => {
+                if let Some(ref err) = source {
+                    Some(err)
+                } else {
+                    None
+                }
+            }
             AdminSharedError::UnknownAction(_) => None,
             AdminSharedError::ValidationFailed(_) => None,
             AdminSharedError::UnableToAddSubscriber(_) => None,
@@ -216,6 +243,18 @@ impl fmt::Display for AdminSharedError {
             AdminSharedError::ServiceSendError(err) => {
                 write!(f, "failed to send service message: {}", err)
             }
+            #[cfg(any(
+                feature = "circuit-abandon",
+                feature = "circuit-disband",
+                feature = "circuit-update"
+            ))]
+            AdminSharedError::ServiceShutdownFailed { context, source } => {
+                if let Some(ref err) = source {
+                    write!(f, "{}: {}", context, err)
+                } else {
+                    f.write_str(&context)
+                }
+            }
             AdminSharedError::UnknownAction(msg) => {
                 write!(f, "received message with unknown action: {}", msg)
             }
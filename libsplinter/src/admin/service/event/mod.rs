@@ -42,6 +42,9 @@ pub enum EventType {
     ProposalAccepted { requester: Vec<u8> },
     ProposalRejected { requester: Vec<u8> },
     CircuitReady,
+    CircuitDisbanded,
+    CircuitAbandoned,
+    CircuitMetadataUpdated,
 }
 
 impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
@@ -83,6 +86,21 @@ impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
                 event_type: EventType::CircuitReady,
                 proposal,
             }),
+            messages::AdminServiceEvent::CircuitDisbanded(_) => Ok(AdminServiceEvent {
+                event_id,
+                event_type: EventType::CircuitDisbanded,
+                proposal,
+            }),
+            messages::AdminServiceEvent::CircuitAbandoned(_) => Ok(AdminServiceEvent {
+                event_id,
+                event_type: EventType::CircuitAbandoned,
+                proposal,
+            }),
+            messages::AdminServiceEvent::CircuitMetadataUpdated(_) => Ok(AdminServiceEvent {
+                event_id,
+                event_type: EventType::CircuitMetadataUpdated,
+                proposal,
+            }),
         }
     }
 }
@@ -444,6 +444,10 @@ impl From<&messages::ProposalType> for String {
             messages::ProposalType::AddNode => String::from("AddNode"),
             messages::ProposalType::RemoveNode => String::from("RemoveNode"),
             messages::ProposalType::Disband => String::from("Disband"),
+            messages::ProposalType::Abandon => String::from("Abandon"),
+            messages::ProposalType::UpdateApplicationMetadata => {
+                String::from("UpdateApplicationMetadata")
+            }
         }
     }
 }
@@ -452,6 +456,7 @@ impl From<&messages::AuthorizationType> for String {
     fn from(variant: &messages::AuthorizationType) -> Self {
         match variant {
             messages::AuthorizationType::Trust => String::from("Trust"),
+            messages::AuthorizationType::Challenge => String::from("Challenge"),
         }
     }
 }
@@ -468,6 +473,8 @@ impl From<&messages::DurabilityType> for String {
     fn from(variant: &messages::DurabilityType) -> Self {
         match variant {
             messages::DurabilityType::NoDurability => String::from("NoDurability"),
+            #[cfg(feature = "circuit-durability")]
+            messages::DurabilityType::StoreAndForward => String::from("StoreAndForward"),
         }
     }
 }
@@ -533,6 +540,18 @@ impl<'a> From<&'a messages::AdminServiceEvent> for NewAdminServiceEventModel<'a>
                 event_type: "CircuitReady",
                 data: None,
             },
+            messages::AdminServiceEvent::CircuitDisbanded(_) => NewAdminServiceEventModel {
+                event_type: "CircuitDisbanded",
+                data: None,
+            },
+            messages::AdminServiceEvent::CircuitAbandoned(_) => NewAdminServiceEventModel {
+                event_type: "CircuitAbandoned",
+                data: None,
+            },
+            messages::AdminServiceEvent::CircuitMetadataUpdated(_) => NewAdminServiceEventModel {
+                event_type: "CircuitMetadataUpdated",
+                data: None,
+            },
         }
     }
 }
@@ -569,6 +588,21 @@ impl TryFrom<(AdminServiceEventModel, CircuitProposal)> for AdminServiceEvent {
                 event_type: EventType::CircuitReady,
                 proposal,
             }),
+            ("CircuitDisbanded", None) => Ok(AdminServiceEvent {
+                event_id: event_model.id,
+                event_type: EventType::CircuitDisbanded,
+                proposal,
+            }),
+            ("CircuitAbandoned", None) => Ok(AdminServiceEvent {
+                event_id: event_model.id,
+                event_type: EventType::CircuitAbandoned,
+                proposal,
+            }),
+            ("CircuitMetadataUpdated", None) => Ok(AdminServiceEvent {
+                event_id: event_model.id,
+                event_type: EventType::CircuitMetadataUpdated,
+                proposal,
+            }),
             _ => Err(AdminServiceEventStoreError::InvalidStateError(
                 InvalidStateError::with_message(
                     "Unable to convert AdminServiceEventModel to AdminServiceEvent".into(),
@@ -54,6 +54,9 @@ impl CreateCircuit {
     pub fn from_proto(mut proto: admin::Circuit) -> Result<Self, MarshallingError> {
         let authorization_type = match proto.get_authorization_type() {
             admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION => AuthorizationType::Trust,
+            admin::Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION => {
+                AuthorizationType::Challenge
+            }
             admin::Circuit_AuthorizationType::UNSET_AUTHORIZATION_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset authorization type".to_string(),
@@ -72,6 +75,14 @@ impl CreateCircuit {
 
         let durability = match proto.get_durability() {
             admin::Circuit_DurabilityType::NO_DURABILITY => DurabilityType::NoDurability,
+            #[cfg(feature = "circuit-durability")]
+            admin::Circuit_DurabilityType::STORE_AND_FORWARD => DurabilityType::StoreAndForward,
+            #[cfg(not(feature = "circuit-durability"))]
+            admin::Circuit_DurabilityType::STORE_AND_FORWARD => {
+                return Err(MarshallingError::UnsetField(
+                    "Unsupported durability type: STORE_AND_FORWARD".to_string(),
+                ));
+            }
             admin::Circuit_DurabilityType::UNSET_DURABILITY_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset durability type".to_string(),
@@ -176,6 +187,11 @@ impl CreateCircuit {
                 circuit
                     .set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
             }
+            AuthorizationType::Challenge => {
+                circuit.set_authorization_type(
+                    admin::Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION,
+                );
+            }
         };
 
         match self.persistence {
@@ -187,6 +203,10 @@ impl CreateCircuit {
             DurabilityType::NoDurability => {
                 circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
             }
+            #[cfg(feature = "circuit-durability")]
+            DurabilityType::StoreAndForward => {
+                circuit.set_durability(admin::Circuit_DurabilityType::STORE_AND_FORWARD);
+            }
         };
 
         match self.routes {
@@ -228,6 +248,7 @@ pub fn is_valid_circuit_id(circuit_id: &str) -> bool {
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum AuthorizationType {
     Trust,
+    Challenge,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -244,6 +265,8 @@ impl Default for PersistenceType {
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum DurabilityType {
     NoDurability,
+    #[cfg(feature = "circuit-durability")]
+    StoreAndForward,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -380,6 +403,10 @@ impl CircuitProposal {
             admin::CircuitProposal_ProposalType::ADD_NODE => ProposalType::AddNode,
             admin::CircuitProposal_ProposalType::REMOVE_NODE => ProposalType::RemoveNode,
             admin::CircuitProposal_ProposalType::DISBAND => ProposalType::Disband,
+            admin::CircuitProposal_ProposalType::ABANDON => ProposalType::Abandon,
+            admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA => {
+                ProposalType::UpdateApplicationMetadata
+            }
             admin::CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => {
                 return Err(MarshallingError::UnsetField(
                     "Unset proposal type".to_string(),
@@ -411,6 +438,10 @@ impl CircuitProposal {
             ProposalType::AddNode => admin::CircuitProposal_ProposalType::ADD_NODE,
             ProposalType::RemoveNode => admin::CircuitProposal_ProposalType::REMOVE_NODE,
             ProposalType::Disband => admin::CircuitProposal_ProposalType::DISBAND,
+            ProposalType::Abandon => admin::CircuitProposal_ProposalType::ABANDON,
+            ProposalType::UpdateApplicationMetadata => {
+                admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA
+            }
         };
 
         let votes = self
@@ -442,6 +473,10 @@ impl From<store::CircuitProposal> for CircuitProposal {
             store::ProposalType::AddNode => ProposalType::AddNode,
             store::ProposalType::RemoveNode => ProposalType::RemoveNode,
             store::ProposalType::Disband => ProposalType::Disband,
+            store::ProposalType::Abandon => ProposalType::Abandon,
+            store::ProposalType::UpdateApplicationMetadata => {
+                ProposalType::UpdateApplicationMetadata
+            }
         };
 
         let store_circuit = store_proposal.circuit();
@@ -519,6 +554,9 @@ pub enum ProposalType {
     AddNode,
     RemoveNode,
     Disband,
+    UpdateApplicationMetadata,
+    /// Not a proposal that is voted on: describes a circuit abandoned by a single node
+    Abandon,
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
@@ -612,6 +650,14 @@ pub enum AdminServiceEvent {
     ProposalAccepted((CircuitProposal, Vec<u8>)),
     ProposalRejected((CircuitProposal, Vec<u8>)),
     CircuitReady(CircuitProposal),
+    CircuitDisbanded(CircuitProposal),
+    /// A circuit was abandoned by a single node, without a proposal.
+    ///
+    /// As no proposal exists, the event carries one built from the circuit: its `proposal_type`
+    /// is `ProposalType::Abandon` and its circuit has the status `CircuitStatus::Abandoned`. The
+    /// proposal has no votes, and its requester is the key that abandoned the circuit.
+    CircuitAbandoned(CircuitProposal),
+    CircuitMetadataUpdated(CircuitProposal),
 }
 
 impl AdminServiceEvent {
@@ -622,6 +668,9 @@ impl AdminServiceEvent {
             AdminServiceEvent::ProposalAccepted((proposal, _)) => proposal,
             AdminServiceEvent::ProposalRejected((proposal, _)) => proposal,
             AdminServiceEvent::CircuitReady(proposal) => proposal,
+            AdminServiceEvent::CircuitDisbanded(proposal) => proposal,
+            AdminServiceEvent::CircuitAbandoned(proposal) => proposal,
+            AdminServiceEvent::CircuitMetadataUpdated(proposal) => proposal,
         }
     }
 }
@@ -642,6 +691,11 @@ impl From<&event::AdminServiceEvent> for AdminServiceEvent {
                 AdminServiceEvent::ProposalRejected((admin_proposal, requester.to_vec()))
             }
             EventType::CircuitReady => AdminServiceEvent::CircuitReady(admin_proposal),
+            EventType::CircuitDisbanded => AdminServiceEvent::CircuitDisbanded(admin_proposal),
+            EventType::CircuitAbandoned => AdminServiceEvent::CircuitAbandoned(admin_proposal),
+            EventType::CircuitMetadataUpdated => {
+                AdminServiceEvent::CircuitMetadataUpdated(admin_proposal)
+            }
         }
     }
 }
@@ -37,11 +37,17 @@ use protobuf::{self, Message};
 
 #[cfg(feature = "admin-service-event-store")]
 use crate::admin::service::event::store::AdminServiceEventStore;
-use crate::admin::store::AdminServiceStore;
+#[cfg(feature = "challenge-authorization")]
+use crate::admin::store::AuthorizationType;
+#[cfg(feature = "circuit-durability")]
+use crate::admin::store::DurabilityType;
+use crate::admin::store::{AdminServiceStore, CircuitStatus};
 use crate::circuit::routing::{self, RoutingTableWriter};
 use crate::consensus::Proposal;
 use crate::hex::to_hex;
 use crate::keys::KeyPermissionManager;
+#[cfg(feature = "challenge-authorization")]
+use crate::network::auth::AuthorizationInquisitor;
 use crate::orchestrator::{ServiceDefinition, ServiceOrchestrator};
 use crate::peer::{PeerManagerConnector, PeerManagerNotification};
 use crate::protocol::{ADMIN_SERVICE_PROTOCOL_MIN, ADMIN_SERVICE_PROTOCOL_VERSION};
@@ -284,12 +290,31 @@ impl AdminService {
         AdminServiceProposals::new(&self.admin_service_shared)
     }
 
+    /// Sets the `AuthorizationInquisitor` used to determine how peers were authorized.
+    ///
+    /// Circuits that require challenge authorization are only created with, or extended to,
+    /// members that were authorized using challenge authorization. Without an inquisitor, such
+    /// circuits are rejected.
+    #[cfg(feature = "challenge-authorization")]
+    pub fn set_authorization_inquisitor(
+        &self,
+        authorization_inquisitor: Box<dyn AuthorizationInquisitor>,
+    ) -> Result<(), ServiceError> {
+        self.admin_service_shared
+            .lock()
+            .map_err(|_| ServiceError::PoisonedLock("the admin shared lock was poisoned".into()))?
+            .set_authorization_inquisitor(Some(authorization_inquisitor));
+
+        Ok(())
+    }
+
     /// On restart of a splinter node, all services that this node should run on the existing
     /// circuits should be initialized using the service orchestrator. This may not include all
     /// services if they are not supported locally. It is expected that some services will be
     /// started externally.
     ///
-    /// Also adds peer references for members of the circuits and proposals.
+    /// Also adds peer references for members of the circuits and proposals. Circuits that have
+    /// been disbanded or abandoned are not restarted.
     fn re_initialize_circuits(&self) -> Result<(), ServiceStartError> {
         let circuits = self
             .admin_service_shared
@@ -325,6 +350,15 @@ impl AdminService {
             .routing_table_writer();
 
         for circuit in circuits {
+            if circuit.circuit_status() != &CircuitStatus::Active {
+                debug!(
+                    "Skipping {:?} circuit {}",
+                    circuit.circuit_status(),
+                    circuit.circuit_id()
+                );
+                continue;
+            }
+
             let mut routing_members = vec![];
             // restart all peer in the circuit
             for member in circuit.members().iter() {
@@ -376,14 +410,23 @@ impl AdminService {
                 })
                 .collect::<Vec<_>>();
 
+            let routing_circuit = routing::Circuit::new(
+                circuit.circuit_id().to_string(),
+                routing_services,
+                circuit.members().to_vec(),
+            );
+            #[cfg(feature = "challenge-authorization")]
+            let routing_circuit = routing_circuit.with_challenge_authorization(
+                circuit.authorization_type() == &AuthorizationType::Challenge,
+            );
+            #[cfg(feature = "circuit-durability")]
+            let routing_circuit = routing_circuit
+                .with_store_and_forward(circuit.durability() == &DurabilityType::StoreAndForward);
+
             writer
                 .add_circuit(
                     circuit.circuit_id().to_string(),
-                    routing::Circuit::new(
-                        circuit.circuit_id().to_string(),
-                        routing_services,
-                        circuit.members().to_vec(),
-                    ),
+                    routing_circuit,
                     routing_members,
                 )
                 .map_err(|err| ServiceStartError::Internal(err.reduce_to_string()))?;
@@ -611,6 +654,18 @@ impl Service for AdminService {
                     ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
                 })?;
 
+                #[cfg(feature = "circuit-update")]
+                {
+                    if proposed_circuit.has_circuit() {
+                        admin_service_shared
+                            .add_joining_circuit(
+                                circuit_payload,
+                                proposed_circuit.get_circuit().clone(),
+                            )
+                            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+                    }
+                }
+
                 admin_service_shared.handle_proposed_circuit(
                     proposal,
                     circuit_payload.clone(),
@@ -630,6 +685,31 @@ impl Service for AdminService {
                     .add_ready_member(circuit_id, member_node_id.into())
                     .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
             }
+            #[cfg(feature = "circuit-abandon")]
+            AdminMessage_Type::ABANDONED_CIRCUIT => {
+                let abandoned_circuit = admin_message.get_abandoned_circuit();
+
+                let mut shared = self.admin_service_shared.lock().map_err(|_| {
+                    ServiceError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?;
+
+                shared
+                    .handle_abandoned_circuit(
+                        abandoned_circuit.get_circuit_id(),
+                        abandoned_circuit.get_member_node_id(),
+                        &message_context.sender,
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+            }
+            #[cfg(not(feature = "circuit-abandon"))]
+            AdminMessage_Type::ABANDONED_CIRCUIT => {
+                debug!(
+                    "Ignoring abandoned circuit message from {}: abandoning circuits is not \
+                     supported",
+                    message_context.sender
+                );
+                Ok(())
+            }
             AdminMessage_Type::SERVICE_PROTOCOL_VERSION_REQUEST => {
                 let request = admin_message.get_protocol_request();
                 let protocol =
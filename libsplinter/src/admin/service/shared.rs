@@ -22,21 +22,50 @@ use std::time::SystemTime;
 
 use cylinder::{PublicKey, Signature, Verifier as SignatureVerifier};
 use protobuf::Message;
-
+#[cfg(feature = "circuit-update")]
+use protobuf::RepeatedField;
+
+#[cfg(feature = "challenge-authorization")]
+use crate::admin::store::AuthorizationType;
+#[cfg(feature = "circuit-durability")]
+use crate::admin::store::DurabilityType;
+#[cfg(feature = "circuit-update")]
+use crate::admin::store::ProposedCircuit;
 use crate::admin::store::{
     AdminServiceStore, Circuit as StoreCircuit, CircuitNode, CircuitPredicate,
-    CircuitProposal as StoreProposal, ProposalType, ProposedNode, Vote, VoteRecordBuilder,
+    CircuitProposal as StoreProposal, ProposalType, Vote, VoteRecordBuilder,
+};
+#[cfg(any(
+    feature = "circuit-abandon",
+    feature = "circuit-disband",
+    feature = "circuit-update"
+))]
+use crate::admin::store::{
+    CircuitStatus, ProposedCircuitBuilder, ProposedNode, ProposedNodeBuilder, ProposedService,
+    ProposedServiceBuilder, Service as StoreService,
 };
 use crate::circuit::routing::{self, RoutingTableWriter};
 use crate::consensus::{Proposal, ProposalId, ProposalUpdate};
 use crate::hex::to_hex;
 use crate::keys::KeyPermissionManager;
+#[cfg(feature = "challenge-authorization")]
+use crate::network::auth::AuthorizationInquisitor;
+#[cfg(any(
+    feature = "circuit-abandon",
+    feature = "circuit-disband",
+    feature = "circuit-update"
+))]
+use crate::orchestrator::ShutdownServiceError;
 use crate::orchestrator::{ServiceDefinition, ServiceOrchestrator};
 use crate::peer::{PeerManagerConnector, PeerRef};
+#[cfg(feature = "challenge-authorization")]
+use crate::protocol::authorization::AuthorizationType as PeerAuthorizationType;
 use crate::protocol::{
     ADMIN_SERVICE_PROTOCOL_MIN, ADMIN_SERVICE_PROTOCOL_VERSION, CIRCUIT_PROTOCOL_VERSION,
 };
-#[cfg(feature = "service-arg-validation")]
+#[cfg(feature = "circuit-abandon")]
+use crate::protos::admin::AbandonedCircuit;
+#[cfg(any(feature = "circuit-update", feature = "service-arg-validation"))]
 use crate::protos::admin::SplinterService;
 use crate::protos::admin::{
     AdminMessage, AdminMessage_Type, Circuit, CircuitManagementPayload,
@@ -46,6 +75,11 @@ use crate::protos::admin::{
     Circuit_PersistenceType, Circuit_RouteType, MemberReady, ServiceProtocolVersionRequest,
     SplinterNode,
 };
+#[cfg(feature = "circuit-update")]
+use crate::protos::admin::{
+    CircuitUpdateAddNodeRequest, CircuitUpdateApplicationMetadataRequest,
+    CircuitUpdateRemoveNodeRequest, CircuitUpdateRosterRequest,
+};
 use crate::service::error::ServiceError;
 #[cfg(feature = "service-arg-validation")]
 use crate::service::validation::ServiceArgValidator;
@@ -209,6 +243,13 @@ pub struct AdminServiceShared {
     pending_changes: Option<CircuitProposalContext>,
     // the verifiers that should be broadcasted for the pending change
     current_consensus_verifiers: Vec<String>,
+    // the current definitions of the circuits this node has been proposed to join, by circuit id
+    #[cfg(feature = "circuit-update")]
+    joining_circuits: HashMap<String, ProposedCircuit>,
+    // the (circuit id, node id) pairs of the members that have abandoned a circuit this node is
+    // still a member of
+    #[cfg(feature = "circuit-abandon")]
+    abandoned_members: HashSet<(String, String)>,
     // Admin Service Event Subscribers
     event_subscribers: SubscriberMap,
     // Mailbox of AdminServiceEvent values
@@ -227,6 +268,10 @@ pub struct AdminServiceShared {
 
     #[cfg(feature = "admin-service-event-store")]
     admin_event_store: Box<dyn AdminServiceEventStore>,
+
+    // reports how peers were authorized, for circuits that require challenge authorization
+    #[cfg(feature = "challenge-authorization")]
+    authorization_inquisitor: Option<Box<dyn AuthorizationInquisitor>>,
 }
 
 impl AdminServiceShared {
@@ -269,6 +314,10 @@ impl AdminServiceShared {
             pending_consensus_proposals: HashMap::new(),
             pending_changes: None,
             current_consensus_verifiers: Vec::new(),
+            #[cfg(feature = "circuit-update")]
+            joining_circuits: HashMap::new(),
+            #[cfg(feature = "circuit-abandon")]
+            abandoned_members: HashSet::new(),
             event_subscribers: SubscriberMap::new(),
             #[cfg(not(feature = "admin-service-event-store"))]
             event_mailbox,
@@ -281,6 +330,8 @@ impl AdminServiceShared {
             routing_table_writer,
             #[cfg(feature = "admin-service-event-store")]
             admin_event_store,
+            #[cfg(feature = "challenge-authorization")]
+            authorization_inquisitor: None,
         })
     }
 
@@ -300,6 +351,14 @@ impl AdminServiceShared {
         self.proposal_sender = proposal_sender;
     }
 
+    #[cfg(feature = "challenge-authorization")]
+    pub fn set_authorization_inquisitor(
+        &mut self,
+        authorization_inquisitor: Option<Box<dyn AuthorizationInquisitor>>,
+    ) {
+        self.authorization_inquisitor = authorization_inquisitor;
+    }
+
     pub fn pop_pending_circuit_payload(&mut self) -> Option<CircuitManagementPayload> {
         self.pending_circuit_payloads.pop_front()
     }
@@ -389,6 +448,56 @@ impl AdminServiceShared {
                     .clone();
 
                 match self.check_approved(&circuit_proposal) {
+                    #[cfg(feature = "circuit-disband")]
+                    Ok(CircuitProposalStatus::Accepted)
+                        if circuit_proposal.get_proposal_type()
+                            == CircuitProposal_ProposalType::DISBAND =>
+                    {
+                        self.disband_circuit(
+                            circuit_proposal,
+                            circuit_proposal_context.signer_public_key,
+                        )
+                    }
+                    #[cfg(feature = "circuit-update")]
+                    Ok(CircuitProposalStatus::Accepted)
+                        if circuit_proposal.get_proposal_type()
+                            == CircuitProposal_ProposalType::UPDATE_ROSTER =>
+                    {
+                        self.update_circuit_roster(
+                            circuit_proposal,
+                            circuit_proposal_context.signer_public_key,
+                        )
+                    }
+                    #[cfg(feature = "circuit-update")]
+                    Ok(CircuitProposalStatus::Accepted)
+                        if circuit_proposal.get_proposal_type()
+                            == CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA =>
+                    {
+                        self.update_circuit_metadata(
+                            circuit_proposal,
+                            circuit_proposal_context.signer_public_key,
+                        )
+                    }
+                    #[cfg(feature = "circuit-update")]
+                    Ok(CircuitProposalStatus::Accepted)
+                        if circuit_proposal.get_proposal_type()
+                            == CircuitProposal_ProposalType::ADD_NODE =>
+                    {
+                        self.add_circuit_node(
+                            circuit_proposal,
+                            circuit_proposal_context.signer_public_key,
+                        )
+                    }
+                    #[cfg(feature = "circuit-update")]
+                    Ok(CircuitProposalStatus::Accepted)
+                        if circuit_proposal.get_proposal_type()
+                            == CircuitProposal_ProposalType::REMOVE_NODE =>
+                    {
+                        self.remove_circuit_node(
+                            circuit_proposal,
+                            circuit_proposal_context.signer_public_key,
+                        )
+                    }
                     Ok(CircuitProposalStatus::Accepted) => {
                         // commit new circuit
                         self.admin_store.upgrade_proposal_to_circuit(circuit_id)?;
@@ -416,6 +525,14 @@ impl AdminServiceShared {
                                 .collect(),
                             circuit.members().to_vec(),
                         );
+                        #[cfg(feature = "challenge-authorization")]
+                        let routing_circuit = routing_circuit.with_challenge_authorization(
+                            circuit.authorization_type() == &AuthorizationType::Challenge,
+                        );
+                        #[cfg(feature = "circuit-durability")]
+                        let routing_circuit = routing_circuit.with_store_and_forward(
+                            circuit.durability() == &DurabilityType::StoreAndForward,
+                        );
 
                         let routing_members = circuit_proposal
                             .get_circuit_proposal()
@@ -491,6 +608,77 @@ impl AdminServiceShared {
                                 Ok(())
                             }
 
+                            #[cfg(feature = "circuit-disband")]
+                            CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST => {
+                                // notify registered application authorization handlers of the
+                                // committed disband proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for disband proposal of circuit {}",
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
+
+                            #[cfg(feature = "circuit-update")]
+                            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ROSTER_REQUEST => {
+                                // notify registered application authorization handlers of the
+                                // committed roster update proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for roster update proposal of circuit {}",
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
+
+                            #[cfg(feature = "circuit-update")]
+                            CircuitManagementPayload_Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST => {
+                                // notify registered application authorization handlers of the
+                                // committed application metadata update proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for application metadata update proposal \
+                                     of circuit {}",
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
+
+                            #[cfg(feature = "circuit-update")]
+                            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ADD_NODE
+                            | CircuitManagementPayload_Action::CIRCUIT_UPDATE_REMOVE_NODE => {
+                                // notify registered application authorization handlers of the
+                                // committed membership update proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for membership update proposal of circuit \
+                                     {}",
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
+
                             CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE => {
                                 // notify registered application authorization handlers of the
                                 // committed circuit proposal
@@ -516,8 +704,25 @@ impl AdminServiceShared {
                         // remove circuit
                         let proposal = self.remove_proposal(&circuit_id)?;
                         if let Some(proposal) = proposal {
-                            for member in proposal.circuit().members().iter() {
-                                self.remove_peer_ref(member.node_id());
+                            // the members of an existing circuit are still peered for the
+                            // circuit itself
+                            if proposal.proposal_type() == &ProposalType::Create {
+                                for member in proposal.circuit().members().iter() {
+                                    self.remove_peer_ref(member.node_id());
+                                }
+                            }
+
+                            #[cfg(feature = "circuit-update")]
+                            {
+                                if proposal.proposal_type() == &ProposalType::AddNode {
+                                    let members = proposal
+                                        .circuit()
+                                        .members()
+                                        .iter()
+                                        .map(|member| member.node_id().to_string())
+                                        .collect::<Vec<String>>();
+                                    self.remove_add_node_peer_refs(circuit_id, &members)?;
+                                }
                             }
                         }
                         let circuit_proposal_proto =
@@ -596,6 +801,28 @@ impl AdminServiceShared {
                     }
                     err
                 })?;
+
+                #[cfg(feature = "challenge-authorization")]
+                {
+                    if proposed_circuit.get_authorization_type()
+                        == Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION
+                    {
+                        let members = proposed_circuit
+                            .get_members()
+                            .iter()
+                            .map(|member| member.get_node_id().to_string())
+                            .collect::<Vec<String>>();
+                        self.validate_challenge_authorized_members(&members)
+                            .map_err(|err| {
+                                // remove peer_ref because we will not accept this proposal
+                                for node_id in members.iter() {
+                                    self.remove_peer_ref(node_id)
+                                }
+                                err
+                            })?;
+                    }
+                }
+
                 debug!("proposing {}", proposed_circuit.get_circuit_id());
 
                 let mut circuit_proposal = CircuitProposal::new();
@@ -705,6 +932,280 @@ impl AdminServiceShared {
                 self.current_consensus_verifiers = verifiers;
                 Ok((expected_hash, proto_circuit_proposal))
             }
+            #[cfg(feature = "circuit-disband")]
+            CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST => {
+                let circuit_id = circuit_payload
+                    .get_circuit_disband_request()
+                    .get_circuit_id();
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+                    AdminSharedError::ValidationFailed(format!(
+                        "Received disband request for a circuit that does not exist: {}",
+                        circuit_id
+                    ))
+                })?;
+
+                let (verifiers, protocol) = self.circuit_change_verifiers(circuit.members());
+
+                self.validate_disband_circuit(
+                    circuit_id,
+                    signer_public_key,
+                    requester_node_id,
+                    protocol,
+                )?;
+                debug!("proposing disband of circuit {}", circuit_id);
+
+                let disbanded_circuit = self
+                    .proposed_circuit_from_circuit(&circuit)?
+                    .with_circuit_status(&CircuitStatus::Disbanded)
+                    .build()
+                    .map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to build disbanded circuit: {}",
+                            err
+                        ))
+                    })?
+                    .into_proto();
+
+                self.set_pending_circuit_change(
+                    CircuitProposal_ProposalType::DISBAND,
+                    disbanded_circuit,
+                    &header,
+                    verifiers,
+                )
+            }
+            #[cfg(feature = "circuit-update")]
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ROSTER_REQUEST => {
+                let update_request = circuit_payload.get_circuit_update_roster_request();
+                let circuit_id = update_request.get_circuit_id();
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+                    AdminSharedError::ValidationFailed(format!(
+                        "Received roster update for a circuit that does not exist: {}",
+                        circuit_id
+                    ))
+                })?;
+
+                let (verifiers, protocol) = self.circuit_change_verifiers(circuit.members());
+
+                self.validate_update_roster(
+                    update_request,
+                    signer_public_key,
+                    requester_node_id,
+                    protocol,
+                )?;
+                debug!("proposing roster update of circuit {}", circuit_id);
+
+                let updated_circuit = self
+                    .proposed_circuit_from_circuit(&circuit)?
+                    .with_roster(&self.updated_roster(&circuit, update_request)?)
+                    .with_circuit_version(circuit.circuit_version() + 1)
+                    .build()
+                    .map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to build updated circuit: {}",
+                            err
+                        ))
+                    })?
+                    .into_proto();
+
+                self.set_pending_circuit_change(
+                    CircuitProposal_ProposalType::UPDATE_ROSTER,
+                    updated_circuit,
+                    &header,
+                    verifiers,
+                )
+            }
+            #[cfg(feature = "circuit-update")]
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST => {
+                let update_request =
+                    circuit_payload.get_circuit_update_application_metadata_request();
+                let circuit_id = update_request.get_circuit_id();
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+                    AdminSharedError::ValidationFailed(format!(
+                        "Received application metadata update for a circuit that does not \
+                         exist: {}",
+                        circuit_id
+                    ))
+                })?;
+
+                let (verifiers, protocol) = self.circuit_change_verifiers(circuit.members());
+
+                self.validate_update_application_metadata(
+                    update_request,
+                    signer_public_key,
+                    requester_node_id,
+                    protocol,
+                )?;
+                debug!(
+                    "proposing application metadata update of circuit {}",
+                    circuit_id
+                );
+
+                let mut updated_circuit = self
+                    .proposed_circuit_from_circuit(&circuit)?
+                    .with_circuit_version(circuit.circuit_version() + 1)
+                    .build()
+                    .map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to build updated circuit: {}",
+                            err
+                        ))
+                    })?
+                    .into_proto();
+                // the values of the request replace the existing values, where empty values
+                // remove them from the circuit
+                updated_circuit
+                    .set_application_metadata(update_request.get_application_metedata().to_vec());
+                updated_circuit.set_comments(update_request.get_comments().to_string());
+                updated_circuit.set_display_name(update_request.get_display_name().to_string());
+
+                self.set_pending_circuit_change(
+                    CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA,
+                    updated_circuit,
+                    &header,
+                    verifiers,
+                )
+            }
+            #[cfg(feature = "circuit-update")]
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ADD_NODE => {
+                let add_node_request = circuit_payload.get_circuit_update_add_node();
+                let circuit_id = add_node_request.get_circuit_id();
+                let new_node = add_node_request.get_node();
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                let circuit = self.current_circuit(circuit_id)?.ok_or_else(|| {
+                    AdminSharedError::ValidationFailed(format!(
+                        "Received request to add a node to a circuit that does not exist: {}",
+                        circuit_id
+                    ))
+                })?;
+
+                // the new node verifies the proposal along with the existing members
+                let mut members = circuit
+                    .members()
+                    .iter()
+                    .map(|member| member.node_id().to_string())
+                    .collect::<Vec<String>>();
+                members.push(new_node.get_node_id().to_string());
+                let (verifiers, protocol) = self.circuit_change_verifiers(&members);
+
+                if let Err(err) = self.validate_add_node(
+                    add_node_request,
+                    signer_public_key,
+                    requester_node_id,
+                    protocol,
+                ) {
+                    // remove peer_refs because we will not accept this proposal
+                    self.remove_add_node_peer_refs(circuit_id, &members)?;
+                    return Err(err);
+                }
+
+                #[cfg(feature = "challenge-authorization")]
+                {
+                    if circuit.authorization_type() == &AuthorizationType::Challenge {
+                        if let Err(err) = self.validate_challenge_authorized_members(&[new_node
+                            .get_node_id()
+                            .to_string()])
+                        {
+                            self.remove_add_node_peer_refs(circuit_id, &members)?;
+                            return Err(err);
+                        }
+                    }
+                }
+
+                debug!(
+                    "proposing adding node {} to circuit {}",
+                    new_node.get_node_id(),
+                    circuit_id
+                );
+
+                let mut updated_members = circuit.members().to_vec();
+                updated_members.push(ProposedNode::from_proto(new_node.clone()));
+                let updated_circuit = circuit
+                    .builder()
+                    .with_members(&updated_members)
+                    .with_circuit_version(circuit.circuit_version() + 1)
+                    .build()
+                    .map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to build updated circuit: {}",
+                            err
+                        ))
+                    })?
+                    .into_proto();
+
+                self.set_pending_circuit_change(
+                    CircuitProposal_ProposalType::ADD_NODE,
+                    updated_circuit,
+                    &header,
+                    verifiers,
+                )
+            }
+            #[cfg(feature = "circuit-update")]
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_REMOVE_NODE => {
+                let remove_node_request = circuit_payload.get_circuit_update_remove_node();
+                let circuit_id = remove_node_request.get_circuit_id();
+                let removed_node_id = remove_node_request.get_node_id();
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+
+                let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+                    AdminSharedError::ValidationFailed(format!(
+                        "Received request to remove a node from a circuit that does not exist: {}",
+                        circuit_id
+                    ))
+                })?;
+
+                // the removed node verifies the proposal along with the remaining members, so it
+                // is notified of its removal
+                let (verifiers, protocol) = self.circuit_change_verifiers(circuit.members());
+
+                self.validate_remove_node(
+                    remove_node_request,
+                    signer_public_key,
+                    requester_node_id,
+                    protocol,
+                )?;
+                debug!(
+                    "proposing removing node {} from circuit {}",
+                    removed_node_id, circuit_id
+                );
+
+                let builder = self.proposed_circuit_from_circuit(&circuit)?;
+                let updated_members = builder
+                    .members()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|member| member.node_id() != removed_node_id)
+                    .collect::<Vec<ProposedNode>>();
+                let updated_circuit = builder
+                    .with_members(&updated_members)
+                    .with_circuit_version(circuit.circuit_version() + 1)
+                    .build()
+                    .map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to build updated circuit: {}",
+                            err
+                        ))
+                    })?
+                    .into_proto();
+
+                self.set_pending_circuit_change(
+                    CircuitProposal_ProposalType::REMOVE_NODE,
+                    updated_circuit,
+                    &header,
+                    verifiers,
+                )
+            }
             CircuitManagementPayload_Action::ACTION_UNSET => Err(
                 AdminSharedError::ValidationFailed("Action must be set".to_string()),
             ),
@@ -771,69 +1272,238 @@ impl AdminServiceShared {
                 )))
             })?;
 
-        self.check_connected_peers_payload_vote(
-            &proposal.circuit().members(),
-            payload,
-            message_sender,
-        )
-    }
-
-    pub fn send_protocol_request(&mut self, node_id: &str) -> Result<(), ServiceError> {
-        if self
-            .service_protocols
-            .get(&admin_service_id(node_id))
-            .is_none()
-        {
-            // we will always have the network sender at this point
-            if let Some(ref network_sender) = self.network_sender {
-                debug!(
-                    "Sending service protocol request to {}",
-                    admin_service_id(node_id)
-                );
-                let mut request = ServiceProtocolVersionRequest::new();
-                request.set_protocol_min(ADMIN_SERVICE_PROTOCOL_MIN);
-                request.set_protocol_max(ADMIN_SERVICE_PROTOCOL_VERSION);
-                let mut msg = AdminMessage::new();
-                msg.set_message_type(AdminMessage_Type::SERVICE_PROTOCOL_VERSION_REQUEST);
-                msg.set_protocol_request(request);
-
-                let envelope_bytes = msg.write_to_bytes()?;
-                network_sender.send(&admin_service_id(node_id), &envelope_bytes)?;
-            }
-        } else {
-            debug!(
-                "Already agreed on protocol version with {}",
-                admin_service_id(node_id)
-            );
-        }
-        Ok(())
+        let members = proposal
+            .circuit()
+            .members()
+            .iter()
+            .map(|node| node.node_id().to_string())
+            .collect::<Vec<String>>();
+        self.check_connected_peers_payload_members(&members, payload, message_sender)
     }
 
-    fn check_connected_peers_payload_vote(
+    /// Propose disbanding an existing circuit
+    ///
+    /// This operation will propose the disbanding of a circuit to all of the circuit's member
+    /// nodes. The members are expected to already be peered, as they share an active circuit.
+    #[cfg(feature = "circuit-disband")]
+    pub fn propose_disband(
         &mut self,
-        members: &[ProposedNode],
         payload: CircuitManagementPayload,
         message_sender: String,
     ) -> Result<(), ServiceError> {
-        let mut missing_protocol_ids = vec![];
-        let mut pending_members = vec![];
-        for node in members {
-            if self.node_id() != node.node_id()
-                && self
-                    .service_protocols
-                    .get(&admin_service_id(node.node_id()))
-                    .is_none()
-            {
-                self.send_protocol_request(node.node_id())?;
-                missing_protocol_ids.push(admin_service_id(node.node_id()))
-            }
-            pending_members.push(node.node_id().to_string());
-        }
+        let circuit_id = payload.get_circuit_disband_request().get_circuit_id();
+        debug!("received circuit disband request for {}", circuit_id);
 
-        if missing_protocol_ids.is_empty() {
-            self.pending_circuit_payloads.push_back(payload);
-        } else {
-            debug!(
+        let circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("error occurred when trying to get circuit {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!(
+                        "Received disband request for a circuit that does not exist: {}",
+                        circuit_id
+                    ),
+                )))
+            })?;
+
+        self.check_connected_peers_payload_members(circuit.members(), payload, message_sender)
+    }
+
+    /// Propose an update to the roster of an existing circuit
+    ///
+    /// This operation will propose adding and removing services on a circuit to all of the
+    /// circuit's member nodes. The members are expected to already be peered, as they share an
+    /// active circuit.
+    #[cfg(feature = "circuit-update")]
+    pub fn propose_roster_update(
+        &mut self,
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        let circuit_id = payload.get_circuit_update_roster_request().get_circuit_id();
+        debug!("received circuit roster update for {}", circuit_id);
+
+        let circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("error occurred when trying to get circuit {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!(
+                        "Received roster update for a circuit that does not exist: {}",
+                        circuit_id
+                    ),
+                )))
+            })?;
+
+        self.check_connected_peers_payload_members(circuit.members(), payload, message_sender)
+    }
+
+    /// Propose an update to the application metadata of an existing circuit
+    ///
+    /// This operation will propose changing the application metadata, comments and display name
+    /// of a circuit to all of the circuit's member nodes. The members are expected to already be
+    /// peered, as they share an active circuit.
+    #[cfg(feature = "circuit-update")]
+    pub fn propose_application_metadata_update(
+        &mut self,
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        let circuit_id = payload
+            .get_circuit_update_application_metadata_request()
+            .get_circuit_id();
+        debug!(
+            "received circuit application metadata update for {}",
+            circuit_id
+        );
+
+        let circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("error occurred when trying to get circuit {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!(
+                        "Received application metadata update for a circuit that does not \
+                         exist: {}",
+                        circuit_id
+                    ),
+                )))
+            })?;
+
+        self.check_connected_peers_payload_members(circuit.members(), payload, message_sender)
+    }
+
+    /// Propose adding a node to an existing circuit
+    ///
+    /// This operation will propose adding a node to all of the circuit's member nodes, as well as
+    /// to the new node. The members are expected to already be peered, as they share an active
+    /// circuit. If there is no peer connection to the new node, it will be established.
+    #[cfg(feature = "circuit-update")]
+    pub fn propose_add_node(
+        &mut self,
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        let add_node_request = payload.get_circuit_update_add_node();
+        debug!(
+            "received request to add node {} to circuit {}",
+            add_node_request.get_node().get_node_id(),
+            add_node_request.get_circuit_id()
+        );
+
+        let new_node = add_node_request.get_node().clone();
+        self.check_connected_peers_payload_create(&[new_node], payload, message_sender)
+    }
+
+    /// Propose removing a node from an existing circuit
+    ///
+    /// This operation will propose removing a node to all of the circuit's member nodes, including
+    /// the node being removed. The members are expected to already be peered, as they share an
+    /// active circuit.
+    #[cfg(feature = "circuit-update")]
+    pub fn propose_remove_node(
+        &mut self,
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        let circuit_id = payload.get_circuit_update_remove_node().get_circuit_id();
+        debug!(
+            "received request to remove node {} from circuit {}",
+            payload.get_circuit_update_remove_node().get_node_id(),
+            circuit_id
+        );
+
+        let circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("error occurred when trying to get circuit {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!(
+                        "Received request to remove a node from a circuit that does not exist: {}",
+                        circuit_id
+                    ),
+                )))
+            })?;
+
+        self.check_connected_peers_payload_members(circuit.members(), payload, message_sender)
+    }
+
+    pub fn send_protocol_request(&mut self, node_id: &str) -> Result<(), ServiceError> {
+        if self
+            .service_protocols
+            .get(&admin_service_id(node_id))
+            .is_none()
+        {
+            // we will always have the network sender at this point
+            if let Some(ref network_sender) = self.network_sender {
+                debug!(
+                    "Sending service protocol request to {}",
+                    admin_service_id(node_id)
+                );
+                let mut request = ServiceProtocolVersionRequest::new();
+                request.set_protocol_min(ADMIN_SERVICE_PROTOCOL_MIN);
+                request.set_protocol_max(ADMIN_SERVICE_PROTOCOL_VERSION);
+                let mut msg = AdminMessage::new();
+                msg.set_message_type(AdminMessage_Type::SERVICE_PROTOCOL_VERSION_REQUEST);
+                msg.set_protocol_request(request);
+
+                let envelope_bytes = msg.write_to_bytes()?;
+                network_sender.send(&admin_service_id(node_id), &envelope_bytes)?;
+            }
+        } else {
+            debug!(
+                "Already agreed on protocol version with {}",
+                admin_service_id(node_id)
+            );
+        }
+        Ok(())
+    }
+
+    fn check_connected_peers_payload_members(
+        &mut self,
+        members: &[String],
+        payload: CircuitManagementPayload,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        let mut missing_protocol_ids = vec![];
+        let mut pending_members = vec![];
+        for node_id in members {
+            if self.node_id() != node_id
+                && self
+                    .service_protocols
+                    .get(&admin_service_id(node_id))
+                    .is_none()
+            {
+                self.send_protocol_request(node_id)?;
+                missing_protocol_ids.push(admin_service_id(node_id))
+            }
+            pending_members.push(node_id.to_string());
+        }
+
+        if missing_protocol_ids.is_empty() {
+            self.pending_circuit_payloads.push_back(payload);
+        } else {
+            debug!(
                 "Members {:?} added; awaiting service protocol agreement before proceeding",
                 &missing_protocol_ids
             );
@@ -970,6 +1640,77 @@ impl AdminServiceShared {
 
                 self.propose_vote(payload, "local".to_string())
             }
+            #[cfg(feature = "circuit-disband")]
+            CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST => {
+                self.validate_disband_circuit(
+                    payload.get_circuit_disband_request().get_circuit_id(),
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_disband(payload, "local".to_string())
+            }
+            #[cfg(feature = "circuit-update")]
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ROSTER_REQUEST => {
+                self.validate_update_roster(
+                    payload.get_circuit_update_roster_request(),
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_roster_update(payload, "local".to_string())
+            }
+            #[cfg(feature = "circuit-update")]
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_APPLICATION_METADATA_REQUEST => {
+                self.validate_update_application_metadata(
+                    payload.get_circuit_update_application_metadata_request(),
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_application_metadata_update(payload, "local".to_string())
+            }
+            #[cfg(feature = "circuit-update")]
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_ADD_NODE => {
+                self.validate_add_node(
+                    payload.get_circuit_update_add_node(),
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_add_node(payload, "local".to_string())
+            }
+            #[cfg(feature = "circuit-update")]
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_REMOVE_NODE => {
+                self.validate_remove_node(
+                    payload.get_circuit_update_remove_node(),
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_remove_node(payload, "local".to_string())
+            }
+            #[cfg(feature = "circuit-abandon")]
+            CircuitManagementPayload_Action::CIRCUIT_ABANDON => {
+                let circuit_id = payload.get_circuit_abandon().get_circuit_id();
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+                self.validate_abandon_circuit(circuit_id, signer_public_key, requester_node_id)
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.abandon_circuit(circuit_id, signer_public_key, requester_node_id)
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+            }
             CircuitManagementPayload_Action::ACTION_UNSET => {
                 Err(ServiceError::UnableToHandleMessage(Box::new(
                     AdminSharedError::ValidationFailed(String::from("No action specified")),
@@ -998,11 +1739,7 @@ impl AdminServiceShared {
         let mut pending_peers = vec![];
         let mut added_peers: Vec<String> = vec![];
         let mut pending_members = vec![];
-        for node in payload
-            .get_circuit_create_request()
-            .get_circuit()
-            .get_members()
-        {
+        for node in self.proposed_circuit_peers(&payload) {
             if self.node_id() != node.get_node_id() {
                 debug!("Referencing node {:?}", node);
                 let peer_ref = self
@@ -1063,6 +1800,92 @@ impl AdminServiceShared {
         }
     }
 
+    /// Returns the nodes that must be peered with to verify a proposed circuit management payload.
+    ///
+    /// These are the members of a new circuit. When a node is added to an existing circuit, the
+    /// new node must be peered with all of the circuit's members, while the members only need to
+    /// be peered with the new node.
+    fn proposed_circuit_peers(&self, payload: &CircuitManagementPayload) -> Vec<SplinterNode> {
+        #[cfg(feature = "circuit-update")]
+        {
+            if payload.has_circuit_update_add_node() {
+                let add_node_request = payload.get_circuit_update_add_node();
+                if add_node_request.get_node().get_node_id() != self.node_id {
+                    return vec![add_node_request.get_node().clone()];
+                }
+
+                return self
+                    .joining_circuits
+                    .get(add_node_request.get_circuit_id())
+                    .map(|circuit| {
+                        circuit
+                            .members()
+                            .iter()
+                            .cloned()
+                            .map(ProposedNode::into_proto)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+            }
+        }
+
+        payload
+            .get_circuit_create_request()
+            .get_circuit()
+            .get_members()
+            .to_vec()
+    }
+
+    /// Keep the current definition of a circuit that this node is being added to, which is sent
+    /// along with the proposal. This node needs the definition to verify the proposal, as it is
+    /// not yet a member of the circuit.
+    #[cfg(feature = "circuit-update")]
+    pub fn add_joining_circuit(
+        &mut self,
+        payload: &CircuitManagementPayload,
+        circuit: Circuit,
+    ) -> Result<(), AdminSharedError> {
+        let add_node_request = payload.get_circuit_update_add_node();
+        if !payload.has_circuit_update_add_node()
+            || add_node_request.get_node().get_node_id() != self.node_id
+            || add_node_request.get_circuit_id() != circuit.get_circuit_id()
+            || self
+                .admin_store
+                .get_circuit(circuit.get_circuit_id())?
+                .is_some()
+        {
+            return Ok(());
+        }
+
+        let circuit = ProposedCircuit::from_proto(circuit).map_err(|err| {
+            AdminSharedError::ValidationFailed(format!(
+                "Received invalid definition of circuit to join: {}",
+                err
+            ))
+        })?;
+        self.joining_circuits
+            .insert(circuit.circuit_id().to_string(), circuit);
+
+        Ok(())
+    }
+
+    /// Returns the current definition of the circuit the pending change adds a node to, which is
+    /// sent to the new node along with the proposal.
+    #[cfg(feature = "circuit-update")]
+    pub fn pending_joining_circuit(&self) -> Result<Option<Circuit>, AdminSharedError> {
+        match self.pending_changes {
+            Some(ref context)
+                if context.circuit_proposal.get_proposal_type()
+                    == CircuitProposal_ProposalType::ADD_NODE =>
+            {
+                Ok(self
+                    .current_circuit(context.circuit_proposal.get_circuit_id())?
+                    .map(ProposedCircuit::into_proto))
+            }
+            _ => Ok(None),
+        }
+    }
+
     #[cfg(not(feature = "admin-service-event-store"))]
     pub fn get_events_since(
         &self,
@@ -1187,6 +2010,39 @@ impl AdminServiceShared {
 
     pub fn on_peer_connected(&mut self, peer_id: &str) -> Result<(), AdminSharedError> {
         let mut unpeered_payloads = std::mem::replace(&mut self.unpeered_payloads, vec![]);
+
+        // A peer that was not authorized using challenge authorization is refused by the circuits
+        // that require it, so the payloads for these circuits that were waiting on the peer are
+        // dropped
+        #[cfg(feature = "challenge-authorization")]
+        {
+            let challenge_authorized = self
+                .authorization_inquisitor
+                .as_ref()
+                .and_then(|inquisitor| inquisitor.authorization_type(peer_id))
+                == Some(PeerAuthorizationType::Challenge);
+            if !challenge_authorized {
+                let (refused, waiting): (Vec<PendingPayload>, Vec<PendingPayload>) =
+                    unpeered_payloads.into_iter().partition(|unpeered_payload| {
+                        unpeered_payload
+                            .unpeered_ids
+                            .iter()
+                            .any(|unpeered_id| unpeered_id == peer_id)
+                            && self.requires_challenge_authorization(&unpeered_payload.payload_type)
+                    });
+                for refused_payload in refused {
+                    warn!(
+                        "Dropping circuit request including node {}: the node was not authorized \
+                         using challenge authorization",
+                        peer_id
+                    );
+                    for member in refused_payload.members {
+                        self.remove_peer_ref(&member);
+                    }
+                }
+                unpeered_payloads = waiting;
+            }
+        }
         for unpeered_payload in unpeered_payloads.iter_mut() {
             unpeered_payload
                 .unpeered_ids
@@ -1589,6 +2445,72 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Returns whether the circuit that the payload creates or adds a node to requires challenge
+    /// authorization.
+    #[cfg(feature = "challenge-authorization")]
+    fn requires_challenge_authorization(&self, payload_type: &PayloadType) -> bool {
+        let payload = match payload_type {
+            PayloadType::Circuit(payload) => payload,
+            PayloadType::Consensus(_, (_, payload)) => payload,
+        };
+
+        if payload.has_circuit_create_request() {
+            return payload
+                .get_circuit_create_request()
+                .get_circuit()
+                .get_authorization_type()
+                == Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION;
+        }
+
+        #[cfg(feature = "circuit-update")]
+        {
+            if payload.has_circuit_update_add_node() {
+                let circuit_id = payload.get_circuit_update_add_node().get_circuit_id();
+                return match self.admin_store.get_circuit(circuit_id) {
+                    Ok(Some(circuit)) => {
+                        circuit.authorization_type() == &AuthorizationType::Challenge
+                    }
+                    Ok(None) => false,
+                    Err(err) => {
+                        error!("Unable to get circuit {}: {}", circuit_id, err);
+                        false
+                    }
+                };
+            }
+        }
+
+        false
+    }
+
+    /// Checks that each of the given members, other than the local node, was authorized using
+    /// challenge authorization; circuits with the challenge authorization type will not be
+    /// created with members that were only trusted.
+    #[cfg(feature = "challenge-authorization")]
+    fn validate_challenge_authorized_members(
+        &self,
+        members: &[String],
+    ) -> Result<(), AdminSharedError> {
+        let authorization_inquisitor = self.authorization_inquisitor.as_ref().ok_or_else(|| {
+            AdminSharedError::ValidationFailed(
+                "Challenge authorization is not enabled on this node".to_string(),
+            )
+        })?;
+
+        for node_id in members.iter().filter(|node_id| *node_id != self.node_id()) {
+            match authorization_inquisitor.authorization_type(node_id) {
+                Some(PeerAuthorizationType::Challenge) => (),
+                _ => {
+                    return Err(AdminSharedError::ValidationFailed(format!(
+                        "Node {} was not authorized using challenge authorization",
+                        node_id
+                    )))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn validate_circuit(&self, circuit: &Circuit) -> Result<(), AdminSharedError> {
         if circuit.get_authorization_type() == Circuit_AuthorizationType::UNSET_AUTHORIZATION_TYPE {
             return Err(AdminSharedError::ValidationFailed(
@@ -1596,6 +2518,17 @@ impl AdminServiceShared {
             ));
         }
 
+        #[cfg(not(feature = "challenge-authorization"))]
+        {
+            if circuit.get_authorization_type()
+                == Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION
+            {
+                return Err(AdminSharedError::ValidationFailed(
+                    "challenge authorization is not supported by this node".to_string(),
+                ));
+            }
+        }
+
         if circuit.get_persistence() == Circuit_PersistenceType::UNSET_PERSISTENCE_TYPE {
             return Err(AdminSharedError::ValidationFailed(
                 "persistence_type cannot be unset".to_string(),
@@ -1824,179 +2757,1784 @@ impl AdminServiceShared {
         Ok(())
     }
 
-    fn validate_circuit_management_payload(
+    #[cfg(feature = "circuit-disband")]
+    fn validate_disband_circuit(
         &self,
-        payload: &CircuitManagementPayload,
-        header: &CircuitManagementPayload_Header,
+        circuit_id: &str,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
     ) -> Result<(), AdminSharedError> {
-        // Validate payload signature
-        if payload.get_signature().is_empty() {
-            return Err(AdminSharedError::ValidationFailed(
-                "CircuitManagementPayload signature must be set".to_string(),
-            ));
-        };
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit disband is not supported on protocol {}",
+                protocol
+            )));
+        }
 
-        // Validate the payload header
-        if payload.get_header().is_empty() {
+        if requester_node_id.is_empty() {
             return Err(AdminSharedError::ValidationFailed(
-                "CircuitManagementPayload header must be set".to_string(),
+                "requester_node_id is empty".to_string(),
             ));
-        };
+        }
 
-        // Validate the header, requester field is set
-        if header.get_requester().is_empty() {
-            return Err(AdminSharedError::ValidationFailed(
-                "CircuitManagementPayload must have a requester".to_string(),
-            ));
-        };
+        self.validate_key(signer_public_key)?;
 
-        self.validate_key(header.get_requester())?;
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
+        }
 
-        // Validate the header, requester_node_id is set
-        if header.get_requester_node_id().is_empty() {
-            return Err(AdminSharedError::ValidationFailed(
-                "CircuitManagementPayload must have a requester node id".to_string(),
-            ));
-        };
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to propose for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
+
+        if self.has_proposal(circuit_id)? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Ignoring disband proposal of circuit {}, a proposal for this circuit already \
+                 exists",
+                circuit_id
+            )));
+        }
+
+        let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::ValidationFailed(format!(
+                "Received disband request for a circuit that does not exist: {}",
+                circuit_id
+            ))
+        })?;
+
+        if circuit.circuit_version() < CIRCUIT_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} has version {}, which does not support disbanding",
+                circuit_id,
+                circuit.circuit_version()
+            )));
+        }
+
+        if circuit.circuit_status() != &CircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} cannot be disbanded, it is {:?}",
+                circuit_id,
+                circuit.circuit_status()
+            )));
+        }
+
+        if !circuit
+            .members()
+            .iter()
+            .any(|member| member == requester_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Requester node {} is not a member of circuit {}",
+                requester_node_id, circuit_id
+            )));
+        }
 
         Ok(())
     }
 
-    fn check_approved(
+    #[cfg(feature = "circuit-abandon")]
+    fn validate_abandon_circuit(
         &self,
-        proposal: &CircuitProposal,
-    ) -> Result<CircuitProposalStatus, AdminSharedError> {
-        let mut received_votes = HashSet::new();
-        for vote in proposal.get_votes() {
-            if vote.get_vote() == CircuitProposalVote_Vote::REJECT {
-                return Ok(CircuitProposalStatus::Rejected);
-            }
-            received_votes.insert(vote.get_voter_node_id().to_string());
+        circuit_id: &str,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+    ) -> Result<(), AdminSharedError> {
+        // abandoning a circuit is a unilateral action, so it may only be requested for this node
+        if requester_node_id != self.node_id {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Node {} cannot abandon circuit {} on behalf of node {}",
+                requester_node_id, circuit_id, self.node_id
+            )));
         }
 
-        let mut required_votes = proposal
-            .get_circuit_proposal()
-            .get_members()
-            .to_vec()
-            .iter()
-            .map(|member| member.get_node_id().to_string())
-            .collect::<HashSet<String>>();
-
-        required_votes.remove(proposal.get_requester_node_id());
+        self.validate_key(signer_public_key)?;
 
-        if required_votes == received_votes {
-            Ok(CircuitProposalStatus::Accepted)
-        } else {
-            Ok(CircuitProposalStatus::Pending)
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
         }
-    }
 
-    /// Initialize all services that this node should run on the created circuit using the service
-    /// orchestrator. This may not include all services if they are not supported locally. It is
-    /// expected that some services will be started externally.
-    pub fn initialize_services(&mut self, circuit: &Circuit) -> Result<(), AdminSharedError> {
-        let orchestrator = self.orchestrator.lock().map_err(|_| {
-            AdminSharedError::ServiceInitializationFailed {
-                context: "ServiceOrchestrator lock poisoned".into(),
-                source: None,
-            }
-        })?;
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to abandon circuits for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
 
-        // Get all services this node is allowed to run
-        let services = circuit
-            .get_roster()
-            .iter()
-            .filter(|service| {
-                service.allowed_nodes.contains(&self.node_id)
-                    && orchestrator
-                        .supported_service_types()
-                        .contains(&service.get_service_type().to_string())
-            })
-            .collect::<Vec<_>>();
+        let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::ValidationFailed(format!(
+                "Received abandon request for a circuit that does not exist: {}",
+                circuit_id
+            ))
+        })?;
 
-        // Start all services the orchestrator has a factory for
-        for service in services {
-            let service_definition = ServiceDefinition {
-                circuit: circuit.circuit_id.clone(),
-                service_id: service.service_id.clone(),
-                service_type: service.service_type.clone(),
-            };
+        if circuit.circuit_version() < CIRCUIT_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} has version {}, which does not support abandoning",
+                circuit_id,
+                circuit.circuit_version()
+            )));
+        }
 
-            let service_arguments = service
-                .arguments
-                .iter()
-                .map(|arg| (arg.key.clone(), arg.value.clone()))
-                .collect();
+        if circuit.circuit_status() != &CircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} cannot be abandoned, it has status {:?}",
+                circuit_id,
+                circuit.circuit_status()
+            )));
+        }
 
-            orchestrator
-                .initialize_service(service_definition.clone(), service_arguments)
-                .map_err(|err| AdminSharedError::ServiceInitializationFailed {
-                    context: format!(
-                        "Unable to start service {} on circuit {}",
-                        service.service_id, circuit.circuit_id
-                    ),
-                    source: Some(err),
-                })?;
+        if !circuit
+            .members()
+            .iter()
+            .any(|member| member == requester_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Requester node {} is not a member of circuit {}",
+                requester_node_id, circuit_id
+            )));
         }
 
         Ok(())
     }
 
-    pub fn get_circuits(
+    #[cfg(feature = "circuit-update")]
+    fn validate_update_roster(
         &self,
-    ) -> Result<Box<dyn ExactSizeIterator<Item = StoreCircuit>>, AdminSharedError> {
-        self.admin_store
-            .list_circuits(&Vec::new())
-            .map_err(AdminSharedError::from)
-    }
+        update_request: &CircuitUpdateRosterRequest,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = update_request.get_circuit_id();
 
-    pub fn get_nodes(&self) -> Result<BTreeMap<String, CircuitNode>, AdminSharedError> {
-        Ok(self
-            .admin_store
-            .list_nodes()
-            .map_err(AdminSharedError::from)?
-            .into_iter()
-            .map(|node| (node.node_id().into(), node))
-            .collect())
-    }
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit roster updates are not supported on protocol {}",
+                protocol
+            )));
+        }
 
-    fn verify_signature(&self, payload: &CircuitManagementPayload) -> Result<bool, ServiceError> {
-        let header: CircuitManagementPayload_Header =
-            Message::parse_from_bytes(payload.get_header())?;
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
+        }
 
-        let signature = payload.get_signature().to_vec();
-        let public_key = header.get_requester().to_vec();
+        self.validate_key(signer_public_key)?;
 
-        self.signature_verifier
-            .verify(
-                &payload.get_header(),
-                &Signature::new(signature),
-                &PublicKey::new(public_key),
-            )
-            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
-    }
-}
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
+        }
 
-#[cfg(all(test, feature = "sqlite"))]
-mod tests {
-    use super::*;
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to propose for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
 
-    use std::sync::{Arc, Mutex};
+        if self.has_proposal(circuit_id)? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Ignoring roster update of circuit {}, a proposal for this circuit already exists",
+                circuit_id
+            )));
+        }
 
-    use cylinder::{secp256k1::Secp256k1Context, Context};
-    use protobuf::{Message, RepeatedField};
+        let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::ValidationFailed(format!(
+                "Received roster update for a circuit that does not exist: {}",
+                circuit_id
+            ))
+        })?;
 
-    use diesel::{
-        r2d2::{ConnectionManager as DieselConnectionManager, Pool},
-        sqlite::SqliteConnection,
-    };
+        if circuit.circuit_version() < CIRCUIT_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} has version {}, which does not support roster updates",
+                circuit_id,
+                circuit.circuit_version()
+            )));
+        }
 
-    #[cfg(feature = "admin-service-event-store")]
-    use crate::admin::service::event::store::memory::MemoryAdminServiceEventStore;
+        if circuit.circuit_status() != &CircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} cannot be updated, it has status {:?}",
+                circuit_id,
+                circuit.circuit_status()
+            )));
+        }
 
-    use crate::admin::service::AdminKeyVerifierError;
+        if !circuit
+            .members()
+            .iter()
+            .any(|member| member == requester_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Requester node {} is not a member of circuit {}",
+                requester_node_id, circuit_id
+            )));
+        }
+
+        if update_request.get_add_services().is_empty()
+            && update_request.get_remove_services().is_empty()
+        {
+            return Err(AdminSharedError::ValidationFailed(
+                "Roster update must add or remove at least one service".to_string(),
+            ));
+        }
+
+        let mut removed_services: Vec<&str> = Vec::new();
+        for service in update_request.get_remove_services() {
+            let service_id = service.get_service_id();
+            if !circuit
+                .roster()
+                .iter()
+                .any(|existing_service| existing_service.service_id() == service_id)
+            {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "Cannot remove service {}, it is not in the roster of circuit {}",
+                    service_id, circuit_id
+                )));
+            } else if removed_services.contains(&service_id) {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Every service to be removed must be unique".to_string(),
+                ));
+            } else {
+                removed_services.push(service_id);
+            }
+        }
+
+        let mut added_services: Vec<&str> = Vec::new();
+        for service in update_request.get_add_services() {
+            if service.get_allowed_nodes().is_empty() {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Service cannot have an empty allowed nodes list".to_string(),
+                ));
+            }
+
+            if service.get_allowed_nodes().len() > 1 {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Only one allowed node for a service is supported".to_string(),
+                ));
+            }
+
+            for node in service.get_allowed_nodes() {
+                if !circuit.members().contains(node) {
+                    return Err(AdminSharedError::ValidationFailed(format!(
+                        "Service cannot have an allowed node that is not in members: {}",
+                        node
+                    )));
+                }
+            }
+
+            let service_id = service.get_service_id();
+            if service_id.is_empty() {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Service id cannot be empty".to_string(),
+                ));
+            } else if !messages::is_valid_service_id(service_id) {
+                return Err(AdminSharedError::ValidationFailed(format!(
+                    "'{}' is not a valid service ID: must be a 4 character base62 string",
+                    service_id,
+                )));
+            } else if added_services.contains(&service_id)
+                || circuit
+                    .roster()
+                    .iter()
+                    .any(|existing_service| existing_service.service_id() == service_id)
+            {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Every service must be unique in the circuit.".to_string(),
+                ));
+            } else {
+                added_services.push(service_id)
+            }
+
+            #[cfg(feature = "service-arg-validation")]
+            {
+                self.validate_service_args(service)?;
+            }
+        }
+
+        if circuit.roster().len() + added_services.len() == removed_services.len() {
+            return Err(AdminSharedError::ValidationFailed(
+                "The circuit must have services".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "circuit-update")]
+    fn validate_update_application_metadata(
+        &self,
+        update_request: &CircuitUpdateApplicationMetadataRequest,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = update_request.get_circuit_id();
+
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Application metadata updates are not supported on protocol {}",
+                protocol
+            )));
+        }
+
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
+        }
+
+        self.validate_key(signer_public_key)?;
+
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
+        }
+
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to propose for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
+
+        if self.has_proposal(circuit_id)? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Ignoring application metadata update for circuit {}, a proposal for this \
+                 circuit already exists",
+                circuit_id
+            )));
+        }
+
+        let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::ValidationFailed(format!(
+                "Received application metadata update for a circuit that does not exist: {}",
+                circuit_id
+            ))
+        })?;
+
+        if circuit.circuit_version() < CIRCUIT_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} has version {}, which does not support application metadata updates",
+                circuit_id,
+                circuit.circuit_version()
+            )));
+        }
+
+        if circuit.circuit_status() != &CircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} cannot be updated, it has status {:?}",
+                circuit_id,
+                circuit.circuit_status()
+            )));
+        }
+
+        if !circuit
+            .members()
+            .iter()
+            .any(|member| member == requester_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Requester node {} is not a member of circuit {}",
+                requester_node_id, circuit_id
+            )));
+        }
+
+        if circuit
+            .application_metadata()
+            .as_deref()
+            .unwrap_or_default()
+            == update_request.get_application_metedata()
+            && circuit.comments().as_deref().unwrap_or_default() == update_request.get_comments()
+            && circuit.display_name().as_deref().unwrap_or_default()
+                == update_request.get_display_name()
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Application metadata update does not change circuit {}",
+                circuit_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "circuit-update")]
+    fn validate_add_node(
+        &self,
+        add_node_request: &CircuitUpdateAddNodeRequest,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = add_node_request.get_circuit_id();
+
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Adding nodes to a circuit is not supported on protocol {}",
+                protocol
+            )));
+        }
+
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
+        }
+
+        self.validate_key(signer_public_key)?;
+
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
+        }
+
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to propose for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
+
+        if self.has_proposal(circuit_id)? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Ignoring request to add a node to circuit {}, a proposal for this circuit \
+                 already exists",
+                circuit_id
+            )));
+        }
+
+        let circuit = self.current_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::ValidationFailed(format!(
+                "Received request to add a node to a circuit that does not exist: {}",
+                circuit_id
+            ))
+        })?;
+
+        if circuit.circuit_version() < CIRCUIT_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} has version {}, which does not support adding nodes",
+                circuit_id,
+                circuit.circuit_version()
+            )));
+        }
+
+        if circuit.circuit_status() != &CircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} cannot be updated, it has status {:?}",
+                circuit_id,
+                circuit.circuit_status()
+            )));
+        }
+
+        if !circuit
+            .members()
+            .iter()
+            .any(|member| member.node_id() == requester_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Requester node {} is not a member of circuit {}",
+                requester_node_id, circuit_id
+            )));
+        }
+
+        let new_node = add_node_request.get_node();
+        if new_node.get_node_id().is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "Member node id cannot be empty".to_string(),
+            ));
+        }
+
+        if circuit
+            .members()
+            .iter()
+            .any(|member| member.node_id() == new_node.get_node_id())
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Node {} is already a member of circuit {}",
+                new_node.get_node_id(),
+                circuit_id
+            )));
+        }
+
+        let endpoints = new_node.get_endpoints();
+        if endpoints.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "Member endpoints cannot be empty".to_string(),
+            ));
+        } else if endpoints.iter().any(|endpoint| endpoint.is_empty()) {
+            return Err(AdminSharedError::ValidationFailed(
+                "Member cannot have an empty endpoint".to_string(),
+            ));
+        }
+
+        let mut all_endpoints = circuit
+            .members()
+            .iter()
+            .flat_map(|member| member.endpoints().iter())
+            .collect::<Vec<&String>>();
+        for endpoint in endpoints {
+            if all_endpoints.contains(&endpoint) {
+                return Err(AdminSharedError::ValidationFailed(
+                    "Every member endpoint must be unique in the circuit.".to_string(),
+                ));
+            }
+            all_endpoints.push(endpoint);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "circuit-update")]
+    fn validate_remove_node(
+        &self,
+        remove_node_request: &CircuitUpdateRemoveNodeRequest,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = remove_node_request.get_circuit_id();
+        let node_id = remove_node_request.get_node_id();
+
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Removing nodes from a circuit is not supported on protocol {}",
+                protocol
+            )));
+        }
+
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
+        }
+
+        self.validate_key(signer_public_key)?;
+
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
+        }
+
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to propose for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
+
+        if self.has_proposal(circuit_id)? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Ignoring request to remove a node from circuit {}, a proposal for this circuit \
+                 already exists",
+                circuit_id
+            )));
+        }
+
+        let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::ValidationFailed(format!(
+                "Received request to remove a node from a circuit that does not exist: {}",
+                circuit_id
+            ))
+        })?;
+
+        if circuit.circuit_version() < CIRCUIT_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} has version {}, which does not support removing nodes",
+                circuit_id,
+                circuit.circuit_version()
+            )));
+        }
+
+        if circuit.circuit_status() != &CircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} cannot be updated, it has status {:?}",
+                circuit_id,
+                circuit.circuit_status()
+            )));
+        }
+
+        if !circuit
+            .members()
+            .iter()
+            .any(|member| member == requester_node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Requester node {} is not a member of circuit {}",
+                requester_node_id, circuit_id
+            )));
+        }
+
+        if !circuit.members().iter().any(|member| member == node_id) {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Node {} is not a member of circuit {}",
+                node_id, circuit_id
+            )));
+        }
+
+        if circuit.members().len() == 1 {
+            return Err(AdminSharedError::ValidationFailed(
+                "The circuit must have members".to_string(),
+            ));
+        }
+
+        if circuit
+            .roster()
+            .iter()
+            .any(|service| service.node_id() == node_id)
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Node {} still runs services on circuit {}, which must be removed from the \
+                 roster first",
+                node_id, circuit_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn validate_circuit_management_payload(
+        &self,
+        payload: &CircuitManagementPayload,
+        header: &CircuitManagementPayload_Header,
+    ) -> Result<(), AdminSharedError> {
+        // Validate payload signature
+        if payload.get_signature().is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "CircuitManagementPayload signature must be set".to_string(),
+            ));
+        };
+
+        // Validate the payload header
+        if payload.get_header().is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "CircuitManagementPayload header must be set".to_string(),
+            ));
+        };
+
+        // Validate the header, requester field is set
+        if header.get_requester().is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "CircuitManagementPayload must have a requester".to_string(),
+            ));
+        };
+
+        self.validate_key(header.get_requester())?;
+
+        // Validate the header, requester_node_id is set
+        if header.get_requester_node_id().is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "CircuitManagementPayload must have a requester node id".to_string(),
+            ));
+        };
+
+        Ok(())
+    }
+
+    fn check_approved(
+        &self,
+        proposal: &CircuitProposal,
+    ) -> Result<CircuitProposalStatus, AdminSharedError> {
+        let mut received_votes = HashSet::new();
+        for vote in proposal.get_votes() {
+            if vote.get_vote() == CircuitProposalVote_Vote::REJECT {
+                return Ok(CircuitProposalStatus::Rejected);
+            }
+            received_votes.insert(vote.get_voter_node_id().to_string());
+        }
+
+        let mut required_votes = proposal
+            .get_circuit_proposal()
+            .get_members()
+            .to_vec()
+            .iter()
+            .map(|member| member.get_node_id().to_string())
+            .collect::<HashSet<String>>();
+
+        required_votes.remove(proposal.get_requester_node_id());
+
+        if required_votes == received_votes {
+            Ok(CircuitProposalStatus::Accepted)
+        } else {
+            Ok(CircuitProposalStatus::Pending)
+        }
+    }
+
+    /// Initialize all services that this node should run on the created circuit using the service
+    /// orchestrator. This may not include all services if they are not supported locally. It is
+    /// expected that some services will be started externally.
+    pub fn initialize_services(&mut self, circuit: &Circuit) -> Result<(), AdminSharedError> {
+        let orchestrator = self.orchestrator.lock().map_err(|_| {
+            AdminSharedError::ServiceInitializationFailed {
+                context: "ServiceOrchestrator lock poisoned".into(),
+                source: None,
+            }
+        })?;
+
+        // Get all services this node is allowed to run
+        let services = circuit
+            .get_roster()
+            .iter()
+            .filter(|service| {
+                service.allowed_nodes.contains(&self.node_id)
+                    && orchestrator
+                        .supported_service_types()
+                        .contains(&service.get_service_type().to_string())
+            })
+            .collect::<Vec<_>>();
+
+        // Start all services the orchestrator has a factory for
+        for service in services {
+            let service_definition = ServiceDefinition {
+                circuit: circuit.circuit_id.clone(),
+                service_id: service.service_id.clone(),
+                service_type: service.service_type.clone(),
+            };
+
+            let service_arguments = service
+                .arguments
+                .iter()
+                .map(|arg| (arg.key.clone(), arg.value.clone()))
+                .collect();
+
+            orchestrator
+                .initialize_service(service_definition.clone(), service_arguments)
+                .map_err(|err| AdminSharedError::ServiceInitializationFailed {
+                    context: format!(
+                        "Unable to start service {} on circuit {}",
+                        service.service_id, circuit.circuit_id
+                    ),
+                    source: Some(err),
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Shut down the given services of a circuit that this node is running using the service
+    /// orchestrator.
+    #[cfg(any(
+        feature = "circuit-abandon",
+        feature = "circuit-disband",
+        feature = "circuit-update"
+    ))]
+    fn shutdown_services(
+        &self,
+        circuit_id: &str,
+        services: &[StoreService],
+    ) -> Result<(), AdminSharedError> {
+        let orchestrator =
+            self.orchestrator
+                .lock()
+                .map_err(|_| AdminSharedError::ServiceShutdownFailed {
+                    context: "ServiceOrchestrator lock poisoned".into(),
+                    source: None,
+                })?;
+
+        // Get the services this node is running
+        let services = services
+            .iter()
+            .filter(|service| {
+                service.node_id() == self.node_id
+                    && orchestrator
+                        .supported_service_types()
+                        .contains(&service.service_type().to_string())
+            })
+            .collect::<Vec<_>>();
+
+        for service in services {
+            let service_definition = ServiceDefinition {
+                circuit: circuit_id.into(),
+                service_id: service.service_id().into(),
+                service_type: service.service_type().into(),
+            };
+
+            match orchestrator.shutdown_service(&service_definition) {
+                Ok(()) => (),
+                // the service was never started on this node, so there is nothing to stop
+                Err(ShutdownServiceError::UnknownService) => debug!(
+                    "Service {} on circuit {} is not running",
+                    service.service_id(),
+                    circuit_id
+                ),
+                Err(err) => {
+                    return Err(AdminSharedError::ServiceShutdownFailed {
+                        context: format!(
+                            "Unable to shutdown service {} on circuit {}",
+                            service.service_id(),
+                            circuit_id
+                        ),
+                        source: Some(err),
+                    })
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Disband a circuit whose disband proposal was accepted by all members.
+    ///
+    /// The circuit is kept in the admin store with a disbanded status, while its routes, the
+    /// services this node runs on the circuit, and the peer references held for its members are
+    /// removed.
+    #[cfg(feature = "circuit-disband")]
+    fn disband_circuit(
+        &mut self,
+        circuit_proposal: CircuitProposal,
+        signer_public_key: Vec<u8>,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit_proposal.get_circuit_id().to_string();
+
+        // a proposal is only stored if it had to wait on votes from other members
+        if self.has_proposal(&circuit_id)? {
+            self.admin_store.remove_proposal(&circuit_id)?;
+        }
+
+        let circuit = self.admin_store.get_circuit(&circuit_id)?.ok_or_else(|| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to get circuit that is being disbanded: {}",
+                circuit_id
+            ))
+        })?;
+
+        let disbanded_circuit = circuit
+            .builder()
+            .with_circuit_status(&CircuitStatus::Disbanded)
+            .build()
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to build disbanded circuit: {}",
+                    err
+                ))
+            })?;
+        self.admin_store.update_circuit(disbanded_circuit)?;
+
+        self.routing_table_writer
+            .remove_circuit(&circuit_id)
+            .map_err(|_| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to remove circuit from routing table: {}",
+                    circuit_id
+                ))
+            })?;
+
+        self.shutdown_services(circuit.circuit_id(), circuit.roster())?;
+
+        for member in circuit.members() {
+            if member != &self.node_id {
+                self.remove_peer_ref(member);
+            }
+        }
+
+        let mgmt_type = circuit.circuit_management_type().to_string();
+        let circuit_proposal_proto = messages::CircuitProposal::from_proto(circuit_proposal)?;
+        let event = messages::AdminServiceEvent::ProposalAccepted((
+            circuit_proposal_proto.clone(),
+            signer_public_key,
+        ));
+        self.send_event(&mgmt_type, event);
+        let event = messages::AdminServiceEvent::CircuitDisbanded(circuit_proposal_proto);
+        self.send_event(&mgmt_type, event);
+
+        info!("circuit {} has been disbanded", circuit_id);
+        Ok(())
+    }
+
+    /// Abandon a circuit on this node, without the agreement of the other members.
+    ///
+    /// The circuit is kept in the admin store with an abandoned status, while its routes, the
+    /// services this node runs on the circuit, and the peer references held for its members are
+    /// removed. Any pending proposal for the circuit is dropped, as it can no longer be committed
+    /// by this node. The other members are notified with an `ABANDONED_CIRCUIT` message before the
+    /// peer references are removed.
+    #[cfg(feature = "circuit-abandon")]
+    fn abandon_circuit(
+        &mut self,
+        circuit_id: &str,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+    ) -> Result<(), AdminSharedError> {
+        let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to get circuit that is being abandoned: {}",
+                circuit_id
+            ))
+        })?;
+
+        if self.has_proposal(circuit_id)? {
+            debug!(
+                "Removing pending proposal for abandoned circuit {}",
+                circuit_id
+            );
+            self.admin_store.remove_proposal(circuit_id)?;
+        }
+
+        let abandoned_circuit = circuit
+            .builder()
+            .with_circuit_status(&CircuitStatus::Abandoned)
+            .build()
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to build abandoned circuit: {}",
+                    err
+                ))
+            })?;
+        self.admin_store.update_circuit(abandoned_circuit)?;
+
+        self.routing_table_writer
+            .remove_circuit(circuit_id)
+            .map_err(|_| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to remove circuit from routing table: {}",
+                    circuit_id
+                ))
+            })?;
+
+        self.shutdown_services(circuit.circuit_id(), circuit.roster())?;
+
+        if let Some(ref network_sender) = self.network_sender {
+            let mut abandoned_circuit = AbandonedCircuit::new();
+            abandoned_circuit.set_circuit_id(circuit_id.to_string());
+            abandoned_circuit.set_member_node_id(self.node_id.clone());
+            let mut msg = AdminMessage::new();
+            msg.set_message_type(AdminMessage_Type::ABANDONED_CIRCUIT);
+            msg.set_abandoned_circuit(abandoned_circuit);
+
+            let envelope_bytes = msg.write_to_bytes().map_err(MarshallingError::from)?;
+            for member in circuit.members() {
+                if member != &self.node_id {
+                    // The circuit is abandoned regardless of whether the members are reachable
+                    if let Err(err) =
+                        network_sender.send(&admin_service_id(member), &envelope_bytes)
+                    {
+                        warn!(
+                            "Unable to notify {} that circuit {} was abandoned: {}",
+                            member, circuit_id, err
+                        );
+                    }
+                }
+            }
+        }
+
+        for member in circuit.members() {
+            if member != &self.node_id {
+                self.remove_peer_ref(member);
+            }
+        }
+
+        // Abandoning a circuit does not go through a proposal, so the event carries an abandon
+        // proposal built from the circuit in the abandoned state; see
+        // `AdminServiceEvent::CircuitAbandoned`.
+        let circuit_proto = self
+            .proposed_circuit_from_circuit(&circuit)?
+            .with_circuit_status(&CircuitStatus::Abandoned)
+            .build()
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to build abandoned circuit: {}",
+                    err
+                ))
+            })?
+            .into_proto();
+
+        let mut circuit_proposal = CircuitProposal::new();
+        circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::ABANDON);
+        circuit_proposal.set_circuit_id(circuit_id.into());
+        circuit_proposal.set_circuit_hash(sha256(&circuit_proto)?);
+        circuit_proposal.set_circuit_proposal(circuit_proto);
+        circuit_proposal.set_requester(signer_public_key.to_vec());
+        circuit_proposal.set_requester_node_id(requester_node_id.to_string());
+
+        let event = messages::AdminServiceEvent::CircuitAbandoned(
+            messages::CircuitProposal::from_proto(circuit_proposal)?,
+        );
+        self.send_event(circuit.circuit_management_type(), event);
+
+        info!("circuit {} has been abandoned", circuit_id);
+        Ok(())
+    }
+
+    /// Handle the notification that another member has abandoned a circuit.
+    ///
+    /// The circuit stays active on this node, but the member no longer takes part in it: the peer
+    /// reference held for the member on behalf of the circuit is removed, and any pending proposal
+    /// for the circuit is dropped, as the member will not vote on it. The notification must be
+    /// sent by the admin service of the member that abandoned the circuit.
+    #[cfg(feature = "circuit-abandon")]
+    pub fn handle_abandoned_circuit(
+        &mut self,
+        circuit_id: &str,
+        member_node_id: &str,
+        sender: &str,
+    ) -> Result<(), AdminSharedError> {
+        if sender != admin_service_id(member_node_id) {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} cannot report that node {} abandoned circuit {}",
+                sender, member_node_id, circuit_id
+            )));
+        }
+
+        let circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::ValidationFailed(format!(
+                "Node {} abandoned a circuit that does not exist: {}",
+                member_node_id, circuit_id
+            ))
+        })?;
+
+        if !circuit
+            .members()
+            .iter()
+            .any(|member| member == member_node_id)
+            || member_node_id == self.node_id
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Node {} is not another member of circuit {}",
+                member_node_id, circuit_id
+            )));
+        }
+
+        if circuit.circuit_status() != &CircuitStatus::Active {
+            debug!(
+                "Ignoring abandonment of circuit {} by {}, the circuit has status {:?}",
+                circuit_id,
+                member_node_id,
+                circuit.circuit_status()
+            );
+            return Ok(());
+        }
+
+        // The notification may be delivered more than once
+        if !self
+            .abandoned_members
+            .insert((circuit_id.to_string(), member_node_id.to_string()))
+        {
+            return Ok(());
+        }
+
+        if self.has_proposal(circuit_id)? {
+            debug!(
+                "Removing pending proposal for circuit {} abandoned by {}",
+                circuit_id, member_node_id
+            );
+            self.admin_store.remove_proposal(circuit_id)?;
+        }
+
+        self.remove_peer_ref(member_node_id);
+
+        info!(
+            "circuit {} has been abandoned by {}",
+            circuit_id, member_node_id
+        );
+        Ok(())
+    }
+
+    /// Apply a roster update whose proposal was accepted by all members.
+    ///
+    /// The updated circuit is written to the admin store and the routing table. Services this
+    /// node ran that were removed from the roster are shut down, and services added to the roster
+    /// for this node are started.
+    #[cfg(feature = "circuit-update")]
+    fn update_circuit_roster(
+        &mut self,
+        circuit_proposal: CircuitProposal,
+        signer_public_key: Vec<u8>,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit_proposal.get_circuit_id().to_string();
+
+        // a proposal is only stored if it had to wait on votes from other members
+        if self.has_proposal(&circuit_id)? {
+            self.admin_store.remove_proposal(&circuit_id)?;
+        }
+
+        let existing_circuit = self.admin_store.get_circuit(&circuit_id)?.ok_or_else(|| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to get circuit that is being updated: {}",
+                circuit_id
+            ))
+        })?;
+
+        let updated_circuit = StoreCircuit::from(
+            ProposedCircuit::from_proto(circuit_proposal.get_circuit_proposal().clone()).map_err(
+                |err| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to build updated circuit: {}",
+                        err
+                    ))
+                },
+            )?,
+        );
+        self.admin_store.update_circuit(updated_circuit.clone())?;
+
+        self.routing_table_writer
+            .update_circuit(routing_circuit(&updated_circuit))
+            .map_err(|_| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to update circuit in routing table: {}",
+                    circuit_id
+                ))
+            })?;
+
+        let removed_services = existing_circuit
+            .roster()
+            .iter()
+            .filter(|service| {
+                !updated_circuit
+                    .roster()
+                    .iter()
+                    .any(|updated_service| updated_service.service_id() == service.service_id())
+            })
+            .cloned()
+            .collect::<Vec<StoreService>>();
+        self.shutdown_services(&circuit_id, &removed_services)?;
+
+        let mut added_services = circuit_proposal.get_circuit_proposal().clone();
+        let added_roster = added_services
+            .take_roster()
+            .into_iter()
+            .filter(|service| {
+                !existing_circuit
+                    .roster()
+                    .iter()
+                    .any(|existing_service| existing_service.service_id() == service.service_id)
+            })
+            .collect::<Vec<SplinterService>>();
+        added_services.set_roster(RepeatedField::from_vec(added_roster));
+        self.initialize_services(&added_services)?;
+
+        let event = messages::AdminServiceEvent::ProposalAccepted((
+            messages::CircuitProposal::from_proto(circuit_proposal)?,
+            signer_public_key,
+        ));
+        self.send_event(updated_circuit.circuit_management_type(), event);
+
+        info!("roster of circuit {} has been updated", circuit_id);
+        Ok(())
+    }
+
+    /// Apply an application metadata update whose proposal was accepted by all members.
+    ///
+    /// The updated circuit is written to the admin store, and application authorization handlers
+    /// are notified of the new application metadata.
+    #[cfg(feature = "circuit-update")]
+    fn update_circuit_metadata(
+        &mut self,
+        circuit_proposal: CircuitProposal,
+        signer_public_key: Vec<u8>,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit_proposal.get_circuit_id().to_string();
+
+        // a proposal is only stored if it had to wait on votes from other members
+        if self.has_proposal(&circuit_id)? {
+            self.admin_store.remove_proposal(&circuit_id)?;
+        }
+
+        let updated_circuit = StoreCircuit::from(
+            ProposedCircuit::from_proto(circuit_proposal.get_circuit_proposal().clone()).map_err(
+                |err| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to build updated circuit: {}",
+                        err
+                    ))
+                },
+            )?,
+        );
+        self.admin_store.update_circuit(updated_circuit.clone())?;
+
+        let circuit_proposal_proto = messages::CircuitProposal::from_proto(circuit_proposal)?;
+        let event = messages::AdminServiceEvent::ProposalAccepted((
+            circuit_proposal_proto.clone(),
+            signer_public_key,
+        ));
+        self.send_event(updated_circuit.circuit_management_type(), event);
+
+        let event = messages::AdminServiceEvent::CircuitMetadataUpdated(circuit_proposal_proto);
+        self.send_event(updated_circuit.circuit_management_type(), event);
+
+        info!(
+            "application metadata of circuit {} has been updated",
+            circuit_id
+        );
+        Ok(())
+    }
+
+    /// Apply the addition of a node to a circuit whose proposal was accepted by all members.
+    ///
+    /// The existing members update the circuit and add the new node's endpoints to the admin
+    /// store and the routing table. The new node adds the whole circuit, as it did not have it
+    /// before.
+    #[cfg(feature = "circuit-update")]
+    fn add_circuit_node(
+        &mut self,
+        circuit_proposal: CircuitProposal,
+        signer_public_key: Vec<u8>,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit_proposal.get_circuit_id().to_string();
+
+        // a proposal is only stored if it had to wait on votes from other members
+        if self.has_proposal(&circuit_id)? {
+            self.admin_store.remove_proposal(&circuit_id)?;
+        }
+        self.joining_circuits.remove(&circuit_id);
+
+        let proposed_circuit = ProposedCircuit::from_proto(
+            circuit_proposal.get_circuit_proposal().clone(),
+        )
+        .map_err(|err| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to build updated circuit: {}",
+                err
+            ))
+        })?;
+        let nodes = proposed_circuit
+            .members()
+            .iter()
+            .map(CircuitNode::from)
+            .collect::<Vec<CircuitNode>>();
+        let updated_circuit = StoreCircuit::from(proposed_circuit);
+
+        if let Some(existing_circuit) = self.admin_store.get_circuit(&circuit_id)? {
+            let added_nodes = nodes
+                .into_iter()
+                .filter(|node| {
+                    !existing_circuit
+                        .members()
+                        .iter()
+                        .any(|member| member == node.node_id())
+                })
+                .collect::<Vec<CircuitNode>>();
+
+            self.admin_store.update_circuit(updated_circuit.clone())?;
+            self.admin_store.add_nodes(added_nodes.clone())?;
+
+            self.routing_table_writer
+                .update_circuit(routing_circuit(&updated_circuit))
+                .map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to update circuit in routing table: {}",
+                        circuit_id
+                    ))
+                })?;
+            self.routing_table_writer
+                .add_nodes(
+                    added_nodes
+                        .iter()
+                        .map(|node| {
+                            routing::CircuitNode::new(
+                                node.node_id().to_string(),
+                                node.endpoints().to_vec(),
+                            )
+                        })
+                        .collect(),
+                )
+                .map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to add nodes of circuit {} to routing table",
+                        circuit_id
+                    ))
+                })?;
+        } else {
+            let routing_members = nodes
+                .iter()
+                .map(|node| {
+                    routing::CircuitNode::new(node.node_id().to_string(), node.endpoints().to_vec())
+                })
+                .collect::<Vec<routing::CircuitNode>>();
+
+            self.admin_store
+                .add_circuit(updated_circuit.clone(), nodes)?;
+
+            self.routing_table_writer
+                .add_circuit(
+                    circuit_id.clone(),
+                    routing_circuit(&updated_circuit),
+                    routing_members,
+                )
+                .map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to add new circuit to routing table: {}",
+                        circuit_id
+                    ))
+                })?;
+        }
+
+        let event = messages::AdminServiceEvent::ProposalAccepted((
+            messages::CircuitProposal::from_proto(circuit_proposal)?,
+            signer_public_key,
+        ));
+        self.send_event(updated_circuit.circuit_management_type(), event);
+
+        info!("a node has been added to circuit {}", circuit_id);
+        Ok(())
+    }
+
+    /// Apply the removal of a node from a circuit whose proposal was accepted by all members.
+    ///
+    /// The remaining members update the circuit in the admin store and the routing table. The
+    /// removed node removes the circuit, as it is no longer a member.
+    #[cfg(feature = "circuit-update")]
+    fn remove_circuit_node(
+        &mut self,
+        circuit_proposal: CircuitProposal,
+        signer_public_key: Vec<u8>,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit_proposal.get_circuit_id().to_string();
+
+        // a proposal is only stored if it had to wait on votes from other members
+        if self.has_proposal(&circuit_id)? {
+            self.admin_store.remove_proposal(&circuit_id)?;
+        }
+
+        let existing_circuit = self.admin_store.get_circuit(&circuit_id)?.ok_or_else(|| {
+            AdminSharedError::SplinterStateError(format!(
+                "Unable to get circuit that is being updated: {}",
+                circuit_id
+            ))
+        })?;
+
+        let updated_circuit = StoreCircuit::from(
+            ProposedCircuit::from_proto(circuit_proposal.get_circuit_proposal().clone()).map_err(
+                |err| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to build updated circuit: {}",
+                        err
+                    ))
+                },
+            )?,
+        );
+
+        if updated_circuit.members().contains(&self.node_id) {
+            self.admin_store.update_circuit(updated_circuit.clone())?;
+
+            self.routing_table_writer
+                .update_circuit(routing_circuit(&updated_circuit))
+                .map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to update circuit in routing table: {}",
+                        circuit_id
+                    ))
+                })?;
+
+            for member in existing_circuit.members() {
+                if !updated_circuit.members().contains(member) {
+                    self.remove_peer_ref(member);
+                }
+            }
+        } else {
+            self.admin_store.remove_circuit(&circuit_id)?;
+
+            self.routing_table_writer
+                .remove_circuit(&circuit_id)
+                .map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to remove circuit from routing table: {}",
+                        circuit_id
+                    ))
+                })?;
+
+            for member in existing_circuit.members() {
+                if member != &self.node_id {
+                    self.remove_peer_ref(member);
+                }
+            }
+        }
+
+        let event = messages::AdminServiceEvent::ProposalAccepted((
+            messages::CircuitProposal::from_proto(circuit_proposal)?,
+            signer_public_key,
+        ));
+        self.send_event(updated_circuit.circuit_management_type(), event);
+
+        info!("a node has been removed from circuit {}", circuit_id);
+        Ok(())
+    }
+
+    /// Returns the current definition of a circuit, from the admin store or, if this node is
+    /// being added to the circuit, from the definition sent with the proposal.
+    #[cfg(feature = "circuit-update")]
+    fn current_circuit(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Option<ProposedCircuit>, AdminSharedError> {
+        match self.admin_store.get_circuit(circuit_id)? {
+            Some(circuit) => self
+                .proposed_circuit_from_circuit(&circuit)?
+                .build()
+                .map(Some)
+                .map_err(|err| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to build current circuit: {}",
+                        err
+                    ))
+                }),
+            None => Ok(self.joining_circuits.get(circuit_id).cloned()),
+        }
+    }
+
+    /// Removes the peer refs that were added for a proposal to add a node to a circuit, which
+    /// will not be accepted.
+    #[cfg(feature = "circuit-update")]
+    fn remove_add_node_peer_refs(
+        &mut self,
+        circuit_id: &str,
+        members: &[String],
+    ) -> Result<(), AdminSharedError> {
+        self.joining_circuits.remove(circuit_id);
+
+        match self.admin_store.get_circuit(circuit_id)? {
+            Some(circuit) => {
+                for member in members {
+                    if !circuit.members().contains(member) {
+                        self.remove_peer_ref(member);
+                    }
+                }
+            }
+            None => {
+                for member in members {
+                    if member != &self.node_id {
+                        self.remove_peer_ref(member);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the roster of the given circuit with the services of the roster update request
+    /// removed and added.
+    #[cfg(feature = "circuit-update")]
+    fn updated_roster(
+        &self,
+        circuit: &StoreCircuit,
+        update_request: &CircuitUpdateRosterRequest,
+    ) -> Result<Vec<ProposedService>, AdminSharedError> {
+        let mut roster = circuit
+            .roster()
+            .iter()
+            .filter(|service| {
+                !update_request
+                    .get_remove_services()
+                    .iter()
+                    .any(|removed_service| removed_service.get_service_id() == service.service_id())
+            })
+            .map(|service| {
+                ProposedServiceBuilder::new()
+                    .with_service_id(service.service_id())
+                    .with_service_type(service.service_type())
+                    .with_node_id(service.node_id())
+                    .with_arguments(service.arguments())
+                    .build()
+                    .map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to build proposed service: {}",
+                            err
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<ProposedService>, AdminSharedError>>()?;
+
+        for service in update_request.get_add_services() {
+            roster.push(ProposedService::from_proto(service.clone()).map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Unable to build proposed service: {}",
+                    err
+                ))
+            })?);
+        }
+
+        Ok(roster)
+    }
+
+    /// Returns the admin services of the given circuit members, which verify a proposal to change
+    /// the circuit, along with the lowest protocol version agreed upon with them.
+    #[cfg(any(feature = "circuit-disband", feature = "circuit-update"))]
+    fn circuit_change_verifiers(&self, members: &[String]) -> (Vec<String>, u32) {
+        let mut verifiers = vec![];
+        let mut protocol = ADMIN_SERVICE_PROTOCOL_VERSION;
+        for member in members {
+            verifiers.push(admin_service_id(member));
+            // Figure out what protocol version should be used for this proposal
+            if let Some(protocol_version) = self.service_protocols.get(&admin_service_id(member)) {
+                if protocol_version < &protocol {
+                    protocol = *protocol_version
+                }
+            }
+        }
+
+        (verifiers, protocol)
+    }
+
+    /// Creates a proposal to change an existing circuit to the given definition and sets it as
+    /// the pending change, to be verified by the given admin services.
+    #[cfg(any(feature = "circuit-disband", feature = "circuit-update"))]
+    fn set_pending_circuit_change(
+        &mut self,
+        proposal_type: CircuitProposal_ProposalType,
+        circuit: Circuit,
+        header: &CircuitManagementPayload_Header,
+        verifiers: Vec<String>,
+    ) -> Result<(String, CircuitProposal), AdminSharedError> {
+        let mut circuit_proposal = CircuitProposal::new();
+        circuit_proposal.set_proposal_type(proposal_type);
+        circuit_proposal.set_circuit_id(circuit.get_circuit_id().into());
+        circuit_proposal.set_circuit_hash(sha256(&circuit)?);
+        circuit_proposal.set_circuit_proposal(circuit);
+        circuit_proposal.set_requester(header.get_requester().to_vec());
+        circuit_proposal.set_requester_node_id(header.get_requester_node_id().to_string());
+
+        let expected_hash = sha256(&circuit_proposal)?;
+        self.pending_changes = Some(CircuitProposalContext {
+            circuit_proposal: circuit_proposal.clone(),
+            signer_public_key: header.get_requester().to_vec(),
+            action: header.get_action(),
+        });
+        self.current_consensus_verifiers = verifiers;
+
+        Ok((expected_hash, circuit_proposal))
+    }
+
+    /// Creates a `ProposedCircuitBuilder` populated with the definition of an existing circuit,
+    /// including the endpoints of the circuit's members.
+    #[cfg(any(
+        feature = "circuit-abandon",
+        feature = "circuit-disband",
+        feature = "circuit-update"
+    ))]
+    fn proposed_circuit_from_circuit(
+        &self,
+        circuit: &StoreCircuit,
+    ) -> Result<ProposedCircuitBuilder, AdminSharedError> {
+        let members = circuit
+            .members()
+            .iter()
+            .map(|node_id| {
+                let node = self.admin_store.get_node(node_id)?.ok_or_else(|| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Missing node information for {}",
+                        node_id
+                    ))
+                })?;
+
+                ProposedNodeBuilder::new()
+                    .with_node_id(node.node_id())
+                    .with_endpoints(node.endpoints())
+                    .build()
+                    .map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to build proposed node: {}",
+                            err
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<ProposedNode>, AdminSharedError>>()?;
+
+        let roster = circuit
+            .roster()
+            .iter()
+            .map(|service| {
+                ProposedServiceBuilder::new()
+                    .with_service_id(service.service_id())
+                    .with_service_type(service.service_type())
+                    .with_node_id(service.node_id())
+                    .with_arguments(service.arguments())
+                    .build()
+                    .map_err(|err| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to build proposed service: {}",
+                            err
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<ProposedService>, AdminSharedError>>()?;
+
+        let mut builder = ProposedCircuitBuilder::new()
+            .with_circuit_id(circuit.circuit_id())
+            .with_roster(&roster)
+            .with_members(&members)
+            .with_authorization_type(circuit.authorization_type())
+            .with_persistence(circuit.persistence())
+            .with_durability(circuit.durability())
+            .with_routes(circuit.routes())
+            .with_circuit_management_type(circuit.circuit_management_type())
+            .with_circuit_version(circuit.circuit_version())
+            .with_circuit_status(circuit.circuit_status());
+
+        if let Some(application_metadata) = circuit.application_metadata() {
+            builder = builder.with_application_metadata(application_metadata);
+        }
+
+        if let Some(comments) = circuit.comments() {
+            builder = builder.with_comments(comments);
+        }
+
+        if let Some(display_name) = circuit.display_name() {
+            builder = builder.with_display_name(display_name);
+        }
+
+        Ok(builder)
+    }
+
+    pub fn get_circuits(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = StoreCircuit>>, AdminSharedError> {
+        self.admin_store
+            .list_circuits(&Vec::new())
+            .map_err(AdminSharedError::from)
+    }
+
+    pub fn get_nodes(&self) -> Result<BTreeMap<String, CircuitNode>, AdminSharedError> {
+        Ok(self
+            .admin_store
+            .list_nodes()
+            .map_err(AdminSharedError::from)?
+            .into_iter()
+            .map(|node| (node.node_id().into(), node))
+            .collect())
+    }
+
+    fn verify_signature(&self, payload: &CircuitManagementPayload) -> Result<bool, ServiceError> {
+        let header: CircuitManagementPayload_Header =
+            Message::parse_from_bytes(payload.get_header())?;
+
+        let signature = payload.get_signature().to_vec();
+        let public_key = header.get_requester().to_vec();
+
+        self.signature_verifier
+            .verify(
+                &payload.get_header(),
+                &Signature::new(signature),
+                &PublicKey::new(public_key),
+            )
+            .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+    }
+}
+
+/// Converts a circuit from the admin store into a circuit for the routing table.
+#[cfg(feature = "circuit-update")]
+#[allow(clippy::let_and_return)]
+fn routing_circuit(circuit: &StoreCircuit) -> routing::Circuit {
+    let routing_circuit = routing::Circuit::new(
+        circuit.circuit_id().to_string(),
+        circuit
+            .roster()
+            .iter()
+            .map(|service| {
+                routing::Service::new(
+                    service.service_id().to_string(),
+                    service.service_type().to_string(),
+                    service.node_id().to_string(),
+                    service.arguments().to_vec(),
+                )
+            })
+            .collect(),
+        circuit.members().to_vec(),
+    );
+    #[cfg(feature = "challenge-authorization")]
+    let routing_circuit = routing_circuit.with_challenge_authorization(
+        circuit.authorization_type() == &AuthorizationType::Challenge,
+    );
+    #[cfg(feature = "circuit-durability")]
+    let routing_circuit = routing_circuit
+        .with_store_and_forward(circuit.durability() == &DurabilityType::StoreAndForward);
+    routing_circuit
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+    use protobuf::{Message, RepeatedField};
+
+    use diesel::{
+        r2d2::{ConnectionManager as DieselConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    #[cfg(feature = "admin-service-event-store")]
+    use crate::admin::service::event::store::memory::MemoryAdminServiceEventStore;
+
+    use crate::admin::service::AdminKeyVerifierError;
     use crate::admin::store::diesel::DieselAdminServiceStore;
+    #[cfg(any(
+        feature = "circuit-abandon",
+        feature = "circuit-disband",
+        feature = "circuit-update"
+    ))]
+    use crate::admin::store::ProposedCircuit;
     use crate::circuit::routing::memory::RoutingTable;
+    #[cfg(any(
+        feature = "circuit-abandon",
+        feature = "circuit-disband",
+        feature = "circuit-update"
+    ))]
+    use crate::circuit::routing::RoutingTableReader;
     use crate::keys::insecure::AllowAllKeyPermissionManager;
     use crate::mesh::{Envelope, Mesh};
     use crate::migrations::run_sqlite_migrations;
@@ -2022,46 +4560,970 @@ mod tests {
         Transport,
     };
 
-    const PUB_KEY: &[u8] = &[
-        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
-        25, 26, 27, 28, 29, 30, 31, 32,
-    ];
+    const PUB_KEY: &[u8] = &[
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+        25, 26, 27, 28, 29, 30, 31, 32,
+    ];
+
+    /// Test that the CircuitManagementPayload is moved to the pending payloads when the peers are
+    /// fully authorized.
+    #[test]
+    fn test_protocol_agreement() {
+        let mut transport = InprocTransport::default();
+        let mut orchestrator_transport = transport.clone();
+
+        let mut other_listener = transport
+            .listen("inproc://otherplace:8000")
+            .expect("Unable to get listener");
+        let _test_listener = transport
+            .listen("inproc://someplace:8000")
+            .expect("Unable to get listener");
+        let _orchestator_listener = transport
+            .listen("inproc://orchestator")
+            .expect("Unable to get listener");
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
+        let orchestrator_connection = orchestrator_transport
+            .connect("inproc://orchestator")
+            .expect("failed to create connection");
+        let (orchestrator, _) = ServiceOrchestrator::new(vec![], orchestrator_connection, 1, 1, 1)
+            .expect("failed to create orchestrator");
+        let store = setup_admin_service_store();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let mut shared = AdminServiceShared::new(
+            "my_peer_id".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+
+        let service_sender = MockServiceNetworkSender::new();
+        shared.set_network_sender(Some(Box::new(service_sender.clone())));
+
+        let mut circuit = admin::Circuit::new();
+        circuit.set_circuit_id("01234-ABCDE".into());
+        circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
+        circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
+        circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE);
+        circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
+        circuit.set_circuit_management_type("test app auth handler".into());
+        circuit.set_comments("test circuit".into());
+        circuit.set_display_name("test_display".into());
+        circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
+
+        circuit.set_members(protobuf::RepeatedField::from_vec(vec![
+            splinter_node("test-node", &["inproc://someplace:8000".to_string()]),
+            splinter_node("other-node", &["inproc://otherplace:8000".to_string()]),
+        ]));
+        circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
+            splinter_service("0123", "sabre"),
+            splinter_service("ABCD", "sabre"),
+        ]));
+
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+
+        let mut payload = admin::CircuitManagementPayload::new();
+
+        payload.set_signature(Vec::new());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_circuit_create_request(request);
+
+        // start up thread for other node
+        std::thread::spawn(move || {
+            let mesh = Mesh::new(2, 2);
+            let conn = other_listener.accept().unwrap();
+            mesh.add(conn, "my_peer_id".to_string()).unwrap();
+
+            handle_auth(&mesh, "my_peer_id", "other-node");
+
+            mesh.shutdown_signaler().shutdown();
+        });
+
+        shared
+            .propose_circuit(payload, "test".to_string())
+            .expect("Proposal not accepted");
+
+        // None of the proposed members are peered
+        assert_eq!(1, shared.unpeered_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        // Set other-node to peered
+        shared
+            .on_peer_connected("other-node")
+            .expect("Unable to set peer to peered");
+
+        // Still waitin on 1 peer
+        assert_eq!(1, shared.unpeered_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        // Set other-node to peered
+        shared
+            .on_peer_connected("test-node")
+            .expect("Unable to set peer to peered");
+
+        // We're fully peered, but need to wait for protocol to be agreed on
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        shared
+            .on_protocol_agreement("admin::other-node", 1)
+            .expect("received unexpected error");
+
+        // Waiting on 1 node for protocol agreement
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        shared
+            .on_protocol_agreement("admin::test-node", 1)
+            .expect("received unexpected error");
+        // We're fully peered and agreed on protocol, so the pending payload is now available
+        assert_eq!(0, shared.pending_protocol_payloads.len());
+        assert_eq!(1, shared.pending_circuit_payloads.len());
+        shutdown(mesh, cm, pm);
+    }
+
+    /// Test that the CircuitManagementPayload message is dropped, if a node fails to match
+    /// protocol versions
+    #[test]
+    fn test_protocol_disagreement() {
+        let mut transport = InprocTransport::default();
+        let mut orchestrator_transport = transport.clone();
+
+        let _listener = transport
+            .listen("inproc://otherplace:8000")
+            .expect("Unable to get listener");
+        let _admin_listener = transport
+            .listen("inproc://admin-service")
+            .expect("Unable to get listener");
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
+        let orchestrator_connection = orchestrator_transport
+            .connect("inproc://admin-service")
+            .expect("failed to create connection");
+        let (orchestrator, _) = ServiceOrchestrator::new(vec![], orchestrator_connection, 1, 1, 1)
+            .expect("failed to create orchestrator");
+        let store = setup_admin_service_store();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let mut shared = AdminServiceShared::new(
+            "test-node".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+
+        let service_sender = MockServiceNetworkSender::new();
+        shared.set_network_sender(Some(Box::new(service_sender.clone())));
+
+        let mut circuit = admin::Circuit::new();
+        circuit.set_circuit_id("01234-ABCDE".into());
+        circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
+        circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
+        circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE);
+        circuit.set_circuit_management_type("test app auth handler".into());
+        circuit.set_comments("test circuit".into());
+        circuit.set_display_name("test_display".into());
+        circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
+
+        circuit.set_members(protobuf::RepeatedField::from_vec(vec![
+            splinter_node("test-node", &["inproc://someplace:8000".to_string()]),
+            splinter_node("other-node", &["inproc://otherplace:8000".to_string()]),
+        ]));
+        circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
+            splinter_service("0123", "sabre"),
+            splinter_service("ABCD", "sabre"),
+        ]));
+
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+
+        let mut payload = admin::CircuitManagementPayload::new();
+
+        payload.set_signature(Vec::new());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_circuit_create_request(request);
+
+        shared
+            .propose_circuit(payload, "local".to_string())
+            .expect("Proposal not accepted");
+
+        // None of the proposed members are peered
+        assert_eq!(1, shared.unpeered_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        // Set other-node to peered
+        shared
+            .on_peer_connected("other-node")
+            .expect("Unable to set peer to peered");
+
+        assert_eq!(0, shared.unpeered_payloads.len());
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+        shared
+            .on_protocol_agreement("admin::other-node", 0)
+            .expect("received unexpected error");
+
+        // The message should be dropped
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+        assert_eq!(0, shared.pending_protocol_payloads.len());
+
+        // sent the service protocol request but not the payload
+        assert_eq!(
+            1,
+            service_sender
+                .sent
+                .lock()
+                .expect("Network sender lock poisoned")
+                .len()
+        );
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that a valid circuit is validated correctly
+    fn test_validate_circuit_valid() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+
+        if let Err(err) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // Test that a valid circuit on version 2, would fail on protocol 1 because display_name is
+    // set. Protocol 1 should fail any circuit that has display name set, as display name is not
+    // included in the protobuf.
+    fn test_validate_invalid_protocol_display_name() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+
+        if let Ok(()) = admin_shared.validate_create_circuit(&circuit, PUB_KEY, "node_a", 1) {
+            panic!("Should have been invalid because display name is set");
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that a circuit proposed with a key that is not permitted for the requesting node is
+    // invalid
+    fn test_validate_circuit_signer_not_permitted() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to requester node not being registered");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit is proposed by a signer key is not a valid public key the proposal is
+    // invalid
+    fn test_validate_circuit_signer_key_invalid() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let circuit = setup_test_circuit();
+
+        let pub_key = (0u8..50).collect::<Vec<_>>();
+        // too short
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            &pub_key[0..10],
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to key being too short");
+        }
+        // too long
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            &pub_key,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to key being too long");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a service in its roster with an allowed node that is not in
+    // members an error is returned
+    fn test_validate_circuit_bad_node() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+
+        let mut service_bad = SplinterService::new();
+        service_bad.set_service_id("0123".to_string());
+        service_bad.set_service_type("type_a".to_string());
+        service_bad.set_allowed_nodes(RepeatedField::from_vec(vec!["node_bad".to_string()]));
+
+        circuit.set_roster(RepeatedField::from_vec(vec![service_bad]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to service having an allowed node not in members");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a service in its roster with too many allowed nodes
+    fn test_validate_circuit_too_many_allowed_nodes() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+
+        let mut service_bad = SplinterService::new();
+        service_bad.set_service_id("0123".to_string());
+        service_bad.set_service_type("type_a".to_string());
+        service_bad.set_allowed_nodes(RepeatedField::from_vec(vec![
+            "node_b".to_string(),
+            "extra".to_string(),
+        ]));
+
+        circuit.set_roster(RepeatedField::from_vec(vec![service_bad]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to service having too many allowed nodes");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a service with "" for a service id an error is returned
+    fn test_validate_circuit_empty_service_id() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+
+        let mut service_ = SplinterService::new();
+        service_.set_service_id("".to_string());
+        service_.set_service_type("type_a".to_string());
+        service_.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
+
+        circuit.set_roster(RepeatedField::from_vec(vec![service_]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to service's id being empty");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a service with an invalid service id an error is returned
+    fn test_validate_circuit_invalid_service_id() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+
+        let mut service_ = SplinterService::new();
+        service_.set_service_id("invalid_service_id".to_string());
+        service_.set_service_type("type_a".to_string());
+        service_.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
+
+        circuit.set_roster(RepeatedField::from_vec(vec![service_]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to service's id being empty");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a service with duplicate service ids an error is returned
+    fn test_validate_circuit_duplicate_service_id() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+
+        let mut service_a = SplinterService::new();
+        service_a.set_service_id("0123".to_string());
+        service_a.set_service_type("type_a".to_string());
+        service_a.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
+
+        let mut service_a2 = SplinterService::new();
+        service_a2.set_service_id("0123".to_string());
+        service_a2.set_service_type("type_a".to_string());
+        service_a2.set_allowed_nodes(RepeatedField::from_vec(vec!["node_b".to_string()]));
+
+        circuit.set_roster(RepeatedField::from_vec(vec![service_a, service_a2]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to service's id being a duplicate");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit does not have any services in its roster an error is returned
+    fn test_validate_circuit_empty_roster() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+        circuit.set_roster(RepeatedField::from_vec(vec![]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to empty roster");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit does not have any nodes in its members an error is returned
+    fn test_validate_circuit_empty_members() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+
+        circuit.set_members(RepeatedField::from_vec(vec![]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid empty members");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit does not have the local node in the member list an error is
+    // returned
+    fn test_validate_circuit_missing_local_node() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+
+        circuit.set_members(RepeatedField::from_vec(vec![node_b]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_a is not in members");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has a member with node id of "" an error is
+    // returned
+    fn test_validate_circuit_empty_node_id() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+
+        let mut node_ = SplinterNode::new();
+        node_.set_node_id("".to_string());
+        node_.set_endpoints(vec!["test://endpoint_:0".to_string()].into());
+
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b, node_]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_ is has an empty node id");
+        }
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that if a circuit has duplicate members an error is returned
+    fn test_validate_circuit_duplicate_members() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+
+        let mut node_b2 = SplinterNode::new();
+        node_b2.set_node_id("node_b".to_string());
+        node_b2.set_endpoints(vec!["test://endpoint_b2:0".to_string()].into());
+
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b, node_b2]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because there are duplicate members");
+        }
+        shutdown(mesh, cm, pm);
+    }
 
-    /// Test that the CircuitManagementPayload is moved to the pending payloads when the peers are
-    /// fully authorized.
     #[test]
-    fn test_protocol_agreement() {
-        let mut transport = InprocTransport::default();
-        let mut orchestrator_transport = transport.clone();
-
-        let mut other_listener = transport
-            .listen("inproc://otherplace:8000")
-            .expect("Unable to get listener");
-        let _test_listener = transport
-            .listen("inproc://someplace:8000")
-            .expect("Unable to get listener");
-        let _orchestator_listener = transport
-            .listen("inproc://orchestator")
-            .expect("Unable to get listener");
-
-        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
-        let orchestrator_connection = orchestrator_transport
-            .connect("inproc://orchestator")
-            .expect("failed to create connection");
-        let (orchestrator, _) = ServiceOrchestrator::new(vec![], orchestrator_connection, 1, 1, 1)
-            .expect("failed to create orchestrator");
+    // test that if a circuit has an empty circuit id an error is returned
+    fn test_validate_circuit_empty_circuit_id() {
         let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
 
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
-
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let mut shared = AdminServiceShared::new(
-            "my_peer_id".into(),
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
             HashMap::new(),
@@ -2075,128 +5537,185 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
+        let mut circuit = setup_test_circuit();
 
-        let service_sender = MockServiceNetworkSender::new();
-        shared.set_network_sender(Some(Box::new(service_sender.clone())));
-
-        let mut circuit = admin::Circuit::new();
-        circuit.set_circuit_id("01234-ABCDE".into());
-        circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
-        circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
-        circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE);
-        circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
-        circuit.set_circuit_management_type("test app auth handler".into());
-        circuit.set_comments("test circuit".into());
-        circuit.set_display_name("test_display".into());
-        circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
-
-        circuit.set_members(protobuf::RepeatedField::from_vec(vec![
-            splinter_node("test-node", &["inproc://someplace:8000".to_string()]),
-            splinter_node("other-node", &["inproc://otherplace:8000".to_string()]),
-        ]));
-        circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
-            splinter_service("0123", "sabre"),
-            splinter_service("ABCD", "sabre"),
-        ]));
-
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
+        circuit.set_circuit_id("".to_string());
 
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit ID is empty");
+        }
+        shutdown(mesh, cm, pm);
+    }
 
-        let mut payload = admin::CircuitManagementPayload::new();
+    #[test]
+    // test that if a circuit has an invalid circuit id an error is returned
+    fn test_validate_circuit_invalid_circuit_id() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
 
-        payload.set_signature(Vec::new());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        payload.set_circuit_create_request(request);
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
-        // start up thread for other node
-        std::thread::spawn(move || {
-            let mesh = Mesh::new(2, 2);
-            let conn = other_listener.accept().unwrap();
-            mesh.add(conn, "my_peer_id".to_string()).unwrap();
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-            handle_auth(&mesh, "my_peer_id", "other-node");
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
 
-            mesh.shutdown_signaler().shutdown();
-        });
+        circuit.set_circuit_id("invalid_circuit_id".to_string());
 
-        shared
-            .propose_circuit(payload, "test".to_string())
-            .expect("Proposal not accepted");
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit ID is invalid");
+        }
+        shutdown(mesh, cm, pm);
+    }
 
-        // None of the proposed members are peered
-        assert_eq!(1, shared.unpeered_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
+    #[test]
+    // test that if a circuit has a member with no endpoints an error is returned
+    fn test_validate_circuit_no_endpoints() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
 
-        // Set other-node to peered
-        shared
-            .on_peer_connected("other-node")
-            .expect("Unable to set peer to peered");
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
-        // Still waitin on 1 peer
-        assert_eq!(1, shared.unpeered_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        // Set other-node to peered
-        shared
-            .on_peer_connected("test-node")
-            .expect("Unable to set peer to peered");
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
 
-        // We're fully peered, but need to wait for protocol to be agreed on
-        assert_eq!(1, shared.pending_protocol_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
 
-        shared
-            .on_protocol_agreement("admin::other-node", 1)
-            .expect("received unexpected error");
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec![].into());
 
-        // Waiting on 1 node for protocol agreement
-        assert_eq!(1, shared.pending_protocol_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
 
-        shared
-            .on_protocol_agreement("admin::test-node", 1)
-            .expect("received unexpected error");
-        // We're fully peered and agreed on protocol, so the pending payload is now available
-        assert_eq!(0, shared.pending_protocol_payloads.len());
-        assert_eq!(1, shared.pending_circuit_payloads.len());
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because a member has no endpoints");
+        }
         shutdown(mesh, cm, pm);
     }
 
-    /// Test that the CircuitManagementPayload message is dropped, if a node fails to match
-    /// protocol versions
     #[test]
-    fn test_protocol_disagreement() {
-        let mut transport = InprocTransport::default();
-        let mut orchestrator_transport = transport.clone();
+    // test that if a circuit has a member with an empty endpoint an error is returned
+    fn test_validate_circuit_empty_endpoint() {
+        let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
 
-        let _listener = transport
-            .listen("inproc://otherplace:8000")
-            .expect("Unable to get listener");
-        let _admin_listener = transport
-            .listen("inproc://admin-service")
-            .expect("Unable to get listener");
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        #[cfg(feature = "admin-service-event-store")]
+        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            Arc::new(Mutex::new(orchestrator)),
+            #[cfg(feature = "service-arg-validation")]
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            #[cfg(feature = "admin-service-event-store")]
+            memory_event_store,
+        )
+        .unwrap();
+        let mut circuit = setup_test_circuit();
+
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["".to_string()].into());
+
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because a member has an empty endpoint");
+        }
+        shutdown(mesh, cm, pm);
+    }
 
-        let (mesh, cm, pm, peer_connector) = setup_peer_connector(Some(transport));
-        let orchestrator_connection = orchestrator_transport
-            .connect("inproc://admin-service")
-            .expect("failed to create connection");
-        let (orchestrator, _) = ServiceOrchestrator::new(vec![], orchestrator_connection, 1, 1, 1)
-            .expect("failed to create orchestrator");
+    #[test]
+    // test that if a circuit has a member with a duplicate endpoint an error is returned
+    fn test_validate_circuit_duplicate_endpoint() {
         let store = setup_admin_service_store();
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
 
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
-
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let mut shared = AdminServiceShared::new(
-            "test-node".into(),
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
             HashMap::new(),
@@ -2210,80 +5729,32 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
+        let mut circuit = setup_test_circuit();
 
-        let service_sender = MockServiceNetworkSender::new();
-        shared.set_network_sender(Some(Box::new(service_sender.clone())));
-
-        let mut circuit = admin::Circuit::new();
-        circuit.set_circuit_id("01234-ABCDE".into());
-        circuit.set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
-        circuit.set_persistence(admin::Circuit_PersistenceType::ANY_PERSISTENCE);
-        circuit.set_routes(admin::Circuit_RouteType::ANY_ROUTE);
-        circuit.set_circuit_management_type("test app auth handler".into());
-        circuit.set_comments("test circuit".into());
-        circuit.set_display_name("test_display".into());
-        circuit.set_circuit_status(admin::Circuit_CircuitStatus::ACTIVE);
-
-        circuit.set_members(protobuf::RepeatedField::from_vec(vec![
-            splinter_node("test-node", &["inproc://someplace:8000".to_string()]),
-            splinter_node("other-node", &["inproc://otherplace:8000".to_string()]),
-        ]));
-        circuit.set_roster(protobuf::RepeatedField::from_vec(vec![
-            splinter_service("0123", "sabre"),
-            splinter_service("ABCD", "sabre"),
-        ]));
-
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
-
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
-
-        let mut payload = admin::CircuitManagementPayload::new();
-
-        payload.set_signature(Vec::new());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        payload.set_circuit_create_request(request);
-
-        shared
-            .propose_circuit(payload, "local".to_string())
-            .expect("Proposal not accepted");
-
-        // None of the proposed members are peered
-        assert_eq!(1, shared.unpeered_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
-
-        // Set other-node to peered
-        shared
-            .on_peer_connected("other-node")
-            .expect("Unable to set peer to peered");
+        let mut node_a = SplinterNode::new();
+        node_a.set_node_id("node_a".to_string());
+        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
 
-        assert_eq!(0, shared.unpeered_payloads.len());
-        assert_eq!(1, shared.pending_protocol_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
-        shared
-            .on_protocol_agreement("admin::other-node", 0)
-            .expect("received unexpected error");
+        let mut node_b = SplinterNode::new();
+        node_b.set_node_id("node_b".to_string());
+        node_b.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
 
-        // The message should be dropped
-        assert_eq!(0, shared.pending_circuit_payloads.len());
-        assert_eq!(0, shared.pending_protocol_payloads.len());
+        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
 
-        // sent the service protocol request but not the payload
-        assert_eq!(
-            1,
-            service_sender
-                .sent
-                .lock()
-                .expect("Network sender lock poisoned")
-                .len()
-        );
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because a member has a duplicate endpoint");
+        }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that a valid circuit is validated correctly
-    fn test_validate_circuit_valid() {
+    // test that if a circuit does not have authorization set an error is returned
+    fn test_validate_circuit_no_authorization() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2292,7 +5763,6 @@ mod tests {
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
-
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
@@ -2311,25 +5781,24 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let circuit = setup_test_circuit();
+        let mut circuit = setup_test_circuit();
 
-        if let Err(err) = admin_shared.validate_create_circuit(
+        circuit.set_authorization_type(Circuit_AuthorizationType::UNSET_AUTHORIZATION_TYPE);
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
             &circuit,
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been valid: {}", err);
+            panic!("Should have been invalid because authorizaiton type is unset");
         }
-
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // Test that a valid circuit on version 2, would fail on protocol 1 because display_name is
-    // set. Protocol 1 should fail any circuit that has display name set, as display name is not
-    // included in the protobuf.
-    fn test_validate_invalid_protocol_display_name() {
+    // test that if a circuit does not have persistence set an error is returned
+    fn test_validate_circuit_no_persitance() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2338,7 +5807,6 @@ mod tests {
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
-
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
@@ -2357,19 +5825,24 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let circuit = setup_test_circuit();
+        let mut circuit = setup_test_circuit();
 
-        if let Ok(()) = admin_shared.validate_create_circuit(&circuit, PUB_KEY, "node_a", 1) {
-            panic!("Should have been invalid because display name is set");
-        }
+        circuit.set_persistence(Circuit_PersistenceType::UNSET_PERSISTENCE_TYPE);
 
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because persistence type is unset");
+        }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that a circuit proposed with a key that is not permitted for the requesting node is
-    // invalid
-    fn test_validate_circuit_signer_not_permitted() {
+    // test that if a circuit does not have durability set an error is returned
+    fn test_validate_circuit_unset_durability() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2378,7 +5851,6 @@ mod tests {
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
-
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
@@ -2390,14 +5862,16 @@ mod tests {
             peer_connector,
             store,
             signature_verifier,
-            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(MockAdminKeyVerifier::default()),
             Box::new(AllowAllKeyPermissionManager),
             writer,
             #[cfg(feature = "admin-service-event-store")]
             memory_event_store,
         )
         .unwrap();
-        let circuit = setup_test_circuit();
+        let mut circuit = setup_test_circuit();
+
+        circuit.set_durability(Circuit_DurabilityType::UNSET_DURABILITY_TYPE);
 
         if let Ok(_) = admin_shared.validate_create_circuit(
             &circuit,
@@ -2405,15 +5879,14 @@ mod tests {
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to requester node not being registered");
+            panic!("Should have been invalid because durabilty type is unset");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit is proposed by a signer key is not a valid public key the proposal is
-    // invalid
-    fn test_validate_circuit_signer_key_invalid() {
+    // test that if a circuit does not have route type set an error is returned
+    fn test_validate_circuit_no_routes() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2422,7 +5895,6 @@ mod tests {
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
-
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
@@ -2441,34 +5913,24 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let circuit = setup_test_circuit();
+        let mut circuit = setup_test_circuit();
+
+        circuit.set_routes(Circuit_RouteType::UNSET_ROUTE_TYPE);
 
-        let pub_key = (0u8..50).collect::<Vec<_>>();
-        // too short
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            &pub_key[0..10],
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid due to key being too short");
-        }
-        // too long
         if let Ok(_) = admin_shared.validate_create_circuit(
             &circuit,
-            &pub_key,
+            PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to key being too long");
+            panic!("Should have been invalid because route type is unset");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a service in its roster with an allowed node that is not in
-    // members an error is returned
-    fn test_validate_circuit_bad_node() {
+    // test that if a circuit does not have circuit_management_type set an error is returned
+    fn test_validate_circuit_no_management_type() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2497,12 +5959,7 @@ mod tests {
         .unwrap();
         let mut circuit = setup_test_circuit();
 
-        let mut service_bad = SplinterService::new();
-        service_bad.set_service_id("0123".to_string());
-        service_bad.set_service_type("type_a".to_string());
-        service_bad.set_allowed_nodes(RepeatedField::from_vec(vec!["node_bad".to_string()]));
-
-        circuit.set_roster(RepeatedField::from_vec(vec![service_bad]));
+        circuit.set_circuit_management_type("".to_string());
 
         if let Ok(_) = admin_shared.validate_create_circuit(
             &circuit,
@@ -2510,14 +5967,14 @@ mod tests {
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to service having an allowed node not in members");
+            panic!("Should have been invalid because route type is unset");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a service in its roster with too many allowed nodes
-    fn test_validate_circuit_too_many_allowed_nodes() {
+    // test that a valid circuit proposal vote comes back as valid
+    fn test_validate_proposal_vote_valid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2544,32 +6001,25 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
-
-        let mut service_bad = SplinterService::new();
-        service_bad.set_service_id("0123".to_string());
-        service_bad.set_service_type("type_a".to_string());
-        service_bad.set_allowed_nodes(RepeatedField::from_vec(vec![
-            "node_b".to_string(),
-            "extra".to_string(),
-        ]));
-
-        circuit.set_roster(RepeatedField::from_vec(vec![service_bad]));
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Err(err) = admin_shared.validate_circuit_vote(
+            &vote,
             PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to service having too many allowed nodes");
+            panic!("Should have been valid: {}", err);
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a service with "" for a service id an error is returned
-    fn test_validate_circuit_empty_service_id() {
+    // test that if the vote is from a key that is not permitted for the voting node the vote is
+    // invalid
+    fn test_validate_proposal_vote_not_permitted() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2589,36 +6039,31 @@ mod tests {
             peer_connector,
             store,
             signature_verifier,
-            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(MockAdminKeyVerifier::new(false)),
             Box::new(AllowAllKeyPermissionManager),
             writer,
             #[cfg(feature = "admin-service-event-store")]
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
-
-        let mut service_ = SplinterService::new();
-        service_.set_service_id("".to_string());
-        service_.set_service_type("type_a".to_string());
-        service_.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
-
-        circuit.set_roster(RepeatedField::from_vec(vec![service_]));
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
             PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to service's id being empty");
+            panic!("Should have been invalid because voting node is not registered");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a service with an invalid service id an error is returned
-    fn test_validate_circuit_invalid_service_id() {
+    // test if the voter is the original requester node the vote is invalid
+    fn test_validate_proposal_vote_requester() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2627,7 +6072,6 @@ mod tests {
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
-
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
@@ -2646,29 +6090,24 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
-
-        let mut service_ = SplinterService::new();
-        service_.set_service_id("invalid_service_id".to_string());
-        service_.set_service_type("type_a".to_string());
-        service_.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
-
-        circuit.set_roster(RepeatedField::from_vec(vec![service_]));
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
             PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
+            "node_b",
         ) {
-            panic!("Should have been invalid due to service's id being empty");
+            panic!("Should have been invalid because voter is the requester");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a service with duplicate service ids an error is returned
-    fn test_validate_circuit_duplicate_service_id() {
+    // test if a voter has already voted on a proposal the new vote is invalid
+    fn test_validate_proposal_vote_duplicate_vote() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2695,34 +6134,32 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
-
-        let mut service_a = SplinterService::new();
-        service_a.set_service_id("0123".to_string());
-        service_a.set_service_type("type_a".to_string());
-        service_a.set_allowed_nodes(RepeatedField::from_vec(vec!["node_a".to_string()]));
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let mut proposal = setup_test_proposal(&circuit);
 
-        let mut service_a2 = SplinterService::new();
-        service_a2.set_service_id("0123".to_string());
-        service_a2.set_service_type("type_a".to_string());
-        service_a2.set_allowed_nodes(RepeatedField::from_vec(vec!["node_b".to_string()]));
+        let mut vote_record = CircuitProposal_VoteRecord::new();
+        vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
+        vote_record.set_public_key(b"test_signer_a".to_vec());
+        vote_record.set_voter_node_id("node_a".to_string());
 
-        circuit.set_roster(RepeatedField::from_vec(vec![service_a, service_a2]));
+        proposal.set_votes(RepeatedField::from_vec(vec![vote_record]));
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
             PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to service's id being a duplicate");
+            panic!("Should have been invalid because node as already submited a vote");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit does not have any services in its roster an error is returned
-    fn test_validate_circuit_empty_roster() {
+    // test that if the circuit hash in the circuit proposal does not match the circuit hash on
+    // the vote, the vote is invalid
+    fn test_validate_proposal_vote_circuit_hash_mismatch() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2731,7 +6168,6 @@ mod tests {
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
-
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
@@ -2750,35 +6186,42 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
-        circuit.set_roster(RepeatedField::from_vec(vec![]));
+        let circuit = setup_test_circuit();
+        let vote = setup_test_vote(&circuit);
+        let mut proposal = setup_test_proposal(&circuit);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        proposal.set_circuit_hash("bad_hash".to_string());
+
+        if let Ok(_) = admin_shared.validate_circuit_vote(
+            &vote,
             PUB_KEY,
+            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid due to empty roster");
+            panic!("Should have been invalid because the circuit hash does not match");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit does not have any nodes in its members an error is returned
-    fn test_validate_circuit_empty_members() {
+    // test that the validate_circuit_management_payload method returns an error in case the
+    // signature is empty.
+    fn test_validate_circuit_management_payload_signature() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let signature_verifier = Secp256k1Context::new().new_verifier();
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        let signer = context.new_signer(private_key);
+        let signature_verifier = context.new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -2793,37 +6236,53 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_members(RepeatedField::from_vec(vec![]));
+        let circuit = setup_test_circuit();
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid empty members");
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_requester(PUB_KEY.into());
+        header.set_requester_node_id("node_b".to_string());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_signature(Vec::new());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_circuit_create_request(request);
+
+        // Asserting the payload will be deemed invalid as the signature is an empty vec.
+        if let Ok(_) = shared.validate_circuit_management_payload(&payload, &header) {
+            panic!("Should have been invalid due to empty signature");
+        }
+
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        // Asserting the payload passed through validation.
+        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
+            panic!("Should have been valid");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit does not have the local node in the member list an error is
-    // returned
-    fn test_validate_circuit_missing_local_node() {
+    // test that the validate_circuit_management_payload method returns an error in case the
+    // header is empty.
+    fn test_validate_circuit_management_payload_header() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let signature_verifier = Secp256k1Context::new().new_verifier();
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        let signer = context.new_signer(private_key);
+        let signature_verifier = context.new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -2838,41 +6297,54 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
 
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+        let circuit = setup_test_circuit();
 
-        circuit.set_members(RepeatedField::from_vec(vec![node_b]));
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because node_a is not in members");
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_requester(PUB_KEY.into());
+        header.set_requester_node_id("node_b".to_string());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        payload.set_circuit_create_request(request);
+
+        // Asserting the payload will be deemed invalid as the header is empty.
+        match shared.validate_circuit_management_payload(&payload, &header) {
+            Err(err) => assert!(err
+                .to_string()
+                .contains("CircuitManagementPayload header must be set")),
+            _ => panic!("Should have been invalid because empty requester field"),
+        }
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        // Asserting the payload passed through validation, and failed at a further step.
+        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
+            panic!("Should have been valid");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has a member with node id of "" an error is
-    // returned
-    fn test_validate_circuit_empty_node_id() {
+    // test that the validate_circuit_management_payload method returns an error in case the header
+    // requester field is empty.
+    fn test_validate_circuit_management_header_requester() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let signature_verifier = Secp256k1Context::new().new_verifier();
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        let signer = context.new_signer(private_key);
+        let signature_verifier = context.new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -2887,48 +6359,56 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
 
-        let mut node_a = SplinterNode::new();
-        node_a.set_node_id("node_a".to_string());
-        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+        let circuit = setup_test_circuit();
 
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
 
-        let mut node_ = SplinterNode::new();
-        node_.set_node_id("".to_string());
-        node_.set_endpoints(vec!["test://endpoint_:0".to_string()].into());
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_requester_node_id("node_b".to_string());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        payload.set_circuit_create_request(request);
 
-        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b, node_]));
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        // Asserting the payload will be deemed invalid as the header is empty.
+        match shared.validate_circuit_management_payload(&payload, &header) {
+            Err(err) => assert!(err
+                .to_string()
+                .contains("CircuitManagementPayload must have a requester")),
+            _ => panic!("Should have been invalid because empty requester field"),
+        }
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because node_ is has an empty node id");
+        header.set_requester(PUB_KEY.into());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        // Asserting the payload passed through validation, and failed at a further step.
+        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
+            panic!("Should have been valid");
         }
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that if a circuit has duplicate members an error is returned
-    fn test_validate_circuit_duplicate_members() {
+    // test that the CircuitManagementPayload returns an error in case the header requester_node_id
+    // field is empty.
+    fn test_validate_circuit_management_header_requester_node_id() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let signature_verifier = Secp256k1Context::new().new_verifier();
+        let context = Secp256k1Context::new();
+        let private_key = context.new_random_private_key();
+        let signer = context.new_signer(private_key);
+        let signature_verifier = context.new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let admin_shared = AdminServiceShared::new(
+        let shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -2943,36 +6423,41 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
 
-        let mut node_a = SplinterNode::new();
-        node_a.set_node_id("node_a".to_string());
-        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+        let circuit = setup_test_circuit();
 
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec!["test://endpoint_b:0".to_string()].into());
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
 
-        let mut node_b2 = SplinterNode::new();
-        node_b2.set_node_id("node_b".to_string());
-        node_b2.set_endpoints(vec!["test://endpoint_b2:0".to_string()].into());
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_requester(PUB_KEY.into());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        payload.set_circuit_create_request(request);
 
-        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b, node_b2]));
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        // Asserting the payload will be deemed invalid as the header is empty.
+        match shared.validate_circuit_management_payload(&payload, &header) {
+            Err(err) => assert!(err
+                .to_string()
+                .contains("CircuitManagementPayload must have a requester node id")),
+            _ => panic!("Should have been invalid because empty requester field"),
+        }
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because there are duplicate members");
+        header.set_requester_node_id("node_b".to_string());
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        // Asserting the payload passed through validation, and failed at a further step.
+        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
+            panic!("Should have been valid");
         }
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-disband")]
     #[test]
-    // test that if a circuit has an empty circuit id an error is returned
-    fn test_validate_circuit_empty_circuit_id() {
+    // test that a disband request for an active circuit is valid
+    fn test_validate_disband_circuit_valid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -2999,24 +6484,25 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
-
-        circuit.set_circuit_id("".to_string());
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Err(err) = admin_shared.validate_disband_circuit(
+            "01234-ABCDE",
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because the circuit ID is empty");
+            panic!("Should have been valid: {}", err);
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-disband")]
     #[test]
-    // test that if a circuit has an invalid circuit id an error is returned
-    fn test_validate_circuit_invalid_circuit_id() {
+    // test that a disband request is invalid if the circuit does not exist, is not active, or if
+    // protocol 1 is in use
+    fn test_validate_disband_circuit_invalid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3043,24 +6529,39 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
 
-        circuit.set_circuit_id("invalid_circuit_id".to_string());
+        if let Ok(_) = admin_shared.validate_disband_circuit(
+            "01234-ABCDE",
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit does not exist");
+        }
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Disbanded);
+
+        if let Ok(_) = admin_shared.validate_disband_circuit(
+            "01234-ABCDE",
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because the circuit ID is invalid");
+            panic!("Should have been invalid because the circuit is already disbanded");
+        }
+
+        if let Ok(_) = admin_shared.validate_disband_circuit("01234-ABCDE", PUB_KEY, "node_a", 1) {
+            panic!("Should have been invalid because protocol 1 does not support disband");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-disband")]
     #[test]
-    // test that if a circuit has a member with no endpoints an error is returned
-    fn test_validate_circuit_no_endpoints() {
+    // test that committing an accepted disband proposal keeps the circuit in the admin store with
+    // a disbanded status and removes the circuit from the routing table
+    fn test_commit_disband_circuit() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3068,11 +6569,24 @@ mod tests {
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
-        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let admin_shared = AdminServiceShared::new(
+        writer
+            .add_circuit(
+                "01234-ABCDE".to_string(),
+                routing::Circuit::new(
+                    "01234-ABCDE".to_string(),
+                    vec![],
+                    vec!["node_a".to_string(), "node_b".to_string()],
+                ),
+                vec![],
+            )
+            .expect("Unable to add circuit to routing table");
+
+        let mut admin_shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -3087,32 +6601,64 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
+
+        let mut disbanded_circuit = setup_test_circuit();
+        disbanded_circuit.set_circuit_version(CIRCUIT_PROTOCOL_VERSION);
+        disbanded_circuit.set_circuit_status(Circuit_CircuitStatus::DISBANDED);
+
+        let mut proposal = CircuitProposal::new();
+        proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
+        proposal.set_circuit_id(disbanded_circuit.get_circuit_id().into());
+        proposal.set_circuit_hash(sha256(&disbanded_circuit).unwrap());
+        proposal.set_circuit_proposal(disbanded_circuit);
+        proposal.set_requester(PUB_KEY.to_vec());
+        proposal.set_requester_node_id("node_a".to_string());
+        admin_shared
+            .add_proposal(proposal.clone())
+            .expect("Unable to add proposal");
 
-        let mut node_a = SplinterNode::new();
-        node_a.set_node_id("node_a".to_string());
-        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+        let mut vote_record = CircuitProposal_VoteRecord::new();
+        vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
+        vote_record.set_public_key(b"test_signer_b".to_vec());
+        vote_record.set_voter_node_id("node_b".to_string());
+        proposal.set_votes(RepeatedField::from_vec(vec![vote_record]));
 
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec![].into());
+        admin_shared.pending_changes = Some(CircuitProposalContext {
+            circuit_proposal: proposal,
+            action: CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE,
+            signer_public_key: b"test_signer_b".to_vec(),
+        });
 
-        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
+        admin_shared
+            .commit()
+            .expect("Unable to commit disband proposal");
+
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Disbanded circuit was removed from the store");
+        assert_eq!(circuit.circuit_status(), &CircuitStatus::Disbanded);
+
+        assert!(admin_shared
+            .admin_store
+            .get_proposal("01234-ABCDE")
+            .expect("Unable to get proposal")
+            .is_none());
+
+        assert!(reader
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit from routing table")
+            .is_none());
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because a member has no endpoints");
-        }
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-abandon")]
     #[test]
-    // test that if a circuit has a member with an empty endpoint an error is returned
-    fn test_validate_circuit_empty_endpoint() {
+    // test that an abandon request from the local node for an active circuit is valid
+    fn test_validate_abandon_circuit_valid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3139,32 +6685,20 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
-
-        let mut node_a = SplinterNode::new();
-        node_a.set_node_id("node_a".to_string());
-        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
-
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec!["".to_string()].into());
-
-        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because a member has an empty endpoint");
+        if let Err(err) = admin_shared.validate_abandon_circuit("01234-ABCDE", PUB_KEY, "node_a") {
+            panic!("Should have been valid: {}", err);
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-abandon")]
     #[test]
-    // test that if a circuit has a member with a duplicate endpoint an error is returned
-    fn test_validate_circuit_duplicate_endpoint() {
+    // test that an abandon request is invalid if it is made on behalf of another node, or if the
+    // circuit does not exist or is not active
+    fn test_validate_abandon_circuit_invalid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3191,32 +6725,43 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
 
-        let mut node_a = SplinterNode::new();
-        node_a.set_node_id("node_a".to_string());
-        node_a.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+        if let Ok(_) = admin_shared.validate_abandon_circuit("01234-ABCDE", PUB_KEY, "node_a") {
+            panic!("Should have been invalid because the circuit does not exist");
+        }
 
-        let mut node_b = SplinterNode::new();
-        node_b.set_node_id("node_b".to_string());
-        node_b.set_endpoints(vec!["test://endpoint_a:0".to_string()].into());
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        circuit.set_members(RepeatedField::from_vec(vec![node_a, node_b]));
+        if let Ok(_) = admin_shared.validate_abandon_circuit("01234-ABCDE", PUB_KEY, "node_b") {
+            panic!("Should have been invalid because node_b is not the local node");
+        }
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because a member has a duplicate endpoint");
+        let abandoned_circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Circuit was not added to the store")
+            .builder()
+            .with_circuit_status(&CircuitStatus::Abandoned)
+            .build()
+            .expect("Unable to build circuit");
+        admin_shared
+            .admin_store
+            .update_circuit(abandoned_circuit)
+            .expect("Unable to update circuit");
+
+        if let Ok(_) = admin_shared.validate_abandon_circuit("01234-ABCDE", PUB_KEY, "node_a") {
+            panic!("Should have been invalid because the circuit is already abandoned");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-abandon")]
     #[test]
-    // test that if a circuit does not have authorization set an error is returned
-    fn test_validate_circuit_no_authorization() {
+    // test that abandoning a circuit keeps the circuit in the admin store with an abandoned status,
+    // removes the circuit from the routing table and drops any pending proposal for the circuit
+    fn test_abandon_circuit() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3224,11 +6769,24 @@ mod tests {
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
-        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let admin_shared = AdminServiceShared::new(
+        writer
+            .add_circuit(
+                "01234-ABCDE".to_string(),
+                routing::Circuit::new(
+                    "01234-ABCDE".to_string(),
+                    vec![],
+                    vec!["node_a".to_string(), "node_b".to_string()],
+                ),
+                vec![],
+            )
+            .expect("Unable to add circuit to routing table");
+
+        let mut admin_shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -3243,24 +6801,92 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
+        let service_sender = MockServiceNetworkSender::new();
+        admin_shared.set_network_sender(Some(Box::new(service_sender.clone())));
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
+
+        let mut disbanded_circuit = setup_test_circuit();
+        disbanded_circuit.set_circuit_version(CIRCUIT_PROTOCOL_VERSION);
+        disbanded_circuit.set_circuit_status(Circuit_CircuitStatus::DISBANDED);
+
+        let mut proposal = CircuitProposal::new();
+        proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
+        proposal.set_circuit_id(disbanded_circuit.get_circuit_id().into());
+        proposal.set_circuit_hash(sha256(&disbanded_circuit).unwrap());
+        proposal.set_circuit_proposal(disbanded_circuit);
+        proposal.set_requester(PUB_KEY.to_vec());
+        proposal.set_requester_node_id("node_b".to_string());
+        admin_shared
+            .add_proposal(proposal)
+            .expect("Unable to add proposal");
+
+        admin_shared
+            .abandon_circuit("01234-ABCDE", PUB_KEY, "node_a")
+            .expect("Unable to abandon circuit");
+
+        // The other member is notified that the circuit was abandoned
+        let sent = service_sender.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, admin_service_id("node_b"));
+        let admin_message: AdminMessage =
+            Message::parse_from_bytes(&sent[0].1).expect("Unable to parse admin message");
+        assert_eq!(
+            admin_message.get_message_type(),
+            AdminMessage_Type::ABANDONED_CIRCUIT
+        );
+        let abandoned_circuit = admin_message.get_abandoned_circuit();
+        assert_eq!(abandoned_circuit.get_circuit_id(), "01234-ABCDE");
+        assert_eq!(abandoned_circuit.get_member_node_id(), "node_a");
 
-        circuit.set_authorization_type(Circuit_AuthorizationType::UNSET_AUTHORIZATION_TYPE);
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Abandoned circuit was removed from the store");
+        assert_eq!(circuit.circuit_status(), &CircuitStatus::Abandoned);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because authorizaiton type is unset");
+        assert!(admin_shared
+            .admin_store
+            .get_proposal("01234-ABCDE")
+            .expect("Unable to get proposal")
+            .is_none());
+
+        assert!(reader
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit from routing table")
+            .is_none());
+
+        // The event carries an abandon proposal for the circuit in the abandoned state
+        #[cfg(not(feature = "admin-service-event-store"))]
+        {
+            let (_, event) = admin_shared
+                .get_events_since(&SystemTime::UNIX_EPOCH, "test_circuit")
+                .expect("Unable to get events")
+                .last()
+                .expect("No event was sent");
+            match event {
+                messages::AdminServiceEvent::CircuitAbandoned(proposal) => {
+                    assert_eq!(proposal.proposal_type, messages::ProposalType::Abandon);
+                    assert_eq!(
+                        proposal.circuit.circuit_status,
+                        messages::CircuitStatus::Abandoned
+                    );
+                    assert!(proposal.votes.is_empty());
+                    assert_eq!(proposal.requester, PUB_KEY.to_vec());
+                    assert_eq!(proposal.requester_node_id, "node_a");
+                }
+                event => panic!("Unexpected event: {:?}", event),
+            }
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-abandon")]
     #[test]
-    // test that if a circuit does not have persistence set an error is returned
-    fn test_validate_circuit_no_persitance() {
+    // test that a notification that another member abandoned a circuit is only accepted from that
+    // member's admin service, keeps the circuit active and drops any pending proposal for it
+    fn test_handle_abandoned_circuit() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3272,7 +6898,7 @@ mod tests {
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let admin_shared = AdminServiceShared::new(
+        let mut admin_shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -3287,24 +6913,65 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
+
+        let mut disbanded_circuit = setup_test_circuit();
+        disbanded_circuit.set_circuit_version(CIRCUIT_PROTOCOL_VERSION);
+        disbanded_circuit.set_circuit_status(Circuit_CircuitStatus::DISBANDED);
+
+        let mut proposal = CircuitProposal::new();
+        proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
+        proposal.set_circuit_id(disbanded_circuit.get_circuit_id().into());
+        proposal.set_circuit_hash(sha256(&disbanded_circuit).unwrap());
+        proposal.set_circuit_proposal(disbanded_circuit);
+        proposal.set_requester(PUB_KEY.to_vec());
+        proposal.set_requester_node_id("node_a".to_string());
+        admin_shared
+            .add_proposal(proposal)
+            .expect("Unable to add proposal");
+
+        // only node_b's admin service may report that node_b abandoned the circuit
+        assert!(admin_shared
+            .handle_abandoned_circuit("01234-ABCDE", "node_b", &admin_service_id("node_c"))
+            .is_err());
+        // node_a is this node
+        assert!(admin_shared
+            .handle_abandoned_circuit("01234-ABCDE", "node_a", &admin_service_id("node_a"))
+            .is_err());
+        assert!(admin_shared
+            .handle_abandoned_circuit("56789-FGHIJ", "node_b", &admin_service_id("node_b"))
+            .is_err());
+
+        admin_shared
+            .handle_abandoned_circuit("01234-ABCDE", "node_b", &admin_service_id("node_b"))
+            .expect("Unable to handle abandoned circuit");
+
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Circuit was removed from the store");
+        assert_eq!(circuit.circuit_status(), &CircuitStatus::Active);
 
-        circuit.set_persistence(Circuit_PersistenceType::UNSET_PERSISTENCE_TYPE);
+        assert!(admin_shared
+            .admin_store
+            .get_proposal("01234-ABCDE")
+            .expect("Unable to get proposal")
+            .is_none());
+
+        // a repeated notification is ignored
+        admin_shared
+            .handle_abandoned_circuit("01234-ABCDE", "node_b", &admin_service_id("node_b"))
+            .expect("Unable to handle repeated notification");
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because persistence type is unset");
-        }
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test that if a circuit does not have durability set an error is returned
-    fn test_validate_circuit_unset_durability() {
+    // test that a roster update that removes an existing service and adds a new service for a
+    // member of an active circuit is valid
+    fn test_validate_update_roster_valid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3331,24 +6998,28 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        circuit.set_durability(Circuit_DurabilityType::UNSET_DURABILITY_TYPE);
+        let update_request = setup_test_update_roster_request(vec![new_test_service("EFGH")]);
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Err(err) = admin_shared.validate_update_roster(
+            &update_request,
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because durabilty type is unset");
+            panic!("Should have been valid: {}", err);
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test that if a circuit does not have route type set an error is returned
-    fn test_validate_circuit_no_routes() {
+    // test that a roster update is invalid if it removes a service that is not in the roster, adds
+    // a service that is already in the roster, adds a service for a node that is not a member, or
+    // does not change the roster
+    fn test_validate_update_roster_invalid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3375,24 +7046,66 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        circuit.set_routes(Circuit_RouteType::UNSET_ROUTE_TYPE);
+        let mut update_request = setup_test_update_roster_request(vec![new_test_service("EFGH")]);
+        let mut unknown_service = SplinterService::new();
+        unknown_service.set_service_id("WXYZ".to_string());
+        update_request.set_remove_services(RepeatedField::from_vec(vec![unknown_service]));
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
+        if let Ok(_) = admin_shared.validate_update_roster(
+            &update_request,
             PUB_KEY,
             "node_a",
             ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because route type is unset");
+            panic!("Should have been invalid because the removed service is not in the roster");
         }
+
+        let update_request = setup_test_update_roster_request(vec![new_test_service("0123")]);
+
+        if let Ok(_) = admin_shared.validate_update_roster(
+            &update_request,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the added service is already in the roster");
+        }
+
+        let mut service = new_test_service("EFGH");
+        service.set_allowed_nodes(RepeatedField::from_vec(vec!["node_c".to_string()]));
+        let update_request = setup_test_update_roster_request(vec![service]);
+
+        if let Ok(_) = admin_shared.validate_update_roster(
+            &update_request,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_c is not a member of the circuit");
+        }
+
+        let mut update_request = CircuitUpdateRosterRequest::new();
+        update_request.set_circuit_id("01234-ABCDE".to_string());
+
+        if let Ok(_) = admin_shared.validate_update_roster(
+            &update_request,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the roster is not changed");
+        }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test that if a circuit does not have circuit_management_type set an error is returned
-    fn test_validate_circuit_no_management_type() {
+    // test that committing an accepted roster update proposal updates the roster and version of
+    // the circuit in the admin store and updates the services in the routing table
+    fn test_commit_update_roster() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3400,11 +7113,37 @@ mod tests {
         let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
-        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let admin_shared = AdminServiceShared::new(
+        writer
+            .add_circuit(
+                "01234-ABCDE".to_string(),
+                routing::Circuit::new(
+                    "01234-ABCDE".to_string(),
+                    vec![
+                        routing::Service::new(
+                            "0123".to_string(),
+                            "type_a".to_string(),
+                            "node_a".to_string(),
+                            vec![],
+                        ),
+                        routing::Service::new(
+                            "ABCD".to_string(),
+                            "type_a".to_string(),
+                            "node_b".to_string(),
+                            vec![],
+                        ),
+                    ],
+                    vec!["node_a".to_string(), "node_b".to_string()],
+                ),
+                vec![],
+            )
+            .expect("Unable to add circuit to routing table");
+
+        let mut admin_shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -3419,24 +7158,97 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let mut circuit = setup_test_circuit();
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        circuit.set_circuit_management_type("".to_string());
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Circuit was not added to the store");
+        let update_request = setup_test_update_roster_request(vec![new_test_service("EFGH")]);
+        let updated_circuit = admin_shared
+            .proposed_circuit_from_circuit(&circuit)
+            .expect("Unable to get proposed circuit")
+            .with_roster(
+                &admin_shared
+                    .updated_roster(&circuit, &update_request)
+                    .expect("Unable to get updated roster"),
+            )
+            .with_circuit_version(CIRCUIT_PROTOCOL_VERSION + 1)
+            .build()
+            .expect("Unable to build updated circuit")
+            .into_proto();
+
+        let mut proposal = CircuitProposal::new();
+        proposal.set_proposal_type(CircuitProposal_ProposalType::UPDATE_ROSTER);
+        proposal.set_circuit_id(updated_circuit.get_circuit_id().into());
+        proposal.set_circuit_hash(sha256(&updated_circuit).unwrap());
+        proposal.set_circuit_proposal(updated_circuit);
+        proposal.set_requester(PUB_KEY.to_vec());
+        proposal.set_requester_node_id("node_a".to_string());
+        admin_shared
+            .add_proposal(proposal.clone())
+            .expect("Unable to add proposal");
+
+        let mut vote_record = CircuitProposal_VoteRecord::new();
+        vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
+        vote_record.set_public_key(b"test_signer_b".to_vec());
+        vote_record.set_voter_node_id("node_b".to_string());
+        proposal.set_votes(RepeatedField::from_vec(vec![vote_record]));
+
+        admin_shared.pending_changes = Some(CircuitProposalContext {
+            circuit_proposal: proposal,
+            action: CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE,
+            signer_public_key: b"test_signer_b".to_vec(),
+        });
+
+        admin_shared
+            .commit()
+            .expect("Unable to commit roster update proposal");
+
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Updated circuit was removed from the store");
+        assert_eq!(circuit.circuit_version(), CIRCUIT_PROTOCOL_VERSION + 1);
+        let mut service_ids = circuit
+            .roster()
+            .iter()
+            .map(|service| service.service_id().to_string())
+            .collect::<Vec<String>>();
+        service_ids.sort();
+        assert_eq!(service_ids, vec!["0123".to_string(), "EFGH".to_string()]);
+
+        assert!(admin_shared
+            .admin_store
+            .get_proposal("01234-ABCDE")
+            .expect("Unable to get proposal")
+            .is_none());
+
+        assert!(reader
+            .get_service(&routing::ServiceId::new(
+                "01234-ABCDE".to_string(),
+                "ABCD".to_string()
+            ))
+            .expect("Unable to get service from routing table")
+            .is_none());
+        assert!(reader
+            .get_service(&routing::ServiceId::new(
+                "01234-ABCDE".to_string(),
+                "EFGH".to_string()
+            ))
+            .expect("Unable to get service from routing table")
+            .is_some());
 
-        if let Ok(_) = admin_shared.validate_create_circuit(
-            &circuit,
-            PUB_KEY,
-            "node_a",
-            ADMIN_SERVICE_PROTOCOL_VERSION,
-        ) {
-            panic!("Should have been invalid because route type is unset");
-        }
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test that a valid circuit proposal vote comes back as valid
-    fn test_validate_proposal_vote_valid() {
+    // test that a request from a member to add a new node with unique endpoints to an active
+    // circuit is valid
+    fn test_validate_add_node_valid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3463,25 +7275,28 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let proposal = setup_test_proposal(&circuit);
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        if let Err(err) = admin_shared.validate_circuit_vote(
-            &vote,
+        let add_node_request =
+            setup_test_add_node_request("node_c", &["test://endpoint_c:0".to_string()]);
+
+        if let Err(err) = admin_shared.validate_add_node(
+            &add_node_request,
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
             panic!("Should have been valid: {}", err);
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test that if the vote is from a key that is not permitted for the voting node the vote is
-    // invalid
-    fn test_validate_proposal_vote_not_permitted() {
+    // test that adding a node is invalid if the node is already a member, if the node has no
+    // endpoints, if one of its endpoints is used by a member, or if the requester is not a member
+    fn test_validate_add_node_invalid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3501,75 +7316,70 @@ mod tests {
             peer_connector,
             store,
             signature_verifier,
-            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(MockAdminKeyVerifier::default()),
             Box::new(AllowAllKeyPermissionManager),
             writer,
             #[cfg(feature = "admin-service-event-store")]
             memory_event_store,
         )
         .unwrap();
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let proposal = setup_test_proposal(&circuit);
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
-            &vote,
+        let add_node_request =
+            setup_test_add_node_request("node_b", &["test://endpoint_c:0".to_string()]);
+
+        if let Ok(_) = admin_shared.validate_add_node(
+            &add_node_request,
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because voting node is not registered");
+            panic!("Should have been invalid because node_b is already a member");
         }
-        shutdown(mesh, cm, pm);
-    }
 
-    #[test]
-    // test if the voter is the original requester node the vote is invalid
-    fn test_validate_proposal_vote_requester() {
-        let store = setup_admin_service_store();
-        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
-        let orchestrator = setup_orchestrator();
+        let add_node_request = setup_test_add_node_request("node_c", &[]);
 
-        let signature_verifier = Secp256k1Context::new().new_verifier();
+        if let Ok(_) = admin_shared.validate_add_node(
+            &add_node_request,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_c has no endpoints");
+        }
 
-        let table = RoutingTable::default();
-        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
-        #[cfg(feature = "admin-service-event-store")]
-        let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
+        let add_node_request =
+            setup_test_add_node_request("node_c", &["test://endpoint_a:0".to_string()]);
+
+        if let Ok(_) = admin_shared.validate_add_node(
+            &add_node_request,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the endpoint is used by node_a");
+        }
 
-        let admin_shared = AdminServiceShared::new(
-            "node_a".into(),
-            Arc::new(Mutex::new(orchestrator)),
-            #[cfg(feature = "service-arg-validation")]
-            HashMap::new(),
-            peer_connector,
-            store,
-            signature_verifier,
-            Box::new(MockAdminKeyVerifier::default()),
-            Box::new(AllowAllKeyPermissionManager),
-            writer,
-            #[cfg(feature = "admin-service-event-store")]
-            memory_event_store,
-        )
-        .unwrap();
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let proposal = setup_test_proposal(&circuit);
+        let add_node_request =
+            setup_test_add_node_request("node_c", &["test://endpoint_c:0".to_string()]);
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
-            &vote,
+        if let Ok(_) = admin_shared.validate_add_node(
+            &add_node_request,
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
-            "node_b",
+            "node_d",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because voter is the requester");
+            panic!("Should have been invalid because node_d is not a member of the circuit");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test if a voter has already voted on a proposal the new vote is invalid
-    fn test_validate_proposal_vote_duplicate_vote() {
+    // test that a request from a member to remove a node that runs no services on an active
+    // circuit is valid
+    fn test_validate_remove_node_valid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3596,32 +7406,30 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let mut proposal = setup_test_proposal(&circuit);
-
-        let mut vote_record = CircuitProposal_VoteRecord::new();
-        vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
-        vote_record.set_public_key(b"test_signer_a".to_vec());
-        vote_record.set_voter_node_id("node_a".to_string());
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
+        add_test_store_member(&*admin_shared.admin_store, "node_c");
 
-        proposal.set_votes(RepeatedField::from_vec(vec![vote_record]));
+        let mut remove_node_request = CircuitUpdateRemoveNodeRequest::new();
+        remove_node_request.set_circuit_id("01234-ABCDE".to_string());
+        remove_node_request.set_node_id("node_c".to_string());
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
-            &vote,
+        if let Err(err) = admin_shared.validate_remove_node(
+            &remove_node_request,
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because node as already submited a vote");
+            panic!("Should have been valid: {}", err);
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test that if the circuit hash in the circuit proposal does not match the circuit hash on
-    // the vote, the vote is invalid
-    fn test_validate_proposal_vote_circuit_hash_mismatch() {
+    // test that removing a node is invalid if the node is not a member or if the node still runs
+    // services on the circuit
+    fn test_validate_remove_node_invalid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
@@ -3648,42 +7456,54 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
-        let circuit = setup_test_circuit();
-        let vote = setup_test_vote(&circuit);
-        let mut proposal = setup_test_proposal(&circuit);
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        proposal.set_circuit_hash("bad_hash".to_string());
+        let mut remove_node_request = CircuitUpdateRemoveNodeRequest::new();
+        remove_node_request.set_circuit_id("01234-ABCDE".to_string());
+        remove_node_request.set_node_id("node_c".to_string());
 
-        if let Ok(_) = admin_shared.validate_circuit_vote(
-            &vote,
+        if let Ok(_) = admin_shared.validate_remove_node(
+            &remove_node_request,
             PUB_KEY,
-            &StoreProposal::from_proto(proposal).expect("Unable to get proposal"),
             "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
         ) {
-            panic!("Should have been invalid because the circuit hash does not match");
+            panic!("Should have been invalid because node_c is not a member of the circuit");
+        }
+
+        remove_node_request.set_node_id("node_b".to_string());
+
+        if let Ok(_) = admin_shared.validate_remove_node(
+            &remove_node_request,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_b runs service ABCD");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test that the validate_circuit_management_payload method returns an error in case the
-    // signature is empty.
-    fn test_validate_circuit_management_payload_signature() {
+    // test that committing an accepted proposal to add a node updates the members and version of
+    // the circuit in the admin store, and adds the new node to the admin store and the routing
+    // table
+    fn test_commit_add_node() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let context = Secp256k1Context::new();
-        let private_key = context.new_random_private_key();
-        let signer = context.new_signer(private_key);
-        let signature_verifier = context.new_verifier();
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let shared = AdminServiceShared::new(
+        let mut admin_shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -3698,53 +7518,111 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
+
+        let circuit = admin_shared
+            .current_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Circuit was not added to the store");
+        let mut members = circuit.members().to_vec();
+        members.push(ProposedNode::from_proto(splinter_node(
+            "node_c",
+            &["test://endpoint_c:0".to_string()],
+        )));
+        let updated_circuit = circuit
+            .builder()
+            .with_members(&members)
+            .with_circuit_version(CIRCUIT_PROTOCOL_VERSION + 1)
+            .build()
+            .expect("Unable to build updated circuit")
+            .into_proto();
+
+        let mut proposal = CircuitProposal::new();
+        proposal.set_proposal_type(CircuitProposal_ProposalType::ADD_NODE);
+        proposal.set_circuit_id(updated_circuit.get_circuit_id().into());
+        proposal.set_circuit_hash(sha256(&updated_circuit).unwrap());
+        proposal.set_circuit_proposal(updated_circuit);
+        proposal.set_requester(PUB_KEY.to_vec());
+        proposal.set_requester_node_id("node_a".to_string());
+        admin_shared
+            .add_proposal(proposal.clone())
+            .expect("Unable to add proposal");
+
+        let mut vote_records = vec![];
+        for node_id in &["node_b", "node_c"] {
+            let mut vote_record = CircuitProposal_VoteRecord::new();
+            vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
+            vote_record.set_public_key(format!("test_signer_{}", node_id).into_bytes());
+            vote_record.set_voter_node_id(node_id.to_string());
+            vote_records.push(vote_record);
+        }
+        proposal.set_votes(RepeatedField::from_vec(vote_records));
 
-        let circuit = setup_test_circuit();
+        admin_shared.pending_changes = Some(CircuitProposalContext {
+            circuit_proposal: proposal,
+            action: CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE,
+            signer_public_key: b"test_signer_node_c".to_vec(),
+        });
 
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
+        admin_shared
+            .commit()
+            .expect("Unable to commit add node proposal");
 
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
-        header.set_requester(PUB_KEY.into());
-        header.set_requester_node_id("node_b".to_string());
-        let mut payload = admin::CircuitManagementPayload::new();
-        payload.set_signature(Vec::new());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        payload.set_circuit_create_request(request);
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Updated circuit was removed from the store");
+        assert_eq!(circuit.circuit_version(), CIRCUIT_PROTOCOL_VERSION + 1);
+        let mut members = circuit.members().to_vec();
+        members.sort();
+        assert_eq!(
+            members,
+            vec![
+                "node_a".to_string(),
+                "node_b".to_string(),
+                "node_c".to_string()
+            ]
+        );
 
-        // Asserting the payload will be deemed invalid as the signature is an empty vec.
-        if let Ok(_) = shared.validate_circuit_management_payload(&payload, &header) {
-            panic!("Should have been invalid due to empty signature");
-        }
+        let node = admin_shared
+            .admin_store
+            .get_node("node_c")
+            .expect("Unable to get node")
+            .expect("New node was not added to the store");
+        assert_eq!(node.endpoints(), &["test://endpoint_c:0".to_string()]);
+
+        assert!(admin_shared
+            .admin_store
+            .get_proposal("01234-ABCDE")
+            .expect("Unable to get proposal")
+            .is_none());
+
+        assert!(reader
+            .get_node("node_c")
+            .expect("Unable to get node from routing table")
+            .is_some());
 
-        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
-        // Asserting the payload passed through validation.
-        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
-            panic!("Should have been valid");
-        }
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test that the validate_circuit_management_payload method returns an error in case the
-    // header is empty.
-    fn test_validate_circuit_management_payload_header() {
+    // test that an application metadata update from a member of an active circuit that changes
+    // the application metadata is valid
+    fn test_validate_update_application_metadata_valid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let context = Secp256k1Context::new();
-        let private_key = context.new_random_private_key();
-        let signer = context.new_signer(private_key);
-        let signature_verifier = context.new_verifier();
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let shared = AdminServiceShared::new(
+        let admin_shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -3759,54 +7637,39 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        let circuit = setup_test_circuit();
-
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
-
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
-        header.set_requester(PUB_KEY.into());
-        header.set_requester_node_id("node_b".to_string());
-        let mut payload = admin::CircuitManagementPayload::new();
-        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
-        payload.set_circuit_create_request(request);
+        let update_request = setup_test_update_application_metadata_request(b"new_test_data");
 
-        // Asserting the payload will be deemed invalid as the header is empty.
-        match shared.validate_circuit_management_payload(&payload, &header) {
-            Err(err) => assert!(err
-                .to_string()
-                .contains("CircuitManagementPayload header must be set")),
-            _ => panic!("Should have been invalid because empty requester field"),
-        }
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        // Asserting the payload passed through validation, and failed at a further step.
-        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
-            panic!("Should have been valid");
+        if let Err(err) = admin_shared.validate_update_application_metadata(
+            &update_request,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test that the validate_circuit_management_payload method returns an error in case the header
-    // requester field is empty.
-    fn test_validate_circuit_management_header_requester() {
+    // test that an application metadata update is invalid if it does not change the circuit, if
+    // the requester is not a member or if the circuit is not active
+    fn test_validate_update_application_metadata_invalid() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let context = Secp256k1Context::new();
-        let private_key = context.new_random_private_key();
-        let signer = context.new_signer(private_key);
-        let signature_verifier = context.new_verifier();
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let shared = AdminServiceShared::new(
+        let admin_shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -3821,56 +7684,75 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        let circuit = setup_test_circuit();
+        let update_request = setup_test_update_application_metadata_request(b"test_data");
 
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
+        if let Ok(_) = admin_shared.validate_update_application_metadata(
+            &update_request,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit is not changed");
+        }
 
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
-        header.set_requester_node_id("node_b".to_string());
-        let mut payload = admin::CircuitManagementPayload::new();
-        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
-        payload.set_circuit_create_request(request);
+        let update_request = setup_test_update_application_metadata_request(b"new_test_data");
 
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        // Asserting the payload will be deemed invalid as the header is empty.
-        match shared.validate_circuit_management_payload(&payload, &header) {
-            Err(err) => assert!(err
-                .to_string()
-                .contains("CircuitManagementPayload must have a requester")),
-            _ => panic!("Should have been invalid because empty requester field"),
+        if let Ok(_) = admin_shared.validate_update_application_metadata(
+            &update_request,
+            PUB_KEY,
+            "node_c",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because node_c is not a member of the circuit");
         }
 
-        header.set_requester(PUB_KEY.into());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        // Asserting the payload passed through validation, and failed at a further step.
-        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
-            panic!("Should have been valid");
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Circuit was not added to the store");
+        admin_shared
+            .admin_store
+            .update_circuit(
+                circuit
+                    .builder()
+                    .with_circuit_status(&CircuitStatus::Disbanded)
+                    .build()
+                    .expect("Unable to build circuit"),
+            )
+            .expect("Unable to update circuit");
+
+        if let Ok(_) = admin_shared.validate_update_application_metadata(
+            &update_request,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit is disbanded");
         }
+
         shutdown(mesh, cm, pm);
     }
 
+    #[cfg(feature = "circuit-update")]
     #[test]
-    // test that the CircuitManagementPayload returns an error in case the header requester_node_id
-    // field is empty.
-    fn test_validate_circuit_management_header_requester_node_id() {
+    // test that committing an accepted application metadata update proposal updates the
+    // application metadata, comments and version of the circuit in the admin store
+    fn test_commit_update_application_metadata() {
         let store = setup_admin_service_store();
         let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
         let orchestrator = setup_orchestrator();
 
-        let context = Secp256k1Context::new();
-        let private_key = context.new_random_private_key();
-        let signer = context.new_signer(private_key);
-        let signature_verifier = context.new_verifier();
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
         #[cfg(feature = "admin-service-event-store")]
         let memory_event_store = MemoryAdminServiceEventStore::new_boxed();
 
-        let shared = AdminServiceShared::new(
+        let mut admin_shared = AdminServiceShared::new(
             "node_a".into(),
             Arc::new(Mutex::new(orchestrator)),
             #[cfg(feature = "service-arg-validation")]
@@ -3885,37 +7767,176 @@ mod tests {
             memory_event_store,
         )
         .unwrap();
+        setup_test_store_circuit(&*admin_shared.admin_store, &CircuitStatus::Active);
 
-        let circuit = setup_test_circuit();
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Circuit was not added to the store");
+        let mut updated_circuit = admin_shared
+            .proposed_circuit_from_circuit(&circuit)
+            .expect("Unable to get proposed circuit")
+            .with_circuit_version(CIRCUIT_PROTOCOL_VERSION + 1)
+            .build()
+            .expect("Unable to build updated circuit")
+            .into_proto();
+        updated_circuit.set_application_metadata(b"new_test_data".to_vec());
+        updated_circuit.clear_comments();
+
+        let mut proposal = CircuitProposal::new();
+        proposal.set_proposal_type(CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA);
+        proposal.set_circuit_id(updated_circuit.get_circuit_id().into());
+        proposal.set_circuit_hash(sha256(&updated_circuit).unwrap());
+        proposal.set_circuit_proposal(updated_circuit);
+        proposal.set_requester(PUB_KEY.to_vec());
+        proposal.set_requester_node_id("node_a".to_string());
+        admin_shared
+            .add_proposal(proposal.clone())
+            .expect("Unable to add proposal");
 
-        let mut request = admin::CircuitCreateRequest::new();
-        request.set_circuit(circuit);
+        let mut vote_record = CircuitProposal_VoteRecord::new();
+        vote_record.set_vote(CircuitProposalVote_Vote::ACCEPT);
+        vote_record.set_public_key(b"test_signer_b".to_vec());
+        vote_record.set_voter_node_id("node_b".to_string());
+        proposal.set_votes(RepeatedField::from_vec(vec![vote_record]));
 
-        let mut header = admin::CircuitManagementPayload_Header::new();
-        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
-        header.set_requester(PUB_KEY.into());
-        let mut payload = admin::CircuitManagementPayload::new();
-        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
-        payload.set_circuit_create_request(request);
+        admin_shared.pending_changes = Some(CircuitProposalContext {
+            circuit_proposal: proposal,
+            action: CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE,
+            signer_public_key: b"test_signer_b".to_vec(),
+        });
 
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        // Asserting the payload will be deemed invalid as the header is empty.
-        match shared.validate_circuit_management_payload(&payload, &header) {
-            Err(err) => assert!(err
-                .to_string()
-                .contains("CircuitManagementPayload must have a requester node id")),
-            _ => panic!("Should have been invalid because empty requester field"),
-        }
+        admin_shared
+            .commit()
+            .expect("Unable to commit application metadata update proposal");
+
+        let circuit = admin_shared
+            .admin_store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Updated circuit was removed from the store");
+        assert_eq!(circuit.circuit_version(), CIRCUIT_PROTOCOL_VERSION + 1);
+        assert_eq!(
+            circuit.application_metadata(),
+            &Some(b"new_test_data".to_vec())
+        );
+        assert_eq!(circuit.comments(), &None);
+
+        assert!(admin_shared
+            .admin_store
+            .get_proposal("01234-ABCDE")
+            .expect("Unable to get proposal")
+            .is_none());
 
-        header.set_requester_node_id("node_b".to_string());
-        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
-        // Asserting the payload passed through validation, and failed at a further step.
-        if let Err(_) = shared.validate_circuit_management_payload(&payload, &header) {
-            panic!("Should have been valid");
-        }
         shutdown(mesh, cm, pm);
     }
 
+    // create an application metadata update request for the test circuit with the given
+    // application metadata, which keeps the comments of the circuit
+    #[cfg(feature = "circuit-update")]
+    fn setup_test_update_application_metadata_request(
+        application_metadata: &[u8],
+    ) -> CircuitUpdateApplicationMetadataRequest {
+        let mut update_request = CircuitUpdateApplicationMetadataRequest::new();
+        update_request.set_circuit_id("01234-ABCDE".to_string());
+        update_request.set_application_metedata(application_metadata.to_vec());
+        update_request.set_comments("test circuit".to_string());
+        update_request
+    }
+
+    // create a request to add the node with the given ID and endpoints to the test circuit
+    #[cfg(feature = "circuit-update")]
+    fn setup_test_add_node_request(
+        node_id: &str,
+        endpoints: &[String],
+    ) -> CircuitUpdateAddNodeRequest {
+        let mut add_node_request = CircuitUpdateAddNodeRequest::new();
+        add_node_request.set_circuit_id("01234-ABCDE".to_string());
+        add_node_request.set_node(splinter_node(node_id, endpoints));
+        add_node_request
+    }
+
+    // add the node with the given ID as a member of the test circuit in the admin store, without
+    // any services
+    #[cfg(feature = "circuit-update")]
+    fn add_test_store_member(store: &dyn AdminServiceStore, node_id: &str) {
+        let circuit = store
+            .get_circuit("01234-ABCDE")
+            .expect("Unable to get circuit")
+            .expect("Circuit was not added to the store");
+        let mut members = circuit.members().to_vec();
+        members.push(node_id.to_string());
+        let circuit = circuit
+            .builder()
+            .with_members(&members)
+            .build()
+            .expect("Unable to build circuit");
+        store
+            .update_circuit(circuit)
+            .expect("Unable to update circuit");
+
+        let node = CircuitNode::from(ProposedNode::from_proto(splinter_node(
+            node_id,
+            &[format!("test://endpoint_{}:0", node_id)],
+        )));
+        store.add_nodes(vec![node]).expect("Unable to add node");
+    }
+
+    // create a roster update request for the test circuit that removes service ABCD and adds the
+    // given services
+    #[cfg(feature = "circuit-update")]
+    fn setup_test_update_roster_request(
+        add_services: Vec<SplinterService>,
+    ) -> CircuitUpdateRosterRequest {
+        let mut removed_service = SplinterService::new();
+        removed_service.set_service_id("ABCD".to_string());
+
+        let mut update_request = CircuitUpdateRosterRequest::new();
+        update_request.set_circuit_id("01234-ABCDE".to_string());
+        update_request.set_add_services(RepeatedField::from_vec(add_services));
+        update_request.set_remove_services(RepeatedField::from_vec(vec![removed_service]));
+        update_request
+    }
+
+    // create a new service for node_b with the given service ID
+    #[cfg(feature = "circuit-update")]
+    fn new_test_service(service_id: &str) -> SplinterService {
+        let mut service = SplinterService::new();
+        service.set_service_id(service_id.to_string());
+        service.set_service_type("type_a".to_string());
+        service.set_allowed_nodes(RepeatedField::from_vec(vec!["node_b".to_string()]));
+        service
+    }
+
+    // add the test circuit, with the given status, to the admin store
+    #[cfg(any(
+        feature = "circuit-abandon",
+        feature = "circuit-disband",
+        feature = "circuit-update"
+    ))]
+    fn setup_test_store_circuit(store: &dyn AdminServiceStore, status: &CircuitStatus) {
+        let mut circuit = setup_test_circuit();
+        circuit.set_circuit_version(CIRCUIT_PROTOCOL_VERSION);
+
+        let proposed_circuit =
+            ProposedCircuit::from_proto(circuit).expect("Unable to build proposed circuit");
+        let nodes = proposed_circuit
+            .members()
+            .iter()
+            .map(CircuitNode::from)
+            .collect::<Vec<CircuitNode>>();
+        let circuit = StoreCircuit::from(proposed_circuit)
+            .builder()
+            .with_circuit_status(status)
+            .build()
+            .expect("Unable to build circuit");
+
+        store
+            .add_circuit(circuit, nodes)
+            .expect("Unable to add circuit");
+    }
+
     pub fn setup_test_circuit() -> Circuit {
         let mut service_a = SplinterService::new();
         service_a.set_service_id("0123".to_string());
@@ -30,6 +30,8 @@ pub struct Circuit {
     durability: DurabilityType,
     routes: RouteType,
     circuit_management_type: String,
+    application_metadata: Option<Vec<u8>>,
+    comments: Option<String>,
     display_name: Option<String>,
     circuit_version: i32,
     circuit_status: CircuitStatus,
@@ -76,6 +78,16 @@ impl Circuit {
         &self.circuit_management_type
     }
 
+    /// Returns the application metadata of the circuit
+    pub fn application_metadata(&self) -> &Option<Vec<u8>> {
+        &self.application_metadata
+    }
+
+    /// Returns the comments about the circuit
+    pub fn comments(&self) -> &Option<String> {
+        &self.comments
+    }
+
     /// Returns the display name for the circuit
     pub fn display_name(&self) -> &Option<String> {
         &self.display_name
@@ -90,18 +102,39 @@ impl Circuit {
     pub fn circuit_status(&self) -> &CircuitStatus {
         &self.circuit_status
     }
+
+    /// Returns a builder populated with the values of this circuit
+    pub fn builder(&self) -> CircuitBuilder {
+        CircuitBuilder {
+            circuit_id: Some(self.id.clone()),
+            roster: Some(self.roster.clone()),
+            members: Some(self.members.clone()),
+            authorization_type: Some(self.authorization_type.clone()),
+            persistence: Some(self.persistence.clone()),
+            durability: Some(self.durability.clone()),
+            routes: Some(self.routes.clone()),
+            circuit_management_type: Some(self.circuit_management_type.clone()),
+            application_metadata: self.application_metadata.clone(),
+            comments: self.comments.clone(),
+            display_name: self.display_name.clone(),
+            circuit_version: Some(self.circuit_version),
+            circuit_status: Some(self.circuit_status.clone()),
+        }
+    }
 }
 
 /// What type of authorization the circuit requires
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AuthorizationType {
     Trust,
+    Challenge,
 }
 
 impl From<&messages::AuthorizationType> for AuthorizationType {
     fn from(message_enum: &messages::AuthorizationType) -> Self {
         match *message_enum {
             messages::AuthorizationType::Trust => AuthorizationType::Trust,
+            messages::AuthorizationType::Challenge => AuthorizationType::Challenge,
         }
     }
 }
@@ -130,12 +163,16 @@ impl From<&messages::PersistenceType> for PersistenceType {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DurabilityType {
     NoDurability,
+    #[cfg(feature = "circuit-durability")]
+    StoreAndForward,
 }
 
 impl From<&messages::DurabilityType> for DurabilityType {
     fn from(message_enum: &messages::DurabilityType) -> Self {
         match *message_enum {
             messages::DurabilityType::NoDurability => DurabilityType::NoDurability,
+            #[cfg(feature = "circuit-durability")]
+            messages::DurabilityType::StoreAndForward => DurabilityType::StoreAndForward,
         }
     }
 }
@@ -195,6 +232,8 @@ pub struct CircuitBuilder {
     durability: Option<DurabilityType>,
     routes: Option<RouteType>,
     circuit_management_type: Option<String>,
+    application_metadata: Option<Vec<u8>>,
+    comments: Option<String>,
     display_name: Option<String>,
     circuit_version: Option<i32>,
     circuit_status: Option<CircuitStatus>,
@@ -246,6 +285,16 @@ impl CircuitBuilder {
         self.circuit_management_type.clone()
     }
 
+    /// Returns the application metadata in the builder
+    pub fn application_metadata(&self) -> Option<Vec<u8>> {
+        self.application_metadata.clone()
+    }
+
+    /// Returns the comments in the builder
+    pub fn comments(&self) -> Option<String> {
+        self.comments.clone()
+    }
+
     /// Returns the display_name in the builder
     pub fn display_name(&self) -> Option<String> {
         self.display_name.clone()
@@ -344,6 +393,26 @@ impl CircuitBuilder {
         self
     }
 
+    /// Sets the application metadata for the circuit
+    ///
+    /// # Arguments
+    ///
+    ///  * `application_metadata` - The application metadata for the circuit
+    pub fn with_application_metadata(mut self, application_metadata: &[u8]) -> CircuitBuilder {
+        self.application_metadata = Some(application_metadata.into());
+        self
+    }
+
+    /// Sets the comments about the circuit
+    ///
+    /// # Arguments
+    ///
+    ///  * `comments` - The human readable comments about the circuit
+    pub fn with_comments(mut self, comments: &str) -> CircuitBuilder {
+        self.comments = Some(comments.into());
+        self
+    }
+
     /// Sets the display name for the circuit
     ///
     /// # Arguments
@@ -419,6 +488,10 @@ impl CircuitBuilder {
             )
         })?;
 
+        let application_metadata = self.application_metadata;
+
+        let comments = self.comments;
+
         let display_name = self.display_name;
 
         let circuit_version = self.circuit_version.unwrap_or(UNSET_CIRCUIT_VERSION);
@@ -434,6 +507,8 @@ impl CircuitBuilder {
             durability,
             routes,
             circuit_management_type,
+            application_metadata,
+            comments,
             display_name,
             circuit_version,
             circuit_status,
@@ -458,6 +533,8 @@ impl From<ProposedCircuit> for Circuit {
             durability: circuit.durability().clone(),
             routes: circuit.routes().clone(),
             circuit_management_type: circuit.circuit_management_type().into(),
+            application_metadata: circuit.application_metadata().clone(),
+            comments: circuit.comments().clone(),
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: circuit.circuit_status().clone(),
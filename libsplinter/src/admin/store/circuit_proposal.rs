@@ -88,6 +88,10 @@ impl CircuitProposal {
             admin::CircuitProposal_ProposalType::ADD_NODE => ProposalType::AddNode,
             admin::CircuitProposal_ProposalType::REMOVE_NODE => ProposalType::RemoveNode,
             admin::CircuitProposal_ProposalType::DISBAND => ProposalType::Disband,
+            admin::CircuitProposal_ProposalType::ABANDON => ProposalType::Abandon,
+            admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA => {
+                ProposalType::UpdateApplicationMetadata
+            }
             admin::CircuitProposal_ProposalType::UNSET_PROPOSAL_TYPE => {
                 return Err(InvalidStateError::with_message(
                     "unable to build, missing field: `proposal type`".to_string(),
@@ -119,6 +123,10 @@ impl CircuitProposal {
             ProposalType::AddNode => admin::CircuitProposal_ProposalType::ADD_NODE,
             ProposalType::RemoveNode => admin::CircuitProposal_ProposalType::REMOVE_NODE,
             ProposalType::Disband => admin::CircuitProposal_ProposalType::DISBAND,
+            ProposalType::Abandon => admin::CircuitProposal_ProposalType::ABANDON,
+            ProposalType::UpdateApplicationMetadata => {
+                admin::CircuitProposal_ProposalType::UPDATE_APPLICATION_METADATA
+            }
         };
 
         let votes = self
@@ -498,6 +506,9 @@ pub enum ProposalType {
     AddNode,
     RemoveNode,
     Disband,
+    UpdateApplicationMetadata,
+    /// Describes a circuit abandoned by a single node; never voted on
+    Abandon,
 }
 
 impl From<&messages::ProposalType> for ProposalType {
@@ -508,6 +519,10 @@ impl From<&messages::ProposalType> for ProposalType {
             messages::ProposalType::AddNode => ProposalType::AddNode,
             messages::ProposalType::RemoveNode => ProposalType::RemoveNode,
             messages::ProposalType::Disband => ProposalType::Disband,
+            messages::ProposalType::Abandon => ProposalType::Abandon,
+            messages::ProposalType::UpdateApplicationMetadata => {
+                ProposalType::UpdateApplicationMetadata
+            }
         }
     }
 }
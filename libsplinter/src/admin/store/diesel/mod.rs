@@ -32,6 +32,7 @@ use crate::admin::store::{
     CircuitProposal, Service, ServiceId,
 };
 use operations::add_circuit::AdminServiceStoreAddCircuitOperation as _;
+use operations::add_nodes::AdminServiceStoreAddNodesOperation as _;
 use operations::add_proposal::AdminServiceStoreAddProposalOperation as _;
 use operations::get_circuit::AdminServiceStoreFetchCircuitOperation as _;
 use operations::get_node::AdminServiceStoreFetchNodeOperation as _;
@@ -142,6 +143,10 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
             .upgrade_proposal_to_circuit(circuit_id)
     }
 
+    fn add_nodes(&self, nodes: Vec<CircuitNode>) -> Result<(), AdminServiceStoreError> {
+        AdminServiceStoreOperations::new(&*self.connection_pool.get()?).add_nodes(nodes)
+    }
+
     fn get_node(&self, node_id: &str) -> Result<Option<CircuitNode>, AdminServiceStoreError> {
         AdminServiceStoreOperations::new(&*self.connection_pool.get()?).get_node(node_id)
     }
@@ -231,6 +236,10 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
             .upgrade_proposal_to_circuit(circuit_id)
     }
 
+    fn add_nodes(&self, nodes: Vec<CircuitNode>) -> Result<(), AdminServiceStoreError> {
+        AdminServiceStoreOperations::new(&*self.connection_pool.get()?).add_nodes(nodes)
+    }
+
     fn get_node(&self, node_id: &str) -> Result<Option<CircuitNode>, AdminServiceStoreError> {
         AdminServiceStoreOperations::new(&*self.connection_pool.get()?).get_node(node_id)
     }
@@ -264,12 +273,7 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
 pub mod tests {
     use super::*;
 
-    use crate::admin::store::{
-        CircuitBuilder, CircuitNodeBuilder, CircuitProposal, CircuitProposalBuilder, CircuitStatus,
-        ProposalType, ProposedCircuitBuilder, ProposedNodeBuilder, ProposedServiceBuilder,
-        ServiceBuilder, Vote, VoteRecordBuilder,
-    };
-    use crate::hex::parse_hex;
+    use crate::admin::store::tests as store_tests;
     use crate::migrations::run_sqlite_migrations;
 
     use diesel::{
@@ -283,544 +287,82 @@ pub mod tests {
         create_connection_pool_and_migrate();
     }
 
-    /// Verify that a proposal can be added to the store correctly and then fetched from the store
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a proposal
-    /// 4. Add proposal to store
-    /// 5. Fetch Proposal from store
-    /// 6. Validate fetched proposal is the same as the proposal added
     #[test]
     fn test_add_get_proposals() {
         let pool = create_connection_pool_and_migrate();
-
-        let store = DieselAdminServiceStore::new(pool);
-
-        let proposal = create_proposal();
-
-        store
-            .add_proposal(proposal.clone())
-            .expect("Unable to add circuit proposal");
-
-        let fetched_proposal = store
-            .get_proposal("WBKLF-BBBBB")
-            .expect("Unable to get proposal")
-            .expect("Got None when expecting proposal");
-
-        assert_eq!(proposal, fetched_proposal);
+        store_tests::test_add_get_proposals(&DieselAdminServiceStore::new(pool));
     }
 
-    /// Verify that list_proposals works correctly
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a proposal
-    /// 4. Add proposal to store
-    /// 5. List Proposal from store with no predicates, validate added proposal is returned
-    /// 6. List Proposal from store with management type predicate, validate added proposal is
-    ///    returned
-    /// 7. List Proposal from store with member predicate, validate added proposal is
-    ///    returned
-    /// 8. List Proposal from store with mismatching management type predicate, validate no
-    ///    proposals are returned
     #[test]
     fn test_list_proposals() {
         let pool = create_connection_pool_and_migrate();
-
-        let store = DieselAdminServiceStore::new(pool);
-
-        let proposal = create_proposal();
-
-        store
-            .add_proposal(proposal.clone())
-            .expect("Unable to add circuit proposal");
-
-        // test no predicates
-        let mut proposals = store
-            .list_proposals(&vec![])
-            .expect("Unable to list proposals");
-
-        assert_eq!(proposals.next(), Some(proposal.clone()));
-        assert_eq!(proposals.next(), None);
-
-        // test management type predicate
-        let mut proposals = store
-            .list_proposals(&vec![CircuitPredicate::ManagementTypeEq(
-                "gameroom".to_string(),
-            )])
-            .expect("Unable to list proposals with management type predicate");
-
-        assert_eq!(proposals.next(), Some(proposal.clone()));
-        assert_eq!(proposals.next(), None);
-
-        // test management type predicate
-        let mut proposals = store
-            .list_proposals(&vec![CircuitPredicate::ManagementTypeEq(
-                "arcade".to_string(),
-            )])
-            .expect("Unable to list proposals with management type predicate");
-
-        assert_eq!(proposals.next(), None);
-
-        let extra_proposal = create_extra_proposal();
-
-        store
-            .add_proposal(extra_proposal.clone())
-            .expect("Unable to add circuit proposal");
-
-        // test management type predicate
-        let mut proposals = store
-            .list_proposals(&vec![CircuitPredicate::MembersInclude(vec![
-                "gumbo-node-000".to_string(),
-            ])])
-            .expect("Unable to list proposals with members include predicate");
-
-        assert_eq!(proposals.next(), Some(extra_proposal));
-        assert_eq!(proposals.next(), None);
-
-        let proposals = store
-            .list_proposals(&vec![])
-            .expect("Unable to list proposals with members include predicate");
-
-        assert_eq!(proposals.len(), 2);
+        store_tests::test_list_proposals(&DieselAdminServiceStore::new(pool));
     }
 
-    /// Verify that a proposal can be removed from the store
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a proposal
-    /// 4. Add proposal to store
-    /// 5. Fetch Proposal from store
-    /// 6. Validate fetched proposal is the same as the proposal added
-    /// 7. Remove proposal
-    /// 8. Validate the proposal was removed
     #[test]
     fn test_remove_proposals() {
         let pool = create_connection_pool_and_migrate();
-
-        let store = DieselAdminServiceStore::new(pool);
-
-        let proposal = create_proposal();
-
-        store
-            .add_proposal(proposal.clone())
-            .expect("Unable to add circuit proposal");
-
-        let fetched_proposal = store
-            .get_proposal("WBKLF-BBBBB")
-            .expect("Unable to get proposal")
-            .expect("Got None when expecting proposal");
-
-        assert_eq!(proposal, fetched_proposal);
-
-        store
-            .remove_proposal("WBKLF-BBBBB")
-            .expect("Unable to add circuit proposal");
-
-        let fetched_proposal = store
-            .get_proposal("WBKLF-BBBBB")
-            .expect("Unable to get proposal");
-
-        assert_eq!(None, fetched_proposal);
+        store_tests::test_remove_proposals(&DieselAdminServiceStore::new(pool));
     }
 
-    /// Verify that a proposal can be added to the store correctly and then updated from the store
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a proposal
-    /// 4. Add proposal to store
-    /// 5. Fetch Proposal from store
-    /// 6. Validate fetched proposal is the same as the proposal added
-    /// 7. Update proposal to have a new vote and call update
-    /// 8. Fetch Proposal from store
-    /// 9. Validate fetched proposal now matches the updated proposal
     #[test]
     fn test_update_proposals() {
         let pool = create_connection_pool_and_migrate();
-
-        let store = DieselAdminServiceStore::new(pool);
-
-        let proposal = create_proposal();
-
-        store
-            .add_proposal(proposal.clone())
-            .expect("Unable to add circuit proposal");
-
-        let fetched_proposal = store
-            .get_proposal("WBKLF-BBBBB")
-            .expect("Unable to get proposal")
-            .expect("Got None when expecting proposal");
-
-        assert_eq!(proposal, fetched_proposal);
-
-        let updated_proposal = proposal
-            .builder()
-            .with_votes(&vec![VoteRecordBuilder::new()
-                .with_public_key(
-                    &parse_hex(
-                        "035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550",
-                    )
-                    .unwrap(),
-                )
-                .with_vote(&Vote::Accept)
-                .with_voter_node_id("bubba-node-000")
-                .build()
-                .expect("Unable to build vote record")])
-            .build()
-            .expect("Unable to build updated proposal");
-
-        store
-            .update_proposal(updated_proposal.clone())
-            .expect("Unable to update proposal");
-
-        let fetched_proposal = store
-            .get_proposal("WBKLF-BBBBB")
-            .expect("Unable to get proposal")
-            .expect("Got None when expecting proposal");
-
-        assert_eq!(updated_proposal, fetched_proposal);
+        store_tests::test_update_proposals(&DieselAdminServiceStore::new(pool));
     }
 
-    /// Verify that a proposal can be upgraded to a circuit
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a proposal
-    /// 4. Add proposal to store
-    /// 5. Fetch Proposal from store
-    /// 6. Validate fetched proposal is the same as the proposal added
-    /// 7. Call upgrade_proposal_to_circuit for the proposal
-    /// 8. Fetch the new circuit and validate it is as expected
     #[test]
     fn test_upgrade_proposals() {
         let pool = create_connection_pool_and_migrate();
-
-        let store = DieselAdminServiceStore::new(pool);
-
-        let proposal = create_proposal();
-
-        store
-            .add_proposal(proposal.clone())
-            .expect("Unable to add circuit proposal");
-
-        let fetched_proposal = store
-            .get_proposal("WBKLF-BBBBB")
-            .expect("Unable to get proposal")
-            .expect("Got None when expecting proposal");
-
-        assert_eq!(proposal, fetched_proposal);
-
-        store
-            .upgrade_proposal_to_circuit("WBKLF-BBBBB")
-            .expect("Unable to add circuit proposal");
-
-        assert!(store
-            .get_proposal("WBKLF-BBBBB")
-            .expect("Unable to get proposal")
-            .is_none());
-
-        let fetched_circuit = store
-            .get_circuit("WBKLF-BBBBB")
-            .expect("Unable to get circuit")
-            .expect("Got None when expecting circuit");
-
-        assert_eq!(create_circuit(), fetched_circuit);
+        store_tests::test_upgrade_proposals(&DieselAdminServiceStore::new(pool));
     }
 
-    /// Verify that a circuit can be added to the store correctly and then fetched from the store
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a circuit and nodes
-    /// 4. Add circuit and nodes to store
-    /// 5. Fetch Circuit from store
-    /// 6. Validate fetched circuit is the same as the circuit added
-    /// 7. Fetch CircuitNode from store
-    /// 8. Validate fetched node is the same as the node added
     #[test]
     fn test_add_get_circuit_and_nodes() {
         let pool = create_connection_pool_and_migrate();
-
-        let store = DieselAdminServiceStore::new(pool);
-
-        let circuit = create_circuit();
-
-        let nodes = create_nodes();
-
-        store
-            .add_circuit(circuit.clone(), nodes)
-            .expect("Unable to add circuit");
-
-        let fetched_circuit = store
-            .get_circuit("WBKLF-BBBBB")
-            .expect("Unable to get circuit")
-            .expect("Got None when expecting circuit");
-
-        let fetched_node = store
-            .get_node("bubba-node-000")
-            .expect("Unable to get node")
-            .expect("Got None when expecting node");
-
-        assert_eq!(circuit, fetched_circuit);
-        assert_eq!(
-            fetched_node,
-            CircuitNodeBuilder::default()
-                .with_node_id("bubba-node-000".into())
-                .with_endpoints(&vec!["tcps://splinterd-node-bubba:8044".into()])
-                .build()
-                .expect("Unable to build node"),
-        )
+        store_tests::test_add_get_circuit_and_nodes(&DieselAdminServiceStore::new(pool));
     }
 
-    /// Verify that list_circuits works correctly
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a circuit and nodes
-    /// 4. Add circuit to store
-    /// 5. List circuits from store with no predicates, validate added circuit is returned
-    /// 6. List circuits from store with management type predicate, validate added circuit is
-    ///    returned
-    /// 7. List circuits from store with member predicate, validate added circuit is
-    ///    returned
-    /// 8. List circuits from store with mismatching management type predicate, validate no
-    ///    circuits are returned
     #[test]
     fn test_list_circuits() {
         let pool = create_connection_pool_and_migrate();
-
-        let store = DieselAdminServiceStore::new(pool);
-
-        let circuit = create_circuit();
-        let nodes = create_nodes();
-
-        let extra_circuit = create_extra_circuit();
-        let extra_nodes = create_extra_nodes();
-
-        store
-            .add_circuit(circuit.clone(), nodes)
-            .expect("Unable to add circuit");
-
-        // test no predicates
-        let mut circuits = store
-            .list_circuits(&vec![])
-            .expect("Unable to list circuits");
-
-        assert_eq!(circuits.next(), Some(circuit.clone()));
-        assert_eq!(circuits.next(), None);
-
-        // test management type predicate
-        let mut circuits = store
-            .list_circuits(&vec![CircuitPredicate::ManagementTypeEq(
-                "gameroom".to_string(),
-            )])
-            .expect("Unable to list circuits with management type predicate");
-
-        assert_eq!(circuits.next(), Some(circuit.clone()));
-        assert_eq!(circuits.next(), None);
-
-        // test bad management type predicate
-        let mut circuits = store
-            .list_circuits(&vec![CircuitPredicate::ManagementTypeEq(
-                "arcade".to_string(),
-            )])
-            .expect("Unable to list circuits with management type predicate");
-
-        assert_eq!(circuits.next(), None);
-
-        store
-            .add_circuit(extra_circuit.clone(), extra_nodes)
-            .expect("Unable to add circuit");
-
-        // test members type predicate
-        let mut circuits = store
-            .list_circuits(&vec![CircuitPredicate::MembersInclude(vec![
-                "gumbo-node-000".to_string(),
-            ])])
-            .expect("Unable to list circuits with members include predicate");
-
-        assert_eq!(circuits.next(), Some(extra_circuit));
-        assert_eq!(circuits.next(), None);
-
-        // show all circuits are returned
-        let circuits = store
-            .list_circuits(&vec![])
-            .expect("Unable to list circuits with members include predicate");
-
-        assert_eq!(circuits.len(), 2);
+        store_tests::test_list_circuits(&DieselAdminServiceStore::new(pool));
     }
 
-    /// Verify that a circuit can be removed from the store
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a circuit
-    /// 4. Add circuit to store
-    /// 5. Fetch circuit from store
-    /// 6. Validate fetched circuit is the same as the proposal added
-    /// 7. Remove circuit
-    /// 8. Validate the circuit was removed
     #[test]
     fn test_remove_circuits() {
         let pool = create_connection_pool_and_migrate();
+        store_tests::test_remove_circuits(&DieselAdminServiceStore::new(pool));
+    }
 
-        let store = DieselAdminServiceStore::new(pool);
-
-        let circuit = create_circuit();
-        let nodes = create_nodes();
-
-        store
-            .add_circuit(circuit.clone(), nodes)
-            .expect("Unable to add circuit");
-
-        let fetched_circuit = store
-            .get_circuit("WBKLF-BBBBB")
-            .expect("Unable to get circuit")
-            .expect("Got None when expecting circuit");
-
-        assert_eq!(circuit, fetched_circuit);
-
-        store
-            .remove_circuit("WBKLF-BBBBB")
-            .expect("Unable to add circuit");
-
-        let fetched_circuit = store
-            .get_circuit("WBKLF-BBBBB")
-            .expect("Unable to get circuit");
-
-        assert_eq!(None, fetched_circuit);
+    #[test]
+    fn test_update_circuit() {
+        let pool = create_connection_pool_and_migrate();
+        store_tests::test_update_circuit(&DieselAdminServiceStore::new(pool));
     }
 
-    /// Verify that a service can be fetched from the store
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a circuit
-    /// 4. Add circuit to store
-    /// 5. Fetch circuit from store
-    /// 6. fetch a service from the store
     #[test]
     fn test_get_service() {
         let pool = create_connection_pool_and_migrate();
-
-        let store = DieselAdminServiceStore::new(pool);
-
-        let circuit = create_circuit();
-        let nodes = create_nodes();
-
-        store
-            .add_circuit(circuit.clone(), nodes)
-            .expect("Unable to add circuit");
-
-        let fetched_circuit = store
-            .get_circuit("WBKLF-BBBBB")
-            .expect("Unable to get circuit")
-            .expect("Got None when expecting circuit");
-
-        assert_eq!(circuit, fetched_circuit);
-
-        let service_id = ServiceId::new("WBKLF-BBBBB".to_string(), "a000".to_string());
-        let fetched_service = store
-            .get_service(&service_id)
-            .expect("Unable to get service")
-            .expect("Got None when expecting service");
-
-        assert_eq!(fetched_circuit.roster()[0], fetched_service);
+        store_tests::test_get_service(&DieselAdminServiceStore::new(pool));
     }
 
-    /// Verify that all service from a circuit can be listed from the store
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a circuit
-    /// 4. Add circuit to store
-    /// 5. Fetch circuit from store
-    /// 6. List all service from the circuit
     #[test]
     fn test_list_service() {
         let pool = create_connection_pool_and_migrate();
-
-        let store = DieselAdminServiceStore::new(pool);
-
-        let circuit = create_circuit();
-        let nodes = create_nodes();
-
-        store
-            .add_circuit(circuit.clone(), nodes)
-            .expect("Unable to add circuit");
-
-        let fetched_circuit = store
-            .get_circuit("WBKLF-BBBBB")
-            .expect("Unable to get circuit")
-            .expect("Got None when expecting circuit");
-
-        assert_eq!(circuit, fetched_circuit);
-
-        let mut services = store
-            .list_services("WBKLF-BBBBB")
-            .expect("Unable to get services");
-
-        assert!(fetched_circuit
-            .roster()
-            .contains(&services.next().expect("Unable to get service")));
-
-        assert!(fetched_circuit
-            .roster()
-            .contains(&services.next().expect("Unable to get service")));
-
-        assert_eq!(None, services.next());
+        store_tests::test_list_service(&DieselAdminServiceStore::new(pool));
     }
 
-    /// Verify that all nodes can be listed from the store
-    ///
-    /// 1. Run sqlite migrations
-    /// 2. Create DieselAdminServiceStore
-    /// 3. Create a circuit and nodes
-    /// 4. Add circuit and nodes to store
-    /// 5. Fetch circuit from store
-    /// 6. List all nodes from the store
     #[test]
     fn test_list_nodes() {
         let pool = create_connection_pool_and_migrate();
+        store_tests::test_list_nodes(&DieselAdminServiceStore::new(pool));
+    }
 
-        let store = DieselAdminServiceStore::new(pool);
-
-        let circuit = create_circuit();
-        let nodes = create_nodes();
-
-        store
-            .add_circuit(circuit.clone(), nodes)
-            .expect("Unable to add circuit");
-
-        let fetched_circuit = store
-            .get_circuit("WBKLF-BBBBB")
-            .expect("Unable to get circuit")
-            .expect("Got None when expecting circuit");
-
-        assert_eq!(circuit, fetched_circuit);
-
-        let mut nodes = store.list_nodes().expect("Unable to get services");
-
-        assert!(fetched_circuit.members().contains(
-            &nodes
-                .next()
-                .expect("Unable to get service")
-                .node_id()
-                .to_string()
-        ));
-
-        assert!(fetched_circuit.members().contains(
-            &nodes
-                .next()
-                .expect("Unable to get service")
-                .node_id()
-                .to_string()
-        ));
-
-        assert!(nodes.next().is_none());
+    #[test]
+    fn test_add_nodes() {
+        let pool = create_connection_pool_and_migrate();
+        store_tests::test_add_nodes(&DieselAdminServiceStore::new(pool));
     }
 
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
@@ -838,245 +380,4 @@ pub mod tests {
 
         pool
     }
-
-    fn create_proposal() -> CircuitProposal {
-        CircuitProposalBuilder::default()
-            .with_proposal_type(&ProposalType::Create)
-            .with_circuit_id("WBKLF-BBBBB")
-            .with_circuit_hash(
-                "7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d")
-            .with_circuit(
-                &ProposedCircuitBuilder::default()
-                    .with_circuit_id("WBKLF-BBBBB")
-                    .with_roster(&vec![
-                        ProposedServiceBuilder::default()
-                            .with_service_id("a000")
-                            .with_service_type("scabbard")
-                            .with_node_id(&"acme-node-000")
-                            .with_arguments(&vec![
-                                ("peer_services".into(), "[\"a001\"]".into()),
-                                ("admin_keys".into(),
-                               "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
-                            ])
-                            .build().expect("Unable to build service"),
-                        ProposedServiceBuilder::default()
-                            .with_service_id("a001")
-                            .with_service_type("scabbard")
-                            .with_node_id(&"bubba-node-000")
-                            .with_arguments(&vec![
-                                ("peer_services".into(), "[\"a000\"]".into()),
-                                ("admin_keys".into(),
-                               "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
-                            ])
-                            .build().expect("Unable to build service")
-                        ])
-
-                    .with_members(
-                        &vec![
-                        ProposedNodeBuilder::default()
-                            .with_node_id("bubba-node-000".into())
-                            .with_endpoints(
-                                &vec!["tcps://splinterd-node-bubba:8044".into(),
-                                      "tcps://splinterd-node-bubba-2:8044".into()])
-                            .build().expect("Unable to build node"),
-                        ProposedNodeBuilder::default()
-                            .with_node_id("acme-node-000".into())
-                            .with_endpoints(&vec!["tcps://splinterd-node-acme:8044".into()])
-                            .build().expect("Unable to build node"),
-                        ]
-                    )
-                    .with_circuit_version(3)
-                    .with_application_metadata(b"test")
-                    .with_comments("This is a test")
-                    .with_circuit_management_type("gameroom")
-                    .with_display_name("test_display")
-                    .build()
-                    .expect("Unable to build circuit")
-            )
-            .with_requester(
-                &parse_hex(
-                    "0283a14e0a17cb7f665311e9b5560f4cde2b502f17e2d03223e15d90d9318d7482").unwrap())
-            .with_requester_node_id("acme-node-000")
-            .with_votes(&vec![VoteRecordBuilder::new()
-                .with_public_key(
-                    &parse_hex(
-                        "035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550",
-                    )
-                    .unwrap(),
-                )
-                .with_vote(&Vote::Accept)
-                .with_voter_node_id("bubba-node-000")
-                .build()
-                .expect("Unable to build vote record"),
-                VoteRecordBuilder::new()
-                    .with_public_key(
-                        &parse_hex(
-                            "035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550",
-                        )
-                        .unwrap(),
-                    )
-                    .with_vote(&Vote::Accept)
-                    .with_voter_node_id("bubba-node-002")
-                    .build()
-                    .expect("Unable to build vote record")]
-            )
-            .build().expect("Unable to build proposals")
-    }
-
-    fn create_extra_proposal() -> CircuitProposal {
-        CircuitProposalBuilder::default()
-            .with_proposal_type(&ProposalType::Create)
-            .with_circuit_id("WBKLF-AAAAA")
-            .with_circuit_hash(
-                "7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d")
-            .with_circuit(
-                &ProposedCircuitBuilder::default()
-                    .with_circuit_id("WBKLF-AAAAA")
-                    .with_roster(&vec![
-                        ProposedServiceBuilder::default()
-                            .with_service_id("a000")
-                            .with_service_type("scabbard")
-                            .with_node_id(&"acme-node-000")
-                            .with_arguments(&vec![
-                                ("peer_services".into(), "[\"a001\"]".into()),
-                                ("admin_keys".into(),
-                               "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
-                            ])
-                            .build().expect("Unable to build service"),
-                        ProposedServiceBuilder::default()
-                            .with_service_id("a001")
-                            .with_service_type("scabbard")
-                            .with_node_id(&"gumbo-node-000")
-                            .with_arguments(&vec![
-                                ("peer_services".into(), "[\"a000\"]".into()),
-                                ("admin_keys".into(),
-                               "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
-                            ])
-                            .build().expect("Unable to build service")
-                        ])
-
-                    .with_members(
-                        &vec![
-                        ProposedNodeBuilder::default()
-                            .with_node_id("gumbo-node-000".into())
-                            .with_endpoints(&vec!["tcps://splinterd-node-gumbo:8044".into()])
-                            .build().expect("Unable to build node"),
-                        ProposedNodeBuilder::default()
-                            .with_node_id("acme-node-000".into())
-                            .with_endpoints(&vec!["tcps://splinterd-node-acme:8044".into()])
-                            .build().expect("Unable to build node"),
-                        ]
-                    )
-                    .with_circuit_management_type("gameroom")
-                    .with_circuit_status(&CircuitStatus::Active)
-                    .build().expect("Unable to build circuit")
-            )
-            .with_requester(
-                &parse_hex(
-                    "0283a14e0a17cb7f665311e9b5560f4cde2b502f17e2d03223e15d90d9318d7482").unwrap())
-            .with_requester_node_id("acme-node-000")
-            .build().expect("Unable to build proposals")
-    }
-
-    fn create_circuit() -> Circuit {
-        CircuitBuilder::default()
-            .with_circuit_id("WBKLF-BBBBB")
-            .with_roster(&vec![
-                ServiceBuilder::default()
-                    .with_service_id("a000")
-                    .with_service_type("scabbard")
-                    .with_node_id("acme-node-000")
-                    .with_arguments(&vec![
-                        ("peer_services".into(), "[\"a001\"]".into()),
-                        ("admin_keys".into(),
-                       "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
-                    ])
-                    .build()
-                    .expect("Unable to build service"),
-                ServiceBuilder::default()
-                    .with_service_id("a001")
-                    .with_service_type("scabbard")
-                    .with_node_id("bubba-node-000")
-                    .with_arguments(&vec![
-                        ("peer_services".into(), "[\"a000\"]".into()),
-                        ("admin_keys".into(),
-                       "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
-                    ])
-                    .build()
-                    .expect("Unable to build service"),
-            ])
-            .with_members(&vec!["bubba-node-000".into(), "acme-node-000".into()])
-            .with_circuit_management_type("gameroom")
-            .with_display_name("test_display")
-            .with_circuit_version(3)
-            .with_circuit_status(&CircuitStatus::Active)
-            .build()
-            .expect("Unable to build circuit")
-    }
-
-    fn create_extra_circuit() -> Circuit {
-        CircuitBuilder::default()
-            .with_circuit_id("WBKLF-CCCCC")
-            .with_roster(&vec![
-                ServiceBuilder::default()
-                    .with_service_id("a000")
-                    .with_service_type("scabbard")
-                    .with_node_id("acme-node-000")
-                    .with_arguments(&vec![
-                        ("admin_keys".into(),
-                       "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]"
-                            .into()),
-                       ("peer_services".into(), "[\"a001\"]".into()),
-                    ])
-                    .build()
-                    .expect("Unable to build service"),
-                ServiceBuilder::default()
-                    .with_service_id("a001")
-                    .with_service_type("scabbard")
-                    .with_node_id("gumbo-node-000")
-                    .with_arguments(&vec![(
-                        "admin_keys".into(),
-                        "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]"
-                            .into()
-                    ),(
-                        "peer_services".into(), "[\"a000\"]".into()
-                    )])
-                    .build()
-                    .expect("Unable to build service"),
-            ])
-            .with_members(&vec!["gumbo-node-000".into(), "acme-node-000".into()])
-            .with_circuit_management_type("other")
-            .build()
-            .expect("Unable to build circuit")
-    }
-
-    fn create_nodes() -> Vec<CircuitNode> {
-        vec![
-            CircuitNodeBuilder::default()
-                .with_node_id("bubba-node-000".into())
-                .with_endpoints(&vec!["tcps://splinterd-node-bubba:8044".into()])
-                .build()
-                .expect("Unable to build node"),
-            CircuitNodeBuilder::default()
-                .with_node_id("acme-node-000".into())
-                .with_endpoints(&vec!["tcps://splinterd-node-acme:8044".into()])
-                .build()
-                .expect("Unable to build node"),
-        ]
-    }
-
-    fn create_extra_nodes() -> Vec<CircuitNode> {
-        vec![
-            CircuitNodeBuilder::default()
-                .with_node_id("gumbo-node-000".into())
-                .with_endpoints(&vec!["tcps://splinterd-node-gumbo:8044".into()])
-                .build()
-                .expect("Unable to build node"),
-            CircuitNodeBuilder::default()
-                .with_node_id("acme-node-000".into())
-                .with_endpoints(&vec!["tcps://splinterd-node-acme:8044".into()])
-                .build()
-                .expect("Unable to build node"),
-        ]
-    }
 }
@@ -405,6 +405,8 @@ pub struct CircuitModel {
     pub display_name: Option<String>,
     pub circuit_version: i32,
     pub circuit_status: CircuitStatusModel,
+    pub application_metadata: Option<Vec<u8>>,
+    pub comments: Option<String>,
 }
 
 impl From<&Circuit> for CircuitModel {
@@ -419,6 +421,8 @@ impl From<&Circuit> for CircuitModel {
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: CircuitStatusModel::from(circuit.circuit_status()),
+            application_metadata: circuit.application_metadata().clone(),
+            comments: circuit.comments().clone(),
         }
     }
 }
@@ -500,6 +504,8 @@ impl TryFrom<String> for ProposalType {
             "AddNode" => Ok(ProposalType::AddNode),
             "RemoveNode" => Ok(ProposalType::RemoveNode),
             "Disband" => Ok(ProposalType::Disband),
+            "Abandon" => Ok(ProposalType::Abandon),
+            "UpdateApplicationMetadata" => Ok(ProposalType::UpdateApplicationMetadata),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message("Unable to convert string to ProposalType".into()),
             )),
@@ -515,6 +521,8 @@ impl From<&ProposalType> for String {
             ProposalType::AddNode => String::from("AddNode"),
             ProposalType::RemoveNode => String::from("RemoveNode"),
             ProposalType::Disband => String::from("Disband"),
+            ProposalType::Abandon => String::from("Abandon"),
+            ProposalType::UpdateApplicationMetadata => String::from("UpdateApplicationMetadata"),
         }
     }
 }
@@ -524,6 +532,7 @@ impl TryFrom<String> for AuthorizationType {
     fn try_from(variant: String) -> Result<Self, Self::Error> {
         match variant.as_ref() {
             "Trust" => Ok(AuthorizationType::Trust),
+            "Challenge" => Ok(AuthorizationType::Challenge),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message(
                     "Unable to convert string to AuthorizationType".into(),
@@ -537,6 +546,7 @@ impl From<&AuthorizationType> for String {
     fn from(variant: &AuthorizationType) -> Self {
         match variant {
             AuthorizationType::Trust => String::from("Trust"),
+            AuthorizationType::Challenge => String::from("Challenge"),
         }
     }
 }
@@ -568,6 +578,8 @@ impl TryFrom<String> for DurabilityType {
     fn try_from(variant: String) -> Result<Self, Self::Error> {
         match variant.as_ref() {
             "NoDurability" => Ok(DurabilityType::NoDurability),
+            #[cfg(feature = "circuit-durability")]
+            "StoreAndForward" => Ok(DurabilityType::StoreAndForward),
             _ => Err(AdminServiceStoreError::InvalidStateError(
                 InvalidStateError::with_message(
                     "Unable to convert string to DurabilityType".into(),
@@ -581,6 +593,8 @@ impl From<&DurabilityType> for String {
     fn from(variant: &DurabilityType) -> Self {
         match variant {
             DurabilityType::NoDurability => String::from("NoDurability"),
+            #[cfg(feature = "circuit-durability")]
+            DurabilityType::StoreAndForward => String::from("StoreAndForward"),
         }
     }
 }
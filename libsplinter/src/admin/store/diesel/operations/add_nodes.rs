@@ -0,0 +1,97 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "add nodes" operation for the `DieselAdminServiceStore`.
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use super::AdminServiceStoreOperations;
+
+use crate::admin::store::{
+    diesel::{models::NodeEndpointModel, schema::node_endpoint},
+    error::AdminServiceStoreError,
+    CircuitNode,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreAddNodesOperation {
+    fn add_nodes(&self, nodes: Vec<CircuitNode>) -> Result<(), AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreAddNodesOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_nodes(&self, nodes: Vec<CircuitNode>) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            for node in nodes.iter() {
+                // Only insert the `node_endpoint` entries of nodes that do not already have
+                // associated entries
+                if let Some(0) = node_endpoint::table
+                    .filter(node_endpoint::node_id.eq(node.node_id()))
+                    .count()
+                    .first(self.conn)
+                    .optional()?
+                {
+                    let endpoints = node
+                        .endpoints()
+                        .iter()
+                        .map(|endpoint| NodeEndpointModel {
+                            node_id: node.node_id().into(),
+                            endpoint: endpoint.into(),
+                        })
+                        .collect::<Vec<NodeEndpointModel>>();
+                    insert_into(node_endpoint::table)
+                        .values(endpoints)
+                        .execute(self.conn)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreAddNodesOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_nodes(&self, nodes: Vec<CircuitNode>) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            for node in nodes.iter() {
+                // Only insert the `node_endpoint` entries of nodes that do not already have
+                // associated entries
+                if let Some(0) = node_endpoint::table
+                    .filter(node_endpoint::node_id.eq(node.node_id()))
+                    .count()
+                    .first(self.conn)
+                    .optional()?
+                {
+                    let endpoints = node
+                        .endpoints()
+                        .iter()
+                        .map(|endpoint| NodeEndpointModel {
+                            node_id: node.node_id().into(),
+                            endpoint: endpoint.into(),
+                        })
+                        .collect::<Vec<NodeEndpointModel>>();
+                    insert_into(node_endpoint::table)
+                        .values(endpoints)
+                        .execute(self.conn)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
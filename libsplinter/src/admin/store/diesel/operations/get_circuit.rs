@@ -85,6 +85,16 @@ where
                 builder = builder.with_display_name(&display_name);
             }
 
+            // if application metadata is set, add to builder
+            if let Some(application_metadata) = circuit.application_metadata {
+                builder = builder.with_application_metadata(&application_metadata);
+            }
+
+            // if comments are set, add to builder
+            if let Some(comments) = circuit.comments {
+                builder = builder.with_comments(&comments);
+            }
+
             Ok(Some(
                 builder
                     .build()
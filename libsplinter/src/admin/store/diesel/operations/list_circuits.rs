@@ -222,6 +222,13 @@ where
                     if let Some(display_name) = &model.display_name {
                         circuit_builder = circuit_builder.with_display_name(&display_name);
                     }
+                    if let Some(application_metadata) = &model.application_metadata {
+                        circuit_builder =
+                            circuit_builder.with_application_metadata(&application_metadata);
+                    }
+                    if let Some(comments) = &model.comments {
+                        circuit_builder = circuit_builder.with_comments(&comments);
+                    }
                     if let Some(members) = circuit_members.get_mut(&model.circuit_id) {
                         members.sort_by_key(|member| member.position);
                         circuit_builder = circuit_builder.with_members(
@@ -15,6 +15,7 @@
 //! Provides database operations for the `DieselAdminServiceStore`.
 
 pub(super) mod add_circuit;
+pub(super) mod add_nodes;
 pub(super) mod add_proposal;
 pub(super) mod get_circuit;
 pub(super) mod get_node;
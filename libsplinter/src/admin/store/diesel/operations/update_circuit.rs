@@ -61,6 +61,11 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::durability.eq(circuit_model.durability),
                     circuit::routes.eq(circuit_model.routes),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
+                    circuit::display_name.eq(circuit_model.display_name),
+                    circuit::circuit_version.eq(circuit_model.circuit_version),
+                    circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::application_metadata.eq(circuit_model.application_metadata),
+                    circuit::comments.eq(circuit_model.comments),
                 ))
                 .execute(self.conn)?;
             // Delete existing data associated with the `Circuit`
@@ -71,6 +76,10 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     .filter(service_argument::circuit_id.eq(circuit.circuit_id())),
             )
             .execute(self.conn)?;
+            delete(
+                circuit_member::table.filter(circuit_member::circuit_id.eq(circuit.circuit_id())),
+            )
+            .execute(self.conn)?;
             // Insert new data associate with the `Circuit`
             let services: Vec<ServiceModel> = Vec::try_from(&circuit)?;
             insert_into(service::table)
@@ -115,6 +124,11 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::durability.eq(circuit_model.durability),
                     circuit::routes.eq(circuit_model.routes),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
+                    circuit::display_name.eq(circuit_model.display_name),
+                    circuit::circuit_version.eq(circuit_model.circuit_version),
+                    circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::application_metadata.eq(circuit_model.application_metadata),
+                    circuit::comments.eq(circuit_model.comments),
                 ))
                 .execute(self.conn)?;
             // Delete existing data associated with the `Circuit`
@@ -125,6 +139,10 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     .filter(service_argument::circuit_id.eq(circuit.circuit_id())),
             )
             .execute(self.conn)?;
+            delete(
+                circuit_member::table.filter(circuit_member::circuit_id.eq(circuit.circuit_id())),
+            )
+            .execute(self.conn)?;
             // Insert new data associate with the `Circuit`
             let services: Vec<ServiceModel> = Vec::try_from(&circuit)?;
             insert_into(service::table)
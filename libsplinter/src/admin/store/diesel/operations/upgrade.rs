@@ -73,6 +73,14 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 builder = builder.with_display_name(display_name);
             }
 
+            if let Some(application_metadata) = proposed_circuit.application_metadata() {
+                builder = builder.with_application_metadata(application_metadata);
+            }
+
+            if let Some(comments) = proposed_circuit.comments() {
+                builder = builder.with_comments(comments);
+            }
+
             let circuit = builder
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError)?;
@@ -135,6 +143,14 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 builder = builder.with_display_name(display_name);
             }
 
+            if let Some(application_metadata) = proposed_circuit.application_metadata() {
+                builder = builder.with_application_metadata(application_metadata);
+            }
+
+            if let Some(comments) = proposed_circuit.comments() {
+                builder = builder.with_comments(comments);
+            }
+
             let circuit = builder
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError)?;
@@ -116,6 +116,8 @@ table! {
         display_name -> Nullable<Text>,
         circuit_version -> Integer,
         circuit_status -> SmallInt,
+        application_metadata -> Nullable<Binary>,
+        comments -> Nullable<Text>,
     }
 }
 
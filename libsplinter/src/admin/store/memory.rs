@@ -0,0 +1,390 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines a memory-backed implementation of the `AdminServiceStore`.
+//!
+//! The public interface includes the struct [`MemoryAdminServiceStore`].
+//!
+//! [`MemoryAdminServiceStore`]: struct.MemoryAdminServiceStore.html
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::{
+    ConstraintViolationError, ConstraintViolationType, InternalError, InvalidStateError,
+};
+
+use super::{
+    AdminServiceStore, AdminServiceStoreError, Circuit, CircuitNode, CircuitPredicate,
+    CircuitProposal, Service, ServiceId,
+};
+
+/// The circuits, proposals and nodes held by the store
+#[derive(Default)]
+struct MemoryAdminServiceState {
+    circuits: BTreeMap<String, Circuit>,
+    proposals: BTreeMap<String, CircuitProposal>,
+    nodes: BTreeMap<String, CircuitNode>,
+}
+
+/// A memory-backed implementation of the `AdminServiceStore`.
+///
+/// The state is lost when the store is dropped, so this store is only suitable for testing and
+/// for deployments that do not need to keep circuits across restarts.
+#[derive(Clone, Default)]
+pub struct MemoryAdminServiceStore {
+    state: Arc<Mutex<MemoryAdminServiceState>>,
+}
+
+impl MemoryAdminServiceStore {
+    /// Creates a new, empty `MemoryAdminServiceStore`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock_state(&self) -> Result<MutexGuard<MemoryAdminServiceState>, AdminServiceStoreError> {
+        self.state.lock().map_err(|_| {
+            AdminServiceStoreError::InternalError(InternalError::with_message(
+                "Memory admin service store's internal lock was poisoned".to_string(),
+            ))
+        })
+    }
+}
+
+impl AdminServiceStore for MemoryAdminServiceStore {
+    fn add_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+        let mut state = self.lock_state()?;
+
+        if state.proposals.contains_key(proposal.circuit_id()) {
+            return Err(AdminServiceStoreError::ConstraintViolationError(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::Unique),
+            ));
+        }
+
+        state
+            .proposals
+            .insert(proposal.circuit_id().to_string(), proposal);
+        Ok(())
+    }
+
+    fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+        let mut state = self.lock_state()?;
+
+        if !state.proposals.contains_key(proposal.circuit_id()) {
+            return Err(AdminServiceStoreError::InvalidStateError(
+                InvalidStateError::with_message(format!(
+                    "A proposal with ID {} does not exist",
+                    proposal.circuit_id()
+                )),
+            ));
+        }
+
+        state
+            .proposals
+            .insert(proposal.circuit_id().to_string(), proposal);
+        Ok(())
+    }
+
+    fn remove_proposal(&self, proposal_id: &str) -> Result<(), AdminServiceStoreError> {
+        self.lock_state()?
+            .proposals
+            .remove(proposal_id)
+            .map(|_| ())
+            .ok_or_else(|| {
+                AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(format!(
+                    "A proposal with ID {} does not exist",
+                    proposal_id
+                )))
+            })
+    }
+
+    fn get_proposal(
+        &self,
+        proposal_id: &str,
+    ) -> Result<Option<CircuitProposal>, AdminServiceStoreError> {
+        Ok(self.lock_state()?.proposals.get(proposal_id).cloned())
+    }
+
+    fn list_proposals(
+        &self,
+        predicates: &[CircuitPredicate],
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        // Proposals are listed in descending order of their IDs, as the database stores do
+        let proposals = self
+            .lock_state()?
+            .proposals
+            .values()
+            .rev()
+            .filter(|proposal| {
+                predicates
+                    .iter()
+                    .all(|predicate| predicate.apply_to_proposals(proposal))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(proposals.into_iter()))
+    }
+
+    fn add_circuit(
+        &self,
+        circuit: Circuit,
+        nodes: Vec<CircuitNode>,
+    ) -> Result<(), AdminServiceStoreError> {
+        let mut state = self.lock_state()?;
+
+        if state.circuits.contains_key(circuit.circuit_id()) {
+            return Err(AdminServiceStoreError::ConstraintViolationError(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::Unique),
+            ));
+        }
+
+        add_nodes(&mut state, nodes);
+        state
+            .circuits
+            .insert(circuit.circuit_id().to_string(), circuit);
+        Ok(())
+    }
+
+    fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError> {
+        let mut state = self.lock_state()?;
+
+        if !state.circuits.contains_key(circuit.circuit_id()) {
+            return Err(AdminServiceStoreError::InvalidStateError(
+                InvalidStateError::with_message(format!(
+                    "A circuit with ID {} does not exist",
+                    circuit.circuit_id()
+                )),
+            ));
+        }
+
+        state
+            .circuits
+            .insert(circuit.circuit_id().to_string(), circuit);
+        Ok(())
+    }
+
+    fn remove_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        let mut state = self.lock_state()?;
+
+        let circuit = state.circuits.remove(circuit_id).ok_or_else(|| {
+            AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(format!(
+                "A circuit with ID {} does not exist",
+                circuit_id
+            )))
+        })?;
+
+        // Remove the nodes that are no longer a member of any circuit
+        for node_id in circuit.members() {
+            if !state
+                .circuits
+                .values()
+                .any(|circuit| circuit.members().contains(node_id))
+            {
+                state.nodes.remove(node_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError> {
+        Ok(self.lock_state()?.circuits.get(circuit_id).cloned())
+    }
+
+    fn list_circuits(
+        &self,
+        predicates: &[CircuitPredicate],
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        // Circuits are listed in descending order of their IDs, as the database stores do
+        let circuits = self
+            .lock_state()?
+            .circuits
+            .values()
+            .rev()
+            .filter(|circuit| {
+                predicates
+                    .iter()
+                    .all(|predicate| predicate.apply_to_circuit(circuit))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(circuits.into_iter()))
+    }
+
+    fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        let mut state = self.lock_state()?;
+
+        let proposal = state.proposals.remove(circuit_id).ok_or_else(|| {
+            AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(format!(
+                "A circuit proposal with ID {} does not exist",
+                circuit_id
+            )))
+        })?;
+
+        let nodes = proposal
+            .circuit()
+            .members()
+            .iter()
+            .cloned()
+            .map(CircuitNode::from)
+            .collect();
+        add_nodes(&mut state, nodes);
+
+        let circuit = Circuit::from(proposal.circuit().clone());
+        state
+            .circuits
+            .insert(circuit.circuit_id().to_string(), circuit);
+        Ok(())
+    }
+
+    fn add_nodes(&self, nodes: Vec<CircuitNode>) -> Result<(), AdminServiceStoreError> {
+        add_nodes(&mut *self.lock_state()?, nodes);
+        Ok(())
+    }
+
+    fn get_node(&self, node_id: &str) -> Result<Option<CircuitNode>, AdminServiceStoreError> {
+        Ok(self.lock_state()?.nodes.get(node_id).cloned())
+    }
+
+    fn list_nodes(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitNode>>, AdminServiceStoreError> {
+        let nodes = self
+            .lock_state()?
+            .nodes
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(nodes.into_iter()))
+    }
+
+    fn get_service(
+        &self,
+        service_id: &ServiceId,
+    ) -> Result<Option<Service>, AdminServiceStoreError> {
+        Ok(self
+            .lock_state()?
+            .circuits
+            .get(service_id.circuit())
+            .and_then(|circuit| {
+                circuit
+                    .roster()
+                    .iter()
+                    .find(|service| service.service_id() == service_id.service_id())
+                    .cloned()
+            }))
+    }
+
+    fn list_services(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Service>>, AdminServiceStoreError> {
+        let services = self
+            .lock_state()?
+            .circuits
+            .get(circuit_id)
+            .map(|circuit| circuit.roster().to_vec())
+            .unwrap_or_default();
+
+        Ok(Box::new(services.into_iter()))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
+        Box::new(self.clone())
+    }
+}
+
+/// Adds the nodes that are not already in the state; existing nodes keep their endpoints.
+fn add_nodes(state: &mut MemoryAdminServiceState, nodes: Vec<CircuitNode>) {
+    for node in nodes {
+        if !state.nodes.contains_key(node.node_id()) {
+            state.nodes.insert(node.node_id().to_string(), node);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::admin::store::tests;
+
+    #[test]
+    fn memory_add_get_proposals() {
+        tests::test_add_get_proposals(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_list_proposals() {
+        tests::test_list_proposals(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_remove_proposals() {
+        tests::test_remove_proposals(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_update_proposals() {
+        tests::test_update_proposals(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_upgrade_proposals() {
+        tests::test_upgrade_proposals(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_add_get_circuit_and_nodes() {
+        tests::test_add_get_circuit_and_nodes(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_list_circuits() {
+        tests::test_list_circuits(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_remove_circuits() {
+        tests::test_remove_circuits(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_update_circuit() {
+        tests::test_update_circuit(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_get_service() {
+        tests::test_get_service(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_list_service() {
+        tests::test_list_service(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_list_nodes() {
+        tests::test_list_nodes(&MemoryAdminServiceStore::new());
+    }
+
+    #[test]
+    fn memory_add_nodes() {
+        tests::test_add_nodes(&MemoryAdminServiceStore::new());
+    }
+}
@@ -18,12 +18,15 @@
 //! proposals. Splinter provides the following implementations of this trait:
 //!
 //! * [`YamlAdminServiceStore`] - A YAML-backed store that is available by default
+//! * [`MemoryAdminServiceStore`] - A memory-backed store that is available by default, which does
+//!   not persist its state
 //! * [`DieselAdminServiceStore`] - A database-backed store, powered by [`Diesel`], that currently
 //!   supports SQLite databases (with the `sqlite` feature) and PostgreSQL databases (with the
 //!   `postgres` feature).
 //!
 //! [`AdminServiceStore`]: trait.AdminServiceStore.html
 //! [`YamlAdminServiceStore`]: yaml/struct.YamlAdminServiceStore.html
+//! [`MemoryAdminServiceStore`]: memory/struct.MemoryAdminServiceStore.html
 //! [`DieselAdminServiceStore`]: diesel/struct.DieselAdminServiceStore.html
 //! [`Diesel`]: https://crates.io/crates/diesel
 
@@ -33,6 +36,7 @@ mod circuit_proposal;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 pub mod diesel;
 pub mod error;
+pub mod memory;
 mod proposed_circuit;
 mod proposed_node;
 mod proposed_service;
@@ -273,6 +277,14 @@ pub trait AdminServiceStore: Send + Sync {
     ///  * `circuit_id` - The ID of the circuit proposal that should be converted to a circuit
     fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError>;
 
+    /// Adds the endpoints of nodes to the store, for nodes that have been added to the members of
+    /// a circuit. Nodes that are already in the store keep their existing endpoints.
+    ///
+    /// # Arguments
+    ///
+    ///  * `nodes` - The nodes to be added to the store
+    fn add_nodes(&self, nodes: Vec<CircuitNode>) -> Result<(), AdminServiceStoreError>;
+
     /// Fetches a node from the store
     ///
     /// # Arguments
@@ -313,3 +325,807 @@ impl Clone for Box<dyn AdminServiceStore> {
         self.clone_boxed()
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use crate::hex::parse_hex;
+
+    /// Verify that a proposal can be added to the store correctly and then fetched from the store
+    ///
+    /// 1. Create a proposal
+    /// 2. Add proposal to store
+    /// 3. Fetch Proposal from store
+    /// 4. Validate fetched proposal is the same as the proposal added
+    pub fn test_add_get_proposals(store: &dyn AdminServiceStore) {
+        let proposal = create_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(proposal, fetched_proposal);
+    }
+
+    /// Verify that list_proposals works correctly
+    ///
+    /// 1. Create a proposal
+    /// 2. Add proposal to store
+    /// 3. List Proposal from store with no predicates, validate added proposal is returned
+    /// 4. List Proposal from store with management type predicate, validate added proposal is
+    ///    returned
+    /// 5. List Proposal from store with member predicate, validate added proposal is
+    ///    returned
+    /// 6. List Proposal from store with mismatching management type predicate, validate no
+    ///    proposals are returned
+    pub fn test_list_proposals(store: &dyn AdminServiceStore) {
+        let proposal = create_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        // test no predicates
+        let mut proposals = store
+            .list_proposals(&vec![])
+            .expect("Unable to list proposals");
+
+        assert_eq!(proposals.next(), Some(proposal.clone()));
+        assert_eq!(proposals.next(), None);
+
+        // test management type predicate
+        let mut proposals = store
+            .list_proposals(&vec![CircuitPredicate::ManagementTypeEq(
+                "gameroom".to_string(),
+            )])
+            .expect("Unable to list proposals with management type predicate");
+
+        assert_eq!(proposals.next(), Some(proposal.clone()));
+        assert_eq!(proposals.next(), None);
+
+        // test management type predicate
+        let mut proposals = store
+            .list_proposals(&vec![CircuitPredicate::ManagementTypeEq(
+                "arcade".to_string(),
+            )])
+            .expect("Unable to list proposals with management type predicate");
+
+        assert_eq!(proposals.next(), None);
+
+        let extra_proposal = create_extra_proposal();
+
+        store
+            .add_proposal(extra_proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        // test management type predicate
+        let mut proposals = store
+            .list_proposals(&vec![CircuitPredicate::MembersInclude(vec![
+                "gumbo-node-000".to_string(),
+            ])])
+            .expect("Unable to list proposals with members include predicate");
+
+        assert_eq!(proposals.next(), Some(extra_proposal));
+        assert_eq!(proposals.next(), None);
+
+        let proposals = store
+            .list_proposals(&vec![])
+            .expect("Unable to list proposals with members include predicate");
+
+        assert_eq!(proposals.len(), 2);
+    }
+
+    /// Verify that a proposal can be removed from the store
+    ///
+    /// 1. Create a proposal
+    /// 2. Add proposal to store
+    /// 3. Fetch Proposal from store
+    /// 4. Validate fetched proposal is the same as the proposal added
+    /// 5. Remove proposal
+    /// 6. Validate the proposal was removed
+    pub fn test_remove_proposals(store: &dyn AdminServiceStore) {
+        let proposal = create_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(proposal, fetched_proposal);
+
+        store
+            .remove_proposal("WBKLF-BBBBB")
+            .expect("Unable to add circuit proposal");
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal");
+
+        assert_eq!(None, fetched_proposal);
+    }
+
+    /// Verify that a proposal can be added to the store correctly and then updated from the store
+    ///
+    /// 1. Create a proposal
+    /// 2. Add proposal to store
+    /// 3. Fetch Proposal from store
+    /// 4. Validate fetched proposal is the same as the proposal added
+    /// 5. Update proposal to have a new vote and call update
+    /// 6. Fetch Proposal from store
+    /// 7. Validate fetched proposal now matches the updated proposal
+    pub fn test_update_proposals(store: &dyn AdminServiceStore) {
+        let proposal = create_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(proposal, fetched_proposal);
+
+        let updated_proposal = proposal
+            .builder()
+            .with_votes(&vec![VoteRecordBuilder::new()
+                .with_public_key(
+                    &parse_hex(
+                        "035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550",
+                    )
+                    .unwrap(),
+                )
+                .with_vote(&Vote::Accept)
+                .with_voter_node_id("bubba-node-000")
+                .build()
+                .expect("Unable to build vote record")])
+            .build()
+            .expect("Unable to build updated proposal");
+
+        store
+            .update_proposal(updated_proposal.clone())
+            .expect("Unable to update proposal");
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(updated_proposal, fetched_proposal);
+    }
+
+    /// Verify that a proposal can be upgraded to a circuit
+    ///
+    /// 1. Create a proposal
+    /// 2. Add proposal to store
+    /// 3. Fetch Proposal from store
+    /// 4. Validate fetched proposal is the same as the proposal added
+    /// 5. Call upgrade_proposal_to_circuit for the proposal
+    /// 6. Fetch the new circuit and validate it is as expected
+    pub fn test_upgrade_proposals(store: &dyn AdminServiceStore) {
+        let proposal = create_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(proposal, fetched_proposal);
+
+        store
+            .upgrade_proposal_to_circuit("WBKLF-BBBBB")
+            .expect("Unable to add circuit proposal");
+
+        assert!(store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .is_none());
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+
+        assert_eq!(create_circuit(), fetched_circuit);
+    }
+
+    /// Verify that a circuit can be added to the store correctly and then fetched from the store
+    ///
+    /// 1. Create a circuit and nodes
+    /// 2. Add circuit and nodes to store
+    /// 3. Fetch Circuit from store
+    /// 4. Validate fetched circuit is the same as the circuit added
+    /// 5. Fetch CircuitNode from store
+    /// 6. Validate fetched node is the same as the node added
+    pub fn test_add_get_circuit_and_nodes(store: &dyn AdminServiceStore) {
+        let circuit = create_circuit();
+
+        let nodes = create_nodes();
+
+        store
+            .add_circuit(circuit.clone(), nodes)
+            .expect("Unable to add circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+
+        let fetched_node = store
+            .get_node("bubba-node-000")
+            .expect("Unable to get node")
+            .expect("Got None when expecting node");
+
+        assert_eq!(circuit, fetched_circuit);
+        assert_eq!(
+            fetched_node,
+            CircuitNodeBuilder::default()
+                .with_node_id("bubba-node-000".into())
+                .with_endpoints(&vec!["tcps://splinterd-node-bubba:8044".into()])
+                .build()
+                .expect("Unable to build node"),
+        )
+    }
+
+    /// Verify that list_circuits works correctly
+    ///
+    /// 1. Create a circuit and nodes
+    /// 2. Add circuit to store
+    /// 3. List circuits from store with no predicates, validate added circuit is returned
+    /// 4. List circuits from store with management type predicate, validate added circuit is
+    ///    returned
+    /// 5. List circuits from store with member predicate, validate added circuit is
+    ///    returned
+    /// 6. List circuits from store with mismatching management type predicate, validate no
+    ///    circuits are returned
+    pub fn test_list_circuits(store: &dyn AdminServiceStore) {
+        let circuit = create_circuit();
+        let nodes = create_nodes();
+
+        let extra_circuit = create_extra_circuit();
+        let extra_nodes = create_extra_nodes();
+
+        store
+            .add_circuit(circuit.clone(), nodes)
+            .expect("Unable to add circuit");
+
+        // test no predicates
+        let mut circuits = store
+            .list_circuits(&vec![])
+            .expect("Unable to list circuits");
+
+        assert_eq!(circuits.next(), Some(circuit.clone()));
+        assert_eq!(circuits.next(), None);
+
+        // test management type predicate
+        let mut circuits = store
+            .list_circuits(&vec![CircuitPredicate::ManagementTypeEq(
+                "gameroom".to_string(),
+            )])
+            .expect("Unable to list circuits with management type predicate");
+
+        assert_eq!(circuits.next(), Some(circuit.clone()));
+        assert_eq!(circuits.next(), None);
+
+        // test bad management type predicate
+        let mut circuits = store
+            .list_circuits(&vec![CircuitPredicate::ManagementTypeEq(
+                "arcade".to_string(),
+            )])
+            .expect("Unable to list circuits with management type predicate");
+
+        assert_eq!(circuits.next(), None);
+
+        store
+            .add_circuit(extra_circuit.clone(), extra_nodes)
+            .expect("Unable to add circuit");
+
+        // test members type predicate
+        let mut circuits = store
+            .list_circuits(&vec![CircuitPredicate::MembersInclude(vec![
+                "gumbo-node-000".to_string(),
+            ])])
+            .expect("Unable to list circuits with members include predicate");
+
+        assert_eq!(circuits.next(), Some(extra_circuit));
+        assert_eq!(circuits.next(), None);
+
+        // show all circuits are returned
+        let circuits = store
+            .list_circuits(&vec![])
+            .expect("Unable to list circuits with members include predicate");
+
+        assert_eq!(circuits.len(), 2);
+    }
+
+    /// Verify that a circuit can be removed from the store
+    ///
+    /// 1. Create a circuit
+    /// 2. Add circuit to store
+    /// 3. Fetch circuit from store
+    /// 4. Validate fetched circuit is the same as the proposal added
+    /// 5. Remove circuit
+    /// 6. Validate the circuit was removed
+    pub fn test_remove_circuits(store: &dyn AdminServiceStore) {
+        let circuit = create_circuit();
+        let nodes = create_nodes();
+
+        store
+            .add_circuit(circuit.clone(), nodes)
+            .expect("Unable to add circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+
+        assert_eq!(circuit, fetched_circuit);
+
+        store
+            .remove_circuit("WBKLF-BBBBB")
+            .expect("Unable to add circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit");
+
+        assert_eq!(None, fetched_circuit);
+    }
+
+    /// Verify that a circuit can be updated in the store
+    ///
+    /// 1. Create a circuit
+    /// 2. Add circuit to store
+    /// 3. Update the circuit's status, version and display name
+    /// 4. Fetch circuit from store
+    /// 5. Validate fetched circuit is the same as the updated circuit
+    pub fn test_update_circuit(store: &dyn AdminServiceStore) {
+        let circuit = create_circuit();
+        let nodes = create_nodes();
+
+        store
+            .add_circuit(circuit.clone(), nodes)
+            .expect("Unable to add circuit");
+
+        let updated_circuit = circuit
+            .builder()
+            .with_display_name("updated_display")
+            .with_circuit_version(2)
+            .with_circuit_status(&CircuitStatus::Disbanded)
+            .build()
+            .expect("Unable to build circuit");
+
+        store
+            .update_circuit(updated_circuit.clone())
+            .expect("Unable to update circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+
+        assert_eq!(updated_circuit, fetched_circuit);
+    }
+
+    /// Verify that a service can be fetched from the store
+    ///
+    /// 1. Create a circuit
+    /// 2. Add circuit to store
+    /// 3. Fetch circuit from store
+    /// 4. fetch a service from the store
+    pub fn test_get_service(store: &dyn AdminServiceStore) {
+        let circuit = create_circuit();
+        let nodes = create_nodes();
+
+        store
+            .add_circuit(circuit.clone(), nodes)
+            .expect("Unable to add circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+
+        assert_eq!(circuit, fetched_circuit);
+
+        let service_id = ServiceId::new("WBKLF-BBBBB".to_string(), "a000".to_string());
+        let fetched_service = store
+            .get_service(&service_id)
+            .expect("Unable to get service")
+            .expect("Got None when expecting service");
+
+        assert_eq!(fetched_circuit.roster()[0], fetched_service);
+    }
+
+    /// Verify that all service from a circuit can be listed from the store
+    ///
+    /// 1. Create a circuit
+    /// 2. Add circuit to store
+    /// 3. Fetch circuit from store
+    /// 4. List all service from the circuit
+    pub fn test_list_service(store: &dyn AdminServiceStore) {
+        let circuit = create_circuit();
+        let nodes = create_nodes();
+
+        store
+            .add_circuit(circuit.clone(), nodes)
+            .expect("Unable to add circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+
+        assert_eq!(circuit, fetched_circuit);
+
+        let mut services = store
+            .list_services("WBKLF-BBBBB")
+            .expect("Unable to get services");
+
+        assert!(fetched_circuit
+            .roster()
+            .contains(&services.next().expect("Unable to get service")));
+
+        assert!(fetched_circuit
+            .roster()
+            .contains(&services.next().expect("Unable to get service")));
+
+        assert_eq!(None, services.next());
+    }
+
+    /// Verify that all nodes can be listed from the store
+    ///
+    /// 1. Create a circuit and nodes
+    /// 2. Add circuit and nodes to store
+    /// 3. Fetch circuit from store
+    /// 4. List all nodes from the store
+    pub fn test_list_nodes(store: &dyn AdminServiceStore) {
+        let circuit = create_circuit();
+        let nodes = create_nodes();
+
+        store
+            .add_circuit(circuit.clone(), nodes)
+            .expect("Unable to add circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+
+        assert_eq!(circuit, fetched_circuit);
+
+        let mut nodes = store.list_nodes().expect("Unable to get services");
+
+        assert!(fetched_circuit.members().contains(
+            &nodes
+                .next()
+                .expect("Unable to get service")
+                .node_id()
+                .to_string()
+        ));
+
+        assert!(fetched_circuit.members().contains(
+            &nodes
+                .next()
+                .expect("Unable to get service")
+                .node_id()
+                .to_string()
+        ));
+
+        assert!(nodes.next().is_none());
+    }
+
+    /// Verify that nodes can be added to the store, and that nodes already in the store keep
+    /// their endpoints
+    ///
+    /// 1. Create a circuit and nodes
+    /// 2. Add circuit and nodes to store
+    /// 3. Update the circuit to include a new member
+    /// 4. Add the new node and an existing node with a different endpoint to the store
+    /// 5. Validate the new node can be fetched from the store
+    /// 6. Validate the existing node still has its original endpoint
+    pub fn test_add_nodes(store: &dyn AdminServiceStore) {
+        let circuit = create_circuit();
+        let nodes = create_nodes();
+
+        store
+            .add_circuit(circuit.clone(), nodes.clone())
+            .expect("Unable to add circuit");
+
+        let mut members = circuit.members().to_vec();
+        members.push("gumbo-node-000".into());
+        let updated_circuit = circuit
+            .builder()
+            .with_members(&members)
+            .build()
+            .expect("Unable to build circuit");
+        store
+            .update_circuit(updated_circuit)
+            .expect("Unable to update circuit");
+
+        let new_node = CircuitNodeBuilder::default()
+            .with_node_id("gumbo-node-000".into())
+            .with_endpoints(&vec!["tcps://splinterd-node-gumbo:8044".into()])
+            .build()
+            .expect("Unable to build node");
+        let existing_node = CircuitNodeBuilder::default()
+            .with_node_id("acme-node-000".into())
+            .with_endpoints(&vec!["tcps://splinterd-node-acme-updated:8044".into()])
+            .build()
+            .expect("Unable to build node");
+
+        store
+            .add_nodes(vec![new_node.clone(), existing_node])
+            .expect("Unable to add nodes");
+
+        assert_eq!(
+            Some(new_node),
+            store
+                .get_node("gumbo-node-000")
+                .expect("Unable to get node")
+        );
+        assert_eq!(
+            Some(nodes[1].clone()),
+            store.get_node("acme-node-000").expect("Unable to get node")
+        );
+        assert_eq!(3, store.list_nodes().expect("Unable to list nodes").len());
+    }
+
+    fn create_proposal() -> CircuitProposal {
+        CircuitProposalBuilder::default()
+            .with_proposal_type(&ProposalType::Create)
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_circuit_hash(
+                "7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d")
+            .with_circuit(
+                &ProposedCircuitBuilder::default()
+                    .with_circuit_id("WBKLF-BBBBB")
+                    .with_roster(&vec![
+                        ProposedServiceBuilder::default()
+                            .with_service_id("a000")
+                            .with_service_type("scabbard")
+                            .with_node_id(&"acme-node-000")
+                            .with_arguments(&vec![
+                                ("peer_services".into(), "[\"a001\"]".into()),
+                                ("admin_keys".into(),
+                               "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
+                            ])
+                            .build().expect("Unable to build service"),
+                        ProposedServiceBuilder::default()
+                            .with_service_id("a001")
+                            .with_service_type("scabbard")
+                            .with_node_id(&"bubba-node-000")
+                            .with_arguments(&vec![
+                                ("peer_services".into(), "[\"a000\"]".into()),
+                                ("admin_keys".into(),
+                               "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
+                            ])
+                            .build().expect("Unable to build service")
+                        ])
+
+                    .with_members(
+                        &vec![
+                        ProposedNodeBuilder::default()
+                            .with_node_id("bubba-node-000".into())
+                            .with_endpoints(
+                                &vec!["tcps://splinterd-node-bubba:8044".into(),
+                                      "tcps://splinterd-node-bubba-2:8044".into()])
+                            .build().expect("Unable to build node"),
+                        ProposedNodeBuilder::default()
+                            .with_node_id("acme-node-000".into())
+                            .with_endpoints(&vec!["tcps://splinterd-node-acme:8044".into()])
+                            .build().expect("Unable to build node"),
+                        ]
+                    )
+                    .with_circuit_version(3)
+                    .with_application_metadata(b"test")
+                    .with_comments("This is a test")
+                    .with_circuit_management_type("gameroom")
+                    .with_display_name("test_display")
+                    .build()
+                    .expect("Unable to build circuit")
+            )
+            .with_requester(
+                &parse_hex(
+                    "0283a14e0a17cb7f665311e9b5560f4cde2b502f17e2d03223e15d90d9318d7482").unwrap())
+            .with_requester_node_id("acme-node-000")
+            .with_votes(&vec![VoteRecordBuilder::new()
+                .with_public_key(
+                    &parse_hex(
+                        "035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550",
+                    )
+                    .unwrap(),
+                )
+                .with_vote(&Vote::Accept)
+                .with_voter_node_id("bubba-node-000")
+                .build()
+                .expect("Unable to build vote record"),
+                VoteRecordBuilder::new()
+                    .with_public_key(
+                        &parse_hex(
+                            "035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550",
+                        )
+                        .unwrap(),
+                    )
+                    .with_vote(&Vote::Accept)
+                    .with_voter_node_id("bubba-node-002")
+                    .build()
+                    .expect("Unable to build vote record")]
+            )
+            .build().expect("Unable to build proposals")
+    }
+
+    fn create_extra_proposal() -> CircuitProposal {
+        CircuitProposalBuilder::default()
+            .with_proposal_type(&ProposalType::Create)
+            .with_circuit_id("WBKLF-AAAAA")
+            .with_circuit_hash(
+                "7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d")
+            .with_circuit(
+                &ProposedCircuitBuilder::default()
+                    .with_circuit_id("WBKLF-AAAAA")
+                    .with_roster(&vec![
+                        ProposedServiceBuilder::default()
+                            .with_service_id("a000")
+                            .with_service_type("scabbard")
+                            .with_node_id(&"acme-node-000")
+                            .with_arguments(&vec![
+                                ("peer_services".into(), "[\"a001\"]".into()),
+                                ("admin_keys".into(),
+                               "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
+                            ])
+                            .build().expect("Unable to build service"),
+                        ProposedServiceBuilder::default()
+                            .with_service_id("a001")
+                            .with_service_type("scabbard")
+                            .with_node_id(&"gumbo-node-000")
+                            .with_arguments(&vec![
+                                ("peer_services".into(), "[\"a000\"]".into()),
+                                ("admin_keys".into(),
+                               "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
+                            ])
+                            .build().expect("Unable to build service")
+                        ])
+
+                    .with_members(
+                        &vec![
+                        ProposedNodeBuilder::default()
+                            .with_node_id("gumbo-node-000".into())
+                            .with_endpoints(&vec!["tcps://splinterd-node-gumbo:8044".into()])
+                            .build().expect("Unable to build node"),
+                        ProposedNodeBuilder::default()
+                            .with_node_id("acme-node-000".into())
+                            .with_endpoints(&vec!["tcps://splinterd-node-acme:8044".into()])
+                            .build().expect("Unable to build node"),
+                        ]
+                    )
+                    .with_circuit_management_type("gameroom")
+                    .with_circuit_status(&CircuitStatus::Active)
+                    .build().expect("Unable to build circuit")
+            )
+            .with_requester(
+                &parse_hex(
+                    "0283a14e0a17cb7f665311e9b5560f4cde2b502f17e2d03223e15d90d9318d7482").unwrap())
+            .with_requester_node_id("acme-node-000")
+            .build().expect("Unable to build proposals")
+    }
+
+    fn create_circuit() -> Circuit {
+        CircuitBuilder::default()
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_roster(&vec![
+                ServiceBuilder::default()
+                    .with_service_id("a000")
+                    .with_service_type("scabbard")
+                    .with_node_id("acme-node-000")
+                    .with_arguments(&vec![
+                        ("peer_services".into(), "[\"a001\"]".into()),
+                        ("admin_keys".into(),
+                       "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
+                    ])
+                    .build()
+                    .expect("Unable to build service"),
+                ServiceBuilder::default()
+                    .with_service_id("a001")
+                    .with_service_type("scabbard")
+                    .with_node_id("bubba-node-000")
+                    .with_arguments(&vec![
+                        ("peer_services".into(), "[\"a000\"]".into()),
+                        ("admin_keys".into(),
+                       "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]".into())
+                    ])
+                    .build()
+                    .expect("Unable to build service"),
+            ])
+            .with_members(&vec!["bubba-node-000".into(), "acme-node-000".into()])
+            .with_circuit_management_type("gameroom")
+            .with_application_metadata(b"test")
+            .with_comments("This is a test")
+            .with_display_name("test_display")
+            .with_circuit_version(3)
+            .with_circuit_status(&CircuitStatus::Active)
+            .build()
+            .expect("Unable to build circuit")
+    }
+
+    fn create_extra_circuit() -> Circuit {
+        CircuitBuilder::default()
+            .with_circuit_id("WBKLF-CCCCC")
+            .with_roster(&vec![
+                ServiceBuilder::default()
+                    .with_service_id("a000")
+                    .with_service_type("scabbard")
+                    .with_node_id("acme-node-000")
+                    .with_arguments(&vec![
+                        ("admin_keys".into(),
+                       "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]"
+                            .into()),
+                       ("peer_services".into(), "[\"a001\"]".into()),
+                    ])
+                    .build()
+                    .expect("Unable to build service"),
+                ServiceBuilder::default()
+                    .with_service_id("a001")
+                    .with_service_type("scabbard")
+                    .with_node_id("gumbo-node-000")
+                    .with_arguments(&vec![(
+                        "admin_keys".into(),
+                        "[\"035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550\"]"
+                            .into()
+                    ),(
+                        "peer_services".into(), "[\"a000\"]".into()
+                    )])
+                    .build()
+                    .expect("Unable to build service"),
+            ])
+            .with_members(&vec!["gumbo-node-000".into(), "acme-node-000".into()])
+            .with_circuit_management_type("other")
+            .build()
+            .expect("Unable to build circuit")
+    }
+
+    fn create_nodes() -> Vec<CircuitNode> {
+        vec![
+            CircuitNodeBuilder::default()
+                .with_node_id("bubba-node-000".into())
+                .with_endpoints(&vec!["tcps://splinterd-node-bubba:8044".into()])
+                .build()
+                .expect("Unable to build node"),
+            CircuitNodeBuilder::default()
+                .with_node_id("acme-node-000".into())
+                .with_endpoints(&vec!["tcps://splinterd-node-acme:8044".into()])
+                .build()
+                .expect("Unable to build node"),
+        ]
+    }
+
+    fn create_extra_nodes() -> Vec<CircuitNode> {
+        vec![
+            CircuitNodeBuilder::default()
+                .with_node_id("gumbo-node-000".into())
+                .with_endpoints(&vec!["tcps://splinterd-node-gumbo:8044".into()])
+                .build()
+                .expect("Unable to build node"),
+            CircuitNodeBuilder::default()
+                .with_node_id("acme-node-000".into())
+                .with_endpoints(&vec!["tcps://splinterd-node-acme:8044".into()])
+                .build()
+                .expect("Unable to build node"),
+        ]
+    }
+}
@@ -108,9 +108,31 @@ impl ProposedCircuit {
         &self.circuit_status
     }
 
+    /// Returns a builder populated with the values of this proposed circuit
+    pub fn builder(&self) -> ProposedCircuitBuilder {
+        ProposedCircuitBuilder {
+            circuit_id: Some(self.circuit_id.clone()),
+            roster: Some(self.roster.clone()),
+            members: Some(self.members.clone()),
+            authorization_type: Some(self.authorization_type.clone()),
+            persistence: Some(self.persistence.clone()),
+            durability: Some(self.durability.clone()),
+            routes: Some(self.routes.clone()),
+            circuit_management_type: Some(self.circuit_management_type.clone()),
+            application_metadata: self.application_metadata.clone(),
+            comments: self.comments.clone(),
+            display_name: self.display_name.clone(),
+            circuit_version: Some(self.circuit_version),
+            circuit_status: Some(self.circuit_status.clone()),
+        }
+    }
+
     pub fn from_proto(mut proto: admin::Circuit) -> Result<Self, InvalidStateError> {
         let authorization_type = match proto.get_authorization_type() {
             admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION => AuthorizationType::Trust,
+            admin::Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION => {
+                AuthorizationType::Challenge
+            }
             admin::Circuit_AuthorizationType::UNSET_AUTHORIZATION_TYPE => {
                 return Err(InvalidStateError::with_message(
                     "unable to build, missing field: `authorization_type`".to_string(),
@@ -129,6 +151,14 @@ impl ProposedCircuit {
 
         let durability = match proto.get_durability() {
             admin::Circuit_DurabilityType::NO_DURABILITY => DurabilityType::NoDurability,
+            #[cfg(feature = "circuit-durability")]
+            admin::Circuit_DurabilityType::STORE_AND_FORWARD => DurabilityType::StoreAndForward,
+            #[cfg(not(feature = "circuit-durability"))]
+            admin::Circuit_DurabilityType::STORE_AND_FORWARD => {
+                return Err(InvalidStateError::with_message(
+                    "unable to build, unsupported durability type: `STORE_AND_FORWARD`".to_string(),
+                ));
+            }
             admin::Circuit_DurabilityType::UNSET_DURABILITY_TYPE => {
                 return Err(InvalidStateError::with_message(
                     "unable to build, missing field: `durability type`".to_string(),
@@ -244,6 +274,11 @@ impl ProposedCircuit {
                 circuit
                     .set_authorization_type(admin::Circuit_AuthorizationType::TRUST_AUTHORIZATION);
             }
+            AuthorizationType::Challenge => {
+                circuit.set_authorization_type(
+                    admin::Circuit_AuthorizationType::CHALLENGE_AUTHORIZATION,
+                );
+            }
         };
 
         match self.persistence {
@@ -255,6 +290,10 @@ impl ProposedCircuit {
             DurabilityType::NoDurability => {
                 circuit.set_durability(admin::Circuit_DurabilityType::NO_DURABILITY);
             }
+            #[cfg(feature = "circuit-durability")]
+            DurabilityType::StoreAndForward => {
+                circuit.set_durability(admin::Circuit_DurabilityType::STORE_AND_FORWARD);
+            }
         };
 
         match self.routes {
@@ -909,6 +909,38 @@ impl AdminServiceStore for YamlAdminServiceStore {
         })
     }
 
+    /// Adds nodes to the underlying storage. Nodes that are already stored keep their existing
+    /// endpoints.
+    ///
+    /// # Arguments
+    ///
+    ///  * `nodes` - The nodes to be added to state
+    fn add_nodes(&self, nodes: Vec<CircuitNode>) -> Result<(), AdminServiceStoreError> {
+        {
+            let mut state = self.state.lock().map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?;
+
+            for node in nodes.into_iter() {
+                if !state.circuit_state.nodes.contains_key(node.node_id()) {
+                    state
+                        .circuit_state
+                        .nodes
+                        .insert(node.node_id().to_string(), node);
+                }
+            }
+        }
+
+        self.write_circuit_state().map_err(|err| {
+            AdminServiceStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                "Unable to write circuit state yaml file".to_string(),
+            ))
+        })
+    }
+
     /// Fetches a node from the underlying storage
     ///
     /// # Arguments
@@ -1022,6 +1054,8 @@ struct YamlCircuit {
     durability: YamlDurabilityType,
     routes: YamlRouteType,
     circuit_management_type: String,
+    application_metadata: Option<String>,
+    comments: Option<String>,
     display_name: Option<String>,
     #[serde(default = "default_circuit_value")]
     circuit_version: i32,
@@ -1050,6 +1084,20 @@ impl TryFrom<YamlCircuit> for Circuit {
             .with_circuit_version(circuit.circuit_version)
             .with_circuit_status(&CircuitStatus::from(circuit.circuit_status));
 
+        if let Some(application_metadata) = circuit.application_metadata {
+            builder = builder.with_application_metadata(&parse_hex(&application_metadata).map_err(
+                |_| {
+                    InvalidStateError::with_message(
+                        "Circuit application metadata is not valid hex".to_string(),
+                    )
+                },
+            )?)
+        }
+
+        if let Some(comments) = &circuit.comments {
+            builder = builder.with_comments(comments);
+        }
+
         if let Some(display_name) = &circuit.display_name {
             builder = builder.with_display_name(display_name);
         }
@@ -1060,6 +1108,8 @@ impl TryFrom<YamlCircuit> for Circuit {
 
 impl From<Circuit> for YamlCircuit {
     fn from(circuit: Circuit) -> Self {
+        let application_metadata = circuit.application_metadata().as_deref().map(to_hex);
+
         YamlCircuit {
             id: circuit.circuit_id().into(),
             roster: circuit
@@ -1073,6 +1123,8 @@ impl From<Circuit> for YamlCircuit {
             durability: circuit.durability().clone().into(),
             routes: circuit.routes().clone().into(),
             circuit_management_type: circuit.circuit_management_type().into(),
+            application_metadata,
+            comments: circuit.comments().clone(),
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: circuit.circuit_status().clone().into(),
@@ -1274,6 +1326,8 @@ pub enum YamlProposalType {
     AddNode,
     RemoveNode,
     Disband,
+    UpdateApplicationMetadata,
+    Abandon,
 }
 
 impl From<YamlProposalType> for ProposalType {
@@ -1284,6 +1338,8 @@ impl From<YamlProposalType> for ProposalType {
             YamlProposalType::AddNode => ProposalType::AddNode,
             YamlProposalType::RemoveNode => ProposalType::RemoveNode,
             YamlProposalType::Disband => ProposalType::Disband,
+            YamlProposalType::Abandon => ProposalType::Abandon,
+            YamlProposalType::UpdateApplicationMetadata => ProposalType::UpdateApplicationMetadata,
         }
     }
 }
@@ -1296,6 +1352,8 @@ impl From<ProposalType> for YamlProposalType {
             ProposalType::AddNode => YamlProposalType::AddNode,
             ProposalType::RemoveNode => YamlProposalType::RemoveNode,
             ProposalType::Disband => YamlProposalType::Disband,
+            ProposalType::Abandon => YamlProposalType::Abandon,
+            ProposalType::UpdateApplicationMetadata => YamlProposalType::UpdateApplicationMetadata,
         }
     }
 }
@@ -1518,12 +1576,14 @@ impl From<ProposedNode> for YamlProposedNode {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum YamlAuthorizationType {
     Trust,
+    Challenge,
 }
 
 impl From<AuthorizationType> for YamlAuthorizationType {
     fn from(authorization_type: AuthorizationType) -> Self {
         match authorization_type {
             AuthorizationType::Trust => YamlAuthorizationType::Trust,
+            AuthorizationType::Challenge => YamlAuthorizationType::Challenge,
         }
     }
 }
@@ -1532,6 +1592,7 @@ impl From<YamlAuthorizationType> for AuthorizationType {
     fn from(yaml_authorization_type: YamlAuthorizationType) -> Self {
         match yaml_authorization_type {
             YamlAuthorizationType::Trust => AuthorizationType::Trust,
+            YamlAuthorizationType::Challenge => AuthorizationType::Challenge,
         }
     }
 }
@@ -1562,12 +1623,16 @@ impl From<YamlPersistenceType> for PersistenceType {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum YamlDurabilityType {
     NoDurability,
+    #[cfg(feature = "circuit-durability")]
+    StoreAndForward,
 }
 
 impl From<DurabilityType> for YamlDurabilityType {
     fn from(durability_type: DurabilityType) -> Self {
         match durability_type {
             DurabilityType::NoDurability => YamlDurabilityType::NoDurability,
+            #[cfg(feature = "circuit-durability")]
+            DurabilityType::StoreAndForward => YamlDurabilityType::StoreAndForward,
         }
     }
 }
@@ -1576,6 +1641,8 @@ impl From<YamlDurabilityType> for DurabilityType {
     fn from(yaml_durability_type: YamlDurabilityType) -> Self {
         match yaml_durability_type {
             YamlDurabilityType::NoDurability => DurabilityType::NoDurability,
+            #[cfg(feature = "circuit-durability")]
+            YamlDurabilityType::StoreAndForward => DurabilityType::StoreAndForward,
         }
     }
 }
@@ -60,7 +60,10 @@ pub use key_management::store::memory::MemoryKeyStore;
 #[cfg(feature = "biome-key-management")]
 pub use key_management::store::KeyStore;
 
-#[cfg(all(feature = "biome-oauth", feature = "diesel"))]
+#[cfg(any(
+    feature = "biome-oauth-user-store-postgres",
+    feature = "biome-oauth-user-store-sqlite"
+))]
 pub use oauth::store::diesel::DieselOAuthUserSessionStore;
 #[cfg(feature = "biome-oauth")]
 pub use oauth::store::memory::MemoryOAuthUserSessionStore;
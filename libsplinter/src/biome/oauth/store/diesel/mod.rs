@@ -43,7 +43,7 @@ impl<C: diesel::Connection + 'static> DieselOAuthUserSessionStore<C> {
     }
 }
 
-#[cfg(feature = "sqlite")]
+#[cfg(feature = "biome-oauth-user-store-sqlite")]
 impl OAuthUserSessionStore for DieselOAuthUserSessionStore<diesel::sqlite::SqliteConnection> {
     fn add_session(
         &self,
@@ -135,7 +135,7 @@ impl OAuthUserSessionStore for DieselOAuthUserSessionStore<diesel::pg::PgConnect
     }
 }
 
-#[cfg(all(test, feature = "sqlite"))]
+#[cfg(all(test, feature = "biome-oauth-user-store-sqlite"))]
 pub mod tests {
     use super::*;
 
@@ -32,7 +32,7 @@ pub trait OAuthUserSessionStoreAddSession {
     ) -> Result<(), OAuthUserSessionStoreError>;
 }
 
-#[cfg(feature = "sqlite")]
+#[cfg(feature = "biome-oauth-user-store-sqlite")]
 impl<'a> OAuthUserSessionStoreAddSession
     for OAuthUserSessionStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
@@ -17,7 +17,10 @@
 use std::error::Error;
 use std::fmt;
 
-#[cfg(any(feature = "biome-oauth-user-store-postgres", feature = "sqlite"))]
+#[cfg(any(
+    feature = "biome-oauth-user-store-postgres",
+    feature = "biome-oauth-user-store-sqlite"
+))]
 use crate::error::ConstraintViolationType;
 use crate::error::{
     ConstraintViolationError, InternalError, InvalidArgumentError, InvalidStateError,
@@ -54,14 +57,20 @@ impl fmt::Display for OAuthUserSessionStoreError {
     }
 }
 
-#[cfg(any(feature = "biome-oauth-user-store-postgres", feature = "sqlite"))]
+#[cfg(any(
+    feature = "biome-oauth-user-store-postgres",
+    feature = "biome-oauth-user-store-sqlite"
+))]
 impl From<diesel::r2d2::PoolError> for OAuthUserSessionStoreError {
     fn from(err: diesel::r2d2::PoolError) -> Self {
         OAuthUserSessionStoreError::Internal(InternalError::from_source(Box::new(err)))
     }
 }
 
-#[cfg(any(feature = "biome-oauth-user-store-postgres", feature = "sqlite"))]
+#[cfg(any(
+    feature = "biome-oauth-user-store-postgres",
+    feature = "biome-oauth-user-store-sqlite"
+))]
 impl From<diesel::result::Error> for OAuthUserSessionStoreError {
     fn from(err: diesel::result::Error) -> Self {
         match err {
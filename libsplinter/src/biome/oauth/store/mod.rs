@@ -19,7 +19,10 @@
 //! * It provides a correlation between an OAuth subject identifier and a Biome user ID
 //! * It stores tokens and other data for an OAuth user's sessions
 
-#[cfg(any(feature = "biome-oauth-user-store-postgres", feature = "sqlite"))]
+#[cfg(any(
+    feature = "biome-oauth-user-store-postgres",
+    feature = "biome-oauth-user-store-sqlite"
+))]
 pub(in crate::biome) mod diesel;
 mod error;
 pub(in crate::biome) mod memory;
@@ -0,0 +1,513 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Store-and-forward delivery of direct messages on durable circuits.
+//!
+//! Direct messages on a circuit with the store-and-forward durability type are persisted by the
+//! sending node before they are sent to the node of the recipient service. Each message is
+//! numbered in the sequence of messages sent to its destination node on its circuit, and stays
+//! queued until the destination node acknowledges it. Queued messages are sent again when the
+//! connection to the destination node is re-established, and periodically with an exponential
+//! backoff while they remain unacknowledged. The queued messages of a circuit that has been
+//! removed are discarded.
+//!
+//! The destination node records the sequence number of the last message it delivered from each
+//! sending node on each circuit. Messages are only delivered in sequence, so a message that is
+//! sent again after it has been delivered is acknowledged but not delivered twice, and a message
+//! that can't be delivered yet only holds up the messages on its own circuit. A message that can
+//! never be delivered, because its sender or recipient is not on the circuit or its recipient is
+//! connected to another node, is logged and acknowledged without being delivered.
+//!
+//! If a node stops after a message is delivered to the recipient service but before the sequence
+//! number is recorded, the message is delivered again when it is resent. Each delivered message
+//! carries its durable sequence number, which is the same each time the message is delivered, so
+//! duplicates can be detected by the recipient. A [`DuplicateFilter`] drops these duplicates
+//! before they reach the service; when it is backed by a [`DurableMessageStore`], it records the
+//! sequence number of each message as the message is handed to the service, so duplicates are
+//! also dropped after the node restarts. A message is only handled twice if the node stops while
+//! the service is handling it.
+//!
+//! [`DuplicateFilter`]: struct.DuplicateFilter.html
+//! [`DurableMessageStore`]: store/trait.DurableMessageStore.html
+
+pub mod store;
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use protobuf::Message;
+
+use crate::circuit::handlers::create_message;
+use crate::circuit::routing::RoutingTableReader;
+use crate::error::InternalError;
+use crate::network::dispatch::{MessageSender, PeerId};
+use crate::peer::{PeerManagerConnector, PeerManagerNotification};
+use crate::protos::circuit::{CircuitDirectMessage, CircuitDurableMessage, CircuitMessageType};
+
+use self::store::DurableMessageStore;
+
+/// The time to wait before resending unacknowledged messages for the first time
+const INITIAL_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+/// The longest time to wait between resending unacknowledged messages
+const MAX_RETRY_INTERVAL: Duration = Duration::from_secs(256);
+/// How often the retry thread checks for messages that are due to be resent
+const RETRY_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// When the queued messages for a destination node are next resent
+struct Backoff {
+    interval: Duration,
+    next_attempt: Instant,
+}
+
+impl Backoff {
+    fn new() -> Self {
+        Backoff {
+            interval: INITIAL_RETRY_INTERVAL,
+            next_attempt: Instant::now() + INITIAL_RETRY_INTERVAL,
+        }
+    }
+}
+
+/// Sends the direct messages of store-and-forward circuits, and resends them until they are
+/// acknowledged by their destination node.
+#[derive(Clone)]
+pub struct DurableDelivery {
+    store: Box<dyn DurableMessageStore>,
+    sender: Arc<Mutex<Box<dyn MessageSender<PeerId>>>>,
+    routing_table: Box<dyn RoutingTableReader>,
+    backoff: Arc<Mutex<HashMap<String, Backoff>>>,
+}
+
+impl DurableDelivery {
+    /// Constructs a new `DurableDelivery`.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store that queued messages are persisted in
+    /// * `sender` - The sender used to send messages to other nodes
+    /// * `routing_table` - The routing table used to find the circuits that have been removed
+    pub fn new(
+        store: Box<dyn DurableMessageStore>,
+        sender: Box<dyn MessageSender<PeerId>>,
+        routing_table: Box<dyn RoutingTableReader>,
+    ) -> Self {
+        DurableDelivery {
+            store,
+            sender: Arc::new(Mutex::new(sender)),
+            routing_table,
+            backoff: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Queues a direct message for the destination node and sends it.
+    ///
+    /// The message is queued before it is sent, so an error is only returned if the message could
+    /// not be queued. A message that could not be sent is resent later.
+    ///
+    /// # Arguments
+    ///
+    /// * `destination_node_id` - The ID of the node the recipient service is connected to
+    /// * `circuit_id` - The ID of the circuit the message is sent on
+    /// * `direct_message_bytes` - The bytes of the `CircuitDirectMessage`
+    pub fn send(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        direct_message_bytes: Vec<u8>,
+    ) -> Result<(), InternalError> {
+        let sequence_number = self
+            .store
+            .add_outbound_message(
+                destination_node_id,
+                circuit_id,
+                direct_message_bytes.clone(),
+            )
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        self.lock_backoff()?
+            .entry(destination_node_id.to_string())
+            .or_insert_with(Backoff::new);
+
+        if let Err(err) = self.send_durable_message(
+            destination_node_id,
+            circuit_id,
+            sequence_number,
+            direct_message_bytes,
+        ) {
+            debug!(
+                "Unable to send durable message {} to {}, will retry: {}",
+                sequence_number, destination_node_id, err
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Removes the messages for the destination node that it has acknowledged.
+    ///
+    /// # Arguments
+    ///
+    /// * `destination_node_id` - The ID of the node that acknowledged the messages
+    /// * `circuit_id` - The ID of the circuit the messages were sent on
+    /// * `sequence_number` - The sequence number of the last message the node has delivered on
+    ///   the circuit
+    pub fn acknowledge(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), InternalError> {
+        self.store
+            .remove_outbound_messages(destination_node_id, circuit_id, sequence_number)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let mut backoff = self.lock_backoff()?;
+        if self.has_outbound_messages(destination_node_id)? {
+            backoff.insert(destination_node_id.to_string(), Backoff::new());
+        } else {
+            backoff.remove(destination_node_id);
+        }
+
+        Ok(())
+    }
+
+    /// Sends all of the queued messages for the destination node again. The queued messages of
+    /// circuits that have been removed are discarded instead, since they can no longer be
+    /// delivered.
+    ///
+    /// # Arguments
+    ///
+    /// * `destination_node_id` - The ID of the node to send the queued messages to
+    pub fn resend(&self, destination_node_id: &str) -> Result<(), InternalError> {
+        let messages = self
+            .store
+            .list_outbound_messages(destination_node_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let mut removed_circuits = BTreeMap::new();
+        for message in messages {
+            if removed_circuits.contains_key(&message.circuit_id)
+                || self.is_circuit_removed(&message.circuit_id)?
+            {
+                removed_circuits.insert(message.circuit_id, message.sequence_number);
+                continue;
+            }
+
+            self.send_durable_message(
+                destination_node_id,
+                &message.circuit_id,
+                message.sequence_number,
+                message.payload,
+            )?;
+        }
+
+        for (circuit_id, last_sequence_number) in removed_circuits {
+            warn!(
+                "Discarding durable messages for {} on circuit {}, which has been removed",
+                destination_node_id, circuit_id
+            );
+            self.acknowledge(destination_node_id, &circuit_id, last_sequence_number)?;
+        }
+
+        Ok(())
+    }
+
+    /// Starts a thread that resends queued messages when the connection to their destination node
+    /// is established, and when their backoff interval has elapsed.
+    ///
+    /// The thread stops when the peer manager shuts down.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_connector` - The connector used to subscribe to peer notifications
+    pub fn start_retry_thread(
+        &self,
+        peer_connector: &PeerManagerConnector,
+    ) -> Result<thread::JoinHandle<()>, InternalError> {
+        let (notification_sender, notification_receiver) = channel();
+        peer_connector
+            .subscribe_sender(notification_sender)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let durable_delivery = self.clone();
+        thread::Builder::new()
+            .name("DurableDelivery Retry".into())
+            .spawn(move || loop {
+                let result = match notification_receiver.recv_timeout(RETRY_CHECK_INTERVAL) {
+                    Ok(PeerManagerNotification::Connected { peer }) => {
+                        durable_delivery.reset_backoff(&peer);
+                        durable_delivery.resend(&peer)
+                    }
+                    Ok(PeerManagerNotification::Disconnected { .. }) => Ok(()),
+                    Err(RecvTimeoutError::Timeout) => durable_delivery.resend_due(),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        debug!(
+                            "Peer manager notifications have stopped, stopping durable delivery \
+                             retries"
+                        );
+                        break;
+                    }
+                };
+
+                if let Err(err) = result {
+                    debug!("Unable to resend durable messages: {}", err);
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    /// Resends the queued messages for each destination node whose backoff interval has elapsed,
+    /// and doubles the interval.
+    fn resend_due(&self) -> Result<(), InternalError> {
+        let destinations = self
+            .store
+            .list_outbound_destinations()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let now = Instant::now();
+        let due = {
+            let mut backoff = self.lock_backoff()?;
+            destinations
+                .into_iter()
+                .filter(|destination| {
+                    let backoff = backoff
+                        .entry(destination.to_string())
+                        .or_insert_with(Backoff::new);
+                    if backoff.next_attempt > now {
+                        return false;
+                    }
+                    backoff.interval = std::cmp::min(backoff.interval * 2, MAX_RETRY_INTERVAL);
+                    backoff.next_attempt = now + backoff.interval;
+                    true
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for destination in due {
+            if let Err(err) = self.resend(&destination) {
+                debug!(
+                    "Unable to resend durable messages to {}: {}",
+                    destination, err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn reset_backoff(&self, destination_node_id: &str) {
+        if let Ok(mut backoff) = self.lock_backoff() {
+            if backoff.contains_key(destination_node_id) {
+                backoff.insert(destination_node_id.to_string(), Backoff::new());
+            }
+        }
+    }
+
+    fn is_circuit_removed(&self, circuit_id: &str) -> Result<bool, InternalError> {
+        self.routing_table
+            .get_circuit(circuit_id)
+            .map(|circuit| circuit.is_none())
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    fn has_outbound_messages(&self, destination_node_id: &str) -> Result<bool, InternalError> {
+        self.store
+            .list_outbound_messages(destination_node_id)
+            .map(|messages| !messages.is_empty())
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    fn send_durable_message(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+        direct_message_bytes: Vec<u8>,
+    ) -> Result<(), InternalError> {
+        let mut durable_message = CircuitDurableMessage::new();
+        durable_message.set_circuit_id(circuit_id.to_string());
+        durable_message.set_sequence_number(sequence_number);
+        durable_message.set_payload(direct_message_bytes);
+        let msg_bytes = durable_message
+            .write_to_bytes()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        let network_msg_bytes =
+            create_message(msg_bytes, CircuitMessageType::CIRCUIT_DURABLE_MESSAGE)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        self.sender
+            .lock()
+            .map_err(|_| {
+                InternalError::with_message("DurableDelivery sender lock poisoned".into())
+            })?
+            .send(destination_node_id.into(), network_msg_bytes)
+            .map_err(|(recipient, _)| {
+                InternalError::with_message(format!("Unable to send message to {}", &*recipient))
+            })
+    }
+
+    fn lock_backoff(&self) -> Result<MutexGuard<HashMap<String, Backoff>>, InternalError> {
+        self.backoff.lock().map_err(|_| {
+            InternalError::with_message("DurableDelivery backoff lock poisoned".into())
+        })
+    }
+}
+
+/// Drops the durable messages that are delivered to a service more than once.
+///
+/// Durable messages from each sender on a circuit are delivered in order, so a message is a
+/// duplicate if its durable sequence number is not greater than that of the last message handed
+/// to the same recipient from the same sender on the same circuit. Messages that were not
+/// delivered as durable messages are never considered duplicates.
+///
+/// A filter that is constructed with a [`DurableMessageStore`] persists the sequence number of the
+/// last message handed to each recipient, so that it still drops duplicates after a restart.
+///
+/// [`DurableMessageStore`]: store/trait.DurableMessageStore.html
+#[derive(Default)]
+pub struct DuplicateFilter {
+    /// Circuit ID, sender ID and recipient ID to the sequence number of the last message handed
+    /// to the recipient
+    last_handled: HashMap<(String, String, String), u64>,
+    store: Option<Box<dyn DurableMessageStore>>,
+}
+
+impl DuplicateFilter {
+    /// Constructs a new `DuplicateFilter` that only remembers the messages it has seen while it
+    /// is running.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a new `DuplicateFilter` that persists the last message handed to each recipient
+    /// in the given store.
+    pub fn with_store(store: Box<dyn DurableMessageStore>) -> Self {
+        Self {
+            last_handled: HashMap::new(),
+            store: Some(store),
+        }
+    }
+
+    /// Returns true if the message has already been handed to its recipient.
+    pub fn is_duplicate(
+        &mut self,
+        direct_message: &CircuitDirectMessage,
+    ) -> Result<bool, InternalError> {
+        let sequence_number = direct_message.get_durable_sequence_number();
+        if sequence_number == 0 {
+            return Ok(false);
+        }
+
+        let key = Self::key(direct_message);
+        let last_handled = match self.last_handled.get(&key) {
+            Some(last_handled) => *last_handled,
+            None => {
+                let last_handled = match &self.store {
+                    Some(store) => store
+                        .get_last_handled(&key.0, &key.1, &key.2)
+                        .map_err(|err| InternalError::from_source(Box::new(err)))?,
+                    None => 0,
+                };
+                self.last_handled.insert(key, last_handled);
+                last_handled
+            }
+        };
+
+        Ok(sequence_number <= last_handled)
+    }
+
+    /// Records that the message has been handed to its recipient.
+    pub fn record_handled(
+        &mut self,
+        direct_message: &CircuitDirectMessage,
+    ) -> Result<(), InternalError> {
+        let sequence_number = direct_message.get_durable_sequence_number();
+        if sequence_number == 0 {
+            return Ok(());
+        }
+
+        let key = Self::key(direct_message);
+        if let Some(store) = &self.store {
+            store
+                .set_last_handled(&key.0, &key.1, &key.2, sequence_number)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        }
+        self.last_handled.insert(key, sequence_number);
+
+        Ok(())
+    }
+
+    fn key(direct_message: &CircuitDirectMessage) -> (String, String, String) {
+        (
+            direct_message.get_circuit().to_string(),
+            direct_message.get_sender().to_string(),
+            direct_message.get_recipient().to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use super::store::MemoryDurableMessageStore;
+
+    /// Test that a `DuplicateFilter` backed by a store still drops a message that was handed to
+    /// its recipient before the filter was recreated, as it would be when the node restarts.
+    ///
+    /// 1. Hand messages 1 and 2 from service a to service b through a filter backed by a store
+    /// 2. Create a new filter backed by the same store, and verify that messages 1 and 2 are
+    ///    duplicates but message 3 and messages between other services are not
+    #[test]
+    fn duplicate_filter_with_store() {
+        let store = MemoryDurableMessageStore::new();
+
+        let mut filter = DuplicateFilter::with_store(Box::new(store.clone()));
+        for sequence_number in 1..=2 {
+            let message = direct_message("a", "b", sequence_number);
+            assert!(!filter
+                .is_duplicate(&message)
+                .expect("Unable to check message"));
+            filter
+                .record_handled(&message)
+                .expect("Unable to record message");
+        }
+
+        let mut filter = DuplicateFilter::with_store(Box::new(store));
+        assert!(filter
+            .is_duplicate(&direct_message("a", "b", 1))
+            .expect("Unable to check message"));
+        assert!(filter
+            .is_duplicate(&direct_message("a", "b", 2))
+            .expect("Unable to check message"));
+        assert!(!filter
+            .is_duplicate(&direct_message("a", "b", 3))
+            .expect("Unable to check message"));
+        assert!(!filter
+            .is_duplicate(&direct_message("b", "a", 1))
+            .expect("Unable to check message"));
+    }
+
+    fn direct_message(sender: &str, recipient: &str, sequence_number: u64) -> CircuitDirectMessage {
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("alpha".into());
+        direct_message.set_sender(sender.into());
+        direct_message.set_recipient(recipient.into());
+        direct_message.set_durable_sequence_number(sequence_number);
+        direct_message
+    }
+}
@@ -0,0 +1,329 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Diesel-backed DurableMessageStore implementation.
+
+mod models;
+mod operations;
+mod schema;
+
+use diesel::{
+    r2d2::{ConnectionManager, Pool},
+    result,
+};
+
+use crate::error::InternalError;
+
+use super::{DurableMessageStore, DurableMessageStoreError, OutboundMessage};
+
+use operations::add_outbound_message::DurableMessageStoreAddOutboundMessageOperation as _;
+use operations::get_last_delivered::DurableMessageStoreGetLastDeliveredOperation as _;
+use operations::get_last_handled::DurableMessageStoreGetLastHandledOperation as _;
+use operations::list_outbound_destinations::DurableMessageStoreListOutboundDestinationsOperation as _;
+use operations::list_outbound_messages::DurableMessageStoreListOutboundMessagesOperation as _;
+use operations::remove_outbound_messages::DurableMessageStoreRemoveOutboundMessagesOperation as _;
+use operations::set_last_delivered::DurableMessageStoreSetLastDeliveredOperation as _;
+use operations::set_last_handled::DurableMessageStoreSetLastHandledOperation as _;
+use operations::DurableMessageOperations;
+
+/// A Diesel-backed DurableMessageStore
+pub struct DieselDurableMessageStore<C: diesel::Connection + 'static> {
+    connection_pool: Pool<ConnectionManager<C>>,
+}
+
+impl<C: diesel::Connection + 'static> DieselDurableMessageStore<C> {
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        Self { connection_pool }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl DurableMessageStore for DieselDurableMessageStore<diesel::sqlite::SqliteConnection> {
+    fn add_outbound_message(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<u64, DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).add_outbound_message(
+            destination_node_id,
+            circuit_id,
+            payload,
+        )
+    }
+
+    fn list_outbound_messages(
+        &self,
+        destination_node_id: &str,
+    ) -> Result<Vec<OutboundMessage>, DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).list_outbound_messages(destination_node_id)
+    }
+
+    fn list_outbound_destinations(&self) -> Result<Vec<String>, DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).list_outbound_destinations()
+    }
+
+    fn remove_outbound_messages(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).remove_outbound_messages(
+            destination_node_id,
+            circuit_id,
+            sequence_number,
+        )
+    }
+
+    fn get_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+    ) -> Result<u64, DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).get_last_delivered(source_node_id, circuit_id)
+    }
+
+    fn set_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).set_last_delivered(
+            source_node_id,
+            circuit_id,
+            sequence_number,
+        )
+    }
+
+    fn get_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+    ) -> Result<u64, DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).get_last_handled(
+            circuit_id,
+            sender_service_id,
+            recipient_service_id,
+        )
+    }
+
+    fn set_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).set_last_handled(
+            circuit_id,
+            sender_service_id,
+            recipient_service_id,
+            sequence_number,
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn DurableMessageStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl DurableMessageStore for DieselDurableMessageStore<diesel::pg::PgConnection> {
+    fn add_outbound_message(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<u64, DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).add_outbound_message(
+            destination_node_id,
+            circuit_id,
+            payload,
+        )
+    }
+
+    fn list_outbound_messages(
+        &self,
+        destination_node_id: &str,
+    ) -> Result<Vec<OutboundMessage>, DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).list_outbound_messages(destination_node_id)
+    }
+
+    fn list_outbound_destinations(&self) -> Result<Vec<String>, DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).list_outbound_destinations()
+    }
+
+    fn remove_outbound_messages(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).remove_outbound_messages(
+            destination_node_id,
+            circuit_id,
+            sequence_number,
+        )
+    }
+
+    fn get_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+    ) -> Result<u64, DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).get_last_delivered(source_node_id, circuit_id)
+    }
+
+    fn set_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).set_last_delivered(
+            source_node_id,
+            circuit_id,
+            sequence_number,
+        )
+    }
+
+    fn get_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+    ) -> Result<u64, DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).get_last_handled(
+            circuit_id,
+            sender_service_id,
+            recipient_service_id,
+        )
+    }
+
+    fn set_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        let connection = self.connection_pool.get()?;
+        DurableMessageOperations::new(&*connection).set_last_handled(
+            circuit_id,
+            sender_service_id,
+            recipient_service_id,
+            sequence_number,
+        )
+    }
+
+    fn clone_box(&self) -> Box<dyn DurableMessageStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+impl From<models::OutboundMessageModel> for OutboundMessage {
+    fn from(model: models::OutboundMessageModel) -> Self {
+        OutboundMessage {
+            circuit_id: model.circuit_id,
+            sequence_number: model.sequence_number as u64,
+            payload: model.payload,
+        }
+    }
+}
+
+impl From<diesel::r2d2::PoolError> for DurableMessageStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> Self {
+        DurableMessageStoreError::InternalError(InternalError::from_source(Box::new(err)))
+    }
+}
+
+impl From<result::Error> for DurableMessageStoreError {
+    fn from(err: result::Error) -> Self {
+        DurableMessageStoreError::InternalError(InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::circuit::durable::store::tests::{
+        test_last_delivered, test_last_handled, test_outbound_messages,
+    };
+    use crate::migrations::run_sqlite_migrations;
+
+    #[test]
+    fn sqlite_outbound_messages() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselDurableMessageStore::new(pool);
+        test_outbound_messages(&store);
+    }
+
+    #[test]
+    fn sqlite_last_delivered() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselDurableMessageStore::new(pool);
+        test_last_delivered(&store);
+    }
+
+    #[test]
+    fn sqlite_last_handled() {
+        let pool = create_connection_pool_and_migrate();
+        let store = DieselDurableMessageStore::new(pool);
+        test_last_handled(&store);
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection insures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::schema::{
+    circuit_inbound_sequence, circuit_outbound_message, circuit_outbound_sequence,
+    circuit_service_inbound_sequence,
+};
+
+#[derive(Insertable, Queryable, PartialEq, Debug)]
+#[table_name = "circuit_outbound_message"]
+pub struct OutboundMessageModel {
+    pub destination_node_id: String,
+    pub circuit_id: String,
+    pub sequence_number: i64,
+    pub payload: Vec<u8>,
+}
+
+#[derive(Insertable, Queryable, PartialEq, Debug)]
+#[table_name = "circuit_outbound_sequence"]
+pub struct OutboundSequenceModel {
+    pub destination_node_id: String,
+    pub circuit_id: String,
+    pub last_sequence_number: i64,
+}
+
+#[derive(Insertable, Queryable, PartialEq, Debug)]
+#[table_name = "circuit_inbound_sequence"]
+pub struct InboundSequenceModel {
+    pub source_node_id: String,
+    pub circuit_id: String,
+    pub last_sequence_number: i64,
+}
+
+#[derive(Insertable, Queryable, PartialEq, Debug)]
+#[table_name = "circuit_service_inbound_sequence"]
+pub struct ServiceInboundSequenceModel {
+    pub circuit_id: String,
+    pub sender_service_id: String,
+    pub recipient_service_id: String,
+    pub last_sequence_number: i64,
+}
@@ -0,0 +1,161 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::circuit::durable::store::{
+    diesel::{
+        models::{OutboundMessageModel, OutboundSequenceModel},
+        schema::{circuit_outbound_message, circuit_outbound_sequence},
+    },
+    DurableMessageStoreError,
+};
+
+use super::DurableMessageOperations;
+
+pub(in crate::circuit::durable::store::diesel) trait DurableMessageStoreAddOutboundMessageOperation
+{
+    fn add_outbound_message(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<u64, DurableMessageStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurableMessageStoreAddOutboundMessageOperation
+    for DurableMessageOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_outbound_message(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<u64, DurableMessageStoreError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                let last_sequence_number = circuit_outbound_sequence::table
+                    .filter(circuit_outbound_sequence::destination_node_id.eq(destination_node_id))
+                    .filter(circuit_outbound_sequence::circuit_id.eq(circuit_id))
+                    .select(circuit_outbound_sequence::last_sequence_number)
+                    .first::<i64>(self.conn)
+                    .optional()?;
+
+                let sequence_number = match last_sequence_number {
+                    Some(last_sequence_number) => {
+                        diesel::update(
+                            circuit_outbound_sequence::table
+                                .filter(
+                                    circuit_outbound_sequence::destination_node_id
+                                        .eq(destination_node_id),
+                                )
+                                .filter(circuit_outbound_sequence::circuit_id.eq(circuit_id)),
+                        )
+                        .set(
+                            circuit_outbound_sequence::last_sequence_number
+                                .eq(last_sequence_number + 1),
+                        )
+                        .execute(self.conn)?;
+                        last_sequence_number + 1
+                    }
+                    None => {
+                        insert_into(circuit_outbound_sequence::table)
+                            .values(OutboundSequenceModel {
+                                destination_node_id: destination_node_id.to_string(),
+                                circuit_id: circuit_id.to_string(),
+                                last_sequence_number: 1,
+                            })
+                            .execute(self.conn)?;
+                        1
+                    }
+                };
+
+                insert_into(circuit_outbound_message::table)
+                    .values(OutboundMessageModel {
+                        destination_node_id: destination_node_id.to_string(),
+                        circuit_id: circuit_id.to_string(),
+                        sequence_number,
+                        payload,
+                    })
+                    .execute(self.conn)?;
+
+                Ok(sequence_number as u64)
+            })
+            .map_err(DurableMessageStoreError::from)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurableMessageStoreAddOutboundMessageOperation
+    for DurableMessageOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_outbound_message(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<u64, DurableMessageStoreError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                let last_sequence_number = circuit_outbound_sequence::table
+                    .filter(circuit_outbound_sequence::destination_node_id.eq(destination_node_id))
+                    .filter(circuit_outbound_sequence::circuit_id.eq(circuit_id))
+                    .select(circuit_outbound_sequence::last_sequence_number)
+                    .first::<i64>(self.conn)
+                    .optional()?;
+
+                let sequence_number = match last_sequence_number {
+                    Some(last_sequence_number) => {
+                        diesel::update(
+                            circuit_outbound_sequence::table
+                                .filter(
+                                    circuit_outbound_sequence::destination_node_id
+                                        .eq(destination_node_id),
+                                )
+                                .filter(circuit_outbound_sequence::circuit_id.eq(circuit_id)),
+                        )
+                        .set(
+                            circuit_outbound_sequence::last_sequence_number
+                                .eq(last_sequence_number + 1),
+                        )
+                        .execute(self.conn)?;
+                        last_sequence_number + 1
+                    }
+                    None => {
+                        insert_into(circuit_outbound_sequence::table)
+                            .values(OutboundSequenceModel {
+                                destination_node_id: destination_node_id.to_string(),
+                                circuit_id: circuit_id.to_string(),
+                                last_sequence_number: 1,
+                            })
+                            .execute(self.conn)?;
+                        1
+                    }
+                };
+
+                insert_into(circuit_outbound_message::table)
+                    .values(OutboundMessageModel {
+                        destination_node_id: destination_node_id.to_string(),
+                        circuit_id: circuit_id.to_string(),
+                        sequence_number,
+                        payload,
+                    })
+                    .execute(self.conn)?;
+
+                Ok(sequence_number as u64)
+            })
+            .map_err(DurableMessageStoreError::from)
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::prelude::*;
+
+use crate::circuit::durable::store::{
+    diesel::schema::circuit_inbound_sequence, DurableMessageStoreError,
+};
+
+use super::DurableMessageOperations;
+
+pub(in crate::circuit::durable::store::diesel) trait DurableMessageStoreGetLastDeliveredOperation {
+    fn get_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+    ) -> Result<u64, DurableMessageStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurableMessageStoreGetLastDeliveredOperation
+    for DurableMessageOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn get_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+    ) -> Result<u64, DurableMessageStoreError> {
+        circuit_inbound_sequence::table
+            .filter(circuit_inbound_sequence::source_node_id.eq(source_node_id))
+            .filter(circuit_inbound_sequence::circuit_id.eq(circuit_id))
+            .select(circuit_inbound_sequence::last_sequence_number)
+            .first::<i64>(self.conn)
+            .optional()
+            .map(|last_sequence_number| last_sequence_number.unwrap_or(0) as u64)
+            .map_err(DurableMessageStoreError::from)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurableMessageStoreGetLastDeliveredOperation
+    for DurableMessageOperations<'a, diesel::pg::PgConnection>
+{
+    fn get_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+    ) -> Result<u64, DurableMessageStoreError> {
+        circuit_inbound_sequence::table
+            .filter(circuit_inbound_sequence::source_node_id.eq(source_node_id))
+            .filter(circuit_inbound_sequence::circuit_id.eq(circuit_id))
+            .select(circuit_inbound_sequence::last_sequence_number)
+            .first::<i64>(self.conn)
+            .optional()
+            .map(|last_sequence_number| last_sequence_number.unwrap_or(0) as u64)
+            .map_err(DurableMessageStoreError::from)
+    }
+}
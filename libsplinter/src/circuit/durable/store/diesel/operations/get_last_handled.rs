@@ -0,0 +1,74 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::prelude::*;
+
+use crate::circuit::durable::store::{
+    diesel::schema::circuit_service_inbound_sequence, DurableMessageStoreError,
+};
+
+use super::DurableMessageOperations;
+
+pub(in crate::circuit::durable::store::diesel) trait DurableMessageStoreGetLastHandledOperation {
+    fn get_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+    ) -> Result<u64, DurableMessageStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurableMessageStoreGetLastHandledOperation
+    for DurableMessageOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn get_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+    ) -> Result<u64, DurableMessageStoreError> {
+        circuit_service_inbound_sequence::table
+            .filter(circuit_service_inbound_sequence::circuit_id.eq(circuit_id))
+            .filter(circuit_service_inbound_sequence::sender_service_id.eq(sender_service_id))
+            .filter(circuit_service_inbound_sequence::recipient_service_id.eq(recipient_service_id))
+            .select(circuit_service_inbound_sequence::last_sequence_number)
+            .first::<i64>(self.conn)
+            .optional()
+            .map(|last_sequence_number| last_sequence_number.unwrap_or(0) as u64)
+            .map_err(DurableMessageStoreError::from)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurableMessageStoreGetLastHandledOperation
+    for DurableMessageOperations<'a, diesel::pg::PgConnection>
+{
+    fn get_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+    ) -> Result<u64, DurableMessageStoreError> {
+        circuit_service_inbound_sequence::table
+            .filter(circuit_service_inbound_sequence::circuit_id.eq(circuit_id))
+            .filter(circuit_service_inbound_sequence::sender_service_id.eq(sender_service_id))
+            .filter(circuit_service_inbound_sequence::recipient_service_id.eq(recipient_service_id))
+            .select(circuit_service_inbound_sequence::last_sequence_number)
+            .first::<i64>(self.conn)
+            .optional()
+            .map(|last_sequence_number| last_sequence_number.unwrap_or(0) as u64)
+            .map_err(DurableMessageStoreError::from)
+    }
+}
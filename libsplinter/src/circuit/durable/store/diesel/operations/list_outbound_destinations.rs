@@ -0,0 +1,52 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::prelude::*;
+
+use crate::circuit::durable::store::{
+    diesel::schema::circuit_outbound_message, DurableMessageStoreError,
+};
+
+use super::DurableMessageOperations;
+
+pub(in crate::circuit::durable::store::diesel) trait DurableMessageStoreListOutboundDestinationsOperation
+{
+    fn list_outbound_destinations(&self) -> Result<Vec<String>, DurableMessageStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurableMessageStoreListOutboundDestinationsOperation
+    for DurableMessageOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_outbound_destinations(&self) -> Result<Vec<String>, DurableMessageStoreError> {
+        circuit_outbound_message::table
+            .select(circuit_outbound_message::destination_node_id)
+            .distinct()
+            .load::<String>(self.conn)
+            .map_err(DurableMessageStoreError::from)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurableMessageStoreListOutboundDestinationsOperation
+    for DurableMessageOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_outbound_destinations(&self) -> Result<Vec<String>, DurableMessageStoreError> {
+        circuit_outbound_message::table
+            .select(circuit_outbound_message::destination_node_id)
+            .distinct()
+            .load::<String>(self.conn)
+            .map_err(DurableMessageStoreError::from)
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::prelude::*;
+
+use crate::circuit::durable::store::{
+    diesel::{models::OutboundMessageModel, schema::circuit_outbound_message},
+    DurableMessageStoreError, OutboundMessage,
+};
+
+use super::DurableMessageOperations;
+
+pub(in crate::circuit::durable::store::diesel) trait DurableMessageStoreListOutboundMessagesOperation
+{
+    fn list_outbound_messages(
+        &self,
+        destination_node_id: &str,
+    ) -> Result<Vec<OutboundMessage>, DurableMessageStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurableMessageStoreListOutboundMessagesOperation
+    for DurableMessageOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_outbound_messages(
+        &self,
+        destination_node_id: &str,
+    ) -> Result<Vec<OutboundMessage>, DurableMessageStoreError> {
+        circuit_outbound_message::table
+            .filter(circuit_outbound_message::destination_node_id.eq(destination_node_id))
+            .order((
+                circuit_outbound_message::circuit_id.asc(),
+                circuit_outbound_message::sequence_number.asc(),
+            ))
+            .load::<OutboundMessageModel>(self.conn)
+            .map(|messages| messages.into_iter().map(OutboundMessage::from).collect())
+            .map_err(DurableMessageStoreError::from)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurableMessageStoreListOutboundMessagesOperation
+    for DurableMessageOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_outbound_messages(
+        &self,
+        destination_node_id: &str,
+    ) -> Result<Vec<OutboundMessage>, DurableMessageStoreError> {
+        circuit_outbound_message::table
+            .filter(circuit_outbound_message::destination_node_id.eq(destination_node_id))
+            .order((
+                circuit_outbound_message::circuit_id.asc(),
+                circuit_outbound_message::sequence_number.asc(),
+            ))
+            .load::<OutboundMessageModel>(self.conn)
+            .map(|messages| messages.into_iter().map(OutboundMessage::from).collect())
+            .map_err(DurableMessageStoreError::from)
+    }
+}
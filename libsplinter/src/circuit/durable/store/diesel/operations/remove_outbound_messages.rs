@@ -0,0 +1,75 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::prelude::*;
+
+use crate::circuit::durable::store::{
+    diesel::schema::circuit_outbound_message, DurableMessageStoreError,
+};
+
+use super::DurableMessageOperations;
+
+pub(in crate::circuit::durable::store::diesel) trait DurableMessageStoreRemoveOutboundMessagesOperation
+{
+    fn remove_outbound_messages(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurableMessageStoreRemoveOutboundMessagesOperation
+    for DurableMessageOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_outbound_messages(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        diesel::delete(
+            circuit_outbound_message::table
+                .filter(circuit_outbound_message::destination_node_id.eq(destination_node_id))
+                .filter(circuit_outbound_message::circuit_id.eq(circuit_id))
+                .filter(circuit_outbound_message::sequence_number.le(sequence_number as i64)),
+        )
+        .execute(self.conn)
+        .map(|_| ())
+        .map_err(DurableMessageStoreError::from)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurableMessageStoreRemoveOutboundMessagesOperation
+    for DurableMessageOperations<'a, diesel::pg::PgConnection>
+{
+    fn remove_outbound_messages(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        diesel::delete(
+            circuit_outbound_message::table
+                .filter(circuit_outbound_message::destination_node_id.eq(destination_node_id))
+                .filter(circuit_outbound_message::circuit_id.eq(circuit_id))
+                .filter(circuit_outbound_message::sequence_number.le(sequence_number as i64)),
+        )
+        .execute(self.conn)
+        .map(|_| ())
+        .map_err(DurableMessageStoreError::from)
+    }
+}
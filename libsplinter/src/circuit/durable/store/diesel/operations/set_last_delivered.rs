@@ -0,0 +1,103 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::circuit::durable::store::{
+    diesel::{models::InboundSequenceModel, schema::circuit_inbound_sequence},
+    DurableMessageStoreError,
+};
+
+use super::DurableMessageOperations;
+
+pub(in crate::circuit::durable::store::diesel) trait DurableMessageStoreSetLastDeliveredOperation {
+    fn set_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> DurableMessageStoreSetLastDeliveredOperation
+    for DurableMessageOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                let updated = diesel::update(
+                    circuit_inbound_sequence::table
+                        .filter(circuit_inbound_sequence::source_node_id.eq(source_node_id))
+                        .filter(circuit_inbound_sequence::circuit_id.eq(circuit_id)),
+                )
+                .set(circuit_inbound_sequence::last_sequence_number.eq(sequence_number as i64))
+                .execute(self.conn)?;
+
+                if updated == 0 {
+                    insert_into(circuit_inbound_sequence::table)
+                        .values(InboundSequenceModel {
+                            source_node_id: source_node_id.to_string(),
+                            circuit_id: circuit_id.to_string(),
+                            last_sequence_number: sequence_number as i64,
+                        })
+                        .execute(self.conn)?;
+                }
+
+                Ok(())
+            })
+            .map_err(DurableMessageStoreError::from)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> DurableMessageStoreSetLastDeliveredOperation
+    for DurableMessageOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                let updated = diesel::update(
+                    circuit_inbound_sequence::table
+                        .filter(circuit_inbound_sequence::source_node_id.eq(source_node_id))
+                        .filter(circuit_inbound_sequence::circuit_id.eq(circuit_id)),
+                )
+                .set(circuit_inbound_sequence::last_sequence_number.eq(sequence_number as i64))
+                .execute(self.conn)?;
+
+                if updated == 0 {
+                    insert_into(circuit_inbound_sequence::table)
+                        .values(InboundSequenceModel {
+                            source_node_id: source_node_id.to_string(),
+                            circuit_id: circuit_id.to_string(),
+                            last_sequence_number: sequence_number as i64,
+                        })
+                        .execute(self.conn)?;
+                }
+
+                Ok(())
+            })
+            .map_err(DurableMessageStoreError::from)
+    }
+}
@@ -0,0 +1,47 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+table! {
+    circuit_outbound_message (destination_node_id, circuit_id, sequence_number) {
+        destination_node_id -> Text,
+        circuit_id -> Text,
+        sequence_number -> BigInt,
+        payload -> Binary,
+    }
+}
+
+table! {
+    circuit_outbound_sequence (destination_node_id, circuit_id) {
+        destination_node_id -> Text,
+        circuit_id -> Text,
+        last_sequence_number -> BigInt,
+    }
+}
+
+table! {
+    circuit_inbound_sequence (source_node_id, circuit_id) {
+        source_node_id -> Text,
+        circuit_id -> Text,
+        last_sequence_number -> BigInt,
+    }
+}
+
+table! {
+    circuit_service_inbound_sequence (circuit_id, sender_service_id, recipient_service_id) {
+        circuit_id -> Text,
+        sender_service_id -> Text,
+        recipient_service_id -> Text,
+        last_sequence_number -> BigInt,
+    }
+}
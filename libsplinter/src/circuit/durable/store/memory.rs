@@ -0,0 +1,234 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory-backed DurableMessageStore implementation.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::InternalError;
+
+use super::{DurableMessageStore, DurableMessageStoreError, OutboundMessage};
+
+#[derive(Default)]
+struct MemoryDurableMessageState {
+    /// Destination node ID to the queued messages for it, by circuit ID and sequence number
+    outbound_messages: HashMap<String, BTreeMap<(String, u64), Vec<u8>>>,
+    /// Destination node ID and circuit ID to the sequence number of the last message queued
+    outbound_sequence_numbers: HashMap<(String, String), u64>,
+    /// Source node ID and circuit ID to the sequence number of the last message delivered
+    last_delivered: HashMap<(String, String), u64>,
+    /// Circuit ID, sender service ID and recipient service ID to the sequence number of the last
+    /// message handed to the recipient
+    last_handled: HashMap<(String, String, String), u64>,
+}
+
+/// A memory-backed implementation of DurableMessageStore.
+///
+/// Queued messages are lost when the node is restarted, so this store is only suitable for
+/// testing.
+#[derive(Clone, Default)]
+pub struct MemoryDurableMessageStore {
+    state: Arc<Mutex<MemoryDurableMessageState>>,
+}
+
+impl MemoryDurableMessageStore {
+    /// Constructs a new instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock_state(&self) -> Result<MutexGuard<MemoryDurableMessageState>, InternalError> {
+        self.state
+            .lock()
+            .map_err(|_| InternalError::with_message("durable message lock was poisoned".into()))
+    }
+}
+
+impl DurableMessageStore for MemoryDurableMessageStore {
+    fn add_outbound_message(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<u64, DurableMessageStoreError> {
+        let mut state = self.lock_state()?;
+
+        let sequence_number = state
+            .outbound_sequence_numbers
+            .entry((destination_node_id.to_string(), circuit_id.to_string()))
+            .or_insert(0);
+        *sequence_number += 1;
+        let sequence_number = *sequence_number;
+
+        state
+            .outbound_messages
+            .entry(destination_node_id.to_string())
+            .or_default()
+            .insert((circuit_id.to_string(), sequence_number), payload);
+
+        Ok(sequence_number)
+    }
+
+    fn list_outbound_messages(
+        &self,
+        destination_node_id: &str,
+    ) -> Result<Vec<OutboundMessage>, DurableMessageStoreError> {
+        Ok(self
+            .lock_state()?
+            .outbound_messages
+            .get(destination_node_id)
+            .map(|messages| {
+                messages
+                    .iter()
+                    .map(|((circuit_id, sequence_number), payload)| OutboundMessage {
+                        circuit_id: circuit_id.clone(),
+                        sequence_number: *sequence_number,
+                        payload: payload.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn list_outbound_destinations(&self) -> Result<Vec<String>, DurableMessageStoreError> {
+        Ok(self
+            .lock_state()?
+            .outbound_messages
+            .keys()
+            .cloned()
+            .collect())
+    }
+
+    fn remove_outbound_messages(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        let mut state = self.lock_state()?;
+
+        let is_empty = match state.outbound_messages.get_mut(destination_node_id) {
+            Some(messages) => {
+                let acknowledged = messages
+                    .range((circuit_id.to_string(), 0)..=(circuit_id.to_string(), sequence_number))
+                    .map(|(key, _)| key.clone())
+                    .collect::<Vec<_>>();
+                for key in acknowledged {
+                    messages.remove(&key);
+                }
+                messages.is_empty()
+            }
+            None => false,
+        };
+        if is_empty {
+            state.outbound_messages.remove(destination_node_id);
+        }
+
+        Ok(())
+    }
+
+    fn get_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+    ) -> Result<u64, DurableMessageStoreError> {
+        Ok(self
+            .lock_state()?
+            .last_delivered
+            .get(&(source_node_id.to_string(), circuit_id.to_string()))
+            .copied()
+            .unwrap_or(0))
+    }
+
+    fn set_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        self.lock_state()?.last_delivered.insert(
+            (source_node_id.to_string(), circuit_id.to_string()),
+            sequence_number,
+        );
+        Ok(())
+    }
+
+    fn get_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+    ) -> Result<u64, DurableMessageStoreError> {
+        Ok(self
+            .lock_state()?
+            .last_handled
+            .get(&(
+                circuit_id.to_string(),
+                sender_service_id.to_string(),
+                recipient_service_id.to_string(),
+            ))
+            .copied()
+            .unwrap_or(0))
+    }
+
+    fn set_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError> {
+        self.lock_state()?.last_handled.insert(
+            (
+                circuit_id.to_string(),
+                sender_service_id.to_string(),
+                recipient_service_id.to_string(),
+            ),
+            sequence_number,
+        );
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn DurableMessageStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::circuit::durable::store::tests::{
+        test_last_delivered, test_last_handled, test_outbound_messages,
+    };
+
+    #[test]
+    fn memory_outbound_messages() {
+        let store = MemoryDurableMessageStore::new();
+        test_outbound_messages(&store);
+    }
+
+    #[test]
+    fn memory_last_delivered() {
+        let store = MemoryDurableMessageStore::new();
+        test_last_delivered(&store);
+    }
+
+    #[test]
+    fn memory_last_handled() {
+        let store = MemoryDurableMessageStore::new();
+        test_last_handled(&store);
+    }
+}
@@ -0,0 +1,281 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines an API to persist the messages of store-and-forward circuits.
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+mod diesel;
+mod error;
+mod memory;
+
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+pub use self::diesel::DieselDurableMessageStore;
+pub use error::DurableMessageStoreError;
+pub use memory::MemoryDurableMessageStore;
+
+/// A message that is queued for delivery to another node
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutboundMessage {
+    /// The circuit the message was sent on
+    pub circuit_id: String,
+    /// The position of the message in the sequence of messages sent to the destination node on
+    /// the circuit
+    pub sequence_number: u64,
+    /// The bytes of the `CircuitDirectMessage`
+    pub payload: Vec<u8>,
+}
+
+/// A store for the messages that are queued for delivery to other nodes, and for the position of
+/// the last message delivered from each other node.
+///
+/// Messages to each destination node are numbered in sequence for each circuit, starting at 1.
+/// The receiving node records the sequence number of the last message it delivered from each
+/// sending node on each circuit, so that a message that is sent again is not delivered twice.
+/// The sequence number of the last message handed to each local service is also recorded, so that
+/// a message that is delivered again after the node restarts is not handled twice.
+pub trait DurableMessageStore: Send + Sync {
+    /// Adds a message to the end of the queue for the destination node on the circuit, returning
+    /// its sequence number.
+    fn add_outbound_message(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        payload: Vec<u8>,
+    ) -> Result<u64, DurableMessageStoreError>;
+
+    /// Lists the queued messages for the destination node, ordered by circuit and then in
+    /// sequence.
+    fn list_outbound_messages(
+        &self,
+        destination_node_id: &str,
+    ) -> Result<Vec<OutboundMessage>, DurableMessageStoreError>;
+
+    /// Lists the nodes that have queued messages.
+    fn list_outbound_destinations(&self) -> Result<Vec<String>, DurableMessageStoreError>;
+
+    /// Removes the queued messages for the destination node on the circuit, up to and including
+    /// the given sequence number.
+    fn remove_outbound_messages(
+        &self,
+        destination_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError>;
+
+    /// Returns the sequence number of the last message delivered from the source node on the
+    /// circuit, or 0 if no message has been delivered from it.
+    fn get_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+    ) -> Result<u64, DurableMessageStoreError>;
+
+    /// Sets the sequence number of the last message delivered from the source node on the
+    /// circuit.
+    fn set_last_delivered(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError>;
+
+    /// Returns the sequence number of the last message from the sender service that was handed to
+    /// the recipient service on the circuit, or 0 if no message has been handed to it.
+    fn get_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+    ) -> Result<u64, DurableMessageStoreError>;
+
+    /// Sets the sequence number of the last message from the sender service that was handed to
+    /// the recipient service on the circuit.
+    fn set_last_handled(
+        &self,
+        circuit_id: &str,
+        sender_service_id: &str,
+        recipient_service_id: &str,
+        sequence_number: u64,
+    ) -> Result<(), DurableMessageStoreError>;
+
+    /// Clone the store for dynamic dispatch.
+    fn clone_box(&self) -> Box<dyn DurableMessageStore>;
+}
+
+impl Clone for Box<dyn DurableMessageStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// This test checks that a store implementation queues outbound messages correctly. It does
+    /// the following:
+    /// 1. Add three messages for one node on one circuit, one for the node on another circuit and
+    ///    one for another node, and verify their sequence numbers
+    /// 2. Verify the messages and the destinations are listed
+    /// 3. Remove the first two messages for the first node on the first circuit, and verify only
+    ///    the third remains along with the message on the other circuit
+    /// 4. Remove the last message for the second node, and verify the node is no longer listed and
+    ///    that a new message for it continues the sequence
+    pub fn test_outbound_messages(store: &dyn DurableMessageStore) {
+        for (i, payload) in [b"a", b"b", b"c"].iter().enumerate() {
+            let sequence_number = store
+                .add_outbound_message("node-1", "circuit-1", payload.to_vec())
+                .expect("Unable to add message");
+            assert_eq!(sequence_number, i as u64 + 1);
+        }
+        assert_eq!(
+            store
+                .add_outbound_message("node-1", "circuit-2", b"x".to_vec())
+                .expect("Unable to add message"),
+            1
+        );
+        assert_eq!(
+            store
+                .add_outbound_message("node-2", "circuit-1", b"d".to_vec())
+                .expect("Unable to add message"),
+            1
+        );
+
+        assert_eq!(
+            store
+                .list_outbound_messages("node-1")
+                .expect("Unable to list messages")
+                .into_iter()
+                .map(|message| message.payload)
+                .collect::<Vec<_>>(),
+            vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec(), b"x".to_vec()]
+        );
+        let mut destinations = store
+            .list_outbound_destinations()
+            .expect("Unable to list destinations");
+        destinations.sort();
+        assert_eq!(destinations, vec!["node-1", "node-2"]);
+
+        store
+            .remove_outbound_messages("node-1", "circuit-1", 2)
+            .expect("Unable to remove messages");
+        assert_eq!(
+            store
+                .list_outbound_messages("node-1")
+                .expect("Unable to list messages"),
+            vec![
+                OutboundMessage {
+                    circuit_id: "circuit-1".into(),
+                    sequence_number: 3,
+                    payload: b"c".to_vec(),
+                },
+                OutboundMessage {
+                    circuit_id: "circuit-2".into(),
+                    sequence_number: 1,
+                    payload: b"x".to_vec(),
+                }
+            ]
+        );
+
+        store
+            .remove_outbound_messages("node-2", "circuit-1", 1)
+            .expect("Unable to remove messages");
+        assert_eq!(
+            store
+                .list_outbound_destinations()
+                .expect("Unable to list destinations"),
+            vec!["node-1"]
+        );
+        assert_eq!(
+            store
+                .add_outbound_message("node-2", "circuit-1", b"e".to_vec())
+                .expect("Unable to add message"),
+            2
+        );
+    }
+
+    /// This test checks that a store implementation records the last message delivered from each
+    /// node on each circuit.
+    pub fn test_last_delivered(store: &dyn DurableMessageStore) {
+        assert_eq!(
+            store
+                .get_last_delivered("node-1", "circuit-1")
+                .expect("Unable to get last delivered"),
+            0
+        );
+
+        store
+            .set_last_delivered("node-1", "circuit-1", 1)
+            .expect("Unable to set last delivered");
+        store
+            .set_last_delivered("node-1", "circuit-1", 2)
+            .expect("Unable to set last delivered");
+
+        assert_eq!(
+            store
+                .get_last_delivered("node-1", "circuit-1")
+                .expect("Unable to get last delivered"),
+            2
+        );
+        assert_eq!(
+            store
+                .get_last_delivered("node-1", "circuit-2")
+                .expect("Unable to get last delivered"),
+            0
+        );
+        assert_eq!(
+            store
+                .get_last_delivered("node-2", "circuit-1")
+                .expect("Unable to get last delivered"),
+            0
+        );
+    }
+
+    /// This test checks that a store implementation records the last message handed to each
+    /// service from each other service on each circuit.
+    pub fn test_last_handled(store: &dyn DurableMessageStore) {
+        assert_eq!(
+            store
+                .get_last_handled("circuit-1", "service-a", "service-b")
+                .expect("Unable to get last handled"),
+            0
+        );
+
+        store
+            .set_last_handled("circuit-1", "service-a", "service-b", 1)
+            .expect("Unable to set last handled");
+        store
+            .set_last_handled("circuit-1", "service-a", "service-b", 2)
+            .expect("Unable to set last handled");
+
+        assert_eq!(
+            store
+                .get_last_handled("circuit-1", "service-a", "service-b")
+                .expect("Unable to get last handled"),
+            2
+        );
+        assert_eq!(
+            store
+                .get_last_handled("circuit-1", "service-b", "service-a")
+                .expect("Unable to get last handled"),
+            0
+        );
+        assert_eq!(
+            store
+                .get_last_handled("circuit-2", "service-a", "service-b")
+                .expect("Unable to get last handled"),
+            0
+        );
+    }
+}
@@ -13,7 +13,11 @@
 // limitations under the License.
 
 use crate::circuit::handlers::create_message;
+#[cfg(feature = "metrics")]
+use crate::circuit::handlers::RoutedMessageCounter;
 use crate::circuit::routing::RoutingTableReader;
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsRegistry;
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
 use crate::protos::circuit::{
     AdminDirectMessage, CircuitError, CircuitError_Error, CircuitMessageType,
@@ -26,6 +30,8 @@ const ADMIN_SERVICE_ID_PREFIX: &str = "admin::";
 pub struct AdminDirectMessageHandler {
     node_id: String,
     routing_table: Box<dyn RoutingTableReader>,
+    #[cfg(feature = "metrics")]
+    routed_messages: Option<RoutedMessageCounter>,
 }
 
 impl Handler for AdminDirectMessageHandler {
@@ -80,9 +86,18 @@ impl AdminDirectMessageHandler {
         Self {
             node_id,
             routing_table,
+            #[cfg(feature = "metrics")]
+            routed_messages: None,
         }
     }
 
+    /// Sets the metrics registry that the number of messages routed per circuit is reported to.
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: MetricsRegistry) -> Self {
+        self.routed_messages = Some(RoutedMessageCounter::new(metrics, "admin"));
+        self
+    }
+
     fn create_response(
         &self,
         msg: AdminDirectMessage,
@@ -131,6 +146,13 @@ impl AdminDirectMessageHandler {
             .map_err(|err| DispatchError::HandleError(err.to_string()))?;
 
         let response = if circuit.is_some() {
+            #[cfg(feature = "metrics")]
+            {
+                if let Some(routed_messages) = &self.routed_messages {
+                    routed_messages.record(circuit_name);
+                }
+            }
+
             let node_id = &recipient[ADMIN_SERVICE_ID_PREFIX.len()..];
             // If the service is on this node send message to the service, otherwise
             // send the message to the node the service is connected to
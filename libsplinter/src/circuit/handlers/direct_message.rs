@@ -12,8 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "circuit-durability")]
+use crate::circuit::durable::DurableDelivery;
 use crate::circuit::handlers::create_message;
+#[cfg(feature = "challenge-authorization")]
+use crate::circuit::handlers::find_unauthorized_node;
+#[cfg(feature = "metrics")]
+use crate::circuit::handlers::RoutedMessageCounter;
+#[cfg(feature = "circuit-relay")]
+use crate::circuit::routing::{relay::RelayTable, Circuit};
 use crate::circuit::routing::{RoutingTableReader, ServiceId};
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsRegistry;
+#[cfg(feature = "challenge-authorization")]
+use crate::network::auth::AuthorizationInquisitor;
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
 use crate::protos::circuit::{
     CircuitDirectMessage, CircuitError, CircuitError_Error, CircuitMessageType,
@@ -21,10 +33,22 @@ use crate::protos::circuit::{
 
 use protobuf::Message;
 
+/// The number of nodes a message may be relayed through if its sender did not set a TTL
+#[cfg(feature = "circuit-relay")]
+const DEFAULT_RELAY_TTL: u32 = 8;
+
 // Implements a handler that handles CircuitDirectMessage
 pub struct CircuitDirectMessageHandler {
     node_id: String,
     routing_table: Box<dyn RoutingTableReader>,
+    #[cfg(feature = "metrics")]
+    routed_messages: Option<RoutedMessageCounter>,
+    #[cfg(feature = "circuit-relay")]
+    relay_table: Option<RelayTable>,
+    #[cfg(feature = "circuit-durability")]
+    durable_delivery: Option<DurableDelivery>,
+    #[cfg(feature = "challenge-authorization")]
+    authorization_inquisitor: Option<Box<dyn AuthorizationInquisitor>>,
 }
 
 impl Handler for CircuitDirectMessageHandler {
@@ -60,6 +84,15 @@ impl Handler for CircuitDirectMessageHandler {
             }
         );
 
+        // If the message is being relayed to another node, forward it on toward that node
+        #[cfg(feature = "circuit-relay")]
+        {
+            if !msg.get_destination_node().is_empty() && msg.get_destination_node() != self.node_id
+            {
+                return self.relay(msg, sender);
+            }
+        }
+
         let circuit_name = msg.get_circuit();
         let msg_sender = msg.get_sender();
         let recipient = msg.get_recipient();
@@ -106,15 +139,79 @@ impl Handler for CircuitDirectMessageHandler {
                         .get_service(&recipient_id)
                         .map_err(|err| DispatchError::HandleError(err.to_string()))?
                     {
+                        // Circuits that require challenge authorization only route messages
+                        // between nodes that were authorized using challenge authorization
+                        #[cfg(feature = "challenge-authorization")]
+                        {
+                            let sender_node_id = circuit
+                                .roster()
+                                .iter()
+                                .find(|service| service.service_id() == msg_sender)
+                                .map(|service| service.node_id())
+                                .unwrap_or_default();
+                            if let Some(node_id) = find_unauthorized_node(
+                                &circuit,
+                                &[sender_node_id, service.node_id()],
+                                &self.node_id,
+                                self.authorization_inquisitor.as_deref(),
+                            ) {
+                                warn!(
+                                    "Dropping direct message {} on {}: node {} was not \
+                                     authorized using challenge authorization",
+                                    msg.get_correlation_id(),
+                                    circuit_name,
+                                    node_id
+                                );
+                                return Ok(());
+                            }
+                        }
+
                         let node_id = service.node_id().to_string();
                         // If the service is on this node send message to the service, otherwise
                         // send the message to the node the service is connected to
                         if node_id != self.node_id {
+                            // Messages on store-and-forward circuits are queued until the node
+                            // acknowledges them
+                            #[cfg(feature = "circuit-durability")]
+                            {
+                                if let Some(durable_delivery) = &self.durable_delivery {
+                                    if circuit.store_and_forward() {
+                                        durable_delivery
+                                            .send(
+                                                &node_id,
+                                                circuit.circuit_id(),
+                                                context.message_bytes().to_vec(),
+                                            )
+                                            .map_err(|err| {
+                                                DispatchError::HandleError(err.to_string())
+                                            })?;
+                                        #[cfg(feature = "metrics")]
+                                        self.record_routed_message(circuit_name);
+                                        return Ok(());
+                                    }
+                                }
+                            }
+
+                            #[cfg(feature = "circuit-relay")]
+                            {
+                                if let Some(relay_table) = &self.relay_table {
+                                    return self.forward(
+                                        msg,
+                                        node_id,
+                                        Some(&circuit),
+                                        relay_table,
+                                        sender,
+                                    );
+                                }
+                            }
+
                             let msg_bytes = context.message_bytes().to_vec();
                             let network_msg_bytes = create_message(
                                 msg_bytes,
                                 CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
                             )?;
+                            #[cfg(feature = "metrics")]
+                            self.record_routed_message(circuit_name);
                             (network_msg_bytes, node_id)
                         } else {
                             let msg_bytes = context.message_bytes().to_vec();
@@ -131,6 +228,8 @@ impl Handler for CircuitDirectMessageHandler {
                                     return Ok(());
                                 }
                             };
+                            #[cfg(feature = "metrics")]
+                            self.record_routed_message(circuit_name);
                             (network_msg_bytes, peer_id)
                         }
                     } else {
@@ -202,8 +301,209 @@ impl CircuitDirectMessageHandler {
         CircuitDirectMessageHandler {
             node_id,
             routing_table,
+            #[cfg(feature = "metrics")]
+            routed_messages: None,
+            #[cfg(feature = "circuit-relay")]
+            relay_table: None,
+            #[cfg(feature = "circuit-durability")]
+            durable_delivery: None,
+            #[cfg(feature = "challenge-authorization")]
+            authorization_inquisitor: None,
+        }
+    }
+
+    /// Sets the metrics registry that the number of messages routed per circuit is reported to.
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: MetricsRegistry) -> Self {
+        self.routed_messages = Some(RoutedMessageCounter::new(metrics, "direct"));
+        self
+    }
+
+    /// Counts a message that has been routed on the given circuit, if metrics are enabled.
+    #[cfg(feature = "metrics")]
+    fn record_routed_message(&self, circuit: &str) {
+        if let Some(routed_messages) = &self.routed_messages {
+            routed_messages.record(circuit);
         }
     }
+
+    /// Sets the relay table used to route messages to nodes this node is not directly connected
+    /// to. Without a relay table, messages are always sent directly to the recipient's node and
+    /// relayed messages are dropped.
+    #[cfg(feature = "circuit-relay")]
+    pub fn with_relay_table(mut self, relay_table: RelayTable) -> Self {
+        self.relay_table = Some(relay_table);
+        self
+    }
+
+    /// Sets the durable delivery used to send the messages of store-and-forward circuits to other
+    /// nodes. Without it, these messages are sent like those of any other circuit.
+    #[cfg(feature = "circuit-durability")]
+    pub fn with_durable_delivery(mut self, durable_delivery: DurableDelivery) -> Self {
+        self.durable_delivery = Some(durable_delivery);
+        self
+    }
+
+    /// Sets the authorization inquisitor used to check how the nodes of circuits that require
+    /// challenge authorization were authorized. Without it, messages on these circuits are only
+    /// routed between the local node's services.
+    #[cfg(feature = "challenge-authorization")]
+    pub fn with_authorization_inquisitor(
+        mut self,
+        authorization_inquisitor: Box<dyn AuthorizationInquisitor>,
+    ) -> Self {
+        self.authorization_inquisitor = Some(authorization_inquisitor);
+        self
+    }
+}
+
+#[cfg(feature = "circuit-relay")]
+impl CircuitDirectMessageHandler {
+    /// Forwards a message that is being relayed through this node to another node.
+    fn relay(
+        &self,
+        msg: CircuitDirectMessage,
+        sender: &dyn MessageSender<PeerId>,
+    ) -> Result<(), DispatchError> {
+        let relay_table = match &self.relay_table {
+            Some(relay_table) => relay_table,
+            None => {
+                warn!(
+                    "Dropping direct message {} for node {}: relaying is not enabled",
+                    msg.get_correlation_id(),
+                    msg.get_destination_node()
+                );
+                return Ok(());
+            }
+        };
+
+        // Only the members of the circuit and the allowed relay nodes relay its messages
+        let circuit = self
+            .routing_table
+            .get_circuit(msg.get_circuit())
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+        if circuit.is_none()
+            && !relay_table
+                .is_relay_node(&self.node_id)
+                .map_err(|err| DispatchError::HandleError(err.to_string()))?
+        {
+            warn!(
+                "Dropping direct message {} on {}: this node is not a member of the circuit or \
+                 an allowed relay node",
+                msg.get_correlation_id(),
+                msg.get_circuit()
+            );
+            return Ok(());
+        }
+
+        // A member of the circuit can check the sender against the roster; an allowed relay node
+        // that is not a member relies on the destination node to do so
+        if let Some(circuit) = &circuit {
+            if !circuit
+                .roster()
+                .iter()
+                .any(|service| service.service_id() == msg.get_sender())
+            {
+                warn!(
+                    "Dropping direct message {} on {}: sender {} is not in the circuit roster",
+                    msg.get_correlation_id(),
+                    msg.get_circuit(),
+                    msg.get_sender()
+                );
+                return Ok(());
+            }
+        }
+
+        let destination = msg.get_destination_node().to_string();
+        self.forward(msg, destination, circuit.as_ref(), relay_table, sender)
+    }
+
+    /// Sends a message toward the destination node, through the next hop of the route to the
+    /// destination if there is one. Messages that have run out of hops or that would loop back to
+    /// a node that has already relayed them are dropped.
+    fn forward(
+        &self,
+        mut msg: CircuitDirectMessage,
+        destination: String,
+        circuit: Option<&Circuit>,
+        relay_table: &RelayTable,
+        sender: &dyn MessageSender<PeerId>,
+    ) -> Result<(), DispatchError> {
+        let ttl = if msg.get_hops().is_empty() && msg.get_ttl() == 0 {
+            DEFAULT_RELAY_TTL
+        } else {
+            msg.get_ttl()
+        };
+        if ttl == 0 {
+            warn!(
+                "Dropping direct message {} for node {}: TTL expired",
+                msg.get_correlation_id(),
+                destination
+            );
+            return Ok(());
+        }
+
+        let next_hop = relay_table
+            .next_hop(&destination)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+            .unwrap_or_else(|| destination.clone());
+
+        if next_hop != destination
+            && !circuit
+                .map(|circuit| circuit.members().contains(&next_hop))
+                .unwrap_or(false)
+            && !relay_table
+                .is_relay_node(&next_hop)
+                .map_err(|err| DispatchError::HandleError(err.to_string()))?
+        {
+            warn!(
+                "Dropping direct message {} for node {}: next hop {} is not a member of the \
+                 circuit or an allowed relay node",
+                msg.get_correlation_id(),
+                destination,
+                next_hop
+            );
+            return Ok(());
+        }
+
+        if msg
+            .get_hops()
+            .iter()
+            .any(|hop| hop == &self.node_id || hop == &next_hop)
+        {
+            warn!(
+                "Dropping direct message {} for node {}: routing loop detected through {:?}",
+                msg.get_correlation_id(),
+                destination,
+                msg.get_hops()
+            );
+            return Ok(());
+        }
+
+        msg.set_destination_node(destination);
+        msg.set_ttl(ttl - 1);
+        msg.mut_hops().push(self.node_id.clone());
+
+        let network_msg_bytes = create_message(
+            msg.write_to_bytes()?,
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+        )?;
+        sender
+            .send(next_hop.into(), network_msg_bytes)
+            .map_err(|(recipient, payload)| {
+                DispatchError::NetworkSendError((recipient.into(), payload))
+            })?;
+
+        // Only messages on circuits this node is a member of are counted
+        #[cfg(feature = "metrics")]
+        {
+            if let Some(circuit) = circuit {
+                self.record_routed_message(circuit.circuit_id());
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -592,6 +892,476 @@ mod tests {
         )
     }
 
+    // Test that a direct message for a service on a node that is reached through a relay node is
+    // sent to the relay node, marked with its destination, TTL and hops
+    #[cfg(feature = "circuit-relay")]
+    #[test]
+    fn test_circuit_direct_message_handler_relay_origin() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let reader = relay_test_routing_table(true);
+        let relay_table = RelayTable::default();
+        relay_table
+            .add_route("123".into(), "hub".into())
+            .expect("Unable to add route");
+        relay_table
+            .add_relay_node("hub".into())
+            .expect("Unable to add relay node");
+
+        let handler = CircuitDirectMessageHandler::new("345".to_string(), reader)
+            .with_relay_table(relay_table);
+        dispatcher.set_handler(Box::new(handler));
+
+        dispatcher
+            .dispatch(
+                "def".into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                relay_test_message().write_to_bytes().unwrap(),
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "hub",
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_recipient(), "abc");
+                assert_eq!(msg.get_payload().to_vec(), b"test".to_vec());
+                assert_eq!(msg.get_destination_node(), "123");
+                assert_eq!(msg.get_ttl(), DEFAULT_RELAY_TTL - 1);
+                assert_eq!(msg.get_hops(), &["345".to_string()]);
+            },
+        )
+    }
+
+    // Test that an allowed relay node that is not a member of the circuit forwards a relayed
+    // message to its destination node, and that a node that is not allowed to relay drops it
+    #[cfg(feature = "circuit-relay")]
+    #[test]
+    fn test_circuit_direct_message_handler_relay_forward() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let relay_table = RelayTable::default();
+        relay_table
+            .add_relay_node("hub".into())
+            .expect("Unable to add relay node");
+
+        let handler =
+            CircuitDirectMessageHandler::new("hub".to_string(), relay_test_routing_table(false))
+                .with_relay_table(relay_table);
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = relay_test_message();
+        direct_message.set_destination_node("123".into());
+        direct_message.set_ttl(DEFAULT_RELAY_TTL - 1);
+        direct_message.mut_hops().push("345".into());
+
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_message.write_to_bytes().unwrap(),
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "123",
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_recipient(), "abc");
+                assert_eq!(msg.get_destination_node(), "123");
+                assert_eq!(msg.get_ttl(), DEFAULT_RELAY_TTL - 2);
+                assert_eq!(msg.get_hops(), &["345".to_string(), "hub".to_string()]);
+            },
+        );
+
+        // A node that is not an allowed relay node does not forward the message
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let handler =
+            CircuitDirectMessageHandler::new("other".to_string(), relay_test_routing_table(false))
+                .with_relay_table(RelayTable::default());
+        dispatcher.set_handler(Box::new(handler));
+
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_message.write_to_bytes().unwrap(),
+            )
+            .unwrap();
+
+        assert!(mock_sender.next_outbound().is_none());
+    }
+
+    // Test that a member of the circuit only relays messages whose sender is in the circuit
+    // roster
+    #[cfg(feature = "circuit-relay")]
+    #[test]
+    fn test_circuit_direct_message_handler_relay_sender_not_in_circuit_roster() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let handler =
+            CircuitDirectMessageHandler::new("345".to_string(), relay_test_routing_table(true))
+                .with_relay_table(RelayTable::default());
+        dispatcher.set_handler(Box::new(handler));
+
+        // The sender is not in the roster of circuit alpha
+        let mut direct_message = relay_test_message();
+        direct_message.set_sender("xyz".into());
+        direct_message.set_destination_node("123".into());
+        direct_message.set_ttl(DEFAULT_RELAY_TTL - 1);
+        direct_message.mut_hops().push("other".into());
+
+        dispatcher
+            .dispatch(
+                "other".into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_message.write_to_bytes().unwrap(),
+            )
+            .unwrap();
+        assert!(mock_sender.next_outbound().is_none());
+
+        // The same message from a service in the roster is relayed
+        direct_message.set_sender("def".into());
+
+        dispatcher
+            .dispatch(
+                "other".into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_message.write_to_bytes().unwrap(),
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "123",
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_sender(), "def");
+                assert_eq!(msg.get_destination_node(), "123");
+                assert_eq!(msg.get_hops(), &["other".to_string(), "345".to_string()]);
+            },
+        );
+    }
+
+    // Test that relayed messages that have run out of hops or that would loop back through a node
+    // that already relayed them are dropped
+    #[cfg(feature = "circuit-relay")]
+    #[test]
+    fn test_circuit_direct_message_handler_relay_ttl_and_loop() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let relay_table = RelayTable::default();
+        relay_table
+            .add_route("123".into(), "345".into())
+            .expect("Unable to add route");
+        relay_table
+            .add_relay_node("hub".into())
+            .expect("Unable to add relay node");
+        relay_table
+            .add_relay_node("345".into())
+            .expect("Unable to add relay node");
+
+        let handler =
+            CircuitDirectMessageHandler::new("hub".to_string(), relay_test_routing_table(false))
+                .with_relay_table(relay_table);
+        dispatcher.set_handler(Box::new(handler));
+
+        // The message has no hops left
+        let mut expired_message = relay_test_message();
+        expired_message.set_destination_node("123".into());
+        expired_message.set_ttl(0);
+        expired_message.mut_hops().push("other".into());
+
+        dispatcher
+            .dispatch(
+                "other".into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                expired_message.write_to_bytes().unwrap(),
+            )
+            .unwrap();
+        assert!(mock_sender.next_outbound().is_none());
+
+        // The next hop has already relayed the message
+        let mut looping_message = relay_test_message();
+        looping_message.set_destination_node("123".into());
+        looping_message.set_ttl(DEFAULT_RELAY_TTL - 1);
+        looping_message.mut_hops().push("345".into());
+
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                looping_message.write_to_bytes().unwrap(),
+            )
+            .unwrap();
+        assert!(mock_sender.next_outbound().is_none());
+    }
+
+    // Test that a direct message on a store-and-forward circuit is queued and sent to the
+    // recipient's node as a durable message
+    #[cfg(feature = "circuit-durability")]
+    #[test]
+    fn test_circuit_direct_message_handler_store_and_forward() {
+        use crate::circuit::durable::store::{DurableMessageStore, MemoryDurableMessageStore};
+        use crate::protos::circuit::CircuitDurableMessage;
+
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()]);
+        let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()]);
+
+        let service_abc = Service::new(
+            "abc".to_string(),
+            "test".to_string(),
+            "123".to_string(),
+            vec![],
+        );
+        let mut service_def = Service::new(
+            "def".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+        service_def.set_peer_id("def_network".to_string());
+
+        let circuit = Circuit::new(
+            "alpha".into(),
+            vec![service_abc, service_def],
+            vec!["123".into(), "345".into()],
+        )
+        .with_store_and_forward(true);
+
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123, node_345],
+            )
+            .expect("Unable to add circuits");
+
+        let store = MemoryDurableMessageStore::new();
+        let durable_delivery = DurableDelivery::new(
+            Box::new(store.clone()),
+            Box::new(mock_sender.clone()),
+            reader.clone(),
+        );
+        let handler = CircuitDirectMessageHandler::new("345".to_string(), reader)
+            .with_durable_delivery(durable_delivery);
+        dispatcher.set_handler(Box::new(handler));
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("alpha".into());
+        direct_message.set_sender("def".into());
+        direct_message.set_recipient("abc".into());
+        direct_message.set_payload(b"test".to_vec());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        dispatcher
+            .dispatch(
+                "def".into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_bytes.clone(),
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "123",
+            CircuitMessageType::CIRCUIT_DURABLE_MESSAGE,
+            |msg: CircuitDurableMessage| {
+                assert_eq!(msg.get_circuit_id(), "alpha");
+                assert_eq!(msg.get_sequence_number(), 1);
+                assert_eq!(msg.get_payload(), &direct_bytes[..]);
+            },
+        );
+        assert_eq!(
+            store
+                .list_outbound_messages("123")
+                .expect("Unable to list messages")
+                .len(),
+            1
+        );
+    }
+
+    // Test that a direct message on a circuit that requires challenge authorization is only routed
+    // to a node that was authorized using challenge authorization
+    #[cfg(feature = "challenge-authorization")]
+    #[test]
+    fn test_circuit_direct_message_handler_challenge_authorization() {
+        use crate::network::auth::AuthorizationInquisitor;
+        use crate::protocol::authorization::AuthorizationType;
+
+        struct MockAuthorizationInquisitor(AuthorizationType);
+
+        impl AuthorizationInquisitor for MockAuthorizationInquisitor {
+            fn authorization_type(&self, _identity: &str) -> Option<AuthorizationType> {
+                Some(self.0)
+            }
+        }
+
+        let table = RoutingTable::default();
+        let reader: Box<dyn RoutingTableReader> = Box::new(table.clone());
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()]);
+        let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()]);
+
+        let service_abc = Service::new(
+            "abc".to_string(),
+            "test".to_string(),
+            "123".to_string(),
+            vec![],
+        );
+        let mut service_def = Service::new(
+            "def".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+        service_def.set_peer_id("def_network".to_string());
+
+        let circuit = Circuit::new(
+            "alpha".into(),
+            vec![service_abc, service_def],
+            vec!["123".into(), "345".into()],
+        )
+        .with_challenge_authorization(true);
+
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123, node_345],
+            )
+            .expect("Unable to add circuits");
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("alpha".into());
+        direct_message.set_sender("def".into());
+        direct_message.set_recipient("abc".into());
+        direct_message.set_payload(b"test".to_vec());
+        let direct_bytes = direct_message.write_to_bytes().unwrap();
+
+        // node 123 was only trusted, so the message is dropped
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let handler = CircuitDirectMessageHandler::new("345".to_string(), reader.clone())
+            .with_authorization_inquisitor(Box::new(MockAuthorizationInquisitor(
+                AuthorizationType::Trust,
+            )));
+        dispatcher.set_handler(Box::new(handler));
+        dispatcher
+            .dispatch(
+                "def".into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_bytes.clone(),
+            )
+            .unwrap();
+        assert!(mock_sender.next_outbound().is_none());
+
+        // node 123 was authorized using challenge authorization, so the message is routed
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let handler = CircuitDirectMessageHandler::new("345".to_string(), reader)
+            .with_authorization_inquisitor(Box::new(MockAuthorizationInquisitor(
+                AuthorizationType::Challenge,
+            )));
+        dispatcher.set_handler(Box::new(handler));
+        dispatcher
+            .dispatch(
+                "def".into(),
+                &CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                direct_bytes,
+            )
+            .unwrap();
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "123",
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_circuit(), "alpha");
+                assert_eq!(msg.get_recipient(), "abc");
+            },
+        );
+    }
+
+    /// Creates a routing table with circuit alpha, where service abc is connected to node 123 and
+    /// service def is connected to node 345. If `with_circuit` is false, the table is empty.
+    #[cfg(feature = "circuit-relay")]
+    fn relay_test_routing_table(with_circuit: bool) -> Box<dyn RoutingTableReader> {
+        let table = RoutingTable::default();
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        if with_circuit {
+            let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()]);
+            let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()]);
+
+            let service_abc = Service::new(
+                "abc".to_string(),
+                "test".to_string(),
+                "123".to_string(),
+                vec![],
+            );
+            let mut service_def = Service::new(
+                "def".to_string(),
+                "test".to_string(),
+                "345".to_string(),
+                vec![],
+            );
+            service_def.set_peer_id("def_network".to_string());
+
+            let circuit = Circuit::new(
+                "alpha".into(),
+                vec![service_abc, service_def],
+                vec!["123".into(), "345".into()],
+            );
+
+            writer
+                .add_circuit(
+                    circuit.circuit_id().into(),
+                    circuit,
+                    vec![node_123, node_345],
+                )
+                .expect("Unable to add circuits");
+        }
+
+        Box::new(table)
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    fn relay_test_message() -> CircuitDirectMessage {
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit("alpha".into());
+        direct_message.set_sender("def".into());
+        direct_message.set_recipient("abc".into());
+        direct_message.set_payload(b"test".to_vec());
+        direct_message.set_correlation_id("1234".into());
+        direct_message
+    }
+
     fn assert_network_message<M: protobuf::Message, F: Fn(M)>(
         message: Vec<u8>,
         recipient: String,
@@ -0,0 +1,760 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::circuit::durable::{store::DurableMessageStore, DurableDelivery};
+use crate::circuit::handlers::create_message;
+#[cfg(feature = "challenge-authorization")]
+use crate::circuit::handlers::find_unauthorized_node;
+use crate::circuit::routing::{RoutingTableReader, ServiceId};
+#[cfg(feature = "challenge-authorization")]
+use crate::network::auth::AuthorizationInquisitor;
+use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
+use crate::protos::circuit::{
+    CircuitDirectMessage, CircuitDurableMessage, CircuitDurableMessageAck, CircuitMessageType,
+};
+
+use protobuf::Message;
+
+// Implements a handler that handles CircuitDurableMessage
+pub struct CircuitDurableMessageHandler {
+    node_id: String,
+    routing_table: Box<dyn RoutingTableReader>,
+    store: Box<dyn DurableMessageStore>,
+    #[cfg(feature = "challenge-authorization")]
+    authorization_inquisitor: Option<Box<dyn AuthorizationInquisitor>>,
+}
+
+impl Handler for CircuitDurableMessageHandler {
+    type Source = PeerId;
+    type MessageType = CircuitMessageType;
+    type Message = CircuitDurableMessage;
+
+    fn match_type(&self) -> Self::MessageType {
+        CircuitMessageType::CIRCUIT_DURABLE_MESSAGE
+    }
+
+    fn handle(
+        &self,
+        msg: Self::Message,
+        context: &MessageContext<Self::Source, Self::MessageType>,
+        sender: &dyn MessageSender<Self::Source>,
+    ) -> Result<(), DispatchError> {
+        let source_node_id = context.source_peer_id();
+        let circuit_id = msg.get_circuit_id();
+        let sequence_number = msg.get_sequence_number();
+        debug!(
+            "Handle Circuit Durable Message {} from {} on circuit {}",
+            sequence_number, source_node_id, circuit_id
+        );
+
+        let mut direct_message: CircuitDirectMessage =
+            Message::parse_from_bytes(msg.get_payload())?;
+        if direct_message.get_circuit() != circuit_id {
+            return Err(DispatchError::HandleError(format!(
+                "Durable message {} from {} on circuit {} contains a message for circuit {}",
+                sequence_number,
+                source_node_id,
+                circuit_id,
+                direct_message.get_circuit()
+            )));
+        }
+
+        #[cfg(feature = "challenge-authorization")]
+        {
+            if !self.is_authorized_sender(source_node_id, circuit_id)? {
+                debug!(
+                    "Unable to deliver durable message {} from {} on circuit {}: the node was \
+                     not authorized using challenge authorization",
+                    sequence_number, source_node_id, circuit_id
+                );
+                return Ok(());
+            }
+        }
+
+        let last_delivered = self
+            .store
+            .get_last_delivered(source_node_id, circuit_id)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+
+        // The message has already been delivered, so the acknowledgement was lost
+        if sequence_number <= last_delivered {
+            return send_ack(source_node_id, circuit_id, last_delivered, sender);
+        }
+
+        // An earlier message has not been delivered yet; it will be resent with this one
+        if sequence_number > last_delivered + 1 {
+            debug!(
+                "Dropping durable message {} from {} on circuit {}: expected message {}",
+                sequence_number,
+                source_node_id,
+                circuit_id,
+                last_delivered + 1
+            );
+            return Ok(());
+        }
+
+        match self.route(&direct_message)? {
+            Route::Deliver(peer_id) => {
+                direct_message.set_durable_sequence_number(sequence_number);
+                let network_msg_bytes = create_message(
+                    direct_message.write_to_bytes()?,
+                    CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+                )?;
+                sender.send(peer_id.into(), network_msg_bytes).map_err(
+                    |(recipient, payload)| {
+                        DispatchError::NetworkSendError((recipient.into(), payload))
+                    },
+                )?;
+            }
+            Route::Retry => {
+                // The message is not acknowledged, so it will be resent until it can be
+                // delivered
+                debug!(
+                    "Unable to deliver durable message {} from {} on circuit {}: recipient {} is \
+                     not connected",
+                    sequence_number,
+                    source_node_id,
+                    circuit_id,
+                    direct_message.get_recipient()
+                );
+                return Ok(());
+            }
+            Route::DeadLetter => {
+                // The message can never be delivered, so it is acknowledged to stop it from
+                // holding up the messages after it
+                warn!(
+                    "Discarding undeliverable durable message {} from {} on circuit {}: {} is \
+                     not a member of the circuit connected to this node",
+                    sequence_number,
+                    source_node_id,
+                    circuit_id,
+                    direct_message.get_recipient()
+                );
+            }
+        }
+
+        self.store
+            .set_last_delivered(source_node_id, circuit_id, sequence_number)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?;
+
+        send_ack(source_node_id, circuit_id, sequence_number, sender)
+    }
+}
+
+/// Where a durable message should be sent.
+enum Route {
+    /// Deliver the message to the recipient service with the given peer ID
+    Deliver(String),
+    /// The message can't be delivered yet, but may be once the circuit is added or the recipient
+    /// service connects
+    Retry,
+    /// The message can never be delivered by this node
+    DeadLetter,
+}
+
+impl CircuitDurableMessageHandler {
+    pub fn new(
+        node_id: String,
+        routing_table: Box<dyn RoutingTableReader>,
+        store: Box<dyn DurableMessageStore>,
+    ) -> Self {
+        CircuitDurableMessageHandler {
+            node_id,
+            routing_table,
+            store,
+            #[cfg(feature = "challenge-authorization")]
+            authorization_inquisitor: None,
+        }
+    }
+
+    /// Sets the authorization inquisitor used to check how the nodes sending the messages of
+    /// circuits that require challenge authorization were authorized. Without it, messages from
+    /// other nodes on these circuits are not delivered.
+    #[cfg(feature = "challenge-authorization")]
+    pub fn with_authorization_inquisitor(
+        mut self,
+        authorization_inquisitor: Box<dyn AuthorizationInquisitor>,
+    ) -> Self {
+        self.authorization_inquisitor = Some(authorization_inquisitor);
+        self
+    }
+
+    /// Returns whether the given node was authorized as the circuit requires. Messages from a node
+    /// that was not are left unacknowledged, so they are resent once it has been.
+    #[cfg(feature = "challenge-authorization")]
+    fn is_authorized_sender(
+        &self,
+        source_node_id: &str,
+        circuit_id: &str,
+    ) -> Result<bool, DispatchError> {
+        // This node may not have added the circuit yet; the message is then retried by `route`
+        let circuit = match self
+            .routing_table
+            .get_circuit(circuit_id)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+        {
+            Some(circuit) => circuit,
+            None => return Ok(true),
+        };
+
+        Ok(find_unauthorized_node(
+            &circuit,
+            &[source_node_id],
+            &self.node_id,
+            self.authorization_inquisitor.as_deref(),
+        )
+        .is_none())
+    }
+
+    /// Determines how a message should be routed. A message can only be delivered if its sender
+    /// and recipient are members of the circuit and the recipient is connected to this node.
+    fn route(&self, msg: &CircuitDirectMessage) -> Result<Route, DispatchError> {
+        // This node may not have added the circuit yet
+        let circuit = match self
+            .routing_table
+            .get_circuit(msg.get_circuit())
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+        {
+            Some(circuit) => circuit,
+            None => return Ok(Route::Retry),
+        };
+
+        let roster = circuit.roster();
+        if !roster
+            .iter()
+            .any(|service| service.service_id() == msg.get_sender())
+            || !roster
+                .iter()
+                .any(|service| service.service_id() == msg.get_recipient())
+        {
+            return Ok(Route::DeadLetter);
+        }
+
+        let recipient_id = ServiceId::new(
+            msg.get_circuit().to_string(),
+            msg.get_recipient().to_string(),
+        );
+        let route = match self
+            .routing_table
+            .get_service(&recipient_id)
+            .map_err(|err| DispatchError::HandleError(err.to_string()))?
+        {
+            Some(service) if service.node_id() == self.node_id => match service.peer_id() {
+                Some(peer_id) => Route::Deliver(peer_id.clone()),
+                None => Route::Retry,
+            },
+            _ => Route::DeadLetter,
+        };
+
+        Ok(route)
+    }
+}
+
+fn send_ack(
+    destination_node_id: &str,
+    circuit_id: &str,
+    sequence_number: u64,
+    sender: &dyn MessageSender<PeerId>,
+) -> Result<(), DispatchError> {
+    let mut ack = CircuitDurableMessageAck::new();
+    ack.set_circuit_id(circuit_id.to_string());
+    ack.set_sequence_number(sequence_number);
+    let network_msg_bytes = create_message(
+        ack.write_to_bytes()?,
+        CircuitMessageType::CIRCUIT_DURABLE_MESSAGE_ACK,
+    )?;
+
+    sender
+        .send(destination_node_id.into(), network_msg_bytes)
+        .map_err(|(recipient, payload)| {
+            DispatchError::NetworkSendError((recipient.into(), payload))
+        })
+}
+
+// Implements a handler that handles CircuitDurableMessageAck
+pub struct CircuitDurableMessageAckHandler {
+    durable_delivery: DurableDelivery,
+}
+
+impl Handler for CircuitDurableMessageAckHandler {
+    type Source = PeerId;
+    type MessageType = CircuitMessageType;
+    type Message = CircuitDurableMessageAck;
+
+    fn match_type(&self) -> Self::MessageType {
+        CircuitMessageType::CIRCUIT_DURABLE_MESSAGE_ACK
+    }
+
+    fn handle(
+        &self,
+        msg: Self::Message,
+        context: &MessageContext<Self::Source, Self::MessageType>,
+        _sender: &dyn MessageSender<Self::Source>,
+    ) -> Result<(), DispatchError> {
+        debug!(
+            "Handle Circuit Durable Message Ack {} from {} on circuit {}",
+            msg.get_sequence_number(),
+            context.source_peer_id(),
+            msg.get_circuit_id()
+        );
+
+        self.durable_delivery
+            .acknowledge(
+                context.source_peer_id(),
+                msg.get_circuit_id(),
+                msg.get_sequence_number(),
+            )
+            .map_err(|err| DispatchError::HandleError(err.to_string()))
+    }
+}
+
+impl CircuitDurableMessageAckHandler {
+    pub fn new(durable_delivery: DurableDelivery) -> Self {
+        CircuitDurableMessageAckHandler { durable_delivery }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    use crate::circuit::durable::store::MemoryDurableMessageStore;
+    use crate::circuit::routing::{
+        memory::RoutingTable, Circuit, CircuitNode, RoutingTableWriter, Service,
+    };
+    use crate::network::dispatch::Dispatcher;
+    use crate::protos::circuit::CircuitMessage;
+    use crate::protos::network::NetworkMessage;
+
+    // Test that durable messages are delivered to the local recipient service in sequence and
+    // acknowledged, that a message that is sent again is acknowledged but not delivered twice,
+    // and that a message that arrives out of sequence is dropped
+    #[test]
+    fn test_circuit_durable_message_handler() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let store = MemoryDurableMessageStore::new();
+
+        let handler = CircuitDurableMessageHandler::new(
+            "123".to_string(),
+            durable_test_routing_table(),
+            Box::new(store.clone()),
+        );
+        dispatcher.set_handler(Box::new(handler));
+
+        // Deliver the first message
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DURABLE_MESSAGE,
+                durable_test_message("alpha", "def", 1, b"one"),
+            )
+            .expect("Unable to dispatch message");
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "abc_network",
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| {
+                assert_eq!(msg.get_payload(), b"one");
+                assert_eq!(msg.get_durable_sequence_number(), 1);
+            },
+        );
+        let (id, message) = mock_sender.next_outbound().expect("No ack was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "345",
+            CircuitMessageType::CIRCUIT_DURABLE_MESSAGE_ACK,
+            |msg: CircuitDurableMessageAck| {
+                assert_eq!(msg.get_circuit_id(), "alpha");
+                assert_eq!(msg.get_sequence_number(), 1);
+            },
+        );
+        assert_eq!(store.get_last_delivered("345", "alpha").unwrap(), 1);
+
+        // Resend the first message; it is acknowledged again but not delivered
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DURABLE_MESSAGE,
+                durable_test_message("alpha", "def", 1, b"one"),
+            )
+            .expect("Unable to dispatch message");
+
+        let (id, message) = mock_sender.next_outbound().expect("No ack was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "345",
+            CircuitMessageType::CIRCUIT_DURABLE_MESSAGE_ACK,
+            |msg: CircuitDurableMessageAck| {
+                assert_eq!(msg.get_circuit_id(), "alpha");
+                assert_eq!(msg.get_sequence_number(), 1);
+            },
+        );
+        assert!(mock_sender.next_outbound().is_none());
+
+        // Skip the second message; the third is dropped
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DURABLE_MESSAGE,
+                durable_test_message("alpha", "def", 3, b"three"),
+            )
+            .expect("Unable to dispatch message");
+
+        assert!(mock_sender.next_outbound().is_none());
+        assert_eq!(store.get_last_delivered("345", "alpha").unwrap(), 1);
+    }
+
+    // Test that an acknowledgement removes the acknowledged messages from the store
+    #[test]
+    fn test_circuit_durable_message_ack_handler() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let store = MemoryDurableMessageStore::new();
+        let durable_delivery = DurableDelivery::new(
+            Box::new(store.clone()),
+            Box::new(mock_sender.clone()),
+            durable_test_routing_table(),
+        );
+
+        durable_delivery
+            .send("345", "alpha", b"one".to_vec())
+            .expect("Unable to send message");
+        durable_delivery
+            .send("345", "alpha", b"two".to_vec())
+            .expect("Unable to send message");
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "345",
+            CircuitMessageType::CIRCUIT_DURABLE_MESSAGE,
+            |msg: CircuitDurableMessage| {
+                assert_eq!(msg.get_circuit_id(), "alpha");
+                assert_eq!(msg.get_sequence_number(), 1);
+                assert_eq!(msg.get_payload(), b"one");
+            },
+        );
+
+        dispatcher.set_handler(Box::new(CircuitDurableMessageAckHandler::new(
+            durable_delivery,
+        )));
+
+        let mut ack = CircuitDurableMessageAck::new();
+        ack.set_circuit_id("alpha".into());
+        ack.set_sequence_number(1);
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DURABLE_MESSAGE_ACK,
+                ack.write_to_bytes().unwrap(),
+            )
+            .expect("Unable to dispatch ack");
+
+        let remaining = store.list_outbound_messages("345").unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].sequence_number, 2);
+    }
+
+    // Test that a durable message whose sender is not a member of the circuit is acknowledged
+    // without being delivered, and does not hold up the messages after it
+    #[test]
+    fn test_circuit_durable_message_handler_dead_letter() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let store = MemoryDurableMessageStore::new();
+
+        let handler = CircuitDurableMessageHandler::new(
+            "123".to_string(),
+            durable_test_routing_table(),
+            Box::new(store.clone()),
+        );
+        dispatcher.set_handler(Box::new(handler));
+
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DURABLE_MESSAGE,
+                durable_test_message("alpha", "xyz", 1, b"one"),
+            )
+            .expect("Unable to dispatch message");
+
+        let (id, message) = mock_sender.next_outbound().expect("No ack was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "345",
+            CircuitMessageType::CIRCUIT_DURABLE_MESSAGE_ACK,
+            |msg: CircuitDurableMessageAck| assert_eq!(msg.get_sequence_number(), 1),
+        );
+        assert!(mock_sender.next_outbound().is_none());
+        assert_eq!(store.get_last_delivered("345", "alpha").unwrap(), 1);
+
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DURABLE_MESSAGE,
+                durable_test_message("alpha", "def", 2, b"two"),
+            )
+            .expect("Unable to dispatch message");
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "abc_network",
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| assert_eq!(msg.get_payload(), b"two"),
+        );
+        assert_eq!(store.get_last_delivered("345", "alpha").unwrap(), 2);
+    }
+
+    // Test that a durable message that can't be delivered yet only holds up the messages on its
+    // own circuit
+    #[test]
+    fn test_circuit_durable_message_handler_per_circuit() {
+        let mock_sender = MockSender::new();
+        let mut dispatcher = Dispatcher::new(Box::new(mock_sender.clone()));
+        let store = MemoryDurableMessageStore::new();
+
+        let handler = CircuitDurableMessageHandler::new(
+            "123".to_string(),
+            durable_test_routing_table(),
+            Box::new(store.clone()),
+        );
+        dispatcher.set_handler(Box::new(handler));
+
+        // The recipient on beta is not connected, so the message is not acknowledged
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DURABLE_MESSAGE,
+                durable_test_message("beta", "jkl", 1, b"beta"),
+            )
+            .expect("Unable to dispatch message");
+        assert!(mock_sender.next_outbound().is_none());
+        assert_eq!(store.get_last_delivered("345", "beta").unwrap(), 0);
+
+        dispatcher
+            .dispatch(
+                "345".into(),
+                &CircuitMessageType::CIRCUIT_DURABLE_MESSAGE,
+                durable_test_message("alpha", "def", 1, b"alpha"),
+            )
+            .expect("Unable to dispatch message");
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "abc_network",
+            CircuitMessageType::CIRCUIT_DIRECT_MESSAGE,
+            |msg: CircuitDirectMessage| assert_eq!(msg.get_payload(), b"alpha"),
+        );
+        let (id, message) = mock_sender.next_outbound().expect("No ack was sent");
+        assert_network_message(
+            message,
+            id.into(),
+            "345",
+            CircuitMessageType::CIRCUIT_DURABLE_MESSAGE_ACK,
+            |msg: CircuitDurableMessageAck| {
+                assert_eq!(msg.get_circuit_id(), "alpha");
+                assert_eq!(msg.get_sequence_number(), 1);
+            },
+        );
+    }
+
+    // Test that resending discards the queued messages of a circuit that has been removed
+    #[test]
+    fn test_resend_discards_removed_circuit() {
+        let mock_sender = MockSender::new();
+        let store = MemoryDurableMessageStore::new();
+        let durable_delivery = DurableDelivery::new(
+            Box::new(store.clone()),
+            Box::new(mock_sender.clone()),
+            durable_test_routing_table(),
+        );
+
+        durable_delivery
+            .send("345", "alpha", b"one".to_vec())
+            .expect("Unable to send message");
+        durable_delivery
+            .send("345", "removed", b"two".to_vec())
+            .expect("Unable to send message");
+        assert!(mock_sender.next_outbound().is_some());
+        assert!(mock_sender.next_outbound().is_some());
+
+        durable_delivery
+            .resend("345")
+            .expect("Unable to resend messages");
+
+        let (id, message) = mock_sender.next_outbound().expect("No message was resent");
+        assert_network_message(
+            message,
+            id.into(),
+            "345",
+            CircuitMessageType::CIRCUIT_DURABLE_MESSAGE,
+            |msg: CircuitDurableMessage| assert_eq!(msg.get_circuit_id(), "alpha"),
+        );
+        assert!(mock_sender.next_outbound().is_none());
+
+        let remaining = store.list_outbound_messages("345").unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].circuit_id, "alpha");
+    }
+
+    fn durable_test_routing_table() -> Box<dyn RoutingTableReader> {
+        let table = RoutingTable::default();
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let node_123 = CircuitNode::new("123".to_string(), vec!["123.0.0.1:0".to_string()]);
+        let node_345 = CircuitNode::new("345".to_string(), vec!["123.0.0.1:1".to_string()]);
+
+        let mut service_abc = Service::new(
+            "abc".to_string(),
+            "test".to_string(),
+            "123".to_string(),
+            vec![],
+        );
+        let service_def = Service::new(
+            "def".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+        service_abc.set_peer_id("abc_network".to_string());
+
+        let circuit = Circuit::new(
+            "alpha".into(),
+            vec![service_abc, service_def],
+            vec!["123".into(), "345".into()],
+        )
+        .with_store_and_forward(true);
+
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123.clone(), node_345.clone()],
+            )
+            .expect("Unable to add circuits");
+
+        // The service on this node is not connected
+        let service_ghi = Service::new(
+            "ghi".to_string(),
+            "test".to_string(),
+            "123".to_string(),
+            vec![],
+        );
+        let service_jkl = Service::new(
+            "jkl".to_string(),
+            "test".to_string(),
+            "345".to_string(),
+            vec![],
+        );
+
+        let circuit = Circuit::new(
+            "beta".into(),
+            vec![service_ghi, service_jkl],
+            vec!["123".into(), "345".into()],
+        )
+        .with_store_and_forward(true);
+
+        writer
+            .add_circuit(
+                circuit.circuit_id().into(),
+                circuit,
+                vec![node_123, node_345],
+            )
+            .expect("Unable to add circuits");
+
+        Box::new(table)
+    }
+
+    fn durable_test_message(
+        circuit_id: &str,
+        sender: &str,
+        sequence_number: u64,
+        payload: &[u8],
+    ) -> Vec<u8> {
+        let recipient = if circuit_id == "beta" { "ghi" } else { "abc" };
+
+        let mut direct_message = CircuitDirectMessage::new();
+        direct_message.set_circuit(circuit_id.into());
+        direct_message.set_sender(sender.into());
+        direct_message.set_recipient(recipient.into());
+        direct_message.set_payload(payload.to_vec());
+
+        let mut durable_message = CircuitDurableMessage::new();
+        durable_message.set_circuit_id(circuit_id.into());
+        durable_message.set_sequence_number(sequence_number);
+        durable_message.set_payload(direct_message.write_to_bytes().unwrap());
+        durable_message.write_to_bytes().unwrap()
+    }
+
+    fn assert_network_message<M: protobuf::Message, F: Fn(M)>(
+        message: Vec<u8>,
+        recipient: String,
+        expected_recipient: &str,
+        expected_circuit_msg_type: CircuitMessageType,
+        detail_assertions: F,
+    ) {
+        assert_eq!(expected_recipient, &recipient);
+
+        let network_msg: NetworkMessage = Message::parse_from_bytes(&message).unwrap();
+        let circuit_msg: CircuitMessage =
+            Message::parse_from_bytes(network_msg.get_payload()).unwrap();
+        assert_eq!(expected_circuit_msg_type, circuit_msg.get_message_type(),);
+        let circuit_msg: M = Message::parse_from_bytes(circuit_msg.get_payload()).unwrap();
+
+        detail_assertions(circuit_msg);
+    }
+
+    #[derive(Clone)]
+    struct MockSender {
+        outbound: Arc<Mutex<VecDeque<(PeerId, Vec<u8>)>>>,
+    }
+
+    impl MockSender {
+        fn new() -> Self {
+            Self {
+                outbound: Arc::new(Mutex::new(VecDeque::new())),
+            }
+        }
+
+        fn next_outbound(&self) -> Option<(PeerId, Vec<u8>)> {
+            self.outbound.lock().expect("lock was poisoned").pop_front()
+        }
+    }
+
+    impl MessageSender<PeerId> for MockSender {
+        fn send(&self, id: PeerId, message: Vec<u8>) -> Result<(), (PeerId, Vec<u8>)> {
+            self.outbound
+                .lock()
+                .expect("lock was poisoned")
+                .push_back((id, message));
+
+            Ok(())
+        }
+    }
+}
@@ -16,10 +16,25 @@ mod admin_message;
 mod circuit_error;
 mod circuit_message;
 mod direct_message;
+#[cfg(feature = "circuit-durability")]
+mod durable_message;
 mod service_handlers;
 
+#[cfg(feature = "metrics")]
+use std::collections::HashMap;
+#[cfg(feature = "metrics")]
+use std::sync::Mutex;
+
 use protobuf::Message;
 
+#[cfg(feature = "challenge-authorization")]
+use crate::circuit::routing::Circuit;
+#[cfg(feature = "metrics")]
+use crate::metrics::{Counter, MetricsRegistry};
+#[cfg(feature = "challenge-authorization")]
+use crate::network::auth::AuthorizationInquisitor;
+#[cfg(feature = "challenge-authorization")]
+use crate::protocol::authorization::AuthorizationType;
 use crate::protos::circuit::{CircuitMessage, CircuitMessageType};
 use crate::protos::network::{NetworkMessage, NetworkMessageType};
 
@@ -27,10 +42,12 @@ pub use self::admin_message::AdminDirectMessageHandler;
 pub use self::circuit_error::CircuitErrorHandler;
 pub use self::circuit_message::CircuitMessageHandler;
 pub use self::direct_message::CircuitDirectMessageHandler;
+#[cfg(feature = "circuit-durability")]
+pub use self::durable_message::{CircuitDurableMessageAckHandler, CircuitDurableMessageHandler};
 pub use self::service_handlers::ServiceConnectRequestHandler;
 pub use self::service_handlers::ServiceDisconnectRequestHandler;
 
-fn create_message(
+pub(in crate::circuit) fn create_message(
     payload: Vec<u8>,
     circuit_message_type: CircuitMessageType,
 ) -> Result<Vec<u8>, protobuf::error::ProtobufError> {
@@ -44,3 +61,107 @@ fn create_message(
     network_msg.set_payload(circuit_bytes);
     network_msg.write_to_bytes()
 }
+
+/// Returns the first of the given nodes that may not send or receive the messages of the circuit,
+/// if any. The local node always may; if the circuit requires challenge authorization, other nodes
+/// must have been authorized using challenge authorization.
+#[cfg(feature = "challenge-authorization")]
+fn find_unauthorized_node<'a>(
+    circuit: &Circuit,
+    node_ids: &[&'a str],
+    local_node_id: &str,
+    authorization_inquisitor: Option<&dyn AuthorizationInquisitor>,
+) -> Option<&'a str> {
+    if !circuit.challenge_authorization() {
+        return None;
+    }
+
+    node_ids
+        .iter()
+        .copied()
+        .filter(|node_id| *node_id != local_node_id)
+        .find(|node_id| {
+            authorization_inquisitor.and_then(|inquisitor| inquisitor.authorization_type(node_id))
+                != Some(AuthorizationType::Challenge)
+        })
+}
+
+/// Counts the messages of one type that are routed on each circuit.
+///
+/// The counter for each circuit is looked up in the metrics registry the first time a message is
+/// routed on the circuit, and reused for the messages after it.
+#[cfg(feature = "metrics")]
+struct RoutedMessageCounter {
+    metrics: MetricsRegistry,
+    message_type: &'static str,
+    counters: Mutex<HashMap<String, Counter>>,
+}
+
+#[cfg(feature = "metrics")]
+impl RoutedMessageCounter {
+    fn new(metrics: MetricsRegistry, message_type: &'static str) -> Self {
+        RoutedMessageCounter {
+            metrics,
+            message_type,
+            counters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Counts a message that has been routed on the given circuit.
+    fn record(&self, circuit: &str) {
+        let mut counters = match self.counters.lock() {
+            Ok(counters) => counters,
+            Err(err) => err.into_inner(),
+        };
+
+        if let Some(counter) = counters.get(circuit) {
+            counter.inc();
+            return;
+        }
+
+        let counter = self.metrics.counter(
+            "splinter_circuit_messages_routed_total",
+            "Number of messages routed by this node, by circuit and message type",
+            &[("circuit", circuit), ("message_type", self.message_type)],
+        );
+        counter.inc();
+        counters.insert(circuit.to_string(), counter);
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::*;
+
+    // Test that the routed message counter counts the messages routed on each circuit separately,
+    // reusing the counter handle it cached for a circuit
+    #[test]
+    fn test_routed_message_counter() {
+        let metrics = MetricsRegistry::new();
+        let routed_messages = RoutedMessageCounter::new(metrics.clone(), "direct");
+
+        routed_messages.record("alpha");
+        routed_messages.record("alpha");
+        routed_messages.record("beta");
+
+        let count = |circuit: &str| {
+            metrics
+                .counter(
+                    "splinter_circuit_messages_routed_total",
+                    "",
+                    &[("circuit", circuit), ("message_type", "direct")],
+                )
+                .get()
+        };
+        assert_eq!(count("alpha"), 2);
+        assert_eq!(count("beta"), 1);
+        assert_eq!(
+            routed_messages
+                .counters
+                .lock()
+                .expect("lock was poisoned")
+                .len(),
+            2
+        );
+    }
+}
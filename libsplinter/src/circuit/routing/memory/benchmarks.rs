@@ -76,6 +76,10 @@ fn generate_circuits(num_circuits: i64, total_num_node: i64) -> (Vec<Circuit>, V
             ),
             roster,
             members,
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization: false,
+            #[cfg(feature = "circuit-durability")]
+            store_and_forward: false,
         };
         circuits.push(circuit);
     }
@@ -332,6 +332,58 @@ impl RoutingTableWriter for RoutingTable {
         Ok(())
     }
 
+    /// Updates the roster and members of a circuit that is already in the routing table.
+    /// Services that remain in the roster are left as is, so any connected services stay
+    /// connected, services that are no longer in the roster are removed and new services are
+    /// added.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The updated circuit
+    ///
+    /// Returns an error if the lock is poisoned or if the circuit does not exist
+    fn update_circuit(&mut self, circuit: Circuit) -> Result<(), InternalError> {
+        let mut state = self
+            .state
+            .write()
+            .map_err(|_| InternalError::with_message(String::from("RoutingTable lock poisoned")))?;
+
+        let existing_circuit = state.circuits.remove(&circuit.circuit_id).ok_or_else(|| {
+            InternalError::with_message(format!("Circuit {} was not found", circuit.circuit_id))
+        })?;
+
+        for service in existing_circuit.roster.iter() {
+            if !circuit
+                .roster
+                .iter()
+                .any(|updated_service| updated_service.service_id == service.service_id)
+            {
+                let service_id = ServiceId::new(
+                    circuit.circuit_id.to_string(),
+                    service.service_id.to_string(),
+                );
+
+                state.service_directory.remove(&service_id);
+            }
+        }
+
+        for service in circuit.roster.iter() {
+            let service_id = ServiceId::new(
+                circuit.circuit_id.to_string(),
+                service.service_id.to_string(),
+            );
+
+            if !state.service_directory.contains_key(&service_id) {
+                state.service_directory.insert(service_id, service.clone());
+            }
+        }
+
+        state
+            .circuits
+            .insert(circuit.circuit_id.to_string(), circuit);
+        Ok(())
+    }
+
     /// Adds a new node to the routing table
     ///
     /// # Arguments
@@ -442,11 +494,19 @@ mod test {
             circuit_id: "012-abc".to_string(),
             roster: circuit_roster0.clone(),
             members: circuit_members0.clone(),
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization: false,
+            #[cfg(feature = "circuit-durability")]
+            store_and_forward: false,
         };
         let circuit1 = Circuit {
             circuit_id: "345-def".to_string(),
             roster: circuit_roster1.clone(),
             members: circuit_members1.clone(),
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization: false,
+            #[cfg(feature = "circuit-durability")]
+            store_and_forward: false,
         };
 
         let mut expected_nodes = BTreeMap::new();
@@ -596,6 +656,10 @@ mod test {
             circuit_id: "012-abc".to_string(),
             roster: vec![service0.clone(), service1.clone()],
             members: vec![node0.node_id.clone(), node1.node_id.clone()],
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization: false,
+            #[cfg(feature = "circuit-durability")]
+            store_and_forward: false,
         };
         let service_id0 = ServiceId::new(
             "012-abc".to_string(),
@@ -660,6 +724,111 @@ mod test {
         assert_eq!(fetched_service_list, vec![service0, service1]);
     }
 
+    // Test updating the roster of a circuit in the routing table
+    //
+    // 1. Write a circuit with two services to the routing table and set the peer id of the first
+    //    service, as if it had connected
+    // 2. Update the circuit, replacing the second service with a new service
+    // 3. Check that the first service kept its peer id, the second service was removed and the
+    //    new service was added
+    // 4. Check that the circuit in the routing table has the updated roster
+    // 5. Check that updating a circuit that is not in the routing table returns an error
+    #[test]
+    fn test_update_circuit() {
+        let routing_table = RoutingTable::default();
+        let mut writer: Box<dyn RoutingTableWriter> = Box::new(routing_table.clone());
+        let reader: Box<dyn RoutingTableReader> = Box::new(routing_table.clone());
+
+        let mut roster = vec![];
+        for x in 0..3 {
+            roster.push(Service {
+                service_id: format!("service-{}", x),
+                service_type: "test".to_string(),
+                node_id: "node-0".to_string(),
+                arguments: vec![],
+                peer_id: None,
+            });
+        }
+
+        let circuit = Circuit {
+            circuit_id: "012-abc".to_string(),
+            roster: vec![roster[0].clone(), roster[1].clone()],
+            members: vec!["node-0".to_string()],
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization: false,
+            #[cfg(feature = "circuit-durability")]
+            store_and_forward: false,
+        };
+
+        writer
+            .add_circuit("012-abc".to_string(), circuit.clone(), vec![])
+            .expect("Unable to add circuit");
+
+        let service_id0 = ServiceId::new("012-abc".to_string(), "service-0".to_string());
+        let mut connected_service = roster[0].clone();
+        connected_service.set_peer_id("service-0-peer".to_string());
+        writer
+            .add_service(service_id0.clone(), connected_service.clone())
+            .expect("Unable to add service");
+
+        let updated_circuit = Circuit {
+            circuit_id: "012-abc".to_string(),
+            roster: vec![roster[0].clone(), roster[2].clone()],
+            members: vec!["node-0".to_string()],
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization: false,
+            #[cfg(feature = "circuit-durability")]
+            store_and_forward: false,
+        };
+
+        writer
+            .update_circuit(updated_circuit.clone())
+            .expect("Unable to update circuit");
+
+        assert_eq!(
+            reader
+                .get_service(&service_id0)
+                .expect("Unable to get service"),
+            Some(connected_service)
+        );
+        assert_eq!(
+            reader
+                .get_service(&ServiceId::new(
+                    "012-abc".to_string(),
+                    "service-1".to_string()
+                ))
+                .expect("Unable to get service"),
+            None
+        );
+        assert_eq!(
+            reader
+                .get_service(&ServiceId::new(
+                    "012-abc".to_string(),
+                    "service-2".to_string()
+                ))
+                .expect("Unable to get service"),
+            Some(roster[2].clone())
+        );
+
+        assert_eq!(
+            reader
+                .get_circuit("012-abc")
+                .expect("Unable to get circuit"),
+            Some(updated_circuit)
+        );
+
+        let unknown_circuit = Circuit {
+            circuit_id: "345-def".to_string(),
+            roster: vec![],
+            members: vec![],
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization: false,
+            #[cfg(feature = "circuit-durability")]
+            store_and_forward: false,
+        };
+        assert!(writer.update_circuit(unknown_circuit).is_err());
+    }
+
     // Test the routing table read and write operations for nodes
     //
     // 1. Create two nodes, write one node to the routing table
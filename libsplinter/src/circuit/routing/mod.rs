@@ -35,6 +35,8 @@
 
 mod error;
 pub mod memory;
+#[cfg(feature = "circuit-relay")]
+pub mod relay;
 
 use std::cmp::Ordering;
 use std::fmt;
@@ -90,6 +92,15 @@ pub trait RoutingTableWriter: Send {
     /// * `circuit_id` - The unique ID for the circuit
     fn remove_circuit(&mut self, circuit_id: &str) -> Result<(), InternalError>;
 
+    /// Updates the roster and members of a circuit that is already in the routing table.
+    /// Services that remain in the roster are left as is, services that are no longer in the
+    /// roster are removed and new services are added.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The updated circuit
+    fn update_circuit(&mut self, circuit: Circuit) -> Result<(), InternalError>;
+
     /// Adds a new node to the routing table
     ///
     /// # Arguments
@@ -186,6 +197,10 @@ pub struct Circuit {
     circuit_id: String,
     roster: Vec<Service>,
     members: Vec<String>,
+    #[cfg(feature = "challenge-authorization")]
+    challenge_authorization: bool,
+    #[cfg(feature = "circuit-durability")]
+    store_and_forward: bool,
 }
 
 impl Circuit {
@@ -201,9 +216,29 @@ impl Circuit {
             circuit_id,
             roster,
             members,
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization: false,
+            #[cfg(feature = "circuit-durability")]
+            store_and_forward: false,
         }
     }
 
+    /// Sets whether the circuit's members must have been authorized using challenge authorization
+    /// for messages to be routed to and from them.
+    #[cfg(feature = "challenge-authorization")]
+    pub fn with_challenge_authorization(mut self, challenge_authorization: bool) -> Self {
+        self.challenge_authorization = challenge_authorization;
+        self
+    }
+
+    /// Sets whether messages to the circuit's other nodes are stored and forwarded, so that they
+    /// are delivered once a disconnected node reconnects.
+    #[cfg(feature = "circuit-durability")]
+    pub fn with_store_and_forward(mut self, store_and_forward: bool) -> Self {
+        self.store_and_forward = store_and_forward;
+        self
+    }
+
     /// Returns the ID of the circuit
     pub fn circuit_id(&self) -> &str {
         &self.circuit_id
@@ -218,6 +253,19 @@ impl Circuit {
     pub fn members(&self) -> &[String] {
         &self.members
     }
+
+    /// Returns whether the circuit's members must have been authorized using challenge
+    /// authorization
+    #[cfg(feature = "challenge-authorization")]
+    pub fn challenge_authorization(&self) -> bool {
+        self.challenge_authorization
+    }
+
+    /// Returns whether messages to the circuit's other nodes are stored and forwarded
+    #[cfg(feature = "circuit-durability")]
+    pub fn store_and_forward(&self) -> bool {
+        self.store_and_forward
+    }
 }
 
 /// The routing table representation of a node
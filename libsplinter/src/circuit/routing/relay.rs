@@ -0,0 +1,164 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A route table for relaying circuit messages through intermediate nodes.
+//!
+//! By default, circuit messages are sent directly to the node a recipient service is connected
+//! to. A [`RelayTable`] holds next-hop routes for nodes that cannot be reached directly, for
+//! example because of a firewall. A message for such a node is sent to the route's next hop,
+//! which forwards it on toward the destination node.
+//!
+//! A node only relays messages through the members of the message's circuit and through the
+//! nodes that have been explicitly allowed as relay nodes. A node that is not a member of a
+//! circuit only forwards the circuit's messages if it is itself an allowed relay node.
+//!
+//! [`RelayTable`]: struct.RelayTable.html
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::error::InternalError;
+
+/// The internal state of the relay table that will be wrapped in a read-write lock
+#[derive(Clone, Default)]
+struct RelayTableState {
+    /// Destination node ID to the ID of the node messages for it are sent to
+    routes: HashMap<String, String>,
+    /// The nodes that may relay messages for circuits they are not a member of
+    relay_nodes: HashSet<String>,
+}
+
+/// A table of next-hop routes and allowed relay nodes that uses a read-write lock to wrap the
+/// state.
+#[derive(Clone, Default)]
+pub struct RelayTable {
+    state: Arc<RwLock<RelayTableState>>,
+}
+
+impl RelayTable {
+    /// Adds a route that sends messages for the destination node through the next hop node,
+    /// replacing any existing route to the destination.
+    ///
+    /// # Arguments
+    ///
+    /// * `destination` - The ID of the node the route leads to
+    /// * `next_hop` - The ID of the node that messages for the destination are sent to
+    ///
+    /// Returns an error if the lock is poisoned.
+    pub fn add_route(&self, destination: String, next_hop: String) -> Result<(), InternalError> {
+        self.write()?.routes.insert(destination, next_hop);
+        Ok(())
+    }
+
+    /// Removes the route to the destination node, returning its next hop if it existed.
+    ///
+    /// # Arguments
+    ///
+    /// * `destination` - The ID of the node the route leads to
+    ///
+    /// Returns an error if the lock is poisoned.
+    pub fn remove_route(&self, destination: &str) -> Result<Option<String>, InternalError> {
+        Ok(self.write()?.routes.remove(destination))
+    }
+
+    /// Returns the next hop for messages to the destination node, if there is a route to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `destination` - The ID of the node the message is for
+    ///
+    /// Returns an error if the lock is poisoned.
+    pub fn next_hop(&self, destination: &str) -> Result<Option<String>, InternalError> {
+        Ok(self.read()?.routes.get(destination).cloned())
+    }
+
+    /// Allows the node to relay messages for circuits it is not a member of.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the relay node
+    ///
+    /// Returns an error if the lock is poisoned.
+    pub fn add_relay_node(&self, node_id: String) -> Result<(), InternalError> {
+        self.write()?.relay_nodes.insert(node_id);
+        Ok(())
+    }
+
+    /// Returns whether the node is allowed to relay messages for circuits it is not a member of.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the node
+    ///
+    /// Returns an error if the lock is poisoned.
+    pub fn is_relay_node(&self, node_id: &str) -> Result<bool, InternalError> {
+        Ok(self.read()?.relay_nodes.contains(node_id))
+    }
+
+    fn read(&self) -> Result<RwLockReadGuard<RelayTableState>, InternalError> {
+        self.state
+            .read()
+            .map_err(|_| InternalError::with_message(String::from("RelayTable lock poisoned")))
+    }
+
+    fn write(&self) -> Result<RwLockWriteGuard<RelayTableState>, InternalError> {
+        self.state
+            .write()
+            .map_err(|_| InternalError::with_message(String::from("RelayTable lock poisoned")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test that routes can be added, replaced and removed, and that only the added relay nodes
+    // are allowed to relay
+    #[test]
+    fn test_relay_table() {
+        let table = RelayTable::default();
+        assert_eq!(
+            table.next_hop("node-c").expect("Unable to get next hop"),
+            None
+        );
+
+        table
+            .add_route("node-c".into(), "hub-1".into())
+            .expect("Unable to add route");
+        table
+            .add_route("node-c".into(), "hub-2".into())
+            .expect("Unable to add route");
+        assert_eq!(
+            table.next_hop("node-c").expect("Unable to get next hop"),
+            Some("hub-2".to_string())
+        );
+
+        assert_eq!(
+            table
+                .remove_route("node-c")
+                .expect("Unable to remove route"),
+            Some("hub-2".to_string())
+        );
+        assert_eq!(
+            table.next_hop("node-c").expect("Unable to get next hop"),
+            None
+        );
+
+        table
+            .add_relay_node("hub-1".into())
+            .expect("Unable to add relay node");
+        assert!(table.is_relay_node("hub-1").expect("Unable to check node"));
+        assert!(!table.is_relay_node("hub-2").expect("Unable to check node"));
+    }
+}
@@ -17,6 +17,8 @@ use std::sync::mpsc::SendError;
 
 use protobuf::error::ProtobufError;
 
+#[cfg(feature = "two-phase-log")]
+use super::two_phase::log::TwoPhaseLogError;
 use super::{PeerId, ProposalId, ProposalUpdate};
 
 #[derive(Debug)]
@@ -138,3 +140,10 @@ impl From<ConsensusSendError> for ConsensusEngineError {
         ConsensusEngineError(Box::new(err))
     }
 }
+
+#[cfg(feature = "two-phase-log")]
+impl From<TwoPhaseLogError> for ConsensusEngineError {
+    fn from(err: TwoPhaseLogError) -> Self {
+        ConsensusEngineError(Box::new(err))
+    }
+}
@@ -15,6 +15,9 @@
 //! The API that defines interactions between consensus and a Splinter service.
 
 pub mod error;
+#[cfg(feature = "pbft")]
+pub mod pbft;
+mod timing;
 pub mod two_phase;
 
 use std::convert::{TryFrom, TryInto};
@@ -173,8 +176,30 @@ pub trait ProposalManager: Send {
         consensus_data: Option<Vec<u8>>,
     ) -> Result<(), ProposalManagerError>;
 
+    /// Consensus approved the given proposal before this node restarted, but the proposal was not
+    /// applied before the restart. Any changes the manager prepared while checking the proposal
+    /// were lost, so the manager must apply the proposal from scratch; if it already applied the
+    /// proposal before the restart, it must not apply it again.
+    ///
+    /// The default implementation accepts the proposal.
+    #[cfg(feature = "two-phase-log")]
+    fn reapply_proposal(&self, id: &ProposalId) -> Result<(), ProposalManagerError> {
+        self.accept_proposal(id, None)
+    }
+
     /// Consensus has rejected the given proposal.
     fn reject_proposal(&self, id: &ProposalId) -> Result<(), ProposalManagerError>;
+
+    /// Consensus has found that the manager's state is out of date, because the network accepted
+    /// proposals that this node did not. The manager should bring its state up to date with its
+    /// peers' and send `ProposalUpdate::StateSynced` once it has; returns `false` if the manager
+    /// has no way to do so.
+    ///
+    /// The default implementation returns `false`.
+    #[cfg(feature = "pbft")]
+    fn sync_state(&self) -> Result<bool, ProposalManagerError> {
+        Ok(false)
+    }
 }
 
 /// Messages the `ProposalManager` sends to consensus
@@ -198,6 +223,10 @@ pub enum ProposalUpdate {
     /// The `Proposal` with the given ID could not be accepted due to the specified error.
     ProposalAcceptFailed(ProposalId, String),
 
+    /// The manager brought its state up to date with its peers', as requested by consensus.
+    #[cfg(feature = "pbft")]
+    StateSynced,
+
     /// Signal consensus to shutdown gracefully.
     Shutdown,
 }
@@ -0,0 +1,811 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A deterministic, in-process harness for testing networks of `PbftEngine`s.
+//!
+//! Rather than running each engine in its own thread, the harness drives every member's engine
+//! directly, one round at a time. In each round, every member that is still running handles the
+//! updates from its proposal manager, and then the messages sent in the previous round are
+//! delivered in the order they were sent. Timeouts never expire on their own; a test decides when
+//! a member times out.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
+use protobuf::Message;
+
+use crate::consensus::{
+    ConsensusMessage, ConsensusNetworkSender, ConsensusSendError, PeerId, Proposal, ProposalId,
+    ProposalManager, ProposalManagerError, ProposalUpdate, StartupState,
+};
+use crate::protos::pbft::{PbftMessage, PbftMessage_Type};
+
+use super::{sign_message, PbftEngine, SyncStatus};
+
+/// Long enough that the view change timeout never expires during a test
+const VIEW_CHANGE_TIMEOUT_SECS: u64 = 3600;
+
+/// How a member of the test network behaves
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Behavior {
+    Honest,
+    /// The member neither sends nor receives any messages
+    Crashed,
+    /// The member sends a different proposal ID than the one it is voting for to every other
+    /// member, by index
+    Equivocating,
+    /// The member finds every proposal invalid, as if it were missing changes that the other
+    /// members have committed
+    OutOfDate,
+}
+
+struct Envelope {
+    from: PeerId,
+    to: PeerId,
+    message: Vec<u8>,
+}
+
+type Outbox = Arc<Mutex<VecDeque<Envelope>>>;
+type UpdateQueue = Arc<Mutex<VecDeque<ProposalUpdate>>>;
+
+struct HarnessNetworkSender {
+    id: PeerId,
+    members: Vec<PeerId>,
+    behavior: Arc<Mutex<Behavior>>,
+    outbox: Outbox,
+    signer: Box<dyn Signer>,
+}
+
+impl HarnessNetworkSender {
+    fn send(&self, to: &PeerId, message: Vec<u8>) {
+        let message = if *self.behavior.lock().expect("behavior lock poisoned")
+            == Behavior::Equivocating
+            && self.members.iter().position(|id| id == to).unwrap_or(0) % 2 == 1
+        {
+            equivocate(message, &self.id, &*self.signer)
+        } else {
+            message
+        };
+
+        self.outbox
+            .lock()
+            .expect("outbox lock poisoned")
+            .push_back(Envelope {
+                from: self.id.clone(),
+                to: to.clone(),
+                message,
+            });
+    }
+}
+
+impl ConsensusNetworkSender for HarnessNetworkSender {
+    fn send_to(&self, peer_id: &PeerId, message: Vec<u8>) -> Result<(), ConsensusSendError> {
+        self.send(peer_id, message);
+        Ok(())
+    }
+
+    fn broadcast(&self, message: Vec<u8>) -> Result<(), ConsensusSendError> {
+        for member in self.members.iter().filter(|id| *id != &self.id) {
+            self.send(member, message.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Replace the proposal ID in `PRE_PREPARE`, `PREPARE`, and `COMMIT` messages with one that
+/// doesn't exist, and sign the message again.
+fn equivocate(message: Vec<u8>, id: &PeerId, signer: &dyn Signer) -> Vec<u8> {
+    let mut pbft_msg: PbftMessage =
+        Message::parse_from_bytes(&message).expect("failed to parse message");
+    match pbft_msg.get_message_type() {
+        PbftMessage_Type::PRE_PREPARE | PbftMessage_Type::PREPARE | PbftMessage_Type::COMMIT => {
+            let mut proposal_id = pbft_msg.take_proposal_id();
+            proposal_id.push(0xff);
+            pbft_msg.set_proposal_id(proposal_id);
+            sign_message(&mut pbft_msg, id, signer).expect("failed to sign message")
+        }
+        _ => message,
+    }
+}
+
+/// A proposal manager that hands out a fixed list of proposals and distributes them to every
+/// member's proposal manager, as a service would.
+struct HarnessProposalManager {
+    id: PeerId,
+    behavior: Arc<Mutex<Behavior>>,
+    update_queues: Vec<(PeerId, UpdateQueue)>,
+    proposals_to_create: Arc<Mutex<VecDeque<ProposalId>>>,
+    accepted_proposals: Arc<Mutex<Vec<ProposalId>>>,
+    rejected_proposals: Arc<Mutex<Vec<ProposalId>>>,
+    /// Whether the manager can sync its state; a test decides when the sync is finished
+    can_sync: bool,
+}
+
+impl HarnessProposalManager {
+    fn send_update(&self, to: &PeerId, update: ProposalUpdate) {
+        if let Some((_, queue)) = self.update_queues.iter().find(|(id, _)| id == to) {
+            queue
+                .lock()
+                .expect("update queue lock poisoned")
+                .push_back(update);
+        }
+    }
+}
+
+impl ProposalManager for HarnessProposalManager {
+    fn create_proposal(
+        &self,
+        _previous_proposal_id: Option<ProposalId>,
+        _consensus_data: Vec<u8>,
+    ) -> Result<(), ProposalManagerError> {
+        let next_proposal = self
+            .proposals_to_create
+            .lock()
+            .expect("proposals lock poisoned")
+            .pop_front();
+
+        match next_proposal {
+            Some(proposal_id) => {
+                let proposal = Proposal {
+                    id: proposal_id.clone(),
+                    summary: proposal_id.clone().into(),
+                    ..Default::default()
+                };
+                for (id, _) in &self.update_queues {
+                    if id == &self.id {
+                        self.send_update(
+                            id,
+                            ProposalUpdate::ProposalCreated(Some(proposal.clone())),
+                        )
+                    } else {
+                        self.send_update(
+                            id,
+                            ProposalUpdate::ProposalReceived(proposal.clone(), self.id.clone()),
+                        )
+                    }
+                }
+            }
+            None => self.send_update(&self.id, ProposalUpdate::ProposalCreated(None)),
+        }
+
+        Ok(())
+    }
+
+    fn check_proposal(&self, id: &ProposalId) -> Result<(), ProposalManagerError> {
+        // Every proposal is valid, except for the ones made up by equivocating members and the
+        // ones checked by out-of-date members
+        let out_of_date =
+            *self.behavior.lock().expect("behavior lock poisoned") == Behavior::OutOfDate;
+        let update = if out_of_date || id.as_ref().last() == Some(&0xff) {
+            ProposalUpdate::ProposalInvalid(id.clone())
+        } else {
+            ProposalUpdate::ProposalValid(id.clone())
+        };
+        self.send_update(&self.id, update);
+        Ok(())
+    }
+
+    fn accept_proposal(
+        &self,
+        id: &ProposalId,
+        _consensus_data: Option<Vec<u8>>,
+    ) -> Result<(), ProposalManagerError> {
+        self.accepted_proposals
+            .lock()
+            .expect("accepted proposals lock poisoned")
+            .push(id.clone());
+        Ok(())
+    }
+
+    fn reject_proposal(&self, id: &ProposalId) -> Result<(), ProposalManagerError> {
+        self.rejected_proposals
+            .lock()
+            .expect("rejected proposals lock poisoned")
+            .push(id.clone());
+        Ok(())
+    }
+
+    fn sync_state(&self) -> Result<bool, ProposalManagerError> {
+        Ok(self.can_sync)
+    }
+}
+
+struct Member {
+    id: PeerId,
+    engine: PbftEngine,
+    network_sender: HarnessNetworkSender,
+    proposal_manager: HarnessProposalManager,
+    updates: UpdateQueue,
+    behavior: Arc<Mutex<Behavior>>,
+}
+
+impl Member {
+    fn behavior(&self) -> Behavior {
+        *self.behavior.lock().expect("behavior lock poisoned")
+    }
+
+    /// Whether the member is still running; an engine exits once it finds that its state is out
+    /// of date and can't be synced.
+    fn is_running(&self) -> bool {
+        self.behavior() != Behavior::Crashed && self.engine.sync != SyncStatus::Unavailable
+    }
+}
+
+pub struct Harness {
+    members: Vec<Member>,
+    outbox: Outbox,
+    dropped_message_types: HashSet<PbftMessage_Type>,
+}
+
+impl Harness {
+    /// Create a network with the given number of members, whose IDs are `[0]`, `[1]`, and so on;
+    /// member `[0]` is the primary of the first view.
+    pub fn new(member_count: u8) -> Self {
+        let ids: Vec<PeerId> = (0..member_count).map(|i| vec![i].into()).collect();
+        let outbox = Outbox::default();
+        let update_queues: Vec<(PeerId, UpdateQueue)> = ids
+            .iter()
+            .map(|id| (id.clone(), UpdateQueue::default()))
+            .collect();
+
+        let context = Secp256k1Context::new();
+        let signers: Vec<Box<dyn Signer>> = ids
+            .iter()
+            .map(|_| context.new_signer(context.new_random_private_key()))
+            .collect();
+        let member_keys: HashMap<_, _> = ids
+            .iter()
+            .cloned()
+            .zip(
+                signers
+                    .iter()
+                    .map(|signer| signer.public_key().expect("failed to get public key")),
+            )
+            .collect();
+
+        let members = update_queues
+            .iter()
+            .zip(signers.iter())
+            .map(|((id, updates), signer)| {
+                let behavior = Arc::new(Mutex::new(Behavior::Honest));
+
+                let mut engine = PbftEngine::new(
+                    Duration::from_secs(VIEW_CHANGE_TIMEOUT_SECS),
+                    signer.clone_box(),
+                    context.new_verifier(),
+                    member_keys.clone(),
+                );
+                engine.initialize(StartupState {
+                    id: id.clone(),
+                    peer_ids: ids.iter().filter(|peer| *peer != id).cloned().collect(),
+                    last_proposal: None,
+                });
+
+                Member {
+                    id: id.clone(),
+                    engine,
+                    network_sender: HarnessNetworkSender {
+                        id: id.clone(),
+                        members: ids.clone(),
+                        behavior: behavior.clone(),
+                        outbox: outbox.clone(),
+                        signer: signer.clone_box(),
+                    },
+                    proposal_manager: HarnessProposalManager {
+                        id: id.clone(),
+                        behavior: behavior.clone(),
+                        update_queues: update_queues.clone(),
+                        proposals_to_create: Default::default(),
+                        accepted_proposals: Default::default(),
+                        rejected_proposals: Default::default(),
+                        can_sync: false,
+                    },
+                    updates: updates.clone(),
+                    behavior,
+                }
+            })
+            .collect();
+
+        Harness {
+            members,
+            outbox,
+            dropped_message_types: HashSet::new(),
+        }
+    }
+
+    pub fn set_behavior(&mut self, member: u8, behavior: Behavior) {
+        *self.members[member as usize]
+            .behavior
+            .lock()
+            .expect("behavior lock poisoned") = behavior;
+    }
+
+    /// Have the given member propose the given proposals, in order, when its engine asks for
+    /// them.
+    pub fn add_proposals(&mut self, member: u8, proposal_ids: &[&[u8]]) {
+        self.members[member as usize]
+            .proposal_manager
+            .proposals_to_create
+            .lock()
+            .expect("proposals lock poisoned")
+            .extend(proposal_ids.iter().map(|id| ProposalId::from(*id)));
+    }
+
+    /// Let the given member's proposal manager sync its state.
+    pub fn enable_sync(&mut self, member: u8) {
+        self.members[member as usize].proposal_manager.can_sync = true;
+    }
+
+    /// Have the given member's proposal manager report that it has finished syncing its state.
+    pub fn finish_sync(&mut self, member: u8) {
+        self.members[member as usize]
+            .updates
+            .lock()
+            .expect("update queue lock poisoned")
+            .push_back(ProposalUpdate::StateSynced);
+    }
+
+    /// Sign the message as the given member.
+    pub fn sign(&self, member: u8, msg: &mut PbftMessage) -> Vec<u8> {
+        let member = &self.members[member as usize];
+        sign_message(msg, &member.id, &*member.network_sender.signer)
+            .expect("failed to sign message")
+    }
+
+    /// Send a message from one member to another in the next round, as a faulty member might.
+    pub fn send(&mut self, from: u8, to: u8, message: Vec<u8>) {
+        self.outbox
+            .lock()
+            .expect("outbox lock poisoned")
+            .push_back(Envelope {
+                from: self.members[from as usize].id.clone(),
+                to: self.members[to as usize].id.clone(),
+                message,
+            });
+    }
+
+    /// Drop all messages of the given type instead of delivering them.
+    pub fn drop_messages(&mut self, message_type: PbftMessage_Type) {
+        self.dropped_message_types.insert(message_type);
+    }
+
+    /// Deliver messages of the given type again.
+    pub fn deliver_messages(&mut self, message_type: PbftMessage_Type) {
+        self.dropped_message_types.remove(&message_type);
+    }
+
+    /// Simulate the expiration of the given member's view change timeout.
+    pub fn time_out(&mut self, member: u8) {
+        let member = &mut self.members[member as usize];
+        member
+            .engine
+            .request_next_view(&member.network_sender, &member.proposal_manager)
+            .expect("failed to request next view");
+    }
+
+    pub fn view(&self, member: u8) -> u64 {
+        self.members[member as usize].engine.view
+    }
+
+    pub fn accepted_proposals(&self, member: u8) -> Vec<ProposalId> {
+        self.members[member as usize]
+            .proposal_manager
+            .accepted_proposals
+            .lock()
+            .expect("accepted proposals lock poisoned")
+            .clone()
+    }
+
+    pub fn rejected_proposals(&self, member: u8) -> Vec<ProposalId> {
+        self.members[member as usize]
+            .proposal_manager
+            .rejected_proposals
+            .lock()
+            .expect("rejected proposals lock poisoned")
+            .clone()
+    }
+
+    /// Run a single round: every member that is still running handles its proposal manager's
+    /// updates, then the messages that were sent before this round are delivered.
+    pub fn run_round(&mut self) {
+        for member in self.members.iter_mut().filter(|member| member.is_running()) {
+            member
+                .engine
+                .evaluate_pre_prepared_proposal(&member.proposal_manager)
+                .expect("failed to evaluate pre-prepared proposal");
+            member
+                .engine
+                .get_next_proposal(&member.proposal_manager)
+                .expect("failed to get next proposal");
+
+            loop {
+                let update = member
+                    .updates
+                    .lock()
+                    .expect("update queue lock poisoned")
+                    .pop_front();
+                match update {
+                    Some(update) => member
+                        .engine
+                        .handle_proposal_update(
+                            update,
+                            &member.network_sender,
+                            &member.proposal_manager,
+                        )
+                        .expect("failed to handle proposal update"),
+                    None => break,
+                }
+            }
+        }
+
+        let envelopes: Vec<Envelope> = self
+            .outbox
+            .lock()
+            .expect("outbox lock poisoned")
+            .drain(..)
+            .collect();
+
+        for envelope in envelopes {
+            let message_type = PbftMessage::parse_from_bytes(&envelope.message)
+                .expect("failed to parse message")
+                .get_message_type();
+            if self.dropped_message_types.contains(&message_type) {
+                continue;
+            }
+
+            let sender_running = self
+                .members
+                .iter()
+                .any(|member| member.id == envelope.from && member.is_running());
+            if !sender_running {
+                continue;
+            }
+
+            if let Some(member) = self
+                .members
+                .iter_mut()
+                .find(|member| member.id == envelope.to && member.is_running())
+            {
+                member
+                    .engine
+                    .handle_consensus_msg(
+                        ConsensusMessage::new(envelope.message, envelope.from),
+                        &member.network_sender,
+                        &member.proposal_manager,
+                    )
+                    .expect("failed to handle consensus message");
+            }
+        }
+    }
+
+    /// Run rounds until the condition is met, or until the given number of rounds have run;
+    /// returns whether the condition was met.
+    pub fn run_until<F>(&mut self, max_rounds: usize, condition: F) -> bool
+    where
+        F: Fn(&Harness) -> bool,
+    {
+        for _ in 0..max_rounds {
+            if condition(self) {
+                return true;
+            }
+            self.run_round();
+        }
+        condition(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::consensus::pbft::State;
+
+    const MAX_ROUNDS: usize = 50;
+
+    fn ids(ids: &[&[u8]]) -> Vec<ProposalId> {
+        ids.iter().map(|id| ProposalId::from(*id)).collect()
+    }
+
+    /// A signed `VIEW_CHANGE` message from a member that hasn't committed or prepared anything
+    fn view_change(harness: &Harness, member: u8, view: u64) -> Vec<u8> {
+        let mut msg = PbftMessage::new();
+        msg.set_message_type(PbftMessage_Type::VIEW_CHANGE);
+        msg.set_view(view);
+        harness.sign(member, &mut msg)
+    }
+
+    /// A signed `NEW_VIEW` message that includes the given `VIEW_CHANGE` messages
+    fn new_view(
+        harness: &Harness,
+        member: u8,
+        view: u64,
+        proposal_id: &[u8],
+        view_changes: Vec<Vec<u8>>,
+    ) -> Vec<u8> {
+        let mut msg = PbftMessage::new();
+        msg.set_message_type(PbftMessage_Type::NEW_VIEW);
+        msg.set_view(view);
+        msg.set_sequence_number(1);
+        msg.set_proposal_id(proposal_id.to_vec());
+        msg.set_view_changes(view_changes.into());
+        harness.sign(member, &mut msg)
+    }
+
+    /// Verify that a network of 4 honest members commits proposals in the same order on every
+    /// member.
+    #[test]
+    fn all_members_honest() {
+        let mut harness = Harness::new(4);
+        harness.add_proposals(0, &[&[1], &[2]]);
+
+        assert!(harness.run_until(MAX_ROUNDS, |harness| (0..4)
+            .all(|member| harness.accepted_proposals(member).len() == 2)));
+
+        for member in 0..4 {
+            assert_eq!(harness.accepted_proposals(member), ids(&[&[1], &[2]]));
+        }
+    }
+
+    /// Verify that a network of 4 members commits proposals when one of them has crashed.
+    #[test]
+    fn one_member_crashed() {
+        let mut harness = Harness::new(4);
+        harness.set_behavior(3, Behavior::Crashed);
+        harness.add_proposals(1, &[&[1]]);
+
+        assert!(harness.run_until(MAX_ROUNDS, |harness| (0..3)
+            .all(|member| harness.accepted_proposals(member).len() == 1)));
+
+        for member in 0..3 {
+            assert_eq!(harness.accepted_proposals(member), ids(&[&[1]]));
+        }
+        assert!(harness.accepted_proposals(3).is_empty());
+    }
+
+    /// Verify that a network of 4 members does not commit anything when two of them have crashed,
+    /// since there is no longer a quorum.
+    #[test]
+    fn too_many_members_crashed() {
+        let mut harness = Harness::new(4);
+        harness.set_behavior(2, Behavior::Crashed);
+        harness.set_behavior(3, Behavior::Crashed);
+        harness.add_proposals(0, &[&[1]]);
+
+        assert!(!harness.run_until(MAX_ROUNDS, |harness| (0..4)
+            .any(|member| !harness.accepted_proposals(member).is_empty())));
+    }
+
+    /// Verify that when the primary crashes, the other members change to a new view once two of
+    /// them time out (the third joins the view change because more than `f` members asked for
+    /// it), and the new primary commits the proposal.
+    #[test]
+    fn primary_crashed() {
+        let mut harness = Harness::new(4);
+        harness.set_behavior(0, Behavior::Crashed);
+        harness.add_proposals(2, &[&[1]]);
+
+        assert!(!harness.run_until(MAX_ROUNDS, |harness| (1..4)
+            .any(|member| !harness.accepted_proposals(member).is_empty())));
+
+        harness.time_out(2);
+        harness.time_out(3);
+
+        assert!(harness.run_until(MAX_ROUNDS, |harness| (1..4)
+            .all(|member| harness.accepted_proposals(member).len() == 1)));
+
+        for member in 1..4 {
+            assert_eq!(harness.view(member), 1);
+            assert_eq!(harness.accepted_proposals(member), ids(&[&[1]]));
+        }
+    }
+
+    /// Verify that a proposal that was prepared, but not committed, before the primary crashed is
+    /// carried over to the new view and committed there, rather than being replaced.
+    #[test]
+    fn prepared_proposal_carried_over() {
+        let mut harness = Harness::new(4);
+        harness.drop_messages(PbftMessage_Type::COMMIT);
+        harness.add_proposals(0, &[&[1]]);
+        harness.add_proposals(1, &[&[2]]);
+
+        // Run until every member has prepared the proposal
+        harness.run_until(MAX_ROUNDS, |harness| {
+            harness
+                .members
+                .iter()
+                .all(|member| match member.engine.state {
+                    State::EvaluatingProposal(ref pbft_proposal) => {
+                        pbft_proposal.prepared_view == Some(0)
+                    }
+                    _ => false,
+                })
+        });
+
+        harness.set_behavior(0, Behavior::Crashed);
+        harness.deliver_messages(PbftMessage_Type::COMMIT);
+        for member in 1..4 {
+            harness.time_out(member);
+        }
+
+        assert!(harness.run_until(MAX_ROUNDS, |harness| (1..4)
+            .all(|member| !harness.accepted_proposals(member).is_empty())));
+
+        for member in 1..4 {
+            assert_eq!(harness.view(member), 1);
+            assert_eq!(
+                harness.accepted_proposals(member)[0],
+                ProposalId::from(vec![1])
+            );
+            assert!(harness.rejected_proposals(member).is_empty());
+        }
+    }
+
+    /// Verify that a member that finds a proposal invalid, but sees a quorum commit it anyway,
+    /// stops taking part in consensus when its state can't be synced, instead of continuing with
+    /// out-of-date state, and that the other members keep committing proposals without it.
+    #[test]
+    fn out_of_date_member_stops() {
+        let mut harness = Harness::new(4);
+        harness.set_behavior(3, Behavior::OutOfDate);
+        harness.add_proposals(0, &[&[1], &[2]]);
+
+        assert!(harness.run_until(MAX_ROUNDS, |harness| (0..3)
+            .all(|member| harness.accepted_proposals(member).len() == 2)));
+
+        for member in 0..3 {
+            assert_eq!(harness.accepted_proposals(member), ids(&[&[1], &[2]]));
+        }
+        assert_eq!(harness.members[3].engine.sync, SyncStatus::Unavailable);
+        assert!(harness.accepted_proposals(3).is_empty());
+        assert_eq!(harness.rejected_proposals(3), ids(&[&[1]]));
+    }
+
+    /// Verify that a member that sends conflicting votes to different members does not prevent
+    /// the honest members from committing the proposal, or cause any of them to commit anything
+    /// else.
+    #[test]
+    fn equivocating_member() {
+        let mut harness = Harness::new(4);
+        harness.set_behavior(3, Behavior::Equivocating);
+        harness.add_proposals(0, &[&[1], &[2]]);
+
+        assert!(harness.run_until(MAX_ROUNDS, |harness| (0..3)
+            .all(|member| harness.accepted_proposals(member).len() == 2)));
+
+        for member in 0..3 {
+            assert_eq!(harness.accepted_proposals(member), ids(&[&[1], &[2]]));
+        }
+    }
+
+    /// Verify that when the primary sends different pre-prepares to different members, no
+    /// proposal is committed in its view, and the honest members replace it with a new primary
+    /// that commits the proposal.
+    #[test]
+    fn equivocating_primary() {
+        let mut harness = Harness::new(4);
+        harness.set_behavior(0, Behavior::Equivocating);
+        harness.add_proposals(0, &[&[1]]);
+
+        assert!(!harness.run_until(MAX_ROUNDS, |harness| (1..4)
+            .any(|member| !harness.accepted_proposals(member).is_empty())));
+
+        for member in 1..4 {
+            harness.time_out(member);
+        }
+
+        assert!(harness.run_until(MAX_ROUNDS, |harness| (1..4)
+            .all(|member| harness.accepted_proposals(member).len() == 1)));
+
+        for member in 1..4 {
+            assert_eq!(harness.view(member), 1);
+            assert_eq!(harness.accepted_proposals(member), ids(&[&[1]]));
+        }
+    }
+
+    /// Verify that a member whose state is out of date stops voting while its proposal manager
+    /// syncs its state, and takes part in consensus again once the sync is finished.
+    #[test]
+    fn out_of_date_member_syncs() {
+        let mut harness = Harness::new(4);
+        harness.set_behavior(3, Behavior::OutOfDate);
+        harness.enable_sync(3);
+        harness.add_proposals(0, &[&[1]]);
+
+        assert!(harness.run_until(MAX_ROUNDS, |harness| (0..3)
+            .all(|member| harness.accepted_proposals(member).len() == 1)
+            && harness.members[3].engine.sync == SyncStatus::Syncing));
+
+        harness.set_behavior(3, Behavior::Honest);
+        harness.finish_sync(3);
+        harness.add_proposals(0, &[&[2]]);
+
+        assert!(harness.run_until(MAX_ROUNDS, |harness| (0..4)
+            .all(|member| harness.accepted_proposals(member).contains(&vec![2].into()))));
+
+        for member in 0..3 {
+            assert_eq!(harness.accepted_proposals(member), ids(&[&[1], &[2]]));
+        }
+        assert_eq!(harness.members[3].engine.sync, SyncStatus::InSync);
+        assert_eq!(harness.accepted_proposals(3), ids(&[&[2]]));
+    }
+
+    /// Verify that a `VIEW_CHANGE` message that reports a committed proposal without the commits
+    /// to prove it is ignored.
+    #[test]
+    fn view_change_without_proof_ignored() {
+        let mut harness = Harness::new(4);
+
+        let mut msg = PbftMessage::new();
+        msg.set_message_type(PbftMessage_Type::VIEW_CHANGE);
+        msg.set_view(1);
+        msg.set_sequence_number(5);
+        let unproven_view_change = harness.sign(3, &mut msg);
+        harness.send(3, 1, unproven_view_change);
+        let view_change = view_change(&harness, 2, 1);
+        harness.send(2, 1, view_change);
+        harness.run_round();
+
+        let view_changes = &harness.members[1].engine.view_changes[&1];
+        assert_eq!(view_changes.len(), 1);
+        assert!(view_changes.contains_key(&PeerId::from(vec![2])));
+    }
+
+    /// Verify that members ignore a `NEW_VIEW` message that doesn't include `VIEW_CHANGE`
+    /// messages from a quorum, and keep committing proposals in their current view.
+    #[test]
+    fn new_view_without_quorum_ignored() {
+        let mut harness = Harness::new(4);
+
+        // Member 1, the primary of view 1, tries to start it with only its own view change
+        let view_changes = vec![view_change(&harness, 1, 1)];
+        let new_view = new_view(&harness, 1, 1, &[], view_changes);
+        for member in &[0, 2, 3] {
+            harness.send(1, *member, new_view.clone());
+        }
+        harness.add_proposals(0, &[&[1]]);
+
+        assert!(harness.run_until(MAX_ROUNDS, |harness| (0..4)
+            .all(|member| harness.accepted_proposals(member).len() == 1)));
+
+        for member in 0..4 {
+            assert_eq!(harness.view(member), 0);
+        }
+    }
+
+    /// Verify that members refuse to enter a view whose primary carries over a proposal that none
+    /// of the included `VIEW_CHANGE` messages prepared, and ask for the next view instead.
+    #[test]
+    fn new_view_with_wrong_proposal_rejected() {
+        let mut harness = Harness::new(4);
+
+        let view_changes = (0..4)
+            .map(|member| view_change(&harness, member, 1))
+            .collect();
+        let new_view = new_view(&harness, 1, 1, &[9], view_changes);
+        for member in &[0, 2, 3] {
+            harness.send(1, *member, new_view.clone());
+        }
+        harness.run_round();
+
+        for member in &[0, 2, 3] {
+            assert_eq!(harness.view(*member), 0);
+            assert_eq!(
+                harness.members[*member as usize].engine.pending_view,
+                Some(2)
+            );
+        }
+    }
+}
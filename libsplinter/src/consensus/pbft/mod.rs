@@ -0,0 +1,1732 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A Practical Byzantine Fault Tolerant (PBFT) consensus algorithm implemented as a
+//! `ConsensusEngine`. Unlike two-phase commit, PBFT does not trust every member of the network: a
+//! network of `n` members commits proposals as long as no more than `f = (n - 1) / 3` of them are
+//! faulty, whether they have crashed or are behaving maliciously.
+//!
+//! The members take turns acting as the primary, one view at a time; the primary of view `v` is
+//! the member at index `v % n` when the members' IDs are sorted. Only one proposal is considered
+//! at a time:
+//!
+//! 1. The primary checks the next proposal it has created or received, assigns it the next
+//!    sequence number, and sends a `PRE_PREPARE` message to the other members.
+//! 2. Each member checks the proposal with its proposal manager and, if it is valid, sends a
+//!    `PREPARE` message to all other members.
+//! 3. Once a member has received matching `PRE_PREPARE`/`PREPARE` messages from a quorum of
+//!    members, the proposal is prepared and the member sends a `COMMIT` message to all others.
+//! 4. Once a member has received matching `COMMIT` messages from a quorum of members, it accepts
+//!    the proposal.
+//!
+//! A quorum is the smallest number of members such that any two quorums share at least `f + 1`
+//! members (`2f + 1` members when `n = 3f + 1`). Every message is signed by the member that sent
+//! it; messages that aren't signed by a member of the network are ignored.
+//!
+//! If a member has been waiting on a proposal for longer than the view change timeout, it
+//! suspects the primary of being faulty and sends a `VIEW_CHANGE` message for the next view; a
+//! member also joins a view change once `f + 1` other members have asked for one. A
+//! `VIEW_CHANGE` message carries the signed `COMMIT` messages that prove the sender committed its
+//! last proposal, and the signed `PRE_PREPARE`/`PREPARE` messages that prove a quorum prepared the
+//! proposal the sender reports, if any; messages without valid proof are ignored.
+//!
+//! When the primary of the new view has received `VIEW_CHANGE` messages from a quorum, it sends a
+//! `NEW_VIEW` message that includes them. The new view starts after the last proposal any of them
+//! committed, and carries over the prepared proposal with the highest view that has not been
+//! committed, if there is one. Each member checks the included `VIEW_CHANGE` messages and works
+//! out the new view's sequence number and carried-over proposal for itself; if the primary got
+//! either wrong, the member asks for the next view instead. Members return any other proposal
+//! they were evaluating to their backlog when they enter the new view, so the new primary can
+//! propose it again.
+//!
+//! A member whose state is out of date, because it sees a quorum commit a proposal that it did not
+//! accept or enters a view that starts after proposals it did not commit, stops voting and asks
+//! its proposal manager to sync its state with its peers. It rejoins consensus once the proposal
+//! manager reports that its state is synced; if the proposal manager can't sync its state, the
+//! member stops taking part in consensus and its engine exits with an error.
+//!
+//! # Known limitations of this PBFT implementation
+//!
+//! A member that misses a `NEW_VIEW` message stays in its old view until the next view change.
+//!
+//! A member can't tell which proposal its proposal manager's state was synced to, so if the
+//! network commits more proposals while the member is syncing, the member may find that it is
+//! out of date again and sync a second time.
+
+#[cfg(test)]
+mod harness;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use cylinder::{PublicKey, Signature, Signer, Verifier};
+use protobuf::{Message, RepeatedField};
+
+use crate::consensus::timing::Timeout;
+use crate::consensus::{
+    ConsensusEngine, ConsensusEngineError, ConsensusMessage, ConsensusNetworkSender, PeerId,
+    ProposalId, ProposalManager, ProposalUpdate, StartupState,
+};
+use crate::protos::pbft::{PbftMessage, PbftMessage_Type};
+
+const MESSAGE_RECV_TIMEOUT_MILLIS: u64 = 100;
+const PROPOSAL_RECV_TIMEOUT_MILLIS: u64 = 100;
+/// Messages about proposals this many sequence numbers past the last committed proposal are
+/// ignored, so faulty members can't exhaust a member's memory
+const SEQUENCE_NUMBER_WINDOW: u64 = 100;
+
+#[derive(Debug)]
+enum State {
+    Idle,
+    AwaitingProposal,
+    EvaluatingProposal(PbftProposal),
+}
+
+/// Whether this member's state matches the rest of the network's
+#[derive(Debug, PartialEq)]
+enum SyncStatus {
+    InSync,
+    /// The proposal manager is bringing this member's state up to date; the member does not vote
+    /// until it has
+    Syncing,
+    /// This member's state is out of date, and the proposal manager can't bring it up to date
+    Unavailable,
+}
+
+/// Contains information about a proposal that PBFT consensus needs to keep track of
+#[derive(Debug)]
+struct PbftProposal {
+    proposal_id: ProposalId,
+    sequence_number: u64,
+    /// Whether the proposal manager has found the proposal to be valid
+    valid: bool,
+    /// The view in which this member prepared the proposal, if it has
+    prepared_view: Option<u64>,
+    /// The signed `PRE_PREPARE` and `PREPARE` messages from the quorum that prepared the proposal
+    /// in `prepared_view`
+    prepare_proof: Vec<Vec<u8>>,
+}
+
+impl PbftProposal {
+    fn new(proposal_id: ProposalId, sequence_number: u64) -> Self {
+        PbftProposal {
+            proposal_id,
+            sequence_number,
+            valid: false,
+            prepared_view: None,
+            prepare_proof: vec![],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+enum Phase {
+    Prepare,
+    Commit,
+}
+
+/// Identifies the proposal and phase that a member voted for
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Vote {
+    phase: Phase,
+    view: u64,
+    sequence_number: u64,
+    proposal_id: ProposalId,
+}
+
+/// The contents of a member's `VIEW_CHANGE` message, which have been checked against the proof
+/// that the message carries
+#[derive(Debug)]
+struct ViewChange {
+    /// The sequence number of the last proposal the member committed
+    sequence_number: u64,
+    /// The signed `COMMIT` messages that prove the member committed that proposal
+    commit_proof: Vec<Vec<u8>>,
+    /// The proposal the member prepared but did not commit, if any
+    prepared: Option<PreparedProposal>,
+    /// The signed `VIEW_CHANGE` message
+    message: Vec<u8>,
+}
+
+/// A proposal that a quorum of members prepared
+#[derive(Debug)]
+struct PreparedProposal {
+    view: u64,
+    sequence_number: u64,
+    proposal_id: ProposalId,
+}
+
+/// How a new view starts, as worked out from the `VIEW_CHANGE` messages it was started with
+struct NewView {
+    /// The sequence number of the last proposal committed before the view
+    sequence_number: u64,
+    /// The signed `COMMIT` messages that prove that proposal was committed
+    commit_proof: Vec<Vec<u8>>,
+    /// The prepared proposal that the view must commit first, if any
+    carried_proposal: Option<ProposalId>,
+}
+
+impl NewView {
+    /// Work out how a new view starts: after the last proposal any of the members committed, with
+    /// the prepared proposal from the highest view that was not committed before then.
+    fn from_view_changes(view_changes: &[&ViewChange]) -> Self {
+        let (sequence_number, commit_proof) = view_changes
+            .iter()
+            .max_by_key(|view_change| view_change.sequence_number)
+            .map(|view_change| {
+                (
+                    view_change.sequence_number,
+                    view_change.commit_proof.clone(),
+                )
+            })
+            .unwrap_or_default();
+
+        let carried_proposal = view_changes
+            .iter()
+            .filter_map(|view_change| view_change.prepared.as_ref())
+            .filter(|prepared| prepared.sequence_number > sequence_number)
+            .max_by(|a, b| {
+                (a.view, a.sequence_number, a.proposal_id.as_ref()).cmp(&(
+                    b.view,
+                    b.sequence_number,
+                    b.proposal_id.as_ref(),
+                ))
+            })
+            .map(|prepared| prepared.proposal_id.clone());
+
+        NewView {
+            sequence_number,
+            commit_proof,
+            carried_proposal,
+        }
+    }
+}
+
+/// Sign the message as the given member, and return the signed message's bytes.
+fn sign_message(
+    msg: &mut PbftMessage,
+    member_id: &PeerId,
+    signer: &dyn Signer,
+) -> Result<Vec<u8>, ConsensusEngineError> {
+    msg.set_member_id(member_id.clone().into());
+    msg.clear_signature();
+    let signature = signer
+        .sign(&msg.write_to_bytes()?)
+        .map_err(|err| ConsensusEngineError(Box::new(err)))?;
+    msg.set_signature(signature.take_bytes());
+
+    Ok(msg.write_to_bytes()?)
+}
+
+pub struct PbftEngine {
+    id: PeerId,
+    /// All members of the network, including this one, sorted by ID
+    members: Vec<PeerId>,
+    /// Signs this member's messages
+    signer: Box<dyn Signer>,
+    /// Verifies the signatures of messages from the other members
+    verifier: Box<dyn Verifier>,
+    /// The public key of each member of the network
+    member_keys: HashMap<PeerId, PublicKey>,
+    view: u64,
+    /// The sequence number of the last proposal this member committed
+    sequence_number: u64,
+    /// The signed `COMMIT` messages that prove the last proposal was committed
+    last_commit_proof: Vec<Vec<u8>>,
+    state: State,
+    /// Proposals that have been created or received, but not yet evaluated
+    proposal_backlog: VecDeque<ProposalId>,
+    /// The proposals that the primary of the current view has assigned to each sequence number
+    pre_prepares: HashMap<u64, ProposalId>,
+    /// The signed messages of the members that have sent each `PREPARE`/`COMMIT` vote
+    votes: HashMap<Vote, HashMap<PeerId, Vec<u8>>>,
+    /// The view this member is trying to change to, if any
+    pending_view: Option<u64>,
+    /// The `VIEW_CHANGE` messages received for each view
+    view_changes: HashMap<u64, HashMap<PeerId, ViewChange>>,
+    /// Active while this member is waiting for progress on a proposal; when it expires, this
+    /// member asks for a view change.
+    view_change_timeout: Timeout,
+    sync: SyncStatus,
+    /// Active while the proposal manager is syncing this member's state; when it expires, this
+    /// member asks the proposal manager to sync again.
+    sync_timeout: Timeout,
+}
+
+impl PbftEngine {
+    /// Create an engine that signs its messages with the given signer, and checks the other
+    /// members' messages with the given verifier against their public keys. The view change
+    /// timeout is also how long the proposal manager has to sync this member's state.
+    pub fn new(
+        view_change_timeout_duration: Duration,
+        signer: Box<dyn Signer>,
+        verifier: Box<dyn Verifier>,
+        member_keys: HashMap<PeerId, PublicKey>,
+    ) -> Self {
+        PbftEngine {
+            id: PeerId::default(),
+            members: vec![],
+            signer,
+            verifier,
+            member_keys,
+            view: 0,
+            sequence_number: 0,
+            last_commit_proof: vec![],
+            state: State::Idle,
+            proposal_backlog: VecDeque::new(),
+            pre_prepares: HashMap::new(),
+            votes: HashMap::new(),
+            pending_view: None,
+            view_changes: HashMap::new(),
+            view_change_timeout: Timeout::new(view_change_timeout_duration),
+            sync: SyncStatus::InSync,
+            sync_timeout: Timeout::new(view_change_timeout_duration),
+        }
+    }
+
+    fn initialize(&mut self, startup_state: StartupState) {
+        self.id = startup_state.id;
+
+        let mut members = startup_state.peer_ids;
+        members.push(self.id.clone());
+        members.sort();
+        members.dedup();
+        self.members = members;
+
+        for member in &self.members {
+            if !self.member_keys.contains_key(member) {
+                warn!(
+                    "No public key for member {}; its PBFT messages will be ignored",
+                    member
+                );
+            }
+        }
+    }
+
+    /// The member that is the primary of the given view
+    fn primary(&self, view: u64) -> &PeerId {
+        &self.members[(view % self.members.len() as u64) as usize]
+    }
+
+    fn is_primary(&self) -> bool {
+        self.primary(self.view) == &self.id
+    }
+
+    /// The maximum number of faulty members the network can tolerate
+    fn max_faulty(&self) -> usize {
+        (self.members.len() - 1) / 3
+    }
+
+    /// The number of members that must agree on a proposal or view change
+    fn quorum(&self) -> usize {
+        (self.members.len() + self.max_faulty()) / 2 + 1
+    }
+
+    fn evaluating_proposal(&self, proposal_id: &ProposalId) -> bool {
+        match self.state {
+            State::EvaluatingProposal(ref pbft_proposal)
+                if &pbft_proposal.proposal_id == proposal_id =>
+            {
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Parse a signed message, and check that it was signed by the member of the network it says
+    /// it is from; returns `None` if it wasn't.
+    fn verify_message(&self, message: &[u8]) -> Option<PbftMessage> {
+        let mut msg: PbftMessage = Message::parse_from_bytes(message).ok()?;
+        let member_id = PeerId::from(msg.get_member_id());
+        if !self.members.contains(&member_id) {
+            return None;
+        }
+        let public_key = self.member_keys.get(&member_id)?;
+
+        let signature = msg.take_signature();
+        let signed_bytes = msg.write_to_bytes().ok()?;
+        match self.verifier.verify(
+            &signed_bytes,
+            &Signature::new(signature.clone()),
+            public_key,
+        ) {
+            Ok(true) => {
+                msg.set_signature(signature);
+                Some(msg)
+            }
+            Ok(false) => None,
+            Err(err) => {
+                debug!("Unable to verify message from {}: {}", member_id, err);
+                None
+            }
+        }
+    }
+
+    /// Check that the given signed messages are votes from a quorum of different members for the
+    /// same view, sequence number, and proposal, where `is_vote` checks each message's type and
+    /// sender; returns the view, sequence number, and proposal if they are.
+    fn check_proof<F>(&self, proof: &[Vec<u8>], is_vote: F) -> Option<(u64, u64, ProposalId)>
+    where
+        F: Fn(&PbftMessage, &PeerId) -> bool,
+    {
+        let mut voters = HashSet::new();
+        let mut voted_for = None;
+
+        for message in proof {
+            let msg = self.verify_message(message)?;
+            let voter = PeerId::from(msg.get_member_id());
+            if !is_vote(&msg, &voter) || !voters.insert(voter) {
+                return None;
+            }
+
+            let vote = (
+                msg.get_view(),
+                msg.get_sequence_number(),
+                ProposalId::from(msg.get_proposal_id()),
+            );
+            match voted_for {
+                Some(ref voted_for) if voted_for != &vote => return None,
+                Some(_) => {}
+                None => voted_for = Some(vote),
+            }
+        }
+
+        if voters.len() < self.quorum() {
+            return None;
+        }
+
+        voted_for
+    }
+
+    /// Check that a signed `VIEW_CHANGE` message proves the sequence number and the prepared
+    /// proposal it reports; returns `None` if it doesn't.
+    fn check_view_change(&self, msg: &PbftMessage, message: Vec<u8>) -> Option<ViewChange> {
+        let sequence_number = msg.get_sequence_number();
+        let commit_proof = msg.get_commit_proof().to_vec();
+        if sequence_number > 0 {
+            let (_, committed_sequence_number, _) = self
+                .check_proof(&commit_proof, |vote, _| {
+                    vote.get_message_type() == PbftMessage_Type::COMMIT
+                })?;
+            if committed_sequence_number != sequence_number {
+                return None;
+            }
+        }
+
+        let prepared = if msg.get_proposal_id().is_empty() {
+            None
+        } else {
+            let (view, prepared_sequence_number, proposal_id) =
+                self.check_proof(msg.get_prepare_proof(), |vote, voter| {
+                    match vote.get_message_type() {
+                        PbftMessage_Type::PRE_PREPARE => voter == self.primary(vote.get_view()),
+                        PbftMessage_Type::PREPARE => voter != self.primary(vote.get_view()),
+                        _ => false,
+                    }
+                })?;
+            if view != msg.get_prepared_view()
+                || view >= msg.get_view()
+                || proposal_id.as_ref() != msg.get_proposal_id()
+                || prepared_sequence_number <= sequence_number
+            {
+                return None;
+            }
+
+            Some(PreparedProposal {
+                view,
+                sequence_number: prepared_sequence_number,
+                proposal_id,
+            })
+        };
+
+        Some(ViewChange {
+            sequence_number,
+            commit_proof,
+            prepared,
+            message,
+        })
+    }
+
+    /// Check that a `NEW_VIEW` message includes valid `VIEW_CHANGE` messages for its view from a
+    /// quorum of members; returns `None` if it doesn't.
+    fn check_new_view(&self, msg: &PbftMessage) -> Option<Vec<ViewChange>> {
+        let mut members = HashSet::new();
+        let mut view_changes = vec![];
+
+        for message in msg.get_view_changes() {
+            let view_change_msg = self.verify_message(message)?;
+            if view_change_msg.get_message_type() != PbftMessage_Type::VIEW_CHANGE
+                || view_change_msg.get_view() != msg.get_view()
+                || !members.insert(PeerId::from(view_change_msg.get_member_id()))
+            {
+                return None;
+            }
+            view_changes.push(self.check_view_change(&view_change_msg, message.clone())?);
+        }
+
+        if view_changes.len() < self.quorum() {
+            return None;
+        }
+
+        Some(view_changes)
+    }
+
+    fn handle_consensus_msg(
+        &mut self,
+        consensus_msg: ConsensusMessage,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        let origin_id = consensus_msg.origin_id;
+        if !self.members.contains(&origin_id) {
+            warn!("Ignoring PBFT message from unknown member {}", origin_id);
+            return Ok(());
+        }
+
+        let message = consensus_msg.message;
+        let pbft_msg = match self.verify_message(&message) {
+            Some(pbft_msg) if pbft_msg.get_member_id() == origin_id.as_ref() => pbft_msg,
+            _ => {
+                warn!(
+                    "Ignoring PBFT message from {} that is not signed by it",
+                    origin_id
+                );
+                return Ok(());
+            }
+        };
+        let view = pbft_msg.get_view();
+        let sequence_number = pbft_msg.get_sequence_number();
+        let proposal_id = ProposalId::from(pbft_msg.get_proposal_id());
+
+        match pbft_msg.get_message_type() {
+            PbftMessage_Type::PRE_PREPARE => {
+                debug!(
+                    "Pre-prepare for proposal {} (view {}, sequence number {}) received from {}",
+                    proposal_id, view, sequence_number, origin_id
+                );
+
+                if &origin_id != self.primary(view) {
+                    warn!(
+                        "Ignoring pre-prepare from {}, which is not the primary of view {}",
+                        origin_id, view
+                    );
+                    return Ok(());
+                }
+                if !self.accepts_messages_for(view, sequence_number) {
+                    debug!("Ignoring pre-prepare for proposal {}", proposal_id);
+                    return Ok(());
+                }
+
+                match self.pre_prepares.get(&sequence_number) {
+                    Some(pre_prepared_id) if pre_prepared_id != &proposal_id => {
+                        warn!(
+                            "Primary {} sent conflicting pre-prepares for sequence number {}",
+                            origin_id, sequence_number
+                        );
+                        self.start_view_change(self.view + 1, network_sender, proposal_manager)?;
+                        return Ok(());
+                    }
+                    Some(_) => {
+                        debug!("Pre-prepare already received; ignoring");
+                        return Ok(());
+                    }
+                    None => {}
+                }
+
+                self.pre_prepares
+                    .insert(sequence_number, proposal_id.clone());
+                // The primary's pre-prepare counts as its prepare
+                self.add_vote(
+                    Phase::Prepare,
+                    sequence_number,
+                    &proposal_id,
+                    origin_id,
+                    message,
+                );
+                if !self.view_change_timeout.is_started() {
+                    self.view_change_timeout.start();
+                }
+
+                // The proposal may have already been found valid, if it was carried over from the
+                // last view
+                let evaluated_proposal_valid = match &mut self.state {
+                    State::EvaluatingProposal(pbft_proposal)
+                        if pbft_proposal.proposal_id == proposal_id =>
+                    {
+                        pbft_proposal.sequence_number = sequence_number;
+                        Some(pbft_proposal.valid)
+                    }
+                    _ => None,
+                };
+
+                match evaluated_proposal_valid {
+                    Some(true) => {
+                        self.send_prepare(&proposal_id, network_sender, proposal_manager)?
+                    }
+                    Some(false) => {}
+                    None => self.evaluate_pre_prepared_proposal(proposal_manager)?,
+                }
+            }
+            PbftMessage_Type::PREPARE => {
+                if !self.accepts_messages_for(view, sequence_number) {
+                    debug!(
+                        "Ignoring prepare for proposal {} (view {}) from {}",
+                        proposal_id, view, origin_id
+                    );
+                    return Ok(());
+                }
+
+                debug!("Proposal {} prepared by member {}", proposal_id, origin_id);
+                self.add_vote(
+                    Phase::Prepare,
+                    sequence_number,
+                    &proposal_id,
+                    origin_id,
+                    message,
+                );
+                self.commit_if_prepared(&proposal_id, network_sender, proposal_manager)?;
+            }
+            PbftMessage_Type::COMMIT => {
+                if view != self.view || sequence_number <= self.sequence_number {
+                    debug!(
+                        "Ignoring commit for proposal {} (view {}) from {}",
+                        proposal_id, view, origin_id
+                    );
+                    return Ok(());
+                }
+                if sequence_number > self.sequence_number + SEQUENCE_NUMBER_WINDOW {
+                    debug!("Ignoring commit for proposal {}", proposal_id);
+                    return Ok(());
+                }
+
+                debug!("Proposal {} committed by member {}", proposal_id, origin_id);
+                self.add_vote(
+                    Phase::Commit,
+                    sequence_number,
+                    &proposal_id,
+                    origin_id,
+                    message,
+                );
+                self.accept_if_committed(sequence_number, &proposal_id, proposal_manager)?;
+            }
+            PbftMessage_Type::VIEW_CHANGE => {
+                if view <= self.view {
+                    debug!(
+                        "Ignoring view change to past view {} from {}",
+                        view, origin_id
+                    );
+                    return Ok(());
+                }
+
+                let view_change = match self.check_view_change(&pbft_msg, message) {
+                    Some(view_change) => view_change,
+                    None => {
+                        warn!(
+                            "Ignoring view change to view {} from {} without valid proof",
+                            view, origin_id
+                        );
+                        return Ok(());
+                    }
+                };
+
+                debug!("View change to view {} requested by {}", view, origin_id);
+                self.view_changes
+                    .entry(view)
+                    .or_default()
+                    .insert(origin_id, view_change);
+
+                self.join_view_change_if_needed(network_sender, proposal_manager)?;
+                self.start_new_view_if_ready(view, network_sender, proposal_manager)?;
+            }
+            PbftMessage_Type::NEW_VIEW => {
+                if view <= self.view || &origin_id != self.primary(view) {
+                    warn!("Ignoring new view {} from {}", view, origin_id);
+                    return Ok(());
+                }
+
+                let view_changes = match self.check_new_view(&pbft_msg) {
+                    Some(view_changes) => view_changes,
+                    None => {
+                        warn!(
+                            "Ignoring new view {} from {} without valid view changes from a \
+                             quorum",
+                            view, origin_id
+                        );
+                        return Ok(());
+                    }
+                };
+
+                let new_view = NewView::from_view_changes(&view_changes.iter().collect::<Vec<_>>());
+                let carried_proposal_id = new_view
+                    .carried_proposal
+                    .as_ref()
+                    .map(|proposal_id| proposal_id.as_ref())
+                    .unwrap_or(&[]);
+                if sequence_number != new_view.sequence_number + 1
+                    || proposal_id.as_ref() != carried_proposal_id
+                {
+                    warn!(
+                        "Primary {} started view {} with the wrong sequence number or proposal; \
+                         requesting the next view",
+                        origin_id, view
+                    );
+                    self.start_view_change(view + 1, network_sender, proposal_manager)?;
+                    return Ok(());
+                }
+
+                info!("Entering view {} started by {}", view, origin_id);
+                self.enter_view(view, new_view, network_sender, proposal_manager)?;
+            }
+            PbftMessage_Type::UNSET_TYPE => warn!(
+                "Ignoring improperly specified PBFT message from {}",
+                origin_id
+            ),
+        }
+
+        Ok(())
+    }
+
+    fn handle_proposal_update(
+        &mut self,
+        update: ProposalUpdate,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        match update {
+            ProposalUpdate::ProposalCreated(None) => {
+                if let State::AwaitingProposal = self.state {
+                    self.state = State::Idle;
+                }
+            }
+            ProposalUpdate::ProposalCreated(Some(proposal)) => {
+                debug!("Proposal created: {}", proposal.id);
+                if let State::AwaitingProposal = self.state {
+                    self.state = State::Idle;
+                }
+                self.add_to_backlog(proposal.id);
+            }
+            ProposalUpdate::ProposalReceived(proposal, _) => {
+                debug!("Proposal received: {}", proposal.id);
+                self.add_to_backlog(proposal.id);
+                self.evaluate_pre_prepared_proposal(proposal_manager)?;
+            }
+            ProposalUpdate::ProposalValid(proposal_id) => match &mut self.state {
+                State::EvaluatingProposal(pbft_proposal)
+                    if pbft_proposal.proposal_id == proposal_id =>
+                {
+                    debug!("Proposal valid: {}", proposal_id);
+                    pbft_proposal.valid = true;
+                    let sequence_number = pbft_proposal.sequence_number;
+
+                    if self.pending_view.is_some() {
+                        debug!("Changing views; not voting for proposal {}", proposal_id);
+                    } else if self.is_primary() {
+                        self.send_pre_prepare(
+                            sequence_number,
+                            &proposal_id,
+                            network_sender,
+                            proposal_manager,
+                        )?;
+                    } else {
+                        self.send_prepare(&proposal_id, network_sender, proposal_manager)?;
+                    }
+                }
+                _ => warn!("Got valid message for unknown proposal: {}", proposal_id),
+            },
+            ProposalUpdate::ProposalInvalid(proposal_id) => match self.state {
+                State::EvaluatingProposal(ref pbft_proposal)
+                    if pbft_proposal.proposal_id == proposal_id =>
+                {
+                    let sequence_number = pbft_proposal.sequence_number;
+                    debug!("Proposal invalid; rejecting: {}", proposal_id);
+                    self.state = State::Idle;
+                    proposal_manager.reject_proposal(&proposal_id)?;
+
+                    // If a quorum of members has already committed the proposal, this member is
+                    // missing changes; if the quorum commits it later, this member will find out
+                    // when it sees the commits. Otherwise, the view change timeout will replace
+                    // the primary.
+                    self.accept_if_committed(sequence_number, &proposal_id, proposal_manager)?;
+                }
+                _ => warn!("Got invalid message for unknown proposal: {}", proposal_id),
+            },
+            ProposalUpdate::ProposalAccepted(proposal_id) => {
+                info!("proposal accepted: {}", proposal_id);
+            }
+            ProposalUpdate::ProposalAcceptFailed(proposal_id, err) => {
+                error!(
+                    "failed to accept proposal {} due to error: {}",
+                    proposal_id, err
+                );
+            }
+            ProposalUpdate::StateSynced => {
+                if self.sync == SyncStatus::Syncing {
+                    info!("State synced; taking part in consensus again");
+                    self.sync = SyncStatus::InSync;
+                    self.sync_timeout.stop();
+                    self.restart_view_change_timeout();
+                }
+            }
+            other => {
+                debug!("ignoring update: {:?}", other);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check if a `PRE_PREPARE` or `PREPARE` message should be handled in the current view
+    fn accepts_messages_for(&self, view: u64, sequence_number: u64) -> bool {
+        view == self.view
+            && self.pending_view.is_none()
+            && sequence_number > self.sequence_number
+            && sequence_number <= self.sequence_number + SEQUENCE_NUMBER_WINDOW
+    }
+
+    fn add_to_backlog(&mut self, proposal_id: ProposalId) {
+        if self.proposal_backlog.contains(&proposal_id) || self.evaluating_proposal(&proposal_id) {
+            debug!("Proposal already received; ignoring: {}", proposal_id);
+            return;
+        }
+
+        self.proposal_backlog.push_back(proposal_id);
+
+        // The primary must make progress on the proposal before the timeout expires
+        if !self.view_change_timeout.is_started() {
+            self.view_change_timeout.start();
+        }
+    }
+
+    fn add_vote(
+        &mut self,
+        phase: Phase,
+        sequence_number: u64,
+        proposal_id: &ProposalId,
+        member: PeerId,
+        message: Vec<u8>,
+    ) {
+        self.votes
+            .entry(Vote {
+                phase,
+                view: self.view,
+                sequence_number,
+                proposal_id: proposal_id.clone(),
+            })
+            .or_default()
+            .insert(member, message);
+    }
+
+    fn vote_count(&self, phase: Phase, sequence_number: u64, proposal_id: &ProposalId) -> usize {
+        self.votes
+            .get(&Vote {
+                phase,
+                view: self.view,
+                sequence_number,
+                proposal_id: proposal_id.clone(),
+            })
+            .map(HashMap::len)
+            .unwrap_or(0)
+    }
+
+    /// The signed messages of the members that have sent the given vote in the current view
+    fn vote_messages(
+        &self,
+        phase: Phase,
+        sequence_number: u64,
+        proposal_id: &ProposalId,
+    ) -> Vec<Vec<u8>> {
+        self.votes
+            .get(&Vote {
+                phase,
+                view: self.view,
+                sequence_number,
+                proposal_id: proposal_id.clone(),
+            })
+            .map(|votes| votes.values().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Sign and broadcast a message about a proposal in the current view; returns the signed
+    /// message.
+    fn send_message(
+        &self,
+        message_type: PbftMessage_Type,
+        sequence_number: u64,
+        proposal_id: &ProposalId,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<Vec<u8>, ConsensusEngineError> {
+        let mut msg = PbftMessage::new();
+        msg.set_message_type(message_type);
+        msg.set_view(self.view);
+        msg.set_sequence_number(sequence_number);
+        msg.set_proposal_id(proposal_id.clone().into());
+
+        let message = sign_message(&mut msg, &self.id, &*self.signer)?;
+        network_sender.broadcast(message.clone())?;
+
+        Ok(message)
+    }
+
+    /// As the primary, assign the proposal to the given sequence number.
+    fn send_pre_prepare(
+        &mut self,
+        sequence_number: u64,
+        proposal_id: &ProposalId,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        debug!(
+            "Sending pre-prepare for proposal {} (view {}, sequence number {})",
+            proposal_id, self.view, sequence_number
+        );
+        let message = self.send_message(
+            PbftMessage_Type::PRE_PREPARE,
+            sequence_number,
+            proposal_id,
+            network_sender,
+        )?;
+        self.pre_prepares
+            .insert(sequence_number, proposal_id.clone());
+        self.add_vote(
+            Phase::Prepare,
+            sequence_number,
+            proposal_id,
+            self.id.clone(),
+            message,
+        );
+        if !self.view_change_timeout.is_started() {
+            self.view_change_timeout.start();
+        }
+
+        // The primary may be the only member of the network
+        self.commit_if_prepared(proposal_id, network_sender, proposal_manager)
+    }
+
+    /// Vote for the proposal that is being evaluated, which this member has found to be valid and
+    /// the primary has pre-prepared.
+    fn send_prepare(
+        &mut self,
+        proposal_id: &ProposalId,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        let sequence_number = match self.state {
+            State::EvaluatingProposal(ref pbft_proposal)
+                if &pbft_proposal.proposal_id == proposal_id =>
+            {
+                pbft_proposal.sequence_number
+            }
+            _ => return Ok(()),
+        };
+
+        if self.pre_prepares.get(&sequence_number) != Some(proposal_id) {
+            debug!(
+                "Proposal {} not pre-prepared in view {}; not sending prepare",
+                proposal_id, self.view
+            );
+            return Ok(());
+        }
+
+        debug!("Sending prepare for proposal {}", proposal_id);
+        let message = self.send_message(
+            PbftMessage_Type::PREPARE,
+            sequence_number,
+            proposal_id,
+            network_sender,
+        )?;
+        self.add_vote(
+            Phase::Prepare,
+            sequence_number,
+            proposal_id,
+            self.id.clone(),
+            message,
+        );
+
+        self.commit_if_prepared(proposal_id, network_sender, proposal_manager)
+    }
+
+    /// If a quorum of members has prepared the proposal that is being evaluated, send a commit
+    /// for it.
+    fn commit_if_prepared(
+        &mut self,
+        proposal_id: &ProposalId,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        let quorum = self.quorum();
+        let view = self.view;
+
+        let sequence_number = match self.state {
+            State::EvaluatingProposal(ref pbft_proposal)
+                if &pbft_proposal.proposal_id == proposal_id
+                    && pbft_proposal.valid
+                    && pbft_proposal.prepared_view != Some(view) =>
+            {
+                pbft_proposal.sequence_number
+            }
+            _ => return Ok(()),
+        };
+
+        if self.pre_prepares.get(&sequence_number) != Some(proposal_id)
+            || self.vote_count(Phase::Prepare, sequence_number, proposal_id) < quorum
+        {
+            return Ok(());
+        }
+
+        let prepare_proof = self.vote_messages(Phase::Prepare, sequence_number, proposal_id);
+        if let State::EvaluatingProposal(pbft_proposal) = &mut self.state {
+            pbft_proposal.prepared_view = Some(view);
+            pbft_proposal.prepare_proof = prepare_proof;
+        }
+
+        debug!("Proposal {} prepared; sending commit", proposal_id);
+        let message = self.send_message(
+            PbftMessage_Type::COMMIT,
+            sequence_number,
+            proposal_id,
+            network_sender,
+        )?;
+        self.add_vote(
+            Phase::Commit,
+            sequence_number,
+            proposal_id,
+            self.id.clone(),
+            message,
+        );
+
+        self.accept_if_committed(sequence_number, proposal_id, proposal_manager)
+    }
+
+    /// If a quorum of members has committed the given proposal, accept it.
+    fn accept_if_committed(
+        &mut self,
+        sequence_number: u64,
+        proposal_id: &ProposalId,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        if sequence_number <= self.sequence_number
+            || self.vote_count(Phase::Commit, sequence_number, proposal_id) < self.quorum()
+        {
+            return Ok(());
+        }
+
+        match self.state {
+            State::EvaluatingProposal(ref pbft_proposal)
+                if &pbft_proposal.proposal_id == proposal_id
+                    && pbft_proposal.sequence_number == sequence_number
+                    && pbft_proposal.valid =>
+            {
+                // This member may not have prepared the proposal yet, but a quorum has committed
+                // it, so it can't be replaced by a view change
+                debug!("Accepting proposal {}", proposal_id);
+                self.state = State::Idle;
+                proposal_manager.accept_proposal(proposal_id, None)?;
+            }
+            State::EvaluatingProposal(ref pbft_proposal)
+                if &pbft_proposal.proposal_id == proposal_id =>
+            {
+                // The proposal is still being checked; it will be accepted once it is found to be
+                // valid
+                return Ok(());
+            }
+            _ => {
+                // This member can't apply the proposal on its own, so any vote it casts from now
+                // on would be based on the wrong state; it keeps track of the proposals the
+                // network commits while its state is synced
+                if self.sync == SyncStatus::InSync {
+                    error!(
+                        "Proposal {} was committed without this member; this member's state is \
+                         out of date",
+                        proposal_id
+                    );
+                    self.start_sync(proposal_manager);
+                }
+            }
+        }
+
+        self.last_commit_proof = self.vote_messages(Phase::Commit, sequence_number, proposal_id);
+        self.sequence_number = sequence_number;
+        self.pre_prepares
+            .retain(|sequence_number, _| *sequence_number > self.sequence_number);
+        let last_sequence_number = self.sequence_number;
+        self.votes
+            .retain(|vote, _| vote.sequence_number > last_sequence_number);
+
+        // The primary is making progress, so there is no need to replace it
+        if let Some(pending_view) = self.pending_view.take() {
+            info!(
+                "Abandoning change to view {}; view {} is making progress",
+                pending_view, self.view
+            );
+        }
+
+        self.restart_view_change_timeout();
+
+        Ok(())
+    }
+
+    /// Stop voting, since this member's state is out of date, and ask the proposal manager to
+    /// bring it up to date.
+    fn start_sync(&mut self, proposal_manager: &dyn ProposalManager) {
+        if let State::EvaluatingProposal(ref pbft_proposal) = self.state {
+            debug!(
+                "Abandoning proposal {}, which was checked against out-of-date state",
+                pbft_proposal.proposal_id
+            );
+        }
+        self.state = State::Idle;
+        self.sync = SyncStatus::Syncing;
+        self.request_sync(proposal_manager);
+    }
+
+    fn request_sync(&mut self, proposal_manager: &dyn ProposalManager) {
+        self.sync_timeout.start();
+        match proposal_manager.sync_state() {
+            Ok(true) => info!("Syncing state with the other members"),
+            Ok(false) => {
+                error!("Unable to sync state; this member will stop taking part in consensus");
+                self.sync = SyncStatus::Unavailable;
+            }
+            Err(err) => error!("Failed to request state sync; will try again: {}", err),
+        }
+    }
+
+    /// If the proposal manager has taken too long to sync this member's state, ask it again.
+    fn retry_sync_if_timed_out(&mut self, proposal_manager: &dyn ProposalManager) {
+        if self.sync == SyncStatus::Syncing && self.sync_timeout.check_expired() {
+            warn!("Timed out waiting for state sync");
+            self.request_sync(proposal_manager);
+        }
+    }
+
+    /// Start the view change timeout if this member is waiting for a proposal to make progress;
+    /// otherwise, stop it.
+    fn restart_view_change_timeout(&mut self) {
+        let waiting = match self.state {
+            State::EvaluatingProposal(_) => true,
+            _ => !self.proposal_backlog.is_empty(),
+        };
+
+        if waiting {
+            self.view_change_timeout.start();
+        } else {
+            self.view_change_timeout.stop();
+        }
+    }
+
+    /// If this member is not the primary and is not doing anything, evaluate the first proposal
+    /// that the primary has pre-prepared and that this member has received.
+    fn evaluate_pre_prepared_proposal(
+        &mut self,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        if !matches!(self.state, State::Idle)
+            || self.pending_view.is_some()
+            || self.sync != SyncStatus::InSync
+            || self.is_primary()
+        {
+            return Ok(());
+        }
+
+        let next = self
+            .pre_prepares
+            .iter()
+            .filter(|(sequence_number, proposal_id)| {
+                **sequence_number > self.sequence_number
+                    && self.proposal_backlog.contains(proposal_id)
+            })
+            .min_by_key(|(sequence_number, _)| **sequence_number)
+            .map(|(sequence_number, proposal_id)| (*sequence_number, proposal_id.clone()));
+
+        if let Some((sequence_number, proposal_id)) = next {
+            self.proposal_backlog.retain(|id| id != &proposal_id);
+
+            debug!("Checking proposal {}", proposal_id);
+            self.state =
+                State::EvaluatingProposal(PbftProposal::new(proposal_id.clone(), sequence_number));
+            if let Err(err) = proposal_manager.check_proposal(&proposal_id) {
+                self.state = State::Idle;
+                return Err(err.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// If not doing anything, try to get the next proposal. If this member is the primary, it
+    /// evaluates the first proposal in its backlog; if there aren't any, it asks the proposal
+    /// manager for one.
+    fn get_next_proposal(
+        &mut self,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        if !matches!(self.state, State::Idle)
+            || self.pending_view.is_some()
+            || self.sync != SyncStatus::InSync
+        {
+            return Ok(());
+        }
+
+        if self.is_primary() {
+            if let Some(proposal_id) = self.proposal_backlog.pop_front() {
+                debug!("Checking proposal {}", proposal_id);
+                self.state = State::EvaluatingProposal(PbftProposal::new(
+                    proposal_id.clone(),
+                    self.sequence_number + 1,
+                ));
+                if let Err(err) = proposal_manager.check_proposal(&proposal_id) {
+                    error!(
+                        "Rejecting proposal {}; failed to check proposal due to err: {}",
+                        proposal_id, err
+                    );
+                    self.state = State::Idle;
+                    proposal_manager.reject_proposal(&proposal_id)?;
+                }
+                return Ok(());
+            }
+        } else if !self.proposal_backlog.is_empty() {
+            // Wait for the primary to pre-prepare one of the backlogged proposals
+            return Ok(());
+        }
+
+        match proposal_manager.create_proposal(None, vec![]) {
+            Ok(()) => self.state = State::AwaitingProposal,
+            Err(err) => error!("Error while creating proposal: {}", err),
+        }
+
+        Ok(())
+    }
+
+    /// If this member has been waiting on a proposal for too long, ask for a view change. A
+    /// member that is syncing its state isn't waiting on the primary, so it doesn't time out.
+    fn change_view_if_timed_out(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        if self.sync == SyncStatus::InSync && self.view_change_timeout.check_expired() {
+            warn!(
+                "Timed out waiting for progress in view {}",
+                self.pending_view.unwrap_or(self.view)
+            );
+            self.request_next_view(network_sender, proposal_manager)?;
+        }
+
+        Ok(())
+    }
+
+    /// Ask for a change to the view after the one this member is in or is changing to.
+    fn request_next_view(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        let next_view = self.pending_view.unwrap_or(self.view) + 1;
+        self.start_view_change(next_view, network_sender, proposal_manager)
+    }
+
+    /// Stop taking part in the current view, and ask the other members to change to the given
+    /// view.
+    fn start_view_change(
+        &mut self,
+        view: u64,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        self.pending_view = Some(view);
+
+        let mut msg = PbftMessage::new();
+        msg.set_message_type(PbftMessage_Type::VIEW_CHANGE);
+        msg.set_view(view);
+        msg.set_sequence_number(self.sequence_number);
+        msg.set_commit_proof(RepeatedField::from_vec(self.last_commit_proof.clone()));
+        if let State::EvaluatingProposal(ref pbft_proposal) = self.state {
+            if let Some(prepared_view) = pbft_proposal.prepared_view {
+                msg.set_proposal_id(pbft_proposal.proposal_id.clone().into());
+                msg.set_prepared_view(prepared_view);
+                msg.set_prepare_proof(RepeatedField::from_vec(pbft_proposal.prepare_proof.clone()));
+            }
+        }
+        let message = sign_message(&mut msg, &self.id, &*self.signer)?;
+
+        // This member's view change is checked the same way as everyone else's, so the new view
+        // is worked out the same way by every member
+        match self.check_view_change(&msg, message.clone()) {
+            Some(view_change) => {
+                self.view_changes
+                    .entry(view)
+                    .or_default()
+                    .insert(self.id.clone(), view_change);
+            }
+            None => warn!(
+                "This member's view change to view {} has invalid proof",
+                view
+            ),
+        }
+
+        // If the new primary doesn't start the view in time, the next view will be requested
+        self.view_change_timeout.start();
+
+        debug!("Requesting view change to view {}", view);
+        network_sender.broadcast(message)?;
+
+        self.start_new_view_if_ready(view, network_sender, proposal_manager)
+    }
+
+    /// Join the view change that `f + 1` other members have asked for, since at least one of them
+    /// is not faulty.
+    fn join_view_change_if_needed(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        let current_view = self.pending_view.unwrap_or(self.view);
+
+        // The lowest view that each member has asked to change to past this member's current view
+        let mut requested_views: HashMap<&PeerId, u64> = HashMap::new();
+        for (view, view_changes) in self.view_changes.iter() {
+            if *view <= current_view {
+                continue;
+            }
+            for member in view_changes.keys() {
+                if member == &self.id {
+                    continue;
+                }
+                let requested_view = requested_views.entry(member).or_insert(*view);
+                if *view < *requested_view {
+                    *requested_view = *view;
+                }
+            }
+        }
+
+        let requesting_members = requested_views.len();
+        let lowest_requested_view = requested_views.values().min().copied();
+
+        if requesting_members > self.max_faulty() {
+            if let Some(view) = lowest_requested_view {
+                info!(
+                    "{} members have requested a view change; requesting view {}",
+                    requesting_members, view
+                );
+                self.start_view_change(view, network_sender, proposal_manager)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// If this member is the primary of the given view, and a quorum of members has asked to
+    /// change to it, start the view.
+    fn start_new_view_if_ready(
+        &mut self,
+        view: u64,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        if self.pending_view != Some(view) || self.primary(view) != &self.id {
+            return Ok(());
+        }
+
+        let view_changes: Vec<&ViewChange> = match self.view_changes.get(&view) {
+            Some(view_changes) if view_changes.len() >= self.quorum() => {
+                view_changes.values().collect()
+            }
+            _ => return Ok(()),
+        };
+
+        let new_view = NewView::from_view_changes(&view_changes);
+
+        info!("Starting view {}", view);
+
+        let mut msg = PbftMessage::new();
+        msg.set_message_type(PbftMessage_Type::NEW_VIEW);
+        msg.set_view(view);
+        msg.set_sequence_number(new_view.sequence_number + 1);
+        if let Some(proposal_id) = &new_view.carried_proposal {
+            msg.set_proposal_id(proposal_id.clone().into());
+        }
+        msg.set_view_changes(RepeatedField::from_vec(
+            view_changes
+                .iter()
+                .map(|view_change| view_change.message.clone())
+                .collect(),
+        ));
+        network_sender.broadcast(sign_message(&mut msg, &self.id, &*self.signer)?)?;
+
+        self.enter_view(view, new_view, network_sender, proposal_manager)
+    }
+
+    /// Start taking part in the given view, which starts after the last proposal the network
+    /// committed and may carry over a proposal from the last view.
+    fn enter_view(
+        &mut self,
+        view: u64,
+        new_view: NewView,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        self.view = view;
+        self.pending_view = None;
+        self.pre_prepares.clear();
+        self.votes.retain(|vote, _| vote.view >= view);
+        self.view_changes
+            .retain(|view_change_view, _| *view_change_view > view);
+
+        // The network has committed every proposal before the new view's first proposal; if this
+        // member hasn't, its state is out of date
+        if new_view.sequence_number > self.sequence_number {
+            self.sequence_number = new_view.sequence_number;
+            self.last_commit_proof = new_view.commit_proof;
+            if self.sync == SyncStatus::InSync {
+                error!(
+                    "View {} starts after proposals this member did not commit; this member's \
+                     state is out of date",
+                    view
+                );
+                self.start_sync(proposal_manager);
+            }
+        }
+        let sequence_number = self.sequence_number + 1;
+        let carried_proposal = new_view.carried_proposal;
+
+        // A proposal that is not carried over to the new view must be pre-prepared again by the
+        // new primary
+        if let State::EvaluatingProposal(ref mut pbft_proposal) = self.state {
+            if Some(&pbft_proposal.proposal_id) == carried_proposal.as_ref() {
+                pbft_proposal.sequence_number = sequence_number;
+            } else {
+                let proposal_id = pbft_proposal.proposal_id.clone();
+                debug!(
+                    "Proposal {} not carried over to view {}; returning it to the backlog",
+                    proposal_id, view
+                );
+                self.state = State::Idle;
+                self.proposal_backlog.push_front(proposal_id);
+            }
+        }
+
+        if self.is_primary() {
+            match self.state {
+                State::EvaluatingProposal(ref pbft_proposal) if pbft_proposal.valid => {
+                    let proposal_id = pbft_proposal.proposal_id.clone();
+                    self.send_pre_prepare(
+                        sequence_number,
+                        &proposal_id,
+                        network_sender,
+                        proposal_manager,
+                    )?;
+                }
+                State::EvaluatingProposal(_) => {
+                    // The proposal will be pre-prepared once it is found to be valid
+                }
+                _ => {
+                    // Propose the carried-over proposal before any others
+                    if let Some(proposal_id) = carried_proposal {
+                        if self.proposal_backlog.contains(&proposal_id) {
+                            self.proposal_backlog.retain(|id| id != &proposal_id);
+                            self.proposal_backlog.push_front(proposal_id);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.restart_view_change_timeout();
+
+        Ok(())
+    }
+}
+
+impl ConsensusEngine for PbftEngine {
+    fn name(&self) -> &str {
+        "pbft"
+    }
+
+    fn version(&self) -> &str {
+        "0.1"
+    }
+
+    fn additional_protocols(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    fn run(
+        &mut self,
+        consensus_messages: Receiver<ConsensusMessage>,
+        proposal_updates: Receiver<ProposalUpdate>,
+        network_sender: Box<dyn ConsensusNetworkSender>,
+        proposal_manager: Box<dyn ProposalManager>,
+        startup_state: StartupState,
+    ) -> Result<(), ConsensusEngineError> {
+        let message_timeout = Duration::from_millis(MESSAGE_RECV_TIMEOUT_MILLIS);
+        let proposal_timeout = Duration::from_millis(PROPOSAL_RECV_TIMEOUT_MILLIS);
+
+        self.initialize(startup_state);
+
+        loop {
+            if self.sync == SyncStatus::Unavailable {
+                let err: Box<dyn Error + Send + Sync> =
+                    "this member's state is out of date and could not be synced".into();
+                return Err(ConsensusEngineError(err));
+            }
+
+            self.retry_sync_if_timed_out(&*proposal_manager);
+
+            if let Err(err) = self.change_view_if_timed_out(&*network_sender, &*proposal_manager) {
+                error!("Failed to change view: {}", err);
+            }
+
+            if let Err(err) = self.evaluate_pre_prepared_proposal(&*proposal_manager) {
+                error!("Failed to evaluate pre-prepared proposal: {}", err);
+            }
+
+            if let Err(err) = self.get_next_proposal(&*proposal_manager) {
+                error!("Failed to get next proposal: {}", err);
+            }
+
+            // Get and handle a consensus message if there is one
+            match consensus_messages.recv_timeout(message_timeout) {
+                Ok(consensus_message) => {
+                    if let Err(err) = self.handle_consensus_msg(
+                        consensus_message,
+                        &*network_sender,
+                        &*proposal_manager,
+                    ) {
+                        error!("error while handling consensus message: {}", err);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    info!("consensus message receiver disconnected");
+                    break;
+                }
+            }
+
+            // Get and handle a proposal update if there is one
+            match proposal_updates.recv_timeout(proposal_timeout) {
+                Ok(ProposalUpdate::Shutdown) => {
+                    info!("received shutdown");
+                    break;
+                }
+                Ok(update) => {
+                    if let Err(err) =
+                        self.handle_proposal_update(update, &*network_sender, &*proposal_manager)
+                    {
+                        error!("error while handling proposal update: {}", err);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    info!("proposal update receiver disconnected");
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use std::sync::mpsc::channel;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+
+    use crate::consensus::tests::{MockConsensusNetworkSender, MockProposalManager};
+
+    const VIEW_CHANGE_TIMEOUT_MILLIS: u64 = 5000;
+
+    /// Create a signer for each of the members `[0]` to `[3]`, and an engine for member `[0]`
+    /// that knows their public keys.
+    fn new_engine() -> (PbftEngine, Vec<Box<dyn Signer>>) {
+        let context = Secp256k1Context::new();
+        let signers: Vec<Box<dyn Signer>> = (0..4)
+            .map(|_| context.new_signer(context.new_random_private_key()))
+            .collect();
+        let member_keys = signers
+            .iter()
+            .enumerate()
+            .map(|(i, signer)| {
+                (
+                    PeerId::from(vec![i as u8]),
+                    signer.public_key().expect("failed to get public key"),
+                )
+            })
+            .collect();
+
+        let engine = PbftEngine::new(
+            Duration::from_millis(VIEW_CHANGE_TIMEOUT_MILLIS),
+            signers[0].clone_box(),
+            context.new_verifier(),
+            member_keys,
+        );
+
+        (engine, signers)
+    }
+
+    /// Verify that the engine properly shuts down when it receives the Shutdown update.
+    #[test]
+    fn test_shutdown() {
+        let (update_tx, update_rx) = channel();
+        let (_, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into(), vec![2].into(), vec![3].into()],
+            last_proposal: None,
+        };
+
+        let (mut engine, _) = new_engine();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network),
+                    Box::new(manager),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Verify that the primary of a 4 member network pre-prepares the proposal it creates, and
+    /// commits it once a quorum of 3 members has prepared and committed it.
+    #[test]
+    fn test_primary() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into(), vec![2].into(), vec![3].into()],
+            last_proposal: None,
+        };
+
+        let (mut engine, signers) = new_engine();
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // The primary creates and checks a proposal, then sends a pre-prepare for it
+        loop {
+            if network.broadcast_messages().len() >= 1 {
+                break;
+            }
+        }
+        let pre_prepare: PbftMessage =
+            Message::parse_from_bytes(&network.broadcast_messages()[0]).unwrap();
+        assert_eq!(
+            pre_prepare.get_message_type(),
+            PbftMessage_Type::PRE_PREPARE
+        );
+        assert_eq!(pre_prepare.get_view(), 0);
+        assert_eq!(pre_prepare.get_sequence_number(), 1);
+        assert_eq!(pre_prepare.get_proposal_id(), &[1]);
+
+        // Once two other members have prepared the proposal, the primary sends a commit
+        for peer in &[1, 2] {
+            consensus_msg_tx
+                .send(ConsensusMessage::new(
+                    pbft_message(
+                        PbftMessage_Type::PREPARE,
+                        0,
+                        1,
+                        vec![1],
+                        *peer,
+                        &*signers[*peer as usize],
+                    ),
+                    vec![*peer].into(),
+                ))
+                .expect("failed to send prepare");
+        }
+        loop {
+            if network.broadcast_messages().len() >= 2 {
+                break;
+            }
+        }
+        let commit: PbftMessage =
+            Message::parse_from_bytes(&network.broadcast_messages()[1]).unwrap();
+        assert_eq!(commit.get_message_type(), PbftMessage_Type::COMMIT);
+        assert_eq!(commit.get_proposal_id(), &[1]);
+
+        // Once two other members have committed the proposal, the primary accepts it
+        for peer in &[1, 2] {
+            consensus_msg_tx
+                .send(ConsensusMessage::new(
+                    pbft_message(
+                        PbftMessage_Type::COMMIT,
+                        0,
+                        1,
+                        vec![1],
+                        *peer,
+                        &*signers[*peer as usize],
+                    ),
+                    vec![*peer].into(),
+                ))
+                .expect("failed to send commit");
+        }
+        loop {
+            if manager.accepted_proposals().len() >= 1 {
+                break;
+            }
+        }
+        assert_eq!(manager.accepted_proposals()[0].0, vec![1].into());
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    fn pbft_message(
+        message_type: PbftMessage_Type,
+        view: u64,
+        sequence_number: u64,
+        proposal_id: Vec<u8>,
+        member: u8,
+        signer: &dyn Signer,
+    ) -> Vec<u8> {
+        let mut msg = PbftMessage::new();
+        msg.set_message_type(message_type);
+        msg.set_view(view);
+        msg.set_sequence_number(sequence_number);
+        msg.set_proposal_id(proposal_id);
+        sign_message(&mut msg, &PeerId::from(vec![member]), signer).expect("failed to sign message")
+    }
+}
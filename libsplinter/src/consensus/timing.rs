@@ -59,4 +59,10 @@ impl Timeout {
     pub fn stop(&mut self) {
         self.state = TimeoutState::Inactive;
     }
+
+    /// Check if the timer has been started and not stopped since
+    #[cfg(any(feature = "pbft", feature = "two-phase-coordinator-policy"))]
+    pub fn is_started(&self) -> bool {
+        self.state != TimeoutState::Inactive
+    }
 }
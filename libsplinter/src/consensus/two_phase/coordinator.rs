@@ -0,0 +1,228 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Policies that determine which verifier coordinates a two-phase commit proposal.
+//!
+//! A policy orders the verifiers of a proposal: the first verifier is the proposal's coordinator,
+//! and each of the others takes over coordination of the proposal if the verifiers before it time
+//! out. Every verifier must use the same policy, so they agree on the order.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::consensus::{PeerId, Proposal};
+
+/// The weight of a verifier that is not given a weight by a `WeightedPolicy`
+const DEFAULT_WEIGHT: u32 = 1;
+
+/// Determines the order in which the verifiers of a proposal coordinate it.
+pub trait CoordinatorPolicy: Send {
+    /// Return the verifiers in the order in which they coordinate the given proposal.
+    fn coordinator_order(&self, proposal: &Proposal, verifiers: &HashSet<PeerId>) -> Vec<PeerId>;
+}
+
+/// The verifier with the lowest ID coordinates every proposal (bully algorithm). This is the
+/// default policy.
+#[derive(Default)]
+pub struct LowestIdPolicy;
+
+impl CoordinatorPolicy for LowestIdPolicy {
+    fn coordinator_order(&self, _proposal: &Proposal, verifiers: &HashSet<PeerId>) -> Vec<PeerId> {
+        sorted(verifiers)
+    }
+}
+
+/// The verifiers take turns coordinating proposals, in order of their IDs, based on the height of
+/// each proposal.
+#[derive(Default)]
+pub struct RoundRobinPolicy;
+
+impl CoordinatorPolicy for RoundRobinPolicy {
+    fn coordinator_order(&self, proposal: &Proposal, verifiers: &HashSet<PeerId>) -> Vec<PeerId> {
+        let mut order = sorted(verifiers);
+        if !order.is_empty() {
+            let turn = (proposal.proposal_height % order.len() as u64) as usize;
+            order.rotate_left(turn);
+        }
+        order
+    }
+}
+
+/// The verifiers coordinate proposals in proportion to their weights, based on the height of each
+/// proposal. Verifiers without a weight have a weight of 1; verifiers with a weight of 0 only
+/// coordinate proposals when the other verifiers time out.
+pub struct WeightedPolicy {
+    weights: HashMap<PeerId, u32>,
+}
+
+impl WeightedPolicy {
+    pub fn new(weights: HashMap<PeerId, u32>) -> Self {
+        WeightedPolicy { weights }
+    }
+}
+
+impl CoordinatorPolicy for WeightedPolicy {
+    fn coordinator_order(&self, proposal: &Proposal, verifiers: &HashSet<PeerId>) -> Vec<PeerId> {
+        let verifiers = sorted(verifiers);
+
+        // Each verifier gets as many consecutive turns as its weight; the turns are counted
+        // rather than listed, so large weights don't need to be allocated
+        let mut first_turns = vec![];
+        let mut total_turns: u64 = 0;
+        for verifier in &verifiers {
+            let weight = u64::from(
+                self.weights
+                    .get(verifier)
+                    .copied()
+                    .unwrap_or(DEFAULT_WEIGHT),
+            );
+            if weight > 0 {
+                first_turns.push((verifier, total_turns, weight));
+                total_turns += weight;
+            }
+        }
+
+        let mut order: Vec<PeerId> = vec![];
+        if total_turns > 0 {
+            // The verifiers are ordered by how many turns after the current one their next turn
+            // is; the verifier whose turn it is comes first
+            let current_turn = proposal.proposal_height % total_turns;
+            let mut turns_until = first_turns
+                .into_iter()
+                .map(|(verifier, first_turn, weight)| {
+                    let turns_until = if (first_turn..first_turn + weight).contains(&current_turn) {
+                        0
+                    } else {
+                        (first_turn + total_turns - current_turn) % total_turns
+                    };
+                    (turns_until, verifier)
+                })
+                .collect::<Vec<_>>();
+            turns_until.sort();
+            order.extend(
+                turns_until
+                    .into_iter()
+                    .map(|(_, verifier)| verifier.clone()),
+            );
+        }
+
+        for verifier in verifiers {
+            if !order.contains(&verifier) {
+                order.push(verifier);
+            }
+        }
+
+        order
+    }
+}
+
+fn sorted(verifiers: &HashSet<PeerId>) -> Vec<PeerId> {
+    let mut verifiers = verifiers.iter().cloned().collect::<Vec<_>>();
+    verifiers.sort();
+    verifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(id: u8) -> PeerId {
+        vec![id].into()
+    }
+
+    fn verifiers() -> HashSet<PeerId> {
+        vec![peer(2), peer(0), peer(1)].into_iter().collect()
+    }
+
+    fn proposal(proposal_height: u64) -> Proposal {
+        Proposal {
+            proposal_height,
+            ..Default::default()
+        }
+    }
+
+    fn coordinators(policy: &dyn CoordinatorPolicy, heights: u64) -> Vec<PeerId> {
+        (0..heights)
+            .map(|height| policy.coordinator_order(&proposal(height), &verifiers())[0].clone())
+            .collect()
+    }
+
+    /// Verify that the lowest ID policy always orders the verifiers by ID.
+    #[test]
+    fn lowest_id() {
+        let policy = LowestIdPolicy;
+
+        for height in 0..3 {
+            assert_eq!(
+                policy.coordinator_order(&proposal(height), &verifiers()),
+                vec![peer(0), peer(1), peer(2)],
+            );
+        }
+    }
+
+    /// Verify that the round-robin policy rotates the coordinator with the proposal height, and
+    /// that the remaining verifiers follow the coordinator in order of their IDs.
+    #[test]
+    fn round_robin() {
+        let policy = RoundRobinPolicy;
+
+        assert_eq!(
+            coordinators(&policy, 4),
+            vec![peer(0), peer(1), peer(2), peer(0)],
+        );
+        assert_eq!(
+            policy.coordinator_order(&proposal(1), &verifiers()),
+            vec![peer(1), peer(2), peer(0)],
+        );
+    }
+
+    /// Verify that the weighted policy gives each verifier as many turns to coordinate as its
+    /// weight, and that a verifier with a weight of 0 is only ordered after the others.
+    #[test]
+    fn weighted() {
+        let weights = vec![(peer(0), 2), (peer(2), 0)].into_iter().collect();
+        let policy = WeightedPolicy::new(weights);
+
+        assert_eq!(
+            coordinators(&policy, 6),
+            vec![peer(0), peer(0), peer(1), peer(0), peer(0), peer(1)],
+        );
+        assert_eq!(
+            policy.coordinator_order(&proposal(2), &verifiers()),
+            vec![peer(1), peer(0), peer(2)],
+        );
+    }
+
+    /// Verify that the weighted policy handles the largest weights without allocating a turn for
+    /// each unit of weight.
+    #[test]
+    fn weighted_large_weights() {
+        let weights = vec![(peer(0), u32::MAX), (peer(1), u32::MAX)]
+            .into_iter()
+            .collect();
+        let policy = WeightedPolicy::new(weights);
+
+        assert_eq!(
+            policy.coordinator_order(&proposal(0), &verifiers()),
+            vec![peer(0), peer(1), peer(2)],
+        );
+        assert_eq!(
+            policy.coordinator_order(&proposal(u64::from(u32::MAX)), &verifiers()),
+            vec![peer(1), peer(2), peer(0)],
+        );
+        assert_eq!(
+            policy.coordinator_order(&proposal(2 * u64::from(u32::MAX)), &verifiers()),
+            vec![peer(2), peer(0), peer(1)],
+        );
+    }
+}
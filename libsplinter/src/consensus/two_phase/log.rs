@@ -0,0 +1,512 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A durable log of the progress of two-phase commit proposals.
+//!
+//! The `TwoPhaseEngine` records each step of a proposal (starting it, voting on it, deciding its
+//! result, and completing it) before acting on that step. When the engine restarts, it replays the
+//! log to finish any proposal that was in progress.
+
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use protobuf::error::ProtobufError;
+use protobuf::Message;
+
+use crate::consensus::{PeerId, ProposalId};
+use crate::protos::two_phase::{
+    TwoPhaseLogEntry as TwoPhaseLogEntryProto, TwoPhaseLogEntry_Type,
+    TwoPhaseMessage_ProposalResult, TwoPhaseMessage_ProposalVerificationResponse,
+};
+
+/// The size, in bytes, of the length prefix of each entry in a `FileTwoPhaseLog`
+const ENTRY_LENGTH_PREFIX_SIZE: usize = 4;
+
+/// A step in the progress of a proposal.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TwoPhaseLogEntry {
+    /// This node started evaluating the proposal.
+    ProposalStarted {
+        proposal_id: ProposalId,
+        coordinator_id: PeerId,
+        required_verifiers: Vec<PeerId>,
+    },
+    /// This node, as a participant, voted for or against the proposal.
+    ProposalVoted {
+        proposal_id: ProposalId,
+        verified: bool,
+    },
+    /// The result of the proposal was decided; `apply` is false if the proposal was rejected.
+    ProposalDecided {
+        proposal_id: ProposalId,
+        apply: bool,
+    },
+    /// This node has finished with the proposal.
+    ProposalCompleted { proposal_id: ProposalId },
+    /// This node's proposal manager accepted the proposal after it was decided.
+    ProposalApplied { proposal_id: ProposalId },
+}
+
+impl TwoPhaseLogEntry {
+    /// Get the ID of the proposal the entry is for.
+    pub fn proposal_id(&self) -> &ProposalId {
+        match self {
+            TwoPhaseLogEntry::ProposalStarted { proposal_id, .. }
+            | TwoPhaseLogEntry::ProposalVoted { proposal_id, .. }
+            | TwoPhaseLogEntry::ProposalDecided { proposal_id, .. }
+            | TwoPhaseLogEntry::ProposalCompleted { proposal_id }
+            | TwoPhaseLogEntry::ProposalApplied { proposal_id } => proposal_id,
+        }
+    }
+}
+
+impl TryFrom<TwoPhaseLogEntryProto> for TwoPhaseLogEntry {
+    type Error = TwoPhaseLogError;
+
+    fn try_from(mut proto: TwoPhaseLogEntryProto) -> Result<Self, Self::Error> {
+        let proposal_id = ProposalId::from(proto.take_proposal_id());
+
+        match proto.get_entry_type() {
+            TwoPhaseLogEntry_Type::PROPOSAL_STARTED => Ok(TwoPhaseLogEntry::ProposalStarted {
+                proposal_id,
+                coordinator_id: proto.take_coordinator_id().into(),
+                required_verifiers: proto
+                    .take_required_verifiers()
+                    .into_iter()
+                    .map(PeerId::from)
+                    .collect(),
+            }),
+            TwoPhaseLogEntry_Type::PROPOSAL_VOTED => match proto.get_vote() {
+                TwoPhaseMessage_ProposalVerificationResponse::VERIFIED => {
+                    Ok(TwoPhaseLogEntry::ProposalVoted {
+                        proposal_id,
+                        verified: true,
+                    })
+                }
+                TwoPhaseMessage_ProposalVerificationResponse::FAILED => {
+                    Ok(TwoPhaseLogEntry::ProposalVoted {
+                        proposal_id,
+                        verified: false,
+                    })
+                }
+                TwoPhaseMessage_ProposalVerificationResponse::UNSET_VERIFICATION_RESPONSE => {
+                    Err(TwoPhaseLogError::InvalidEntry(format!(
+                        "vote unset for proposal {}",
+                        proposal_id
+                    )))
+                }
+            },
+            TwoPhaseLogEntry_Type::PROPOSAL_DECIDED => match proto.get_result() {
+                TwoPhaseMessage_ProposalResult::APPLY => Ok(TwoPhaseLogEntry::ProposalDecided {
+                    proposal_id,
+                    apply: true,
+                }),
+                TwoPhaseMessage_ProposalResult::REJECT => Ok(TwoPhaseLogEntry::ProposalDecided {
+                    proposal_id,
+                    apply: false,
+                }),
+                TwoPhaseMessage_ProposalResult::UNSET_RESULT => {
+                    Err(TwoPhaseLogError::InvalidEntry(format!(
+                        "result unset for proposal {}",
+                        proposal_id
+                    )))
+                }
+            },
+            TwoPhaseLogEntry_Type::PROPOSAL_COMPLETED => {
+                Ok(TwoPhaseLogEntry::ProposalCompleted { proposal_id })
+            }
+            TwoPhaseLogEntry_Type::PROPOSAL_APPLIED => {
+                Ok(TwoPhaseLogEntry::ProposalApplied { proposal_id })
+            }
+            TwoPhaseLogEntry_Type::UNSET_ENTRY_TYPE => Err(TwoPhaseLogError::InvalidEntry(
+                format!("entry type unset for proposal {}", proposal_id),
+            )),
+        }
+    }
+}
+
+impl Into<TwoPhaseLogEntryProto> for TwoPhaseLogEntry {
+    fn into(self) -> TwoPhaseLogEntryProto {
+        let mut proto = TwoPhaseLogEntryProto::new();
+
+        match self {
+            TwoPhaseLogEntry::ProposalStarted {
+                proposal_id,
+                coordinator_id,
+                required_verifiers,
+            } => {
+                proto.set_entry_type(TwoPhaseLogEntry_Type::PROPOSAL_STARTED);
+                proto.set_proposal_id(proposal_id.into());
+                proto.set_coordinator_id(coordinator_id.into());
+                proto.set_required_verifiers(
+                    required_verifiers
+                        .into_iter()
+                        .map(Into::into)
+                        .collect::<Vec<Vec<u8>>>()
+                        .into(),
+                );
+            }
+            TwoPhaseLogEntry::ProposalVoted {
+                proposal_id,
+                verified,
+            } => {
+                proto.set_entry_type(TwoPhaseLogEntry_Type::PROPOSAL_VOTED);
+                proto.set_proposal_id(proposal_id.into());
+                proto.set_vote(if verified {
+                    TwoPhaseMessage_ProposalVerificationResponse::VERIFIED
+                } else {
+                    TwoPhaseMessage_ProposalVerificationResponse::FAILED
+                });
+            }
+            TwoPhaseLogEntry::ProposalDecided { proposal_id, apply } => {
+                proto.set_entry_type(TwoPhaseLogEntry_Type::PROPOSAL_DECIDED);
+                proto.set_proposal_id(proposal_id.into());
+                proto.set_result(if apply {
+                    TwoPhaseMessage_ProposalResult::APPLY
+                } else {
+                    TwoPhaseMessage_ProposalResult::REJECT
+                });
+            }
+            TwoPhaseLogEntry::ProposalCompleted { proposal_id } => {
+                proto.set_entry_type(TwoPhaseLogEntry_Type::PROPOSAL_COMPLETED);
+                proto.set_proposal_id(proposal_id.into());
+            }
+            TwoPhaseLogEntry::ProposalApplied { proposal_id } => {
+                proto.set_entry_type(TwoPhaseLogEntry_Type::PROPOSAL_APPLIED);
+                proto.set_proposal_id(proposal_id.into());
+            }
+        }
+
+        proto
+    }
+}
+
+impl TryFrom<&[u8]> for TwoPhaseLogEntry {
+    type Error = TwoPhaseLogError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let proto: TwoPhaseLogEntryProto = Message::parse_from_bytes(bytes)?;
+        TwoPhaseLogEntry::try_from(proto)
+    }
+}
+
+impl TryInto<Vec<u8>> for TwoPhaseLogEntry {
+    type Error = TwoPhaseLogError;
+
+    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
+        let proto: TwoPhaseLogEntryProto = self.into();
+        Ok(proto.write_to_bytes()?)
+    }
+}
+
+/// Durable storage for the entries recorded by a `TwoPhaseEngine`.
+pub trait TwoPhaseLog: Send {
+    /// Add an entry to the end of the log. The entry must be durable when this method returns.
+    fn append(&mut self, entry: &TwoPhaseLogEntry) -> Result<(), TwoPhaseLogError>;
+
+    /// Get all entries in the log, in the order they were appended.
+    fn entries(&self) -> Result<Vec<TwoPhaseLogEntry>, TwoPhaseLogError>;
+
+    /// Replace the contents of the log with the given entries; used to discard entries for
+    /// proposals that are no longer needed.
+    fn compact(&mut self, entries: &[TwoPhaseLogEntry]) -> Result<(), TwoPhaseLogError>;
+}
+
+/// A `TwoPhaseLog` that is backed by a file.
+///
+/// Each entry is written as a 4-byte, big-endian length followed by the entry's bytes, and the
+/// file is synced after every append. If the node crashed while an entry was being written, the
+/// incomplete entry is discarded when the log is opened.
+pub struct FileTwoPhaseLog {
+    path: PathBuf,
+    file: File,
+}
+
+impl FileTwoPhaseLog {
+    /// Open the log at the given path, creating it if it does not exist.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, TwoPhaseLogError> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+
+        let mut bytes = vec![];
+        file.read_to_end(&mut bytes)?;
+        let (_, valid_len) = parse_entries(&bytes)?;
+        if valid_len < bytes.len() {
+            warn!(
+                "Discarding incomplete entry at the end of two-phase log {}",
+                path.display()
+            );
+            file.set_len(valid_len as u64)?;
+            file.sync_all()?;
+        }
+
+        Ok(Self { path, file })
+    }
+}
+
+impl TwoPhaseLog for FileTwoPhaseLog {
+    fn append(&mut self, entry: &TwoPhaseLogEntry) -> Result<(), TwoPhaseLogError> {
+        self.file.write_all(&encode_entry(entry)?)?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<TwoPhaseLogEntry>, TwoPhaseLogError> {
+        let (entries, _) = parse_entries(&fs::read(&self.path)?)?;
+        Ok(entries)
+    }
+
+    fn compact(&mut self, entries: &[TwoPhaseLogEntry]) -> Result<(), TwoPhaseLogError> {
+        // Write the new contents to a temporary file and move it into place, so the log is never
+        // left partially written
+        let mut temp_path = self.path.clone().into_os_string();
+        temp_path.push(".tmp");
+
+        let mut temp_file = File::create(&temp_path)?;
+        for entry in entries {
+            temp_file.write_all(&encode_entry(entry)?)?;
+        }
+        temp_file.sync_all()?;
+        fs::rename(&temp_path, &self.path)?;
+
+        self.file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(&self.path)?;
+
+        Ok(())
+    }
+}
+
+/// A `TwoPhaseLog` that is kept in memory. This log is not durable; it is intended for testing.
+/// Clones of the log share the same entries.
+#[derive(Clone, Default)]
+pub struct MemoryTwoPhaseLog {
+    entries: Arc<Mutex<Vec<TwoPhaseLogEntry>>>,
+}
+
+impl MemoryTwoPhaseLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TwoPhaseLog for MemoryTwoPhaseLog {
+    fn append(&mut self, entry: &TwoPhaseLogEntry) -> Result<(), TwoPhaseLogError> {
+        self.entries
+            .lock()
+            .map_err(|_| TwoPhaseLogError::LockPoisoned)?
+            .push(entry.clone());
+        Ok(())
+    }
+
+    fn entries(&self) -> Result<Vec<TwoPhaseLogEntry>, TwoPhaseLogError> {
+        Ok(self
+            .entries
+            .lock()
+            .map_err(|_| TwoPhaseLogError::LockPoisoned)?
+            .clone())
+    }
+
+    fn compact(&mut self, entries: &[TwoPhaseLogEntry]) -> Result<(), TwoPhaseLogError> {
+        *self
+            .entries
+            .lock()
+            .map_err(|_| TwoPhaseLogError::LockPoisoned)? = entries.to_vec();
+        Ok(())
+    }
+}
+
+fn encode_entry(entry: &TwoPhaseLogEntry) -> Result<Vec<u8>, TwoPhaseLogError> {
+    let entry_bytes: Vec<u8> = entry.clone().try_into()?;
+
+    let mut bytes = Vec::with_capacity(ENTRY_LENGTH_PREFIX_SIZE + entry_bytes.len());
+    bytes.extend_from_slice(&(entry_bytes.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(&entry_bytes);
+
+    Ok(bytes)
+}
+
+/// Parse the complete entries in the given bytes, and return them along with the number of bytes
+/// they take up.
+fn parse_entries(bytes: &[u8]) -> Result<(Vec<TwoPhaseLogEntry>, usize), TwoPhaseLogError> {
+    let mut entries = vec![];
+    let mut offset = 0;
+
+    while bytes.len() - offset >= ENTRY_LENGTH_PREFIX_SIZE {
+        let mut len_bytes = [0; ENTRY_LENGTH_PREFIX_SIZE];
+        len_bytes.copy_from_slice(&bytes[offset..offset + ENTRY_LENGTH_PREFIX_SIZE]);
+        let entry_len = u32::from_be_bytes(len_bytes) as usize;
+
+        let entry_start = offset + ENTRY_LENGTH_PREFIX_SIZE;
+        if bytes.len() - entry_start < entry_len {
+            break;
+        }
+
+        entries.push(TwoPhaseLogEntry::try_from(
+            &bytes[entry_start..entry_start + entry_len],
+        )?);
+        offset = entry_start + entry_len;
+    }
+
+    Ok((entries, offset))
+}
+
+#[derive(Debug)]
+pub enum TwoPhaseLogError {
+    /// The log contains an entry that is not valid.
+    InvalidEntry(String),
+    /// The log could not be read or written.
+    Io(io::Error),
+    /// An internal lock was poisoned.
+    LockPoisoned,
+}
+
+impl Error for TwoPhaseLogError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TwoPhaseLogError::InvalidEntry(_) => None,
+            TwoPhaseLogError::Io(err) => Some(err),
+            TwoPhaseLogError::LockPoisoned => None,
+        }
+    }
+}
+
+impl std::fmt::Display for TwoPhaseLogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TwoPhaseLogError::InvalidEntry(msg) => {
+                write!(f, "two-phase log contains an invalid entry: {}", msg)
+            }
+            TwoPhaseLogError::Io(err) => write!(f, "unable to access two-phase log: {}", err),
+            TwoPhaseLogError::LockPoisoned => write!(f, "two-phase log lock poisoned"),
+        }
+    }
+}
+
+impl From<io::Error> for TwoPhaseLogError {
+    fn from(err: io::Error) -> Self {
+        TwoPhaseLogError::Io(err)
+    }
+}
+
+impl From<ProtobufError> for TwoPhaseLogError {
+    fn from(err: ProtobufError) -> Self {
+        TwoPhaseLogError::InvalidEntry(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempdir::TempDir;
+
+    fn test_entries() -> Vec<TwoPhaseLogEntry> {
+        vec![
+            TwoPhaseLogEntry::ProposalStarted {
+                proposal_id: vec![1].into(),
+                coordinator_id: vec![0].into(),
+                required_verifiers: vec![vec![0].into(), vec![1].into()],
+            },
+            TwoPhaseLogEntry::ProposalVoted {
+                proposal_id: vec![1].into(),
+                verified: true,
+            },
+            TwoPhaseLogEntry::ProposalDecided {
+                proposal_id: vec![1].into(),
+                apply: true,
+            },
+            TwoPhaseLogEntry::ProposalApplied {
+                proposal_id: vec![1].into(),
+            },
+            TwoPhaseLogEntry::ProposalCompleted {
+                proposal_id: vec![1].into(),
+            },
+        ]
+    }
+
+    /// Verify that entries appended to a `FileTwoPhaseLog` are returned when the log is reopened,
+    /// and that compacting the log replaces its entries.
+    #[test]
+    fn file_log_append_and_compact() {
+        let temp_dir = TempDir::new("file_log_append_and_compact").expect("Failed to create dir");
+        let path = temp_dir.path().join("two_phase.log");
+
+        let mut log = FileTwoPhaseLog::new(&path).expect("Failed to open log");
+        for entry in test_entries() {
+            log.append(&entry).expect("Failed to append entry");
+        }
+        assert_eq!(
+            log.entries().expect("Failed to get entries"),
+            test_entries()
+        );
+
+        let mut log = FileTwoPhaseLog::new(&path).expect("Failed to reopen log");
+        assert_eq!(
+            log.entries().expect("Failed to get entries"),
+            test_entries()
+        );
+
+        log.compact(&test_entries()[2..])
+            .expect("Failed to compact log");
+        log.append(&test_entries()[0])
+            .expect("Failed to append entry");
+
+        let mut expected = test_entries()[2..].to_vec();
+        expected.push(test_entries()[0].clone());
+        assert_eq!(log.entries().expect("Failed to get entries"), expected);
+    }
+
+    /// Verify that an entry that was only partially written to a `FileTwoPhaseLog` is discarded
+    /// when the log is opened, and that new entries can be appended after it is discarded.
+    #[test]
+    fn file_log_incomplete_entry() {
+        let temp_dir = TempDir::new("file_log_incomplete_entry").expect("Failed to create dir");
+        let path = temp_dir.path().join("two_phase.log");
+
+        let mut log = FileTwoPhaseLog::new(&path).expect("Failed to open log");
+        log.append(&test_entries()[0])
+            .expect("Failed to append entry");
+
+        let partial_entry = encode_entry(&test_entries()[1]).expect("Failed to encode entry");
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("Failed to open log file")
+            .write_all(&partial_entry[..partial_entry.len() - 1])
+            .expect("Failed to write partial entry");
+
+        let mut log = FileTwoPhaseLog::new(&path).expect("Failed to reopen log");
+        assert_eq!(
+            log.entries().expect("Failed to get entries"),
+            test_entries()[..1].to_vec()
+        );
+
+        log.append(&test_entries()[1])
+            .expect("Failed to append entry");
+        assert_eq!(
+            log.entries().expect("Failed to get entries"),
+            test_entries()[..2].to_vec()
+        );
+    }
+}
@@ -18,6 +18,19 @@
 //! time. A proposal manager can define its own set of required verifiers by setting this
 //! information in the consensus data.
 //!
+//! With the `two-phase-coordinator-policy` feature, the coordinator is chosen by a
+//! `CoordinatorPolicy` instead, which may rotate coordination between the verifiers. If a
+//! coordinator does not complete a proposal within the coordinator timeout, the next verifier in
+//! the policy's order takes over coordination of the proposal, rather than the proposal being
+//! stalled. Every verifier, including the coordinators that were passed over, is still required
+//! to verify the proposal. A verifier that takes over first asks its peers for the result of the
+//! proposal, and adopts the result if a previous coordinator already decided it; a previous
+//! coordinator hands over coordination by sending its own vote to the new coordinator. The new
+//! coordinator does not reject the proposal when it times out while a previous coordinator that
+//! it approved the proposal for has not responded, since that coordinator may have applied the
+//! proposal; the proposal is stalled until that coordinator responds instead. This feature
+//! requires the `two-phase-log` feature, which keeps the results of decided proposals.
+//!
 //! # Known limitations of this 2PC implementation
 //!
 //! There is a potential race condition in two-phase commit where two different proposals are in
@@ -36,10 +49,21 @@
 //! Another limitation of this implementation is that it is not fully resilient to crashes; for
 //! instance, if the coordinator commits a proposal but crashes before it is able to send the
 //! `APPLY` message to the other nodes, the network will be out of sync because the coordinator
-//! does not know to send the message when it restarts. This limitation will be solved by
-//! re-implementing 2PC as a stateless algorithm.
-
-mod timing;
+//! does not know to send the message when it restarts.
+//!
+//! With the `two-phase-log` feature, an engine that is given a `TwoPhaseLog` addresses this by
+//! recording each proposal's progress before acting on it. When the engine restarts, it replays
+//! the log: a coordinator resends the result of a proposal it decided but did not complete, and
+//! rejects a proposal it had not yet decided. A participant that voted for a proposal but did not
+//! learn its result asks its peers for the result until one of them responds. A proposal that was
+//! approved is only completed once the log records that it was applied; if the engine stopped
+//! between the decision and applying it, the proposal manager is asked to re-apply it before the
+//! proposal is completed.
+
+#[cfg(feature = "two-phase-coordinator-policy")]
+pub mod coordinator;
+#[cfg(feature = "two-phase-log")]
+pub mod log;
 
 use std::collections::{HashSet, VecDeque};
 use std::sync::mpsc::{Receiver, RecvTimeoutError};
@@ -47,6 +71,9 @@ use std::time::Duration;
 
 use protobuf::Message;
 
+use crate::consensus::timing::Timeout;
+#[cfg(feature = "two-phase-log")]
+use crate::consensus::ProposalManagerError;
 use crate::consensus::{
     ConsensusEngine, ConsensusEngineError, ConsensusMessage, ConsensusNetworkSender, PeerId,
     Proposal, ProposalId, ProposalManager, ProposalUpdate, StartupState,
@@ -56,10 +83,20 @@ use crate::protos::two_phase::{
     TwoPhaseMessage_ProposalVerificationResponse, TwoPhaseMessage_Type,
 };
 
-use self::timing::Timeout;
+#[cfg(feature = "two-phase-coordinator-policy")]
+use self::coordinator::{CoordinatorPolicy, LowestIdPolicy};
+#[cfg(feature = "two-phase-log")]
+use self::log::{TwoPhaseLog, TwoPhaseLogEntry};
 
 const MESSAGE_RECV_TIMEOUT_MILLIS: u64 = 100;
 const PROPOSAL_RECV_TIMEOUT_MILLIS: u64 = 100;
+/// The number of proposal results to remember for peers that ask for them
+#[cfg(feature = "two-phase-log")]
+const DECIDED_PROPOSALS_SIZE: usize = 100;
+/// The number of entries the log may grow to before the entries for completed proposals are
+/// discarded
+#[cfg(feature = "two-phase-log")]
+const LOG_COMPACTION_THRESHOLD: usize = 1000;
 
 #[derive(Debug)]
 enum State {
@@ -75,6 +112,16 @@ struct TwoPhaseProposal {
     coordinator_id: PeerId,
     peers_verified: HashSet<PeerId>,
     required_verifiers: HashSet<PeerId>,
+    /// The verifiers in the order in which they coordinate the proposal
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    coordinator_order: Vec<PeerId>,
+    /// This node's verification response for the proposal, once it has checked it
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    vote: Option<TwoPhaseMessage_ProposalVerificationResponse>,
+    /// The other coordinators that this node sent an approving vote to; any of them may have
+    /// applied the proposal
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    approved_coordinators: HashSet<PeerId>,
 }
 
 impl TwoPhaseProposal {
@@ -88,9 +135,21 @@ impl TwoPhaseProposal {
             coordinator_id,
             peers_verified: HashSet::new(),
             required_verifiers,
+            #[cfg(feature = "two-phase-coordinator-policy")]
+            coordinator_order: vec![],
+            #[cfg(feature = "two-phase-coordinator-policy")]
+            vote: None,
+            #[cfg(feature = "two-phase-coordinator-policy")]
+            approved_coordinators: HashSet::new(),
         }
     }
 
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    fn with_coordinator_order(mut self, coordinator_order: Vec<PeerId>) -> Self {
+        self.coordinator_order = coordinator_order;
+        self
+    }
+
     fn proposal_id(&self) -> &ProposalId {
         &self.proposal_id
     }
@@ -110,6 +169,75 @@ impl TwoPhaseProposal {
     fn add_verified_peer(&mut self, id: PeerId) {
         self.peers_verified.insert(id);
     }
+
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    fn vote(&self) -> Option<TwoPhaseMessage_ProposalVerificationResponse> {
+        self.vote
+    }
+
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    fn set_vote(&mut self, vote: TwoPhaseMessage_ProposalVerificationResponse) {
+        self.vote = Some(vote);
+    }
+
+    /// Record that this node sent its vote to the given coordinator, if the vote approved the
+    /// proposal.
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    fn record_vote_sent(&mut self, coordinator_id: &PeerId) {
+        if self.vote == Some(TwoPhaseMessage_ProposalVerificationResponse::VERIFIED) {
+            self.approved_coordinators.insert(coordinator_id.clone());
+        }
+    }
+
+    /// Whether a coordinator that this node approved the proposal for has not handed coordination
+    /// over to this node, and so may have applied the proposal.
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    fn may_have_been_applied(&self) -> bool {
+        self.approved_coordinators
+            .iter()
+            .any(|id| !self.peers_verified.contains(id))
+    }
+
+    /// Make the given verifier the coordinator, if it follows the current coordinator in the
+    /// coordinator order. The verifiers before it are still required to verify the proposal.
+    /// Returns whether the coordinator changed.
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    fn change_coordinator(&mut self, coordinator_id: &PeerId) -> bool {
+        let current = self
+            .coordinator_order
+            .iter()
+            .position(|id| id == &self.coordinator_id);
+        let new = self
+            .coordinator_order
+            .iter()
+            .position(|id| id == coordinator_id);
+
+        match (current, new) {
+            (Some(current), Some(new)) if new > current => {
+                self.coordinator_id = coordinator_id.clone();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Make the verifier that follows the current coordinator in the coordinator order the
+    /// coordinator. Returns the new coordinator, if there is a verifier left to take over.
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    fn fail_over(&mut self) -> Option<PeerId> {
+        let next = self
+            .coordinator_order
+            .iter()
+            .position(|id| id == &self.coordinator_id)
+            .and_then(|current| self.coordinator_order.get(current + 1))
+            .cloned()?;
+
+        if self.change_coordinator(&next) {
+            Some(next)
+        } else {
+            None
+        }
+    }
 }
 
 pub struct TwoPhaseEngine {
@@ -119,6 +247,32 @@ pub struct TwoPhaseEngine {
     coordinator_timeout: Timeout,
     proposal_backlog: VecDeque<TwoPhaseProposal>,
     verification_request_backlog: VecDeque<ProposalId>,
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    coordinator_policy: Box<dyn CoordinatorPolicy>,
+    /// Active while this node, as a participant, is waiting for the coordinator to complete a
+    /// proposal it verified; when it expires, the next verifier takes over coordination.
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    failover_timeout: Timeout,
+    /// Active while this node has backlogged proposals but is not evaluating any proposal; when
+    /// it expires, the next verifier takes over coordination of the oldest backlogged proposal.
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    backlog_timeout: Timeout,
+    #[cfg(feature = "two-phase-log")]
+    log: Option<Box<dyn TwoPhaseLog>>,
+    /// The number of entries in the log
+    #[cfg(feature = "two-phase-log")]
+    log_entries: usize,
+    /// The results of recently decided proposals, for peers that ask for them
+    #[cfg(feature = "two-phase-log")]
+    decided_proposals: VecDeque<(ProposalId, TwoPhaseMessage_ProposalResult)>,
+    /// Active while this node, as a participant, is waiting for the result of a proposal it
+    /// verified; when it expires, the result is requested from peers.
+    #[cfg(feature = "two-phase-log")]
+    result_request_timeout: Timeout,
+    /// A proposal that was in progress when the engine last stopped, and whose result this node
+    /// is waiting for
+    #[cfg(feature = "two-phase-log")]
+    recovered_proposal: Option<ProposalId>,
 }
 
 impl TwoPhaseEngine {
@@ -130,9 +284,44 @@ impl TwoPhaseEngine {
             coordinator_timeout: Timeout::new(coordinator_timeout_duration),
             proposal_backlog: VecDeque::new(),
             verification_request_backlog: VecDeque::new(),
+            #[cfg(feature = "two-phase-coordinator-policy")]
+            coordinator_policy: Box::new(LowestIdPolicy),
+            #[cfg(feature = "two-phase-coordinator-policy")]
+            failover_timeout: Timeout::new(coordinator_timeout_duration),
+            #[cfg(feature = "two-phase-coordinator-policy")]
+            backlog_timeout: Timeout::new(coordinator_timeout_duration),
+            #[cfg(feature = "two-phase-log")]
+            log: None,
+            #[cfg(feature = "two-phase-log")]
+            log_entries: 0,
+            #[cfg(feature = "two-phase-log")]
+            decided_proposals: VecDeque::new(),
+            #[cfg(feature = "two-phase-log")]
+            result_request_timeout: Timeout::new(coordinator_timeout_duration),
+            #[cfg(feature = "two-phase-log")]
+            recovered_proposal: None,
         }
     }
 
+    /// Choose the coordinator of each proposal with the given policy, rather than choosing the
+    /// verifier with the lowest ID.
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    pub fn with_coordinator_policy(
+        mut self,
+        coordinator_policy: Box<dyn CoordinatorPolicy>,
+    ) -> Self {
+        self.coordinator_policy = coordinator_policy;
+        self
+    }
+
+    /// Record the progress of proposals in the given log, and recover any proposals that were in
+    /// progress from the log when the engine is run.
+    #[cfg(feature = "two-phase-log")]
+    pub fn with_log(mut self, log: Box<dyn TwoPhaseLog>) -> Self {
+        self.log = Some(log);
+        self
+    }
+
     fn handle_consensus_msg(
         &mut self,
         consensus_msg: ConsensusMessage,
@@ -146,6 +335,18 @@ impl TwoPhaseEngine {
             TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST => {
                 debug!("Proposal verification request received: {}", proposal_id);
 
+                #[cfg(feature = "two-phase-coordinator-policy")]
+                {
+                    if self.evaluating_proposal(&proposal_id) || self.proposal_decided(&proposal_id)
+                    {
+                        return self.handle_repeated_verification_request(
+                            proposal_id,
+                            &consensus_msg.origin_id,
+                            network_sender,
+                        );
+                    }
+                }
+
                 match self.state {
                     State::EvaluatingProposal(ref tpc_proposal)
                         if tpc_proposal.proposal_id() != &proposal_id =>
@@ -164,11 +365,18 @@ impl TwoPhaseEngine {
                             .position(|tpc_proposal| tpc_proposal.proposal_id() == &proposal_id)
                         {
                             Some(idx) => {
+                                // The request may come from a verifier that has taken over
+                                // coordination of the proposal
+                                #[cfg(feature = "two-phase-coordinator-policy")]
+                                self.proposal_backlog[idx]
+                                    .change_coordinator(&consensus_msg.origin_id);
+
                                 debug!("Checking proposal {}", proposal_id);
                                 proposal_manager.check_proposal(&proposal_id)?;
-                                self.state = State::EvaluatingProposal(
-                                    self.proposal_backlog.remove(idx).unwrap(),
-                                );
+                                let tpc_proposal = self.proposal_backlog.remove(idx).unwrap();
+                                #[cfg(feature = "two-phase-log")]
+                                self.record_proposal_started(&tpc_proposal)?;
+                                self.state = State::EvaluatingProposal(tpc_proposal);
                             }
                             None => {
                                 debug!(
@@ -191,6 +399,20 @@ impl TwoPhaseEngine {
                     return Ok(());
                 }
 
+                // A verifier that handed coordination over to another verifier must not decide
+                // the proposal
+                #[cfg(feature = "two-phase-coordinator-policy")]
+                {
+                    if !self.coordinating_proposal(&proposal_id) {
+                        debug!(
+                            "Ignoring verification response for proposal {} from {}; this node is \
+                             no longer its coordinator",
+                            proposal_id, consensus_msg.origin_id
+                        );
+                        return Ok(());
+                    }
+                }
+
                 match two_phase_msg.get_proposal_verification_response() {
                     TwoPhaseMessage_ProposalVerificationResponse::VERIFIED => {
                         debug!(
@@ -235,12 +457,32 @@ impl TwoPhaseEngine {
                     }
                 }
             }
+            #[cfg(feature = "two-phase-coordinator-policy")]
+            TwoPhaseMessage_Type::PROPOSAL_RESULT if self.coordinating_proposal(&proposal_id) => {
+                self.adopt_proposal_result(
+                    proposal_id,
+                    two_phase_msg.get_proposal_result(),
+                    &consensus_msg.origin_id,
+                    network_sender,
+                    proposal_manager,
+                )?;
+            }
             TwoPhaseMessage_Type::PROPOSAL_RESULT => match two_phase_msg.get_proposal_result() {
                 TwoPhaseMessage_ProposalResult::APPLY => {
                     if self.evaluating_proposal(&proposal_id) {
                         debug!("Accepting proposal {}", proposal_id);
+                        #[cfg(feature = "two-phase-log")]
+                        self.record_proposal_decision(
+                            &proposal_id,
+                            TwoPhaseMessage_ProposalResult::APPLY,
+                        )?;
+                        #[cfg(feature = "two-phase-log")]
+                        self.apply_decided_proposal(&proposal_id, proposal_manager)?;
+                        #[cfg(not(feature = "two-phase-log"))]
                         proposal_manager.accept_proposal(&proposal_id, None)?;
                         self.state = State::Idle;
+                        #[cfg(feature = "two-phase-log")]
+                        self.record_proposal_completion(&proposal_id)?;
                     } else {
                         warn!(
                             "Received unexpected apply result for proposal {}",
@@ -250,11 +492,27 @@ impl TwoPhaseEngine {
                 }
                 TwoPhaseMessage_ProposalResult::REJECT => {
                     debug!("Rejecting proposal {}", proposal_id);
-                    proposal_manager.reject_proposal(&proposal_id)?;
+                    let evaluating = self.evaluating_proposal(&proposal_id);
+                    #[cfg(feature = "two-phase-log")]
+                    {
+                        if evaluating {
+                            self.record_proposal_decision(
+                                &proposal_id,
+                                TwoPhaseMessage_ProposalResult::REJECT,
+                            )?;
+                        }
+                    }
+                    let reject_result = proposal_manager.reject_proposal(&proposal_id);
+                    #[cfg(feature = "two-phase-log")]
+                    let reject_result =
+                        self.check_recovered_proposal_result(&proposal_id, reject_result);
+                    reject_result?;
 
                     // Only update state if this was the currently evaluating proposal
-                    if self.evaluating_proposal(&proposal_id) {
+                    if evaluating {
                         self.state = State::Idle;
+                        #[cfg(feature = "two-phase-log")]
+                        self.record_proposal_completion(&proposal_id)?;
                     }
                 }
                 TwoPhaseMessage_ProposalResult::UNSET_RESULT => warn!(
@@ -262,6 +520,19 @@ impl TwoPhaseEngine {
                     consensus_msg.origin_id
                 ),
             },
+            #[cfg(feature = "two-phase-log")]
+            TwoPhaseMessage_Type::PROPOSAL_RESULT_REQUEST => {
+                self.handle_proposal_result_request(
+                    proposal_id,
+                    &consensus_msg.origin_id,
+                    network_sender,
+                )?;
+            }
+            #[cfg(not(feature = "two-phase-log"))]
+            TwoPhaseMessage_Type::PROPOSAL_RESULT_REQUEST => debug!(
+                "Ignoring proposal result request from {}",
+                consensus_msg.origin_id
+            ),
             TwoPhaseMessage_Type::UNSET_TYPE => warn!(
                 "Ignoring improperly specified two-phase message from {}",
                 consensus_msg.origin_id
@@ -297,6 +568,9 @@ impl TwoPhaseEngine {
                 {
                     debug!("Proposal valid: {}", proposal_id);
 
+                    #[cfg(feature = "two-phase-coordinator-policy")]
+                    tpc_proposal.set_vote(TwoPhaseMessage_ProposalVerificationResponse::VERIFIED);
+
                     if &self.id == tpc_proposal.coordinator_id() {
                         tpc_proposal.add_verified_peer(self.id.clone());
 
@@ -310,6 +584,13 @@ impl TwoPhaseEngine {
                         network_sender.broadcast(request.write_to_bytes()?)?;
                     } else {
                         debug!("Sending verified response for proposal {}", proposal_id);
+                        let coordinator_id = tpc_proposal.coordinator_id().clone();
+
+                        #[cfg(feature = "two-phase-coordinator-policy")]
+                        tpc_proposal.record_vote_sent(&coordinator_id);
+
+                        #[cfg(feature = "two-phase-log")]
+                        self.record_proposal_vote(&proposal_id, true)?;
 
                         let mut response = TwoPhaseMessage::new();
                         response
@@ -319,18 +600,28 @@ impl TwoPhaseEngine {
                             TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
                         );
 
-                        network_sender
-                            .send_to(tpc_proposal.coordinator_id(), response.write_to_bytes()?)?;
+                        network_sender.send_to(&coordinator_id, response.write_to_bytes()?)?;
+
+                        // If the coordinator's result is lost, it will be requested from peers
+                        #[cfg(feature = "two-phase-log")]
+                        self.result_request_timeout.start();
+
+                        // If the coordinator fails, the next verifier will take over
+                        #[cfg(feature = "two-phase-coordinator-policy")]
+                        self.failover_timeout.start();
                     }
                 }
                 _ => warn!("Got valid message for unknown proposal: {}", proposal_id),
             },
-            ProposalUpdate::ProposalInvalid(proposal_id) => match self.state {
-                State::EvaluatingProposal(ref tpc_proposal)
+            ProposalUpdate::ProposalInvalid(proposal_id) => match &mut self.state {
+                State::EvaluatingProposal(tpc_proposal)
                     if tpc_proposal.proposal_id() == &proposal_id =>
                 {
                     debug!("Proposal invalid: {}", proposal_id);
 
+                    #[cfg(feature = "two-phase-coordinator-policy")]
+                    tpc_proposal.set_vote(TwoPhaseMessage_ProposalVerificationResponse::FAILED);
+
                     if &self.id == tpc_proposal.coordinator_id() {
                         debug!("Rejecting proposal {}", proposal_id);
                         self.complete_coordination(
@@ -341,6 +632,10 @@ impl TwoPhaseEngine {
                         )?;
                     } else {
                         debug!("Sending failed response for proposal {}", proposal_id);
+                        let coordinator_id = tpc_proposal.coordinator_id().clone();
+
+                        #[cfg(feature = "two-phase-log")]
+                        self.record_proposal_vote(&proposal_id, false)?;
 
                         let mut response = TwoPhaseMessage::new();
                         response
@@ -350,8 +645,11 @@ impl TwoPhaseEngine {
                             TwoPhaseMessage_ProposalVerificationResponse::FAILED,
                         );
 
-                        network_sender
-                            .send_to(tpc_proposal.coordinator_id(), response.write_to_bytes()?)?;
+                        network_sender.send_to(&coordinator_id, response.write_to_bytes()?)?;
+
+                        // If the coordinator fails, the next verifier will take over
+                        #[cfg(feature = "two-phase-coordinator-policy")]
+                        self.failover_timeout.start();
                     }
                 }
                 _ => warn!("Got invalid message for unknown proposal: {}", proposal_id),
@@ -393,6 +691,8 @@ impl TwoPhaseEngine {
         debug!("Checking proposal {}", tpc_proposal.proposal_id());
         match proposal_manager.check_proposal(tpc_proposal.proposal_id()) {
             Ok(_) => {
+                #[cfg(feature = "two-phase-log")]
+                self.record_proposal_started(&tpc_proposal)?;
                 self.state = State::EvaluatingProposal(tpc_proposal);
                 self.coordinator_timeout.start();
             }
@@ -420,12 +720,28 @@ impl TwoPhaseEngine {
         network_sender: &dyn ConsensusNetworkSender,
         proposal_manager: &dyn ProposalManager,
     ) -> Result<(), ConsensusEngineError> {
+        // The result must be recorded before it is acted on, so that it can be sent to peers if
+        // this node crashes before sending it
+        #[cfg(feature = "two-phase-log")]
+        {
+            if proposal_result != TwoPhaseMessage_ProposalResult::UNSET_RESULT {
+                self.record_proposal_decision(&proposal_id, proposal_result)?;
+            }
+        }
+
         match proposal_result {
             TwoPhaseMessage_ProposalResult::APPLY => {
+                #[cfg(feature = "two-phase-log")]
+                self.apply_decided_proposal(&proposal_id, proposal_manager)?;
+                #[cfg(not(feature = "two-phase-log"))]
                 proposal_manager.accept_proposal(&proposal_id, None)?;
             }
             TwoPhaseMessage_ProposalResult::REJECT => {
-                proposal_manager.reject_proposal(&proposal_id)?;
+                let reject_result = proposal_manager.reject_proposal(&proposal_id);
+                #[cfg(feature = "two-phase-log")]
+                let reject_result =
+                    self.check_recovered_proposal_result(&proposal_id, reject_result);
+                reject_result?;
             }
             TwoPhaseMessage_ProposalResult::UNSET_RESULT => {
                 warn!(
@@ -441,11 +757,14 @@ impl TwoPhaseEngine {
 
         let mut result = TwoPhaseMessage::new();
         result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
-        result.set_proposal_id(proposal_id.into());
+        result.set_proposal_id(proposal_id.clone().into());
         result.set_proposal_result(proposal_result);
 
         network_sender.broadcast(result.write_to_bytes()?)?;
 
+        #[cfg(feature = "two-phase-log")]
+        self.record_proposal_completion(&proposal_id)?;
+
         Ok(())
     }
 
@@ -484,10 +803,19 @@ impl TwoPhaseEngine {
             verifiers
         };
 
-        // Determines which verifier is the coordinator; the coordinator is the verifier with the
-        // lowest peer ID (bully algorithm).
-        let coordinator = match verifiers.iter().min() {
-            Some(coordinator) => coordinator.clone(),
+        // Determines which verifier is the coordinator; by default, the coordinator is the
+        // verifier with the lowest peer ID (bully algorithm).
+        #[cfg(feature = "two-phase-coordinator-policy")]
+        let coordinator_order = self
+            .coordinator_policy
+            .coordinator_order(&proposal, &verifiers);
+        #[cfg(feature = "two-phase-coordinator-policy")]
+        let coordinator = coordinator_order.first().cloned();
+        #[cfg(not(feature = "two-phase-coordinator-policy"))]
+        let coordinator = verifiers.iter().min().cloned();
+
+        let coordinator = match coordinator {
+            Some(coordinator) => coordinator,
             None => {
                 error!(
                     "Rejecting proposal; no verifiers specified: {}",
@@ -500,6 +828,8 @@ impl TwoPhaseEngine {
         };
 
         let tpc_proposal = TwoPhaseProposal::new(proposal.id, coordinator, verifiers);
+        #[cfg(feature = "two-phase-coordinator-policy")]
+        let tpc_proposal = tpc_proposal.with_coordinator_order(coordinator_order);
 
         if let State::EvaluatingProposal(ref current_proposal) = self.state {
             if tpc_proposal.proposal_id() == current_proposal.proposal_id() {
@@ -531,7 +861,9 @@ impl TwoPhaseEngine {
         Ok(())
     }
 
-    /// If the coordinator timeout has expired, abort the current proposal.
+    /// If the coordinator timeout has expired, abort the current proposal. With the
+    /// `two-phase-coordinator-policy` feature, a proposal that a previous coordinator may have
+    /// applied is not aborted; its result and the verifiers' votes are requested again instead.
     fn abort_proposal_if_timed_out(
         &mut self,
         network_sender: &dyn ConsensusNetworkSender,
@@ -539,6 +871,31 @@ impl TwoPhaseEngine {
     ) -> Result<(), ConsensusEngineError> {
         if let State::EvaluatingProposal(ref tpc_proposal) = self.state {
             if self.coordinator_timeout.check_expired() {
+                #[cfg(feature = "two-phase-coordinator-policy")]
+                {
+                    if tpc_proposal.may_have_been_applied() {
+                        warn!(
+                            "Proposal {} timed out, but a previous coordinator may have applied \
+                             it; requesting its result again",
+                            tpc_proposal.proposal_id()
+                        );
+                        let proposal_id = tpc_proposal.proposal_id().clone();
+                        let vote = tpc_proposal.vote();
+                        self.coordinator_timeout.start();
+                        self.request_decided_result(&proposal_id, network_sender)?;
+                        if vote == Some(TwoPhaseMessage_ProposalVerificationResponse::VERIFIED) {
+                            let mut request = TwoPhaseMessage::new();
+                            request.set_message_type(
+                                TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST,
+                            );
+                            request.set_proposal_id(proposal_id.into());
+
+                            network_sender.broadcast(request.write_to_bytes()?)?;
+                        }
+                        return Ok(());
+                    }
+                }
+
                 warn!(
                     "Proposal timed out; rejecting: {}",
                     tpc_proposal.proposal_id()
@@ -582,6 +939,8 @@ impl TwoPhaseEngine {
 
                 debug!("Checking proposal from backlog: {}", proposal_id);
                 proposal_manager.check_proposal(&proposal_id)?;
+                #[cfg(feature = "two-phase-log")]
+                self.record_proposal_started(&tpc_proposal)?;
                 self.state = State::EvaluatingProposal(tpc_proposal);
             }
         }
@@ -624,153 +983,1689 @@ impl TwoPhaseEngine {
     }
 }
 
-impl ConsensusEngine for TwoPhaseEngine {
-    fn name(&self) -> &str {
-        "two-phase"
-    }
+#[cfg(feature = "two-phase-coordinator-policy")]
+impl TwoPhaseEngine {
+    /// Respond to a verification request for the proposal this node is evaluating or has already
+    /// decided. The request is either resent by the coordinator or sent by a verifier that has
+    /// taken over coordination of the proposal; in both cases, this node's vote is sent to the
+    /// requester if this node has checked the proposal, or the result is sent if this node knows
+    /// it.
+    fn handle_repeated_verification_request(
+        &mut self,
+        proposal_id: ProposalId,
+        requester_id: &PeerId,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        let vote = match &mut self.state {
+            State::EvaluatingProposal(tpc_proposal)
+                if tpc_proposal.proposal_id() == &proposal_id =>
+            {
+                if tpc_proposal.coordinator_id() != requester_id {
+                    let was_coordinator = tpc_proposal.coordinator_id() == &self.id;
+                    if !tpc_proposal.change_coordinator(requester_id) {
+                        warn!(
+                            "Ignoring verification request for proposal {} from {}, which is not \
+                             its coordinator",
+                            proposal_id, requester_id
+                        );
+                        return Ok(());
+                    }
 
-    fn version(&self) -> &str {
-        "0.1"
-    }
+                    info!(
+                        "{} has taken over coordination of proposal {}",
+                        requester_id, proposal_id
+                    );
+                    if was_coordinator {
+                        self.coordinator_timeout.stop();
+                    }
+                }
+                tpc_proposal.record_vote_sent(requester_id);
+                tpc_proposal.vote()
+            }
+            _ => {
+                return self.handle_proposal_result_request(
+                    proposal_id,
+                    requester_id,
+                    network_sender,
+                )
+            }
+        };
 
-    fn additional_protocols(&self) -> Vec<(String, String)> {
-        vec![]
+        if let Some(vote) = vote {
+            self.send_verification_response(requester_id, proposal_id, vote, network_sender)?;
+            self.failover_timeout.start();
+        }
+
+        Ok(())
     }
 
-    fn run(
-        &mut self,
-        consensus_messages: Receiver<ConsensusMessage>,
-        proposal_updates: Receiver<ProposalUpdate>,
-        network_sender: Box<dyn ConsensusNetworkSender>,
-        proposal_manager: Box<dyn ProposalManager>,
-        startup_state: StartupState,
+    fn send_verification_response(
+        &self,
+        coordinator_id: &PeerId,
+        proposal_id: ProposalId,
+        vote: TwoPhaseMessage_ProposalVerificationResponse,
+        network_sender: &dyn ConsensusNetworkSender,
     ) -> Result<(), ConsensusEngineError> {
-        let message_timeout = Duration::from_millis(MESSAGE_RECV_TIMEOUT_MILLIS);
-        let proposal_timeout = Duration::from_millis(PROPOSAL_RECV_TIMEOUT_MILLIS);
+        debug!(
+            "Sending {:?} response for proposal {} to {}",
+            vote, proposal_id, coordinator_id
+        );
 
-        self.id = startup_state.id;
+        let mut response = TwoPhaseMessage::new();
+        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+        response.set_proposal_id(proposal_id.into());
+        response.set_proposal_verification_response(vote);
 
-        for id in startup_state.peer_ids {
-            self.peers.insert(id);
-        }
+        network_sender.send_to(coordinator_id, response.write_to_bytes()?)?;
 
-        loop {
-            if let Err(err) = self.abort_proposal_if_timed_out(&*network_sender, &*proposal_manager)
-            {
-                error!("Failed to abort timed-out proposal: {}", err);
-            }
+        Ok(())
+    }
 
-            if let Err(err) = self.handle_backlogged_verification_request(&*proposal_manager) {
-                error!("Failed to handle backlogged verification request: {}", err);
-            }
+    /// If the coordinator of the proposal this node is evaluating, or of the oldest proposal in
+    /// this node's backlog, has not made progress within the coordinator timeout, fail over to
+    /// the next verifier.
+    fn fail_over_if_timed_out(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        if let State::EvaluatingProposal(_) = self.state {
+            self.backlog_timeout.stop();
 
-            if let Err(err) = self.get_next_proposal(&*network_sender, &*proposal_manager) {
-                error!("Failed to get next proposal: {}", err);
+            if self.failover_timeout.check_expired() {
+                self.failover_timeout.stop();
+                self.fail_over_evaluating_proposal(network_sender, proposal_manager)?;
             }
+        } else {
+            self.failover_timeout.stop();
+            self.fail_over_backlogged_proposal_if_timed_out();
+        }
 
-            // Get and handle a consensus message if there is one
-            match consensus_messages.recv_timeout(message_timeout) {
-                Ok(consensus_message) => {
-                    if let Err(err) = self.handle_consensus_msg(
-                        consensus_message,
-                        &*network_sender,
-                        &*proposal_manager,
-                    ) {
-                        error!("error while handling consensus message: {}", err);
+        Ok(())
+    }
+
+    /// Pass coordination of the proposal this node is evaluating to the next verifier, and send
+    /// this node's vote to it. If this node is the next verifier, it takes over coordination.
+    fn fail_over_evaluating_proposal(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        let (proposal_id, coordinator_id, vote) = match &mut self.state {
+            State::EvaluatingProposal(tpc_proposal) => {
+                let failed_id = tpc_proposal.coordinator_id().clone();
+                match tpc_proposal.fail_over() {
+                    Some(coordinator_id) => {
+                        warn!(
+                            "Coordinator {} of proposal {} timed out; failing over to {}",
+                            failed_id,
+                            tpc_proposal.proposal_id(),
+                            coordinator_id
+                        );
+                        if coordinator_id != self.id {
+                            tpc_proposal.record_vote_sent(&coordinator_id);
+                        }
+                        (
+                            tpc_proposal.proposal_id().clone(),
+                            coordinator_id,
+                            tpc_proposal.vote(),
+                        )
+                    }
+                    None => {
+                        warn!(
+                            "Coordinator {} of proposal {} timed out, and no verifier is left to \
+                             take over",
+                            failed_id,
+                            tpc_proposal.proposal_id()
+                        );
+                        return Ok(());
                     }
-                }
-                Err(RecvTimeoutError::Timeout) => {}
-                Err(RecvTimeoutError::Disconnected) => {
-                    info!("consensus message receiver disconnected");
-                    break;
                 }
             }
+            _ => return Ok(()),
+        };
 
-            // Get and handle a proposal update if there is one
-            match proposal_updates.recv_timeout(proposal_timeout) {
-                Ok(ProposalUpdate::Shutdown) => {
-                    info!("received shutdown");
-                    break;
-                }
-                Ok(update) => {
-                    if let Err(err) =
-                        self.handle_proposal_update(update, &*network_sender, &*proposal_manager)
-                    {
-                        error!("error while handling proposal update: {}", err);
-                    }
-                }
-                Err(RecvTimeoutError::Timeout) => {}
-                Err(RecvTimeoutError::Disconnected) => {
-                    info!("proposal update receiver disconnected");
-                    break;
+        if coordinator_id != self.id {
+            if let Some(vote) = vote {
+                self.send_verification_response(
+                    &coordinator_id,
+                    proposal_id,
+                    vote,
+                    network_sender,
+                )?;
+            }
+            self.failover_timeout.start();
+            return Ok(());
+        }
+
+        info!("Taking over coordination of proposal {}", proposal_id);
+        self.coordinator_timeout.start();
+
+        // A previous coordinator may have decided the proposal already, in which case its result
+        // is adopted when a peer sends it
+        self.request_decided_result(&proposal_id, network_sender)?;
+
+        match vote {
+            Some(TwoPhaseMessage_ProposalVerificationResponse::VERIFIED) => {
+                if let State::EvaluatingProposal(tpc_proposal) = &mut self.state {
+                    tpc_proposal.add_verified_peer(self.id.clone());
                 }
+
+                // The previous coordinators are still required to verify the proposal, so the
+                // proposal can't be approved yet
+                debug!("Requesting verification of proposal {}", proposal_id);
+
+                let mut request = TwoPhaseMessage::new();
+                request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
+                request.set_proposal_id(proposal_id.into());
+
+                network_sender.broadcast(request.write_to_bytes()?)?;
+            }
+            // No coordinator can have applied the proposal without this node's approval
+            Some(_) => {
+                self.complete_coordination(
+                    proposal_id,
+                    TwoPhaseMessage_ProposalResult::REJECT,
+                    network_sender,
+                    proposal_manager,
+                )?;
             }
+            // Verification is requested once this node has checked the proposal
+            None => (),
         }
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-pub mod tests {
-    use super::*;
+    /// Whether this node is the coordinator of the given proposal, which it is evaluating.
+    fn coordinating_proposal(&self, proposal_id: &ProposalId) -> bool {
+        match self.state {
+            State::EvaluatingProposal(ref tpc_proposal) => {
+                tpc_proposal.proposal_id() == proposal_id
+                    && tpc_proposal.coordinator_id() == &self.id
+            }
+            _ => false,
+        }
+    }
 
-    use std::sync::mpsc::channel;
+    /// Ask the peers for the result of a proposal that this node has taken over coordination of.
+    fn request_decided_result(
+        &self,
+        proposal_id: &ProposalId,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        debug!("Requesting result of proposal {}", proposal_id);
 
-    use protobuf::RepeatedField;
+        let mut request = TwoPhaseMessage::new();
+        request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT_REQUEST);
+        request.set_proposal_id(proposal_id.clone().into());
 
-    use crate::consensus::tests::{MockConsensusNetworkSender, MockProposalManager};
-    use crate::consensus::Proposal;
+        network_sender.broadcast(request.write_to_bytes()?)?;
 
-    const COORDINATOR_TIMEOUT_MILLIS: u64 = 5000;
+        Ok(())
+    }
 
-    /// Verify that the engine properly shuts down when it receives the Shutdown update.
-    #[test]
-    fn test_shutdown() {
-        let (update_tx, update_rx) = channel();
-        let (_, consensus_msg_rx) = channel();
+    /// Adopt the result that a previous coordinator decided for a proposal this node has taken
+    /// over coordination of, and send it to the peers that have not learned it.
+    fn adopt_proposal_result(
+        &mut self,
+        proposal_id: ProposalId,
+        proposal_result: TwoPhaseMessage_ProposalResult,
+        sender_id: &PeerId,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        if proposal_result == TwoPhaseMessage_ProposalResult::UNSET_RESULT {
+            warn!(
+                "Ignoring improperly specified proposal result from {}",
+                sender_id
+            );
+            return Ok(());
+        }
 
-        let manager = MockProposalManager::new(update_tx.clone());
-        let network = MockConsensusNetworkSender::new();
-        let startup_state = StartupState {
-            id: vec![0].into(),
+        info!(
+            "Adopting {:?} result of proposal {} from {}",
+            proposal_result, proposal_id, sender_id
+        );
+        self.complete_coordination(
+            proposal_id,
+            proposal_result,
+            network_sender,
+            proposal_manager,
+        )
+    }
+
+    /// If this node has had proposals in its backlog for longer than the coordinator timeout
+    /// without evaluating any proposal, pass coordination of the oldest backlogged proposal to
+    /// the next verifier.
+    fn fail_over_backlogged_proposal_if_timed_out(&mut self) {
+        if self.proposal_backlog.is_empty() {
+            self.backlog_timeout.stop();
+        } else if !self.backlog_timeout.is_started() {
+            self.backlog_timeout.start();
+        } else if self.backlog_timeout.check_expired() {
+            if let Some(tpc_proposal) = self.proposal_backlog.front_mut() {
+                let failed_id = tpc_proposal.coordinator_id().clone();
+                if let Some(coordinator_id) = tpc_proposal.fail_over() {
+                    warn!(
+                        "Coordinator {} did not start proposal {}; failing over to {}",
+                        failed_id,
+                        tpc_proposal.proposal_id(),
+                        coordinator_id
+                    );
+                }
+            }
+            self.backlog_timeout.start();
+        }
+    }
+}
+
+/// The progress of a proposal that was not completed when the engine last stopped
+#[cfg(feature = "two-phase-log")]
+struct RecoveredProposal {
+    tpc_proposal: TwoPhaseProposal,
+    verified: Option<bool>,
+    result: Option<TwoPhaseMessage_ProposalResult>,
+    applied: bool,
+}
+
+#[cfg(feature = "two-phase-log")]
+impl TwoPhaseEngine {
+    fn append_to_log(&mut self, entry: TwoPhaseLogEntry) -> Result<(), ConsensusEngineError> {
+        if let Some(log) = self.log.as_mut() {
+            log.append(&entry)?;
+            self.log_entries += 1;
+        }
+
+        Ok(())
+    }
+
+    fn record_proposal_started(
+        &mut self,
+        tpc_proposal: &TwoPhaseProposal,
+    ) -> Result<(), ConsensusEngineError> {
+        self.append_to_log(TwoPhaseLogEntry::ProposalStarted {
+            proposal_id: tpc_proposal.proposal_id().clone(),
+            coordinator_id: tpc_proposal.coordinator_id().clone(),
+            required_verifiers: tpc_proposal.required_verifiers().iter().cloned().collect(),
+        })
+    }
+
+    fn record_proposal_vote(
+        &mut self,
+        proposal_id: &ProposalId,
+        verified: bool,
+    ) -> Result<(), ConsensusEngineError> {
+        self.append_to_log(TwoPhaseLogEntry::ProposalVoted {
+            proposal_id: proposal_id.clone(),
+            verified,
+        })
+    }
+
+    fn record_proposal_decision(
+        &mut self,
+        proposal_id: &ProposalId,
+        result: TwoPhaseMessage_ProposalResult,
+    ) -> Result<(), ConsensusEngineError> {
+        self.append_to_log(TwoPhaseLogEntry::ProposalDecided {
+            proposal_id: proposal_id.clone(),
+            apply: result == TwoPhaseMessage_ProposalResult::APPLY,
+        })?;
+        self.remember_decision(proposal_id.clone(), result);
+
+        Ok(())
+    }
+
+    fn record_proposal_applied(
+        &mut self,
+        proposal_id: &ProposalId,
+    ) -> Result<(), ConsensusEngineError> {
+        self.append_to_log(TwoPhaseLogEntry::ProposalApplied {
+            proposal_id: proposal_id.clone(),
+        })
+    }
+
+    fn record_proposal_completion(
+        &mut self,
+        proposal_id: &ProposalId,
+    ) -> Result<(), ConsensusEngineError> {
+        self.result_request_timeout.stop();
+        self.append_to_log(TwoPhaseLogEntry::ProposalCompleted {
+            proposal_id: proposal_id.clone(),
+        })
+    }
+
+    /// Whether this node knows the result of the given proposal.
+    fn proposal_decided(&self, proposal_id: &ProposalId) -> bool {
+        self.decided_proposals
+            .iter()
+            .any(|(decided_id, _)| decided_id == proposal_id)
+    }
+
+    fn remember_decision(
+        &mut self,
+        proposal_id: ProposalId,
+        result: TwoPhaseMessage_ProposalResult,
+    ) {
+        self.decided_proposals.push_back((proposal_id, result));
+        if self.decided_proposals.len() > DECIDED_PROPOSALS_SIZE {
+            self.decided_proposals.pop_front();
+        }
+    }
+
+    /// Apply a proposal whose result was decided. A proposal that was in progress before the
+    /// engine restarted is re-applied, since the changes the proposal manager prepared for it were
+    /// lost.
+    fn apply_decided_proposal(
+        &mut self,
+        proposal_id: &ProposalId,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        if self.recovered_proposal.as_ref() == Some(proposal_id) {
+            self.recovered_proposal = None;
+            proposal_manager.reapply_proposal(proposal_id)?;
+        } else {
+            proposal_manager.accept_proposal(proposal_id, None)?;
+        }
+
+        self.record_proposal_applied(proposal_id)
+    }
+
+    /// The proposal manager may not know about a proposal that was in progress before the engine
+    /// restarted, so it may fail to reject it; since the result has already been decided, the
+    /// failure is logged rather than returned.
+    fn check_recovered_proposal_result(
+        &mut self,
+        proposal_id: &ProposalId,
+        result: Result<(), ProposalManagerError>,
+    ) -> Result<(), ProposalManagerError> {
+        if self.recovered_proposal.as_ref() != Some(proposal_id) {
+            return result;
+        }
+
+        self.recovered_proposal = None;
+        if let Err(err) = result {
+            warn!(
+                "Proposal manager was unable to complete proposal {}, which was in progress \
+                 before restart: {}",
+                proposal_id, err
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Replay the log to finish any proposal that was in progress when the engine last stopped.
+    fn recover_from_log(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+        proposal_manager: &dyn ProposalManager,
+    ) -> Result<(), ConsensusEngineError> {
+        let entries = match self.log.as_ref() {
+            Some(log) => log.entries()?,
+            None => return Ok(()),
+        };
+        self.log_entries = entries.len();
+
+        let mut incomplete_proposals: Vec<RecoveredProposal> = vec![];
+        for entry in entries {
+            match entry {
+                TwoPhaseLogEntry::ProposalStarted {
+                    proposal_id,
+                    coordinator_id,
+                    required_verifiers,
+                } => incomplete_proposals.push(RecoveredProposal {
+                    tpc_proposal: TwoPhaseProposal::new(
+                        proposal_id,
+                        coordinator_id,
+                        required_verifiers.into_iter().collect(),
+                    ),
+                    verified: None,
+                    result: None,
+                    applied: false,
+                }),
+                TwoPhaseLogEntry::ProposalVoted {
+                    proposal_id,
+                    verified,
+                } => {
+                    if let Some(recovered) = incomplete_proposals
+                        .iter_mut()
+                        .find(|recovered| recovered.tpc_proposal.proposal_id() == &proposal_id)
+                    {
+                        recovered.verified = Some(verified);
+                    }
+                }
+                TwoPhaseLogEntry::ProposalDecided { proposal_id, apply } => {
+                    let result = if apply {
+                        TwoPhaseMessage_ProposalResult::APPLY
+                    } else {
+                        TwoPhaseMessage_ProposalResult::REJECT
+                    };
+                    if let Some(recovered) = incomplete_proposals
+                        .iter_mut()
+                        .find(|recovered| recovered.tpc_proposal.proposal_id() == &proposal_id)
+                    {
+                        recovered.result = Some(result);
+                    }
+                    self.remember_decision(proposal_id, result);
+                }
+                TwoPhaseLogEntry::ProposalApplied { proposal_id } => {
+                    if let Some(recovered) = incomplete_proposals
+                        .iter_mut()
+                        .find(|recovered| recovered.tpc_proposal.proposal_id() == &proposal_id)
+                    {
+                        recovered.applied = true;
+                    }
+                }
+                TwoPhaseLogEntry::ProposalCompleted { proposal_id } => incomplete_proposals
+                    .retain(|recovered| recovered.tpc_proposal.proposal_id() != &proposal_id),
+            }
+        }
+
+        for recovered in incomplete_proposals {
+            let proposal_id = recovered.tpc_proposal.proposal_id().clone();
+            let is_coordinator = recovered.tpc_proposal.coordinator_id() == &self.id;
+
+            // A proposal that was approved must be applied before it is completed; if the proposal
+            // manager is unable to apply it, the engine stops rather than continue out of sync
+            // with its peers, and the proposal is recovered again on the next restart
+            if recovered.result == Some(TwoPhaseMessage_ProposalResult::APPLY) && !recovered.applied
+            {
+                info!(
+                    "Applying proposal {}, which was approved before restart",
+                    proposal_id
+                );
+                proposal_manager.reapply_proposal(&proposal_id)?;
+                self.record_proposal_applied(&proposal_id)?;
+            }
+
+            match (is_coordinator, recovered.result, recovered.verified) {
+                (true, Some(result), _) => {
+                    // The result may not have been sent to peers before the engine stopped
+                    info!(
+                        "Resending result of proposal {}, which was decided before restart",
+                        proposal_id
+                    );
+                    self.broadcast_proposal_result(&proposal_id, result, network_sender);
+                    self.record_proposal_completion(&proposal_id)?;
+                }
+                (true, None, _) => {
+                    // No result was sent, so no peer can have applied the proposal
+                    info!(
+                        "Rejecting proposal {}, which was in progress before restart",
+                        proposal_id
+                    );
+                    self.record_proposal_decision(
+                        &proposal_id,
+                        TwoPhaseMessage_ProposalResult::REJECT,
+                    )?;
+                    if let Err(err) = proposal_manager.reject_proposal(&proposal_id) {
+                        debug!(
+                            "Proposal manager was unable to reject proposal {}: {}",
+                            proposal_id, err
+                        );
+                    }
+                    self.broadcast_proposal_result(
+                        &proposal_id,
+                        TwoPhaseMessage_ProposalResult::REJECT,
+                        network_sender,
+                    );
+                    self.record_proposal_completion(&proposal_id)?;
+                }
+                (false, None, Some(true)) if self.recovered_proposal.is_none() => {
+                    // The coordinator may have applied the proposal, so this node must wait for
+                    // the result
+                    warn!(
+                        "Result of proposal {} is unknown; requesting it from peers",
+                        proposal_id
+                    );
+                    self.recovered_proposal = Some(proposal_id);
+                    self.state = State::EvaluatingProposal(recovered.tpc_proposal);
+                    if let Err(err) = self.request_proposal_result(network_sender) {
+                        warn!("Unable to request proposal result: {}", err);
+                    }
+                }
+                _ => {
+                    // This node already knew and acted on the result, or did not verify the
+                    // proposal, so the proposal cannot still be waiting on this node
+                    debug!(
+                        "Completing proposal {}, which was in progress before restart",
+                        proposal_id
+                    );
+                    self.record_proposal_completion(&proposal_id)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn broadcast_proposal_result(
+        &self,
+        proposal_id: &ProposalId,
+        proposal_result: TwoPhaseMessage_ProposalResult,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) {
+        let mut result = TwoPhaseMessage::new();
+        result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
+        result.set_proposal_id(proposal_id.clone().into());
+        result.set_proposal_result(proposal_result);
+
+        // Peers that don't receive the result will request it
+        if let Err(err) = result
+            .write_to_bytes()
+            .map_err(ConsensusEngineError::from)
+            .and_then(|bytes| Ok(network_sender.broadcast(bytes)?))
+        {
+            warn!("Unable to send result of proposal {}: {}", proposal_id, err);
+        }
+    }
+
+    /// Ask peers for the result of the proposal this node is waiting on, if any.
+    fn request_proposal_result(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        if let State::EvaluatingProposal(ref tpc_proposal) = self.state {
+            if tpc_proposal.coordinator_id() != &self.id {
+                debug!(
+                    "Requesting result of proposal {}",
+                    tpc_proposal.proposal_id()
+                );
+
+                let mut request = TwoPhaseMessage::new();
+                request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT_REQUEST);
+                request.set_proposal_id(tpc_proposal.proposal_id().clone().into());
+
+                self.result_request_timeout.start();
+                network_sender.broadcast(request.write_to_bytes()?)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// If this node has waited too long for the result of a proposal it verified, ask its peers
+    /// for the result.
+    fn request_result_if_timed_out(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        if self.result_request_timeout.check_expired() {
+            self.result_request_timeout.stop();
+            self.request_proposal_result(network_sender)?;
+        }
+
+        Ok(())
+    }
+
+    /// Send the result of the given proposal to a peer that asked for it, if this node knows it.
+    fn handle_proposal_result_request(
+        &self,
+        proposal_id: ProposalId,
+        requester_id: &PeerId,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        match self
+            .decided_proposals
+            .iter()
+            .find(|(decided_id, _)| decided_id == &proposal_id)
+        {
+            Some((_, proposal_result)) => {
+                debug!(
+                    "Sending result of proposal {} to {}",
+                    proposal_id, requester_id
+                );
+
+                let mut result = TwoPhaseMessage::new();
+                result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
+                result.set_proposal_id(proposal_id.into());
+                result.set_proposal_result(*proposal_result);
+
+                network_sender.send_to(requester_id, result.write_to_bytes()?)?;
+            }
+            None => debug!(
+                "Result of proposal {} is not known; ignoring request from {}",
+                proposal_id, requester_id
+            ),
+        }
+
+        Ok(())
+    }
+
+    /// Discard the log entries of completed proposals, except for the results of recently decided
+    /// proposals, once the log has grown large enough. The log is only compacted when no proposal
+    /// is being evaluated, since the entries of that proposal would otherwise be lost.
+    fn compact_log_if_needed(&mut self) -> Result<(), ConsensusEngineError> {
+        if self.log_entries < LOG_COMPACTION_THRESHOLD {
+            return Ok(());
+        }
+        if let State::EvaluatingProposal(_) = self.state {
+            return Ok(());
+        }
+
+        let entries = self
+            .decided_proposals
+            .iter()
+            .flat_map(|(proposal_id, result)| {
+                vec![
+                    TwoPhaseLogEntry::ProposalDecided {
+                        proposal_id: proposal_id.clone(),
+                        apply: *result == TwoPhaseMessage_ProposalResult::APPLY,
+                    },
+                    TwoPhaseLogEntry::ProposalCompleted {
+                        proposal_id: proposal_id.clone(),
+                    },
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(log) = self.log.as_mut() {
+            log.compact(&entries)?;
+            self.log_entries = entries.len();
+        }
+
+        Ok(())
+    }
+}
+
+impl ConsensusEngine for TwoPhaseEngine {
+    fn name(&self) -> &str {
+        "two-phase"
+    }
+
+    fn version(&self) -> &str {
+        "0.1"
+    }
+
+    fn additional_protocols(&self) -> Vec<(String, String)> {
+        vec![]
+    }
+
+    fn run(
+        &mut self,
+        consensus_messages: Receiver<ConsensusMessage>,
+        proposal_updates: Receiver<ProposalUpdate>,
+        network_sender: Box<dyn ConsensusNetworkSender>,
+        proposal_manager: Box<dyn ProposalManager>,
+        startup_state: StartupState,
+    ) -> Result<(), ConsensusEngineError> {
+        let message_timeout = Duration::from_millis(MESSAGE_RECV_TIMEOUT_MILLIS);
+        let proposal_timeout = Duration::from_millis(PROPOSAL_RECV_TIMEOUT_MILLIS);
+
+        self.id = startup_state.id;
+
+        for id in startup_state.peer_ids {
+            self.peers.insert(id);
+        }
+
+        #[cfg(feature = "two-phase-log")]
+        self.recover_from_log(&*network_sender, &*proposal_manager)?;
+
+        loop {
+            if let Err(err) = self.abort_proposal_if_timed_out(&*network_sender, &*proposal_manager)
+            {
+                error!("Failed to abort timed-out proposal: {}", err);
+            }
+
+            #[cfg(feature = "two-phase-coordinator-policy")]
+            {
+                if let Err(err) = self.fail_over_if_timed_out(&*network_sender, &*proposal_manager)
+                {
+                    error!("Failed to fail over timed-out proposal: {}", err);
+                }
+            }
+
+            #[cfg(feature = "two-phase-log")]
+            {
+                if let Err(err) = self.request_result_if_timed_out(&*network_sender) {
+                    error!("Failed to request proposal result: {}", err);
+                }
+
+                if let Err(err) = self.compact_log_if_needed() {
+                    error!("Failed to compact two-phase log: {}", err);
+                }
+            }
+
+            if let Err(err) = self.handle_backlogged_verification_request(&*proposal_manager) {
+                error!("Failed to handle backlogged verification request: {}", err);
+            }
+
+            if let Err(err) = self.get_next_proposal(&*network_sender, &*proposal_manager) {
+                error!("Failed to get next proposal: {}", err);
+            }
+
+            // Get and handle a consensus message if there is one
+            match consensus_messages.recv_timeout(message_timeout) {
+                Ok(consensus_message) => {
+                    if let Err(err) = self.handle_consensus_msg(
+                        consensus_message,
+                        &*network_sender,
+                        &*proposal_manager,
+                    ) {
+                        error!("error while handling consensus message: {}", err);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    info!("consensus message receiver disconnected");
+                    break;
+                }
+            }
+
+            // Get and handle a proposal update if there is one
+            match proposal_updates.recv_timeout(proposal_timeout) {
+                Ok(ProposalUpdate::Shutdown) => {
+                    info!("received shutdown");
+                    break;
+                }
+                Ok(update) => {
+                    if let Err(err) =
+                        self.handle_proposal_update(update, &*network_sender, &*proposal_manager)
+                    {
+                        error!("error while handling proposal update: {}", err);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    info!("proposal update receiver disconnected");
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    use std::sync::mpsc::channel;
+
+    use protobuf::RepeatedField;
+
+    use crate::consensus::tests::{MockConsensusNetworkSender, MockProposalManager};
+    use crate::consensus::Proposal;
+
+    #[cfg(feature = "two-phase-log")]
+    use super::log::MemoryTwoPhaseLog;
+
+    const COORDINATOR_TIMEOUT_MILLIS: u64 = 5000;
+
+    /// Verify that the engine properly shuts down when it receives the Shutdown update.
+    #[test]
+    fn test_shutdown() {
+        let (update_tx, update_rx) = channel();
+        let (_, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
             peer_ids: vec![vec![1].into()],
             last_proposal: None,
         };
 
-        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS));
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS));
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network),
+                    Box::new(manager),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Test the coordinator (leader) of a 3 node network by simulating the flow of a valid
+    /// proposal (both participants verify the proposal) and a failed proposal (one participant
+    /// fails the proposal). This test uses default peers for which nodes need to verify the
+    /// proposal. The peers are defined in the startup state.
+    #[test]
+    fn test_coordinator_default_peers() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into(), vec![2].into()],
+            last_proposal: None,
+        };
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Check that verification request is sent for the first proposal
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(0) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        // Receive the verification responses
+        let mut response = TwoPhaseMessage::new();
+        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+        response.set_proposal_id(vec![1]);
+        response.set_proposal_verification_response(
+            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
+        );
+        let message_bytes = response
+            .write_to_bytes()
+            .expect("failed to write failed response to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes.clone(), vec![1].into()))
+            .expect("failed to send 1st response");
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![2].into()))
+            .expect("failed to send 2nd response");
+
+        // Verify the Apply message is sent for the proposal
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(1) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT
+                );
+                assert_eq!(
+                    msg.get_proposal_result(),
+                    TwoPhaseMessage_ProposalResult::APPLY
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        // Verify the proposal was accepted
+        loop {
+            if let Some((id, _)) = manager.accepted_proposals().get(0) {
+                assert_eq!(id, &vec![1].into());
+                break;
+            }
+        }
+
+        // Check that verification request is sent for the second proposal
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(2) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
+                );
+                assert_eq!(msg.get_proposal_id(), vec![2].as_slice());
+                break;
+            }
+        }
+
+        // Receive the verification responses
+        let mut response = TwoPhaseMessage::new();
+        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+        response.set_proposal_id(vec![2]);
+        response.set_proposal_verification_response(
+            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
+        );
+        let message_bytes = response
+            .write_to_bytes()
+            .expect("failed to write failed response to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![1].into()))
+            .expect("failed to send 1st response");
+
+        let mut response = TwoPhaseMessage::new();
+        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+        response.set_proposal_id(vec![2]);
+        response.set_proposal_verification_response(
+            TwoPhaseMessage_ProposalVerificationResponse::FAILED,
+        );
+        let message_bytes = response
+            .write_to_bytes()
+            .expect("failed to write failed response to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![2].into()))
+            .expect("failed to send 2nd response");
+
+        // Verify the Reject message is sent for the proposal
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(3) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT
+                );
+                assert_eq!(
+                    msg.get_proposal_result(),
+                    TwoPhaseMessage_ProposalResult::REJECT
+                );
+                assert_eq!(msg.get_proposal_id(), vec![2].as_slice());
+                break;
+            }
+        }
+
+        // Verify the proposal was rejected
+        loop {
+            if let Some(id) = manager.rejected_proposals().get(0) {
+                assert_eq!(id, &vec![2].into());
+                break;
+            }
+        }
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Test the coordinator (leader) of a 3 node network by simulating the flow of a valid
+    /// proposal (both participants verify the proposal) and a failed proposal (one participant
+    /// fails the proposal). This test uses dynamic peers for which nodes need to verify the
+    /// proposal. The peers are defined in the consensus data on the proposal.
+    #[test]
+    fn test_coordinator_dynamic_peers() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let mut manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+
+        let mut required_verifiers = RequiredVerifiers::new();
+        required_verifiers.set_verifiers(RepeatedField::from_vec(vec![
+            vec![0].into(),
+            vec![1].into(),
+            vec![2].into(),
+        ]));
+        let data = required_verifiers.write_to_bytes().unwrap();
+        manager.set_consensus_data(Some(data));
+
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![],
+            last_proposal: None,
+        };
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Check that verification request is sent for the first proposal
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(0) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        // Receive the verification responses
+        let mut response = TwoPhaseMessage::new();
+        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+        response.set_proposal_id(vec![1]);
+        response.set_proposal_verification_response(
+            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
+        );
+        let message_bytes = response
+            .write_to_bytes()
+            .expect("failed to write failed response to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes.clone(), vec![1].into()))
+            .expect("failed to send 1st response");
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![2].into()))
+            .expect("failed to send 2nd response");
+
+        // Verify the Apply message is sent for the proposal
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(1) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT
+                );
+                assert_eq!(
+                    msg.get_proposal_result(),
+                    TwoPhaseMessage_ProposalResult::APPLY
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        // Verify the proposal was accepted
+        loop {
+            if let Some((id, _)) = manager.accepted_proposals().get(0) {
+                assert_eq!(id, &vec![1].into());
+                break;
+            }
+        }
+
+        // Check that verification request is sent for the second proposal
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(2) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
+                );
+                assert_eq!(msg.get_proposal_id(), vec![2].as_slice());
+                break;
+            }
+        }
+
+        // Receive the verification responses
+        let mut response = TwoPhaseMessage::new();
+        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+        response.set_proposal_id(vec![2]);
+        response.set_proposal_verification_response(
+            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
+        );
+        let message_bytes = response
+            .write_to_bytes()
+            .expect("failed to write failed response to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![1].into()))
+            .expect("failed to send 1st response");
+
+        let mut response = TwoPhaseMessage::new();
+        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+        response.set_proposal_id(vec![2]);
+        response.set_proposal_verification_response(
+            TwoPhaseMessage_ProposalVerificationResponse::FAILED,
+        );
+        let message_bytes = response
+            .write_to_bytes()
+            .expect("failed to write failed response to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![2].into()))
+            .expect("failed to send 2nd response");
+
+        // Verify the Reject message is sent for the proposal
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(3) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT
+                );
+                assert_eq!(
+                    msg.get_proposal_result(),
+                    TwoPhaseMessage_ProposalResult::REJECT
+                );
+                assert_eq!(msg.get_proposal_id(), vec![2].as_slice());
+                break;
+            }
+        }
+
+        // Verify the proposal was rejected
+        loop {
+            if let Some(id) = manager.rejected_proposals().get(0) {
+                assert_eq!(id, &vec![2].into());
+                break;
+            }
+        }
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Test a participant (follower) by simulating the flow of a valid and a failed proposal.
+    #[test]
+    fn test_participant() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![1].into(),
+            peer_ids: vec![vec![0].into()],
+            last_proposal: None,
+        };
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Receive the first proposal
+        let mut proposal = Proposal::default();
+        proposal.id = vec![1].into();
+        update_tx
+            .send(ProposalUpdate::ProposalReceived(proposal, vec![0].into()))
+            .expect("failed to send 1st proposal");
+
+        // Receive the first verification request
+        let mut request = TwoPhaseMessage::new();
+        request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
+        request.set_proposal_id(vec![1]);
+        let message_bytes = request
+            .write_to_bytes()
+            .expect("failed to write request to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![0].into()))
+            .expect("failed to send 1st verification request");
+
+        // Check that the Verified verification response is sent
+        loop {
+            if let Some((msg, peer_id)) = network.sent_messages().get(0) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(peer_id, &vec![0].into());
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE
+                );
+                assert_eq!(
+                    msg.get_proposal_verification_response(),
+                    TwoPhaseMessage_ProposalVerificationResponse::VERIFIED
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        // Receive the Apply result
+        let mut result = TwoPhaseMessage::new();
+        result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
+        result.set_proposal_id(vec![1]);
+        result.set_proposal_result(TwoPhaseMessage_ProposalResult::APPLY);
+        let message_bytes = result
+            .write_to_bytes()
+            .expect("failed to write apply result to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![0].into()))
+            .expect("failed to send apply result");
+
+        // Verify the proposal was accepted
+        loop {
+            if let Some((id, _)) = manager.accepted_proposals().get(0) {
+                assert_eq!(id, &vec![1].into());
+                break;
+            }
+        }
+
+        // Receive the second proposal
+        let mut proposal = Proposal::default();
+        proposal.id = vec![2].into();
+        update_tx
+            .send(ProposalUpdate::ProposalReceived(proposal, vec![0].into()))
+            .expect("failed to send 2nd proposal");
+
+        // Receive the second verification request (the manager will say this proposal is invalid)
+        manager.set_next_proposal_valid(false);
+
+        let mut request = TwoPhaseMessage::new();
+        request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
+        request.set_proposal_id(vec![2]);
+        let message_bytes = request
+            .write_to_bytes()
+            .expect("failed to write request to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![0].into()))
+            .expect("failed to send 2nd verification request");
+
+        // Check that the Failed verification response is sent
+        loop {
+            if let Some((msg, peer_id)) = network.sent_messages().get(1) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(peer_id, &vec![0].into());
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE
+                );
+                assert_eq!(
+                    msg.get_proposal_verification_response(),
+                    TwoPhaseMessage_ProposalVerificationResponse::FAILED
+                );
+                assert_eq!(msg.get_proposal_id(), vec![2].as_slice());
+                break;
+            }
+        }
+
+        // Receive the Reject result
+        let mut result = TwoPhaseMessage::new();
+        result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
+        result.set_proposal_id(vec![2]);
+        result.set_proposal_result(TwoPhaseMessage_ProposalResult::REJECT);
+        let message_bytes = result
+            .write_to_bytes()
+            .expect("failed to write reject result to bytes");
+
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![0].into()))
+            .expect("failed to send reject result");
+
+        // Verify the proposal was rejected
+        loop {
+            if let Some(id) = manager.rejected_proposals().get(0) {
+                assert_eq!(id, &vec![2].into());
+                break;
+            }
+        }
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Test that the coordinator will abort a commit if the coordinator timeout expires while
+    /// evaluating the commit.
+    #[test]
+    fn test_coordinator_timeout() {
+        let (update_tx, update_rx) = channel();
+        let (_consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into(), vec![2].into()],
+            last_proposal: None,
+        };
+
+        // Start engine with a very short coordinator timeout
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(10));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Check that a proposal verification request is sent
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(0) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        // Verify the Reject message is sent for the proposal (due to the timeout)
+        loop {
+            if let Some(msg) = network.broadcast_messages().get(1) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT
+                );
+                assert_eq!(
+                    msg.get_proposal_result(),
+                    TwoPhaseMessage_ProposalResult::REJECT
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        // Verify the proposal was rejected
+        loop {
+            if let Some(id) = manager.rejected_proposals().get(0) {
+                assert_eq!(id, &vec![1].into());
+                break;
+            }
+        }
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Test that a participant takes over coordination of a proposal when the coordinator times
+    /// out, and still requires the failed coordinator to verify the proposal.
+    ///
+    /// 1. Start a participant with a short coordinator timeout, and send it a proposal and a
+    ///    verification request from the coordinator.
+    /// 2. Verify that the participant sends its vote to the coordinator.
+    /// 3. Verify that, once the coordinator times out, the participant (which is next in the
+    ///    coordinator order) asks its peers for the result of the proposal and requests
+    ///    verification of the proposal.
+    /// 4. Send a verified response from the other participant, and verify that the proposal is
+    ///    neither accepted nor rejected, even after the new coordinator times out, since the
+    ///    original coordinator may have applied it.
+    /// 5. Send a verified response from the original coordinator, which hands over coordination,
+    ///    and verify that the proposal is accepted and the apply result is broadcast.
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    #[test]
+    fn test_coordinator_failover() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![1].into(),
+            peer_ids: vec![vec![0].into(), vec![2].into()],
+            last_proposal: None,
+        };
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(200));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        let mut proposal = Proposal::default();
+        proposal.id = vec![1].into();
+        update_tx
+            .send(ProposalUpdate::ProposalReceived(proposal, vec![0].into()))
+            .expect("failed to send proposal");
+
+        let mut request = TwoPhaseMessage::new();
+        request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
+        request.set_proposal_id(vec![1]);
+        consensus_msg_tx
+            .send(ConsensusMessage::new(
+                request
+                    .write_to_bytes()
+                    .expect("failed to write request to bytes"),
+                vec![0].into(),
+            ))
+            .expect("failed to send verification request");
+
+        // Check that the vote is sent to the original coordinator
+        loop {
+            if let Some((msg, peer_id)) = network.sent_messages().get(0) {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(peer_id, &vec![0].into());
+                assert_eq!(
+                    msg.get_proposal_verification_response(),
+                    TwoPhaseMessage_ProposalVerificationResponse::VERIFIED
+                );
+                break;
+            }
+        }
+
+        // Check that this node takes over, asks for the result and requests verification once
+        // the coordinator times out
+        loop {
+            let broadcast_messages = network.broadcast_messages();
+            if broadcast_messages.len() >= 2 {
+                let message_types = broadcast_messages[..2]
+                    .iter()
+                    .map(|msg| {
+                        Message::parse_from_bytes(msg)
+                            .map(|msg: TwoPhaseMessage| msg.get_message_type())
+                            .expect("failed to parse message")
+                    })
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    message_types,
+                    vec![
+                        TwoPhaseMessage_Type::PROPOSAL_RESULT_REQUEST,
+                        TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
+                    ]
+                );
+                break;
+            }
+        }
+
+        let send_response = |origin_id: u8| {
+            let mut response = TwoPhaseMessage::new();
+            response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+            response.set_proposal_id(vec![1]);
+            response.set_proposal_verification_response(
+                TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
+            );
+            consensus_msg_tx
+                .send(ConsensusMessage::new(
+                    response
+                        .write_to_bytes()
+                        .expect("failed to write response to bytes"),
+                    vec![origin_id].into(),
+                ))
+                .expect("failed to send verification response");
+        };
+
+        // Verify that the proposal is not decided without the original coordinator's vote, even
+        // once the new coordinator times out
+        send_response(2);
+        std::thread::sleep(Duration::from_millis(500));
+        assert!(manager.accepted_proposals().is_empty());
+        assert!(manager.rejected_proposals().is_empty());
+
+        // Verify that the proposal is applied once the original coordinator hands over
+        send_response(0);
+        loop {
+            let applied = network.broadcast_messages().iter().any(|msg| {
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(msg).expect("failed to parse message");
+                msg.get_message_type() == TwoPhaseMessage_Type::PROPOSAL_RESULT
+                    && msg.get_proposal_result() == TwoPhaseMessage_ProposalResult::APPLY
+            });
+            if applied {
+                break;
+            }
+        }
+        assert_eq!(
+            manager.accepted_proposals().get(0).map(|(id, _)| id),
+            Some(&vec![1].into())
+        );
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Test that a participant that takes over coordination of a proposal adopts the result that
+    /// a peer sends for it, rather than deciding the proposal itself.
+    ///
+    /// 1. Start a participant with a short coordinator timeout, send it a proposal and a
+    ///    verification request from the coordinator, and wait for it to take over.
+    /// 2. Send the apply result from the other participant, which learned the result from the
+    ///    original coordinator.
+    /// 3. Verify that the proposal is accepted and the apply result is broadcast.
+    #[cfg(feature = "two-phase-coordinator-policy")]
+    #[test]
+    fn test_new_coordinator_adopts_decided_result() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![1].into(),
+            peer_ids: vec![vec![0].into(), vec![2].into()],
+            last_proposal: None,
+        };
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(200));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
         let thread = std::thread::spawn(move || {
             engine
                 .run(
                     consensus_msg_rx,
                     update_rx,
-                    Box::new(network),
-                    Box::new(manager),
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
                     startup_state,
                 )
                 .expect("engine failed")
         });
 
+        let mut proposal = Proposal::default();
+        proposal.id = vec![1].into();
+        update_tx
+            .send(ProposalUpdate::ProposalReceived(proposal, vec![0].into()))
+            .expect("failed to send proposal");
+
+        let mut request = TwoPhaseMessage::new();
+        request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
+        request.set_proposal_id(vec![1]);
+        consensus_msg_tx
+            .send(ConsensusMessage::new(
+                request
+                    .write_to_bytes()
+                    .expect("failed to write request to bytes"),
+                vec![0].into(),
+            ))
+            .expect("failed to send verification request");
+
+        // Wait for this node to take over and ask for the result
+        loop {
+            if network.broadcast_messages().len() >= 2 {
+                break;
+            }
+        }
+
+        let mut result = TwoPhaseMessage::new();
+        result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
+        result.set_proposal_id(vec![1]);
+        result.set_proposal_result(TwoPhaseMessage_ProposalResult::APPLY);
+        consensus_msg_tx
+            .send(ConsensusMessage::new(
+                result
+                    .write_to_bytes()
+                    .expect("failed to write result to bytes"),
+                vec![2].into(),
+            ))
+            .expect("failed to send result");
+
+        loop {
+            if let Some((id, _)) = manager.accepted_proposals().get(0) {
+                assert_eq!(id, &vec![1].into());
+                break;
+            }
+        }
+        let msg: TwoPhaseMessage = Message::parse_from_bytes(
+            network
+                .broadcast_messages()
+                .last()
+                .expect("no messages broadcast"),
+        )
+        .expect("failed to parse message");
+        assert_eq!(
+            msg.get_message_type(),
+            TwoPhaseMessage_Type::PROPOSAL_RESULT
+        );
+        assert_eq!(
+            msg.get_proposal_result(),
+            TwoPhaseMessage_ProposalResult::APPLY
+        );
+
         update_tx
             .send(ProposalUpdate::Shutdown)
             .expect("failed to send shutdown");
         thread.join().expect("failed to join engine thread");
     }
 
-    /// Test the coordinator (leader) of a 3 node network by simulating the flow of a valid
-    /// proposal (both participants verify the proposal) and a failed proposal (one participant
-    /// fails the proposal). This test uses default peers for which nodes need to verify the
-    /// proposal. The peers are defined in the startup state.
+    /// Test that a participant that already learned the result of a proposal sends the result to
+    /// a verifier that takes over coordination of the proposal and requests verification.
+    #[cfg(feature = "two-phase-coordinator-policy")]
     #[test]
-    fn test_coordinator_default_peers() {
+    fn test_decided_result_sent_to_new_coordinator() {
         let (update_tx, update_rx) = channel();
         let (consensus_msg_tx, consensus_msg_rx) = channel();
 
         let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
         let network = MockConsensusNetworkSender::new();
         let startup_state = StartupState {
-            id: vec![0].into(),
-            peer_ids: vec![vec![1].into(), vec![2].into()],
+            id: vec![2].into(),
+            peer_ids: vec![vec![0].into(), vec![1].into()],
             last_proposal: None,
         };
 
@@ -789,129 +2684,57 @@ pub mod tests {
                 .expect("engine failed")
         });
 
-        // Check that verification request is sent for the first proposal
-        loop {
-            if let Some(msg) = network.broadcast_messages().get(0) {
-                let msg: TwoPhaseMessage =
-                    Message::parse_from_bytes(msg).expect("failed to parse message");
-                assert_eq!(
-                    msg.get_message_type(),
-                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
-                );
-                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
-                break;
-            }
-        }
-
-        // Receive the verification responses
-        let mut response = TwoPhaseMessage::new();
-        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
-        response.set_proposal_id(vec![1]);
-        response.set_proposal_verification_response(
-            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
-        );
-        let message_bytes = response
-            .write_to_bytes()
-            .expect("failed to write failed response to bytes");
-
-        consensus_msg_tx
-            .send(ConsensusMessage::new(message_bytes.clone(), vec![1].into()))
-            .expect("failed to send 1st response");
-        consensus_msg_tx
-            .send(ConsensusMessage::new(message_bytes, vec![2].into()))
-            .expect("failed to send 2nd response");
-
-        // Verify the Apply message is sent for the proposal
-        loop {
-            if let Some(msg) = network.broadcast_messages().get(1) {
-                let msg: TwoPhaseMessage =
-                    Message::parse_from_bytes(msg).expect("failed to parse message");
-                assert_eq!(
-                    msg.get_message_type(),
-                    TwoPhaseMessage_Type::PROPOSAL_RESULT
-                );
-                assert_eq!(
-                    msg.get_proposal_result(),
-                    TwoPhaseMessage_ProposalResult::APPLY
-                );
-                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
-                break;
+        let mut proposal = Proposal::default();
+        proposal.id = vec![1].into();
+        update_tx
+            .send(ProposalUpdate::ProposalReceived(proposal, vec![0].into()))
+            .expect("failed to send proposal");
+
+        let send_message = |message_type: TwoPhaseMessage_Type, origin_id: u8| {
+            let mut msg = TwoPhaseMessage::new();
+            msg.set_message_type(message_type);
+            msg.set_proposal_id(vec![1]);
+            if message_type == TwoPhaseMessage_Type::PROPOSAL_RESULT {
+                msg.set_proposal_result(TwoPhaseMessage_ProposalResult::APPLY);
             }
-        }
+            consensus_msg_tx
+                .send(ConsensusMessage::new(
+                    msg.write_to_bytes()
+                        .expect("failed to write message to bytes"),
+                    vec![origin_id].into(),
+                ))
+                .expect("failed to send message");
+        };
 
-        // Verify the proposal was accepted
+        // Vote for the proposal and learn its result from the original coordinator
+        send_message(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST, 0);
         loop {
-            if let Some((id, _)) = manager.accepted_proposals().get(0) {
-                assert_eq!(id, &vec![1].into());
+            if network.sent_messages().len() == 1 {
                 break;
             }
         }
-
-        // Check that verification request is sent for the second proposal
+        send_message(TwoPhaseMessage_Type::PROPOSAL_RESULT, 0);
         loop {
-            if let Some(msg) = network.broadcast_messages().get(2) {
-                let msg: TwoPhaseMessage =
-                    Message::parse_from_bytes(msg).expect("failed to parse message");
-                assert_eq!(
-                    msg.get_message_type(),
-                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
-                );
-                assert_eq!(msg.get_proposal_id(), vec![2].as_slice());
+            if !manager.accepted_proposals().is_empty() {
                 break;
             }
         }
 
-        // Receive the verification responses
-        let mut response = TwoPhaseMessage::new();
-        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
-        response.set_proposal_id(vec![2]);
-        response.set_proposal_verification_response(
-            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
-        );
-        let message_bytes = response
-            .write_to_bytes()
-            .expect("failed to write failed response to bytes");
-
-        consensus_msg_tx
-            .send(ConsensusMessage::new(message_bytes, vec![1].into()))
-            .expect("failed to send 1st response");
-
-        let mut response = TwoPhaseMessage::new();
-        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
-        response.set_proposal_id(vec![2]);
-        response.set_proposal_verification_response(
-            TwoPhaseMessage_ProposalVerificationResponse::FAILED,
-        );
-        let message_bytes = response
-            .write_to_bytes()
-            .expect("failed to write failed response to bytes");
-
-        consensus_msg_tx
-            .send(ConsensusMessage::new(message_bytes, vec![2].into()))
-            .expect("failed to send 2nd response");
-
-        // Verify the Reject message is sent for the proposal
+        // The next verifier, which did not receive the result, takes over
+        send_message(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST, 1);
         loop {
-            if let Some(msg) = network.broadcast_messages().get(3) {
+            if let Some((msg, peer_id)) = network.sent_messages().get(1) {
                 let msg: TwoPhaseMessage =
                     Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(peer_id, &vec![1].into());
                 assert_eq!(
                     msg.get_message_type(),
                     TwoPhaseMessage_Type::PROPOSAL_RESULT
                 );
                 assert_eq!(
                     msg.get_proposal_result(),
-                    TwoPhaseMessage_ProposalResult::REJECT
+                    TwoPhaseMessage_ProposalResult::APPLY
                 );
-                assert_eq!(msg.get_proposal_id(), vec![2].as_slice());
-                break;
-            }
-        }
-
-        // Verify the proposal was rejected
-        loop {
-            if let Some(id) = manager.rejected_proposals().get(0) {
-                assert_eq!(id, &vec![2].into());
                 break;
             }
         }
@@ -922,30 +2745,20 @@ pub mod tests {
         thread.join().expect("failed to join engine thread");
     }
 
-    /// Test the coordinator (leader) of a 3 node network by simulating the flow of a valid
-    /// proposal (both participants verify the proposal) and a failed proposal (one participant
-    /// fails the proposal). This test uses dynamic peers for which nodes need to verify the
-    /// proposal. The peers are defined in the consensus data on the proposal.
+    /// Test that a participant sends its vote to a verifier that has taken over coordination of a
+    /// proposal, and ignores verification requests from verifiers that precede the coordinator.
+    #[cfg(feature = "two-phase-coordinator-policy")]
     #[test]
-    fn test_coordinator_dynamic_peers() {
+    fn test_participant_follows_new_coordinator() {
         let (update_tx, update_rx) = channel();
         let (consensus_msg_tx, consensus_msg_rx) = channel();
 
-        let mut manager = MockProposalManager::new(update_tx.clone());
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
         let network = MockConsensusNetworkSender::new();
-
-        let mut required_verifiers = RequiredVerifiers::new();
-        required_verifiers.set_verifiers(RepeatedField::from_vec(vec![
-            vec![0].into(),
-            vec![1].into(),
-            vec![2].into(),
-        ]));
-        let data = required_verifiers.write_to_bytes().unwrap();
-        manager.set_consensus_data(Some(data));
-
         let startup_state = StartupState {
-            id: vec![0].into(),
-            peer_ids: vec![],
+            id: vec![2].into(),
+            peer_ids: vec![vec![0].into(), vec![1].into()],
             last_proposal: None,
         };
 
@@ -964,129 +2777,158 @@ pub mod tests {
                 .expect("engine failed")
         });
 
-        // Check that verification request is sent for the first proposal
-        loop {
-            if let Some(msg) = network.broadcast_messages().get(0) {
-                let msg: TwoPhaseMessage =
-                    Message::parse_from_bytes(msg).expect("failed to parse message");
-                assert_eq!(
-                    msg.get_message_type(),
-                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
-                );
-                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
-                break;
-            }
-        }
-
-        // Receive the verification responses
-        let mut response = TwoPhaseMessage::new();
-        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
-        response.set_proposal_id(vec![1]);
-        response.set_proposal_verification_response(
-            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
-        );
-        let message_bytes = response
-            .write_to_bytes()
-            .expect("failed to write failed response to bytes");
-
-        consensus_msg_tx
-            .send(ConsensusMessage::new(message_bytes.clone(), vec![1].into()))
-            .expect("failed to send 1st response");
-        consensus_msg_tx
-            .send(ConsensusMessage::new(message_bytes, vec![2].into()))
-            .expect("failed to send 2nd response");
+        let mut proposal = Proposal::default();
+        proposal.id = vec![1].into();
+        update_tx
+            .send(ProposalUpdate::ProposalReceived(proposal, vec![0].into()))
+            .expect("failed to send proposal");
+
+        let send_request = |origin_id: u8| {
+            let mut request = TwoPhaseMessage::new();
+            request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
+            request.set_proposal_id(vec![1]);
+            consensus_msg_tx
+                .send(ConsensusMessage::new(
+                    request
+                        .write_to_bytes()
+                        .expect("failed to write request to bytes"),
+                    vec![origin_id].into(),
+                ))
+                .expect("failed to send verification request");
+        };
 
-        // Verify the Apply message is sent for the proposal
+        // The original coordinator requests verification; then the next verifier takes over and
+        // requests it again; then the original coordinator's request is repeated
+        send_request(0);
         loop {
-            if let Some(msg) = network.broadcast_messages().get(1) {
-                let msg: TwoPhaseMessage =
-                    Message::parse_from_bytes(msg).expect("failed to parse message");
-                assert_eq!(
-                    msg.get_message_type(),
-                    TwoPhaseMessage_Type::PROPOSAL_RESULT
-                );
-                assert_eq!(
-                    msg.get_proposal_result(),
-                    TwoPhaseMessage_ProposalResult::APPLY
-                );
-                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+            if network.sent_messages().len() == 1 {
                 break;
             }
         }
-
-        // Verify the proposal was accepted
+        send_request(1);
         loop {
-            if let Some((id, _)) = manager.accepted_proposals().get(0) {
-                assert_eq!(id, &vec![1].into());
+            if network.sent_messages().len() == 2 {
                 break;
             }
         }
+        send_request(0);
 
-        // Check that verification request is sent for the second proposal
+        // Wait for the last request to be handled
+        std::thread::sleep(Duration::from_millis(500));
+
+        let recipients = network
+            .sent_messages()
+            .iter()
+            .map(|(_, peer_id)| peer_id.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            recipients,
+            vec![PeerId::from(vec![0]), PeerId::from(vec![1])]
+        );
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Test that a coordinator that decided a proposal but stopped before completing it resends
+    /// the result when it restarts, and sends the result to a peer that asks for it.
+    ///
+    /// 1. Start the coordinator with a log in which the proposal was started and applied, but not
+    ///    completed.
+    /// 2. Verify that the coordinator broadcasts the apply result and completes the proposal in the
+    ///    log.
+    /// 3. Send a result request from the participant and verify that the coordinator responds
+    ///    with the apply result.
+    #[cfg(feature = "two-phase-log")]
+    #[test]
+    fn test_coordinator_recovery() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into()],
+            last_proposal: None,
+        };
+
+        let mut log = MemoryTwoPhaseLog::new();
+        log.append(&TwoPhaseLogEntry::ProposalStarted {
+            proposal_id: vec![1].into(),
+            coordinator_id: vec![0].into(),
+            required_verifiers: vec![vec![0].into(), vec![1].into()],
+        })
+        .expect("failed to append entry");
+        log.append(&TwoPhaseLogEntry::ProposalDecided {
+            proposal_id: vec![1].into(),
+            apply: true,
+        })
+        .expect("failed to append entry");
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS))
+            .with_log(Box::new(log.clone()));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Check that the Apply result is resent
         loop {
-            if let Some(msg) = network.broadcast_messages().get(2) {
+            if let Some(msg) = network.broadcast_messages().get(0) {
                 let msg: TwoPhaseMessage =
                     Message::parse_from_bytes(msg).expect("failed to parse message");
                 assert_eq!(
                     msg.get_message_type(),
-                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT
                 );
-                assert_eq!(msg.get_proposal_id(), vec![2].as_slice());
+                assert_eq!(
+                    msg.get_proposal_result(),
+                    TwoPhaseMessage_ProposalResult::APPLY
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
                 break;
             }
         }
 
-        // Receive the verification responses
-        let mut response = TwoPhaseMessage::new();
-        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
-        response.set_proposal_id(vec![2]);
-        response.set_proposal_verification_response(
-            TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
-        );
-        let message_bytes = response
+        // Receive a result request from the participant
+        let mut request = TwoPhaseMessage::new();
+        request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT_REQUEST);
+        request.set_proposal_id(vec![1]);
+        let message_bytes = request
             .write_to_bytes()
-            .expect("failed to write failed response to bytes");
+            .expect("failed to write request to bytes");
 
         consensus_msg_tx
             .send(ConsensusMessage::new(message_bytes, vec![1].into()))
-            .expect("failed to send 1st response");
-
-        let mut response = TwoPhaseMessage::new();
-        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
-        response.set_proposal_id(vec![2]);
-        response.set_proposal_verification_response(
-            TwoPhaseMessage_ProposalVerificationResponse::FAILED,
-        );
-        let message_bytes = response
-            .write_to_bytes()
-            .expect("failed to write failed response to bytes");
-
-        consensus_msg_tx
-            .send(ConsensusMessage::new(message_bytes, vec![2].into()))
-            .expect("failed to send 2nd response");
+            .expect("failed to send result request");
 
-        // Verify the Reject message is sent for the proposal
+        // Check that the Apply result is sent to the participant
         loop {
-            if let Some(msg) = network.broadcast_messages().get(3) {
+            if let Some((msg, peer_id)) = network.sent_messages().get(0) {
                 let msg: TwoPhaseMessage =
                     Message::parse_from_bytes(msg).expect("failed to parse message");
+                assert_eq!(peer_id, &vec![1].into());
                 assert_eq!(
                     msg.get_message_type(),
                     TwoPhaseMessage_Type::PROPOSAL_RESULT
                 );
                 assert_eq!(
                     msg.get_proposal_result(),
-                    TwoPhaseMessage_ProposalResult::REJECT
+                    TwoPhaseMessage_ProposalResult::APPLY
                 );
-                assert_eq!(msg.get_proposal_id(), vec![2].as_slice());
-                break;
-            }
-        }
-
-        // Verify the proposal was rejected
-        loop {
-            if let Some(id) = manager.rejected_proposals().get(0) {
-                assert_eq!(id, &vec![2].into());
                 break;
             }
         }
@@ -1095,11 +2937,25 @@ pub mod tests {
             .send(ProposalUpdate::Shutdown)
             .expect("failed to send shutdown");
         thread.join().expect("failed to join engine thread");
+
+        assert_eq!(
+            log.entries().expect("failed to get entries").last(),
+            Some(&TwoPhaseLogEntry::ProposalCompleted {
+                proposal_id: vec![1].into()
+            })
+        );
     }
 
-    /// Test a participant (follower) by simulating the flow of a valid and a failed proposal.
+    /// Test that a participant that verified a proposal but stopped before learning its result
+    /// requests the result from its peers when it restarts, and applies the result when it is
+    /// received.
+    ///
+    /// 1. Start the participant with a log in which it verified the proposal.
+    /// 2. Verify that the participant broadcasts a result request for the proposal.
+    /// 3. Send the apply result and verify that the proposal is accepted and completed in the log.
+    #[cfg(feature = "two-phase-log")]
     #[test]
-    fn test_participant() {
+    fn test_participant_recovery() {
         let (update_tx, update_rx) = channel();
         let (consensus_msg_tx, consensus_msg_rx) = channel();
 
@@ -1112,7 +2968,21 @@ pub mod tests {
             last_proposal: None,
         };
 
-        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS));
+        let mut log = MemoryTwoPhaseLog::new();
+        log.append(&TwoPhaseLogEntry::ProposalStarted {
+            proposal_id: vec![1].into(),
+            coordinator_id: vec![0].into(),
+            required_verifiers: vec![vec![0].into(), vec![1].into()],
+        })
+        .expect("failed to append entry");
+        log.append(&TwoPhaseLogEntry::ProposalVoted {
+            proposal_id: vec![1].into(),
+            verified: true,
+        })
+        .expect("failed to append entry");
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS))
+            .with_log(Box::new(log.clone()));
         let network_clone = network.clone();
         let manager_clone = manager.clone();
         let thread = std::thread::spawn(move || {
@@ -1127,38 +2997,14 @@ pub mod tests {
                 .expect("engine failed")
         });
 
-        // Receive the first proposal
-        let mut proposal = Proposal::default();
-        proposal.id = vec![1].into();
-        update_tx
-            .send(ProposalUpdate::ProposalReceived(proposal, vec![0].into()))
-            .expect("failed to send 1st proposal");
-
-        // Receive the first verification request
-        let mut request = TwoPhaseMessage::new();
-        request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
-        request.set_proposal_id(vec![1]);
-        let message_bytes = request
-            .write_to_bytes()
-            .expect("failed to write request to bytes");
-
-        consensus_msg_tx
-            .send(ConsensusMessage::new(message_bytes, vec![0].into()))
-            .expect("failed to send 1st verification request");
-
-        // Check that the Verified verification response is sent
+        // Check that the result is requested
         loop {
-            if let Some((msg, peer_id)) = network.sent_messages().get(0) {
+            if let Some(msg) = network.broadcast_messages().get(0) {
                 let msg: TwoPhaseMessage =
                     Message::parse_from_bytes(msg).expect("failed to parse message");
-                assert_eq!(peer_id, &vec![0].into());
                 assert_eq!(
                     msg.get_message_type(),
-                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE
-                );
-                assert_eq!(
-                    msg.get_proposal_verification_response(),
-                    TwoPhaseMessage_ProposalVerificationResponse::VERIFIED
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT_REQUEST
                 );
                 assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
                 break;
@@ -1186,63 +3032,83 @@ pub mod tests {
             }
         }
 
-        // Receive the second proposal
-        let mut proposal = Proposal::default();
-        proposal.id = vec![2].into();
         update_tx
-            .send(ProposalUpdate::ProposalReceived(proposal, vec![0].into()))
-            .expect("failed to send 2nd proposal");
-
-        // Receive the second verification request (the manager will say this proposal is invalid)
-        manager.set_next_proposal_valid(false);
-
-        let mut request = TwoPhaseMessage::new();
-        request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
-        request.set_proposal_id(vec![2]);
-        let message_bytes = request
-            .write_to_bytes()
-            .expect("failed to write request to bytes");
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
 
-        consensus_msg_tx
-            .send(ConsensusMessage::new(message_bytes, vec![0].into()))
-            .expect("failed to send 2nd verification request");
+        let entries = log.entries().expect("failed to get entries");
+        assert_eq!(
+            &entries[2..],
+            &[
+                TwoPhaseLogEntry::ProposalDecided {
+                    proposal_id: vec![1].into(),
+                    apply: true,
+                },
+                TwoPhaseLogEntry::ProposalApplied {
+                    proposal_id: vec![1].into()
+                },
+                TwoPhaseLogEntry::ProposalCompleted {
+                    proposal_id: vec![1].into()
+                },
+            ]
+        );
+    }
 
-        // Check that the Failed verification response is sent
-        loop {
-            if let Some((msg, peer_id)) = network.sent_messages().get(1) {
-                let msg: TwoPhaseMessage =
-                    Message::parse_from_bytes(msg).expect("failed to parse message");
-                assert_eq!(peer_id, &vec![0].into());
-                assert_eq!(
-                    msg.get_message_type(),
-                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE
-                );
-                assert_eq!(
-                    msg.get_proposal_verification_response(),
-                    TwoPhaseMessage_ProposalVerificationResponse::FAILED
-                );
-                assert_eq!(msg.get_proposal_id(), vec![2].as_slice());
-                break;
-            }
-        }
+    /// Test that a coordinator that approved a proposal but stopped before applying it applies
+    /// the proposal when it restarts, before the result is resent and the proposal is completed.
+    ///
+    /// 1. Start the coordinator with a log in which it decided to apply the proposal, but did not
+    ///    record that the proposal was applied.
+    /// 2. Verify that the proposal is accepted and the apply result is resent.
+    /// 3. Verify that the log records the proposal as applied before it is completed.
+    #[cfg(feature = "two-phase-log")]
+    #[test]
+    fn test_coordinator_recovery_applies_decided_proposal() {
+        let (update_tx, update_rx) = channel();
+        let (_consensus_msg_tx, consensus_msg_rx) = channel();
 
-        // Receive the Reject result
-        let mut result = TwoPhaseMessage::new();
-        result.set_message_type(TwoPhaseMessage_Type::PROPOSAL_RESULT);
-        result.set_proposal_id(vec![2]);
-        result.set_proposal_result(TwoPhaseMessage_ProposalResult::REJECT);
-        let message_bytes = result
-            .write_to_bytes()
-            .expect("failed to write reject result to bytes");
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into()],
+            last_proposal: None,
+        };
 
-        consensus_msg_tx
-            .send(ConsensusMessage::new(message_bytes, vec![0].into()))
-            .expect("failed to send reject result");
+        let mut log = MemoryTwoPhaseLog::new();
+        log.append(&TwoPhaseLogEntry::ProposalStarted {
+            proposal_id: vec![1].into(),
+            coordinator_id: vec![0].into(),
+            required_verifiers: vec![vec![0].into(), vec![1].into()],
+        })
+        .expect("failed to append entry");
+        log.append(&TwoPhaseLogEntry::ProposalDecided {
+            proposal_id: vec![1].into(),
+            apply: true,
+        })
+        .expect("failed to append entry");
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS))
+            .with_log(Box::new(log.clone()));
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
 
-        // Verify the proposal was rejected
+        // Check that the Apply result is resent
         loop {
-            if let Some(id) = manager.rejected_proposals().get(0) {
-                assert_eq!(id, &vec![2].into());
+            if !network.broadcast_messages().is_empty() {
                 break;
             }
         }
@@ -1251,25 +3117,67 @@ pub mod tests {
             .send(ProposalUpdate::Shutdown)
             .expect("failed to send shutdown");
         thread.join().expect("failed to join engine thread");
+
+        assert_eq!(manager.accepted_proposals().len(), 1);
+        assert_eq!(manager.accepted_proposals()[0].0, vec![1].into());
+
+        let entries = log.entries().expect("failed to get entries");
+        assert_eq!(
+            &entries[2..],
+            &[
+                TwoPhaseLogEntry::ProposalApplied {
+                    proposal_id: vec![1].into()
+                },
+                TwoPhaseLogEntry::ProposalCompleted {
+                    proposal_id: vec![1].into()
+                },
+            ]
+        );
     }
 
-    /// Test that the coordinator will abort a commit if the coordinator timeout expires while
-    /// evaluating the commit.
+    /// Test that a participant that received the apply result of a proposal but stopped before
+    /// applying it applies the proposal when it restarts, without waiting for the result again.
+    ///
+    /// 1. Start the participant with a log in which it decided to apply the proposal, but did not
+    ///    record that the proposal was applied.
+    /// 2. Verify that the proposal is accepted.
+    /// 3. Verify that no result request is sent, and that the log records the proposal as applied
+    ///    before it is completed.
+    #[cfg(feature = "two-phase-log")]
     #[test]
-    fn test_coordinator_timeout() {
+    fn test_participant_recovery_applies_decided_proposal() {
         let (update_tx, update_rx) = channel();
         let (_consensus_msg_tx, consensus_msg_rx) = channel();
 
         let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
         let network = MockConsensusNetworkSender::new();
         let startup_state = StartupState {
-            id: vec![0].into(),
-            peer_ids: vec![vec![1].into(), vec![2].into()],
+            id: vec![1].into(),
+            peer_ids: vec![vec![0].into()],
             last_proposal: None,
         };
 
-        // Start engine with a very short coordinator timeout
-        let mut engine = TwoPhaseEngine::new(Duration::from_millis(10));
+        let mut log = MemoryTwoPhaseLog::new();
+        log.append(&TwoPhaseLogEntry::ProposalStarted {
+            proposal_id: vec![1].into(),
+            coordinator_id: vec![0].into(),
+            required_verifiers: vec![vec![0].into(), vec![1].into()],
+        })
+        .expect("failed to append entry");
+        log.append(&TwoPhaseLogEntry::ProposalVoted {
+            proposal_id: vec![1].into(),
+            verified: true,
+        })
+        .expect("failed to append entry");
+        log.append(&TwoPhaseLogEntry::ProposalDecided {
+            proposal_id: vec![1].into(),
+            apply: true,
+        })
+        .expect("failed to append entry");
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS))
+            .with_log(Box::new(log.clone()));
         let network_clone = network.clone();
         let manager_clone = manager.clone();
         let thread = std::thread::spawn(move || {
@@ -1284,41 +3192,9 @@ pub mod tests {
                 .expect("engine failed")
         });
 
-        // Check that a proposal verification request is sent
-        loop {
-            if let Some(msg) = network.broadcast_messages().get(0) {
-                let msg: TwoPhaseMessage =
-                    Message::parse_from_bytes(msg).expect("failed to parse message");
-                assert_eq!(
-                    msg.get_message_type(),
-                    TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
-                );
-                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
-                break;
-            }
-        }
-
-        // Verify the Reject message is sent for the proposal (due to the timeout)
-        loop {
-            if let Some(msg) = network.broadcast_messages().get(1) {
-                let msg: TwoPhaseMessage =
-                    Message::parse_from_bytes(msg).expect("failed to parse message");
-                assert_eq!(
-                    msg.get_message_type(),
-                    TwoPhaseMessage_Type::PROPOSAL_RESULT
-                );
-                assert_eq!(
-                    msg.get_proposal_result(),
-                    TwoPhaseMessage_ProposalResult::REJECT
-                );
-                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
-                break;
-            }
-        }
-
-        // Verify the proposal was rejected
+        // Verify the proposal was accepted
         loop {
-            if let Some(id) = manager.rejected_proposals().get(0) {
+            if let Some((id, _)) = manager.accepted_proposals().get(0) {
                 assert_eq!(id, &vec![1].into());
                 break;
             }
@@ -1328,5 +3204,20 @@ pub mod tests {
             .send(ProposalUpdate::Shutdown)
             .expect("failed to send shutdown");
         thread.join().expect("failed to join engine thread");
+
+        assert!(network.broadcast_messages().is_empty());
+
+        let entries = log.entries().expect("failed to get entries");
+        assert_eq!(
+            &entries[3..],
+            &[
+                TwoPhaseLogEntry::ProposalApplied {
+                    proposal_id: vec![1].into()
+                },
+                TwoPhaseLogEntry::ProposalCompleted {
+                    proposal_id: vec![1].into()
+                },
+            ]
+        );
     }
 }
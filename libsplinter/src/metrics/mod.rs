@@ -0,0 +1,553 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metrics for splinter components.
+//!
+//! A [`MetricsRegistry`] holds the counters, gauges and histograms that are recorded by
+//! components, and renders them in the Prometheus text exposition format. Registries are cheap to
+//! clone; all clones share the same metrics.
+//!
+//! [`MetricsRegistry`]: struct.MetricsRegistry.html
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The default histogram buckets, in seconds.
+const DEFAULT_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+type Labels = Vec<(String, String)>;
+
+/// A metric whose value only increases.
+#[derive(Clone, Default)]
+pub struct Counter {
+    value: Arc<AtomicU64>,
+}
+
+impl Counter {
+    /// Increments the counter by one.
+    pub fn inc(&self) {
+        self.inc_by(1)
+    }
+
+    /// Increments the counter by the given amount.
+    pub fn inc_by(&self, amount: u64) {
+        self.value.fetch_add(amount, Ordering::Relaxed);
+    }
+
+    /// Returns the current value of the counter.
+    pub fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+/// A metric whose value may be set, increased or decreased.
+#[derive(Clone, Default)]
+pub struct Gauge {
+    value: Arc<AtomicI64>,
+}
+
+impl Gauge {
+    /// Sets the gauge to the given value.
+    pub fn set(&self, value: i64) {
+        self.value.store(value, Ordering::Relaxed);
+    }
+
+    /// Increments the gauge by one.
+    pub fn inc(&self) {
+        self.value.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Decrements the gauge by one.
+    pub fn dec(&self) {
+        self.value.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Returns the current value of the gauge.
+    pub fn get(&self) -> i64 {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+/// A metric that samples observations, such as latencies, into buckets.
+#[derive(Clone)]
+pub struct Histogram {
+    state: Arc<Mutex<HistogramState>>,
+}
+
+struct HistogramState {
+    // The upper bound of each bucket, along with the number of observations in that bucket
+    buckets: Vec<(f64, u64)>,
+    sum: f64,
+    count: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram {
+            state: Arc::new(Mutex::new(HistogramState {
+                buckets: DEFAULT_BUCKETS.iter().map(|bound| (*bound, 0)).collect(),
+                sum: 0.0,
+                count: 0,
+            })),
+        }
+    }
+}
+
+impl Histogram {
+    /// Records the given observation.
+    pub fn observe(&self, value: f64) {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(err) => err.into_inner(),
+        };
+
+        if let Some((_, count)) = state
+            .buckets
+            .iter_mut()
+            .find(|(upper_bound, _)| value <= *upper_bound)
+        {
+            *count += 1;
+        }
+        state.sum += value;
+        state.count += 1;
+    }
+
+    /// Records the given duration, in seconds.
+    pub fn observe_duration(&self, duration: Duration) {
+        self.observe(duration.as_secs_f64())
+    }
+
+    /// Returns the number of observations that have been recorded.
+    pub fn count(&self) -> u64 {
+        match self.state.lock() {
+            Ok(state) => state.count,
+            Err(err) => err.into_inner().count,
+        }
+    }
+}
+
+// Shared so that gauge functions can be evaluated after the registry's lock has been released;
+// a gauge function may itself use the registry
+type GaugeFn = Arc<Mutex<Box<dyn Fn() -> f64 + Send>>>;
+
+#[derive(Clone)]
+enum Series {
+    Counter(Counter),
+    Gauge(Gauge),
+    GaugeFn(GaugeFn),
+    Histogram(Histogram),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+            MetricKind::Histogram => "histogram",
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Family {
+    help: String,
+    kind: MetricKind,
+    series: BTreeMap<Labels, Series>,
+}
+
+/// A collection of metrics that may be rendered in the Prometheus text exposition format.
+///
+/// Metrics are identified by their name and labels; requesting a metric that already exists
+/// returns a handle to the existing metric.
+#[derive(Clone, Default)]
+pub struct MetricsRegistry {
+    families: Arc<Mutex<BTreeMap<String, Family>>>,
+}
+
+impl MetricsRegistry {
+    /// Creates a new, empty `MetricsRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the counter with the given name and labels, creating it if it does not exist.
+    pub fn counter(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Counter {
+        match self.series(name, help, MetricKind::Counter, labels, || {
+            Series::Counter(Counter::default())
+        }) {
+            Some(Series::Counter(counter)) => counter,
+            _ => Counter::default(),
+        }
+    }
+
+    /// Returns the gauge with the given name and labels, creating it if it does not exist.
+    pub fn gauge(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Gauge {
+        match self.series(name, help, MetricKind::Gauge, labels, || {
+            Series::Gauge(Gauge::default())
+        }) {
+            Some(Series::Gauge(gauge)) => gauge,
+            _ => Gauge::default(),
+        }
+    }
+
+    /// Returns the histogram with the given name and labels, creating it if it does not exist.
+    ///
+    /// Histograms use buckets suited to latencies measured in seconds.
+    pub fn histogram(&self, name: &str, help: &str, labels: &[(&str, &str)]) -> Histogram {
+        match self.series(name, help, MetricKind::Histogram, labels, || {
+            Series::Histogram(Histogram::default())
+        }) {
+            Some(Series::Histogram(histogram)) => histogram,
+            _ => Histogram::default(),
+        }
+    }
+
+    /// Registers a gauge whose value is computed by the given function each time the metrics are
+    /// rendered. This replaces any gauge function previously registered with the same name and
+    /// labels.
+    pub fn register_gauge_fn<F>(&self, name: &str, help: &str, labels: &[(&str, &str)], f: F)
+    where
+        F: Fn() -> f64 + Send + 'static,
+    {
+        let mut families = match self.families.lock() {
+            Ok(families) => families,
+            Err(err) => err.into_inner(),
+        };
+
+        let family = families.entry(name.to_string()).or_insert_with(|| Family {
+            help: help.to_string(),
+            kind: MetricKind::Gauge,
+            series: BTreeMap::new(),
+        });
+
+        if family.kind != MetricKind::Gauge {
+            error!(
+                "Unable to register gauge {}: metric is a {}",
+                name,
+                family.kind.as_str()
+            );
+            return;
+        }
+
+        family.series.insert(
+            to_labels(labels),
+            Series::GaugeFn(Arc::new(Mutex::new(Box::new(f)))),
+        );
+    }
+
+    /// Removes all of the series of the metric with the given name that have the given labels.
+    ///
+    /// This is used to stop reporting metrics for components that no longer exist, such as a
+    /// service that has been stopped.
+    pub fn remove(&self, name: &str, labels: &[(&str, &str)]) {
+        let mut families = match self.families.lock() {
+            Ok(families) => families,
+            Err(err) => err.into_inner(),
+        };
+
+        if let Some(family) = families.get_mut(name) {
+            family.series.remove(&to_labels(labels));
+        }
+    }
+
+    /// Renders all metrics in the Prometheus text exposition format.
+    ///
+    /// Gauge functions are evaluated without holding the registry's lock, so they may record or
+    /// read other metrics.
+    pub fn render(&self) -> String {
+        // Series are cheap to clone, since they share their values with the registry
+        let families = match self.families.lock() {
+            Ok(families) => families.clone(),
+            Err(err) => err.into_inner().clone(),
+        };
+
+        let mut output = String::new();
+        for (name, family) in families.iter() {
+            // Writing to a String cannot fail
+            let _ = writeln!(output, "# HELP {} {}", name, escape_help(&family.help));
+            let _ = writeln!(output, "# TYPE {} {}", name, family.kind.as_str());
+
+            for (labels, series) in family.series.iter() {
+                match series {
+                    Series::Counter(counter) => {
+                        let _ = writeln!(
+                            output,
+                            "{}{} {}",
+                            name,
+                            format_labels(labels),
+                            counter.get()
+                        );
+                    }
+                    Series::Gauge(gauge) => {
+                        let _ =
+                            writeln!(output, "{}{} {}", name, format_labels(labels), gauge.get());
+                    }
+                    Series::GaugeFn(f) => {
+                        let value = match f.lock() {
+                            Ok(f) => f(),
+                            Err(err) => (err.into_inner())(),
+                        };
+                        let _ = writeln!(output, "{}{} {}", name, format_labels(labels), value);
+                    }
+                    Series::Histogram(histogram) => {
+                        render_histogram(&mut output, name, labels, histogram);
+                    }
+                }
+            }
+        }
+
+        output
+    }
+
+    fn series<F>(
+        &self,
+        name: &str,
+        help: &str,
+        kind: MetricKind,
+        labels: &[(&str, &str)],
+        new_series: F,
+    ) -> Option<Series>
+    where
+        F: FnOnce() -> Series,
+    {
+        let mut families = match self.families.lock() {
+            Ok(families) => families,
+            Err(err) => err.into_inner(),
+        };
+
+        let family = families.entry(name.to_string()).or_insert_with(|| Family {
+            help: help.to_string(),
+            kind,
+            series: BTreeMap::new(),
+        });
+
+        if family.kind != kind {
+            error!(
+                "Unable to record {} {}: metric is a {}",
+                kind.as_str(),
+                name,
+                family.kind.as_str()
+            );
+            return None;
+        }
+
+        match family
+            .series
+            .entry(to_labels(labels))
+            .or_insert_with(new_series)
+        {
+            Series::Counter(counter) => Some(Series::Counter(counter.clone())),
+            Series::Gauge(gauge) => Some(Series::Gauge(gauge.clone())),
+            Series::Histogram(histogram) => Some(Series::Histogram(histogram.clone())),
+            Series::GaugeFn(_) => None,
+        }
+    }
+}
+
+fn render_histogram(
+    output: &mut String,
+    name: &str,
+    labels: &[(String, String)],
+    histogram: &Histogram,
+) {
+    let state = match histogram.state.lock() {
+        Ok(state) => state,
+        Err(err) => err.into_inner(),
+    };
+
+    let mut cumulative_count = 0;
+    for (upper_bound, count) in state.buckets.iter() {
+        cumulative_count += count;
+        let mut bucket_labels = labels.to_vec();
+        bucket_labels.push(("le".into(), upper_bound.to_string()));
+        let _ = writeln!(
+            output,
+            "{}_bucket{} {}",
+            name,
+            format_labels(&bucket_labels),
+            cumulative_count
+        );
+    }
+
+    let mut bucket_labels = labels.to_vec();
+    bucket_labels.push(("le".into(), "+Inf".into()));
+    let _ = writeln!(
+        output,
+        "{}_bucket{} {}",
+        name,
+        format_labels(&bucket_labels),
+        state.count
+    );
+    let _ = writeln!(
+        output,
+        "{}_sum{} {}",
+        name,
+        format_labels(labels),
+        state.sum
+    );
+    let _ = writeln!(
+        output,
+        "{}_count{} {}",
+        name,
+        format_labels(labels),
+        state.count
+    );
+}
+
+fn to_labels(labels: &[(&str, &str)]) -> Labels {
+    labels
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+fn format_labels(labels: &[(String, String)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+
+    let labels = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("{{{}}}", labels)
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that counters and gauges are shared between clones of the registry, and that they
+    /// are rendered in the Prometheus text format.
+    #[test]
+    fn test_render_counters_and_gauges() {
+        let registry = MetricsRegistry::new();
+
+        registry
+            .counter(
+                "splinter_messages_total",
+                "Messages routed",
+                &[("circuit", "abcDE-01234")],
+            )
+            .inc();
+        registry
+            .clone()
+            .counter(
+                "splinter_messages_total",
+                "Messages routed",
+                &[("circuit", "abcDE-01234")],
+            )
+            .inc_by(2);
+        registry.gauge("splinter_peers", "Peers", &[]).set(4);
+        registry.register_gauge_fn(
+            "splinter_queue_depth",
+            "Queue depth",
+            &[("queue", "a\"b")],
+            || 7.0,
+        );
+
+        assert_eq!(
+            registry.render(),
+            "# HELP splinter_messages_total Messages routed\n\
+             # TYPE splinter_messages_total counter\n\
+             splinter_messages_total{circuit=\"abcDE-01234\"} 3\n\
+             # HELP splinter_peers Peers\n\
+             # TYPE splinter_peers gauge\n\
+             splinter_peers 4\n\
+             # HELP splinter_queue_depth Queue depth\n\
+             # TYPE splinter_queue_depth gauge\n\
+             splinter_queue_depth{queue=\"a\\\"b\"} 7\n"
+        );
+    }
+
+    /// Verify that a gauge function may use the registry it is registered with, since it is
+    /// evaluated after the registry's lock has been released.
+    #[test]
+    fn test_gauge_fn_uses_registry() {
+        let registry = MetricsRegistry::new();
+        registry
+            .counter("requests_total", "Requests", &[])
+            .inc_by(5);
+
+        let gauge_registry = registry.clone();
+        registry.register_gauge_fn("requests_doubled", "Requests, doubled", &[], move || {
+            gauge_registry
+                .counter("requests_total", "Requests", &[])
+                .get() as f64
+                * 2.0
+        });
+
+        assert!(registry.render().contains("requests_doubled 10\n"));
+    }
+
+    /// Verify that histogram observations are rendered as cumulative buckets, along with their
+    /// sum and count.
+    #[test]
+    fn test_render_histogram() {
+        let registry = MetricsRegistry::new();
+        let histogram = registry.histogram("latency_seconds", "Latency", &[]);
+        histogram.observe(0.003);
+        histogram.observe(0.2);
+        histogram.observe(20.0);
+
+        let rendered = registry.render();
+        assert!(rendered.contains("# TYPE latency_seconds histogram\n"));
+        assert!(rendered.contains("latency_seconds_bucket{le=\"0.005\"} 1\n"));
+        assert!(rendered.contains("latency_seconds_bucket{le=\"0.25\"} 2\n"));
+        assert!(rendered.contains("latency_seconds_bucket{le=\"10\"} 2\n"));
+        assert!(rendered.contains("latency_seconds_bucket{le=\"+Inf\"} 3\n"));
+        assert!(rendered.contains("latency_seconds_count 3\n"));
+        assert_eq!(3, histogram.count());
+    }
+
+    /// Verify that a metric requested with a different kind than it was registered with is not
+    /// recorded, and that removed series are no longer rendered.
+    #[test]
+    fn test_mismatched_kind_and_remove() {
+        let registry = MetricsRegistry::new();
+        registry.counter("metric", "A metric", &[("a", "1")]).inc();
+
+        let gauge = registry.gauge("metric", "A metric", &[("a", "1")]);
+        gauge.set(10);
+        assert!(registry.render().contains("metric{a=\"1\"} 1\n"));
+
+        registry.remove("metric", &[("a", "1")]);
+        assert!(!registry.render().contains("metric{a=\"1\"}"));
+    }
+}
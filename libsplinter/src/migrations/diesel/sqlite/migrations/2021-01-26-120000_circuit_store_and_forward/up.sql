@@ -0,0 +1,44 @@
+---- Copyright 2018-2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- --
+
+CREATE TABLE IF NOT EXISTS circuit_outbound_message (
+    destination_node_id       TEXT NOT NULL,
+    circuit_id                TEXT NOT NULL,
+    sequence_number           BIGINT NOT NULL,
+    payload                   BINARY NOT NULL,
+    PRIMARY KEY (destination_node_id, circuit_id, sequence_number)
+);
+
+CREATE TABLE IF NOT EXISTS circuit_outbound_sequence (
+    destination_node_id       TEXT NOT NULL,
+    circuit_id                TEXT NOT NULL,
+    last_sequence_number      BIGINT NOT NULL,
+    PRIMARY KEY (destination_node_id, circuit_id)
+);
+
+CREATE TABLE IF NOT EXISTS circuit_inbound_sequence (
+    source_node_id            TEXT NOT NULL,
+    circuit_id                TEXT NOT NULL,
+    last_sequence_number      BIGINT NOT NULL,
+    PRIMARY KEY (source_node_id, circuit_id)
+);
+
+CREATE TABLE IF NOT EXISTS circuit_service_inbound_sequence (
+    circuit_id                TEXT NOT NULL,
+    sender_service_id         TEXT NOT NULL,
+    recipient_service_id      TEXT NOT NULL,
+    last_sequence_number      BIGINT NOT NULL,
+    PRIMARY KEY (circuit_id, sender_service_id, recipient_service_id)
+);
@@ -0,0 +1,35 @@
+-- Copyright 2018-2021 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS role_inclusions (
+    role_id          TEXT    NOT NULL,
+    included_role_id TEXT    NOT NULL,
+    PRIMARY KEY(role_id, included_role_id),
+    FOREIGN KEY (role_id) REFERENCES roles(id) ON DELETE CASCADE,
+    FOREIGN KEY (included_role_id) REFERENCES roles(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS identity_groups (
+    id           TEXT    PRIMARY KEY,
+    display_name TEXT    NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS identity_group_members (
+    group_id      TEXT    NOT NULL,
+    identity      TEXT    NOT NULL,
+    identity_type INTEGER NOT NULL,
+    PRIMARY KEY(group_id, identity),
+    FOREIGN KEY (group_id) REFERENCES identity_groups(id) ON DELETE CASCADE
+);
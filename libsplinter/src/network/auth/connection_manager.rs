@@ -33,6 +33,11 @@ impl Authorizer for AuthorizationConnector {
         )
         .map_err(AuthorizerError::from)
     }
+
+    #[cfg(feature = "challenge-authorization")]
+    fn on_disconnect(&self, connection_id: &str, identity: &str) {
+        self.remove_peer_authorization(connection_id, identity);
+    }
 }
 
 impl From<ConnectionAuthorizationState> for AuthorizationResult {
@@ -12,8 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "challenge-authorization")]
+use cylinder::{PublicKey, Signature};
 use protobuf::Message;
+#[cfg(feature = "challenge-authorization")]
+use rand::Rng;
 
+#[cfg(feature = "challenge-authorization")]
+use crate::hex::to_hex;
 use crate::network::dispatch::{
     ConnectionId, DispatchError, Dispatcher, Handler, MessageContext, MessageSender,
 };
@@ -21,15 +27,25 @@ use crate::protocol::authorization::{
     AuthorizationError, AuthorizationMessage, AuthorizationType, Authorized, ConnectRequest,
     ConnectResponse, TrustRequest,
 };
+#[cfg(feature = "challenge-authorization")]
+use crate::protocol::authorization::{
+    ChallengeNonceRequest, ChallengeNonceResponse, ChallengeSubmitRequest,
+};
 use crate::protos::authorization;
 use crate::protos::network::{NetworkMessage, NetworkMessageType};
 use crate::protos::prelude::*;
 
+#[cfg(feature = "challenge-authorization")]
+use super::ChallengeAuthorization;
 use super::{
     AuthorizationAction, AuthorizationActionError, AuthorizationManagerStateMachine,
     AuthorizationMessageSender, AuthorizationState,
 };
 
+/// The number of random bytes in a challenge nonce.
+#[cfg(feature = "challenge-authorization")]
+const NONCE_SIZE: usize = 64;
+
 /// Create a Dispatcher for Authorization messages
 ///
 /// Creates and configures a Dispatcher to handle messages from an AuthorizationMessage envelope.
@@ -37,19 +53,61 @@ use super::{
 /// itself to handle updating identities (or removing connections with authorization failures).
 ///
 /// The identity provided is sent to connections for Trust authorizations.
+///
+/// If challenge authorization is provided, the dispatcher will also accept Challenge
+/// authorizations from connections, and will prefer Challenge authorization when connecting to a
+/// node that accepts it.
 pub fn create_authorization_dispatcher(
     identity: String,
     auth_manager: AuthorizationManagerStateMachine,
     auth_msg_sender: impl MessageSender<ConnectionId> + Clone + 'static,
+    #[cfg(feature = "challenge-authorization")] challenge_authorization: Option<
+        ChallengeAuthorization,
+    >,
 ) -> Dispatcher<NetworkMessageType, ConnectionId> {
     let mut auth_dispatcher = Dispatcher::new(Box::new(auth_msg_sender.clone()));
 
-    auth_dispatcher.set_handler(Box::new(ConnectRequestHandler::new(auth_manager.clone())));
+    #[allow(unused_mut)]
+    let mut accepted_authorization_types = vec![AuthorizationType::Trust];
+    #[cfg(feature = "challenge-authorization")]
+    {
+        if challenge_authorization.is_some() {
+            accepted_authorization_types.push(AuthorizationType::Challenge);
+        }
+    }
 
-    auth_dispatcher.set_handler(Box::new(ConnectResponseHandler::new(identity)));
+    auth_dispatcher.set_handler(Box::new(ConnectRequestHandler::new(
+        auth_manager.clone(),
+        accepted_authorization_types,
+    )));
+
+    auth_dispatcher.set_handler(Box::new(ConnectResponseHandler::new(
+        identity.clone(),
+        #[cfg(feature = "challenge-authorization")]
+        challenge_authorization.clone(),
+    )));
 
     auth_dispatcher.set_handler(Box::new(TrustRequestHandler::new(auth_manager.clone())));
 
+    #[cfg(feature = "challenge-authorization")]
+    {
+        if let Some(challenge_authorization) = challenge_authorization {
+            auth_dispatcher.set_handler(Box::new(ChallengeNonceRequestHandler::new(
+                auth_manager.clone(),
+            )));
+
+            auth_dispatcher.set_handler(Box::new(ChallengeNonceResponseHandler::new(
+                identity,
+                challenge_authorization.clone(),
+            )));
+
+            auth_dispatcher.set_handler(Box::new(ChallengeSubmitRequestHandler::new(
+                auth_manager.clone(),
+                challenge_authorization,
+            )));
+        }
+    }
+
     auth_dispatcher.set_handler(Box::new(AuthorizedHandler::new(auth_manager.clone())));
 
     auth_dispatcher.set_handler(Box::new(AuthorizationErrorHandler::new(auth_manager)));
@@ -157,11 +215,18 @@ impl Handler for AuthorizedHandler {
 /// Handler for the Connect Request Authorization Message Type
 struct ConnectRequestHandler {
     auth_manager: AuthorizationManagerStateMachine,
+    accepted_authorization_types: Vec<AuthorizationType>,
 }
 
 impl ConnectRequestHandler {
-    fn new(auth_manager: AuthorizationManagerStateMachine) -> Self {
-        ConnectRequestHandler { auth_manager }
+    fn new(
+        auth_manager: AuthorizationManagerStateMachine,
+        accepted_authorization_types: Vec<AuthorizationType>,
+    ) -> Self {
+        ConnectRequestHandler {
+            auth_manager,
+            accepted_authorization_types,
+        }
     }
 }
 
@@ -228,7 +293,7 @@ impl Handler for ConnectRequestHandler {
                 }
 
                 let response = AuthorizationMessage::ConnectResponse(ConnectResponse {
-                    accepted_authorization_types: vec![AuthorizationType::Trust],
+                    accepted_authorization_types: self.accepted_authorization_types.clone(),
                 });
 
                 let mut msg = NetworkMessage::new();
@@ -253,11 +318,22 @@ impl Handler for ConnectRequestHandler {
 /// Handler for the ConnectResponse Authorization Message Type
 struct ConnectResponseHandler {
     identity: String,
+    #[cfg(feature = "challenge-authorization")]
+    challenge_authorization: Option<ChallengeAuthorization>,
 }
 
 impl ConnectResponseHandler {
-    fn new(identity: String) -> Self {
-        ConnectResponseHandler { identity }
+    fn new(
+        identity: String,
+        #[cfg(feature = "challenge-authorization")] challenge_authorization: Option<
+            ChallengeAuthorization,
+        >,
+    ) -> Self {
+        ConnectResponseHandler {
+            identity,
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization,
+        }
     }
 }
 
@@ -283,6 +359,32 @@ impl Handler for ConnectResponseHandler {
             connect_response,
         );
 
+        // Prefer challenge authorization, if both the local and the remote node support it
+        #[cfg(feature = "challenge-authorization")]
+        {
+            if self.challenge_authorization.is_some()
+                && connect_response
+                    .accepted_authorization_types
+                    .iter()
+                    .any(|t| matches!(t, AuthorizationType::Challenge))
+            {
+                let nonce_request =
+                    AuthorizationMessage::ChallengeNonceRequest(ChallengeNonceRequest);
+                let mut msg = NetworkMessage::new();
+                msg.set_message_type(NetworkMessageType::AUTHORIZATION);
+                msg.set_payload(
+                    IntoBytes::<authorization::AuthorizationMessage>::into_bytes(nonce_request)?,
+                );
+                sender
+                    .send(context.source_id().clone(), msg.write_to_bytes()?)
+                    .map_err(|(recipient, payload)| {
+                        DispatchError::NetworkSendError((recipient.into(), payload))
+                    })?;
+
+                return Ok(());
+            }
+        }
+
         if connect_response
             .accepted_authorization_types
             .iter()
@@ -369,6 +471,294 @@ impl Handler for TrustRequestHandler {
     }
 }
 
+/// Handler for the ChallengeNonceRequest Authorization Message Type
+#[cfg(feature = "challenge-authorization")]
+struct ChallengeNonceRequestHandler {
+    auth_manager: AuthorizationManagerStateMachine,
+}
+
+#[cfg(feature = "challenge-authorization")]
+impl ChallengeNonceRequestHandler {
+    fn new(auth_manager: AuthorizationManagerStateMachine) -> Self {
+        ChallengeNonceRequestHandler { auth_manager }
+    }
+}
+
+#[cfg(feature = "challenge-authorization")]
+impl Handler for ChallengeNonceRequestHandler {
+    type Source = ConnectionId;
+    type MessageType = authorization::AuthorizationMessageType;
+    type Message = authorization::ChallengeNonceRequest;
+
+    fn match_type(&self) -> Self::MessageType {
+        authorization::AuthorizationMessageType::CHALLENGE_NONCE_REQUEST
+    }
+
+    fn handle(
+        &self,
+        _: Self::Message,
+        context: &MessageContext<Self::Source, Self::MessageType>,
+        sender: &dyn MessageSender<Self::Source>,
+    ) -> Result<(), DispatchError> {
+        let mut nonce = vec![0u8; NONCE_SIZE];
+        rand::thread_rng().fill(&mut nonce[..]);
+
+        match self.auth_manager.next_state(
+            context.source_connection_id(),
+            AuthorizationAction::ChallengeNonceIssuing(nonce.clone()),
+        ) {
+            Err(err) => {
+                warn!(
+                    "Ignoring challenge nonce request message from connection {}: {}",
+                    context.source_connection_id(),
+                    err
+                );
+            }
+            Ok(_) => {
+                debug!(
+                    "Sending challenge nonce to connection {}",
+                    context.source_connection_id(),
+                );
+                let nonce_response =
+                    AuthorizationMessage::ChallengeNonceResponse(ChallengeNonceResponse { nonce });
+                let mut msg = NetworkMessage::new();
+                msg.set_message_type(NetworkMessageType::AUTHORIZATION);
+                msg.set_payload(
+                    IntoBytes::<authorization::AuthorizationMessage>::into_bytes(nonce_response)?,
+                );
+                sender
+                    .send(context.source_id().clone(), msg.write_to_bytes()?)
+                    .map_err(|(recipient, payload)| {
+                        DispatchError::NetworkSendError((recipient.into(), payload))
+                    })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Handler for the ChallengeNonceResponse Authorization Message Type
+#[cfg(feature = "challenge-authorization")]
+struct ChallengeNonceResponseHandler {
+    identity: String,
+    challenge_authorization: ChallengeAuthorization,
+}
+
+#[cfg(feature = "challenge-authorization")]
+impl ChallengeNonceResponseHandler {
+    fn new(identity: String, challenge_authorization: ChallengeAuthorization) -> Self {
+        ChallengeNonceResponseHandler {
+            identity,
+            challenge_authorization,
+        }
+    }
+}
+
+#[cfg(feature = "challenge-authorization")]
+impl Handler for ChallengeNonceResponseHandler {
+    type Source = ConnectionId;
+    type MessageType = authorization::AuthorizationMessageType;
+    type Message = authorization::ChallengeNonceResponse;
+
+    fn match_type(&self) -> Self::MessageType {
+        authorization::AuthorizationMessageType::CHALLENGE_NONCE_RESPONSE
+    }
+
+    fn handle(
+        &self,
+        msg: Self::Message,
+        context: &MessageContext<Self::Source, Self::MessageType>,
+        sender: &dyn MessageSender<Self::Source>,
+    ) -> Result<(), DispatchError> {
+        let nonce_response = ChallengeNonceResponse::from_proto(msg)?;
+
+        let signer = &self.challenge_authorization.signer;
+        let signature = signer
+            .sign(&nonce_response.nonce)
+            .map_err(|err| DispatchError::HandleError(format!("Unable to sign nonce: {}", err)))?;
+        let public_key = signer.public_key().map_err(|err| {
+            DispatchError::HandleError(format!("Unable to get public key: {}", err))
+        })?;
+
+        let submit_request = AuthorizationMessage::ChallengeSubmitRequest(ChallengeSubmitRequest {
+            identity: self.identity.clone(),
+            public_key: public_key.as_slice().to_vec(),
+            signature: signature.as_slice().to_vec(),
+        });
+        let mut msg = NetworkMessage::new();
+        msg.set_message_type(NetworkMessageType::AUTHORIZATION);
+        msg.set_payload(
+            IntoBytes::<authorization::AuthorizationMessage>::into_bytes(submit_request)?,
+        );
+        sender
+            .send(context.source_id().clone(), msg.write_to_bytes()?)
+            .map_err(|(recipient, payload)| {
+                DispatchError::NetworkSendError((recipient.into(), payload))
+            })?;
+
+        Ok(())
+    }
+}
+
+/// Handler for the ChallengeSubmitRequest Authorization Message Type
+#[cfg(feature = "challenge-authorization")]
+struct ChallengeSubmitRequestHandler {
+    auth_manager: AuthorizationManagerStateMachine,
+    challenge_authorization: ChallengeAuthorization,
+}
+
+#[cfg(feature = "challenge-authorization")]
+impl ChallengeSubmitRequestHandler {
+    fn new(
+        auth_manager: AuthorizationManagerStateMachine,
+        challenge_authorization: ChallengeAuthorization,
+    ) -> Self {
+        ChallengeSubmitRequestHandler {
+            auth_manager,
+            challenge_authorization,
+        }
+    }
+
+    /// Verifies that the submitted signature is a signature of the nonce issued to the connection,
+    /// made by a key that is registered for the submitted identity.
+    ///
+    /// Returns the reason for rejecting the request, if it is invalid.
+    fn verify_submit_request(
+        &self,
+        connection_id: &str,
+        submit_request: &ChallengeSubmitRequest,
+    ) -> Result<(), String> {
+        let nonce = self
+            .auth_manager
+            .challenge_nonce(connection_id)
+            .map_err(|err| err.to_string())?
+            .ok_or_else(|| "No challenge nonce was issued".to_string())?;
+
+        let verified = self
+            .challenge_authorization
+            .verifier
+            .lock()
+            .map_err(|_| "Challenge verifier lock was poisoned".to_string())?
+            .verify(
+                &nonce,
+                &Signature::new(submit_request.signature.clone()),
+                &PublicKey::new(submit_request.public_key.clone()),
+            )
+            .map_err(|err| format!("Unable to verify challenge signature: {}", err))?;
+        if !verified {
+            return Err("Challenge signature is invalid".into());
+        }
+
+        let node = self
+            .challenge_authorization
+            .registry
+            .fetch_node(&submit_request.identity)
+            .map_err(|err| format!("Unable to look up node in registry: {}", err))?
+            .ok_or_else(|| format!("Node {} is not in the registry", submit_request.identity))?;
+
+        if !node.has_key(&to_hex(&submit_request.public_key)) {
+            return Err(format!(
+                "Key {} is not registered for node {}",
+                to_hex(&submit_request.public_key),
+                submit_request.identity
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "challenge-authorization")]
+impl Handler for ChallengeSubmitRequestHandler {
+    type Source = ConnectionId;
+    type MessageType = authorization::AuthorizationMessageType;
+    type Message = authorization::ChallengeSubmitRequest;
+
+    fn match_type(&self) -> Self::MessageType {
+        authorization::AuthorizationMessageType::CHALLENGE_SUBMIT_REQUEST
+    }
+
+    fn handle(
+        &self,
+        msg: Self::Message,
+        context: &MessageContext<Self::Source, Self::MessageType>,
+        sender: &dyn MessageSender<Self::Source>,
+    ) -> Result<(), DispatchError> {
+        let submit_request = ChallengeSubmitRequest::from_proto(msg)?;
+
+        if let Err(reason) =
+            self.verify_submit_request(context.source_connection_id(), &submit_request)
+        {
+            info!(
+                "Rejecting challenge submitted by connection {}: {}",
+                context.source_connection_id(),
+                reason
+            );
+            let error_msg = AuthorizationMessage::AuthorizationError(
+                AuthorizationError::AuthorizationRejected(reason),
+            );
+            let mut msg = NetworkMessage::new();
+            msg.set_message_type(NetworkMessageType::AUTHORIZATION);
+            msg.set_payload(
+                IntoBytes::<authorization::AuthorizationMessage>::into_bytes(error_msg)?,
+            );
+            sender
+                .send(context.source_id().clone(), msg.write_to_bytes()?)
+                .map_err(|(recipient, payload)| {
+                    DispatchError::NetworkSendError((recipient.into(), payload))
+                })?;
+
+            if let Err(err) = self.auth_manager.next_state(
+                context.source_connection_id(),
+                AuthorizationAction::Unauthorizing,
+            ) {
+                warn!(
+                    "Unable to unauthorize connection {}: {}",
+                    context.source_connection_id(),
+                    err
+                );
+            }
+
+            return Ok(());
+        }
+
+        match self.auth_manager.next_state(
+            context.source_connection_id(),
+            AuthorizationAction::ChallengeIdentifying(submit_request.identity),
+        ) {
+            Err(err) => {
+                warn!(
+                    "Ignoring challenge submit request message from connection {}: {}",
+                    context.source_connection_id(),
+                    err
+                );
+            }
+            Ok(AuthorizationState::RemoteIdentified(identity))
+            | Ok(AuthorizationState::Authorized(identity)) => {
+                debug!(
+                    "Sending Authorized message to connection {} after verifying identity {}",
+                    context.source_connection_id(),
+                    identity,
+                );
+                let auth_msg = AuthorizationMessage::Authorized(Authorized);
+                let mut msg = NetworkMessage::new();
+                msg.set_message_type(NetworkMessageType::AUTHORIZATION);
+                msg.set_payload(
+                    IntoBytes::<authorization::AuthorizationMessage>::into_bytes(auth_msg)?,
+                );
+                sender
+                    .send(context.source_id().clone(), msg.write_to_bytes()?)
+                    .map_err(|(recipient, payload)| {
+                        DispatchError::NetworkSendError((recipient.into(), payload))
+                    })?;
+            }
+            Ok(next_state) => panic!("Should not have been able to transition to {}", next_state),
+        }
+        Ok(())
+    }
+}
+
 /// Handler for the Authorization Error Message Type
 struct AuthorizationErrorHandler {
     auth_manager: AuthorizationManagerStateMachine,
@@ -436,13 +826,19 @@ impl MessageSender<ConnectionId> for AuthorizationMessageSender {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "challenge-authorization")]
+    use std::collections::HashMap;
     use std::collections::VecDeque;
     use std::sync::{Arc, Mutex};
 
+    #[cfg(feature = "challenge-authorization")]
+    use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
     use protobuf::Message;
 
     use crate::protos::authorization;
     use crate::protos::network::{NetworkMessage, NetworkMessageType};
+    #[cfg(feature = "challenge-authorization")]
+    use crate::registry::{MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader};
 
     /// Test that an connect request is properly handled via the dispatcher.
     ///
@@ -456,8 +852,13 @@ mod tests {
         let auth_mgr = AuthorizationManagerStateMachine::default();
         let mock_sender = MockSender::new();
         let dispatch_sender = mock_sender.clone();
-        let dispatcher =
-            create_authorization_dispatcher("mock_identity".into(), auth_mgr, dispatch_sender);
+        let dispatcher = create_authorization_dispatcher(
+            "mock_identity".into(),
+            auth_mgr,
+            dispatch_sender,
+            #[cfg(feature = "challenge-authorization")]
+            None,
+        );
 
         let connection_id = "test_connection".to_string();
         let mut msg = authorization::ConnectRequest::new();
@@ -515,8 +916,13 @@ mod tests {
         let auth_mgr = AuthorizationManagerStateMachine::default();
         let mock_sender = MockSender::new();
         let dispatch_sender = mock_sender.clone();
-        let dispatcher =
-            create_authorization_dispatcher("mock_identity".into(), auth_mgr, dispatch_sender);
+        let dispatcher = create_authorization_dispatcher(
+            "mock_identity".into(),
+            auth_mgr,
+            dispatch_sender,
+            #[cfg(feature = "challenge-authorization")]
+            None,
+        );
         let connection_id = "test_connection".to_string();
         let mut msg = authorization::ConnectResponse::new();
         msg.set_accepted_authorization_types(
@@ -559,8 +965,13 @@ mod tests {
         let auth_mgr = AuthorizationManagerStateMachine::default();
         let mock_sender = MockSender::new();
         let dispatch_sender = mock_sender.clone();
-        let dispatcher =
-            create_authorization_dispatcher("mock_identity".into(), auth_mgr, dispatch_sender);
+        let dispatcher = create_authorization_dispatcher(
+            "mock_identity".into(),
+            auth_mgr,
+            dispatch_sender,
+            #[cfg(feature = "challenge-authorization")]
+            None,
+        );
         let connection_id = "test_connection".to_string();
         // Begin the connection process, otherwise, the response will fail
         let mut msg = authorization::ConnectRequest::new();
@@ -613,6 +1024,292 @@ mod tests {
         );
     }
 
+    /// Test that a connect response is properly handled via the dispatcher when challenge
+    /// authorization is accepted by both nodes.
+    ///
+    /// This is verified by:
+    ///
+    /// 1) a challenge nonce request is sent to the remote connection, instead of a trust request
+    #[cfg(feature = "challenge-authorization")]
+    #[test]
+    fn connect_response_dispatch_challenge() {
+        let auth_mgr = AuthorizationManagerStateMachine::default();
+        let mock_sender = MockSender::new();
+        let dispatch_sender = mock_sender.clone();
+        let (challenge_authorization, _) = challenge_authorization("mock_identity");
+        let dispatcher = create_authorization_dispatcher(
+            "mock_identity".into(),
+            auth_mgr,
+            dispatch_sender,
+            Some(challenge_authorization),
+        );
+        let connection_id = "test_connection".to_string();
+        let mut msg = authorization::ConnectResponse::new();
+        msg.set_accepted_authorization_types(
+            vec![
+                authorization::ConnectResponse_AuthorizationType::TRUST,
+                authorization::ConnectResponse_AuthorizationType::CHALLENGE,
+            ]
+            .into(),
+        );
+        let mut auth_msg = authorization::AuthorizationMessage::new();
+        auth_msg.set_message_type(authorization::AuthorizationMessageType::CONNECT_RESPONSE);
+        auth_msg.set_payload(msg.write_to_bytes().unwrap());
+        let msg_bytes = auth_msg.write_to_bytes().unwrap();
+
+        assert_eq!(
+            Ok(()),
+            dispatcher.dispatch(
+                connection_id.clone().into(),
+                &NetworkMessageType::AUTHORIZATION,
+                msg_bytes
+            )
+        );
+
+        let (_, msg_bytes) = mock_sender
+            .next_outbound()
+            .expect("Unable to receive message over the network");
+
+        let _nonce_req: authorization::ChallengeNonceRequest = expect_auth_message(
+            authorization::AuthorizationMessageType::CHALLENGE_NONCE_REQUEST,
+            &msg_bytes,
+        );
+    }
+
+    /// Test a challenge authorization is properly handled via the dispatcher
+    ///
+    /// This is verified by:
+    ///
+    /// 1). sending a ConnectRequest, and receiving a ConnectResponse that accepts both trust and
+    ///     challenge authorization
+    /// 2). sending a ChallengeNonceRequest, and receiving a nonce
+    /// 3). sending a ChallengeSubmitRequest, with the nonce signed by a key that is registered for
+    ///     the identity
+    /// 4). receiving an Authorize message, which is the result of successful authorization
+    #[cfg(feature = "challenge-authorization")]
+    #[test]
+    fn challenge_submit_request_dispatch() {
+        let auth_mgr = AuthorizationManagerStateMachine::default();
+        let mock_sender = MockSender::new();
+        let dispatch_sender = mock_sender.clone();
+        let (challenge_authorization, signer) = challenge_authorization("my_identity");
+        let dispatcher = create_authorization_dispatcher(
+            "mock_identity".into(),
+            auth_mgr,
+            dispatch_sender,
+            Some(challenge_authorization),
+        );
+        let connection_id = "test_connection".to_string();
+
+        let nonce = request_challenge_nonce(&dispatcher, &mock_sender, &connection_id);
+
+        let mut submit_req = authorization::ChallengeSubmitRequest::new();
+        submit_req.set_identity("my_identity".into());
+        submit_req.set_public_key(
+            signer
+                .public_key()
+                .expect("Unable to get public key")
+                .as_slice()
+                .to_vec(),
+        );
+        submit_req.set_signature(
+            signer
+                .sign(&nonce)
+                .expect("Unable to sign nonce")
+                .as_slice()
+                .to_vec(),
+        );
+        let mut auth_msg = authorization::AuthorizationMessage::new();
+        auth_msg
+            .set_message_type(authorization::AuthorizationMessageType::CHALLENGE_SUBMIT_REQUEST);
+        auth_msg.set_payload(submit_req.write_to_bytes().unwrap());
+        let msg_bytes = auth_msg.write_to_bytes().unwrap();
+        assert_eq!(
+            Ok(()),
+            dispatcher.dispatch(
+                connection_id.clone().into(),
+                &NetworkMessageType::AUTHORIZATION,
+                msg_bytes
+            )
+        );
+
+        let (_, msg_bytes) = mock_sender
+            .next_outbound()
+            .expect("Unable to receive message over the network");
+
+        let _auth_msg: authorization::AuthorizedMessage = expect_auth_message(
+            authorization::AuthorizationMessageType::AUTHORIZE,
+            &msg_bytes,
+        );
+    }
+
+    /// Test that a challenge signed by a key that is not registered for the submitted identity is
+    /// rejected.
+    ///
+    /// This is verified by:
+    ///
+    /// 1). requesting a nonce, as in the successful case
+    /// 2). sending a ChallengeSubmitRequest, with the nonce signed by an unregistered key
+    /// 3). receiving an AuthorizationError message
+    #[cfg(feature = "challenge-authorization")]
+    #[test]
+    fn challenge_submit_request_dispatch_unregistered_key() {
+        let auth_mgr = AuthorizationManagerStateMachine::default();
+        let mock_sender = MockSender::new();
+        let dispatch_sender = mock_sender.clone();
+        let (challenge_authorization, _) = challenge_authorization("my_identity");
+        let dispatcher = create_authorization_dispatcher(
+            "mock_identity".into(),
+            auth_mgr,
+            dispatch_sender,
+            Some(challenge_authorization),
+        );
+        let connection_id = "test_connection".to_string();
+
+        let nonce = request_challenge_nonce(&dispatcher, &mock_sender, &connection_id);
+
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let mut submit_req = authorization::ChallengeSubmitRequest::new();
+        submit_req.set_identity("my_identity".into());
+        submit_req.set_public_key(
+            signer
+                .public_key()
+                .expect("Unable to get public key")
+                .as_slice()
+                .to_vec(),
+        );
+        submit_req.set_signature(
+            signer
+                .sign(&nonce)
+                .expect("Unable to sign nonce")
+                .as_slice()
+                .to_vec(),
+        );
+        let mut auth_msg = authorization::AuthorizationMessage::new();
+        auth_msg
+            .set_message_type(authorization::AuthorizationMessageType::CHALLENGE_SUBMIT_REQUEST);
+        auth_msg.set_payload(submit_req.write_to_bytes().unwrap());
+        let msg_bytes = auth_msg.write_to_bytes().unwrap();
+        assert_eq!(
+            Ok(()),
+            dispatcher.dispatch(
+                connection_id.clone().into(),
+                &NetworkMessageType::AUTHORIZATION,
+                msg_bytes
+            )
+        );
+
+        let (_, msg_bytes) = mock_sender
+            .next_outbound()
+            .expect("Unable to receive message over the network");
+
+        let auth_error: authorization::AuthorizationError = expect_auth_message(
+            authorization::AuthorizationMessageType::AUTHORIZATION_ERROR,
+            &msg_bytes,
+        );
+        assert_eq!(
+            authorization::AuthorizationError_AuthorizationErrorType::AUTHORIZATION_REJECTED,
+            auth_error.get_error_type()
+        );
+    }
+
+    /// Begin the connection process and request a challenge nonce, returning the nonce.
+    #[cfg(feature = "challenge-authorization")]
+    fn request_challenge_nonce(
+        dispatcher: &Dispatcher<NetworkMessageType, ConnectionId>,
+        mock_sender: &MockSender,
+        connection_id: &str,
+    ) -> Vec<u8> {
+        let mut msg = authorization::ConnectRequest::new();
+        msg.set_handshake_mode(authorization::ConnectRequest_HandshakeMode::UNIDIRECTIONAL);
+        let mut auth_msg = authorization::AuthorizationMessage::new();
+        auth_msg.set_message_type(authorization::AuthorizationMessageType::CONNECT_REQUEST);
+        auth_msg.set_payload(msg.write_to_bytes().unwrap());
+        let msg_bytes = auth_msg.write_to_bytes().unwrap();
+        assert_eq!(
+            Ok(()),
+            dispatcher.dispatch(
+                connection_id.to_string().into(),
+                &NetworkMessageType::AUTHORIZATION,
+                msg_bytes
+            )
+        );
+
+        let (_, msg_bytes) = mock_sender
+            .next_outbound()
+            .expect("Unable to receive message over the network");
+
+        let connect_res_msg: authorization::ConnectResponse = expect_auth_message(
+            authorization::AuthorizationMessageType::CONNECT_RESPONSE,
+            &msg_bytes,
+        );
+        assert_eq!(
+            vec![
+                authorization::ConnectResponse_AuthorizationType::TRUST,
+                authorization::ConnectResponse_AuthorizationType::CHALLENGE,
+            ],
+            connect_res_msg.get_accepted_authorization_types().to_vec()
+        );
+
+        let mut auth_msg = authorization::AuthorizationMessage::new();
+        auth_msg.set_message_type(authorization::AuthorizationMessageType::CHALLENGE_NONCE_REQUEST);
+        auth_msg.set_payload(
+            authorization::ChallengeNonceRequest::new()
+                .write_to_bytes()
+                .unwrap(),
+        );
+        let msg_bytes = auth_msg.write_to_bytes().unwrap();
+        assert_eq!(
+            Ok(()),
+            dispatcher.dispatch(
+                connection_id.to_string().into(),
+                &NetworkMessageType::AUTHORIZATION,
+                msg_bytes
+            )
+        );
+
+        let (_, msg_bytes) = mock_sender
+            .next_outbound()
+            .expect("Unable to receive message over the network");
+
+        let nonce_res_msg: authorization::ChallengeNonceResponse = expect_auth_message(
+            authorization::AuthorizationMessageType::CHALLENGE_NONCE_RESPONSE,
+            &msg_bytes,
+        );
+        assert_eq!(NONCE_SIZE, nonce_res_msg.get_nonce().len());
+
+        nonce_res_msg.get_nonce().to_vec()
+    }
+
+    /// Create a challenge authorization with a registry that contains the given identity, along
+    /// with a signer whose key is registered for that identity.
+    #[cfg(feature = "challenge-authorization")]
+    fn challenge_authorization(identity: &str) -> (ChallengeAuthorization, Box<dyn Signer>) {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let public_key = signer.public_key().expect("Unable to get public key");
+
+        let node = Node::builder(identity)
+            .with_endpoint("tcp://localhost:8044")
+            .with_display_name(identity)
+            .with_key(to_hex(public_key.as_slice()))
+            .build()
+            .expect("Unable to build node");
+        let registry = MemRegistry {
+            nodes: vec![(identity.to_string(), node)].into_iter().collect(),
+        };
+
+        (
+            ChallengeAuthorization {
+                signer: signer.clone_box(),
+                verifier: Arc::new(Mutex::new(context.new_verifier())),
+                registry: Arc::new(registry),
+            },
+            signer,
+        )
+    }
+
     fn expect_auth_message<M: protobuf::Message>(
         message_type: authorization::AuthorizationMessageType,
         msg_bytes: &[u8],
@@ -636,6 +1333,31 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "challenge-authorization")]
+    struct MemRegistry {
+        nodes: HashMap<String, Node>,
+    }
+
+    #[cfg(feature = "challenge-authorization")]
+    impl RegistryReader for MemRegistry {
+        fn list_nodes<'a, 'b: 'a>(
+            &'b self,
+            predicates: &'a [MetadataPredicate],
+        ) -> Result<NodeIter<'a>, RegistryError> {
+            let mut nodes = self.nodes.clone();
+            nodes.retain(|_, node| predicates.iter().all(|predicate| predicate.apply(node)));
+            Ok(Box::new(nodes.into_iter().map(|(_, node)| node)))
+        }
+
+        fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError> {
+            self.list_nodes(predicates).map(|iter| iter.count() as u32)
+        }
+
+        fn fetch_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
+            Ok(self.nodes.get(identity).cloned())
+        }
+    }
+
     #[derive(Clone)]
     struct MockSender {
         outbound: Arc<Mutex<VecDeque<(ConnectionId, Vec<u8>)>>>,
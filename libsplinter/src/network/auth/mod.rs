@@ -20,12 +20,18 @@ use std::collections::HashMap;
 use std::fmt;
 use std::sync::{mpsc, Arc, Mutex};
 
+#[cfg(feature = "challenge-authorization")]
+use cylinder::{Signer, Verifier};
 use protobuf::Message;
 
+#[cfg(feature = "challenge-authorization")]
+use crate::protocol::authorization::AuthorizationType;
 use crate::protocol::authorization::{AuthorizationMessage, ConnectRequest};
 use crate::protos::authorization;
 use crate::protos::network::{NetworkMessage, NetworkMessageType};
 use crate::protos::prelude::*;
+#[cfg(feature = "challenge-authorization")]
+use crate::registry::RegistryReader;
 use crate::transport::{Connection, RecvError};
 
 use self::handlers::create_authorization_dispatcher;
@@ -64,6 +70,10 @@ type Identity = String;
 pub(crate) enum AuthorizationAction {
     Connecting,
     TrustIdentifying(Identity),
+    #[cfg(feature = "challenge-authorization")]
+    ChallengeNonceIssuing(Vec<u8>),
+    #[cfg(feature = "challenge-authorization")]
+    ChallengeIdentifying(Identity),
     Unauthorizing,
     RemoteAuthorizing,
 }
@@ -73,6 +83,10 @@ impl fmt::Display for AuthorizationAction {
         match self {
             AuthorizationAction::Connecting => f.write_str("Connecting"),
             AuthorizationAction::TrustIdentifying(_) => f.write_str("TrustIdentifying"),
+            #[cfg(feature = "challenge-authorization")]
+            AuthorizationAction::ChallengeNonceIssuing(_) => f.write_str("ChallengeNonceIssuing"),
+            #[cfg(feature = "challenge-authorization")]
+            AuthorizationAction::ChallengeIdentifying(_) => f.write_str("ChallengeIdentifying"),
             AuthorizationAction::Unauthorizing => f.write_str("Unauthorizing"),
             AuthorizationAction::RemoteAuthorizing => f.write_str("RemoteAuthorizing"),
         }
@@ -112,11 +126,44 @@ impl fmt::Display for AuthorizationManagerError {
     }
 }
 
+/// The signer, verifier and registry used to authorize connections via challenge authorization.
+///
+/// The signer is used to prove the local node's identity to remote nodes, while the verifier
+/// and registry are used to check that a remote node has signed its challenge with a key
+/// registered for the identity it claims.
+#[cfg(feature = "challenge-authorization")]
+pub(crate) struct ChallengeAuthorization {
+    signer: Box<dyn Signer>,
+    verifier: Arc<Mutex<Box<dyn Verifier>>>,
+    registry: Arc<dyn RegistryReader>,
+}
+
+#[cfg(feature = "challenge-authorization")]
+impl Clone for ChallengeAuthorization {
+    fn clone(&self) -> Self {
+        Self {
+            signer: self.signer.clone_box(),
+            verifier: Arc::clone(&self.verifier),
+            registry: Arc::clone(&self.registry),
+        }
+    }
+}
+
+/// Provides the type of authorization that was used to authorize a peer.
+#[cfg(feature = "challenge-authorization")]
+pub trait AuthorizationInquisitor: Send {
+    /// Returns the authorization type that was used to authorize the peer with the given
+    /// identity, or `None` if the peer has not been authorized.
+    fn authorization_type(&self, identity: &str) -> Option<AuthorizationType>;
+}
+
 /// Manages authorization states for connections on a network.
 pub struct AuthorizationManager {
     local_identity: String,
     thread_pool: ThreadPool,
     shared: Arc<Mutex<ManagedAuthorizations>>,
+    #[cfg(feature = "challenge-authorization")]
+    challenge_authorization: Option<ChallengeAuthorization>,
 }
 
 impl AuthorizationManager {
@@ -134,6 +181,44 @@ impl AuthorizationManager {
             thread_pool,
             shared,
             local_identity,
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization: None,
+        })
+    }
+
+    /// Constructs an AuthorizationManager that accepts challenge authorization, in addition to
+    /// trust authorization.
+    ///
+    /// # Arguments
+    ///
+    /// * `local_identity` - The identity of the local node
+    /// * `signer` - The signer used to sign challenges sent by remote nodes; its public key must
+    ///   be registered for the local node
+    /// * `verifier` - The verifier used to check the signatures of challenges submitted by remote
+    ///   nodes
+    /// * `registry` - The registry used to look up the keys permitted for a remote node
+    #[cfg(feature = "challenge-authorization")]
+    pub fn new_with_challenge_authorization(
+        local_identity: String,
+        signer: Box<dyn Signer>,
+        verifier: Box<dyn Verifier>,
+        registry: Box<dyn RegistryReader>,
+    ) -> Result<Self, AuthorizationManagerError> {
+        let mut authorization_manager = Self::new(local_identity)?;
+        authorization_manager.challenge_authorization = Some(ChallengeAuthorization {
+            signer,
+            verifier: Arc::new(Mutex::new(verifier)),
+            registry: Arc::from(registry),
+        });
+
+        Ok(authorization_manager)
+    }
+
+    /// Returns an `AuthorizationInquisitor` that reports how peers were authorized.
+    #[cfg(feature = "challenge-authorization")]
+    pub fn authorization_inquisitor(&self) -> Box<dyn AuthorizationInquisitor> {
+        Box::new(ManagedAuthorizationInquisitor {
+            shared: Arc::clone(&self.shared),
         })
     }
 
@@ -152,6 +237,8 @@ impl AuthorizationManager {
             local_identity: self.local_identity.clone(),
             shared: Arc::clone(&self.shared),
             executor: self.thread_pool.executor(),
+            #[cfg(feature = "challenge-authorization")]
+            challenge_authorization: self.challenge_authorization.clone(),
         }
     }
 }
@@ -173,9 +260,23 @@ pub struct AuthorizationConnector {
     local_identity: String,
     shared: Arc<Mutex<ManagedAuthorizations>>,
     executor: pool::JobExecutor,
+    #[cfg(feature = "challenge-authorization")]
+    challenge_authorization: Option<ChallengeAuthorization>,
 }
 
 impl AuthorizationConnector {
+    /// Forgets how the given connection to the peer with the given identity was authorized, once
+    /// it has disconnected. The peer's other connections are still reported.
+    #[cfg(feature = "challenge-authorization")]
+    fn remove_peer_authorization(&self, connection_id: &str, identity: &str) {
+        match self.shared.lock() {
+            Ok(mut shared) => shared
+                .peer_authorization_types
+                .remove(connection_id, identity),
+            Err(_) => error!("connection authorization lock poisoned"),
+        }
+    }
+
     pub fn add_connection(
         &self,
         connection_id: String,
@@ -190,8 +291,13 @@ impl AuthorizationConnector {
             shared: Arc::clone(&self.shared),
         };
         let msg_sender = AuthorizationMessageSender { sender: tx };
-        let dispatcher =
-            create_authorization_dispatcher(self.local_identity.clone(), state_machine, msg_sender);
+        let dispatcher = create_authorization_dispatcher(
+            self.local_identity.clone(),
+            state_machine,
+            msg_sender,
+            #[cfg(feature = "challenge-authorization")]
+            self.challenge_authorization.clone(),
+        );
         self.executor.execute(move || {
             let connect_request_bytes = match connect_msg_bytes() {
                 Ok(bytes) => bytes,
@@ -340,9 +446,10 @@ impl AuthorizationManagerStateMachine {
         connection_id: &str,
         action: AuthorizationAction,
     ) -> Result<AuthorizationState, AuthorizationActionError> {
-        let mut shared = self.shared.lock().map_err(|_| {
+        let mut guard = self.shared.lock().map_err(|_| {
             AuthorizationActionError::InternalError("Authorization pool lock was poisoned".into())
         })?;
+        let shared = &mut *guard;
 
         let cur_state = shared
             .states
@@ -350,6 +457,17 @@ impl AuthorizationManagerStateMachine {
             .or_insert(AuthorizationState::Unknown);
 
         if action == AuthorizationAction::Unauthorizing {
+            // A connection that fails authorization is no longer reported as authorized
+            #[cfg(feature = "challenge-authorization")]
+            {
+                if let AuthorizationState::RemoteIdentified(identity)
+                | AuthorizationState::Authorized(identity) = &*cur_state
+                {
+                    shared
+                        .peer_authorization_types
+                        .remove(connection_id, identity);
+                }
+            }
             *cur_state = AuthorizationState::Unauthorized;
             return Ok(AuthorizationState::Unauthorized);
         }
@@ -370,7 +488,32 @@ impl AuthorizationManagerStateMachine {
                 AuthorizationAction::TrustIdentifying(identity) => {
                     let new_state = AuthorizationState::RemoteIdentified(identity);
                     *cur_state = new_state.clone();
-                    // Verify pub key allowed
+                    #[cfg(feature = "challenge-authorization")]
+                    shared
+                        .authorization_types
+                        .insert(connection_id.to_string(), AuthorizationType::Trust);
+                    Ok(new_state)
+                }
+                #[cfg(feature = "challenge-authorization")]
+                AuthorizationAction::ChallengeNonceIssuing(nonce) => {
+                    shared.nonces.insert(connection_id.to_string(), nonce);
+                    Ok(AuthorizationState::Connecting)
+                }
+                #[cfg(feature = "challenge-authorization")]
+                AuthorizationAction::ChallengeIdentifying(identity) => {
+                    // A challenge may only be submitted for a nonce that was issued to the
+                    // connection
+                    if shared.nonces.remove(connection_id).is_none() {
+                        return Err(AuthorizationActionError::InvalidMessageOrder(
+                            AuthorizationState::Connecting,
+                            AuthorizationAction::ChallengeIdentifying(identity),
+                        ));
+                    }
+                    let new_state = AuthorizationState::RemoteIdentified(identity);
+                    *cur_state = new_state.clone();
+                    shared
+                        .authorization_types
+                        .insert(connection_id.to_string(), AuthorizationType::Challenge);
                     Ok(new_state)
                 }
                 AuthorizationAction::RemoteAuthorizing => {
@@ -397,6 +540,30 @@ impl AuthorizationManagerStateMachine {
                 AuthorizationAction::TrustIdentifying(identity) => {
                     let new_state = AuthorizationState::Authorized(identity);
                     *cur_state = new_state.clone();
+                    #[cfg(feature = "challenge-authorization")]
+                    shared
+                        .authorization_types
+                        .insert(connection_id.to_string(), AuthorizationType::Trust);
+                    Ok(new_state)
+                }
+                #[cfg(feature = "challenge-authorization")]
+                AuthorizationAction::ChallengeNonceIssuing(nonce) => {
+                    shared.nonces.insert(connection_id.to_string(), nonce);
+                    Ok(AuthorizationState::RemoteAccepted)
+                }
+                #[cfg(feature = "challenge-authorization")]
+                AuthorizationAction::ChallengeIdentifying(identity) => {
+                    if shared.nonces.remove(connection_id).is_none() {
+                        return Err(AuthorizationActionError::InvalidMessageOrder(
+                            AuthorizationState::RemoteAccepted,
+                            AuthorizationAction::ChallengeIdentifying(identity),
+                        ));
+                    }
+                    let new_state = AuthorizationState::Authorized(identity);
+                    *cur_state = new_state.clone();
+                    shared
+                        .authorization_types
+                        .insert(connection_id.to_string(), AuthorizationType::Challenge);
                     Ok(new_state)
                 }
                 _ => Err(AuthorizationActionError::InvalidMessageOrder(
@@ -410,27 +577,64 @@ impl AuthorizationManagerStateMachine {
             )),
         }
     }
+
+    /// Returns the challenge nonce that was issued to the given connection, if any.
+    #[cfg(feature = "challenge-authorization")]
+    pub(crate) fn challenge_nonce(
+        &self,
+        connection_id: &str,
+    ) -> Result<Option<Vec<u8>>, AuthorizationActionError> {
+        let shared = self.shared.lock().map_err(|_| {
+            AuthorizationActionError::InternalError("Authorization pool lock was poisoned".into())
+        })?;
+
+        Ok(shared.nonces.get(connection_id).cloned())
+    }
 }
 
 #[derive(Default)]
 struct ManagedAuthorizations {
     states: HashMap<String, AuthorizationState>,
+    // The challenge nonces issued to connections, keyed by connection id
+    #[cfg(feature = "challenge-authorization")]
+    nonces: HashMap<String, Vec<u8>>,
+    // The authorization types used by connections still being authorized, keyed by connection id
+    #[cfg(feature = "challenge-authorization")]
+    authorization_types: HashMap<String, AuthorizationType>,
+    // The authorization types used by the live connections of authorized peers
+    #[cfg(feature = "challenge-authorization")]
+    peer_authorization_types: PeerAuthorizationTypes,
 }
 
 impl ManagedAuthorizations {
     fn new() -> Self {
-        Self {
-            states: HashMap::new(),
-        }
+        Self::default()
     }
 
     fn take_connection_identity(&mut self, connection_id: &str) -> Option<String> {
-        self.states
+        #[cfg(feature = "challenge-authorization")]
+        let authorization_type = {
+            self.nonces.remove(connection_id);
+            self.authorization_types.remove(connection_id)
+        };
+
+        let identity = self
+            .states
             .remove(connection_id)
             .and_then(|state| match state {
                 AuthorizationState::Authorized(identity) => Some(identity),
                 _ => None,
-            })
+            });
+
+        #[cfg(feature = "challenge-authorization")]
+        {
+            if let (Some(identity), Some(authorization_type)) = (&identity, authorization_type) {
+                self.peer_authorization_types
+                    .insert(connection_id, identity, authorization_type);
+            }
+        }
+
+        identity
     }
 
     fn is_complete(&self, connection_id: &str) -> Option<bool> {
@@ -439,6 +643,78 @@ impl ManagedAuthorizations {
     }
 }
 
+/// The authorization types used by the live connections of authorized peers, keyed by peer
+/// identity and then by connection id.
+///
+/// A peer may have more than one connection open at a time, so the authorization type of a peer is
+/// only forgotten once all of its connections have been removed.
+#[cfg(feature = "challenge-authorization")]
+#[derive(Default)]
+struct PeerAuthorizationTypes {
+    peers: HashMap<String, HashMap<String, AuthorizationType>>,
+}
+
+#[cfg(feature = "challenge-authorization")]
+impl PeerAuthorizationTypes {
+    fn insert(
+        &mut self,
+        connection_id: &str,
+        identity: &str,
+        authorization_type: AuthorizationType,
+    ) {
+        self.peers
+            .entry(identity.to_string())
+            .or_insert_with(HashMap::new)
+            .insert(connection_id.to_string(), authorization_type);
+    }
+
+    fn remove(&mut self, connection_id: &str, identity: &str) {
+        let remaining = match self.peers.get_mut(identity) {
+            Some(connections) => {
+                connections.remove(connection_id);
+                connections.len()
+            }
+            None => return,
+        };
+
+        if remaining == 0 {
+            self.peers.remove(identity);
+        }
+    }
+
+    /// Returns how the peer with the given identity was authorized. If the peer's connections
+    /// were authorized differently, the weakest authorization type is returned.
+    fn get(&self, identity: &str) -> Option<AuthorizationType> {
+        let connections = self.peers.get(identity)?;
+        if connections
+            .values()
+            .any(|authorization_type| *authorization_type == AuthorizationType::Trust)
+        {
+            Some(AuthorizationType::Trust)
+        } else {
+            connections.values().next().copied()
+        }
+    }
+}
+
+#[cfg(feature = "challenge-authorization")]
+struct ManagedAuthorizationInquisitor {
+    shared: Arc<Mutex<ManagedAuthorizations>>,
+}
+
+#[cfg(feature = "challenge-authorization")]
+impl AuthorizationInquisitor for ManagedAuthorizationInquisitor {
+    fn authorization_type(&self, identity: &str) -> Option<AuthorizationType> {
+        match self.shared.lock() {
+            Ok(shared) => shared.peer_authorization_types.get(identity),
+            Err(_) => {
+                error!("connection authorization lock poisoned");
+                None
+            }
+        }
+    }
+}
+
 pub enum ConnectionAuthorizationState {
     Authorized {
         connection_id: String,
@@ -478,6 +754,8 @@ pub(in crate::network) mod tests {
     use protobuf::Message;
 
     use crate::mesh::{Envelope, Mesh};
+    #[cfg(feature = "challenge-authorization")]
+    use crate::network::connection_manager::Authorizer;
     use crate::protocol::authorization::{
         AuthorizationMessage, AuthorizationType, Authorized, ConnectRequest, ConnectResponse,
         TrustRequest,
@@ -561,6 +839,166 @@ pub(in crate::network) mod tests {
         assert!(matches!(trust_request, AuthorizationMessage::Authorized(_)));
     }
 
+    /// Test that the state machine only accepts a challenge identification for a connection that
+    /// was issued a nonce, and that the resulting authorization type is reported for the peer.
+    #[cfg(feature = "challenge-authorization")]
+    #[test]
+    fn challenge_identifying_requires_nonce() {
+        let state_machine = AuthorizationManagerStateMachine::default();
+        let inquisitor = ManagedAuthorizationInquisitor {
+            shared: Arc::clone(&state_machine.shared),
+        };
+
+        assert_eq!(
+            Ok(AuthorizationState::Connecting),
+            state_machine.next_state("conn_1", AuthorizationAction::Connecting)
+        );
+        assert!(state_machine
+            .next_state(
+                "conn_1",
+                AuthorizationAction::ChallengeIdentifying("node_a".into())
+            )
+            .is_err());
+
+        assert_eq!(
+            Ok(AuthorizationState::Connecting),
+            state_machine.next_state(
+                "conn_1",
+                AuthorizationAction::ChallengeNonceIssuing(vec![1, 2, 3])
+            )
+        );
+        assert_eq!(
+            Ok(Some(vec![1, 2, 3])),
+            state_machine.challenge_nonce("conn_1")
+        );
+        assert_eq!(
+            Ok(AuthorizationState::RemoteIdentified("node_a".into())),
+            state_machine.next_state(
+                "conn_1",
+                AuthorizationAction::ChallengeIdentifying("node_a".into())
+            )
+        );
+        assert_eq!(Ok(None), state_machine.challenge_nonce("conn_1"));
+        assert_eq!(
+            Ok(AuthorizationState::Authorized("node_a".into())),
+            state_machine.next_state("conn_1", AuthorizationAction::RemoteAuthorizing)
+        );
+
+        let identity = state_machine
+            .shared
+            .lock()
+            .expect("lock was poisoned")
+            .take_connection_identity("conn_1");
+        assert_eq!(Some("node_a".to_string()), identity);
+        assert_eq!(
+            Some(AuthorizationType::Challenge),
+            inquisitor.authorization_type("node_a")
+        );
+        assert_eq!(None, inquisitor.authorization_type("node_b"));
+    }
+
+    /// Test that a peer's authorization type is reported while any of its connections is
+    /// authorized, and is no longer reported once the authorization of its last connection fails
+    /// or its last connection disconnects.
+    #[cfg(feature = "challenge-authorization")]
+    #[test]
+    fn peer_authorization_type_removed() {
+        let authorization_manager =
+            AuthorizationManager::new("local".into()).expect("Unable to create manager");
+        let state_machine = AuthorizationManagerStateMachine {
+            shared: Arc::clone(&authorization_manager.shared),
+        };
+        let inquisitor = authorization_manager.authorization_inquisitor();
+
+        authorize_with_trust(&state_machine, "conn_1", "node_a");
+        assert_eq!(
+            Some(AuthorizationType::Trust),
+            inquisitor.authorization_type("node_a")
+        );
+
+        assert_eq!(
+            Ok(AuthorizationState::Connecting),
+            state_machine.next_state("conn_2", AuthorizationAction::Connecting)
+        );
+        assert_eq!(
+            Ok(AuthorizationState::RemoteIdentified("node_a".into())),
+            state_machine.next_state(
+                "conn_2",
+                AuthorizationAction::TrustIdentifying("node_a".into())
+            )
+        );
+        assert_eq!(
+            Ok(AuthorizationState::Unauthorized),
+            state_machine.next_state("conn_2", AuthorizationAction::Unauthorizing)
+        );
+        assert_eq!(
+            Some(AuthorizationType::Trust),
+            inquisitor.authorization_type("node_a")
+        );
+
+        // conn_1 is re-authorized after a reconnection, which fails
+        assert_eq!(
+            Ok(AuthorizationState::Connecting),
+            state_machine.next_state("conn_1", AuthorizationAction::Connecting)
+        );
+        assert_eq!(
+            Ok(AuthorizationState::RemoteIdentified("node_a".into())),
+            state_machine.next_state(
+                "conn_1",
+                AuthorizationAction::TrustIdentifying("node_a".into())
+            )
+        );
+        assert_eq!(
+            Ok(AuthorizationState::Unauthorized),
+            state_machine.next_state("conn_1", AuthorizationAction::Unauthorizing)
+        );
+        assert_eq!(None, inquisitor.authorization_type("node_a"));
+
+        authorize_with_trust(&state_machine, "conn_3", "node_a");
+        authorize_with_trust(&state_machine, "conn_4", "node_a");
+        assert_eq!(
+            Some(AuthorizationType::Trust),
+            inquisitor.authorization_type("node_a")
+        );
+
+        let connector = authorization_manager.authorization_connector();
+        connector.on_disconnect("conn_3", "node_a");
+        assert_eq!(
+            Some(AuthorizationType::Trust),
+            inquisitor.authorization_type("node_a")
+        );
+
+        connector.on_disconnect("conn_4", "node_a");
+        assert_eq!(None, inquisitor.authorization_type("node_a"));
+
+        authorization_manager.shutdown_and_await();
+    }
+
+    #[cfg(feature = "challenge-authorization")]
+    fn authorize_with_trust(
+        state_machine: &AuthorizationManagerStateMachine,
+        connection_id: &str,
+        identity: &str,
+    ) {
+        state_machine
+            .next_state(connection_id, AuthorizationAction::Connecting)
+            .expect("Unable to start connecting");
+        state_machine
+            .next_state(
+                connection_id,
+                AuthorizationAction::TrustIdentifying(identity.into()),
+            )
+            .expect("Unable to identify");
+        state_machine
+            .next_state(connection_id, AuthorizationAction::RemoteAuthorizing)
+            .expect("Unable to authorize");
+        state_machine
+            .shared
+            .lock()
+            .expect("lock was poisoned")
+            .take_connection_identity(connection_id);
+    }
+
     fn read_auth_message(bytes: &[u8]) -> AuthorizationMessage {
         let msg: NetworkMessage =
             Message::parse_from_bytes(bytes).expect("Cannot parse network message");
@@ -126,6 +126,13 @@ impl Authorizer for Authorizers {
             connection.remote_endpoint()
         )))
     }
+
+    fn on_disconnect(&self, connection_id: &str, identity: &str) {
+        // The endpoint of the connection is not known, so each authorizer is notified
+        for (_, authorizer) in &self.authorizers {
+            authorizer.on_disconnect(connection_id, identity);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -231,9 +231,12 @@ fn handle_request<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
             subscribers,
         ),
         CmRequest::RemoveConnection { endpoint, sender } => {
-            let response = state
-                .remove_connection(&endpoint)
-                .map(|meta_opt| meta_opt.map(|meta| meta.endpoint().to_owned()));
+            let response = state.remove_connection(&endpoint).map(|meta_opt| {
+                meta_opt.map(|meta| {
+                    authorizer.on_disconnect(meta.connection_id(), meta.identity());
+                    meta.endpoint().to_owned()
+                })
+            });
 
             if sender.send(response).is_err() {
                 warn!("connector dropped before receiving result of remove connection");
@@ -335,6 +338,7 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
                             endpoint, err
                         );
 
+                        authorizer.on_disconnect(&metadata.connection_id, &metadata.identity);
                         subscribers.broadcast(ConnectionManagerNotification::Disconnected {
                             endpoint: endpoint.clone(),
                             identity: metadata.identity.to_string(),
@@ -357,6 +361,7 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
 
                     if !*disconnected {
                         *disconnected = true;
+                        authorizer.on_disconnect(&metadata.connection_id, &metadata.identity);
                         subscribers.broadcast(ConnectionManagerNotification::Disconnected {
                             endpoint: endpoint.clone(),
                             identity: metadata.identity.to_string(),
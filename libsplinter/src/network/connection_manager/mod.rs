@@ -45,6 +45,11 @@ pub trait Authorizer {
         connection: Box<dyn Connection>,
         on_complete: AuthorizerCallback,
     ) -> Result<(), AuthorizerError>;
+
+    /// Notifies the authorizer that the connection with the given ID, authorized for the given
+    /// identity, was disconnected or removed, so it no longer needs to keep any state about the
+    /// connection's authorization.
+    fn on_disconnect(&self, _connection_id: &str, _identity: &str) {}
 }
 
 pub enum AuthorizationResult {
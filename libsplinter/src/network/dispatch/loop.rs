@@ -17,7 +17,11 @@ use std::error::Error;
 use std::fmt;
 use std::fmt::Debug;
 use std::hash::Hash;
+#[cfg(feature = "metrics")]
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::{channel, Receiver, RecvError, Sender};
+#[cfg(feature = "metrics")]
+use std::sync::Arc;
 
 use super::{Dispatcher, PeerId};
 
@@ -139,6 +143,8 @@ where
                         source_id,
                         parent_context: Some(context),
                     }) => {
+                        #[cfg(feature = "metrics")]
+                        rx.queue_depth.fetch_sub(1, Ordering::Relaxed);
                         if let Err(err) = dispatcher.dispatch_with_parent_context(
                             source_id,
                             &message_type,
@@ -154,6 +160,8 @@ where
                         source_id,
                         parent_context: None,
                     }) => {
+                        #[cfg(feature = "metrics")]
+                        rx.queue_depth.fetch_sub(1, Ordering::Relaxed);
                         if let Err(err) =
                             dispatcher.dispatch(source_id, &message_type, message_bytes)
                         {
@@ -174,6 +182,8 @@ where
         match join_handle {
             Ok(join_handle) => Ok(DispatchLoop {
                 sender: tx.sender,
+                #[cfg(feature = "metrics")]
+                queue_depth: tx.queue_depth,
                 join_handle,
             }),
             Err(err) => Err(format!("Unable to start up dispatch loop thread: {}", err)),
@@ -191,6 +201,8 @@ where
     MT: Any + Hash + Eq + Debug + Clone,
 {
     sender: Sender<DispatchMessage<MT, Source>>,
+    #[cfg(feature = "metrics")]
+    queue_depth: Arc<AtomicUsize>,
     join_handle: std::thread::JoinHandle<()>,
 }
 
@@ -207,9 +219,17 @@ where
     pub fn new_dispatcher_sender(&self) -> DispatchMessageSender<MT, Source> {
         DispatchMessageSender {
             sender: self.sender.clone(),
+            #[cfg(feature = "metrics")]
+            queue_depth: self.queue_depth.clone(),
         }
     }
 
+    /// Returns the number of messages that are waiting to be dispatched.
+    #[cfg(feature = "metrics")]
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth.load(Ordering::Relaxed)
+    }
+
     pub fn shutdown_signaler(&self) -> DispatchLoopShutdownSignaler<MT, Source> {
         DispatchLoopShutdownSignaler {
             sender: self.sender.clone(),
@@ -225,9 +245,19 @@ where
     MT: Any + Hash + Eq + Debug + Clone,
 {
     let (tx, rx) = channel();
+    #[cfg(feature = "metrics")]
+    let queue_depth = Arc::new(AtomicUsize::new(0));
     (
-        DispatchMessageSender { sender: tx },
-        DispatchMessageReceiver { receiver: rx },
+        DispatchMessageSender {
+            sender: tx,
+            #[cfg(feature = "metrics")]
+            queue_depth: queue_depth.clone(),
+        },
+        DispatchMessageReceiver {
+            receiver: rx,
+            #[cfg(feature = "metrics")]
+            queue_depth,
+        },
     )
 }
 
@@ -236,6 +266,8 @@ where
     MT: Any + Hash + Eq + Debug + Clone,
 {
     receiver: Receiver<DispatchMessage<MT, Source>>,
+    #[cfg(feature = "metrics")]
+    queue_depth: Arc<AtomicUsize>,
 }
 
 // These type defs make clippy happy.
@@ -248,18 +280,30 @@ where
     MT: Any + Hash + Eq + Debug + Clone,
 {
     sender: Sender<DispatchMessage<MT, Source>>,
+    #[cfg(feature = "metrics")]
+    queue_depth: Arc<AtomicUsize>,
 }
 
 impl<MT, Source> DispatchMessageSender<MT, Source>
 where
     MT: Any + Hash + Eq + Debug + Clone,
 {
+    /// Returns the number of messages that are waiting to be dispatched.
+    #[cfg(feature = "metrics")]
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth.load(Ordering::Relaxed)
+    }
+
     pub fn send(
         &self,
         message_type: MT,
         message_bytes: Vec<u8>,
         source_id: Source,
     ) -> Result<(), MessageTuple<MT, Source>> {
+        // The depth is incremented before sending, so the dispatch loop never decrements it below
+        // zero
+        #[cfg(feature = "metrics")]
+        self.queue_depth.fetch_add(1, Ordering::Relaxed);
         self.sender
             .send(DispatchMessage::Message {
                 message_type,
@@ -267,14 +311,18 @@ where
                 source_id,
                 parent_context: None,
             })
-            .map_err(|err| match err.0 {
-                DispatchMessage::Message {
-                    message_type,
-                    message_bytes,
-                    source_id,
-                    ..
-                } => (message_type, message_bytes, source_id),
-                DispatchMessage::Shutdown => unreachable!(), // we didn't send this
+            .map_err(|err| {
+                #[cfg(feature = "metrics")]
+                self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+                match err.0 {
+                    DispatchMessage::Message {
+                        message_type,
+                        message_bytes,
+                        source_id,
+                        ..
+                    } => (message_type, message_bytes, source_id),
+                    DispatchMessage::Shutdown => unreachable!(), // we didn't send this
+                }
             })
     }
 
@@ -285,6 +333,8 @@ where
         source_id: Source,
         parent_context: Box<dyn Any + Send>,
     ) -> Result<(), MessageTupleWithParentContext<MT, Source>> {
+        #[cfg(feature = "metrics")]
+        self.queue_depth.fetch_add(1, Ordering::Relaxed);
         self.sender
             .send(DispatchMessage::Message {
                 message_type,
@@ -292,14 +342,18 @@ where
                 source_id,
                 parent_context: Some(parent_context),
             })
-            .map_err(|err| match err.0 {
-                DispatchMessage::Message {
-                    message_type,
-                    message_bytes,
-                    source_id,
-                    parent_context: Some(pc),
-                } => (message_type, message_bytes, source_id, pc),
-                _ => unreachable!(), // we didn't anything else
+            .map_err(|err| {
+                #[cfg(feature = "metrics")]
+                self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+                match err.0 {
+                    DispatchMessage::Message {
+                        message_type,
+                        message_bytes,
+                        source_id,
+                        parent_context: Some(pc),
+                    } => (message_type, message_bytes, source_id, pc),
+                    _ => unreachable!(), // we didn't anything else
+                }
             })
     }
 }
@@ -43,7 +43,7 @@ impl<C: diesel::Connection + 'static> DieselInflightOAuthRequestStore<C> {
     }
 }
 
-#[cfg(feature = "sqlite")]
+#[cfg(feature = "oauth-inflight-request-store-sqlite")]
 impl InflightOAuthRequestStore
     for DieselInflightOAuthRequestStore<diesel::sqlite::SqliteConnection>
 {
@@ -163,7 +163,7 @@ impl From<result::Error> for InflightOAuthRequestStoreError {
     }
 }
 
-#[cfg(all(test, feature = "sqlite"))]
+#[cfg(all(test, feature = "oauth-inflight-request-store-sqlite"))]
 pub mod tests {
     use super::*;
 
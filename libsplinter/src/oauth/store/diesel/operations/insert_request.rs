@@ -28,7 +28,7 @@ pub(in crate::oauth::store::diesel) trait InflightOAuthRequestStoreInsertRequest
     ) -> Result<(), InflightOAuthRequestStoreError>;
 }
 
-#[cfg(feature = "sqlite")]
+#[cfg(feature = "oauth-inflight-request-store-sqlite")]
 impl<'a> InflightOAuthRequestStoreInsertRequestOperation
     for InflightOAuthRequestOperations<'a, diesel::sqlite::SqliteConnection>
 {
@@ -44,7 +44,7 @@ impl<'a> InflightOAuthRequestStoreInsertRequestOperation
     }
 }
 
-#[cfg(feature = "postgres")]
+#[cfg(feature = "oauth-inflight-request-store-postgres")]
 impl<'a> InflightOAuthRequestStoreInsertRequestOperation
     for InflightOAuthRequestOperations<'a, diesel::pg::PgConnection>
 {
@@ -27,6 +27,8 @@ use protobuf::Message;
 use uuid::Uuid;
 
 use crate::channel;
+#[cfg(feature = "circuit-durability")]
+use crate::circuit::durable::{store::DurableMessageStore, DuplicateFilter};
 use crate::mesh::{Envelope, Mesh, RecvTimeoutError as MeshRecvTimeoutError};
 use crate::network::reply::InboundRouter;
 use crate::protos::circuit::{
@@ -84,6 +86,47 @@ impl ServiceOrchestrator {
         incoming_capacity: usize,
         outgoing_capacity: usize,
         channel_capacity: usize,
+    ) -> Result<(Self, JoinHandles<Result<(), OrchestratorError>>), NewOrchestratorError> {
+        Self::start(
+            service_factories,
+            connection,
+            incoming_capacity,
+            outgoing_capacity,
+            channel_capacity,
+            #[cfg(feature = "circuit-durability")]
+            DuplicateFilter::new(),
+        )
+    }
+
+    /// Create a new `ServiceOrchestrator` that records the durable messages handed to its
+    /// services in the given store, so that a durable message that is delivered again after the
+    /// node restarts is not handled twice.
+    #[cfg(feature = "circuit-durability")]
+    pub fn with_durable_message_store(
+        service_factories: Vec<Box<dyn ServiceFactory>>,
+        connection: Box<dyn Connection>,
+        incoming_capacity: usize,
+        outgoing_capacity: usize,
+        channel_capacity: usize,
+        durable_message_store: Box<dyn DurableMessageStore>,
+    ) -> Result<(Self, JoinHandles<Result<(), OrchestratorError>>), NewOrchestratorError> {
+        Self::start(
+            service_factories,
+            connection,
+            incoming_capacity,
+            outgoing_capacity,
+            channel_capacity,
+            DuplicateFilter::with_store(durable_message_store),
+        )
+    }
+
+    fn start(
+        service_factories: Vec<Box<dyn ServiceFactory>>,
+        connection: Box<dyn Connection>,
+        incoming_capacity: usize,
+        outgoing_capacity: usize,
+        channel_capacity: usize,
+        #[cfg(feature = "circuit-durability")] duplicate_filter: DuplicateFilter,
     ) -> Result<(Self, JoinHandles<Result<(), OrchestratorError>>), NewOrchestratorError> {
         let services = Arc::new(Mutex::new(HashMap::new()));
         let mesh = Mesh::new(incoming_capacity, outgoing_capacity);
@@ -124,9 +167,13 @@ impl ServiceOrchestrator {
         let inbound_join_handle = thread::Builder::new()
             .name("Orchestrator Inbound".into())
             .spawn(move || {
-                if let Err(err) =
-                    run_inbound_loop(inbound_services, inbound_receiver, inbound_running)
-                {
+                if let Err(err) = run_inbound_loop(
+                    inbound_services,
+                    inbound_receiver,
+                    inbound_running,
+                    #[cfg(feature = "circuit-durability")]
+                    duplicate_filter,
+                ) {
                     error!(
                         "Terminating orchestrator inbound thread due to error: {}",
                         err
@@ -287,22 +334,16 @@ impl ServiceOrchestrator {
         circuits: Vec<String>,
         service_types: Vec<String>,
     ) -> Result<Vec<ServiceDefinition>, ListServicesError> {
-        Ok(self
-            .services
-            .lock()
-            .map_err(|_| ListServicesError::LockPoisoned)?
-            .iter()
-            .filter_map(|(service, _)| {
-                if (circuits.is_empty() || circuits.contains(&service.circuit))
-                    && (service_types.is_empty() || service_types.contains(&service.service_type))
-                {
-                    Some(service)
-                } else {
-                    None
-                }
-            })
-            .cloned()
-            .collect())
+        list_services(&self.services, circuits, service_types)
+    }
+
+    /// Get a `ServiceLister` that lists the services managed by this `ServiceOrchestrator`. The
+    /// lister remains usable after the orchestrator has been moved to its owner.
+    #[cfg(feature = "health")]
+    pub fn service_lister(&self) -> ServiceLister {
+        ServiceLister {
+            services: self.services.clone(),
+        }
     }
 
     pub fn supported_service_types(&self) -> &[String] {
@@ -310,6 +351,48 @@ impl ServiceOrchestrator {
     }
 }
 
+/// Lists the services managed by a `ServiceOrchestrator`.
+#[cfg(feature = "health")]
+#[derive(Clone)]
+pub struct ServiceLister {
+    services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
+}
+
+#[cfg(feature = "health")]
+impl ServiceLister {
+    /// List services managed by the `ServiceOrchestrator`; filters may be provided to only show
+    /// services on specified circuit(s) and of given service type(s).
+    pub fn list_services(
+        &self,
+        circuits: Vec<String>,
+        service_types: Vec<String>,
+    ) -> Result<Vec<ServiceDefinition>, ListServicesError> {
+        list_services(&self.services, circuits, service_types)
+    }
+}
+
+fn list_services(
+    services: &Mutex<HashMap<ServiceDefinition, ManagedService>>,
+    circuits: Vec<String>,
+    service_types: Vec<String>,
+) -> Result<Vec<ServiceDefinition>, ListServicesError> {
+    Ok(services
+        .lock()
+        .map_err(|_| ListServicesError::LockPoisoned)?
+        .iter()
+        .filter_map(|(service, _)| {
+            if (circuits.is_empty() || circuits.contains(&service.circuit))
+                && (service_types.is_empty() || service_types.contains(&service.service_type))
+            {
+                Some(service)
+            } else {
+                None
+            }
+        })
+        .cloned()
+        .collect())
+}
+
 pub struct JoinHandles<T> {
     join_handles: Vec<JoinHandle<T>>,
 }
@@ -443,6 +526,7 @@ fn run_inbound_loop(
     services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
     inbound_receiver: Receiver<Result<(CircuitMessageType, Vec<u8>), channel::RecvError>>,
     inbound_running: Arc<AtomicBool>,
+    #[cfg(feature = "circuit-durability")] mut duplicate_filter: DuplicateFilter,
 ) -> Result<(), OrchestratorError> {
     let timeout = Duration::from_secs(TIMEOUT_SEC);
     while inbound_running.load(Ordering::SeqCst) {
@@ -495,9 +579,27 @@ fn run_inbound_loop(
                 }
             }
             (CircuitMessageType::CIRCUIT_DIRECT_MESSAGE, msg) => {
-                let mut circuit_direct_message: CircuitDirectMessage =
-                    Message::parse_from_bytes(&msg)
-                        .map_err(|err| OrchestratorError::Internal(Box::new(err)))?;
+                let circuit_direct_message: CircuitDirectMessage = Message::parse_from_bytes(&msg)
+                    .map_err(|err| OrchestratorError::Internal(Box::new(err)))?;
+
+                #[cfg(feature = "circuit-durability")]
+                match duplicate_filter.is_duplicate(&circuit_direct_message) {
+                    Ok(true) => {
+                        debug!(
+                            "Dropping duplicate durable message {} from {} to {}",
+                            circuit_direct_message.get_durable_sequence_number(),
+                            circuit_direct_message.get_sender(),
+                            circuit_direct_message.get_recipient(),
+                        );
+                        continue;
+                    }
+                    Ok(false) => (),
+                    Err(err) => error!(
+                        "Unable to check durable message {} for duplicates: {}",
+                        circuit_direct_message.get_durable_sequence_number(),
+                        err
+                    ),
+                }
 
                 let services = services
                     .lock()
@@ -514,9 +616,9 @@ fn run_inbound_loop(
                 }) {
                     Some(service) => {
                         let msg_context = ServiceMessageContext {
-                            sender: circuit_direct_message.take_sender(),
-                            circuit: circuit_direct_message.take_circuit(),
-                            correlation_id: circuit_direct_message.take_correlation_id(),
+                            sender: circuit_direct_message.get_sender().to_string(),
+                            circuit: circuit_direct_message.get_circuit().to_string(),
+                            correlation_id: circuit_direct_message.get_correlation_id().to_string(),
                         };
 
                         if let Err(err) = service
@@ -524,6 +626,19 @@ fn run_inbound_loop(
                         {
                             error!("unable to handle direct message: {}", err);
                         }
+
+                        #[cfg(feature = "circuit-durability")]
+                        {
+                            if let Err(err) =
+                                duplicate_filter.record_handled(&circuit_direct_message)
+                            {
+                                error!(
+                                    "Unable to record durable message {} as handled: {}",
+                                    circuit_direct_message.get_durable_sequence_number(),
+                                    err
+                                );
+                            }
+                        }
                     }
                     None => warn!(
                         "Service with id {} does not exist on circuit {}; ignoring message",
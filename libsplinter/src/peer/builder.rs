@@ -16,6 +16,8 @@
 //!
 //! The public interface includes the structs [`PeerManagerBuilder`]
 
+#[cfg(feature = "metrics")]
+use crate::metrics::MetricsRegistry;
 use crate::network::connection_manager::Connector;
 
 use super::error::PeerManagerError;
@@ -43,6 +45,8 @@ pub struct PeerManagerBuilder {
     endpoint_retry_frequency: Option<u64>,
     identity: Option<String>,
     strict_ref_counts: Option<bool>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<MetricsRegistry>,
 }
 
 /// Constructs new `PeerManager` instances.
@@ -128,6 +132,16 @@ impl PeerManagerBuilder {
         self
     }
 
+    /// Set the metrics registry that the resulting `PeerManager` will report to.
+    ///
+    /// The `PeerManager` will report the number of peers by connection status, as well as the
+    /// number of unreferenced peers.
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: MetricsRegistry) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     /// Starts the `PeerManager`
     ///
     /// Starts up a thread that will handle incoming requests to add, remove and get peers. Also
@@ -163,6 +177,8 @@ impl PeerManagerBuilder {
             retry_frequency,
             max_retry_frequency,
             endpoint_retry_frequency,
+            #[cfg(feature = "metrics")]
+            self.metrics.take(),
         )
     }
 }
@@ -18,11 +18,15 @@ use std::sync::mpsc::{channel, Sender};
 
 use crate::collections::BiHashMap;
 
+#[cfg(feature = "peer-endpoint-update")]
+use super::error::PeerRefUpdateError;
 use super::error::{
     PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
     PeerRefRemoveError, PeerUnknownAddError,
 };
 use super::notification::{PeerManagerNotification, PeerNotificationIter, SubscriberId};
+#[cfg(feature = "health")]
+use super::PeerStatus;
 use super::{EndpointPeerRef, PeerRef};
 use super::{PeerManagerMessage, PeerManagerRequest};
 
@@ -133,6 +137,74 @@ impl PeerManagerConnector {
             .map_err(|err| PeerUnknownAddError::ReceiveError(format!("{:?}", err)))?
     }
 
+    /// Requests that the endpoints of a peer are replaced.
+    ///
+    /// If the peer is currently connected to an endpoint that is no longer listed, the connection
+    /// is closed and the peer is reconnected using the new endpoints. Peers that have not been
+    /// added to the `PeerManager` are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` -  The unique ID for the peer.
+    /// * `endpoints` -  The new list of endpoints associated with the peer, in order of
+    ///   preference.
+    #[cfg(feature = "peer-endpoint-update")]
+    pub fn update_peer_endpoints(
+        &self,
+        peer_id: String,
+        endpoints: Vec<String>,
+    ) -> Result<(), PeerRefUpdateError> {
+        let (sender, recv) = channel();
+
+        let message = PeerManagerMessage::Request(PeerManagerRequest::UpdatePeerEndpoints {
+            peer_id,
+            endpoints,
+            sender,
+        });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerRefUpdateError::InternalError(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerRefUpdateError::ReceiveError(format!("{:?}", err)))?
+    }
+
+    /// Requests that a peer is reconnected.
+    ///
+    /// The peer's current connection is closed and a new connection is requested to its
+    /// endpoints, so the peer is authorized again. This is used when the keys that a peer may
+    /// authorize with have changed. Peers that have not been added to the `PeerManager` are
+    /// ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` -  The unique ID for the peer.
+    #[cfg(feature = "peer-endpoint-update")]
+    pub fn reconnect_peer(&self, peer_id: String) -> Result<(), PeerRefUpdateError> {
+        let (sender, recv) = channel();
+
+        let message =
+            PeerManagerMessage::Request(PeerManagerRequest::ReconnectPeer { peer_id, sender });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerRefUpdateError::InternalError(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerRefUpdateError::ReceiveError(format!("{:?}", err)))?
+    }
+
     /// Requests the list of currently connected peers.
     ///
     /// Returns the list of peer IDs.
@@ -176,6 +248,27 @@ impl PeerManagerConnector {
             .map_err(|err| PeerListError::ReceiveError(format!("{:?}", err)))?
     }
 
+    /// Requests the list of peers, along with the status of each peer's connection.
+    ///
+    /// Returns a list of peer IDs and their statuses.
+    #[cfg(feature = "health")]
+    pub fn list_peer_statuses(&self) -> Result<Vec<(String, PeerStatus)>, PeerListError> {
+        let (sender, recv) = channel();
+        let message = PeerManagerMessage::Request(PeerManagerRequest::ListPeerStatuses { sender });
+
+        match self.sender.send(message) {
+            Ok(()) => (),
+            Err(_) => {
+                return Err(PeerListError::InternalError(
+                    "Unable to send message to PeerManager, receiver dropped".to_string(),
+                ))
+            }
+        };
+
+        recv.recv()
+            .map_err(|err| PeerListError::ReceiveError(format!("{:?}", err)))?
+    }
+
     /// Requests the map of currently connected peers to connection IDs
     ///
     /// Returns a map of peer IDs to connection IDs
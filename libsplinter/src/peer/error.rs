@@ -115,6 +115,34 @@ impl fmt::Display for PeerRefRemoveError {
     }
 }
 
+/// Errors that could be raised when requesting that a peer's endpoints are updated
+#[cfg(feature = "peer-endpoint-update")]
+#[derive(Debug, PartialEq)]
+pub enum PeerRefUpdateError {
+    /// Internal `PeerManager` error
+    InternalError(String),
+    /// Unable to receive response
+    ReceiveError(String),
+    /// Unable to update requested peer
+    UpdateError(String),
+}
+
+#[cfg(feature = "peer-endpoint-update")]
+impl error::Error for PeerRefUpdateError {}
+
+#[cfg(feature = "peer-endpoint-update")]
+impl fmt::Display for PeerRefUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PeerRefUpdateError::InternalError(msg) => write!(f, "Received internal error: {}", msg),
+            PeerRefUpdateError::ReceiveError(msg) => {
+                write!(f, "Unable to receive response from PeerManager: {}", msg)
+            }
+            PeerRefUpdateError::UpdateError(msg) => write!(f, "Unable to update peer: {}", msg),
+        }
+    }
+}
+
 /// Errors that could be raised when requesting a list of peers
 #[derive(Debug, PartialEq)]
 pub enum PeerListError {
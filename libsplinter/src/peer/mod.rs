@@ -40,6 +40,8 @@ use std::time::Instant;
 use uuid::Uuid;
 
 use crate::collections::{BiHashMap, RefMap};
+#[cfg(feature = "metrics")]
+use crate::metrics::{Gauge, MetricsRegistry};
 use crate::network::connection_manager::ConnectionManagerNotification;
 use crate::network::connection_manager::{ConnectionManagerError, Connector};
 use crate::threading::pacemaker;
@@ -47,13 +49,21 @@ use crate::threading::pacemaker;
 pub use self::builder::PeerManagerBuilder;
 pub use self::connector::PeerManagerConnector;
 use self::connector::PeerRemover;
+#[cfg(feature = "peer-endpoint-update")]
+use self::error::PeerRefUpdateError;
 use self::error::{
     PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
     PeerRefRemoveError, PeerUnknownAddError,
 };
 pub use self::notification::{PeerManagerNotification, PeerNotificationIter, SubscriberId};
 use self::notification::{Subscriber, SubscriberMap};
-use self::peer_map::{PeerMap, PeerStatus};
+use self::peer_map::PeerMap;
+#[cfg(feature = "peer-endpoint-update")]
+use self::peer_map::PeerMetadata;
+#[cfg(feature = "health")]
+pub use self::peer_map::PeerStatus;
+#[cfg(not(feature = "health"))]
+use self::peer_map::PeerStatus;
 pub use self::peer_ref::{EndpointPeerRef, PeerRef};
 
 /// Internal messages to drive management
@@ -96,12 +106,27 @@ pub(crate) enum PeerManagerRequest {
         endpoint: String,
         sender: Sender<Result<(), PeerRefRemoveError>>,
     },
+    #[cfg(feature = "peer-endpoint-update")]
+    UpdatePeerEndpoints {
+        peer_id: String,
+        endpoints: Vec<String>,
+        sender: Sender<Result<(), PeerRefUpdateError>>,
+    },
+    #[cfg(feature = "peer-endpoint-update")]
+    ReconnectPeer {
+        peer_id: String,
+        sender: Sender<Result<(), PeerRefUpdateError>>,
+    },
     ListPeers {
         sender: Sender<Result<Vec<String>, PeerListError>>,
     },
     ListUnreferencedPeers {
         sender: Sender<Result<Vec<String>, PeerListError>>,
     },
+    #[cfg(feature = "health")]
+    ListPeerStatuses {
+        sender: Sender<Result<Vec<(String, PeerStatus)>, PeerListError>>,
+    },
     ConnectionIds {
         sender: Sender<Result<BiHashMap<String, String>, PeerConnectionIdError>>,
     },
@@ -240,6 +265,7 @@ impl PeerManager {
         retry_frequency: u64,
         max_retry_frequency: u64,
         endpoint_retry_frequency: u64,
+        #[cfg(feature = "metrics")] metrics: Option<MetricsRegistry>,
     ) -> Result<PeerManager, PeerManagerError> {
         debug!(
             "Starting peer manager with retry_interval={}s, max_retry_attempts={} \
@@ -280,6 +306,9 @@ impl PeerManager {
 
         let pacemaker_shutdown_signaler = pacemaker.shutdown_signaler();
 
+        #[cfg(feature = "metrics")]
+        let peer_metrics = metrics.as_ref().map(PeerManagerMetrics::new);
+
         let join_handle = thread::Builder::new()
             .name("Peer Manager".into())
             .spawn(move || {
@@ -334,6 +363,13 @@ impl PeerManager {
                             break;
                         }
                     }
+
+                    #[cfg(feature = "metrics")]
+                    {
+                        if let Some(peer_metrics) = peer_metrics.as_ref() {
+                            peer_metrics.update(&peers, &unreferenced_peers);
+                        }
+                    }
                 }
 
                 if let Err(err) = connector.unsubscribe(subscriber_id) {
@@ -490,6 +526,34 @@ fn handle_request(
                 warn!("Connector dropped before receiving result of removing peer");
             }
         }
+        #[cfg(feature = "peer-endpoint-update")]
+        PeerManagerRequest::UpdatePeerEndpoints {
+            peer_id,
+            endpoints,
+            sender,
+        } => {
+            if sender
+                .send(update_peer_endpoints(
+                    peer_id,
+                    endpoints,
+                    connector,
+                    peers,
+                    subscribers,
+                ))
+                .is_err()
+            {
+                warn!("Connector dropped before receiving result of updating peer endpoints");
+            }
+        }
+        #[cfg(feature = "peer-endpoint-update")]
+        PeerManagerRequest::ReconnectPeer { peer_id, sender } => {
+            if sender
+                .send(reconnect_peer(peer_id, connector, peers, subscribers))
+                .is_err()
+            {
+                warn!("Connector dropped before receiving result of reconnecting peer");
+            }
+        }
         PeerManagerRequest::ListPeers { sender } => {
             if sender.send(Ok(peers.peer_ids())).is_err() {
                 warn!("Connector dropped before receiving result of list peers");
@@ -506,6 +570,13 @@ fn handle_request(
                 warn!("Connector dropped before receiving result of list unreferenced peers");
             }
         }
+        #[cfg(feature = "health")]
+        PeerManagerRequest::ListPeerStatuses { sender } => {
+            if sender.send(Ok(peers.peer_statuses())).is_err() {
+                warn!("Connector dropped before receiving result of list peer statuses");
+            }
+        }
+
         PeerManagerRequest::ConnectionIds { sender } => {
             if sender.send(Ok(peers.connection_ids())).is_err() {
                 warn!("Connector dropped before receiving result of connection IDs");
@@ -592,6 +663,47 @@ impl UnreferencedPeerState {
     }
 }
 
+/// The gauges the `PeerManager` reports to, which are updated after each message it handles.
+#[cfg(feature = "metrics")]
+struct PeerManagerMetrics {
+    connected: Gauge,
+    pending: Gauge,
+    disconnected: Gauge,
+    unreferenced: Gauge,
+}
+
+#[cfg(feature = "metrics")]
+impl PeerManagerMetrics {
+    fn new(registry: &MetricsRegistry) -> Self {
+        let peers = |status: &str| {
+            registry.gauge(
+                "splinter_peers",
+                "Number of peers by connection status",
+                &[("status", status)],
+            )
+        };
+
+        PeerManagerMetrics {
+            connected: peers("connected"),
+            pending: peers("pending"),
+            disconnected: peers("disconnected"),
+            unreferenced: registry.gauge(
+                "splinter_unreferenced_peers",
+                "Number of connected peers that have not been requested locally",
+                &[],
+            ),
+        }
+    }
+
+    fn update(&self, peers: &PeerMap, unreferenced_peers: &UnreferencedPeerState) {
+        let (connected, pending, disconnected) = peers.status_counts();
+        self.connected.set(connected as i64);
+        self.pending.set(pending as i64);
+        self.disconnected.set(disconnected as i64);
+        self.unreferenced.set(unreferenced_peers.peers.len() as i64);
+    }
+}
+
 // Allow clippy errors for too_many_arguments. The arguments are required
 // to avoid needing a lock in the PeerManager.
 #[allow(clippy::too_many_arguments)]
@@ -825,6 +937,159 @@ fn remove_peer(
     }
 }
 
+/// Replaces the endpoints of a referenced peer. If the peer is not connected to one of the new
+/// endpoints (or through an inbound connection), its connection is replaced by a connection to
+/// the new endpoints.
+#[cfg(feature = "peer-endpoint-update")]
+fn update_peer_endpoints(
+    peer_id: String,
+    endpoints: Vec<String>,
+    connector: Connector,
+    peers: &mut PeerMap,
+    subscribers: &mut SubscriberMap,
+) -> Result<(), PeerRefUpdateError> {
+    let peer_metadata = match peers.get_by_peer_id(&peer_id) {
+        Some(peer_metadata) => peer_metadata.clone(),
+        None => {
+            debug!("Not updating endpoints of unknown peer {}", peer_id);
+            return Ok(());
+        }
+    };
+
+    if peer_metadata.endpoints == endpoints {
+        return Ok(());
+    }
+
+    if endpoints.is_empty() {
+        return Err(PeerRefUpdateError::UpdateError(format!(
+            "No endpoints provided for peer {}",
+            peer_id
+        )));
+    }
+
+    info!("Updating peer {} to endpoints {:?}", peer_id, endpoints);
+
+    // A connection to one of the new endpoints, or an inbound connection from the peer, can still
+    // be used
+    if peer_metadata.status == PeerStatus::Connected
+        && (endpoints.contains(&peer_metadata.active_endpoint)
+            || !peer_metadata
+                .endpoints
+                .contains(&peer_metadata.active_endpoint))
+    {
+        peers.remove(&peer_id);
+        peers.insert(
+            peer_id,
+            peer_metadata.connection_id,
+            endpoints,
+            peer_metadata.active_endpoint,
+            PeerStatus::Connected,
+        );
+        return Ok(());
+    }
+
+    replace_peer_connection(
+        peer_id,
+        peer_metadata,
+        endpoints,
+        connector,
+        peers,
+        subscribers,
+    );
+
+    Ok(())
+}
+
+/// Closes the connection of a referenced peer and requests a new connection to its endpoints, so
+/// the peer is authorized again.
+#[cfg(feature = "peer-endpoint-update")]
+fn reconnect_peer(
+    peer_id: String,
+    connector: Connector,
+    peers: &mut PeerMap,
+    subscribers: &mut SubscriberMap,
+) -> Result<(), PeerRefUpdateError> {
+    let peer_metadata = match peers.get_by_peer_id(&peer_id) {
+        Some(peer_metadata) => peer_metadata.clone(),
+        None => {
+            debug!("Not reconnecting unknown peer {}", peer_id);
+            return Ok(());
+        }
+    };
+
+    if peer_metadata.endpoints.is_empty() {
+        return Err(PeerRefUpdateError::UpdateError(format!(
+            "No endpoints to reconnect peer {}",
+            peer_id
+        )));
+    }
+
+    info!("Reconnecting peer {}", peer_id);
+
+    let endpoints = peer_metadata.endpoints.clone();
+    replace_peer_connection(
+        peer_id,
+        peer_metadata,
+        endpoints,
+        connector,
+        peers,
+        subscribers,
+    );
+
+    Ok(())
+}
+
+/// Removes the peer's current connection, if any, and requests a connection to the first of the
+/// given endpoints that accepts the request. The peer is left pending until the new connection is
+/// established.
+///
+/// The given endpoints must not be empty.
+#[cfg(feature = "peer-endpoint-update")]
+fn replace_peer_connection(
+    peer_id: String,
+    peer_metadata: PeerMetadata,
+    endpoints: Vec<String>,
+    connector: Connector,
+    peers: &mut PeerMap,
+    subscribers: &mut SubscriberMap,
+) {
+    // If the peer is pending there is no connection to remove
+    if peer_metadata.status != PeerStatus::Pending {
+        if let Err(err) = connector.remove_connection(&peer_metadata.active_endpoint) {
+            error!("Unable to clean up old connection: {}", err);
+        }
+    }
+
+    if peer_metadata.status == PeerStatus::Connected {
+        subscribers.broadcast(PeerManagerNotification::Disconnected {
+            peer: peer_id.clone(),
+        });
+    }
+
+    let mut active_endpoint = endpoints[0].clone();
+    for endpoint in endpoints.iter() {
+        match connector.request_connection(endpoint, &peer_metadata.connection_id) {
+            Ok(()) => {
+                active_endpoint = endpoint.to_string();
+                break;
+            }
+            // If the request_connection errored we will retry in the future
+            Err(err) => {
+                log_connect_request_err(err, &peer_id, endpoint);
+            }
+        }
+    }
+
+    peers.remove(&peer_id);
+    peers.insert(
+        peer_id,
+        peer_metadata.connection_id,
+        endpoints,
+        active_endpoint,
+        PeerStatus::Pending,
+    );
+}
+
 fn remove_peer_by_endpoint(
     endpoint: String,
     connector: Connector,
@@ -1558,6 +1823,199 @@ pub mod tests {
         mesh.shutdown_signaler().shutdown();
     }
 
+    // Test that updating the endpoints of a connected peer reconnects the peer using the new
+    // endpoints
+    //
+    // 1. add test_peer at inproc://test and wait for the Connected notification
+    // 2. update the endpoints of test_peer to inproc://test_updated
+    // 3. verify that a Disconnected notification is received, followed by a Connected
+    //    notification
+    // 4. verify that test_peer is still listed as a peer
+    #[cfg(feature = "peer-endpoint-update")]
+    #[test]
+    fn test_peer_manager_update_peer_endpoints() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+        let mut updated_listener = transport.listen("inproc://test_updated").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+        thread::spawn(move || {
+            updated_listener.accept().unwrap();
+        });
+
+        let mesh = Mesh::new(512, 128);
+        let cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport.clone())
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+        let peer_manager = PeerManager::builder()
+            .with_connector(connector)
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+        let (tx, notification_rx): (
+            Sender<PeerManagerNotification>,
+            mpsc::Receiver<PeerManagerNotification>,
+        ) = channel();
+        peer_connector
+            .subscribe_sender(tx)
+            .expect("Unable to get subscriber");
+        let _peer_ref = peer_connector
+            .add_peer_ref("test_peer".to_string(), vec!["inproc://test".to_string()])
+            .expect("Unable to add peer");
+
+        // timeout after 60 seconds
+        let timeout = Duration::from_secs(60);
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(
+            notification
+                == PeerManagerNotification::Connected {
+                    peer: "test_peer".to_string(),
+                }
+        );
+
+        peer_connector
+            .update_peer_endpoints(
+                "test_peer".to_string(),
+                vec!["inproc://test_updated".to_string()],
+            )
+            .expect("Unable to update peer endpoints");
+
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(
+            notification
+                == PeerManagerNotification::Disconnected {
+                    peer: "test_peer".to_string(),
+                }
+        );
+
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(
+            notification
+                == PeerManagerNotification::Connected {
+                    peer: "test_peer".to_string(),
+                }
+        );
+
+        let peers = peer_connector.list_peers().expect("Unable to list peers");
+        assert_eq!(peers, vec!["test_peer".to_string()]);
+
+        peer_manager.shutdown_signaler().shutdown();
+        cm.shutdown_signaler().shutdown();
+        peer_manager.await_shutdown();
+        cm.await_shutdown();
+        mesh.shutdown_signaler().shutdown();
+    }
+
+    // Test that a peer can be reconnected, so that it is authorized again
+    //
+    // 1. add test_peer at inproc://test and wait for the Connected notification
+    // 2. reconnect test_peer
+    // 3. verify that a Disconnected notification is received, followed by a Connected
+    //    notification
+    // 4. verify that test_peer is still listed as a peer
+    #[cfg(feature = "peer-endpoint-update")]
+    #[test]
+    fn test_peer_manager_reconnect_peer() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+            listener.accept().unwrap();
+        });
+
+        let mesh = Mesh::new(512, 128);
+        let cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport.clone())
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+        let peer_manager = PeerManager::builder()
+            .with_connector(connector)
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+        let (tx, notification_rx): (
+            Sender<PeerManagerNotification>,
+            mpsc::Receiver<PeerManagerNotification>,
+        ) = channel();
+        peer_connector
+            .subscribe_sender(tx)
+            .expect("Unable to get subscriber");
+        let _peer_ref = peer_connector
+            .add_peer_ref("test_peer".to_string(), vec!["inproc://test".to_string()])
+            .expect("Unable to add peer");
+
+        // timeout after 60 seconds
+        let timeout = Duration::from_secs(60);
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(
+            notification
+                == PeerManagerNotification::Connected {
+                    peer: "test_peer".to_string(),
+                }
+        );
+
+        peer_connector
+            .reconnect_peer("test_peer".to_string())
+            .expect("Unable to reconnect peer");
+
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(
+            notification
+                == PeerManagerNotification::Disconnected {
+                    peer: "test_peer".to_string(),
+                }
+        );
+
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(
+            notification
+                == PeerManagerNotification::Connected {
+                    peer: "test_peer".to_string(),
+                }
+        );
+
+        let peers = peer_connector.list_peers().expect("Unable to list peers");
+        assert_eq!(peers, vec!["test_peer".to_string()]);
+
+        peer_manager.shutdown_signaler().shutdown();
+        cm.shutdown_signaler().shutdown();
+        peer_manager.await_shutdown();
+        cm.await_shutdown();
+        mesh.shutdown_signaler().shutdown();
+    }
+
     // Test that a call to add_peer_ref, where the authorizer returns an different id than
     // requested, the connector returns an error.
     //
@@ -1877,6 +2335,79 @@ pub mod tests {
         mesh.shutdown_signaler().shutdown();
     }
 
+    // Test that list_peer_statuses returns the status of each peer
+    //
+    // 1. add test_peer
+    // 2. verify the the a Connected notification is received
+    // 3. call list_peer_statuses
+    // 4. verify that test_peer is listed as connected
+    #[cfg(feature = "health")]
+    #[test]
+    fn test_peer_manager_list_peer_statuses() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mesh = Mesh::new(512, 128);
+        let cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport.clone())
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+        let peer_manager = PeerManager::builder()
+            .with_connector(connector)
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+        let (tx, notification_rx): (
+            Sender<PeerManagerNotification>,
+            mpsc::Receiver<PeerManagerNotification>,
+        ) = channel();
+        peer_connector
+            .subscribe_sender(tx)
+            .expect("Unable to get subscriber");
+        let _peer_ref = peer_connector
+            .add_peer_ref("test_peer".to_string(), vec!["inproc://test".to_string()])
+            .expect("Unable to add peer");
+
+        // timeout after 60 seconds
+        let timeout = Duration::from_secs(60);
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(
+            notification
+                == PeerManagerNotification::Connected {
+                    peer: "test_peer".to_string(),
+                }
+        );
+
+        let peer_statuses = peer_connector
+            .list_peer_statuses()
+            .expect("Unable to get peer statuses");
+
+        assert_eq!(
+            peer_statuses,
+            vec![("test_peer".to_string(), PeerStatus::Connected)]
+        );
+
+        peer_manager.shutdown_signaler().shutdown();
+        cm.shutdown_signaler().shutdown();
+        peer_manager.await_shutdown();
+        cm.await_shutdown();
+        mesh.shutdown_signaler().shutdown();
+    }
+
     // Test that list_peer returns the correct list of connection IDs
     //
     // 1. add test_peer
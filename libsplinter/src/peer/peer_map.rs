@@ -83,6 +83,15 @@ impl PeerMap {
             .collect()
     }
 
+    /// Returns the current list of peer IDs, along with each peer's status
+    #[cfg(feature = "health")]
+    pub fn peer_statuses(&self) -> Vec<(String, PeerStatus)> {
+        self.peers
+            .values()
+            .map(|metadata| (metadata.id.to_string(), metadata.status.clone()))
+            .collect()
+    }
+
     /// Returns the current map of peer IDs to connection IDs
     pub fn connection_ids(&self) -> BiHashMap<String, String> {
         let mut peer_to_connection_id = BiHashMap::new();
@@ -203,6 +212,19 @@ impl PeerMap {
     pub fn contains_endpoint(&self, endpoint: &str) -> bool {
         self.endpoints.contains_key(endpoint)
     }
+
+    /// Returns the number of connected, pending, and disconnected peers, in that order
+    #[cfg(feature = "metrics")]
+    pub fn status_counts(&self) -> (usize, usize, usize) {
+        self.peers.values().fold(
+            (0, 0, 0),
+            |(connected, pending, disconnected), peer_meta| match peer_meta.status {
+                PeerStatus::Connected => (connected + 1, pending, disconnected),
+                PeerStatus::Pending => (connected, pending + 1, disconnected),
+                PeerStatus::Disconnected { .. } => (connected, pending, disconnected + 1),
+            },
+        )
+    }
 }
 
 #[cfg(test)]
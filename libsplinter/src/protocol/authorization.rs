@@ -29,12 +29,17 @@ pub enum AuthorizationMessage {
     AuthorizationError(AuthorizationError),
 
     TrustRequest(TrustRequest),
+
+    ChallengeNonceRequest(ChallengeNonceRequest),
+    ChallengeNonceResponse(ChallengeNonceResponse),
+    ChallengeSubmitRequest(ChallengeSubmitRequest),
 }
 
 /// The possible types of authorization that may be computed during the handshake.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AuthorizationType {
     Trust,
+    Challenge,
 }
 
 /// A connection request message.
@@ -64,10 +69,36 @@ pub struct TrustRequest {
     pub identity: String,
 }
 
+/// A challenge nonce request.
+///
+/// A challenge nonce request is sent in response to a Connect Message, if the node is using
+/// challenge authorization as its means of allowing a node to connect.
+#[derive(Debug)]
+pub struct ChallengeNonceRequest;
+
+/// A challenge nonce response.
+///
+/// This message contains the nonce that the connecting node must sign.
+#[derive(Debug)]
+pub struct ChallengeNonceResponse {
+    pub nonce: Vec<u8>,
+}
+
+/// A challenge submit request.
+///
+/// This message provides the connecting node's identity, along with the signature of the nonce
+/// and the public key that may be used to verify it.
+#[derive(Debug)]
+pub struct ChallengeSubmitRequest {
+    pub identity: String,
+    pub public_key: Vec<u8>,
+    pub signature: Vec<u8>,
+}
+
 /// A successful authorization message.
 ///
-/// This message is returned after either a TrustResponse has been returned by the remote
-/// connection.
+/// This message is returned after either a TrustRequest or a ChallengeSubmitRequest has been
+/// accepted from the remote connection.
 #[derive(Debug)]
 pub struct Authorized;
 
@@ -114,6 +145,7 @@ impl FromProto<authorization::ConnectResponse> for ConnectResponse {
                 .iter()
                 .map(|t| match t {
                     TRUST => Ok(AuthorizationType::Trust),
+                    CHALLENGE => Ok(AuthorizationType::Challenge),
                     UNSET_AUTHORIZATION_TYPE => Err(ProtoConversionError::InvalidTypeError(
                         "no authorization type was set".into(),
                     )),
@@ -135,6 +167,9 @@ impl FromNative<ConnectResponse> for authorization::ConnectResponse {
                     AuthorizationType::Trust => {
                         authorization::ConnectResponse_AuthorizationType::TRUST
                     }
+                    AuthorizationType::Challenge => {
+                        authorization::ConnectResponse_AuthorizationType::CHALLENGE
+                    }
                 })
                 .collect(),
         );
@@ -160,6 +195,60 @@ impl FromNative<TrustRequest> for authorization::TrustRequest {
     }
 }
 
+impl FromProto<authorization::ChallengeNonceRequest> for ChallengeNonceRequest {
+    fn from_proto(_: authorization::ChallengeNonceRequest) -> Result<Self, ProtoConversionError> {
+        Ok(ChallengeNonceRequest)
+    }
+}
+
+impl FromNative<ChallengeNonceRequest> for authorization::ChallengeNonceRequest {
+    fn from_native(_: ChallengeNonceRequest) -> Result<Self, ProtoConversionError> {
+        Ok(authorization::ChallengeNonceRequest::new())
+    }
+}
+
+impl FromProto<authorization::ChallengeNonceResponse> for ChallengeNonceResponse {
+    fn from_proto(
+        mut source: authorization::ChallengeNonceResponse,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(Self {
+            nonce: source.take_nonce(),
+        })
+    }
+}
+
+impl FromNative<ChallengeNonceResponse> for authorization::ChallengeNonceResponse {
+    fn from_native(source: ChallengeNonceResponse) -> Result<Self, ProtoConversionError> {
+        let mut response = authorization::ChallengeNonceResponse::new();
+        response.set_nonce(source.nonce);
+
+        Ok(response)
+    }
+}
+
+impl FromProto<authorization::ChallengeSubmitRequest> for ChallengeSubmitRequest {
+    fn from_proto(
+        mut source: authorization::ChallengeSubmitRequest,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(Self {
+            identity: source.take_identity(),
+            public_key: source.take_public_key(),
+            signature: source.take_signature(),
+        })
+    }
+}
+
+impl FromNative<ChallengeSubmitRequest> for authorization::ChallengeSubmitRequest {
+    fn from_native(source: ChallengeSubmitRequest) -> Result<Self, ProtoConversionError> {
+        let mut request = authorization::ChallengeSubmitRequest::new();
+        request.set_identity(source.identity);
+        request.set_public_key(source.public_key);
+        request.set_signature(source.signature);
+
+        Ok(request)
+    }
+}
+
 impl FromProto<authorization::AuthorizedMessage> for Authorized {
     fn from_proto(_: authorization::AuthorizedMessage) -> Result<Self, ProtoConversionError> {
         Ok(Authorized)
@@ -233,6 +322,27 @@ impl FromProto<authorization::AuthorizationMessage> for AuthorizationMessage {
             >::from_bytes(
                 source.get_payload()
             )?)),
+            CHALLENGE_NONCE_REQUEST => {
+                Ok(AuthorizationMessage::ChallengeNonceRequest(FromBytes::<
+                    authorization::ChallengeNonceRequest,
+                >::from_bytes(
+                    source.get_payload(),
+                )?))
+            }
+            CHALLENGE_NONCE_RESPONSE => {
+                Ok(AuthorizationMessage::ChallengeNonceResponse(FromBytes::<
+                    authorization::ChallengeNonceResponse,
+                >::from_bytes(
+                    source.get_payload(),
+                )?))
+            }
+            CHALLENGE_SUBMIT_REQUEST => {
+                Ok(AuthorizationMessage::ChallengeSubmitRequest(FromBytes::<
+                    authorization::ChallengeSubmitRequest,
+                >::from_bytes(
+                    source.get_payload(),
+                )?))
+            }
             UNSET_AUTHORIZATION_MESSAGE_TYPE => Err(ProtoConversionError::InvalidTypeError(
                 "no message type was set".into(),
             )),
@@ -277,6 +387,24 @@ impl FromNative<AuthorizationMessage> for authorization::AuthorizationMessage {
                     payload,
                 )?);
             }
+            AuthorizationMessage::ChallengeNonceRequest(payload) => {
+                message.set_message_type(CHALLENGE_NONCE_REQUEST);
+                message.set_payload(
+                    IntoBytes::<authorization::ChallengeNonceRequest>::into_bytes(payload)?,
+                );
+            }
+            AuthorizationMessage::ChallengeNonceResponse(payload) => {
+                message.set_message_type(CHALLENGE_NONCE_RESPONSE);
+                message.set_payload(
+                    IntoBytes::<authorization::ChallengeNonceResponse>::into_bytes(payload)?,
+                );
+            }
+            AuthorizationMessage::ChallengeSubmitRequest(payload) => {
+                message.set_message_type(CHALLENGE_SUBMIT_REQUEST);
+                message.set_payload(
+                    IntoBytes::<authorization::ChallengeSubmitRequest>::into_bytes(payload)?,
+                );
+            }
         }
         Ok(message)
     }
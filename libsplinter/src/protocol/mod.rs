@@ -59,6 +59,14 @@ pub(crate) const AUTHORIZATION_MAINTENANCE_MIN: u32 = 1;
 pub(crate) const AUTHORIZATION_RBAC_ROLES_MIN: u32 = 1;
 #[cfg(feature = "authorization")]
 pub(crate) const AUTHORIZATION_RBAC_ROLE_MIN: u32 = 1;
+#[cfg(feature = "authorization")]
+pub(crate) const AUTHORIZATION_RBAC_ASSIGNMENTS_MIN: u32 = 1;
+#[cfg(feature = "authorization")]
+pub(crate) const AUTHORIZATION_RBAC_ASSIGNMENT_MIN: u32 = 1;
+#[cfg(feature = "authorization")]
+pub(crate) const AUTHORIZATION_RBAC_GROUPS_MIN: u32 = 1;
+#[cfg(feature = "authorization")]
+pub(crate) const AUTHORIZATION_RBAC_GROUP_MIN: u32 = 1;
 
 #[cfg(feature = "oauth")]
 pub const OAUTH_PROTOCOL_VERSION: u32 = 1;
@@ -77,6 +85,8 @@ pub const REGISTRY_PROTOCOL_VERSION: u32 = 1;
 pub(crate) const REGISTRY_LIST_NODES_MIN: u32 = 1;
 #[cfg(all(feature = "registry", feature = "rest-api-actix"))]
 pub(crate) const REGISTRY_FETCH_NODE_MIN: u32 = 1;
+#[cfg(all(feature = "registry-notifications", feature = "rest-api-actix"))]
+pub(crate) const REGISTRY_WATCH_NODES_MIN: u32 = 1;
 
 #[cfg(any(
     feature = "biome-credentials",
@@ -95,6 +95,31 @@ impl From<InvalidNodeError> for RegistryError {
     }
 }
 
+/// Returned by a `RegistrySubscriber` that could not handle a notification
+#[cfg(feature = "registry-notifications")]
+#[derive(Debug)]
+pub enum RegistrySubscriberError {
+    /// The subscriber failed to handle the notification, but should remain subscribed
+    UnableToHandleNotification(String),
+    /// The subscriber should be removed from the registry
+    Unsubscribe,
+}
+
+#[cfg(feature = "registry-notifications")]
+impl Error for RegistrySubscriberError {}
+
+#[cfg(feature = "registry-notifications")]
+impl fmt::Display for RegistrySubscriberError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RegistrySubscriberError::UnableToHandleNotification(msg) => {
+                write!(f, "Unable to handle notification: {}", msg)
+            }
+            RegistrySubscriberError::Unsubscribe => f.write_str("Unsubscribe"),
+        }
+    }
+}
+
 /// Represents the reason that a node was found to be invalid
 #[derive(Debug)]
 pub enum InvalidNodeError {
@@ -0,0 +1,244 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A memory-backed implementation of the registry traits.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+#[cfg(feature = "registry-notifications")]
+use super::notification::RegistrySubscribers;
+use super::{
+    check_node_required_fields_are_not_empty, InvalidNodeError, MetadataPredicate, Node, NodeIter,
+    RegistryError, RegistryReader, RegistryWriter, RwRegistry,
+};
+#[cfg(feature = "registry-notifications")]
+use super::{RegistryNotification, RegistrySubscriber};
+
+/// A registry that keeps its nodes in memory.
+///
+/// The nodes are lost when the registry is dropped, so this registry is only suitable for testing
+/// and for deployments that do not need to keep nodes across restarts.
+#[derive(Clone, Default)]
+pub struct MemoryRegistry {
+    nodes: Arc<Mutex<BTreeMap<String, Node>>>,
+    #[cfg(feature = "registry-notifications")]
+    subscribers: RegistrySubscribers,
+}
+
+impl MemoryRegistry {
+    /// Constructs a new, empty `MemoryRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock_nodes(&self) -> Result<MutexGuard<BTreeMap<String, Node>>, RegistryError> {
+        self.nodes
+            .lock()
+            .map_err(|_| RegistryError::general_error("Memory registry's internal lock poisoned"))
+    }
+}
+
+impl RegistryReader for MemoryRegistry {
+    fn list_nodes<'a, 'b: 'a>(
+        &'b self,
+        predicates: &'a [MetadataPredicate],
+    ) -> Result<NodeIter<'a>, RegistryError> {
+        let nodes = self
+            .lock_nodes()?
+            .values()
+            .filter(|node| predicates.iter().all(|predicate| predicate.apply(node)))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(nodes.into_iter()))
+    }
+
+    fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError> {
+        Ok(self
+            .lock_nodes()?
+            .values()
+            .filter(|node| predicates.iter().all(|predicate| predicate.apply(node)))
+            .count() as u32)
+    }
+
+    fn fetch_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
+        Ok(self.lock_nodes()?.get(identity).cloned())
+    }
+
+    fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
+        Ok(self.lock_nodes()?.contains_key(identity))
+    }
+
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(&self, subscriber: Box<dyn RegistrySubscriber>) -> Result<(), RegistryError> {
+        self.subscribers.add(subscriber)
+    }
+}
+
+impl RegistryWriter for MemoryRegistry {
+    fn insert_node(&self, node: Node) -> Result<(), RegistryError> {
+        check_node_required_fields_are_not_empty(&node)?;
+
+        let mut nodes = self.lock_nodes()?;
+
+        // As in the database registry, an endpoint may not already be in use by any node,
+        // including the node that is being replaced
+        if let Some(endpoint) = nodes
+            .values()
+            .flat_map(|existing_node| existing_node.endpoints.iter())
+            .find(|endpoint| node.endpoints.contains(endpoint))
+        {
+            return Err(RegistryError::from(InvalidNodeError::DuplicateEndpoint(
+                endpoint.clone(),
+            )));
+        }
+
+        #[cfg(not(feature = "registry-notifications"))]
+        nodes.insert(node.identity.clone(), node);
+
+        #[cfg(feature = "registry-notifications")]
+        {
+            let notification = match nodes.insert(node.identity.clone(), node.clone()) {
+                Some(previous) => RegistryNotification::NodeUpdated { previous, node },
+                None => RegistryNotification::NodeAdded(node),
+            };
+            drop(nodes);
+            self.subscribers.broadcast(&[notification]);
+        }
+
+        Ok(())
+    }
+
+    fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
+        let removed = self.lock_nodes()?.remove(identity);
+
+        #[cfg(feature = "registry-notifications")]
+        {
+            if let Some(node) = &removed {
+                self.subscribers
+                    .broadcast(&[RegistryNotification::NodeRemoved(node.clone())]);
+            }
+        }
+
+        Ok(removed)
+    }
+}
+
+impl RwRegistry for MemoryRegistry {
+    fn clone_box(&self) -> Box<dyn RwRegistry> {
+        Box::new(self.clone())
+    }
+
+    fn clone_box_as_reader(&self) -> Box<dyn RegistryReader> {
+        Box::new(self.clone())
+    }
+
+    fn clone_box_as_writer(&self) -> Box<dyn RegistryWriter> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::registry::tests;
+
+    #[test]
+    fn memory_insert_nodes() {
+        tests::test_insert_nodes(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_fetch_node_not_found() {
+        tests::test_fetch_node_not_found(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_has_node() {
+        tests::test_has_node(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_list_nodes_ok() {
+        tests::test_list_nodes_ok(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_list_nodes_empty_ok() {
+        tests::test_list_nodes_empty_ok(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_list_nodes_filter_metadata_ok() {
+        tests::test_list_nodes_filter_metadata_ok(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_list_nodes_filter_metadata_mutliple() {
+        tests::test_list_nodes_filter_metadata_mutliple(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_list_nodes_filter_empty_ok() {
+        tests::test_list_nodes_filter_empty_ok(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_list_nodes_filter_metadata_not_equal() {
+        tests::test_list_nodes_filter_metadata_not_equal(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_list_nodes_filter_metadata_gt() {
+        tests::test_list_nodes_filter_metadata_gt(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_list_nodes_filter_metadata_ge() {
+        tests::test_list_nodes_filter_metadata_ge(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_list_nodes_filter_metadata_lt() {
+        tests::test_list_nodes_filter_metadata_lt(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_list_nodes_filter_metadata_le() {
+        tests::test_list_nodes_filter_metadata_le(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_delete_node() {
+        tests::test_delete_node(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_count_node() {
+        tests::test_count_node(&MemoryRegistry::new());
+    }
+
+    #[test]
+    fn memory_count_node_metadata() {
+        tests::test_count_node_metadata(&MemoryRegistry::new());
+    }
+
+    #[cfg(feature = "registry-notifications")]
+    #[test]
+    fn memory_notifications() {
+        tests::test_notifications(&MemoryRegistry::new());
+    }
+}
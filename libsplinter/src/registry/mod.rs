@@ -27,6 +27,9 @@
 #[cfg(feature = "registry-database")]
 mod diesel;
 mod error;
+mod memory;
+#[cfg(feature = "registry-notifications")]
+mod notification;
 #[cfg(feature = "rest-api")]
 mod rest_api;
 mod unified;
@@ -37,9 +40,18 @@ use std::iter::ExactSizeIterator;
 
 #[cfg(feature = "registry-database")]
 pub use self::diesel::DieselRegistry;
+#[cfg(feature = "registry-notifications")]
+pub use error::RegistrySubscriberError;
 pub use error::{InvalidNodeError, RegistryError};
+pub use memory::MemoryRegistry;
+#[cfg(feature = "registry-notifications")]
+pub use notification::{RegistryNotification, RegistrySubscriber};
 pub use unified::UnifiedRegistry;
 pub use yaml::LocalYamlRegistry;
+#[cfg(feature = "registry-signatures")]
+pub use yaml::{sign_registry_file, REGISTRY_SIGNATURE_FILE_EXTENSION};
+#[cfg(all(feature = "health", feature = "registry-remote"))]
+pub use yaml::{RemoteYamlRefreshStatus, RemoteYamlRefreshStatusHandle};
 #[cfg(feature = "registry-remote")]
 pub use yaml::{RemoteYamlRegistry, RemoteYamlShutdownHandle};
 
@@ -252,6 +264,24 @@ pub trait RegistryReader: Send + Sync {
     fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
         self.fetch_node(identity).map(|opt| opt.is_some())
     }
+
+    /// Adds a subscriber that is notified of every node that is added to, updated in, or removed
+    /// from the registry.
+    ///
+    /// Returns an error if the registry does not support notifications.
+    ///
+    /// # Arguments
+    ///
+    ///  * `subscriber` - The subscriber to notify.
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(
+        &self,
+        _subscriber: Box<dyn RegistrySubscriber>,
+    ) -> Result<(), RegistryError> {
+        Err(RegistryError::general_error(
+            "Registry does not support notifications",
+        ))
+    }
 }
 
 /// Defines registry write capabilities.
@@ -320,6 +350,11 @@ where
     fn has_node(&self, identity: &str) -> Result<bool, RegistryError> {
         (**self).has_node(identity)
     }
+
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(&self, subscriber: Box<dyn RegistrySubscriber>) -> Result<(), RegistryError> {
+        (**self).add_subscriber(subscriber)
+    }
 }
 
 impl<NW> RegistryWriter for Box<NW>
@@ -384,7 +419,7 @@ fn check_if_node_is_duplicate(
 }
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
     use super::*;
 
     /// Verify that the `NodeBuilder` properly constructs a new `Node` when just the minimum values
@@ -719,4 +754,471 @@ mod tests {
         };
         assert!(validate_nodes(&[node1, node2, valid_node3]).is_ok());
     }
+
+    ///  Test that a new node can be inserted into the registry and fetched
+    ///
+    /// 1. Insert node 1
+    /// 2. Validate that the node can be fetched correctly from state
+    /// 3. Try to insert the node again with same endpoints, should fail
+    pub fn test_insert_nodes(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        let node = registry
+            .fetch_node(&get_node_1().identity)
+            .expect("Failed to fetch node")
+            .expect("Node not found");
+
+        assert_eq!(node, get_node_1());
+
+        if registry.insert_node(get_node_1()).is_ok() {
+            panic!("Should have returned an error because of duplicate endpoint")
+        }
+    }
+
+    ///  Test that a new node can be inserted into the registry and fetched
+    ///
+    /// 1. Insert node 1 and 2
+    /// 2. Try to fetch that does not exist
+    pub fn test_fetch_node_not_found(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+
+        assert_eq!(
+            registry
+                .fetch_node("DoesNotExist")
+                .expect("Failed to fetch node"),
+            None
+        )
+    }
+
+    /// Verifies that `has_node` properly determines if a node exists in the registry.
+    ///
+    /// 1. Insert node 1
+    /// 2. Validate that the registry has node 1 but not node 2
+    pub fn test_has_node(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+
+        assert!(registry
+            .has_node(&get_node_1().identity)
+            .expect("Failed to check if node1 exists"));
+        assert!(!registry
+            .has_node(&get_node_2().identity)
+            .expect("Failed to check if node2 exists"));
+    }
+
+    /// Verifies that list_nodes returns a list of nodes.
+    ///
+    /// 1. Insert node 1 and 2
+    /// 2. Validate that the registry returns both nodes in the list
+    pub fn test_list_nodes_ok(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+
+        let nodes = registry
+            .list_nodes(&[])
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0], get_node_1());
+        assert_eq!(nodes[1], get_node_2());
+    }
+
+    /// Verifies that list_nodes returns an empty list when there are no nodes in the registry.
+    ///
+    /// 1. Validate that the registry returns an empty list
+    pub fn test_list_nodes_empty_ok(registry: &dyn RwRegistry) {
+        let nodes = registry
+            .list_nodes(&[])
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+        assert_eq!(nodes.len(), 0);
+    }
+
+    /// Verifies that list_nodes returns the correct items when it is filtered by metadata.
+    ///
+    /// 1. Insert node 1 and 2
+    /// 2. Validate that the registry returns only node 2 when filtered by company
+    pub fn test_list_nodes_filter_metadata_ok(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+
+        let filter = vec![MetadataPredicate::Eq(
+            "company".into(),
+            get_node_2().metadata.get("company").unwrap().to_string(),
+        )];
+
+        let nodes = registry
+            .list_nodes(&filter)
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0], get_node_2());
+    }
+
+    /// Verifies that list_nodes returns the correct items when it is filtered by multiple
+    /// metadata fields.
+    ///
+    /// 1. Insert node 1, 2 and 3
+    /// 2. Validate that the registry returns only node 3 when filtered by company and admin
+    pub fn test_list_nodes_filter_metadata_mutliple(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_3())
+            .expect("Unable to insert node");
+
+        let filter = vec![
+            MetadataPredicate::Eq(
+                "company".to_string(),
+                get_node_3().metadata.get("company").unwrap().to_string(),
+            ),
+            MetadataPredicate::Eq(
+                "admin".to_string(),
+                get_node_3().metadata.get("admin").unwrap().to_string(),
+            ),
+        ];
+
+        let nodes = registry
+            .list_nodes(&filter)
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0], get_node_3());
+    }
+
+    /// Verifies that list_nodes returns an empty list when no nodes fits the filtering criteria.
+    ///
+    ///
+    /// 1. Insert node 1, and
+    /// 2. Validate that the registry returns an empty list
+    pub fn test_list_nodes_filter_empty_ok(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+
+        let filter = vec![MetadataPredicate::Eq(
+            "admin".to_string(),
+            get_node_3().metadata.get("admin").unwrap().to_string(),
+        )];
+
+        let nodes = registry
+            .list_nodes(&filter)
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 0);
+    }
+
+    /// Verifies that list_nodes returns the correct items when it is filtered by metadata.
+    ///
+    /// 1. Insert node 1 and 2
+    /// 2. Validate that the registry returns only node 1 when filtered by company
+    pub fn test_list_nodes_filter_metadata_not_equal(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+
+        let filter = vec![MetadataPredicate::Ne(
+            "company".into(),
+            get_node_2().metadata.get("company").unwrap().to_string(),
+        )];
+
+        let nodes = registry
+            .list_nodes(&filter)
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0], get_node_1());
+    }
+
+    /// Verifies that list_nodes returns the correct items when it is filtered by metadata.
+    ///
+    /// 1. Insert node 1 and 2
+    /// 2. Validate that the registry returns only node 2 when filtered by gt admin Bob
+    pub fn test_list_nodes_filter_metadata_gt(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+
+        let filter = vec![MetadataPredicate::Gt(
+            "admin".into(),
+            get_node_1().metadata.get("admin").unwrap().to_string(),
+        )];
+
+        let nodes = registry
+            .list_nodes(&filter)
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0], get_node_2());
+    }
+
+    /// Verifies that list_nodes returns the correct items when it is filtered by metadata.
+    ///
+    /// 1. Insert node 1, 2, and 3
+    /// 2. Validate that the registry returns node 2 and 3 when filtered by ge admin Carol
+    pub fn test_list_nodes_filter_metadata_ge(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_3())
+            .expect("Unable to insert node");
+
+        let filter = vec![MetadataPredicate::Ge(
+            "admin".into(),
+            get_node_2().metadata.get("admin").unwrap().to_string(),
+        )];
+
+        let nodes = registry
+            .list_nodes(&filter)
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes, [get_node_2(), get_node_3()]);
+    }
+
+    /// Verifies that list_nodes returns the correct items when it is filtered by metadata.
+    ///
+    /// 1. Insert node 1 and 2
+    /// 2. Validate that the registry returns only node 1 when filtered by lt admin Carol
+    pub fn test_list_nodes_filter_metadata_lt(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+
+        let filter = vec![MetadataPredicate::Lt(
+            "admin".into(),
+            get_node_2().metadata.get("admin").unwrap().to_string(),
+        )];
+
+        let nodes = registry
+            .list_nodes(&filter)
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0], get_node_1());
+    }
+
+    /// Verifies that list_nodes returns the correct items when it is filtered by metadata.
+    ///
+    /// 1. Insert node 1, 2, and 3
+    /// 2. Validate that the registry returns node 1 and 2 when filtered by le admin Carol
+    pub fn test_list_nodes_filter_metadata_le(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_3())
+            .expect("Unable to insert node");
+
+        let filter = vec![MetadataPredicate::Le(
+            "admin".into(),
+            get_node_2().metadata.get("admin").unwrap().to_string(),
+        )];
+
+        let nodes = registry
+            .list_nodes(&filter)
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes, [get_node_1(), get_node_2()]);
+    }
+
+    /// Verifies that delete_nodes removes the required node
+    ///
+    /// 1. Insert node 1, 2, and 3
+    /// 2. Delete node 2
+    /// 3. Verify that only node 1 and 3 are returned from list
+    pub fn test_delete_node(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_3())
+            .expect("Unable to insert node");
+
+        registry
+            .delete_node("Node-456")
+            .expect("Unable to delete node");
+
+        let nodes = registry
+            .list_nodes(&[])
+            .expect("Failed to retrieve nodes")
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes, [get_node_1(), get_node_3()]);
+    }
+
+    /// Verifies that count_nodes returns the correct number of nodes
+    ///
+    /// 1. Insert node 1, 2, and 3
+    /// 3. Verify that the registry count_nodes returns 3
+    pub fn test_count_node(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_3())
+            .expect("Unable to insert node");
+
+        let count = registry.count_nodes(&[]).expect("Failed to retrieve nodes");
+
+        assert_eq!(count, 3);
+    }
+
+    /// Verifies that count_nodes returns the correct number of nodes when filtered with metadata
+    ///
+    /// 1. Insert node 1, 2, and 3
+    /// 3. Verify that the registry count_nodes returns 2 when filtered by company Cargill
+    pub fn test_count_node_metadata(registry: &dyn RwRegistry) {
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_2())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(get_node_3())
+            .expect("Unable to insert node");
+
+        let filter = vec![MetadataPredicate::Eq(
+            "company".into(),
+            get_node_2().metadata.get("company").unwrap().to_string(),
+        )];
+
+        let count = registry
+            .count_nodes(&filter)
+            .expect("Failed to retrieve nodes");
+
+        assert_eq!(count, 2);
+    }
+
+    /// Verifies that the registry notifies its subscribers of every change to its nodes
+    ///
+    /// 1. Add a subscriber to the registry
+    /// 2. Insert node 1, replace it with a copy that has a different endpoint, and delete it
+    /// 3. Verify that the subscriber was notified of the addition, the update and the removal, in
+    ///    that order
+    #[cfg(feature = "registry-notifications")]
+    pub fn test_notifications(registry: &dyn RwRegistry) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        registry
+            .add_subscriber(Box::new(move |notification: &RegistryNotification| {
+                sender
+                    .send(notification.clone())
+                    .map_err(|_| RegistrySubscriberError::Unsubscribe)
+            }))
+            .expect("Unable to add subscriber");
+
+        let mut updated_node = get_node_1();
+        updated_node.endpoints = vec!["tcps://12.0.0.123:8432".into()];
+
+        registry
+            .insert_node(get_node_1())
+            .expect("Unable to insert node");
+        registry
+            .insert_node(updated_node.clone())
+            .expect("Unable to update node");
+        registry
+            .delete_node(&get_node_1().identity)
+            .expect("Unable to delete node");
+
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![
+                RegistryNotification::NodeAdded(get_node_1()),
+                RegistryNotification::NodeUpdated {
+                    previous: get_node_1(),
+                    node: updated_node.clone(),
+                },
+                RegistryNotification::NodeRemoved(updated_node),
+            ]
+        );
+    }
+
+    fn get_node_1() -> Node {
+        Node::builder("Node-123")
+            .with_endpoint("tcps://12.0.0.123:8431")
+            .with_display_name("Bitwise IO - Node 1")
+            .with_key("abcd")
+            .with_metadata("company", "Bitwise IO")
+            .with_metadata("admin", "Bob")
+            .build()
+            .expect("Failed to build node1")
+    }
+
+    fn get_node_2() -> Node {
+        Node::builder("Node-456")
+            .with_endpoint("tcps://12.0.0.123:8434")
+            .with_display_name("Cargill - Node 1")
+            .with_key("0123")
+            .with_metadata("company", "Cargill")
+            .with_metadata("admin", "Carol")
+            .build()
+            .expect("Failed to build node2")
+    }
+
+    fn get_node_3() -> Node {
+        Node::builder("Node-789")
+            .with_endpoint("tcps://12.0.0.123:8435")
+            .with_display_name("Cargill - Node 2")
+            .with_key("4567")
+            .with_metadata("company", "Cargill")
+            .with_metadata("admin", "Charlie")
+            .build()
+            .expect("Failed to build node3")
+    }
 }
@@ -0,0 +1,241 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Notifications about changes to the nodes in a registry.
+//!
+//! Registries that support notifications accept a [`RegistrySubscriber`] through
+//! [`RegistryReader::add_subscriber`] and pass it a [`RegistryNotification`] for every node that
+//! is added to, updated in, or removed from the registry.
+//!
+//! [`RegistryNotification`]: enum.RegistryNotification.html
+//! [`RegistryReader::add_subscriber`]: trait.RegistryReader.html#method.add_subscriber
+//! [`RegistrySubscriber`]: trait.RegistrySubscriber.html
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::{Node, RegistryError, RegistrySubscriberError};
+
+/// A change to a node in a registry.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "eventType", content = "message")]
+pub enum RegistryNotification {
+    /// A node that was not in the registry has been added.
+    NodeAdded(Node),
+    /// A node in the registry has been replaced; includes the previous and the current definition
+    /// of the node.
+    NodeUpdated { previous: Node, node: Node },
+    /// A node has been removed from the registry; includes the removed node.
+    NodeRemoved(Node),
+}
+
+impl RegistryNotification {
+    /// Returns the identity of the node that the notification is about.
+    pub fn identity(&self) -> &str {
+        match self {
+            RegistryNotification::NodeAdded(node) => &node.identity,
+            RegistryNotification::NodeUpdated { node, .. } => &node.identity,
+            RegistryNotification::NodeRemoved(node) => &node.identity,
+        }
+    }
+}
+
+/// Receives the notifications of a registry.
+///
+/// Subscribers are called while the registry applies the change, so they must not read from or
+/// write to the registry that notifies them.
+pub trait RegistrySubscriber: Send {
+    /// Handles a single notification. Returning `RegistrySubscriberError::Unsubscribe` removes
+    /// the subscriber from the registry.
+    fn handle_notification(
+        &self,
+        notification: &RegistryNotification,
+    ) -> Result<(), RegistrySubscriberError>;
+}
+
+impl<F> RegistrySubscriber for F
+where
+    F: Fn(&RegistryNotification) -> Result<(), RegistrySubscriberError> + Send,
+{
+    fn handle_notification(
+        &self,
+        notification: &RegistryNotification,
+    ) -> Result<(), RegistrySubscriberError> {
+        (*self)(notification)
+    }
+}
+
+/// The subscribers of a registry.
+///
+/// Clones share the same subscribers, so a registry and its clones notify the same subscribers.
+#[derive(Clone, Default)]
+pub(super) struct RegistrySubscribers {
+    subscribers: Arc<Mutex<Vec<Box<dyn RegistrySubscriber>>>>,
+}
+
+impl RegistrySubscribers {
+    pub fn add(&self, subscriber: Box<dyn RegistrySubscriber>) -> Result<(), RegistryError> {
+        self.subscribers
+            .lock()
+            .map_err(|_| RegistryError::general_error("Registry subscribers lock poisoned"))?
+            .push(subscriber);
+        Ok(())
+    }
+
+    /// Passes each of the notifications to every subscriber, dropping the subscribers that ask to
+    /// be unsubscribed.
+    pub fn broadcast(&self, notifications: &[RegistryNotification]) {
+        if notifications.is_empty() {
+            return;
+        }
+
+        let mut subscribers = match self.subscribers.lock() {
+            Ok(subscribers) => subscribers,
+            Err(_) => {
+                error!("Registry subscribers lock poisoned; dropping notifications");
+                return;
+            }
+        };
+
+        for notification in notifications {
+            subscribers.retain(
+                |subscriber| match subscriber.handle_notification(notification) {
+                    Ok(()) => true,
+                    Err(RegistrySubscriberError::Unsubscribe) => false,
+                    Err(RegistrySubscriberError::UnableToHandleNotification(msg)) => {
+                        error!(
+                            "Registry subscriber unable to handle notification for node {}: {}",
+                            notification.identity(),
+                            msg
+                        );
+                        true
+                    }
+                },
+            );
+        }
+    }
+}
+
+/// Compares two snapshots of a registry's nodes and returns the notifications that describe the
+/// changes between them.
+pub(super) fn diff_nodes(previous: &[Node], current: &[Node]) -> Vec<RegistryNotification> {
+    let mut previous_nodes = previous
+        .iter()
+        .map(|node| (node.identity.as_str(), node))
+        .collect::<HashMap<_, _>>();
+
+    let mut notifications = current
+        .iter()
+        .filter_map(|node| match previous_nodes.remove(node.identity.as_str()) {
+            None => Some(RegistryNotification::NodeAdded(node.clone())),
+            Some(previous_node) if previous_node != node => {
+                Some(RegistryNotification::NodeUpdated {
+                    previous: previous_node.clone(),
+                    node: node.clone(),
+                })
+            }
+            Some(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    // Whatever is left in the previous snapshot is no longer in the registry
+    notifications.extend(
+        previous
+            .iter()
+            .filter(|node| previous_nodes.contains_key(node.identity.as_str()))
+            .map(|node| RegistryNotification::NodeRemoved(node.clone())),
+    );
+
+    notifications
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that `diff_nodes` reports added, updated and removed nodes, and ignores nodes that
+    /// have not changed.
+    #[test]
+    fn diff_nodes_reports_changes() {
+        let unchanged = node("unchanged", "tcps://127.0.0.1:8000");
+        let updated = node("updated", "tcps://127.0.0.1:8001");
+        let moved = node("updated", "tcps://127.0.0.1:8011");
+        let removed = node("removed", "tcps://127.0.0.1:8002");
+        let added = node("added", "tcps://127.0.0.1:8003");
+
+        let notifications = diff_nodes(
+            &[unchanged.clone(), updated.clone(), removed.clone()],
+            &[unchanged, moved.clone(), added.clone()],
+        );
+
+        assert_eq!(
+            notifications,
+            vec![
+                RegistryNotification::NodeUpdated {
+                    previous: updated,
+                    node: moved,
+                },
+                RegistryNotification::NodeAdded(added),
+                RegistryNotification::NodeRemoved(removed),
+            ]
+        );
+    }
+
+    /// Verifies that a subscriber that asks to be unsubscribed no longer receives notifications,
+    /// while the remaining subscribers do.
+    #[test]
+    fn unsubscribed_subscriber_is_dropped() {
+        let subscribers = RegistrySubscribers::default();
+
+        let received = Arc::new(Mutex::new(vec![]));
+        let subscriber_received = received.clone();
+        subscribers
+            .add(Box::new(move |notification: &RegistryNotification| {
+                subscriber_received
+                    .lock()
+                    .expect("Received lock poisoned")
+                    .push(notification.clone());
+                Ok(())
+            }))
+            .expect("Unable to add subscriber");
+
+        let unsubscribed_calls = Arc::new(Mutex::new(0));
+        let subscriber_calls = unsubscribed_calls.clone();
+        subscribers
+            .add(Box::new(move |_: &RegistryNotification| {
+                *subscriber_calls.lock().expect("Calls lock poisoned") += 1;
+                Err(RegistrySubscriberError::Unsubscribe)
+            }))
+            .expect("Unable to add subscriber");
+
+        let first = RegistryNotification::NodeAdded(node("first", "tcps://127.0.0.1:8000"));
+        let second = RegistryNotification::NodeAdded(node("second", "tcps://127.0.0.1:8001"));
+        subscribers.broadcast(&[first.clone()]);
+        subscribers.broadcast(&[second.clone()]);
+
+        assert_eq!(
+            *received.lock().expect("Received lock poisoned"),
+            vec![first, second]
+        );
+        assert_eq!(*unsubscribed_calls.lock().expect("Calls lock poisoned"), 1);
+    }
+
+    fn node(identity: &str, endpoint: &str) -> Node {
+        Node::builder(identity)
+            .with_endpoint(endpoint)
+            .with_key("abcd")
+            .build()
+            .expect("Failed to build node")
+    }
+}
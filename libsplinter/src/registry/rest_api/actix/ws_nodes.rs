@@ -0,0 +1,282 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoint:
+//!
+//! * `GET /ws/registry/nodes` for watching the nodes in the registry over a websocket
+
+use std::sync::{Arc, Mutex};
+
+use crate::actix_web::{Error, HttpResponse};
+use crate::futures::{future::IntoFuture, Future};
+use crate::protocol;
+#[cfg(feature = "authorization")]
+use crate::registry::rest_api::REGISTRY_READ_PERMISSION;
+use crate::registry::{
+    Node, RegistryNotification, RegistrySubscriber, RegistrySubscriberError, RwRegistry,
+};
+use crate::rest_api::actix_web_1::{
+    new_websocket_event_sender, EventSender, Method, ProtocolVersionRangeGuard, Request, Resource,
+};
+
+pub fn make_nodes_watch_resource(registry: Box<dyn RwRegistry>) -> Resource {
+    let resource =
+        Resource::build("/ws/registry/nodes").add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::REGISTRY_WATCH_NODES_MIN,
+            protocol::REGISTRY_PROTOCOL_VERSION,
+        ));
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, REGISTRY_READ_PERMISSION, move |r, p| {
+            watch_nodes(Request::from((r, p)), &*registry)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, p| {
+            watch_nodes(Request::from((r, p)), &*registry)
+        })
+    }
+}
+
+/// Starts a websocket that receives a `NodeAdded` notification for each node that is currently
+/// in the registry, followed by a notification for every subsequent change to the registry's
+/// nodes.
+///
+/// The subscriber is added before the nodes are listed, so no change is missed between the two.
+/// The notifications received while the nodes are listed are reconciled with the list, so that a
+/// change is not sent again if the list already includes it.
+fn watch_nodes(
+    request: Request,
+    registry: &dyn RwRegistry,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let state = Arc::new(Mutex::new(WatchState::Buffering(vec![])));
+    if let Err(err) = registry.add_subscriber(Box::new(WsRegistrySubscriber {
+        state: state.clone(),
+    })) {
+        error!("Unable to add registry subscriber: {}", err);
+        return Box::new(HttpResponse::InternalServerError().finish().into_future());
+    }
+
+    // The registry notifies subscribers while holding its own lock, so the state must not be
+    // locked while the nodes are listed.
+    let nodes = registry
+        .list_nodes(&[])
+        .map(|nodes| nodes.collect::<Vec<_>>());
+
+    let mut state = match state.lock() {
+        Ok(state) => state,
+        Err(_) => {
+            error!("Registry watch state lock was poisoned");
+            return Box::new(HttpResponse::InternalServerError().finish().into_future());
+        }
+    };
+
+    let nodes = match nodes {
+        Ok(nodes) => nodes,
+        Err(err) => {
+            error!("Unable to list the nodes in the registry: {}", err);
+            *state = WatchState::Closed;
+            return Box::new(HttpResponse::InternalServerError().finish().into_future());
+        }
+    };
+
+    let buffered = match std::mem::replace(&mut *state, WatchState::Closed) {
+        WatchState::Buffering(buffered) => buffered,
+        _ => vec![],
+    };
+    let initial_notifications = initial_notifications(nodes, buffered);
+
+    match new_websocket_event_sender(request, Box::new(initial_notifications.into_iter())) {
+        Ok((sender, res)) => {
+            *state = WatchState::Sending(sender);
+            debug!("Websocket response: {:?}", res);
+            Box::new(res.into_future())
+        }
+        Err(err) => {
+            debug!("Failed to create websocket: {:?}", err);
+            Box::new(HttpResponse::InternalServerError().finish().into_future())
+        }
+    }
+}
+
+/// Returns the notifications that bring a watcher from an empty registry to the current nodes:
+/// a `NodeAdded` notification for each listed node, followed by the changes in the buffered
+/// notifications that the list does not include.
+///
+/// The buffered notifications may have been received before or after the nodes were listed, so
+/// only the last notification for each node is considered, and it is skipped if the list already
+/// has the node in the state it results in.
+fn initial_notifications(
+    nodes: Vec<Node>,
+    buffered: Vec<RegistryNotification>,
+) -> Vec<RegistryNotification> {
+    let mut last_notifications: Vec<RegistryNotification> = vec![];
+    for notification in buffered {
+        last_notifications.retain(|last| last.identity() != notification.identity());
+        last_notifications.push(notification);
+    }
+
+    let changes = last_notifications
+        .into_iter()
+        .filter_map(|notification| {
+            let listed = nodes
+                .iter()
+                .find(|node| node.identity == notification.identity());
+            match (notification, listed) {
+                (RegistryNotification::NodeRemoved(_), None) => None,
+                (RegistryNotification::NodeRemoved(node), Some(_)) => {
+                    Some(RegistryNotification::NodeRemoved(node))
+                }
+                (RegistryNotification::NodeAdded(node), None)
+                | (RegistryNotification::NodeUpdated { node, .. }, None) => {
+                    Some(RegistryNotification::NodeAdded(node))
+                }
+                (RegistryNotification::NodeAdded(node), Some(listed))
+                | (RegistryNotification::NodeUpdated { node, .. }, Some(listed)) => {
+                    if &node == listed {
+                        None
+                    } else {
+                        Some(RegistryNotification::NodeUpdated {
+                            previous: listed.clone(),
+                            node,
+                        })
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    nodes
+        .into_iter()
+        .map(RegistryNotification::NodeAdded)
+        .chain(changes)
+        .collect()
+}
+
+/// Where a watcher's notifications go
+enum WatchState {
+    /// The websocket has not been started yet, so notifications are buffered
+    Buffering(Vec<RegistryNotification>),
+    /// Notifications are sent to the websocket
+    Sending(EventSender<RegistryNotification>),
+    /// The websocket could not be started or has been closed
+    Closed,
+}
+
+struct WsRegistrySubscriber {
+    state: Arc<Mutex<WatchState>>,
+}
+
+impl RegistrySubscriber for WsRegistrySubscriber {
+    fn handle_notification(
+        &self,
+        notification: &RegistryNotification,
+    ) -> Result<(), RegistrySubscriberError> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| RegistrySubscriberError::Unsubscribe)?;
+        match &mut *state {
+            WatchState::Buffering(buffered) => {
+                buffered.push(notification.clone());
+                Ok(())
+            }
+            WatchState::Sending(sender) => sender.send(notification.clone()).map_err(|_| {
+                debug!(
+                    "Dropping registry notification and unsubscribing due to websocket being \
+                     closed"
+                );
+                RegistrySubscriberError::Unsubscribe
+            }),
+            WatchState::Closed => Err(RegistrySubscriberError::Unsubscribe),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(identity: &str, endpoint: &str) -> Node {
+        Node::builder(identity)
+            .with_endpoint(endpoint)
+            .with_display_name(identity)
+            .with_key("abcd")
+            .build()
+            .expect("Failed to build node")
+    }
+
+    /// Verifies that the listed nodes are added, and that buffered notifications the list
+    /// already includes are not sent again.
+    #[test]
+    fn initial_notifications_skip_listed_changes() {
+        let node_1 = node("node-1", "tcps://127.0.0.1:8080");
+        let node_2 = node("node-2", "tcps://127.0.0.1:8081");
+        let removed = node("node-3", "tcps://127.0.0.1:8082");
+
+        let notifications = initial_notifications(
+            vec![node_1.clone(), node_2.clone()],
+            vec![
+                RegistryNotification::NodeAdded(node_1.clone()),
+                RegistryNotification::NodeAdded(removed.clone()),
+                RegistryNotification::NodeRemoved(removed),
+            ],
+        );
+
+        assert_eq!(
+            notifications,
+            vec![
+                RegistryNotification::NodeAdded(node_1),
+                RegistryNotification::NodeAdded(node_2),
+            ]
+        );
+    }
+
+    /// Verifies that buffered notifications the list does not include yet are sent after the
+    /// listed nodes, and that only the last change to each node is sent.
+    #[test]
+    fn initial_notifications_send_later_changes() {
+        let node_1 = node("node-1", "tcps://127.0.0.1:8080");
+        let node_1_updated = node("node-1", "tcps://127.0.0.1:9080");
+        let node_2 = node("node-2", "tcps://127.0.0.1:8081");
+        let node_3 = node("node-3", "tcps://127.0.0.1:8082");
+
+        let notifications = initial_notifications(
+            vec![node_1.clone(), node_2.clone()],
+            vec![
+                RegistryNotification::NodeAdded(node_3.clone()),
+                RegistryNotification::NodeUpdated {
+                    previous: node_1.clone(),
+                    node: node_1_updated.clone(),
+                },
+                RegistryNotification::NodeRemoved(node_2.clone()),
+            ],
+        );
+
+        assert_eq!(
+            notifications,
+            vec![
+                RegistryNotification::NodeAdded(node_1.clone()),
+                RegistryNotification::NodeAdded(node_2.clone()),
+                RegistryNotification::NodeAdded(node_3),
+                RegistryNotification::NodeUpdated {
+                    previous: node_1,
+                    node: node_1_updated,
+                },
+                RegistryNotification::NodeRemoved(node_2),
+            ]
+        );
+    }
+}
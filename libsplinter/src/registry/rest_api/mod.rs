@@ -36,6 +36,8 @@ const REGISTRY_WRITE_PERMISSION: Permission = Permission::Check("registry.write"
 /// * `GET /registry/nodes/{identity}` - Fetch a specific node in the registry
 /// * `PUT /registry/nodes/{identity}` - Replace a node in the registry
 /// * `DELETE /registry/nodes/{identity}` - Delete a node from the registry
+/// * `GET /ws/registry/nodes` - Watch the nodes in the registry over a websocket (requires the
+///   `registry-notifications` feature)
 ///
 /// These endpoints are only available if the following REST API backend feature is enabled:
 ///
@@ -53,6 +55,9 @@ impl RestResourceProvider for dyn RwRegistry {
                 actix::nodes_identity::make_nodes_identity_resource(self.clone_box()),
                 actix::nodes::make_nodes_resource(self.clone_box()),
             ]);
+
+            #[cfg(feature = "registry-notifications")]
+            resources.push(actix::ws_nodes::make_nodes_watch_resource(self.clone_box()));
         }
 
         resources
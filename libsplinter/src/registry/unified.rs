@@ -22,10 +22,16 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+#[cfg(feature = "registry-notifications")]
+use std::sync::Mutex;
 
+#[cfg(feature = "registry-notifications")]
+use super::notification::RegistrySubscribers;
 use super::{
     MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader, RegistryWriter, RwRegistry,
 };
+#[cfg(feature = "registry-notifications")]
+use super::{RegistryNotification, RegistrySubscriber, RegistrySubscriberError};
 
 /// A registry with multiple sources.
 ///
@@ -61,6 +67,13 @@ use super::{
 /// If the same metadata key is set for the node in different registires, the value for that key
 /// from the highest-precedence registry will be used.
 ///
+/// # Notifications
+///
+/// The `UnifiedRegistry` subscribes to all of its source registries that support notifications.
+/// Its own subscribers are notified when the unified view of a node changes, so a change to a
+/// node in a source registry that is hidden by a higher-precedence definition of the node only
+/// results in a notification if it changes the node's merged metadata.
+///
 /// [`RegistryReader`]: ../trait.RegistryReader.html
 /// [`RegistryWriter`]: ../trait.RegistryWriter.html
 /// [`RwRegistry`]: ../trait.RwRegistry.html
@@ -72,6 +85,8 @@ use super::{
 pub struct UnifiedRegistry {
     internal_source: Arc<dyn RwRegistry>,
     external_sources: Vec<Arc<dyn RegistryReader>>,
+    #[cfg(feature = "registry-notifications")]
+    subscribers: RegistrySubscribers,
 }
 
 impl UnifiedRegistry {
@@ -81,9 +96,55 @@ impl UnifiedRegistry {
         internal_source: Box<dyn RwRegistry>,
         external_sources: Vec<Box<dyn RegistryReader>>,
     ) -> Self {
-        Self {
+        let registry = Self {
             internal_source: internal_source.into(),
             external_sources: external_sources.into_iter().map(Arc::from).collect(),
+            #[cfg(feature = "registry-notifications")]
+            subscribers: RegistrySubscribers::default(),
+        };
+
+        #[cfg(feature = "registry-notifications")]
+        registry.subscribe_to_sources();
+
+        registry
+    }
+
+    /// Subscribes to the notifications of all sources (in descending order of precedence), keeping
+    /// a snapshot of each source's nodes to determine how a change affects the unified view.
+    #[cfg(feature = "registry-notifications")]
+    fn subscribe_to_sources(&self) {
+        let sources = std::iter::once(&*self.internal_source as &dyn RegistryReader)
+            .chain(self.external_sources.iter().map(|source| &**source))
+            .collect::<Vec<_>>();
+
+        let snapshots = Arc::new(Mutex::new(
+            sources
+                .iter()
+                .map(|source| {
+                    source
+                        .list_nodes(&[])
+                        .map(|nodes| {
+                            nodes
+                                .map(|node| (node.identity.clone(), node))
+                                .collect::<HashMap<_, _>>()
+                        })
+                        .unwrap_or_else(|err| {
+                            debug!("Failed to list nodes in source registry: {}", err);
+                            HashMap::new()
+                        })
+                })
+                .collect::<Vec<_>>(),
+        ));
+
+        for (index, source) in sources.into_iter().enumerate() {
+            let subscriber = SourceSubscriber {
+                index,
+                snapshots: snapshots.clone(),
+                subscribers: self.subscribers.clone(),
+            };
+            if let Err(err) = source.add_subscriber(Box::new(subscriber)) {
+                debug!("Failed to subscribe to source registry: {}", err);
+            }
         }
     }
 
@@ -197,6 +258,11 @@ impl RegistryReader for UnifiedRegistry {
                 })
             }))
     }
+
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(&self, subscriber: Box<dyn RegistrySubscriber>) -> Result<(), RegistryError> {
+        self.subscribers.add(subscriber)
+    }
 }
 
 impl RegistryWriter for UnifiedRegistry {
@@ -223,6 +289,77 @@ impl RwRegistry for UnifiedRegistry {
     }
 }
 
+/// Subscribed to a single source of a `UnifiedRegistry`; translates the source's notifications
+/// into notifications about the unified view of the nodes.
+#[cfg(feature = "registry-notifications")]
+struct SourceSubscriber {
+    index: usize,
+    snapshots: Arc<Mutex<Vec<HashMap<String, Node>>>>,
+    subscribers: RegistrySubscribers,
+}
+
+#[cfg(feature = "registry-notifications")]
+impl RegistrySubscriber for SourceSubscriber {
+    fn handle_notification(
+        &self,
+        notification: &RegistryNotification,
+    ) -> Result<(), RegistrySubscriberError> {
+        let mut snapshots = self.snapshots.lock().map_err(|_| {
+            RegistrySubscriberError::UnableToHandleNotification(
+                "Unified registry's snapshot lock poisoned".into(),
+            )
+        })?;
+
+        let identity = notification.identity();
+        let previous = merge_node(&snapshots, identity);
+
+        if let Some(nodes) = snapshots.get_mut(self.index) {
+            match notification {
+                RegistryNotification::NodeAdded(node)
+                | RegistryNotification::NodeUpdated { node, .. } => {
+                    nodes.insert(identity.to_string(), node.clone());
+                }
+                RegistryNotification::NodeRemoved(_) => {
+                    nodes.remove(identity);
+                }
+            }
+        }
+
+        let unified_notification = match (previous, merge_node(&snapshots, identity)) {
+            (None, Some(node)) => RegistryNotification::NodeAdded(node),
+            (Some(previous), Some(node)) if previous != node => {
+                RegistryNotification::NodeUpdated { previous, node }
+            }
+            (Some(node), None) => RegistryNotification::NodeRemoved(node),
+            _ => return Ok(()),
+        };
+
+        // Broadcast while holding the snapshot lock, so notifications from different sources are
+        // delivered in the order they were applied
+        self.subscribers.broadcast(&[unified_notification]);
+
+        Ok(())
+    }
+}
+
+/// Gets the unified definition of a node from the snapshots of the sources (in descending order
+/// of precedence), merging its metadata the same way `fetch_node` does.
+#[cfg(feature = "registry-notifications")]
+fn merge_node(snapshots: &[HashMap<String, Node>], identity: &str) -> Option<Node> {
+    snapshots
+        .iter()
+        .rev()
+        .filter_map(|nodes| nodes.get(identity).cloned())
+        .fold(None, |final_opt, mut node| {
+            if let Some(existing) = final_opt {
+                let mut merged_metadata = existing.metadata;
+                merged_metadata.extend(node.metadata);
+                node.metadata = merged_metadata;
+            }
+            Some(node)
+        })
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
@@ -666,6 +803,80 @@ mod test {
             .expect("Unable to check writeable for node1"));
     }
 
+    /// Verify that subscribers of the unified registry are notified when the unified view of a
+    /// node changes.
+    ///
+    /// 1. Add a node to the read-only source; the node is added to the unified view
+    /// 2. Add the node to the internal source with different metadata; the unified view is
+    ///    updated with the internal definition and the merged metadata
+    /// 3. Change the node in the read-only source without changing its metadata; the unified view
+    ///    does not change because the internal definition takes precedence
+    /// 4. Remove the node from the internal source; the unified view reverts to the read-only
+    ///    definition
+    /// 5. Remove the node from the read-only source; the node is removed from the unified view
+    #[cfg(feature = "registry-notifications")]
+    #[test]
+    fn notify_unified_changes() {
+        use crate::registry::MemoryRegistry;
+
+        let read_only_node = new_node("node1", "endpoint1", &[("meta_a", "val_a")]);
+        let moved_read_only_node = new_node("node1", "endpoint3", &[("meta_a", "val_a")]);
+        let internal_node = new_node("node1", "endpoint2", &[("meta_b", "val_b")]);
+        let merged_node = new_node(
+            "node1",
+            "endpoint2",
+            &[("meta_a", "val_a"), ("meta_b", "val_b")],
+        );
+
+        let writeable = MemoryRegistry::new();
+        let readable = MemoryRegistry::new();
+        let unified = UnifiedRegistry::new(
+            Box::new(writeable.clone()),
+            vec![Box::new(readable.clone())],
+        );
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        unified
+            .add_subscriber(Box::new(move |notification: &RegistryNotification| {
+                sender
+                    .send(notification.clone())
+                    .map_err(|_| RegistrySubscriberError::Unsubscribe)
+            }))
+            .expect("Unable to add subscriber");
+
+        readable
+            .insert_node(read_only_node.clone())
+            .expect("Unable to insert node into read-only registry");
+        unified
+            .insert_node(internal_node)
+            .expect("Unable to insert node into unified registry");
+        readable
+            .insert_node(moved_read_only_node.clone())
+            .expect("Unable to update node in read-only registry");
+        unified
+            .delete_node("node1")
+            .expect("Unable to remove node from unified registry");
+        readable
+            .delete_node("node1")
+            .expect("Unable to remove node from read-only registry");
+
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![
+                RegistryNotification::NodeAdded(read_only_node.clone()),
+                RegistryNotification::NodeUpdated {
+                    previous: read_only_node,
+                    node: merged_node.clone(),
+                },
+                RegistryNotification::NodeUpdated {
+                    previous: merged_node,
+                    node: moved_read_only_node.clone(),
+                },
+                RegistryNotification::NodeRemoved(moved_read_only_node),
+            ]
+        );
+    }
+
     #[derive(Clone, Default)]
     struct MemRegistry {
         nodes: Arc<Mutex<HashMap<String, Node>>>,
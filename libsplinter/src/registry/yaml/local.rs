@@ -23,9 +23,15 @@
 use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
+#[cfg(feature = "registry-notifications")]
+use std::sync::MutexGuard;
 use std::sync::{Arc, Mutex};
 use std::time::SystemTime;
 
+#[cfg(feature = "registry-notifications")]
+use crate::registry::notification::{diff_nodes, RegistryNotification, RegistrySubscribers};
+#[cfg(feature = "registry-notifications")]
+use crate::registry::RegistrySubscriber;
 use crate::registry::{
     validate_nodes, MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader,
     RegistryWriter, RwRegistry,
@@ -46,10 +52,16 @@ use crate::registry::{
 /// file already exists, the registry will attempt to load, parse, and validate it. If the backing
 /// file does not already exist, the registry will attempt to create it.
 ///
+/// Subscribers of the registry are notified of the changes written through the registry, and of
+/// the changes made to the backing file once the registry reads it again. Subscribers are notified
+/// after the registry's internal lock has been released.
+///
 /// [`Node`]: struct.Node.html
 #[derive(Clone)]
 pub struct LocalYamlRegistry {
     internal: Arc<Mutex<Internal>>,
+    #[cfg(feature = "registry-notifications")]
+    subscribers: RegistrySubscribers,
 }
 
 impl LocalYamlRegistry {
@@ -64,24 +76,47 @@ impl LocalYamlRegistry {
     pub fn new(file_path: &str) -> Result<LocalYamlRegistry, RegistryError> {
         Ok(LocalYamlRegistry {
             internal: Arc::new(Mutex::new(Internal::new(file_path)?)),
+            #[cfg(feature = "registry-notifications")]
+            subscribers: RegistrySubscribers::default(),
         })
     }
 
     /// Get all nodes in the registry.
     pub(super) fn get_nodes(&self) -> Result<Vec<Node>, RegistryError> {
-        Ok(self
+        let mut internal = self
             .internal
             .lock()
-            .map_err(|_| RegistryError::general_error("YAML registry's internal lock poisoned"))?
-            .get_nodes())
+            .map_err(|_| RegistryError::general_error("YAML registry's internal lock poisoned"))?;
+        let nodes = internal.get_nodes();
+
+        #[cfg(feature = "registry-notifications")]
+        self.notify_subscribers(internal);
+
+        Ok(nodes)
     }
 
     /// Write the given list of nodes to the backing YAML file.
     pub(super) fn write_nodes(&self, nodes: Vec<Node>) -> Result<(), RegistryError> {
-        self.internal
+        let mut internal = self
+            .internal
             .lock()
-            .map_err(|_| RegistryError::general_error("YAML registry's internal lock poisoned"))?
-            .write_nodes(nodes)
+            .map_err(|_| RegistryError::general_error("YAML registry's internal lock poisoned"))?;
+        let result = internal.write_nodes(nodes);
+
+        #[cfg(feature = "registry-notifications")]
+        self.notify_subscribers(internal);
+
+        result
+    }
+
+    /// Releases the internal lock, then notifies the subscribers of the changes that were cached
+    /// while it was held.
+    #[cfg(feature = "registry-notifications")]
+    fn notify_subscribers(&self, mut internal: MutexGuard<Internal>) {
+        let notifications = std::mem::replace(&mut internal.pending_notifications, vec![]);
+        drop(internal);
+
+        self.subscribers.broadcast(&notifications);
     }
 }
 
@@ -117,6 +152,11 @@ impl RegistryReader for LocalYamlRegistry {
             .iter()
             .any(|node| node.identity == identity))
     }
+
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(&self, subscriber: Box<dyn RegistrySubscriber>) -> Result<(), RegistryError> {
+        self.subscribers.add(subscriber)
+    }
 }
 
 impl RegistryWriter for LocalYamlRegistry {
@@ -164,6 +204,9 @@ struct Internal {
     file_path: String,
     cached_nodes: Vec<Node>,
     last_read: SystemTime,
+    // The notifications for the changes cached since the subscribers were last notified
+    #[cfg(feature = "registry-notifications")]
+    pending_notifications: Vec<RegistryNotification>,
 }
 
 impl Internal {
@@ -172,6 +215,8 @@ impl Internal {
             file_path: file_path.into(),
             cached_nodes: vec![],
             last_read: SystemTime::UNIX_EPOCH,
+            #[cfg(feature = "registry-notifications")]
+            pending_notifications: vec![],
         };
 
         // If file already exists, read it; otherwise initialize it.
@@ -181,6 +226,10 @@ impl Internal {
             internal.write_nodes(vec![])?;
         }
 
+        // Nothing can have subscribed to the registry yet
+        #[cfg(feature = "registry-notifications")]
+        internal.pending_notifications.clear();
+
         Ok(internal)
     }
 
@@ -232,8 +281,7 @@ impl Internal {
 
         validate_nodes(&nodes)?;
 
-        self.cached_nodes = nodes;
-        self.last_read = SystemTime::now();
+        self.cache_nodes(nodes);
 
         Ok(())
     }
@@ -267,11 +315,21 @@ impl Internal {
             )
         })?;
 
-        self.cached_nodes = nodes;
-        self.last_read = SystemTime::now();
+        self.cache_nodes(nodes);
 
         Ok(())
     }
+
+    /// Replace the in-memory cache and queue the notifications for any nodes that have changed;
+    /// the subscribers are notified once the internal lock is released.
+    fn cache_nodes(&mut self, nodes: Vec<Node>) {
+        #[cfg(feature = "registry-notifications")]
+        self.pending_notifications
+            .extend(diff_nodes(&self.cached_nodes, &nodes));
+
+        self.cached_nodes = nodes;
+        self.last_read = SystemTime::now();
+    }
 }
 
 #[cfg(test)]
@@ -1185,6 +1243,56 @@ mod test {
         assert_eq!(nodes, vec![get_node_1()]);
     }
 
+    ///
+    /// Verifies that subscribers are notified of the changes made to the YAML file directly once
+    /// the registry reads it again.
+    ///
+    #[cfg(feature = "registry-notifications")]
+    #[test]
+    fn test_notify_modified_file() {
+        use crate::registry::{RegistryNotification, RegistrySubscriberError};
+
+        let temp_dir =
+            TempDir::new("test_notify_modified_file").expect("Failed to create temp dir");
+        let path = temp_dir
+            .path()
+            .join("registry.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+
+        write_to_file(&[get_node_1()], &path);
+
+        let registry = LocalYamlRegistry::new(&path).expect("Failed to create LocalYamlRegistry");
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        registry
+            .add_subscriber(Box::new(move |notification: &RegistryNotification| {
+                sender
+                    .send(notification.clone())
+                    .map_err(|_| RegistrySubscriberError::Unsubscribe)
+            }))
+            .expect("Unable to add subscriber");
+
+        // Allow some time before writing the file to make sure the read time is earlier than the
+        // write time; the sytem clock may not be very precise.
+        std::thread::sleep(std::time::Duration::from_secs(1));
+
+        write_to_file(&[get_node_2()], &path);
+
+        registry
+            .get_nodes()
+            .expect("Failed to get nodes from updated file");
+
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            vec![
+                RegistryNotification::NodeAdded(get_node_2()),
+                RegistryNotification::NodeRemoved(get_node_1()),
+            ]
+        );
+    }
+
     ///
     /// Verifies that if the YAML file is removed, the registry will still return nodes using its
     /// in-memory cache.
@@ -17,7 +17,17 @@
 mod local;
 #[cfg(feature = "registry-remote")]
 mod remote;
+#[cfg(feature = "registry-signatures")]
+mod signature;
 
 pub use local::LocalYamlRegistry;
+#[cfg(all(feature = "health", feature = "registry-remote"))]
+pub use remote::{
+    RefreshStatus as RemoteYamlRefreshStatus, RefreshStatusHandle as RemoteYamlRefreshStatusHandle,
+};
 #[cfg(feature = "registry-remote")]
 pub use remote::{RemoteYamlRegistry, ShutdownHandle as RemoteYamlShutdownHandle};
+#[cfg(feature = "registry-signatures")]
+pub use signature::{
+    sign_registry_file, SIGNATURE_FILE_EXTENSION as REGISTRY_SIGNATURE_FILE_EXTENSION,
+};
@@ -26,15 +26,23 @@ use std::sync::{
     Arc, Mutex,
 };
 use std::thread;
+#[cfg(feature = "health")]
+use std::time::SystemTime;
 use std::time::{Duration, Instant};
 
+#[cfg(feature = "registry-signatures")]
+use cylinder::{PublicKey, Verifier};
 use openssl::hash::{hash, MessageDigest};
 
 use crate::hex::to_hex;
+#[cfg(feature = "registry-notifications")]
+use crate::registry::RegistrySubscriber;
 use crate::registry::{
     validate_nodes, MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader,
 };
 
+#[cfg(feature = "registry-signatures")]
+use super::signature::{RegistryPublishers, SIGNATURE_FILE_EXTENSION};
 use super::LocalYamlRegistry;
 
 /// A remote, read-only registry.
@@ -67,6 +75,17 @@ use super::LocalYamlRegistry;
 /// and the previously cached registry values will continue to be used. The next time the registry
 /// is read, it will try again to refresh the cache.
 ///
+/// If the registry is constructed with publisher keys (see [`new_with_publisher_keys`]), the
+/// remote file must be signed by one of the publishers. The detached signature file is fetched
+/// along with the remote file on every refresh, and a file that is not signed by a publisher is
+/// treated as a failed refresh. The version of the last accepted file is saved with the cache, and
+/// a signed file with an older version is also treated as a failed refresh, so an old registry
+/// file can't be served again to undo a change.
+///
+/// Subscribers of the registry are notified of the nodes that changed each time the cache is
+/// refreshed.
+///
+/// [`new_with_publisher_keys`]: struct.RemoteYamlRegistry.html#method.new_with_publisher_keys
 /// [`Node`]: struct.Node.html
 /// [`RegistryReader`]: trait.RegistryReader.html
 /// [`constructor`]: struct.RemoteYamlRegistry.html#method.new
@@ -94,18 +113,64 @@ impl RemoteYamlRegistry {
         automatic_refresh_period: Option<Duration>,
         forced_refresh_period: Option<Duration>,
     ) -> Result<Self, RegistryError> {
-        let internal = Arc::new(Mutex::new(Internal::new(
-            url,
-            cache_dir,
-            forced_refresh_period,
-        )?));
+        Self::start(
+            Internal::new(url, cache_dir, forced_refresh_period)?,
+            automatic_refresh_period,
+        )
+    }
+
+    /// Construct a new `RemoteYamlRegistry` that only accepts remote files signed by one of the
+    /// given publishers.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - URL of the registry's backing YAML file.
+    /// * `cache_dir` - Directory that the local registry cache will be stored in.
+    /// * `automatic_refresh_period` - Amount of time between attempts to automatically fetch and
+    ///   cache the remote YAML file in the background. If `None`, background refreshes will be
+    ///   disabled. The automatic refresh occurs with a tolerance of +/- 1 second.
+    /// * `forced_refresh_period` - Amount of time since the last successful cache refresh before
+    ///   attempting to refresh on every read operation. If `None`, forced refreshes will be
+    ///   disabled.
+    /// * `publisher_keys` - Public keys of the publishers whose signatures are accepted.
+    /// * `verifier` - Verifier used to check the signatures of the remote file.
+    #[cfg(feature = "registry-signatures")]
+    pub fn new_with_publisher_keys(
+        url: &str,
+        cache_dir: &str,
+        automatic_refresh_period: Option<Duration>,
+        forced_refresh_period: Option<Duration>,
+        publisher_keys: Vec<PublicKey>,
+        verifier: Box<dyn Verifier>,
+    ) -> Result<Self, RegistryError> {
+        let mut internal = Internal::new(url, cache_dir, forced_refresh_period)?;
+        internal.publishers = Some(RegistryPublishers::new(publisher_keys, verifier));
+        Self::start(internal, automatic_refresh_period)
+    }
+
+    /// Attempt to populate the cache and start the automatic refresh thread, if enabled.
+    fn start(
+        mut internal: Internal,
+        automatic_refresh_period: Option<Duration>,
+    ) -> Result<Self, RegistryError> {
+        // If initial fetch/cache fails, it will be re-attempted on the next registry read, so just
+        // log a message
+        if let Err(err) = internal.refresh_cache() {
+            warn!(
+                "Couldn't initialize cache on startup of remote registry '{}': {}",
+                internal.url, err
+            );
+        }
+
+        let url = internal.url.clone();
+        let internal = Arc::new(Mutex::new(internal));
 
         let running = automatic_refresh_period
             .map::<Result<_, RegistryError>, _>(|refresh_period| {
                 let running = Arc::new(AtomicBool::new(true));
 
                 let thread_internal = internal.clone();
-                let thread_url = url.to_string();
+                let thread_url = url.clone();
                 let thread_running = running.clone();
                 thread::Builder::new()
                     .name(format!("Remote Registry Automatic Refresh: {}", url))
@@ -142,6 +207,14 @@ impl RemoteYamlRegistry {
         self.shutdown_handle.clone()
     }
 
+    /// Get a `RefreshStatusHandle` for checking whether the registry is able to refresh its cache.
+    #[cfg(feature = "health")]
+    pub fn refresh_status_handle(&self) -> RefreshStatusHandle {
+        RefreshStatusHandle {
+            internal: self.internal.clone(),
+        }
+    }
+
     /// Acquire the lock for the internal cache and get the nodes from it.
     fn get_nodes(&self) -> Result<Vec<Node>, RegistryError> {
         self.internal
@@ -175,6 +248,15 @@ impl RegistryReader for RemoteYamlRegistry {
             .filter(move |node| predicates.iter().all(|predicate| predicate.apply(node)))
             .count() as u32)
     }
+
+    #[cfg(feature = "registry-notifications")]
+    fn add_subscriber(&self, subscriber: Box<dyn RegistrySubscriber>) -> Result<(), RegistryError> {
+        self.internal
+            .lock()
+            .map_err(|_| RegistryError::general_error("Internal lock poisoned"))?
+            .cache
+            .add_subscriber(subscriber)
+    }
 }
 
 /// Holds the internal state of the remote registry.
@@ -182,12 +264,21 @@ struct Internal {
     url: String,
     cache: LocalYamlRegistry,
     last_refresh_successful: bool,
+    #[cfg(feature = "health")]
+    last_successful_refresh: Option<SystemTime>,
     forced_refresh_period: Option<Duration>,
     next_forced_refresh: Option<Instant>,
+    #[cfg(feature = "registry-signatures")]
+    publishers: Option<RegistryPublishers>,
+    /// The file the version of the last accepted signed file is saved in
+    #[cfg(feature = "registry-signatures")]
+    version_file: String,
+    #[cfg(feature = "registry-signatures")]
+    last_version: Option<u64>,
 }
 
 impl Internal {
-    /// Initialize the internal cache and attempt to populate it immediately.
+    /// Initialize the internal cache.
     fn new(
         url: &str,
         cache_dir: &str,
@@ -195,31 +286,34 @@ impl Internal {
     ) -> Result<Self, RegistryError> {
         let url = url.to_string();
 
-        let cache = LocalYamlRegistry::new(&compute_cache_filename(&url, cache_dir)?)?;
+        let cache_filename = compute_cache_filename(&url, cache_dir)?;
+        let cache = LocalYamlRegistry::new(&cache_filename)?;
 
-        let mut internal = Self {
+        #[cfg(feature = "registry-signatures")]
+        let version_file = format!("{}.version", cache_filename);
+        #[cfg(feature = "registry-signatures")]
+        let last_version = read_version_file(&version_file)?;
+
+        Ok(Self {
             url,
             cache,
             last_refresh_successful: false,
+            #[cfg(feature = "health")]
+            last_successful_refresh: None,
             forced_refresh_period,
             next_forced_refresh: None,
-        };
-
-        // If initial fetch/cache fails, it will be re-attempted on the next registry read, so just
-        // log a message
-        if let Err(err) = internal.refresh_cache() {
-            warn!(
-                "Couldn't initialize cache on startup of remote registry '{}': {}",
-                internal.url, err
-            );
-        }
-
-        Ok(internal)
+            #[cfg(feature = "registry-signatures")]
+            publishers: None,
+            #[cfg(feature = "registry-signatures")]
+            version_file,
+            #[cfg(feature = "registry-signatures")]
+            last_version,
+        })
     }
 
     /// Attempt to refresh the internal cache and update state accordingly.
     fn refresh_cache(&mut self) -> Result<(), RegistryError> {
-        fetch_nodes_from_remote(&self.url)
+        self.fetch_nodes()
             .and_then(|nodes| self.cache.write_nodes(nodes))
             .map_err(|err| {
                 self.last_refresh_successful = false;
@@ -227,6 +321,10 @@ impl Internal {
             })
             .and_then(|_| {
                 self.last_refresh_successful = true;
+                #[cfg(feature = "health")]
+                {
+                    self.last_successful_refresh = Some(SystemTime::now());
+                }
                 // If a forced refresh period was configured, set the next time a forced refresh
                 // will be required
                 self.next_forced_refresh = self
@@ -244,6 +342,42 @@ impl Internal {
             })
     }
 
+    /// Fetch the remote file, verify its signature and version if publishers are configured, and
+    /// parse and validate its nodes.
+    fn fetch_nodes(&mut self) -> Result<Vec<Node>, RegistryError> {
+        let bytes = fetch_remote_file(&self.url)?;
+
+        #[cfg(feature = "registry-signatures")]
+        {
+            if let Some(publishers) = &self.publishers {
+                let signature_url = format!("{}.{}", self.url, SIGNATURE_FILE_EXTENSION);
+                let version = publishers.verify(&bytes, &fetch_remote_file(&signature_url)?)?;
+                if let Some(last_version) = self.last_version {
+                    if version < last_version {
+                        return Err(RegistryError::general_error(&format!(
+                            "Remote registry file version {} is older than the accepted version {}",
+                            version, last_version
+                        )));
+                    }
+                }
+
+                let nodes = parse_nodes(&bytes)?;
+                if self.last_version != Some(version) {
+                    std::fs::write(&self.version_file, version.to_string()).map_err(|err| {
+                        RegistryError::general_error_with_source(
+                            "Failed to save remote registry file version",
+                            Box::new(err),
+                        )
+                    })?;
+                    self.last_version = Some(version);
+                }
+                return Ok(nodes);
+            }
+        }
+
+        parse_nodes(&bytes)
+    }
+
     /// Attempt to refresh the internal cache if necessary and return the cache's contents.
     fn get_nodes(&mut self) -> Result<Vec<Node>, RegistryError> {
         // If the last attempt to refresh the cache wasn't successful, try again
@@ -297,9 +431,27 @@ fn compute_cache_filename(url: &str, cache_dir: &str) -> Result<String, Registry
         .to_string())
 }
 
-/// Fetch, parse, and validate the YAML registry file at the given URL.
-fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
-    let bytes = reqwest::blocking::get(url)
+/// Read the version of the last accepted signed file, if one has been saved.
+#[cfg(feature = "registry-signatures")]
+fn read_version_file(path: &str) -> Result<Option<u64>, RegistryError> {
+    match std::fs::read_to_string(path) {
+        Ok(version) => version.trim().parse().map(Some).map_err(|err| {
+            RegistryError::general_error_with_source(
+                "Failed to parse remote registry file version",
+                Box::new(err),
+            )
+        }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(RegistryError::general_error_with_source(
+            "Failed to read remote registry file version",
+            Box::new(err),
+        )),
+    }
+}
+
+/// Fetch the bytes of the remote file at the given URL.
+fn fetch_remote_file(url: &str) -> Result<Vec<u8>, RegistryError> {
+    reqwest::blocking::get(url)
         .and_then(|response| response.error_for_status())
         .map_err(|err| {
             RegistryError::general_error_with_source(
@@ -308,13 +460,18 @@ fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
             )
         })?
         .bytes()
+        .map(|bytes| bytes.to_vec())
         .map_err(|err| {
             RegistryError::general_error_with_source(
                 "Failed to get bytes from remote registry file HTTP response",
                 Box::new(err),
             )
-        })?;
-    let nodes: Vec<Node> = serde_yaml::from_slice(&bytes).map_err(|_| {
+        })
+}
+
+/// Parse and validate the bytes of a YAML registry file.
+fn parse_nodes(bytes: &[u8]) -> Result<Vec<Node>, RegistryError> {
+    let nodes: Vec<Node> = serde_yaml::from_slice(bytes).map_err(|_| {
         RegistryError::general_error(
             "Failed to deserialize remote registry file: Not a valid YAML sequence of nodes",
         )
@@ -390,6 +547,43 @@ impl ShutdownHandle {
     }
 }
 
+/// The outcome of a `RemoteYamlRegistry`'s attempts to refresh its cache.
+#[cfg(feature = "health")]
+#[derive(Clone, Debug)]
+pub struct RefreshStatus {
+    /// URL of the registry's backing YAML file
+    pub url: String,
+    /// Whether or not the most recent attempt to refresh the cache was successful
+    pub last_refresh_successful: bool,
+    /// When the cache was last refreshed successfully, if it has been refreshed since the
+    /// registry was created
+    pub last_successful_refresh: Option<SystemTime>,
+}
+
+/// Handle for checking the refresh status of a `RemoteYamlRegistry`.
+#[cfg(feature = "health")]
+#[derive(Clone)]
+pub struct RefreshStatusHandle {
+    internal: Arc<Mutex<Internal>>,
+}
+
+#[cfg(feature = "health")]
+impl RefreshStatusHandle {
+    /// Get the current refresh status of the `RemoteYamlRegistry`.
+    pub fn refresh_status(&self) -> Result<RefreshStatus, RegistryError> {
+        let internal = self
+            .internal
+            .lock()
+            .map_err(|_| RegistryError::general_error("Internal lock poisoned"))?;
+
+        Ok(RefreshStatus {
+            url: internal.url.clone(),
+            last_refresh_successful: internal.last_refresh_successful,
+            last_successful_refresh: internal.last_successful_refresh,
+        })
+    }
+}
+
 #[cfg(all(test, feature = "rest-api", feature = "rest-api-actix"))]
 mod tests {
     use super::*;
@@ -397,9 +591,13 @@ mod tests {
     use std::fs::File;
 
     use actix_web::HttpResponse;
+    #[cfg(feature = "registry-signatures")]
+    use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
     use futures::future::IntoFuture;
     use tempdir::TempDir;
 
+    #[cfg(feature = "registry-signatures")]
+    use crate::registry::sign_registry_file;
     use crate::rest_api::actix_web_1::{Method, Resource, RestApiBuilder, RestApiShutdownHandle};
     #[cfg(feature = "authorization")]
     use crate::rest_api::auth::Permission;
@@ -845,6 +1043,54 @@ mod tests {
         test_config.shutdown();
     }
 
+    /// Verifies that the subscribers of the registry are notified of the nodes that changed when
+    /// the cache is refreshed.
+    ///
+    /// 1. Start the registry with automatic refreshes and add a subscriber
+    /// 2. Remove the first node from the remote file and wait for the registry to refresh
+    /// 3. Verify that the subscriber was only notified of the removed node
+    #[cfg(feature = "registry-notifications")]
+    #[test]
+    fn refresh_notifies_subscribers() {
+        use crate::registry::{RegistryNotification, RegistrySubscriberError};
+
+        let test_config = TestConfig::setup("refresh_notifies_subscribers", Some(mock_registry()));
+
+        let refresh_period = Duration::from_secs(1);
+        let remote_registry = RemoteYamlRegistry::new(
+            test_config.url(),
+            test_config.path(),
+            Some(refresh_period),
+            None,
+        )
+        .expect("Failed to create registry");
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        remote_registry
+            .add_subscriber(Box::new(move |notification: &RegistryNotification| {
+                sender
+                    .send(notification.clone())
+                    .map_err(|_| RegistrySubscriberError::Unsubscribe)
+            }))
+            .expect("Unable to add subscriber");
+
+        let mut registry = mock_registry();
+        let removed_node = registry.remove(0);
+        test_config.update_registry(Some(registry));
+
+        assert_eq!(
+            receiver
+                .recv_timeout(refresh_period * 2)
+                .expect("Failed to receive notification"),
+            RegistryNotification::NodeRemoved(removed_node)
+        );
+
+        remote_registry.shutdown_handle().shutdown();
+        test_config.shutdown();
+
+        assert!(receiver.try_recv().is_err());
+    }
+
     /// Verifies that when forced refresh feature is disabled, the registry is not refreshed on
     /// read.
     #[test]
@@ -980,6 +1226,159 @@ mod tests {
         ]
     }
 
+    /// Verifies that a remote file signed by a configured publisher is loaded.
+    #[cfg(feature = "registry-signatures")]
+    #[test]
+    fn signed_file_accepted() {
+        let test_config = TestConfig::setup("signed_file_accepted", Some(mock_registry()));
+        let context = Secp256k1Context::new();
+        let publisher = context.new_signer(context.new_random_private_key());
+        test_config.sign_registry(&*publisher, 1, &mock_registry());
+
+        let remote_registry = RemoteYamlRegistry::new_with_publisher_keys(
+            test_config.url(),
+            test_config.path(),
+            None,
+            None,
+            vec![publisher.public_key().expect("Failed to get public key")],
+            context.new_verifier(),
+        )
+        .expect("Failed to create registry");
+
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+
+        remote_registry.shutdown_handle().shutdown();
+        test_config.shutdown();
+    }
+
+    /// Verifies that a remote file without a signature file, or signed by a key that is not a
+    /// configured publisher, is rejected (not loaded).
+    #[cfg(feature = "registry-signatures")]
+    #[test]
+    fn unsigned_file_rejected() {
+        let test_config = TestConfig::setup("unsigned_file_rejected", Some(mock_registry()));
+        let context = Secp256k1Context::new();
+        let publisher = context.new_signer(context.new_random_private_key());
+
+        let remote_registry = RemoteYamlRegistry::new_with_publisher_keys(
+            test_config.url(),
+            test_config.path(),
+            None,
+            None,
+            vec![publisher.public_key().expect("Failed to get public key")],
+            context.new_verifier(),
+        )
+        .expect("Failed to create registry");
+
+        // Verify that the registry is still empty
+        verify_internal_cache(&test_config, &remote_registry, vec![]);
+
+        let other_signer = context.new_signer(context.new_random_private_key());
+        test_config.sign_registry(&*other_signer, 1, &mock_registry());
+
+        // Verify that the registry is still empty
+        verify_internal_cache(&test_config, &remote_registry, vec![]);
+
+        remote_registry.shutdown_handle().shutdown();
+        test_config.shutdown();
+    }
+
+    /// Verifies that a remote file that changes without being signed again does not replace the
+    /// cache of the previously signed file.
+    ///
+    /// 1. Serve a signed file and verify that it is loaded
+    /// 2. Change the file without updating the signature and verify that the cache is unchanged
+    ///    after a forced refresh
+    /// 3. Sign the new file and verify that the cache is updated
+    #[cfg(feature = "registry-signatures")]
+    #[test]
+    fn badly_signed_file_rejected() {
+        let test_config = TestConfig::setup("badly_signed_file_rejected", Some(mock_registry()));
+        let context = Secp256k1Context::new();
+        let publisher = context.new_signer(context.new_random_private_key());
+        test_config.sign_registry(&*publisher, 1, &mock_registry());
+
+        let refresh_period = Duration::from_millis(10);
+        let remote_registry = RemoteYamlRegistry::new_with_publisher_keys(
+            test_config.url(),
+            test_config.path(),
+            None,
+            Some(refresh_period),
+            vec![publisher.public_key().expect("Failed to get public key")],
+            context.new_verifier(),
+        )
+        .expect("Failed to create registry");
+
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+
+        test_config.update_registry(Some(vec![]));
+
+        // Wait at least as long as the forced refresh period
+        std::thread::sleep(refresh_period);
+
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+
+        test_config.sign_registry(&*publisher, 2, &[]);
+        std::thread::sleep(refresh_period);
+
+        verify_internal_cache(&test_config, &remote_registry, vec![]);
+
+        remote_registry.shutdown_handle().shutdown();
+        test_config.shutdown();
+    }
+
+    /// Verifies that a signed remote file with an older version than the last accepted file is
+    /// rejected, including after the registry is restarted.
+    ///
+    /// 1. Serve a file signed as version 2 and verify that it is loaded
+    /// 2. Serve a different file signed as version 1 and verify that the cache is unchanged after
+    ///    a forced refresh
+    /// 3. Restart the registry and verify that the cache is still unchanged
+    /// 4. Sign the different file as version 3 and verify that the cache is updated
+    #[cfg(feature = "registry-signatures")]
+    #[test]
+    fn older_signed_file_rejected() {
+        let test_config = TestConfig::setup("older_signed_file_rejected", Some(mock_registry()));
+        let context = Secp256k1Context::new();
+        let publisher = context.new_signer(context.new_random_private_key());
+        test_config.sign_registry(&*publisher, 2, &mock_registry());
+
+        let refresh_period = Duration::from_millis(10);
+        let new_registry = || {
+            RemoteYamlRegistry::new_with_publisher_keys(
+                test_config.url(),
+                test_config.path(),
+                None,
+                Some(refresh_period),
+                vec![publisher.public_key().expect("Failed to get public key")],
+                context.new_verifier(),
+            )
+            .expect("Failed to create registry")
+        };
+
+        let remote_registry = new_registry();
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+
+        test_config.update_registry(Some(vec![]));
+        test_config.sign_registry(&*publisher, 1, &[]);
+        std::thread::sleep(refresh_period);
+
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+
+        remote_registry.shutdown_handle().shutdown();
+        let remote_registry = new_registry();
+
+        verify_internal_cache(&test_config, &remote_registry, mock_registry());
+
+        test_config.sign_registry(&*publisher, 3, &[]);
+        std::thread::sleep(refresh_period);
+
+        verify_internal_cache(&test_config, &remote_registry, vec![]);
+
+        remote_registry.shutdown_handle().shutdown();
+        test_config.shutdown();
+    }
+
     /// Verifies that the retrieved nodes and the backing file of the `remote_registry` match the
     /// contents of the `expected_registry`.
     fn verify_internal_cache(
@@ -1007,6 +1406,8 @@ mod tests {
         _temp_dir: TempDir,
         temp_dir_path: String,
         registry: Arc<Mutex<Option<Vec<Node>>>>,
+        #[cfg(feature = "registry-signatures")]
+        signature: Arc<Mutex<Option<Vec<u8>>>>,
         registry_url: String,
         rest_api_shutdown_handle: RestApiShutdownHandle,
         rest_api_join_handle: std::thread::JoinHandle<()>,
@@ -1025,14 +1426,21 @@ mod tests {
                 .to_string();
 
             let registry = Arc::new(Mutex::new(registry));
+            #[cfg(feature = "registry-signatures")]
+            let signature = Arc::new(Mutex::new(None));
 
-            let (rest_api_shutdown_handle, rest_api_join_handle, registry_url) =
-                serve_registry(registry.clone());
+            let (rest_api_shutdown_handle, rest_api_join_handle, registry_url) = serve_registry(
+                registry.clone(),
+                #[cfg(feature = "registry-signatures")]
+                signature.clone(),
+            );
 
             Self {
                 _temp_dir: temp_dir,
                 temp_dir_path,
                 registry,
+                #[cfg(feature = "registry-signatures")]
+                signature,
                 registry_url,
                 rest_api_shutdown_handle,
                 rest_api_join_handle,
@@ -1055,6 +1463,16 @@ mod tests {
             *self.registry.lock().expect("Registry lock poisonsed") = registry;
         }
 
+        /// Signs the serialized `registry` as the given `version` with the `signer` and serves the
+        /// result as the registry's signature file.
+        #[cfg(feature = "registry-signatures")]
+        fn sign_registry(&self, signer: &dyn Signer, version: u64, registry: &[Node]) {
+            let contents = serde_yaml::to_vec(registry).expect("Failed to serialize registry file");
+            let signature = sign_registry_file(&contents, version, &[signer])
+                .expect("Failed to sign registry file");
+            *self.signature.lock().expect("Signature lock poisoned") = Some(signature.into_bytes());
+        }
+
         /// Shuts down the REST API; this should be called at the end of every test that uses
         /// `TestConfig`.
         fn shutdown(self) {
@@ -1072,6 +1490,7 @@ mod tests {
     /// be available.
     fn serve_registry(
         registry: Arc<Mutex<Option<Vec<Node>>>>,
+        #[cfg(feature = "registry-signatures")] signature: Arc<Mutex<Option<Vec<u8>>>>,
     ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
         let mut resource = Resource::build("/registry.yaml");
         #[cfg(feature = "authorization")]
@@ -1106,11 +1525,45 @@ mod tests {
                 })
             })
         }
-        let (shutdown, join, url) = run_rest_api_on_open_port(vec![resource]);
+        #[allow(unused_mut)]
+        let mut resources = vec![resource];
+        #[cfg(feature = "registry-signatures")]
+        resources.push(serve_signature(signature));
+        let (shutdown, join, url) = run_rest_api_on_open_port(resources);
 
         (shutdown, join, format!("http://{}/registry.yaml", url))
     }
 
+    /// Serves up the given `signature` as the registry file's signature file. If `signature` is
+    /// `None`, the signature file will not be available.
+    #[cfg(feature = "registry-signatures")]
+    fn serve_signature(signature: Arc<Mutex<Option<Vec<u8>>>>) -> Resource {
+        let mut resource = Resource::build("/registry.yaml.sig");
+        #[cfg(feature = "authorization")]
+        {
+            resource = resource.add_method(
+                Method::Get,
+                Permission::AllowUnauthenticated,
+                move |_, _| {
+                    Box::new(match &*signature.lock().expect("Signature lock poisoned") {
+                        Some(signature) => HttpResponse::Ok().body(signature.clone()).into_future(),
+                        None => HttpResponse::NotFound().finish().into_future(),
+                    })
+                },
+            )
+        }
+        #[cfg(not(feature = "authorization"))]
+        {
+            resource = resource.add_method(Method::Get, move |_, _| {
+                Box::new(match &*signature.lock().expect("Signature lock poisoned") {
+                    Some(signature) => HttpResponse::Ok().body(signature.clone()).into_future(),
+                    None => HttpResponse::NotFound().finish().into_future(),
+                })
+            })
+        }
+        resource
+    }
+
     fn run_rest_api_on_open_port(
         resources: Vec<Resource>,
     ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
@@ -0,0 +1,203 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Detached signatures for registry YAML files.
+//!
+//! A registry file is signed by one or more publishers. The signatures are stored in a separate
+//! signature file, which contains one line per signature: the version of the registry file,
+//! followed by a space and the hex-encoded signature of the version and the registry file's
+//! contents. For a remote registry file, the signature file is fetched from the registry file's
+//! URL with the [`SIGNATURE_FILE_EXTENSION`] appended (for example, `nodes.yaml.sig`).
+//!
+//! Because the version is signed along with the contents, a reader that remembers the version of
+//! the last file it accepted can reject an older signed file that is served again. Publishers must
+//! increase the version each time they publish a new registry file.
+//!
+//! [`SIGNATURE_FILE_EXTENSION`]: constant.SIGNATURE_FILE_EXTENSION.html
+
+use cylinder::Signer;
+#[cfg(feature = "registry-remote")]
+use cylinder::{PublicKey, Signature, Verifier};
+
+#[cfg(feature = "registry-remote")]
+use crate::hex::parse_hex;
+use crate::hex::to_hex;
+use crate::registry::RegistryError;
+
+/// The extension that is appended to a registry file's name or URL to locate its signature file.
+pub const SIGNATURE_FILE_EXTENSION: &str = "sig";
+
+/// Signs the contents of a registry file with each of the given signers, returning the contents
+/// of the registry file's signature file.
+///
+/// # Arguments
+///
+/// * `contents` - The bytes of the registry file, exactly as it will be published
+/// * `version` - The version of the registry file, which must be greater than the version of any
+///   registry file the publishers have published before
+/// * `signers` - The signers of the registry file's publishers
+pub fn sign_registry_file(
+    contents: &[u8],
+    version: u64,
+    signers: &[&dyn Signer],
+) -> Result<String, RegistryError> {
+    let message = signed_message(contents, version);
+    signers
+        .iter()
+        .map(|signer| {
+            signer
+                .sign(&message)
+                .map(|signature| format!("{} {}\n", version, to_hex(&signature.take_bytes())))
+                .map_err(|err| {
+                    RegistryError::general_error_with_source(
+                        "Failed to sign registry file",
+                        Box::new(err),
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Returns the bytes that are signed for the given version of a registry file.
+fn signed_message(contents: &[u8], version: u64) -> Vec<u8> {
+    let mut message = version.to_be_bytes().to_vec();
+    message.extend_from_slice(contents);
+    message
+}
+
+/// The publishers whose signatures are accepted for a registry file.
+#[cfg(feature = "registry-remote")]
+pub(super) struct RegistryPublishers {
+    keys: Vec<PublicKey>,
+    verifier: Box<dyn Verifier>,
+}
+
+#[cfg(feature = "registry-remote")]
+impl RegistryPublishers {
+    pub fn new(keys: Vec<PublicKey>, verifier: Box<dyn Verifier>) -> Self {
+        Self { keys, verifier }
+    }
+
+    /// Verifies that the signature file contains a valid signature of the registry file's
+    /// contents by at least one of the publishers, returning the signed version of the file.
+    pub fn verify(&self, contents: &[u8], signature_file: &[u8]) -> Result<u64, RegistryError> {
+        let signature_file = std::str::from_utf8(signature_file).map_err(|err| {
+            RegistryError::general_error_with_source(
+                "Registry signature file is not valid UTF-8",
+                Box::new(err),
+            )
+        })?;
+
+        for line in signature_file.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut parts = line.splitn(2, ' ');
+            let (version, signature) = match (parts.next(), parts.next()) {
+                (Some(version), Some(signature)) => (version, signature),
+                _ => {
+                    return Err(RegistryError::general_error(
+                        "Registry signature file contains a signature without a version",
+                    ))
+                }
+            };
+            let version = version.parse::<u64>().map_err(|err| {
+                RegistryError::general_error_with_source(
+                    "Registry signature file contains an invalid version",
+                    Box::new(err),
+                )
+            })?;
+            let signature = Signature::new(parse_hex(signature.trim()).map_err(|err| {
+                RegistryError::general_error_with_source(
+                    "Registry signature file contains an invalid signature",
+                    Box::new(err),
+                )
+            })?);
+            let message = signed_message(contents, version);
+
+            for key in &self.keys {
+                let valid = self
+                    .verifier
+                    .verify(&message, &signature, key)
+                    .map_err(|err| {
+                        RegistryError::general_error_with_source(
+                            "Failed to verify registry file signature",
+                            Box::new(err),
+                        )
+                    })?;
+                if valid {
+                    return Ok(version);
+                }
+            }
+        }
+
+        Err(RegistryError::general_error(
+            "Registry file is not signed by any of the configured publishers",
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "registry-remote"))]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+
+    /// Verifies that a registry file signed by one of several signers is accepted by publishers
+    /// that trust any of those signers, and rejected by publishers that do not.
+    #[test]
+    fn sign_and_verify() {
+        let context = Secp256k1Context::new();
+        let publisher_signer = context.new_signer(context.new_random_private_key());
+        let other_signer = context.new_signer(context.new_random_private_key());
+        let publisher_key = publisher_signer
+            .public_key()
+            .expect("Failed to get public key");
+
+        let contents = b"- identity: node-1\n";
+        let signature_file = sign_registry_file(contents, 7, &[&*other_signer, &*publisher_signer])
+            .expect("Failed to sign registry file");
+        assert_eq!(signature_file.lines().count(), 2);
+
+        let publishers = RegistryPublishers::new(vec![publisher_key], context.new_verifier());
+        assert_eq!(
+            publishers
+                .verify(contents, signature_file.as_bytes())
+                .expect("Signed file was rejected"),
+            7
+        );
+
+        // A signature whose version is changed is rejected
+        let changed_version = signature_file.replace("7 ", "8 ");
+        assert!(publishers
+            .verify(contents, changed_version.as_bytes())
+            .is_err());
+
+        // A modified file is rejected
+        assert!(publishers
+            .verify(b"- identity: node-2\n", signature_file.as_bytes())
+            .is_err());
+
+        // A file that is only signed by an untrusted signer is rejected
+        let other_signature_file = sign_registry_file(contents, 7, &[&*other_signer])
+            .expect("Failed to sign registry file");
+        assert!(publishers
+            .verify(contents, other_signature_file.as_bytes())
+            .is_err());
+
+        // An empty signature file is rejected
+        assert!(publishers.verify(contents, b"").is_err());
+    }
+}
@@ -26,6 +26,8 @@ mod permission_map;
 #[cfg(feature = "authorization")]
 pub mod rbac;
 
+#[cfg(feature = "authorization")]
+use std::collections::HashMap;
 use std::str::FromStr;
 
 #[cfg(feature = "authorization")]
@@ -53,6 +55,41 @@ pub enum Permission {
     AllowUnauthenticated,
 }
 
+/// The resource that a request applies to, as determined by the path variables of the requested
+/// endpoint
+#[cfg(feature = "authorization")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResourceScope {
+    /// The request applies to the circuit with the given ID
+    Circuit(String),
+    /// The request applies to a service on a circuit
+    Service {
+        circuit_id: String,
+        service_id: String,
+    },
+}
+
+#[cfg(feature = "authorization")]
+impl ResourceScope {
+    /// Determines the scope of a request from the path variables of the requested endpoint. The
+    /// circuit is identified by a `circuit_id` or `circuit` variable and the service by a
+    /// `service_id` variable. Returns `None` if the endpoint does not identify a circuit.
+    fn from_path_params(path_params: &HashMap<String, String>) -> Option<Self> {
+        let circuit_id = path_params
+            .get("circuit_id")
+            .or_else(|| path_params.get("circuit"))?
+            .to_string();
+
+        match path_params.get("service_id") {
+            Some(service_id) => Some(ResourceScope::Service {
+                circuit_id,
+                service_id: service_id.to_string(),
+            }),
+            None => Some(ResourceScope::Circuit(circuit_id)),
+        }
+    }
+}
+
 /// An authorization handler's decision about whether to allow, deny, or pass on the request
 #[cfg(feature = "authorization")]
 pub enum AuthorizationHandlerResult {
@@ -75,6 +112,20 @@ pub trait AuthorizationHandler: Send + Sync {
         permission_id: &str,
     ) -> Result<AuthorizationHandlerResult, InternalError>;
 
+    /// Determines if the given identity has the requested permission for the given resource. This
+    /// is checked instead of `has_permission` when the requested endpoint identifies a circuit or
+    /// service.
+    ///
+    /// The default implementation ignores the scope and defers to `has_permission`.
+    fn has_scoped_permission(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+        _scope: &ResourceScope,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        self.has_permission(identity, permission_id)
+    }
+
     /// Clone implementation for `AuthorizationHandler`. The implementation of the `Clone` trait for
     /// `Box<dyn AuthorizationHandler>` calls this method.
     ///
@@ -128,10 +179,11 @@ fn authorize(
     #[cfg(feature = "authorization")]
     {
         // Get the permission that applies to this request
-        let permission = match permission_map.get_permission(&method, endpoint) {
-            Some(perm) => perm,
-            None => return AuthorizationResult::UnknownEndpoint,
-        };
+        let (permission, path_params) =
+            match permission_map.get_permission_with_path_params(&method, endpoint) {
+                Some(perm_and_params) => perm_and_params,
+                None => return AuthorizationResult::UnknownEndpoint,
+            };
 
         match *permission {
             Permission::AllowUnauthenticated => AuthorizationResult::NoAuthorizationNecessary,
@@ -141,8 +193,13 @@ fn authorize(
             },
             Permission::Check(perm) => match get_identity(auth_header, identity_providers) {
                 Some(identity) => {
+                    let scope = ResourceScope::from_path_params(&path_params);
                     for handler in authorization_handlers {
-                        match handler.has_permission(&identity, perm) {
+                        let result = match &scope {
+                            Some(scope) => handler.has_scoped_permission(&identity, perm, scope),
+                            None => handler.has_permission(&identity, perm),
+                        };
+                        match result {
                             Ok(AuthorizationHandlerResult::Allow) => {
                                 return AuthorizationResult::Authorized(identity)
                             }
@@ -907,6 +964,83 @@ mod tests {
         ));
     }
 
+    /// Verifies that the `authorize` function checks the scope of the request with the
+    /// authorization handlers when the endpoint identifies a circuit, and that the request is not
+    /// authorized for other circuits.
+    #[cfg(feature = "authorization")]
+    #[test]
+    fn authorize_scoped_authorization_handler() {
+        let expected_auth = "auth".parse().unwrap();
+        let expected_identity = AlwaysAcceptIdentityProvider
+            .get_identity(&expected_auth)
+            .unwrap()
+            .unwrap();
+
+        let permission_map = {
+            let mut map = PermissionMap::new();
+            map.add_permission(
+                Method::Get,
+                "/test/{circuit_id}",
+                Permission::Check("permission"),
+            );
+            map.add_permission(
+                Method::Get,
+                "/test/{circuit}/{service_id}",
+                Permission::Check("permission"),
+            );
+            map.add_permission(Method::Get, "/test", Permission::Check("permission"));
+            map
+        };
+
+        assert!(matches!(
+            authorize(
+                &Method::Get,
+                "/test/abcde-01234",
+                Some("auth"),
+                &permission_map,
+                &[Box::new(AlwaysAcceptIdentityProvider)],
+                &[Box::new(CircuitScopedAuthorizationHandler)],
+            ),
+            AuthorizationResult::Authorized(identity) if identity == expected_identity
+        ));
+
+        assert!(matches!(
+            authorize(
+                &Method::Get,
+                "/test/abcde-01234/a000",
+                Some("auth"),
+                &permission_map,
+                &[Box::new(AlwaysAcceptIdentityProvider)],
+                &[Box::new(CircuitScopedAuthorizationHandler)],
+            ),
+            AuthorizationResult::Authorized(identity) if identity == expected_identity
+        ));
+
+        assert!(matches!(
+            authorize(
+                &Method::Get,
+                "/test/fghij-56789",
+                Some("auth"),
+                &permission_map,
+                &[Box::new(AlwaysAcceptIdentityProvider)],
+                &[Box::new(CircuitScopedAuthorizationHandler)],
+            ),
+            AuthorizationResult::Unauthorized
+        ));
+
+        assert!(matches!(
+            authorize(
+                &Method::Get,
+                "/test",
+                Some("auth"),
+                &permission_map,
+                &[Box::new(AlwaysAcceptIdentityProvider)],
+                &[Box::new(CircuitScopedAuthorizationHandler)],
+            ),
+            AuthorizationResult::Unauthorized
+        ));
+    }
+
     /// An identity provider that always returns `Ok(Some(_))`
     #[derive(Clone)]
     struct AlwaysAcceptIdentityProvider;
@@ -1037,4 +1171,41 @@ mod tests {
             Box::new(self.clone())
         }
     }
+
+    /// An authorization handler that only returns `Ok(AuthorizationHandlerResult::Allow)` for
+    /// requests scoped to the circuit `abcde-01234`
+    #[cfg(feature = "authorization")]
+    #[derive(Clone)]
+    struct CircuitScopedAuthorizationHandler;
+
+    #[cfg(feature = "authorization")]
+    impl AuthorizationHandler for CircuitScopedAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Continue)
+        }
+
+        fn has_scoped_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+            scope: &ResourceScope,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            match scope {
+                ResourceScope::Circuit(circuit_id) | ResourceScope::Service { circuit_id, .. }
+                    if circuit_id == "abcde-01234" =>
+                {
+                    Ok(AuthorizationHandlerResult::Allow)
+                }
+                _ => Ok(AuthorizationHandlerResult::Continue),
+            }
+        }
+
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
 }
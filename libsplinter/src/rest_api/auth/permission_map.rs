@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use crate::rest_api::Method;
 
 use super::Permission;
@@ -37,6 +39,7 @@ impl PermissionMap {
 
     /// Gets the permission for a request. This will attempt to match the method and endpoint to a
     /// known (method, endpoint) pair, considering path variables of known endpoints.
+    #[cfg(test)]
     pub fn get_permission(&self, method: &Method, endpoint: &str) -> Option<&Permission> {
         self.internal
             .iter()
@@ -44,6 +47,19 @@ impl PermissionMap {
             .map(|(_, perm)| perm)
     }
 
+    /// Gets the permission for a request, along with the values of the path variables of the
+    /// matched endpoint, keyed by the variable names.
+    pub fn get_permission_with_path_params(
+        &self,
+        method: &Method,
+        endpoint: &str,
+    ) -> Option<(&Permission, HashMap<String, String>)> {
+        self.internal
+            .iter()
+            .find(|(req, _)| req.matches(&method, endpoint))
+            .map(|(req, perm)| (perm, req.path_params(endpoint)))
+    }
+
     /// Takes the contents of another `PermissionMap` and merges them into itself. This consumes the
     /// contents of the other map.
     pub fn append(&mut self, other: &mut PermissionMap) {
@@ -88,6 +104,21 @@ impl RequestDefinition {
                     .unwrap_or(false)
             })
     }
+
+    /// Gets the values of the variable path components of the given endpoint, keyed by the
+    /// variable names. The endpoint is expected to match this definition.
+    pub fn path_params(&self, endpoint: &str) -> HashMap<String, String> {
+        endpoint
+            .strip_prefix('/')
+            .unwrap_or(endpoint)
+            .split('/')
+            .zip(self.path.iter())
+            .filter_map(|(component, path_component)| match path_component {
+                PathComponent::Variable(name) => Some((name.clone(), component.to_string())),
+                PathComponent::Text(_) => None,
+            })
+            .collect()
+    }
 }
 
 /// A component of an endpoint path
@@ -95,14 +126,14 @@ impl RequestDefinition {
 enum PathComponent {
     /// A standard path component where matching is done on the internal string
     Text(String),
-    /// A variable path component that matches any string
-    Variable,
+    /// A variable path component that matches any string; contains the name of the variable
+    Variable(String),
 }
 
 impl From<&str> for PathComponent {
     fn from(component: &str) -> Self {
         if component.starts_with('{') && component.ends_with('}') {
-            PathComponent::Variable
+            PathComponent::Variable(component[1..component.len() - 1].into())
         } else {
             PathComponent::Text(component.into())
         }
@@ -112,7 +143,7 @@ impl From<&str> for PathComponent {
 impl PartialEq<&str> for PathComponent {
     fn eq(&self, other: &&str) -> bool {
         match self {
-            PathComponent::Variable => true,
+            PathComponent::Variable(_) => true,
             PathComponent::Text(component) => other == component,
         }
     }
@@ -127,14 +158,14 @@ mod tests {
     fn path_component_parse() {
         assert!(PathComponent::from("") == PathComponent::Text("".into()));
         assert!(PathComponent::from("test") == PathComponent::Text("test".into()));
-        assert!(PathComponent::from("{test}") == PathComponent::Variable);
+        assert!(PathComponent::from("{test}") == PathComponent::Variable("test".into()));
     }
 
     /// Verifies that a `PathComponent` can be correctly compared with a `&str`
     #[test]
     fn path_component_str_comparison() {
-        assert!(PathComponent::Variable == "test1");
-        assert!(PathComponent::Variable == "test2");
+        assert!(PathComponent::Variable("test".into()) == "test1");
+        assert!(PathComponent::Variable("test".into()) == "test2");
         assert!(PathComponent::Text("test1".into()) == "test1");
         assert!(PathComponent::Text("test1".into()) != "test2");
     }
@@ -158,6 +189,19 @@ mod tests {
         assert!(definition.matches(&Method::Get, "/"));
     }
 
+    /// Verifies that the `RequestDefinition` struct returns the values of its path variables
+    #[test]
+    fn request_definition_path_params() {
+        let definition = RequestDefinition::new(Method::Get, "/test/endpoint");
+        assert!(definition.path_params("/test/endpoint").is_empty());
+
+        let definition = RequestDefinition::new(Method::Get, "/test/{circuit}/{service_id}/state");
+        let params = definition.path_params("/test/abcde-01234/a000/state");
+        assert_eq!(params.len(), 2);
+        assert_eq!(params.get("circuit"), Some(&"abcde-01234".to_string()));
+        assert_eq!(params.get("service_id"), Some(&"a000".to_string()));
+    }
+
     /// Verifies that the `PermissionMap` works correctly
     #[test]
     fn permission_map() {
@@ -196,5 +240,11 @@ mod tests {
             map.get_permission(&Method::Get, "/test/endpoint/test1"),
             None
         );
+
+        let (perm, params) = map
+            .get_permission_with_path_params(&Method::Put, "/test/endpoint/test1")
+            .expect("Permission not found");
+        assert_eq!(perm, &perm2);
+        assert_eq!(params.get("variable"), Some(&"test1".to_string()));
     }
 }
@@ -12,11 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
+
 use crate::error::InternalError;
 
-use crate::rest_api::auth::{identity::Identity, AuthorizationHandler, AuthorizationHandlerResult};
+use crate::rest_api::auth::{
+    identity::Identity, AuthorizationHandler, AuthorizationHandlerResult, ResourceScope,
+};
 
-use super::store::{Identity as StoreIdentity, RoleBasedAuthorizationStore};
+use super::store::{
+    Identity as StoreIdentity, RoleBasedAuthorizationStore, RoleBasedAuthorizationStoreError, Scope,
+};
 
 /// A Role-based authorization handler.
 ///
@@ -24,6 +30,12 @@ use super::store::{Identity as StoreIdentity, RoleBasedAuthorizationStore};
 /// it has been assigned.  If one of the identity's assigned roles contains the permission, then
 /// the identity is allowed access. If not, the handler defers to the next handler in the chain.
 ///
+/// Requests for a circuit or service are also allowed by roles assigned to the identity for that
+/// circuit or service; roles that are not assigned to a scope apply to every request.
+///
+/// The roles assigned to the groups that the identity is a member of apply to the identity as
+/// well, and each role grants the permissions of the roles that it includes.
+///
 /// It currently does not deny any permissions.
 pub struct RoleBasedAuthorizationHandler {
     role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
@@ -36,32 +48,98 @@ impl RoleBasedAuthorizationHandler {
             role_based_auth_store,
         }
     }
-}
 
-impl AuthorizationHandler for RoleBasedAuthorizationHandler {
-    fn has_permission(
+    /// Checks whether any of the roles that apply to the identity, either directly or through its
+    /// groups and the roles they include, contains the requested permission.  If a scope is
+    /// provided, the roles assigned to that scope are considered along with the global roles.
+    fn check_permission(
         &self,
         identity: &Identity,
         permission_id: &str,
-    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        scope: Option<&Scope>,
+    ) -> Result<AuthorizationHandlerResult, RoleBasedAuthorizationStoreError> {
         let store_identity = match identity {
-            Identity::Custom(_) =>
             // RoleBasedAuthorization does not currently support custom identities, so return
             // continue in case a downstream handler will support it.
-            {
-                return Ok(AuthorizationHandlerResult::Continue)
-            }
+            Identity::Custom(_) => return Ok(AuthorizationHandlerResult::Continue),
             Identity::Key(key) => StoreIdentity::Key(key.to_string()),
             Identity::User(user_id) => StoreIdentity::User(user_id.to_string()),
         };
 
-        Ok(self
+        let mut identities = self
             .role_based_auth_store
-            .get_assigned_roles(&store_identity)
-            .map_err(|err| InternalError::from_source(Box::new(err)))?
-            .find(|role| role.permissions().iter().any(|perm| perm == permission_id))
-            .map(|_| AuthorizationHandlerResult::Allow)
-            .unwrap_or(AuthorizationHandlerResult::Continue))
+            .list_groups_for_identity(&store_identity)?
+            .map(|group| StoreIdentity::Group(group.id().to_string()))
+            .collect::<Vec<_>>();
+        identities.push(store_identity);
+
+        let mut roles = vec![];
+        for identity in &identities {
+            match scope {
+                Some(scope) => roles.extend(
+                    self.role_based_auth_store
+                        .get_assigned_roles_in_scope(identity, scope)?,
+                ),
+                None => roles.extend(self.role_based_auth_store.get_assigned_roles(identity)?),
+            }
+        }
+
+        // Expand the included roles; checking each role only once guards against cycles
+        let mut checked_roles = HashSet::new();
+        while let Some(role) = roles.pop() {
+            if !checked_roles.insert(role.id().to_string()) {
+                continue;
+            }
+
+            if role.permissions().iter().any(|perm| perm == permission_id) {
+                return Ok(AuthorizationHandlerResult::Allow);
+            }
+
+            for included_role_id in role.included_roles() {
+                if checked_roles.contains(included_role_id) {
+                    continue;
+                }
+                if let Some(included_role) =
+                    self.role_based_auth_store.get_role(included_role_id)?
+                {
+                    roles.push(included_role);
+                }
+            }
+        }
+
+        Ok(AuthorizationHandlerResult::Continue)
+    }
+}
+
+impl AuthorizationHandler for RoleBasedAuthorizationHandler {
+    fn has_permission(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        self.check_permission(identity, permission_id, None)
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    fn has_scoped_permission(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+        scope: &ResourceScope,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        let store_scope = match scope {
+            ResourceScope::Circuit(circuit_id) => Scope::Circuit(circuit_id.to_string()),
+            ResourceScope::Service {
+                circuit_id,
+                service_id,
+            } => Scope::Service {
+                circuit_id: circuit_id.to_string(),
+                service_id: service_id.to_string(),
+            },
+        };
+
+        self.check_permission(identity, permission_id, Some(&store_scope))
+            .map_err(|err| InternalError::from_source(Box::new(err)))
     }
 
     fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
@@ -71,12 +149,14 @@ impl AuthorizationHandler for RoleBasedAuthorizationHandler {
     }
 }
 
-#[cfg(all(test, feature = "sqlite",))]
+#[cfg(all(test, feature = "role-based-authorization-store-sqlite"))]
 mod tests {
     use super::*;
 
+    use std::collections::BTreeMap;
+
     use crate::rest_api::auth::rbac::store::{
-        AssignmentBuilder, DieselRoleBasedAuthorizationStore, RoleBuilder,
+        AssignmentBuilder, DieselRoleBasedAuthorizationStore, GroupBuilder, RoleBuilder,
     };
 
     use crate::migrations::run_sqlite_migrations;
@@ -139,6 +219,179 @@ mod tests {
         assert!(matches!(result, AuthorizationHandlerResult::Continue));
     }
 
+    /// This test checks that a role assigned to a circuit only allows requests for that circuit
+    /// and its services, and that a globally assigned role allows requests for any circuit.
+    #[test]
+    fn allow_scoped_assignment() {
+        let role_based_auth_store = create_role_based_authorization_store();
+
+        let role = RoleBuilder::new()
+            .with_id("circuit-writer".into())
+            .with_display_name("Circuit Writer".into())
+            .with_permissions(vec!["write".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let role = RoleBuilder::new()
+            .with_id("reader".into())
+            .with_display_name("Reader".into())
+            .with_permissions(vec!["read".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let mut scoped_roles = BTreeMap::new();
+        scoped_roles.insert(
+            Scope::Circuit("abcde-01234".into()),
+            vec!["circuit-writer".to_string()],
+        );
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(StoreIdentity::Key("abc123".into()))
+            .with_roles(vec!["reader".to_string()])
+            .with_scoped_roles(scoped_roles)
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let handler = RoleBasedAuthorizationHandler::new(role_based_auth_store);
+        let identity = Identity::Key("abc123".into());
+
+        let result = handler
+            .has_scoped_permission(
+                &identity,
+                "write",
+                &ResourceScope::Circuit("abcde-01234".into()),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        let result = handler
+            .has_scoped_permission(
+                &identity,
+                "write",
+                &ResourceScope::Service {
+                    circuit_id: "abcde-01234".into(),
+                    service_id: "a000".into(),
+                },
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        let result = handler
+            .has_scoped_permission(
+                &identity,
+                "write",
+                &ResourceScope::Circuit("fghij-56789".into()),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        let result = handler
+            .has_permission(&identity, "write")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        let result = handler
+            .has_scoped_permission(
+                &identity,
+                "read",
+                &ResourceScope::Circuit("fghij-56789".into()),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+    }
+
+    /// This test checks that the roles assigned to a group apply to the group's members, and that
+    /// a role grants the permissions of the roles it includes.
+    #[test]
+    fn allow_group_assignment_with_included_roles() {
+        let role_based_auth_store = create_role_based_authorization_store();
+
+        let role = RoleBuilder::new()
+            .with_id("reader".into())
+            .with_display_name("Reader".into())
+            .with_permissions(vec!["read".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let role = RoleBuilder::new()
+            .with_id("writer".into())
+            .with_display_name("Writer".into())
+            .with_permissions(vec!["write".to_string()])
+            .with_included_roles(vec!["reader".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let group = GroupBuilder::new()
+            .with_id("operators".into())
+            .with_display_name("Operators".into())
+            .with_members(vec![StoreIdentity::Key("abc123".into())])
+            .build()
+            .expect("Unable to build group");
+
+        role_based_auth_store
+            .add_group(group)
+            .expect("Unable to add group");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(StoreIdentity::Group("operators".into()))
+            .with_roles(vec!["writer".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let handler = RoleBasedAuthorizationHandler::new(role_based_auth_store);
+        let identity = Identity::Key("abc123".into());
+
+        let result = handler
+            .has_permission(&identity, "write")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        // The permission of the included role
+        let result = handler
+            .has_permission(&identity, "read")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        let result = handler
+            .has_scoped_permission(
+                &identity,
+                "read",
+                &ResourceScope::Circuit("abcde-01234".into()),
+            )
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        // An identity that is not a member of the group
+        let result = handler
+            .has_permission(&Identity::Key("def456".into()), "read")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+    }
+
     /// This test checks that an identity with an assigned role will return Allow when queried.
     fn test_allow_identity_with_assignment(identity: Identity, store_identity: StoreIdentity) {
         let role_based_auth_store = create_role_based_authorization_store();
@@ -0,0 +1,712 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /authorization/assignments` for listing assignments
+//! * `POST /authorization/assignments` for adding an assignment
+//! * `GET /authorization/assignments/{identity_type}/{identity}` for fetching an assignment
+//! * `PATCH /authorization/assignments/{identity_type}/{identity}` for updating an assignment
+//! * `DELETE /authorization/assignments/{identity_type}/{identity}` for removing an assignment
+
+use std::convert::TryInto;
+
+use crate::actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use crate::error::InvalidStateError;
+use crate::futures::{stream::Stream, Future, IntoFuture};
+use crate::protocol;
+use crate::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    auth::rbac::{
+        rest_api::{
+            resources::assignments::{
+                parse_identity, parse_scoped_roles, AssignmentPayload, AssignmentResponse,
+                AssignmentUpdatePayload, ListAssignmentResponse,
+            },
+            RBAC_READ_PERMISSION, RBAC_WRITE_PERMISSION,
+        },
+        store::{Assignment, Identity, RoleBasedAuthorizationStore},
+    },
+    paging::{get_response_paging_info, DEFAULT_LIMIT, DEFAULT_OFFSET},
+    ErrorResponse,
+};
+
+use super::error::SendableRoleBasedAuthorizationStoreError;
+
+#[derive(Deserialize)]
+struct PagingQuery {
+    #[serde(default = "default_limit")]
+    limit: usize,
+    #[serde(default = "default_offset")]
+    offset: usize,
+}
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+fn default_offset() -> usize {
+    DEFAULT_OFFSET
+}
+
+pub fn make_assignments_resource(
+    role_based_authorization_store: Box<dyn RoleBasedAuthorizationStore>,
+) -> Resource {
+    let list_store = role_based_authorization_store.clone();
+    let post_store = role_based_authorization_store;
+    Resource::build("/authorization/assignments")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::AUTHORIZATION_RBAC_ASSIGNMENTS_MIN,
+            protocol::AUTHORIZATION_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, RBAC_READ_PERMISSION, move |r, _| {
+            list_assignments(r, web::Data::new(list_store.clone()))
+        })
+        .add_method(Method::Post, RBAC_WRITE_PERMISSION, move |_, p| {
+            add_assignment(p, web::Data::new(post_store.clone()))
+        })
+}
+
+pub fn make_assignment_resource(
+    role_based_authorization_store: Box<dyn RoleBasedAuthorizationStore>,
+) -> Resource {
+    let get_store = role_based_authorization_store.clone();
+    let patch_store = role_based_authorization_store.clone();
+    let delete_store = role_based_authorization_store;
+    Resource::build("/authorization/assignments/{identity_type}/{identity}")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::AUTHORIZATION_RBAC_ASSIGNMENT_MIN,
+            protocol::AUTHORIZATION_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, RBAC_READ_PERMISSION, move |r, _| {
+            get_assignment(r, web::Data::new(get_store.clone()))
+        })
+        .add_method(Method::Patch, RBAC_WRITE_PERMISSION, move |r, p| {
+            patch_assignment(r, p, web::Data::new(patch_store.clone()))
+        })
+        .add_method(Method::Delete, RBAC_WRITE_PERMISSION, move |r, _| {
+            delete_assignment(r, web::Data::new(delete_store.clone()))
+        })
+}
+
+/// Gets the identity from the `identity_type` and `identity` path variables.
+fn identity_from_path(req: &HttpRequest) -> Result<Identity, InvalidStateError> {
+    let identity_type = req.match_info().get("identity_type").unwrap_or("");
+    let identity = req.match_info().get("identity").unwrap_or("").to_string();
+    parse_identity(identity_type, identity)
+}
+
+fn list_assignments(
+    req: HttpRequest,
+    role_based_authorization_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let web::Query(paging_query): web::Query<PagingQuery> =
+        match web::Query::from_query(req.query_string()) {
+            Ok(paging_query) => paging_query,
+            Err(_) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("Invalid query"))
+                        .into_future(),
+                )
+            }
+        };
+
+    let link = format!("{}?", req.uri().path());
+
+    Box::new(
+        web::block(move || {
+            let assignments = role_based_authorization_store
+                .list_assignments()
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)?;
+
+            let total = assignments.len();
+            let assignments = assignments
+                .skip(paging_query.offset)
+                .take(paging_query.limit)
+                .collect::<Vec<_>>();
+
+            Ok((assignments, link, paging_query, total))
+        })
+        .then(
+            |res: Result<_, BlockingError<SendableRoleBasedAuthorizationStoreError>>| match res {
+                Ok((assignments, link, paging_query, total)) => {
+                    Ok(HttpResponse::Ok().json(ListAssignmentResponse {
+                        data: assignments.iter().map(AssignmentResponse::from).collect(),
+                        paging: get_response_paging_info(
+                            Some(paging_query.limit),
+                            Some(paging_query.offset),
+                            &link,
+                            total,
+                        ),
+                    }))
+                }
+                Err(err) => {
+                    error!("Unable to list assignments: {}", err);
+                    Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+                }
+            },
+        ),
+    )
+}
+
+fn add_assignment(
+    payload: web::Payload,
+    role_based_authorization_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let assignment_res: Result<Assignment, _> =
+                    serde_json::from_slice::<AssignmentPayload>(&body)
+                        .map_err(|err| err.to_string())
+                        .and_then(|assignment_payload| {
+                            assignment_payload
+                                .try_into()
+                                .map_err(|err: InvalidStateError| err.to_string())
+                        });
+
+                match assignment_res {
+                    Ok(assignment) => Box::new(
+                        web::block(move || {
+                            role_based_authorization_store
+                                .add_assignment(assignment)
+                                .map_err(SendableRoleBasedAuthorizationStoreError::from)
+                        })
+                        .then(|res| {
+                            Ok(match res {
+                                Ok(_) => HttpResponse::Ok().finish(),
+                                Err(BlockingError::Error(
+                                    SendableRoleBasedAuthorizationStoreError::ConstraintViolation(
+                                        msg,
+                                    ),
+                                )) => HttpResponse::Conflict().json(ErrorResponse::conflict(&msg)),
+                                Err(err) => {
+                                    error!("Unable to add assignment: {}", err);
+                                    HttpResponse::InternalServerError()
+                                        .json(ErrorResponse::internal_error())
+                                }
+                            })
+                        }),
+                    )
+                        as Box<dyn Future<Item = HttpResponse, Error = Error>>,
+                    Err(err) => Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Invalid assignment payload: {}",
+                                err
+                            )))
+                            .into_future(),
+                    ),
+                }
+            }),
+    )
+}
+
+fn get_assignment(
+    req: HttpRequest,
+    role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let identity = match identity_from_path(&req) {
+        Ok(identity) => identity,
+        Err(err) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&err.to_string()))
+                    .into_future(),
+            )
+        }
+    };
+    Box::new(
+        web::block(move || {
+            role_based_auth_store
+                .get_assignment(&identity)
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)
+        })
+        .then(|assignment_res| {
+            Ok(match assignment_res {
+                Ok(Some(assignment)) => HttpResponse::Ok().json(json!({
+                    "data": AssignmentResponse::from(&assignment),
+                })),
+                Ok(None) => {
+                    HttpResponse::NotFound().json(ErrorResponse::not_found("Assignment not found"))
+                }
+                Err(err) => {
+                    error!("Unable to get assignment: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+fn patch_assignment(
+    req: HttpRequest,
+    payload: web::Payload,
+    role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let identity = match identity_from_path(&req) {
+        Ok(identity) => identity,
+        Err(err) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&err.to_string()))
+                    .into_future(),
+            )
+        }
+    };
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let assignment_update =
+                    match serde_json::from_slice::<AssignmentUpdatePayload>(&body) {
+                        Ok(assignment_update) => assignment_update,
+                        Err(err) => {
+                            return Box::new(
+                                HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request(&format!(
+                                        "Invalid assignment payload: {}",
+                                        err
+                                    )))
+                                    .into_future(),
+                            )
+                                as Box<dyn Future<Item = HttpResponse, Error = Error>>;
+                        }
+                    };
+
+                Box::new(
+                    web::block(move || {
+                        update_assignment(&**role_based_auth_store, &identity, assignment_update)
+                    })
+                    .then(|res| {
+                        use SendableRoleBasedAuthorizationStoreError::*;
+                        Ok(match res {
+                            Ok(_) => HttpResponse::Ok().finish(),
+                            Err(BlockingError::Error(InvalidState(err))) => {
+                                HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request(&err.to_string()))
+                            }
+                            Err(BlockingError::Error(ConstraintViolation(msg))) => {
+                                HttpResponse::NotFound().json(ErrorResponse::not_found(&msg))
+                            }
+                            Err(err) => {
+                                error!("Unable to update assignment: {}", err);
+                                HttpResponse::InternalServerError()
+                                    .json(ErrorResponse::internal_error())
+                            }
+                        })
+                    }),
+                ) as Box<dyn Future<Item = HttpResponse, Error = Error>>
+            }),
+    )
+}
+
+fn update_assignment(
+    role_based_auth_store: &dyn RoleBasedAuthorizationStore,
+    identity: &Identity,
+    assignment_update: AssignmentUpdatePayload,
+) -> Result<(), SendableRoleBasedAuthorizationStoreError> {
+    role_based_auth_store
+        .get_assignment(identity)
+        .map_err(SendableRoleBasedAuthorizationStoreError::from)
+        .and_then(|assignment_opt| {
+            if let Some(assignment) = assignment_opt {
+                let AssignmentUpdatePayload {
+                    roles,
+                    scoped_roles,
+                } = assignment_update;
+                let mut update_builder = assignment.into_update_builder();
+
+                if let Some(roles) = roles {
+                    update_builder = update_builder.with_roles(roles);
+                }
+                if let Some(scoped_roles) = scoped_roles {
+                    update_builder = update_builder.with_scoped_roles(
+                        parse_scoped_roles(scoped_roles)
+                            .map_err(SendableRoleBasedAuthorizationStoreError::InvalidState)?,
+                    );
+                }
+
+                let updated_assignment = update_builder
+                    .build()
+                    .map_err(SendableRoleBasedAuthorizationStoreError::InvalidState)?;
+
+                role_based_auth_store
+                    .update_assignment(updated_assignment)
+                    .map_err(SendableRoleBasedAuthorizationStoreError::from)
+            } else {
+                Err(
+                    SendableRoleBasedAuthorizationStoreError::ConstraintViolation(
+                        "assignment not found".into(),
+                    ),
+                )
+            }
+        })
+}
+
+fn delete_assignment(
+    req: HttpRequest,
+    role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let identity = match identity_from_path(&req) {
+        Ok(identity) => identity,
+        Err(err) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&err.to_string()))
+                    .into_future(),
+            )
+        }
+    };
+    Box::new(
+        web::block(move || {
+            role_based_auth_store
+                .remove_assignment(&identity)
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)
+        })
+        .then(|assignment_res| {
+            Ok(match assignment_res {
+                Ok(()) => HttpResponse::Ok().finish(),
+                Err(err) => {
+                    error!("Unable to delete assignment: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+
+    use reqwest::{blocking::Client, StatusCode, Url};
+    use serde_json::Value as JsonValue;
+
+    use crate::rest_api::actix_web_1::{RestApiBuilder, RestApiShutdownHandle};
+    use crate::rest_api::auth::rbac::store::{
+        AssignmentBuilder, MemoryRoleBasedAuthorizationStore, RoleBuilder, Scope,
+    };
+
+    /// Tests a POST /authorization/assignments request with global and scoped roles returns OK.
+    /// Verify that the assignment has been added by fetching it with a
+    /// GET /authorization/assignments/key/{identity} request.
+    #[test]
+    fn test_post_and_get_assignment_ok() {
+        let role_based_auth_store = create_store_with_role();
+        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
+            make_assignments_resource(role_based_auth_store.clone_box()),
+            make_assignment_resource(role_based_auth_store.clone_box()),
+        ]);
+
+        let url = Url::parse(&format!("http://{}/authorization/assignments", bind_url))
+            .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .post(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .json(&json!({
+                "identity": "abc123",
+                "identity_type": "key",
+                "roles": ["test-role"],
+                "scoped_roles": {
+                    "abcde-01234::a000": ["test-role"],
+                },
+            }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let url = Url::parse(&format!(
+            "http://{}/authorization/assignments/key/abc123",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .get(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            body.get("data").expect("No data field in response"),
+            &json!({
+                "identity": "abc123",
+                "identity_type": "key",
+                "roles": ["test-role"],
+                "scoped_roles": {
+                    "abcde-01234::a000": ["test-role"],
+                },
+            })
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Tests that a POST /authorization/assignments request with an invalid scope or identity
+    /// type returns BAD_REQUEST.
+    #[test]
+    fn test_post_assignment_bad_request() {
+        let role_based_auth_store = create_store_with_role();
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_assignments_resource(
+                role_based_auth_store.clone_box(),
+            )]);
+
+        let url = Url::parse(&format!("http://{}/authorization/assignments", bind_url))
+            .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .post(url.clone())
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .json(&json!({
+                "identity": "abc123",
+                "identity_type": "key",
+                "scoped_roles": {
+                    "abcde-01234::": ["test-role"],
+                },
+            }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = Client::new()
+            .post(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .json(&json!({
+                "identity": "abc123",
+                "identity_type": "unknown",
+                "roles": ["test-role"],
+            }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        assert!(role_based_auth_store
+            .get_assignment(&Identity::Key("abc123".into()))
+            .expect("Unable to get assignment")
+            .is_none());
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Tests that a PATCH /authorization/assignments/user/{identity} request replaces the scoped
+    /// roles of the assignment, and that a PATCH for an unknown identity returns NOT_FOUND.
+    #[test]
+    fn test_patch_assignment_ok() {
+        let role_based_auth_store = create_store_with_role();
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role".into()])
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_assignment_resource(
+                role_based_auth_store.clone_box(),
+            )]);
+
+        let url = Url::parse(&format!(
+            "http://{}/authorization/assignments/user/some-user-id",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .patch(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .json(&json!({
+                "roles": [],
+                "scoped_roles": {
+                    "abcde-01234": ["test-role"],
+                },
+            }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let assignment = role_based_auth_store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment not found");
+
+        let mut expected_scoped_roles = BTreeMap::new();
+        expected_scoped_roles.insert(
+            Scope::Circuit("abcde-01234".into()),
+            vec!["test-role".to_string()],
+        );
+        assert!(assignment.roles().is_empty());
+        assert_eq!(&expected_scoped_roles, assignment.scoped_roles());
+
+        let url = Url::parse(&format!(
+            "http://{}/authorization/assignments/user/unknown-user-id",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .patch(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .json(&json!({
+                "roles": ["test-role"],
+            }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Test that a DELETE to /authorization/assignments/key/{identity} removes the assignment.
+    #[test]
+    fn test_delete_assignment_ok() {
+        let role_based_auth_store = create_store_with_role();
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::Key("abc123".into()))
+            .with_roles(vec!["test-role".into()])
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_assignment_resource(
+                role_based_auth_store.clone_box(),
+            )]);
+
+        let url = Url::parse(&format!(
+            "http://{}/authorization/assignments/key/abc123",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .delete(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        assert!(role_based_auth_store
+            .get_assignment(&Identity::Key("abc123".into()))
+            .expect("Unable to get assignment")
+            .is_none());
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_store_with_role() -> MemoryRoleBasedAuthorizationStore {
+        let role_based_auth_store = MemoryRoleBasedAuthorizationStore::new();
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        role_based_auth_store
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = crate::rest_api::RestApiBind::Insecure("127.0.0.1:0".into());
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .build_insecure()
+            .expect("Failed to build REST API")
+            .run_insecure();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+}
@@ -0,0 +1,641 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! This module provides the following endpoints:
+//!
+//! * `GET /authorization/groups` for listing groups
+//! * `POST /authorization/groups` for adding a group
+//! * `GET /authorization/groups/{group_id}` for fetching a group
+//! * `PATCH /authorization/groups/{group_id}` for updating a group
+//! * `DELETE /authorization/groups/{group_id}` for removing a group and its assignment
+
+use std::convert::TryInto;
+
+use crate::actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use crate::error::InvalidStateError;
+use crate::futures::{stream::Stream, Future, IntoFuture};
+use crate::protocol;
+use crate::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    auth::rbac::{
+        rest_api::{
+            resources::groups::{
+                parse_members, GroupPayload, GroupResponse, GroupUpdatePayload, ListGroupResponse,
+            },
+            RBAC_READ_PERMISSION, RBAC_WRITE_PERMISSION,
+        },
+        store::{Group, RoleBasedAuthorizationStore},
+    },
+    paging::{get_response_paging_info, DEFAULT_LIMIT, DEFAULT_OFFSET},
+    ErrorResponse,
+};
+
+use super::error::SendableRoleBasedAuthorizationStoreError;
+
+#[derive(Deserialize)]
+struct PagingQuery {
+    #[serde(default = "default_limit")]
+    limit: usize,
+    #[serde(default = "default_offset")]
+    offset: usize,
+}
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+fn default_offset() -> usize {
+    DEFAULT_OFFSET
+}
+
+pub fn make_groups_resource(
+    role_based_authorization_store: Box<dyn RoleBasedAuthorizationStore>,
+) -> Resource {
+    let list_store = role_based_authorization_store.clone();
+    let post_store = role_based_authorization_store;
+    Resource::build("/authorization/groups")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::AUTHORIZATION_RBAC_GROUPS_MIN,
+            protocol::AUTHORIZATION_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, RBAC_READ_PERMISSION, move |r, _| {
+            list_groups(r, web::Data::new(list_store.clone()))
+        })
+        .add_method(Method::Post, RBAC_WRITE_PERMISSION, move |_, p| {
+            add_group(p, web::Data::new(post_store.clone()))
+        })
+}
+
+pub fn make_group_resource(
+    role_based_authorization_store: Box<dyn RoleBasedAuthorizationStore>,
+) -> Resource {
+    let get_store = role_based_authorization_store.clone();
+    let patch_store = role_based_authorization_store.clone();
+    let delete_store = role_based_authorization_store;
+    Resource::build("/authorization/groups/{group_id}")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            protocol::AUTHORIZATION_RBAC_GROUP_MIN,
+            protocol::AUTHORIZATION_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, RBAC_READ_PERMISSION, move |r, _| {
+            get_group(r, web::Data::new(get_store.clone()))
+        })
+        .add_method(Method::Patch, RBAC_WRITE_PERMISSION, move |r, p| {
+            patch_group(r, p, web::Data::new(patch_store.clone()))
+        })
+        .add_method(Method::Delete, RBAC_WRITE_PERMISSION, move |r, _| {
+            delete_group(r, web::Data::new(delete_store.clone()))
+        })
+}
+
+fn list_groups(
+    req: HttpRequest,
+    role_based_authorization_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let web::Query(paging_query): web::Query<PagingQuery> =
+        match web::Query::from_query(req.query_string()) {
+            Ok(paging_query) => paging_query,
+            Err(_) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request("Invalid query"))
+                        .into_future(),
+                )
+            }
+        };
+
+    let link = format!("{}?", req.uri().path());
+
+    Box::new(
+        web::block(move || {
+            let groups = role_based_authorization_store
+                .list_groups()
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)?;
+
+            let total = groups.len();
+            let groups = groups
+                .skip(paging_query.offset)
+                .take(paging_query.limit)
+                .collect::<Vec<_>>();
+
+            Ok((groups, link, paging_query, total))
+        })
+        .then(
+            |res: Result<_, BlockingError<SendableRoleBasedAuthorizationStoreError>>| match res {
+                Ok((groups, link, paging_query, total)) => {
+                    Ok(HttpResponse::Ok().json(ListGroupResponse {
+                        data: groups.iter().map(GroupResponse::from).collect(),
+                        paging: get_response_paging_info(
+                            Some(paging_query.limit),
+                            Some(paging_query.offset),
+                            &link,
+                            total,
+                        ),
+                    }))
+                }
+                Err(err) => {
+                    error!("Unable to list groups: {}", err);
+                    Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+                }
+            },
+        ),
+    )
+}
+
+fn add_group(
+    payload: web::Payload,
+    role_based_authorization_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let group_res: Result<Group, _> = serde_json::from_slice::<GroupPayload>(&body)
+                    .map_err(|err| err.to_string())
+                    .and_then(|group_payload| {
+                        group_payload
+                            .try_into()
+                            .map_err(|err: InvalidStateError| err.to_string())
+                    });
+
+                match group_res {
+                    Ok(group) => Box::new(
+                        web::block(move || {
+                            role_based_authorization_store
+                                .add_group(group)
+                                .map_err(SendableRoleBasedAuthorizationStoreError::from)
+                        })
+                        .then(|res| {
+                            Ok(match res {
+                                Ok(_) => HttpResponse::Ok().finish(),
+                                Err(BlockingError::Error(
+                                    SendableRoleBasedAuthorizationStoreError::ConstraintViolation(
+                                        msg,
+                                    ),
+                                )) => HttpResponse::Conflict().json(ErrorResponse::conflict(&msg)),
+                                Err(err) => {
+                                    error!("Unable to add group: {}", err);
+                                    HttpResponse::InternalServerError()
+                                        .json(ErrorResponse::internal_error())
+                                }
+                            })
+                        }),
+                    )
+                        as Box<dyn Future<Item = HttpResponse, Error = Error>>,
+                    Err(err) => Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&format!(
+                                "Invalid group payload: {}",
+                                err
+                            )))
+                            .into_future(),
+                    ),
+                }
+            }),
+    )
+}
+
+fn get_group(
+    req: HttpRequest,
+    role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let group_id = req.match_info().get("group_id").unwrap_or("").to_string();
+    Box::new(
+        web::block(move || {
+            role_based_auth_store
+                .get_group(&group_id)
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)
+        })
+        .then(|group_res| {
+            Ok(match group_res {
+                Ok(Some(group)) => HttpResponse::Ok().json(json!({
+                    "data": GroupResponse::from(&group),
+                })),
+                Ok(None) => {
+                    HttpResponse::NotFound().json(ErrorResponse::not_found("Group not found"))
+                }
+                Err(err) => {
+                    error!("Unable to get group: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+fn patch_group(
+    req: HttpRequest,
+    payload: web::Payload,
+    role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let group_id = req.match_info().get("group_id").unwrap_or("").to_string();
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let group_update = match serde_json::from_slice::<GroupUpdatePayload>(&body) {
+                    Ok(group_update) => group_update,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid group payload: {}",
+                                    err
+                                )))
+                                .into_future(),
+                        )
+                            as Box<dyn Future<Item = HttpResponse, Error = Error>>;
+                    }
+                };
+
+                Box::new(
+                    web::block(move || {
+                        update_group(&**role_based_auth_store, &group_id, group_update)
+                    })
+                    .then(|res| {
+                        use SendableRoleBasedAuthorizationStoreError::*;
+                        Ok(match res {
+                            Ok(_) => HttpResponse::Ok().finish(),
+                            Err(BlockingError::Error(InvalidState(err))) => {
+                                HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request(&err.to_string()))
+                            }
+                            Err(BlockingError::Error(ConstraintViolation(msg))) => {
+                                HttpResponse::NotFound().json(ErrorResponse::not_found(&msg))
+                            }
+                            Err(err) => {
+                                error!("Unable to update group: {}", err);
+                                HttpResponse::InternalServerError()
+                                    .json(ErrorResponse::internal_error())
+                            }
+                        })
+                    }),
+                ) as Box<dyn Future<Item = HttpResponse, Error = Error>>
+            }),
+    )
+}
+
+fn update_group(
+    role_based_auth_store: &dyn RoleBasedAuthorizationStore,
+    group_id: &str,
+    group_update: GroupUpdatePayload,
+) -> Result<(), SendableRoleBasedAuthorizationStoreError> {
+    role_based_auth_store
+        .get_group(group_id)
+        .map_err(SendableRoleBasedAuthorizationStoreError::from)
+        .and_then(|group_opt| {
+            if let Some(group) = group_opt {
+                let GroupUpdatePayload {
+                    display_name,
+                    members,
+                } = group_update;
+                let mut update_builder = group.into_update_builder();
+
+                if let Some(display_name) = display_name {
+                    update_builder = update_builder.with_display_name(display_name);
+                }
+                if let Some(members) = members {
+                    update_builder = update_builder.with_members(
+                        parse_members(members)
+                            .map_err(SendableRoleBasedAuthorizationStoreError::InvalidState)?,
+                    );
+                }
+
+                let updated_group = update_builder
+                    .build()
+                    .map_err(SendableRoleBasedAuthorizationStoreError::InvalidState)?;
+
+                role_based_auth_store
+                    .update_group(updated_group)
+                    .map_err(SendableRoleBasedAuthorizationStoreError::from)
+            } else {
+                Err(
+                    SendableRoleBasedAuthorizationStoreError::ConstraintViolation(format!(
+                        "group {} not found",
+                        group_id
+                    )),
+                )
+            }
+        })
+}
+
+fn delete_group(
+    req: HttpRequest,
+    role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let group_id = req.match_info().get("group_id").unwrap_or("").to_string();
+    Box::new(
+        web::block(move || {
+            role_based_auth_store
+                .remove_group(&group_id)
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)
+        })
+        .then(|group_res| {
+            Ok(match group_res {
+                Ok(()) => HttpResponse::Ok().finish(),
+                Err(err) => {
+                    error!("Unable to delete group: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::{blocking::Client, StatusCode, Url};
+    use serde_json::Value as JsonValue;
+
+    use crate::rest_api::actix_web_1::{RestApiBuilder, RestApiShutdownHandle};
+    use crate::rest_api::auth::rbac::store::{
+        AssignmentBuilder, GroupBuilder, Identity, MemoryRoleBasedAuthorizationStore, RoleBuilder,
+    };
+
+    /// Tests a POST /authorization/groups request returns OK. Verify that the group has been
+    /// added by fetching it with a GET /authorization/groups/{group_id} request.
+    #[test]
+    fn test_post_and_get_group_ok() {
+        let role_based_auth_store = MemoryRoleBasedAuthorizationStore::new();
+        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
+            make_groups_resource(role_based_auth_store.clone_box()),
+            make_group_resource(role_based_auth_store.clone_box()),
+        ]);
+
+        let url = Url::parse(&format!("http://{}/authorization/groups", bind_url))
+            .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .post(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .json(&json!({
+                "group_id": "operators",
+                "display_name": "Operators",
+                "members": [
+                    {"identity": "abc123", "identity_type": "key"},
+                    {"identity": "some-user-id", "identity_type": "user"},
+                ],
+            }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let url = Url::parse(&format!(
+            "http://{}/authorization/groups/operators",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .get(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            body.get("data").expect("No data field in response"),
+            &json!({
+                "group_id": "operators",
+                "display_name": "Operators",
+                "members": [
+                    {"identity": "abc123", "identity_type": "key"},
+                    {"identity": "some-user-id", "identity_type": "user"},
+                ],
+            })
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Tests that a POST /authorization/groups request with a group as a member returns
+    /// BAD_REQUEST.
+    #[test]
+    fn test_post_group_bad_request() {
+        let role_based_auth_store = MemoryRoleBasedAuthorizationStore::new();
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_groups_resource(
+                role_based_auth_store.clone_box(),
+            )]);
+
+        let url = Url::parse(&format!("http://{}/authorization/groups", bind_url))
+            .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .post(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .json(&json!({
+                "group_id": "operators",
+                "display_name": "Operators",
+                "members": [
+                    {"identity": "admins", "identity_type": "group"},
+                ],
+            }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Tests that a PATCH /authorization/groups/{group_id} request updates the group's members,
+    /// and that a request for an unknown group returns NOT_FOUND.
+    #[test]
+    fn test_patch_group_ok() {
+        let role_based_auth_store = create_store_with_group();
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_group_resource(role_based_auth_store.clone_box())]);
+
+        let url = Url::parse(&format!(
+            "http://{}/authorization/groups/operators",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .patch(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .json(&json!({
+                "members": [
+                    {"identity": "def456", "identity_type": "key"},
+                ],
+            }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let group = role_based_auth_store
+            .get_group("operators")
+            .expect("Unable to get group")
+            .expect("Group not found");
+        assert_eq!("Operators", group.display_name());
+        assert_eq!(&[Identity::Key("def456".into())], group.members());
+
+        let url = Url::parse(&format!("http://{}/authorization/groups/unknown", bind_url))
+            .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .patch(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .json(&json!({
+                "display_name": "Unknown",
+            }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Tests that a DELETE /authorization/groups/{group_id} request removes the group and the
+    /// group's assignment.
+    #[test]
+    fn test_delete_group_ok() {
+        let role_based_auth_store = create_store_with_group();
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::Group("operators".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_group_resource(role_based_auth_store.clone_box())]);
+
+        let url = Url::parse(&format!(
+            "http://{}/authorization/groups/operators",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .delete(url)
+            .header(
+                "SplinterProtocolVersion",
+                protocol::AUTHORIZATION_PROTOCOL_VERSION,
+            )
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        assert!(role_based_auth_store
+            .get_group("operators")
+            .expect("Unable to get group")
+            .is_none());
+        assert!(role_based_auth_store
+            .get_assignment(&Identity::Group("operators".into()))
+            .expect("Unable to get assignment")
+            .is_none());
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_store_with_group() -> MemoryRoleBasedAuthorizationStore {
+        let role_based_auth_store = MemoryRoleBasedAuthorizationStore::new();
+
+        let group = GroupBuilder::new()
+            .with_id("operators".into())
+            .with_display_name("Operators".into())
+            .with_members(vec![Identity::Key("abc123".into())])
+            .build()
+            .expect("Unable to build group");
+
+        role_based_auth_store
+            .add_group(group)
+            .expect("Unable to add group");
+
+        role_based_auth_store
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = crate::rest_api::RestApiBind::Insecure("127.0.0.1:0".into());
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .build_insecure()
+            .expect("Failed to build REST API")
+            .run_insecure();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+}
@@ -14,7 +14,9 @@
 
 //! Actix Web 1.x RBAC REST Resource implementations.
 
+mod assignments;
 mod error;
+mod groups;
 mod roles;
 
 use crate::rest_api::{
@@ -40,6 +42,10 @@ impl RestResourceProvider for RoleBasedAuthorizationResourceProvider {
         vec![
             roles::make_roles_resource(self.role_based_authorization_store.clone()),
             roles::make_role_resource(self.role_based_authorization_store.clone()),
+            assignments::make_assignments_resource(self.role_based_authorization_store.clone()),
+            assignments::make_assignment_resource(self.role_based_authorization_store.clone()),
+            groups::make_groups_resource(self.role_based_authorization_store.clone()),
+            groups::make_group_resource(self.role_based_authorization_store.clone()),
         ]
     }
 }
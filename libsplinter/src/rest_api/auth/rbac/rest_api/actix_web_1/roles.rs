@@ -183,6 +183,10 @@ fn add_role(
                                         msg,
                                     ),
                                 )) => HttpResponse::Conflict().json(ErrorResponse::conflict(&msg)),
+                                Err(BlockingError::Error(
+                                    SendableRoleBasedAuthorizationStoreError::InvalidState(err),
+                                )) => HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request(&err.to_string())),
                                 Err(err) => {
                                     error!("Unable to add role: {}", err);
                                     HttpResponse::InternalServerError()
@@ -303,6 +307,7 @@ fn update_role(
                 let RoleUpdatePayload {
                     display_name,
                     permissions,
+                    included_roles,
                 } = role_update;
                 let mut update_builder = role.into_update_builder();
 
@@ -312,6 +317,9 @@ fn update_role(
                 if let Some(permissions) = permissions {
                     update_builder = update_builder.with_permissions(permissions);
                 }
+                if let Some(included_roles) = included_roles {
+                    update_builder = update_builder.with_included_roles(included_roles);
+                }
 
                 let updated_role = update_builder
                     .build()
@@ -366,7 +374,7 @@ mod tests {
 
     use crate::error::{ConstraintViolationError, ConstraintViolationType};
     use crate::rest_api::auth::rbac::store::{
-        Assignment, Identity, Role, RoleBasedAuthorizationStoreError, RoleBuilder,
+        Assignment, Group, Identity, Role, RoleBasedAuthorizationStoreError, RoleBuilder, Scope,
     };
     use crate::rest_api::{
         actix_web_1::{RestApiBuilder, RestApiShutdownHandle},
@@ -440,6 +448,7 @@ mod tests {
                 role_id: "test-role-1",
                 display_name: "Test Role 1",
                 permissions: &["a".to_string(), "b".to_string(), "c".to_string()],
+                included_roles: &[],
             })
             .expect("Failed to convert to value"),
             json_roles.get(0).expect("no first item")
@@ -449,6 +458,7 @@ mod tests {
                 role_id: "test-role-2",
                 display_name: "Test Role 2",
                 permissions: &["x".to_string(), "y".to_string(), "z".to_string()],
+                included_roles: &[],
             })
             .expect("Failed to convert to value"),
             json_roles.get(1).expect("no first item")
@@ -522,6 +532,7 @@ mod tests {
                     role_id: &format!("test-role-{:0>3}", i),
                     display_name: &format!("Test Role {}", i),
                     permissions: &[format!("perm-{}", i)],
+                    included_roles: &[],
                 })
                 .expect("Failed to convert to value"),
                 json_roles.get(i).expect("no first item")
@@ -573,6 +584,7 @@ mod tests {
                 role_id: "test-role-100",
                 display_name: "Test Role 100",
                 permissions: &["perm-100".to_string()],
+                included_roles: &[],
             })
             .expect("Failed to convert to value"),
             json_roles.get(0).expect("no first item")
@@ -637,6 +649,7 @@ mod tests {
                 role_id: "new_test_role",
                 display_name: "New Test Display Name",
                 permissions: &["my-permission-1".to_string(), "my-permission-2".to_string()],
+                included_roles: &[],
             })
             .expect("Failed to convert to value"),
             json_roles.get(0).expect("no first item")
@@ -747,6 +760,7 @@ mod tests {
                     "role_id": "test-role-1",
                     "display_name": "Test Role 1",
                     "permissions": ["a", "b", "c"],
+                    "included_roles": [],
                 }
             }),
             body
@@ -883,6 +897,7 @@ mod tests {
                     "role_id": "test-role-1",
                     "display_name": "New Test Display Name",
                     "permissions": ["a", "b", "c"],
+                    "included_roles": [],
                 }
             }),
             body
@@ -920,6 +935,7 @@ mod tests {
                     "role_id": "test-role-1",
                     "display_name": "New Test Display Name",
                     "permissions": ["new-perm-1", "new-perm-2"],
+                    "included_roles": [],
                 }
             }),
             body
@@ -958,6 +974,7 @@ mod tests {
                     "role_id": "test-role-1",
                     "display_name": "Better Display Name",
                     "permissions": ["updated-perm-1", "updated-perm-2"],
+                    "included_roles": [],
                 }
             }),
             body
@@ -1232,6 +1249,15 @@ mod tests {
             unimplemented!()
         }
 
+        fn get_assigned_roles_in_scope(
+            &self,
+            _identity: &Identity,
+            _scope: &Scope,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
         fn list_assignments(
             &self,
         ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
@@ -1260,6 +1286,37 @@ mod tests {
             unimplemented!()
         }
 
+        fn get_group(&self, _id: &str) -> Result<Option<Group>, RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn list_groups(
+            &self,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
+        fn list_groups_for_identity(
+            &self,
+            _identity: &Identity,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
+        fn add_group(&self, _group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn update_group(&self, _group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn remove_group(&self, _group_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
             Box::new(self.clone())
         }
@@ -0,0 +1,123 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use crate::error::InvalidStateError;
+use crate::rest_api::{
+    auth::rbac::store::{Assignment, AssignmentBuilder, Identity, Scope},
+    paging::Paging,
+};
+
+#[derive(Serialize)]
+pub struct ListAssignmentResponse<'a> {
+    pub data: Vec<AssignmentResponse<'a>>,
+    pub paging: Paging,
+}
+
+#[derive(Serialize)]
+pub struct AssignmentResponse<'a> {
+    pub identity: &'a str,
+    pub identity_type: &'a str,
+    pub roles: &'a [String],
+    pub scoped_roles: BTreeMap<String, &'a [String]>,
+}
+
+#[derive(Deserialize)]
+pub struct AssignmentPayload {
+    pub identity: String,
+    pub identity_type: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub scoped_roles: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+pub struct AssignmentUpdatePayload {
+    pub roles: Option<Vec<String>>,
+    pub scoped_roles: Option<BTreeMap<String, Vec<String>>>,
+}
+
+impl<'a> From<&'a Assignment> for AssignmentResponse<'a> {
+    fn from(assignment: &'a Assignment) -> Self {
+        let (identity, identity_type) = identity_parts(assignment.identity());
+
+        Self {
+            identity,
+            identity_type,
+            roles: assignment.roles(),
+            scoped_roles: assignment
+                .scoped_roles()
+                .iter()
+                .map(|(scope, roles)| (scope.to_string(), roles.as_slice()))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<AssignmentPayload> for Assignment {
+    type Error = InvalidStateError;
+
+    fn try_from(payload: AssignmentPayload) -> Result<Self, Self::Error> {
+        AssignmentBuilder::new()
+            .with_identity(parse_identity(&payload.identity_type, payload.identity)?)
+            .with_roles(payload.roles)
+            .with_scoped_roles(parse_scoped_roles(payload.scoped_roles)?)
+            .build()
+    }
+}
+
+/// Returns the identity and its type, either `key`, `user` or `group`.
+pub fn identity_parts(identity: &Identity) -> (&str, &str) {
+    match identity {
+        Identity::Key(key) => (key.as_str(), "key"),
+        Identity::User(user_id) => (user_id.as_str(), "user"),
+        Identity::Group(group_id) => (group_id.as_str(), "group"),
+    }
+}
+
+/// Converts an identity type, either `key`, `user` or `group`, and an identity into an
+/// `Identity`.
+pub fn parse_identity(
+    identity_type: &str,
+    identity: String,
+) -> Result<Identity, InvalidStateError> {
+    match identity_type {
+        "key" => Ok(Identity::Key(identity)),
+        "user" => Ok(Identity::User(identity)),
+        "group" => Ok(Identity::Group(identity)),
+        _ => Err(InvalidStateError::with_message(format!(
+            "Invalid identity type \"{}\"; must be \"key\", \"user\" or \"group\"",
+            identity_type
+        ))),
+    }
+}
+
+/// Parses the scopes of the given scoped roles, which must be of the form `<circuit_id>` or
+/// `<circuit_id>::<service_id>`.
+pub fn parse_scoped_roles(
+    scoped_roles: BTreeMap<String, Vec<String>>,
+) -> Result<BTreeMap<Scope, Vec<String>>, InvalidStateError> {
+    scoped_roles
+        .into_iter()
+        .map(|(scope, roles)| {
+            scope
+                .parse::<Scope>()
+                .map(|scope| (scope, roles))
+                .map_err(|err| InvalidStateError::with_message(err.to_string()))
+        })
+        .collect()
+}
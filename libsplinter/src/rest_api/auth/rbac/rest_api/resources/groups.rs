@@ -0,0 +1,111 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! REST API Response structs for identity groups.
+
+use std::convert::{TryFrom, TryInto};
+
+use crate::error::InvalidStateError;
+use crate::rest_api::{
+    auth::rbac::store::{Group, GroupBuilder, Identity},
+    paging::Paging,
+};
+
+use super::assignments::{identity_parts, parse_identity};
+
+#[derive(Serialize)]
+pub struct ListGroupResponse<'a> {
+    pub data: Vec<GroupResponse<'a>>,
+    pub paging: Paging,
+}
+
+#[derive(Serialize)]
+pub struct GroupResponse<'a> {
+    pub group_id: &'a str,
+    pub display_name: &'a str,
+    pub members: Vec<MemberResponse<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct MemberResponse<'a> {
+    pub identity: &'a str,
+    pub identity_type: &'a str,
+}
+
+#[derive(Deserialize)]
+pub struct GroupPayload {
+    pub group_id: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub members: Vec<MemberPayload>,
+}
+
+#[derive(Deserialize)]
+pub struct GroupUpdatePayload {
+    pub display_name: Option<String>,
+    pub members: Option<Vec<MemberPayload>>,
+}
+
+#[derive(Deserialize)]
+pub struct MemberPayload {
+    pub identity: String,
+    pub identity_type: String,
+}
+
+impl<'a> From<&'a Group> for GroupResponse<'a> {
+    fn from(group: &'a Group) -> Self {
+        Self {
+            group_id: group.id(),
+            display_name: group.display_name(),
+            members: group
+                .members()
+                .iter()
+                .map(|member| {
+                    let (identity, identity_type) = identity_parts(member);
+                    MemberResponse {
+                        identity,
+                        identity_type,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<MemberPayload> for Identity {
+    type Error = InvalidStateError;
+
+    fn try_from(payload: MemberPayload) -> Result<Self, Self::Error> {
+        parse_identity(&payload.identity_type, payload.identity)
+    }
+}
+
+impl TryFrom<GroupPayload> for Group {
+    type Error = InvalidStateError;
+
+    fn try_from(payload: GroupPayload) -> Result<Self, Self::Error> {
+        GroupBuilder::new()
+            .with_id(payload.group_id)
+            .with_display_name(payload.display_name)
+            .with_members(parse_members(payload.members)?)
+            .build()
+    }
+}
+
+/// Converts the given member payloads into identities.
+pub fn parse_members(members: Vec<MemberPayload>) -> Result<Vec<Identity>, InvalidStateError> {
+    members
+        .into_iter()
+        .map(|member| member.try_into())
+        .collect()
+}
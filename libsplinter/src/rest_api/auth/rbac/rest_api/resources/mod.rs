@@ -14,4 +14,6 @@
 
 //! Web-framework-agnostic resources.
 
+pub mod assignments;
+pub mod groups;
 pub mod roles;
@@ -33,19 +33,24 @@ pub struct RoleResponse<'a> {
     pub role_id: &'a str,
     pub display_name: &'a str,
     pub permissions: &'a [String],
+    pub included_roles: &'a [String],
 }
 
 #[derive(Deserialize)]
 pub struct RolePayload {
     pub role_id: String,
     pub display_name: String,
+    #[serde(default)]
     pub permissions: Vec<String>,
+    #[serde(default)]
+    pub included_roles: Vec<String>,
 }
 
 #[derive(Deserialize)]
 pub struct RoleUpdatePayload {
     pub display_name: Option<String>,
     pub permissions: Option<Vec<String>>,
+    pub included_roles: Option<Vec<String>>,
 }
 
 impl<'a> From<&'a Role> for RoleResponse<'a> {
@@ -54,6 +59,7 @@ impl<'a> From<&'a Role> for RoleResponse<'a> {
             role_id: role.id(),
             display_name: role.display_name(),
             permissions: role.permissions(),
+            included_roles: role.included_roles(),
         }
     }
 }
@@ -66,6 +72,7 @@ impl TryFrom<RolePayload> for Role {
             .with_id(payload.role_id)
             .with_display_name(payload.display_name)
             .with_permissions(payload.permissions)
+            .with_included_roles(payload.included_roles)
             .build()
     }
 }
@@ -16,6 +16,7 @@ mod models;
 mod operations;
 mod schema;
 
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 
 use crate::error::{
@@ -25,20 +26,27 @@ use crate::error::{
 use diesel::r2d2::{ConnectionManager, Pool};
 
 use super::{
-    Assignment, AssignmentBuilder, Identity, Role, RoleBasedAuthorizationStore,
-    RoleBasedAuthorizationStoreError, RoleBuilder,
+    Assignment, AssignmentBuilder, Group, GroupBuilder, Identity, Role,
+    RoleBasedAuthorizationStore, RoleBasedAuthorizationStoreError, RoleBuilder, Scope,
 };
 
 use operations::add_assignment::RoleBasedAuthorizationStoreAddAssignment as _;
+use operations::add_group::RoleBasedAuthorizationStoreAddGroup as _;
 use operations::add_role::RoleBasedAuthorizationStoreAddRole as _;
 use operations::get_assigned_roles::RoleBasedAuthorizationStoreGetAssignedRoles as _;
+use operations::get_assigned_roles_in_scope::RoleBasedAuthorizationStoreGetAssignedRolesInScope as _;
 use operations::get_assignment::RoleBasedAuthorizationStoreGetAssignment as _;
+use operations::get_group::RoleBasedAuthorizationStoreGetGroup as _;
 use operations::get_role::RoleBasedAuthorizationStoreGetRole as _;
 use operations::list_assignments::RoleBasedAuthorizationStoreListAssignments as _;
+use operations::list_groups::RoleBasedAuthorizationStoreListGroups as _;
+use operations::list_groups_for_identity::RoleBasedAuthorizationStoreListGroupsForIdentity as _;
 use operations::list_roles::RoleBasedAuthorizationStoreListRoles as _;
 use operations::remove_assignment::RoleBasedAuthorizationStoreRemoveAssignment as _;
+use operations::remove_group::RoleBasedAuthorizationStoreRemoveGroup as _;
 use operations::remove_role::RoleBasedAuthorizationStoreRemoveRole as _;
 use operations::update_assignment::RoleBasedAuthorizationStoreUpdateAssignment as _;
+use operations::update_group::RoleBasedAuthorizationStoreUpdateGroup as _;
 use operations::update_role::RoleBasedAuthorizationStoreUpdateRole as _;
 use operations::RoleBasedAuthorizationStoreOperations;
 
@@ -53,7 +61,7 @@ impl<C: diesel::Connection + 'static> DieselRoleBasedAuthorizationStore<C> {
     }
 }
 
-#[cfg(feature = "sqlite")]
+#[cfg(feature = "role-based-authorization-store-sqlite")]
 impl RoleBasedAuthorizationStore
     for DieselRoleBasedAuthorizationStore<diesel::sqlite::SqliteConnection>
 {
@@ -119,6 +127,17 @@ impl RoleBasedAuthorizationStore
         RoleBasedAuthorizationStoreOperations::new(&*connection).get_assigned_roles(identity)
     }
 
+    /// Returns the roles that apply to the given Identity within the given scope.
+    fn get_assigned_roles_in_scope(
+        &self,
+        identity: &Identity,
+        scope: &Scope,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection)
+            .get_assigned_roles_in_scope(identity, scope)
+    }
+
     /// Lists all assignments.
     fn list_assignments(
         &self,
@@ -168,6 +187,55 @@ impl RoleBasedAuthorizationStore
         RoleBasedAuthorizationStoreOperations::new(&*connection).remove_assignment(identity)
     }
 
+    /// Returns the group for the given ID, if one exists.
+    fn get_group(&self, id: &str) -> Result<Option<Group>, RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).get_group(id)
+    }
+
+    /// Lists all groups.
+    fn list_groups(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).list_groups()
+    }
+
+    /// Lists the groups that the given Identity is a member of.
+    fn list_groups_for_identity(
+        &self,
+        identity: &Identity,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).list_groups_for_identity(identity)
+    }
+
+    /// Adds a group.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConstraintViolation` error if a duplicate group ID is added.
+    fn add_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).add_group(group)
+    }
+
+    /// Updates a group.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `InvalidState` error if the group does not exist.
+    fn update_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).update_group(group)
+    }
+
+    /// Removes a group, along with the group's assignment.
+    fn remove_group(&self, group_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).remove_group(group_id)
+    }
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
         Box::new(DieselRoleBasedAuthorizationStore {
@@ -240,6 +308,17 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         RoleBasedAuthorizationStoreOperations::new(&*connection).get_assigned_roles(identity)
     }
 
+    /// Returns the roles that apply to the given Identity within the given scope.
+    fn get_assigned_roles_in_scope(
+        &self,
+        identity: &Identity,
+        scope: &Scope,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection)
+            .get_assigned_roles_in_scope(identity, scope)
+    }
+
     /// Lists all assignments.
     fn list_assignments(
         &self,
@@ -289,6 +368,55 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         RoleBasedAuthorizationStoreOperations::new(&*connection).remove_assignment(identity)
     }
 
+    /// Returns the group for the given ID, if one exists.
+    fn get_group(&self, id: &str) -> Result<Option<Group>, RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).get_group(id)
+    }
+
+    /// Lists all groups.
+    fn list_groups(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).list_groups()
+    }
+
+    /// Lists the groups that the given Identity is a member of.
+    fn list_groups_for_identity(
+        &self,
+        identity: &Identity,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).list_groups_for_identity(identity)
+    }
+
+    /// Adds a group.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConstraintViolation` error if a duplicate group ID is added.
+    fn add_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).add_group(group)
+    }
+
+    /// Updates a group.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `InvalidState` error if the group does not exist.
+    fn update_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).update_group(group)
+    }
+
+    /// Removes a group, along with the group's assignment.
+    fn remove_group(&self, group_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let connection = self.connection_pool.get()?;
+        RoleBasedAuthorizationStoreOperations::new(&*connection).remove_group(group_id)
+    }
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
         Box::new(DieselRoleBasedAuthorizationStore {
@@ -297,9 +425,15 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
     }
 }
 
-impl From<Role> for (models::RoleModel, Vec<models::RolePermissionModel>) {
+impl From<Role>
+    for (
+        models::RoleModel,
+        Vec<models::RolePermissionModel>,
+        Vec<models::RoleInclusionModel>,
+    )
+{
     fn from(role: Role) -> Self {
-        let (id, display_name, permissions) = role.into_parts();
+        let (id, display_name, permissions, included_roles) = role.into_parts();
 
         let perm_models = permissions
             .into_iter()
@@ -308,15 +442,38 @@ impl From<Role> for (models::RoleModel, Vec<models::RolePermissionModel>) {
                 permission,
             })
             .collect::<Vec<_>>();
-        (models::RoleModel { id, display_name }, perm_models)
+
+        let inclusion_models = included_roles
+            .into_iter()
+            .map(|included_role_id| models::RoleInclusionModel {
+                role_id: id.clone(),
+                included_role_id,
+            })
+            .collect::<Vec<_>>();
+
+        (
+            models::RoleModel { id, display_name },
+            perm_models,
+            inclusion_models,
+        )
     }
 }
 
-impl TryFrom<(models::RoleModel, Vec<models::RolePermissionModel>)> for Role {
+impl
+    TryFrom<(
+        models::RoleModel,
+        Vec<models::RolePermissionModel>,
+        Vec<models::RoleInclusionModel>,
+    )> for Role
+{
     type Error = InvalidStateError;
 
     fn try_from(
-        (role_model, perm_models): (models::RoleModel, Vec<models::RolePermissionModel>),
+        (role_model, perm_models, inclusion_models): (
+            models::RoleModel,
+            Vec<models::RolePermissionModel>,
+            Vec<models::RoleInclusionModel>,
+        ),
     ) -> Result<Self, Self::Error> {
         RoleBuilder::new()
             .with_id(role_model.id)
@@ -327,23 +484,89 @@ impl TryFrom<(models::RoleModel, Vec<models::RolePermissionModel>)> for Role {
                     .map(|perm| perm.permission)
                     .collect(),
             )
+            .with_included_roles(
+                inclusion_models
+                    .into_iter()
+                    .map(|inclusion| inclusion.included_role_id)
+                    .collect(),
+            )
             .build()
     }
 }
 
-impl From<Assignment> for (models::IdentityModel, Vec<models::AssignmentModel>) {
+impl From<Identity> for (String, models::IdentityModelType) {
+    fn from(identity: Identity) -> Self {
+        match identity {
+            Identity::Key(identity) => (identity, models::IdentityModelType::Key),
+            Identity::User(identity) => (identity, models::IdentityModelType::User),
+            Identity::Group(identity) => (identity, models::IdentityModelType::Group),
+        }
+    }
+}
+
+impl From<(String, models::IdentityModelType)> for Identity {
+    fn from((identity, identity_type): (String, models::IdentityModelType)) -> Self {
+        match identity_type {
+            models::IdentityModelType::Key => Identity::Key(identity),
+            models::IdentityModelType::User => Identity::User(identity),
+            models::IdentityModelType::Group => Identity::Group(identity),
+        }
+    }
+}
+
+impl From<Group> for (models::GroupModel, Vec<models::GroupMemberModel>) {
+    fn from(group: Group) -> Self {
+        let (id, display_name, members) = group.into_parts();
+
+        let member_models = members
+            .into_iter()
+            .map(|member| {
+                let (identity, identity_type): (String, models::IdentityModelType) = member.into();
+                models::GroupMemberModel {
+                    group_id: id.clone(),
+                    identity,
+                    identity_type,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        (models::GroupModel { id, display_name }, member_models)
+    }
+}
+
+impl TryFrom<(models::GroupModel, Vec<models::GroupMemberModel>)> for Group {
+    type Error = InvalidStateError;
+
+    fn try_from(
+        (group_model, member_models): (models::GroupModel, Vec<models::GroupMemberModel>),
+    ) -> Result<Self, Self::Error> {
+        GroupBuilder::new()
+            .with_id(group_model.id)
+            .with_display_name(group_model.display_name)
+            .with_members(
+                member_models
+                    .into_iter()
+                    .map(|member| Identity::from((member.identity, member.identity_type)))
+                    .collect(),
+            )
+            .build()
+    }
+}
+
+impl From<Assignment>
+    for (
+        models::IdentityModel,
+        Vec<models::AssignmentModel>,
+        Vec<models::ScopedAssignmentModel>,
+    )
+{
     fn from(assignment: Assignment) -> Self {
-        let (identity, roles) = assignment.into_parts();
+        let (identity, roles, scoped_roles) = assignment.into_parts();
 
-        let identity_model = match identity {
-            Identity::Key(identity) => models::IdentityModel {
-                identity,
-                identity_type: models::IdentityModelType::Key,
-            },
-            Identity::User(identity) => models::IdentityModel {
-                identity,
-                identity_type: models::IdentityModelType::User,
-            },
+        let (identity, identity_type): (String, models::IdentityModelType) = identity.into();
+        let identity_model = models::IdentityModel {
+            identity,
+            identity_type,
         };
 
         let role_models = roles
@@ -354,24 +577,59 @@ impl From<Assignment> for (models::IdentityModel, Vec<models::AssignmentModel>)
             })
             .collect::<Vec<_>>();
 
-        (identity_model, role_models)
+        let scoped_role_models = scoped_roles
+            .into_iter()
+            .flat_map(|(scope, roles)| {
+                let identity = identity_model.identity.clone();
+                let scope = scope.to_string();
+                roles
+                    .into_iter()
+                    .map(move |role_id| models::ScopedAssignmentModel {
+                        identity: identity.clone(),
+                        scope: scope.clone(),
+                        role_id,
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        (identity_model, role_models, scoped_role_models)
     }
 }
 
-impl TryFrom<(models::IdentityModel, Vec<models::AssignmentModel>)> for Assignment {
+impl
+    TryFrom<(
+        models::IdentityModel,
+        Vec<models::AssignmentModel>,
+        Vec<models::ScopedAssignmentModel>,
+    )> for Assignment
+{
     type Error = InvalidStateError;
 
     fn try_from(
-        (identity_model, assignments): (models::IdentityModel, Vec<models::AssignmentModel>),
+        (identity_model, assignments, scoped_assignments): (
+            models::IdentityModel,
+            Vec<models::AssignmentModel>,
+            Vec<models::ScopedAssignmentModel>,
+        ),
     ) -> Result<Self, Self::Error> {
         let models::IdentityModel {
             identity,
             identity_type,
         } = identity_model;
-        let identity = match identity_type {
-            models::IdentityModelType::Key => Identity::Key(identity),
-            models::IdentityModelType::User => Identity::User(identity),
-        };
+        let identity = Identity::from((identity, identity_type));
+
+        let mut scoped_roles: BTreeMap<Scope, Vec<String>> = BTreeMap::new();
+        for scoped_assignment in scoped_assignments {
+            let scope = scoped_assignment
+                .scope
+                .parse::<Scope>()
+                .map_err(|err| InvalidStateError::with_message(err.to_string()))?;
+            scoped_roles
+                .entry(scope)
+                .or_default()
+                .push(scoped_assignment.role_id);
+        }
+
         AssignmentBuilder::new()
             .with_identity(identity)
             .with_roles(
@@ -380,6 +638,7 @@ impl TryFrom<(models::IdentityModel, Vec<models::AssignmentModel>)> for Assignme
                     .map(|assignment| assignment.role_id)
                     .collect(),
             )
+            .with_scoped_roles(scoped_roles)
             .build()
     }
 }
@@ -413,13 +672,12 @@ impl From<diesel::r2d2::PoolError> for RoleBasedAuthorizationStoreError {
     }
 }
 
-#[cfg(all(test, feature = "sqlite"))]
+#[cfg(all(test, feature = "role-based-authorization-store-sqlite"))]
 mod tests {
     use super::*;
 
-    use crate::rest_api::auth::rbac::store::RoleBuilder;
-
     use crate::migrations::run_sqlite_migrations;
+    use crate::rest_api::auth::rbac::store::tests as store_tests;
 
     use diesel::{
         prelude::*,
@@ -427,199 +685,30 @@ mod tests {
         sqlite::SqliteConnection,
     };
 
-    /// This tests verifies the following:
-    /// 1. Adds a role via the store API
-    /// 2. Verifies it has been added by getting the role via the store API
     #[test]
     fn sqlite_add_and_get_role() {
         let pool = create_connection_pool_and_migrate();
-
-        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
-
-        let stored_role = role_based_auth_store
-            .get_role("test-role")
-            .expect("Unable to lookup role by id");
-        assert!(stored_role.is_none());
-
-        let role = RoleBuilder::new()
-            .with_id("test-role".into())
-            .with_display_name("Test Role".into())
-            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
-
-        let stored_role = role_based_auth_store
-            .get_role("test-role")
-            .expect("Unable to lookup role by id")
-            .expect("Did not find the added role");
-
-        assert_eq!("test-role", stored_role.id());
-        assert_eq!("Test Role", stored_role.display_name());
-        assert_eq!(
-            &["a".to_string(), "b".to_string(), "c".to_string()],
-            stored_role.permissions()
-        );
+        store_tests::test_add_and_get_role(&DieselRoleBasedAuthorizationStore::new(pool));
     }
 
-    /// This tests verifies the following:
-    /// 1. Adds two roles via the store API
-    /// 2. Verifies they have been added by listing the roles via the store API
     #[test]
     fn sqlite_list_roles() {
         let pool = create_connection_pool_and_migrate();
+        store_tests::test_list_roles(&DieselRoleBasedAuthorizationStore::new(pool));
+    }
 
-        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
-
-        let role = RoleBuilder::new()
-            .with_id("test-role-1".into())
-            .with_display_name("Test Role 1".into())
-            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
-
-        let role = RoleBuilder::new()
-            .with_id("test-role-2".into())
-            .with_display_name("Test Role 2".into())
-            .with_permissions(vec!["x".to_string(), "y".to_string(), "z".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
-
-        let mut stored_role_iter = role_based_auth_store
-            .list_roles()
-            .expect("Unable to lookup role by id");
-
-        assert_eq!(2, stored_role_iter.len());
-
-        let stored_role = stored_role_iter
-            .next()
-            .expect("has 2 items, but returned None");
-        assert_eq!("test-role-1", stored_role.id());
-        assert_eq!("Test Role 1", stored_role.display_name());
-        assert_eq!(
-            &["a".to_string(), "b".to_string(), "c".to_string()],
-            stored_role.permissions()
-        );
-
-        let stored_role = stored_role_iter
-            .next()
-            .expect("has 2 items, but returned None");
-        assert_eq!("test-role-2", stored_role.id());
-        assert_eq!("Test Role 2", stored_role.display_name());
-        assert_eq!(
-            &["x".to_string(), "y".to_string(), "z".to_string()],
-            stored_role.permissions()
-        );
-    }
-
-    /// This tests verifies the following:
-    /// 1. Adds a role and verifies that it has been inserted
-    /// 2. Update the role and verifies that it has been changed, via the store API
     #[test]
     fn sqlite_update_role() {
         let pool = create_connection_pool_and_migrate();
-
-        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
-
-        let role = RoleBuilder::new()
-            .with_id("test-role".into())
-            .with_display_name("Test Role".into())
-            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
-
-        let stored_role = role_based_auth_store
-            .get_role("test-role")
-            .expect("Unable to lookup role by id")
-            .expect("Did not find the added role");
-
-        assert_eq!("test-role", stored_role.id());
-        assert_eq!("Test Role", stored_role.display_name());
-        assert_eq!(
-            &["a".to_string(), "b".to_string(), "c".to_string()],
-            stored_role.permissions()
-        );
-
-        let updated_role = stored_role
-            .into_update_builder()
-            .with_display_name("Updated Test Role".into())
-            .with_permissions(vec!["a".to_string(), "b".to_string()])
-            .build()
-            .expect("Unable to build updated role");
-
-        role_based_auth_store
-            .update_role(updated_role)
-            .expect("Unable to update role");
-
-        let stored_role = role_based_auth_store
-            .get_role("test-role")
-            .expect("Unable to lookup role by id")
-            .expect("Did not find the added role");
-
-        assert_eq!("test-role", stored_role.id());
-        assert_eq!("Updated Test Role", stored_role.display_name());
-        assert_eq!(
-            &["a".to_string(), "b".to_string()],
-            stored_role.permissions()
-        );
+        store_tests::test_update_role(&DieselRoleBasedAuthorizationStore::new(pool));
     }
 
-    /// This tests verifies the following:
-    /// 1. Adds a role and verifies that it has been inserted
-    /// 2. Removes a role and verifies that it has been removed, via the store API
-    /// 3. Verify that the role permissions have been removed
+    /// Verifies the store API behavior, then that the role permission records have been removed.
     #[test]
     fn sqlite_remove_role() {
         let pool = create_connection_pool_and_migrate();
 
-        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
-
-        let role = RoleBuilder::new()
-            .with_id("test-role".into())
-            .with_display_name("Test Role".into())
-            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
-
-        let stored_role = role_based_auth_store
-            .get_role("test-role")
-            .expect("Unable to lookup role by id")
-            .expect("Did not find the added role");
-
-        assert_eq!("test-role", stored_role.id());
-        assert_eq!("Test Role", stored_role.display_name());
-        assert_eq!(
-            &["a".to_string(), "b".to_string(), "c".to_string()],
-            stored_role.permissions()
-        );
-
-        role_based_auth_store
-            .remove_role(stored_role.id())
-            .expect("Unable to remove role");
-
-        let stored_role = role_based_auth_store
-            .get_role("test-role")
-            .expect("Unable to lookup role by id");
-        assert!(stored_role.is_none());
+        store_tests::test_remove_role(&DieselRoleBasedAuthorizationStore::new(pool.clone()));
 
         // verify that the permissions have been removed (in a block, so the connection is dropped)
         {
@@ -630,344 +719,94 @@ mod tests {
                 .expect("Unable to load permissions");
             assert!(perms.is_empty());
         }
-
-        // verify that the remove is idempotent
-        role_based_auth_store
-            .remove_role("test-role")
-            .expect("Unable to remove role");
     }
 
-    /// This test verifies the following:
-    /// 1. Adds a role.
-    /// 2. Adds an assignment for that role
-    /// 3. Verifies the assignment was added via the store API
     #[test]
     fn sqlite_add_and_get_assignment() {
         let pool = create_connection_pool_and_migrate();
-
-        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
-
-        let role = RoleBuilder::new()
-            .with_id("test-role".into())
-            .with_display_name("Test Role".into())
-            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
-
-        let assignment = AssignmentBuilder::new()
-            .with_identity(Identity::User("some-user-id".into()))
-            .with_roles(vec!["test-role".to_string()])
-            .build()
-            .expect("Unable to build assignment");
-
-        role_based_auth_store
-            .add_assignment(assignment)
-            .expect("Unable to add assignment");
-
-        let stored_assignment = role_based_auth_store
-            .get_assignment(&Identity::User("some-user-id".into()))
-            .expect("Unable to get assignment")
-            .expect("Assignment was not found");
-
-        assert_eq!(
-            &Identity::User("some-user-id".into()),
-            stored_assignment.identity()
-        );
-        assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
+        store_tests::test_add_and_get_assignment(&DieselRoleBasedAuthorizationStore::new(pool));
     }
 
-    /// This test verifies the following:
-    /// 1. Adds two roles
-    /// 2. Adds an assignment for those roles
-    /// 3. Verifies the roles are returned via the get_assigned_roles API
     #[test]
     fn sqlite_get_assigned_roles() {
         let pool = create_connection_pool_and_migrate();
+        store_tests::test_get_assigned_roles(&DieselRoleBasedAuthorizationStore::new(pool));
+    }
 
-        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
-
-        let role = RoleBuilder::new()
-            .with_id("test-role-1".into())
-            .with_display_name("Test Role 1".into())
-            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
-
-        let role = RoleBuilder::new()
-            .with_id("test-role-2".into())
-            .with_display_name("Test Role 2".into())
-            .with_permissions(vec!["x".to_string(), "y".to_string(), "z".to_string()])
-            .build()
-            .expect("Unable to build role");
+    #[test]
+    fn sqlite_get_assigned_roles_in_scope() {
+        let pool = create_connection_pool_and_migrate();
+        store_tests::test_get_assigned_roles_in_scope(&DieselRoleBasedAuthorizationStore::new(
+            pool,
+        ));
+    }
 
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
+    #[test]
+    fn sqlite_scoped_assignment() {
+        let pool = create_connection_pool_and_migrate();
+        store_tests::test_scoped_assignment(&DieselRoleBasedAuthorizationStore::new(pool));
+    }
 
-        let assignment = AssignmentBuilder::new()
-            .with_identity(Identity::User("some-user-id".into()))
-            .with_roles(vec!["test-role-1".to_string(), "test-role-2".to_string()])
-            .build()
-            .expect("Unable to build assignment");
-
-        role_based_auth_store
-            .add_assignment(assignment)
-            .expect("Unable to add assignment");
-
-        let mut assigned_roles = role_based_auth_store
-            .get_assigned_roles(&Identity::User("some-user-id".into()))
-            .expect("Unable to get assigned roles");
-
-        assert_eq!(2, assigned_roles.len());
-
-        let stored_role = assigned_roles
-            .next()
-            .expect("has 2 items, but returned None");
-        assert_eq!("test-role-1", stored_role.id());
-        assert_eq!("Test Role 1", stored_role.display_name());
-        assert_eq!(
-            &["a".to_string(), "b".to_string(), "c".to_string()],
-            stored_role.permissions()
-        );
-
-        let stored_role = assigned_roles
-            .next()
-            .expect("has 2 items, but returned None");
-        assert_eq!("test-role-2", stored_role.id());
-        assert_eq!("Test Role 2", stored_role.display_name());
-        assert_eq!(
-            &["x".to_string(), "y".to_string(), "z".to_string()],
-            stored_role.permissions()
-        );
-    }
-
-    /// This test verifies the following:
-    /// 1. Adds a role.
-    /// 2. Add two assignments for that role
-    /// 3. Verifies the assignments were added via the store's list API
     #[test]
     fn sqlite_list_assignments() {
         let pool = create_connection_pool_and_migrate();
+        store_tests::test_list_assignments(&DieselRoleBasedAuthorizationStore::new(pool));
+    }
 
-        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
-
-        let role = RoleBuilder::new()
-            .with_id("test-role".into())
-            .with_display_name("Test Role".into())
-            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
-
-        let assignment = AssignmentBuilder::new()
-            .with_identity(Identity::User("some-user-id-1".into()))
-            .with_roles(vec!["test-role".to_string()])
-            .build()
-            .expect("Unable to build assignment");
-
-        role_based_auth_store
-            .add_assignment(assignment)
-            .expect("Unable to add assignment");
-
-        let assignment = AssignmentBuilder::new()
-            .with_identity(Identity::Key("some-key-1".into()))
-            .with_roles(vec!["test-role".to_string()])
-            .build()
-            .expect("Unable to build assignment");
-
-        role_based_auth_store
-            .add_assignment(assignment)
-            .expect("Unable to add assignment");
-
-        let mut stored_assignment_iter = role_based_auth_store
-            .list_assignments()
-            .expect("Unable to get assignment");
-
-        assert_eq!(2, stored_assignment_iter.len());
-
-        let stored_assignment = stored_assignment_iter
-            .next()
-            .expect("has 2 items, but returned None");
-        assert_eq!(
-            &Identity::User("some-user-id-1".into()),
-            stored_assignment.identity()
-        );
-        assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
-
-        let stored_assignment = stored_assignment_iter
-            .next()
-            .expect("has 2 items, but returned None");
-        assert_eq!(
-            &Identity::Key("some-key-1".into()),
-            stored_assignment.identity()
-        );
-        assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
-    }
-
-    /// This test verifies the following:
-    /// 1. Add two roles
-    /// 2. Add an assignment to one of the roles
-    /// 3. Update the assignment to have both roles and verify via the store API
-    /// 4. Update the assignment to only have the other role, and verify via the store API
     #[test]
     fn sqlite_update_assignment() {
         let pool = create_connection_pool_and_migrate();
+        store_tests::test_update_assignment(&DieselRoleBasedAuthorizationStore::new(pool));
+    }
 
-        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
-
-        let role = RoleBuilder::new()
-            .with_id("test-role-1".into())
-            .with_display_name("Test Role 1".into())
-            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
-
-        let role = RoleBuilder::new()
-            .with_id("test-role-2".into())
-            .with_display_name("Test Role 2".into())
-            .with_permissions(vec!["x".to_string(), "y".to_string(), "z".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
-
-        let assignment = AssignmentBuilder::new()
-            .with_identity(Identity::User("some-user-id".into()))
-            .with_roles(vec!["test-role-1".to_string()])
-            .build()
-            .expect("Unable to build assignment");
-
-        role_based_auth_store
-            .add_assignment(assignment)
-            .expect("Unable to add assignment");
-
-        let stored_assignment = role_based_auth_store
-            .get_assignment(&Identity::User("some-user-id".into()))
-            .expect("Unable to get assignment")
-            .expect("Assignment was not found");
-
-        assert_eq!(
-            &Identity::User("some-user-id".into()),
-            stored_assignment.identity()
-        );
-        assert_eq!(&vec!["test-role-1".to_string()], stored_assignment.roles());
-
-        let updated_assignment = stored_assignment
-            .into_update_builder()
-            .with_roles(vec!["test-role-1".to_string(), "test-role-2".to_string()])
-            .build()
-            .expect("Unable to build updated assignment");
-
-        role_based_auth_store
-            .update_assignment(updated_assignment)
-            .expect("Unable to update assignment");
-
-        let stored_assignment = role_based_auth_store
-            .get_assignment(&Identity::User("some-user-id".into()))
-            .expect("Unable to get assignment")
-            .expect("Assignment was not found");
-
-        assert_eq!(
-            &Identity::User("some-user-id".into()),
-            stored_assignment.identity()
-        );
-        assert_eq!(
-            &vec!["test-role-1".to_string(), "test-role-2".to_string()],
-            stored_assignment.roles()
-        );
-
-        let updated_assignment = stored_assignment
-            .into_update_builder()
-            .with_roles(vec!["test-role-2".to_string()])
-            .build()
-            .expect("Unable to build updated assignment");
-
-        role_based_auth_store
-            .update_assignment(updated_assignment)
-            .expect("Unable to update assignment");
-
-        let stored_assignment = role_based_auth_store
-            .get_assignment(&Identity::User("some-user-id".into()))
-            .expect("Unable to get assignment")
-            .expect("Assignment was not found");
-
-        assert_eq!(
-            &Identity::User("some-user-id".into()),
-            stored_assignment.identity()
-        );
-        assert_eq!(&vec!["test-role-2".to_string()], stored_assignment.roles());
-    }
-
-    /// This test verifies the following:
-    /// 1. Add a role
-    /// 2. Add an assignment for the role and verify with the store API
-    /// 3. Remove the assignment and verify its removal with the API
-    /// 4. Verify that the assignment records have been removed.
-    /// 5. Verify that the removal is idempotent
     #[test]
-    fn sqlite_remove_assignment() {
+    fn sqlite_role_inclusions() {
         let pool = create_connection_pool_and_migrate();
+        store_tests::test_role_inclusions(&DieselRoleBasedAuthorizationStore::new(pool));
+    }
 
-        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
-
-        let role = RoleBuilder::new()
-            .with_id("test-role".into())
-            .with_display_name("Test Role".into())
-            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
-            .build()
-            .expect("Unable to build role");
-
-        role_based_auth_store
-            .add_role(role)
-            .expect("Unable to add role");
+    #[test]
+    fn sqlite_add_and_get_group() {
+        let pool = create_connection_pool_and_migrate();
+        store_tests::test_add_and_get_group(&DieselRoleBasedAuthorizationStore::new(pool));
+    }
 
-        let assignment = AssignmentBuilder::new()
-            .with_identity(Identity::User("some-user-id".into()))
-            .with_roles(vec!["test-role".to_string()])
-            .build()
-            .expect("Unable to build assignment");
+    #[test]
+    fn sqlite_list_groups_for_identity() {
+        let pool = create_connection_pool_and_migrate();
+        store_tests::test_list_groups_for_identity(&DieselRoleBasedAuthorizationStore::new(pool));
+    }
 
-        role_based_auth_store
-            .add_assignment(assignment)
-            .expect("Unable to add assignment");
+    #[test]
+    fn sqlite_update_group() {
+        let pool = create_connection_pool_and_migrate();
+        store_tests::test_update_group(&DieselRoleBasedAuthorizationStore::new(pool));
+    }
 
-        let stored_assignment = role_based_auth_store
-            .get_assignment(&Identity::User("some-user-id".into()))
-            .expect("Unable to get assignment")
-            .expect("Assignment was not found");
+    /// Verifies the store API behavior, then that the group member records have been removed.
+    #[test]
+    fn sqlite_remove_group() {
+        let pool = create_connection_pool_and_migrate();
 
-        assert_eq!(
-            &Identity::User("some-user-id".into()),
-            stored_assignment.identity()
-        );
-        assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
+        store_tests::test_remove_group(&DieselRoleBasedAuthorizationStore::new(pool.clone()));
 
-        role_based_auth_store
-            .remove_assignment(&Identity::User("some-user-id".into()))
-            .expect("Unable to remove assignment");
+        // verify that the members have been removed (in a block, so the connection is dropped)
+        {
+            let connection = pool.get().expect("Unable to get connection");
+            let members = schema::identity_group_members::table
+                .filter(schema::identity_group_members::group_id.eq("test-group"))
+                .load::<models::GroupMemberModel>(&*connection)
+                .expect("Unable to load group members");
+            assert!(members.is_empty());
+        }
+    }
 
-        let stored_assignment = role_based_auth_store
-            .get_assignment(&Identity::User("some-user-id".into()))
-            .expect("Unable to get assignment");
+    /// Verifies the store API behavior, then that the assignment records have been removed.
+    #[test]
+    fn sqlite_remove_assignment() {
+        let pool = create_connection_pool_and_migrate();
 
-        assert!(stored_assignment.is_none());
+        store_tests::test_remove_assignment(&DieselRoleBasedAuthorizationStore::new(pool.clone()));
 
         // verify that the assignments have been removed (in a block, so the connection is dropped)
         {
@@ -978,11 +817,6 @@ mod tests {
                 .expect("Unable to load permissions");
             assert!(perms.is_empty());
         }
-
-        // verify that the removal is idempotent
-        role_based_auth_store
-            .remove_assignment(&Identity::User("some-user-id".into()))
-            .expect("Unable to remove assignment");
     }
 
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
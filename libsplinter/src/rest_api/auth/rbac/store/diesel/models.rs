@@ -22,7 +22,10 @@ use diesel::{
     sql_types::SmallInt,
 };
 
-use super::schema::{assignments, identities, role_permissions, roles};
+use super::schema::{
+    assignments, identities, identity_group_members, identity_groups, role_inclusions,
+    role_permissions, roles, scoped_assignments,
+};
 
 #[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
 #[table_name = "roles"]
@@ -41,11 +44,21 @@ pub(super) struct RolePermissionModel {
     pub permission: String,
 }
 
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
+#[table_name = "role_inclusions"]
+#[belongs_to(RoleModel, foreign_key = "role_id")]
+#[primary_key(role_id, included_role_id)]
+pub(super) struct RoleInclusionModel {
+    pub role_id: String,
+    pub included_role_id: String,
+}
+
 #[repr(i16)]
 #[derive(Debug, Copy, Clone, PartialEq, FromSqlRow)]
 pub(super) enum IdentityModelType {
     Key = 1,
     User = 2,
+    Group = 3,
 }
 
 impl<DB> ToSql<SmallInt, DB> for IdentityModelType
@@ -83,6 +96,7 @@ where
         match i16::from_sql(bytes)? {
             1 => Ok(IdentityModelType::Key),
             2 => Ok(IdentityModelType::User),
+            3 => Ok(IdentityModelType::Group),
             int => Err(format!("Invalid identity type {}", int).into()),
         }
     }
@@ -104,3 +118,31 @@ pub(super) struct AssignmentModel {
     pub identity: String,
     pub role_id: String,
 }
+
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
+#[table_name = "scoped_assignments"]
+#[belongs_to(IdentityModel, foreign_key = "identity")]
+#[primary_key(identity, scope, role_id)]
+pub(super) struct ScopedAssignmentModel {
+    pub identity: String,
+    pub scope: String,
+    pub role_id: String,
+}
+
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
+#[table_name = "identity_groups"]
+#[primary_key(id)]
+pub(super) struct GroupModel {
+    pub id: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
+#[table_name = "identity_group_members"]
+#[belongs_to(GroupModel, foreign_key = "group_id")]
+#[primary_key(group_id, identity)]
+pub(super) struct GroupMemberModel {
+    pub group_id: String,
+    pub identity: String,
+    pub identity_type: IdentityModelType,
+}
@@ -16,8 +16,8 @@ use diesel::{dsl::insert_into, prelude::*};
 
 use crate::rest_api::auth::rbac::store::{
     diesel::{
-        models::{AssignmentModel, IdentityModel},
-        schema::{assignments, identities},
+        models::{AssignmentModel, IdentityModel, ScopedAssignmentModel},
+        schema::{assignments, identities, scoped_assignments},
     },
     Assignment, RoleBasedAuthorizationStoreError,
 };
@@ -31,7 +31,7 @@ pub trait RoleBasedAuthorizationStoreAddAssignment {
     ) -> Result<(), RoleBasedAuthorizationStoreError>;
 }
 
-#[cfg(feature = "sqlite")]
+#[cfg(feature = "role-based-authorization-store-sqlite")]
 impl<'a> RoleBasedAuthorizationStoreAddAssignment
     for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
@@ -39,15 +39,27 @@ impl<'a> RoleBasedAuthorizationStoreAddAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, assignments): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, assignments, scoped): (
+            IdentityModel,
+            Vec<AssignmentModel>,
+            Vec<ScopedAssignmentModel>,
+        ) = assignment.into();
         self.conn.transaction::<_, _, _>(|| {
             insert_into(identities::table)
                 .values(identity)
                 .execute(self.conn)?;
 
-            insert_into(assignments::table)
-                .values(assignments)
-                .execute(self.conn)?;
+            if !assignments.is_empty() {
+                insert_into(assignments::table)
+                    .values(assignments)
+                    .execute(self.conn)?;
+            }
+
+            if !scoped.is_empty() {
+                insert_into(scoped_assignments::table)
+                    .values(scoped)
+                    .execute(self.conn)?;
+            }
 
             Ok(())
         })
@@ -62,15 +74,27 @@ impl<'a> RoleBasedAuthorizationStoreAddAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, assignments): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, assignments, scoped): (
+            IdentityModel,
+            Vec<AssignmentModel>,
+            Vec<ScopedAssignmentModel>,
+        ) = assignment.into();
         self.conn.transaction::<_, _, _>(|| {
             insert_into(identities::table)
                 .values(identity)
                 .execute(self.conn)?;
 
-            insert_into(assignments::table)
-                .values(assignments)
-                .execute(self.conn)?;
+            if !assignments.is_empty() {
+                insert_into(assignments::table)
+                    .values(assignments)
+                    .execute(self.conn)?;
+            }
+
+            if !scoped.is_empty() {
+                insert_into(scoped_assignments::table)
+                    .values(scoped)
+                    .execute(self.conn)?;
+            }
 
             Ok(())
         })
@@ -0,0 +1,74 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::rest_api::auth::rbac::store::{
+    diesel::{
+        models::{GroupMemberModel, GroupModel},
+        schema::{identity_group_members, identity_groups},
+    },
+    Group, RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreAddGroup {
+    fn add_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError>;
+}
+
+#[cfg(feature = "role-based-authorization-store-sqlite")]
+impl<'a> RoleBasedAuthorizationStoreAddGroup
+    for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let (group, members): (GroupModel, Vec<GroupMemberModel>) = group.into();
+
+        self.conn.transaction::<_, _, _>(|| {
+            insert_into(identity_groups::table)
+                .values(group)
+                .execute(self.conn)?;
+
+            if !members.is_empty() {
+                insert_into(identity_group_members::table)
+                    .values(members)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "role-based-authorization-store-postgres")]
+impl<'a> RoleBasedAuthorizationStoreAddGroup
+    for RoleBasedAuthorizationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let (group, members): (GroupModel, Vec<GroupMemberModel>) = group.into();
+
+        self.conn.transaction::<_, _, _>(|| {
+            insert_into(identity_groups::table)
+                .values(group)
+                .execute(self.conn)?;
+
+            if !members.is_empty() {
+                insert_into(identity_group_members::table)
+                    .values(members)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
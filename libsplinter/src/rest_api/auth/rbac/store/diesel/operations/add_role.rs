@@ -16,8 +16,8 @@ use diesel::{dsl::insert_into, prelude::*};
 
 use crate::rest_api::auth::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
-        schema::{role_permissions, roles},
+        models::{RoleInclusionModel, RoleModel, RolePermissionModel},
+        schema::{role_inclusions, role_permissions, roles},
     },
     Role, RoleBasedAuthorizationStoreError,
 };
@@ -28,19 +28,37 @@ pub trait RoleBasedAuthorizationStoreAddRole {
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError>;
 }
 
-#[cfg(feature = "sqlite")]
+#[cfg(feature = "role-based-authorization-store-sqlite")]
 impl<'a> RoleBasedAuthorizationStoreAddRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, inclusions): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleInclusionModel>,
+        ) = role.into();
 
         self.conn.transaction::<_, _, _>(|| {
+            let included_roles = inclusions
+                .iter()
+                .map(|inclusion| inclusion.included_role_id.clone())
+                .collect::<Vec<_>>();
+            self.check_role_inclusions(&role.id, &included_roles)?;
+
             insert_into(roles::table).values(role).execute(self.conn)?;
 
-            insert_into(role_permissions::table)
-                .values(permissions)
-                .execute(self.conn)?;
+            if !permissions.is_empty() {
+                insert_into(role_permissions::table)
+                    .values(permissions)
+                    .execute(self.conn)?;
+            }
+
+            if !inclusions.is_empty() {
+                insert_into(role_inclusions::table)
+                    .values(inclusions)
+                    .execute(self.conn)?;
+            }
 
             Ok(())
         })
@@ -52,13 +70,32 @@ impl<'a> RoleBasedAuthorizationStoreAddRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::pg::PgConnection>
 {
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, inclusions): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleInclusionModel>,
+        ) = role.into();
+
         self.conn.transaction::<_, _, _>(|| {
+            let included_roles = inclusions
+                .iter()
+                .map(|inclusion| inclusion.included_role_id.clone())
+                .collect::<Vec<_>>();
+            self.check_role_inclusions(&role.id, &included_roles)?;
+
             insert_into(roles::table).values(role).execute(self.conn)?;
 
-            insert_into(role_permissions::table)
-                .values(permissions)
-                .execute(self.conn)?;
+            if !permissions.is_empty() {
+                insert_into(role_permissions::table)
+                    .values(permissions)
+                    .execute(self.conn)?;
+            }
+
+            if !inclusions.is_empty() {
+                insert_into(role_inclusions::table)
+                    .values(inclusions)
+                    .execute(self.conn)?;
+            }
 
             Ok(())
         })
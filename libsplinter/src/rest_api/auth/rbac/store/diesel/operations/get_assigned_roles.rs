@@ -18,7 +18,9 @@ use diesel::prelude::*;
 
 use crate::rest_api::auth::rbac::store::{
     diesel::{
-        models::{AssignmentModel, IdentityModel, RoleModel, RolePermissionModel},
+        models::{
+            AssignmentModel, IdentityModel, RoleInclusionModel, RoleModel, RolePermissionModel,
+        },
         schema::{identities, roles},
     },
     Identity, Role, RoleBasedAuthorizationStoreError,
@@ -47,6 +49,7 @@ where
         let search_identity = match identity {
             Identity::Key(ref key) => key,
             Identity::User(ref user_id) => user_id,
+            Identity::Group(ref group_id) => group_id,
         };
         self.conn
             .transaction::<Box<dyn ExactSizeIterator<Item = Role>>, _, _>(|| {
@@ -68,11 +71,16 @@ where
                     .load::<RolePermissionModel>(self.conn)?
                     .grouped_by(&roles);
 
+                let inclusions = RoleInclusionModel::belonging_to(&roles)
+                    .load::<RoleInclusionModel>(self.conn)?
+                    .grouped_by(&roles);
+
                 Ok(Box::new(
                     roles
                         .into_iter()
                         .zip(perms)
-                        .map(|models| models.try_into())
+                        .zip(inclusions)
+                        .map(|((role, perms), inclusions)| (role, perms, inclusions).try_into())
                         .collect::<Result<Vec<_>, _>>()
                         .map_err(RoleBasedAuthorizationStoreError::from)?
                         .into_iter(),
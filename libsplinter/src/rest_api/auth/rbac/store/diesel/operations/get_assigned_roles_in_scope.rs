@@ -0,0 +1,107 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+
+use diesel::prelude::*;
+
+use crate::rest_api::auth::rbac::store::{
+    diesel::{
+        models::{
+            AssignmentModel, IdentityModel, RoleInclusionModel, RoleModel, RolePermissionModel,
+        },
+        schema::{identities, roles, scoped_assignments},
+    },
+    Identity, Role, RoleBasedAuthorizationStoreError, Scope,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreGetAssignedRolesInScope {
+    fn get_assigned_roles_in_scope(
+        &self,
+        identity: &Identity,
+        scope: &Scope,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreGetAssignedRolesInScope
+    for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
+{
+    fn get_assigned_roles_in_scope(
+        &self,
+        identity: &Identity,
+        scope: &Scope,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        let search_identity = match identity {
+            Identity::Key(ref key) => key,
+            Identity::User(ref user_id) => user_id,
+            Identity::Group(ref group_id) => group_id,
+        };
+
+        // The scopes that contain the requested scope
+        let search_scopes = match scope {
+            Scope::Circuit(_) => vec![scope.to_string()],
+            Scope::Service { circuit_id, .. } => vec![circuit_id.to_string(), scope.to_string()],
+        };
+
+        self.conn
+            .transaction::<Box<dyn ExactSizeIterator<Item = Role>>, _, _>(|| {
+                let identities = identities::table
+                    .filter(identities::identity.eq(search_identity))
+                    .load::<IdentityModel>(self.conn)?;
+
+                let mut role_ids = AssignmentModel::belonging_to(&identities)
+                    .load::<AssignmentModel>(self.conn)?
+                    .into_iter()
+                    .map(|assignment| assignment.role_id)
+                    .collect::<Vec<_>>();
+
+                role_ids.extend(
+                    scoped_assignments::table
+                        .filter(scoped_assignments::identity.eq(search_identity))
+                        .filter(scoped_assignments::scope.eq_any(search_scopes))
+                        .select(scoped_assignments::role_id)
+                        .load::<String>(self.conn)?,
+                );
+
+                let roles = roles::table
+                    .filter(roles::id.eq_any(role_ids))
+                    .load::<RoleModel>(self.conn)?;
+
+                let perms = RolePermissionModel::belonging_to(&roles)
+                    .load::<RolePermissionModel>(self.conn)?
+                    .grouped_by(&roles);
+
+                let inclusions = RoleInclusionModel::belonging_to(&roles)
+                    .load::<RoleInclusionModel>(self.conn)?
+                    .grouped_by(&roles);
+
+                Ok(Box::new(
+                    roles
+                        .into_iter()
+                        .zip(perms)
+                        .zip(inclusions)
+                        .map(|((role, perms), inclusions)| (role, perms, inclusions).try_into())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(RoleBasedAuthorizationStoreError::from)?
+                        .into_iter(),
+                ))
+            })
+    }
+}
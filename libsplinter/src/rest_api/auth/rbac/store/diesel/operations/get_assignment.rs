@@ -18,7 +18,7 @@ use diesel::prelude::*;
 
 use crate::rest_api::auth::rbac::store::{
     diesel::{
-        models::{AssignmentModel, IdentityModel},
+        models::{AssignmentModel, IdentityModel, ScopedAssignmentModel},
         schema::identities,
     },
     Assignment, Identity, RoleBasedAuthorizationStoreError,
@@ -47,6 +47,7 @@ where
         let search_identity = match identity {
             Identity::Key(ref key) => key,
             Identity::User(ref user_id) => user_id,
+            Identity::Group(ref group_id) => group_id,
         };
         self.conn.transaction(|| {
             let identities = identities::table
@@ -57,11 +58,18 @@ where
                 .load::<AssignmentModel>(self.conn)?
                 .grouped_by(&identities);
 
+            let scoped_assignments = ScopedAssignmentModel::belonging_to(&identities)
+                .load::<ScopedAssignmentModel>(self.conn)?
+                .grouped_by(&identities);
+
             identities
                 .into_iter()
                 .zip(assignments)
+                .zip(scoped_assignments)
                 .next()
-                .map(|model| model.try_into())
+                .map(|((identity, assignments), scoped_assignments)| {
+                    (identity, assignments, scoped_assignments).try_into()
+                })
                 .transpose()
                 .map_err(RoleBasedAuthorizationStoreError::from)
         })
@@ -18,7 +18,7 @@ use diesel::prelude::*;
 
 use crate::rest_api::auth::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
+        models::{RoleInclusionModel, RoleModel, RolePermissionModel},
         schema::roles,
     },
     Role, RoleBasedAuthorizationStoreError,
@@ -45,11 +45,16 @@ where
                 .load::<RolePermissionModel>(self.conn)?
                 .grouped_by(&roles);
 
+            let inclusions = RoleInclusionModel::belonging_to(&roles)
+                .load::<RoleInclusionModel>(self.conn)?
+                .grouped_by(&roles);
+
             roles
                 .into_iter()
                 .zip(perms)
+                .zip(inclusions)
                 .next()
-                .map(|models| models.try_into())
+                .map(|((role, perms), inclusions)| (role, perms, inclusions).try_into())
                 .transpose()
                 .map_err(RoleBasedAuthorizationStoreError::from)
         })
@@ -18,7 +18,7 @@ use diesel::prelude::*;
 
 use crate::rest_api::auth::rbac::store::{
     diesel::{
-        models::{AssignmentModel, IdentityModel},
+        models::{AssignmentModel, IdentityModel, ScopedAssignmentModel},
         schema::identities,
     },
     Assignment, RoleBasedAuthorizationStoreError,
@@ -51,11 +51,18 @@ where
                     .load::<AssignmentModel>(self.conn)?
                     .grouped_by(&identities);
 
+                let scoped_assignments = ScopedAssignmentModel::belonging_to(&identities)
+                    .load::<ScopedAssignmentModel>(self.conn)?
+                    .grouped_by(&identities);
+
                 Ok(Box::new(
                     identities
                         .into_iter()
                         .zip(assignments)
-                        .map(|models| models.try_into())
+                        .zip(scoped_assignments)
+                        .map(|((identity, assignments), scoped_assignments)| {
+                            (identity, assignments, scoped_assignments).try_into()
+                        })
                         .collect::<Result<Vec<_>, _>>()
                         .map_err(RoleBasedAuthorizationStoreError::from)?
                         .into_iter(),
@@ -0,0 +1,62 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::convert::TryInto;
+
+use diesel::prelude::*;
+
+use crate::rest_api::auth::rbac::store::{
+    diesel::{
+        models::{GroupMemberModel, GroupModel},
+        schema::identity_groups,
+    },
+    Group, RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreListGroups {
+    fn list_groups(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreListGroups for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
+{
+    fn list_groups(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError> {
+        self.conn
+            .transaction::<Box<dyn ExactSizeIterator<Item = Group>>, _, _>(|| {
+                let groups = identity_groups::table.load::<GroupModel>(self.conn)?;
+
+                let members = GroupMemberModel::belonging_to(&groups)
+                    .load::<GroupMemberModel>(self.conn)?
+                    .grouped_by(&groups);
+
+                Ok(Box::new(
+                    groups
+                        .into_iter()
+                        .zip(members)
+                        .map(|models| models.try_into())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(RoleBasedAuthorizationStoreError::from)?
+                        .into_iter(),
+                ))
+            })
+    }
+}
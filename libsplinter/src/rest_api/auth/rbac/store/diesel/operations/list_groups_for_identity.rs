@@ -0,0 +1,77 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::convert::TryInto;
+
+use diesel::prelude::*;
+
+use crate::rest_api::auth::rbac::store::{
+    diesel::{
+        models::{GroupMemberModel, GroupModel},
+        schema::{identity_group_members, identity_groups},
+    },
+    Group, Identity, RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreListGroupsForIdentity {
+    fn list_groups_for_identity(
+        &self,
+        identity: &Identity,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreListGroupsForIdentity
+    for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
+{
+    fn list_groups_for_identity(
+        &self,
+        identity: &Identity,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError> {
+        let search_identity = match identity {
+            Identity::Key(ref key) => key,
+            Identity::User(ref user_id) => user_id,
+            Identity::Group(ref group_id) => group_id,
+        };
+        self.conn
+            .transaction::<Box<dyn ExactSizeIterator<Item = Group>>, _, _>(|| {
+                let group_ids = identity_group_members::table
+                    .filter(identity_group_members::identity.eq(search_identity))
+                    .select(identity_group_members::group_id)
+                    .load::<String>(self.conn)?;
+
+                let groups = identity_groups::table
+                    .filter(identity_groups::id.eq_any(group_ids))
+                    .load::<GroupModel>(self.conn)?;
+
+                let members = GroupMemberModel::belonging_to(&groups)
+                    .load::<GroupMemberModel>(self.conn)?
+                    .grouped_by(&groups);
+
+                Ok(Box::new(
+                    groups
+                        .into_iter()
+                        .zip(members)
+                        .map(|models| models.try_into())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(RoleBasedAuthorizationStoreError::from)?
+                        .into_iter(),
+                ))
+            })
+    }
+}
@@ -18,7 +18,7 @@ use diesel::prelude::*;
 
 use crate::rest_api::auth::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
+        models::{RoleInclusionModel, RoleModel, RolePermissionModel},
         schema::roles,
     },
     Role, RoleBasedAuthorizationStoreError,
@@ -48,11 +48,16 @@ where
                     .load::<RolePermissionModel>(self.conn)?
                     .grouped_by(&roles);
 
+                let inclusions = RoleInclusionModel::belonging_to(&roles)
+                    .load::<RoleInclusionModel>(self.conn)?
+                    .grouped_by(&roles);
+
                 Ok(Box::new(
                     roles
                         .into_iter()
                         .zip(perms)
-                        .map(|models| models.try_into())
+                        .zip(inclusions)
+                        .map(|((role, perms), inclusions)| (role, perms, inclusions).try_into())
                         .collect::<Result<Vec<_>, _>>()
                         .map_err(RoleBasedAuthorizationStoreError::from)?
                         .into_iter(),
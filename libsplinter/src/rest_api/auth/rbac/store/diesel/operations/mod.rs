@@ -13,17 +13,32 @@
 // limitations under the License.
 
 pub(super) mod add_assignment;
+pub(super) mod add_group;
 pub(super) mod add_role;
 pub(super) mod get_assigned_roles;
+pub(super) mod get_assigned_roles_in_scope;
 pub(super) mod get_assignment;
+pub(super) mod get_group;
 pub(super) mod get_role;
 pub(super) mod list_assignments;
+pub(super) mod list_groups;
+pub(super) mod list_groups_for_identity;
 pub(super) mod list_roles;
 pub(super) mod remove_assignment;
+pub(super) mod remove_group;
 pub(super) mod remove_role;
 pub(super) mod update_assignment;
+pub(super) mod update_group;
 pub(super) mod update_role;
 
+use diesel::prelude::*;
+
+use crate::rest_api::auth::rbac::store::{
+    check_role_inclusions,
+    diesel::schema::{role_inclusions, roles},
+    RoleBasedAuthorizationStoreError,
+};
+
 pub(super) struct RoleBasedAuthorizationStoreOperations<'a, C> {
     conn: &'a C,
 }
@@ -36,3 +51,36 @@ where
         Self { conn }
     }
 }
+
+impl<'a, C> RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    /// Checks that the given included roles exist and would not create a cycle of inclusions
+    /// with the stored roles.
+    fn check_role_inclusions(
+        &self,
+        role_id: &str,
+        included_roles: &[String],
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        check_role_inclusions(role_id, included_roles, |search_id| {
+            let exists = !roles::table
+                .filter(roles::id.eq(search_id))
+                .select(roles::id)
+                .load::<String>(self.conn)?
+                .is_empty();
+
+            if !exists {
+                return Ok(None);
+            }
+
+            Ok(Some(
+                role_inclusions::table
+                    .filter(role_inclusions::role_id.eq(search_id))
+                    .select(role_inclusions::included_role_id)
+                    .load::<String>(self.conn)?,
+            ))
+        })
+    }
+}
@@ -15,7 +15,7 @@
 use diesel::{dsl::delete, prelude::*};
 
 use crate::rest_api::auth::rbac::store::{
-    diesel::schema::{assignments, identities},
+    diesel::schema::{assignments, identities, scoped_assignments},
     Identity, RoleBasedAuthorizationStoreError,
 };
 
@@ -41,10 +41,15 @@ where
         let search_identity = match identity {
             Identity::Key(ref key) => key,
             Identity::User(ref user_id) => user_id,
+            Identity::Group(ref group_id) => group_id,
         };
         self.conn.transaction::<_, _, _>(|| {
             delete(assignments::table.filter(assignments::identity.eq(search_identity)))
                 .execute(self.conn)?;
+            delete(
+                scoped_assignments::table.filter(scoped_assignments::identity.eq(search_identity)),
+            )
+            .execute(self.conn)?;
             delete(identities::table.filter(identities::identity.eq(search_identity)))
                 .execute(self.conn)?;
 
@@ -0,0 +1,46 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::{dsl::delete, prelude::*};
+
+use crate::rest_api::auth::rbac::store::{
+    diesel::schema::{identity_group_members, identity_groups},
+    Identity, RoleBasedAuthorizationStoreError,
+};
+
+use super::remove_assignment::RoleBasedAuthorizationStoreRemoveAssignment;
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreRemoveGroup {
+    fn remove_group(&self, group_id: &str) -> Result<(), RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreRemoveGroup for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn remove_group(&self, group_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            delete(
+                identity_group_members::table.filter(identity_group_members::group_id.eq(group_id)),
+            )
+            .execute(self.conn)?;
+
+            delete(identity_groups::table.filter(identity_groups::id.eq(group_id)))
+                .execute(self.conn)?;
+
+            self.remove_assignment(&Identity::Group(group_id.to_string()))
+        })
+    }
+}
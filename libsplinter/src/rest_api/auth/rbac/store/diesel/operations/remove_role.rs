@@ -15,7 +15,7 @@
 use diesel::{dsl::delete, prelude::*};
 
 use crate::rest_api::auth::rbac::store::{
-    diesel::schema::{role_permissions, roles},
+    diesel::schema::{role_inclusions, role_permissions, roles},
     RoleBasedAuthorizationStoreError,
 };
 
@@ -34,6 +34,14 @@ where
         self.conn.transaction::<_, _, _>(|| {
             delete(role_permissions::table.filter(role_permissions::role_id.eq(role_id)))
                 .execute(self.conn)?;
+            delete(
+                role_inclusions::table.filter(
+                    role_inclusions::role_id
+                        .eq(role_id)
+                        .or(role_inclusions::included_role_id.eq(role_id)),
+                ),
+            )
+            .execute(self.conn)?;
 
             delete(roles::table.filter(roles::id.eq(role_id))).execute(self.conn)?;
 
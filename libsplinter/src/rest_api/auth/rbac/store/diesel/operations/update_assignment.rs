@@ -19,8 +19,8 @@ use diesel::{
 
 use crate::rest_api::auth::rbac::store::{
     diesel::{
-        models::{AssignmentModel, IdentityModel},
-        schema::assignments,
+        models::{AssignmentModel, IdentityModel, ScopedAssignmentModel},
+        schema::{assignments, scoped_assignments},
     },
     Assignment, RoleBasedAuthorizationStoreError,
 };
@@ -34,7 +34,7 @@ pub trait RoleBasedAuthorizationStoreUpdateAssignment {
     ) -> Result<(), RoleBasedAuthorizationStoreError>;
 }
 
-#[cfg(feature = "sqlite")]
+#[cfg(feature = "role-based-authorization-store-sqlite")]
 impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
     for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
@@ -42,14 +42,31 @@ impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, roles): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, roles, scoped): (
+            IdentityModel,
+            Vec<AssignmentModel>,
+            Vec<ScopedAssignmentModel>,
+        ) = assignment.into();
         self.conn.transaction::<_, _, _>(|| {
             delete(assignments::table.filter(assignments::identity.eq(&identity.identity)))
                 .execute(self.conn)?;
+            delete(
+                scoped_assignments::table
+                    .filter(scoped_assignments::identity.eq(&identity.identity)),
+            )
+            .execute(self.conn)?;
 
-            insert_into(assignments::table)
-                .values(roles)
-                .execute(self.conn)?;
+            if !roles.is_empty() {
+                insert_into(assignments::table)
+                    .values(roles)
+                    .execute(self.conn)?;
+            }
+
+            if !scoped.is_empty() {
+                insert_into(scoped_assignments::table)
+                    .values(scoped)
+                    .execute(self.conn)?;
+            }
 
             Ok(())
         })
@@ -64,14 +81,31 @@ impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, roles): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, roles, scoped): (
+            IdentityModel,
+            Vec<AssignmentModel>,
+            Vec<ScopedAssignmentModel>,
+        ) = assignment.into();
         self.conn.transaction::<_, _, _>(|| {
             delete(assignments::table.filter(assignments::identity.eq(&identity.identity)))
                 .execute(self.conn)?;
+            delete(
+                scoped_assignments::table
+                    .filter(scoped_assignments::identity.eq(&identity.identity)),
+            )
+            .execute(self.conn)?;
 
-            insert_into(assignments::table)
-                .values(roles)
-                .execute(self.conn)?;
+            if !roles.is_empty() {
+                insert_into(assignments::table)
+                    .values(roles)
+                    .execute(self.conn)?;
+            }
+
+            if !scoped.is_empty() {
+                insert_into(scoped_assignments::table)
+                    .values(scoped)
+                    .execute(self.conn)?;
+            }
 
             Ok(())
         })
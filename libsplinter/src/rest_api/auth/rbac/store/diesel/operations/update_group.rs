@@ -0,0 +1,106 @@
+// Copyright 2018-2021 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use diesel::{
+    dsl::{delete, insert_into, update},
+    prelude::*,
+};
+
+use crate::error::InvalidStateError;
+use crate::rest_api::auth::rbac::store::{
+    diesel::{
+        models::{GroupMemberModel, GroupModel},
+        schema::{identity_group_members, identity_groups},
+    },
+    Group, RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreUpdateGroup {
+    fn update_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError>;
+}
+
+#[cfg(feature = "role-based-authorization-store-sqlite")]
+impl<'a> RoleBasedAuthorizationStoreUpdateGroup
+    for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn update_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let (group, members): (GroupModel, Vec<GroupMemberModel>) = group.into();
+
+        self.conn.transaction::<_, _, _>(|| {
+            let updated = update(identity_groups::table.find(&group.id))
+                .set(identity_groups::display_name.eq(&group.display_name))
+                .execute(self.conn)?;
+
+            if updated == 0 {
+                return Err(InvalidStateError::with_message(format!(
+                    "Group {} does not exist",
+                    group.id
+                ))
+                .into());
+            }
+
+            delete(
+                identity_group_members::table
+                    .filter(identity_group_members::group_id.eq(&group.id)),
+            )
+            .execute(self.conn)?;
+
+            if !members.is_empty() {
+                insert_into(identity_group_members::table)
+                    .values(members)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "role-based-authorization-store-postgres")]
+impl<'a> RoleBasedAuthorizationStoreUpdateGroup
+    for RoleBasedAuthorizationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn update_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let (group, members): (GroupModel, Vec<GroupMemberModel>) = group.into();
+
+        self.conn.transaction::<_, _, _>(|| {
+            let updated = update(identity_groups::table.find(&group.id))
+                .set(identity_groups::display_name.eq(&group.display_name))
+                .execute(self.conn)?;
+
+            if updated == 0 {
+                return Err(InvalidStateError::with_message(format!(
+                    "Group {} does not exist",
+                    group.id
+                ))
+                .into());
+            }
+
+            delete(
+                identity_group_members::table
+                    .filter(identity_group_members::group_id.eq(&group.id)),
+            )
+            .execute(self.conn)?;
+
+            if !members.is_empty() {
+                insert_into(identity_group_members::table)
+                    .values(members)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
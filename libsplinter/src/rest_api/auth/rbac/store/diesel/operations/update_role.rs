@@ -19,8 +19,8 @@ use diesel::{
 
 use crate::rest_api::auth::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
-        schema::{role_permissions, roles},
+        models::{RoleInclusionModel, RoleModel, RolePermissionModel},
+        schema::{role_inclusions, role_permissions, roles},
     },
     Role, RoleBasedAuthorizationStoreError,
 };
@@ -31,24 +31,44 @@ pub trait RoleBasedAuthorizationStoreUpdateRole {
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError>;
 }
 
-#[cfg(feature = "sqlite")]
+#[cfg(feature = "role-based-authorization-store-sqlite")]
 impl<'a> RoleBasedAuthorizationStoreUpdateRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, inclusions): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleInclusionModel>,
+        ) = role.into();
 
         self.conn.transaction::<_, _, _>(|| {
+            let included_roles = inclusions
+                .iter()
+                .map(|inclusion| inclusion.included_role_id.clone())
+                .collect::<Vec<_>>();
+            self.check_role_inclusions(&role.id, &included_roles)?;
+
             delete(role_permissions::table.filter(role_permissions::role_id.eq(&role.id)))
                 .execute(self.conn)?;
+            delete(role_inclusions::table.filter(role_inclusions::role_id.eq(&role.id)))
+                .execute(self.conn)?;
 
             update(roles::table.find(&role.id))
                 .set(roles::display_name.eq(&role.display_name))
                 .execute(self.conn)?;
 
-            insert_into(role_permissions::table)
-                .values(permissions)
-                .execute(self.conn)?;
+            if !permissions.is_empty() {
+                insert_into(role_permissions::table)
+                    .values(permissions)
+                    .execute(self.conn)?;
+            }
+
+            if !inclusions.is_empty() {
+                insert_into(role_inclusions::table)
+                    .values(inclusions)
+                    .execute(self.conn)?;
+            }
 
             Ok(())
         })
@@ -60,19 +80,39 @@ impl<'a> RoleBasedAuthorizationStoreUpdateRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::pg::PgConnection>
 {
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, inclusions): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleInclusionModel>,
+        ) = role.into();
 
         self.conn.transaction::<_, _, _>(|| {
+            let included_roles = inclusions
+                .iter()
+                .map(|inclusion| inclusion.included_role_id.clone())
+                .collect::<Vec<_>>();
+            self.check_role_inclusions(&role.id, &included_roles)?;
+
             delete(role_permissions::table.filter(role_permissions::role_id.eq(&role.id)))
                 .execute(self.conn)?;
+            delete(role_inclusions::table.filter(role_inclusions::role_id.eq(&role.id)))
+                .execute(self.conn)?;
 
             update(roles::table.find(&role.id))
                 .set(roles::display_name.eq(&role.display_name))
                 .execute(self.conn)?;
 
-            insert_into(role_permissions::table)
-                .values(permissions)
-                .execute(self.conn)?;
+            if !permissions.is_empty() {
+                insert_into(role_permissions::table)
+                    .values(permissions)
+                    .execute(self.conn)?;
+            }
+
+            if !inclusions.is_empty() {
+                insert_into(role_inclusions::table)
+                    .values(inclusions)
+                    .execute(self.conn)?;
+            }
 
             Ok(())
         })
@@ -42,3 +42,38 @@ table! {
         role_id -> Text,
     }
 }
+
+table! {
+    scoped_assignments (identity, scope, role_id) {
+        identity -> Text,
+        scope -> Text,
+        role_id -> Text,
+    }
+}
+
+table! {
+    role_inclusions (role_id, included_role_id) {
+        role_id -> Text,
+        included_role_id -> Text,
+    }
+}
+
+joinable!(role_inclusions -> roles (role_id));
+
+table! {
+    identity_groups (id) {
+        id -> Text,
+        display_name -> Text,
+    }
+}
+
+table! {
+    identity_group_members (group_id, identity) {
+        group_id -> Text,
+        identity -> Text,
+        identity_type -> SmallInt,
+    }
+}
+
+joinable!(identity_group_members -> identity_groups (group_id));
+allow_tables_to_appear_in_same_query!(identity_groups, identity_group_members);
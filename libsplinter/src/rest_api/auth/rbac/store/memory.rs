@@ -0,0 +1,433 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory-backed RoleBasedAuthorizationStore implementation.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::{
+    ConstraintViolationError, ConstraintViolationType, InternalError, InvalidStateError,
+};
+
+use super::{
+    check_role_inclusions, Assignment, Group, Identity, Role, RoleBasedAuthorizationStore,
+    RoleBasedAuthorizationStoreError, Scope,
+};
+
+#[derive(Default)]
+struct MemoryRoleBasedAuthorizationState {
+    /// The roles, in the order they were added
+    roles: Vec<Role>,
+    /// The assignments, in the order they were added
+    assignments: Vec<Assignment>,
+    /// The groups, in the order they were added
+    groups: Vec<Group>,
+}
+
+impl MemoryRoleBasedAuthorizationState {
+    /// Checks the inclusions of the given role against the stored roles.
+    fn check_role_inclusions(&self, role: &Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+        check_role_inclusions(role.id(), role.included_roles(), |role_id| {
+            Ok(self
+                .roles
+                .iter()
+                .find(|existing| existing.id() == role_id)
+                .map(|existing| existing.included_roles().to_vec()))
+        })
+    }
+}
+
+/// A memory-backed implementation of RoleBasedAuthorizationStore.
+///
+/// Roles, assignments and groups are lost when the store is dropped, so this store is only suitable for
+/// testing or for nodes that do not require persistent state.
+#[derive(Clone, Default)]
+pub struct MemoryRoleBasedAuthorizationStore {
+    state: Arc<Mutex<MemoryRoleBasedAuthorizationState>>,
+}
+
+impl MemoryRoleBasedAuthorizationStore {
+    /// Constructs a new instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock_state(&self) -> Result<MutexGuard<MemoryRoleBasedAuthorizationState>, InternalError> {
+        self.state.lock().map_err(|_| {
+            InternalError::with_message("role-based authorization lock was poisoned".into())
+        })
+    }
+}
+
+/// Returns the string that an identity is stored under; keys, user IDs and group IDs share a
+/// namespace.
+fn identity_value(identity: &Identity) -> &str {
+    match identity {
+        Identity::Key(key) => key,
+        Identity::User(user_id) => user_id,
+        Identity::Group(group_id) => group_id,
+    }
+}
+
+impl RoleBasedAuthorizationStore for MemoryRoleBasedAuthorizationStore {
+    fn get_role(&self, id: &str) -> Result<Option<Role>, RoleBasedAuthorizationStoreError> {
+        Ok(self
+            .lock_state()?
+            .roles
+            .iter()
+            .find(|role| role.id() == id)
+            .cloned())
+    }
+
+    fn list_roles(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        Ok(Box::new(self.lock_state()?.roles.clone().into_iter()))
+    }
+
+    fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let mut state = self.lock_state()?;
+        if state
+            .roles
+            .iter()
+            .any(|existing| existing.id() == role.id())
+        {
+            return Err(ConstraintViolationError::with_violation_type(
+                ConstraintViolationType::Unique,
+            )
+            .into());
+        }
+
+        state.check_role_inclusions(&role)?;
+
+        state.roles.push(role);
+        Ok(())
+    }
+
+    fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let mut state = self.lock_state()?;
+        state.check_role_inclusions(&role)?;
+
+        let existing = state
+            .roles
+            .iter_mut()
+            .find(|existing| existing.id() == role.id())
+            .ok_or_else(|| {
+                InvalidStateError::with_message(format!("Role {} does not exist", role.id()))
+            })?;
+
+        *existing = role;
+        Ok(())
+    }
+
+    fn remove_role(&self, role_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let mut state = self.lock_state()?;
+        state.roles.retain(|role| role.id() != role_id);
+        for role in state.roles.iter_mut() {
+            role.included_roles.retain(|included| included != role_id);
+        }
+        Ok(())
+    }
+
+    fn get_assignment(
+        &self,
+        identity: &Identity,
+    ) -> Result<Option<Assignment>, RoleBasedAuthorizationStoreError> {
+        Ok(self
+            .lock_state()?
+            .assignments
+            .iter()
+            .find(|assignment| identity_value(assignment.identity()) == identity_value(identity))
+            .cloned())
+    }
+
+    fn get_assigned_roles(
+        &self,
+        identity: &Identity,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        let state = self.lock_state()?;
+        let role_ids = state
+            .assignments
+            .iter()
+            .find(|assignment| identity_value(assignment.identity()) == identity_value(identity))
+            .map(|assignment| assignment.roles().to_vec())
+            .unwrap_or_default();
+
+        Ok(Box::new(
+            state
+                .roles
+                .iter()
+                .filter(|role| role_ids.iter().any(|role_id| role_id == role.id()))
+                .cloned()
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ))
+    }
+
+    fn get_assigned_roles_in_scope(
+        &self,
+        identity: &Identity,
+        scope: &Scope,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        let state = self.lock_state()?;
+        let role_ids = state
+            .assignments
+            .iter()
+            .find(|assignment| identity_value(assignment.identity()) == identity_value(identity))
+            .map(|assignment| {
+                assignment
+                    .scoped_roles()
+                    .iter()
+                    .filter(|(assigned_scope, _)| assigned_scope.contains(scope))
+                    .flat_map(|(_, roles)| roles.iter())
+                    .chain(assignment.roles().iter())
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        Ok(Box::new(
+            state
+                .roles
+                .iter()
+                .filter(|role| role_ids.iter().any(|role_id| role_id == role.id()))
+                .cloned()
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ))
+    }
+
+    fn list_assignments(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
+    {
+        Ok(Box::new(self.lock_state()?.assignments.clone().into_iter()))
+    }
+
+    fn add_assignment(
+        &self,
+        assignment: Assignment,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let mut state = self.lock_state()?;
+        if state.assignments.iter().any(|existing| {
+            identity_value(existing.identity()) == identity_value(assignment.identity())
+        }) {
+            return Err(ConstraintViolationError::with_violation_type(
+                ConstraintViolationType::Unique,
+            )
+            .into());
+        }
+
+        state.assignments.push(assignment);
+        Ok(())
+    }
+
+    fn update_assignment(
+        &self,
+        assignment: Assignment,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let mut state = self.lock_state()?;
+        let existing = state
+            .assignments
+            .iter_mut()
+            .find(|existing| {
+                identity_value(existing.identity()) == identity_value(assignment.identity())
+            })
+            .ok_or_else(|| {
+                InvalidStateError::with_message(format!(
+                    "Assignment for {} does not exist",
+                    identity_value(assignment.identity())
+                ))
+            })?;
+
+        *existing = assignment;
+        Ok(())
+    }
+
+    fn remove_assignment(
+        &self,
+        identity: &Identity,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.lock_state()?
+            .assignments
+            .retain(|assignment| identity_value(assignment.identity()) != identity_value(identity));
+        Ok(())
+    }
+
+    fn get_group(&self, id: &str) -> Result<Option<Group>, RoleBasedAuthorizationStoreError> {
+        Ok(self
+            .lock_state()?
+            .groups
+            .iter()
+            .find(|group| group.id() == id)
+            .cloned())
+    }
+
+    fn list_groups(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError> {
+        Ok(Box::new(self.lock_state()?.groups.clone().into_iter()))
+    }
+
+    fn list_groups_for_identity(
+        &self,
+        identity: &Identity,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError> {
+        Ok(Box::new(
+            self.lock_state()?
+                .groups
+                .iter()
+                .filter(|group| {
+                    group
+                        .members()
+                        .iter()
+                        .any(|member| identity_value(member) == identity_value(identity))
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ))
+    }
+
+    fn add_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let mut state = self.lock_state()?;
+        if state
+            .groups
+            .iter()
+            .any(|existing| existing.id() == group.id())
+        {
+            return Err(ConstraintViolationError::with_violation_type(
+                ConstraintViolationType::Unique,
+            )
+            .into());
+        }
+
+        state.groups.push(group);
+        Ok(())
+    }
+
+    fn update_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let mut state = self.lock_state()?;
+        let existing = state
+            .groups
+            .iter_mut()
+            .find(|existing| existing.id() == group.id())
+            .ok_or_else(|| {
+                InvalidStateError::with_message(format!("Group {} does not exist", group.id()))
+            })?;
+
+        *existing = group;
+        Ok(())
+    }
+
+    fn remove_group(&self, group_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let mut state = self.lock_state()?;
+        state.groups.retain(|group| group.id() != group_id);
+        state.assignments.retain(
+            |assignment| !matches!(assignment.identity(), Identity::Group(id) if id == group_id),
+        );
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::rest_api::auth::rbac::store::tests;
+
+    #[test]
+    fn memory_add_and_get_role() {
+        tests::test_add_and_get_role(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_list_roles() {
+        tests::test_list_roles(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_update_role() {
+        tests::test_update_role(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_remove_role() {
+        tests::test_remove_role(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_add_and_get_assignment() {
+        tests::test_add_and_get_assignment(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_get_assigned_roles() {
+        tests::test_get_assigned_roles(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_get_assigned_roles_in_scope() {
+        tests::test_get_assigned_roles_in_scope(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_scoped_assignment() {
+        tests::test_scoped_assignment(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_list_assignments() {
+        tests::test_list_assignments(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_update_assignment() {
+        tests::test_update_assignment(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_remove_assignment() {
+        tests::test_remove_assignment(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_role_inclusions() {
+        tests::test_role_inclusions(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_add_and_get_group() {
+        tests::test_add_and_get_group(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_list_groups_for_identity() {
+        tests::test_list_groups_for_identity(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_update_group() {
+        tests::test_update_group(&MemoryRoleBasedAuthorizationStore::new());
+    }
+
+    #[test]
+    fn memory_remove_group() {
+        tests::test_remove_group(&MemoryRoleBasedAuthorizationStore::new());
+    }
+}
@@ -17,20 +17,30 @@
 #[cfg(feature = "diesel")]
 mod diesel;
 mod error;
+mod memory;
 
-use crate::error::InvalidStateError;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{InvalidArgumentError, InvalidStateError};
 
 #[cfg(feature = "diesel")]
 pub use self::diesel::DieselRoleBasedAuthorizationStore;
 
 pub use error::RoleBasedAuthorizationStoreError;
+pub use memory::MemoryRoleBasedAuthorizationStore;
 
 /// A Role is a named set of permissions.
+///
+/// A role may also include other roles, in which case it grants the permissions of the included
+/// roles as well as its own.
 #[derive(Clone)]
 pub struct Role {
     id: String,
     display_name: String,
     permissions: Vec<String>,
+    included_roles: Vec<String>,
 }
 
 impl Role {
@@ -49,20 +59,52 @@ impl Role {
         &self.permissions
     }
 
+    /// Returns the IDs of the roles included in this role.
+    pub fn included_roles(&self) -> &[String] {
+        &self.included_roles
+    }
+
     /// Convert this role back into a builder, in order to update its values.
     pub fn into_update_builder(self) -> RoleUpdateBuilder {
         RoleUpdateBuilder {
             id: self.id,
             display_name: Some(self.display_name),
             permissions: self.permissions,
+            included_roles: self.included_roles,
         }
     }
 
     /// Converts this role into it's constituent parts.  These parts are in the tuple:
-    /// `(id, display_name, permissions)`.
-    pub fn into_parts(self) -> (String, String, Vec<String>) {
-        (self.id, self.display_name, self.permissions)
+    /// `(id, display_name, permissions, included_roles)`.
+    pub fn into_parts(self) -> (String, String, Vec<String>, Vec<String>) {
+        (
+            self.id,
+            self.display_name,
+            self.permissions,
+            self.included_roles,
+        )
+    }
+}
+
+/// Checks that a role has at least one permission or included role, and does not include itself.
+fn validate_role_contents(
+    id: &str,
+    permissions: &[String],
+    included_roles: &[String],
+) -> Result<(), InvalidStateError> {
+    if permissions.is_empty() && included_roles.is_empty() {
+        return Err(InvalidStateError::with_message(
+            "A role requires at least one permission or included role".into(),
+        ));
+    }
+
+    if included_roles.iter().any(|included| included == id) {
+        return Err(InvalidStateError::with_message(
+            "A role cannot include itself".into(),
+        ));
     }
+
+    Ok(())
 }
 
 /// A builder to create new roles.
@@ -71,6 +113,7 @@ pub struct RoleBuilder {
     id: Option<String>,
     display_name: Option<String>,
     permissions: Vec<String>,
+    included_roles: Vec<String>,
 }
 
 impl RoleBuilder {
@@ -97,6 +140,12 @@ impl RoleBuilder {
         self
     }
 
+    /// Sets the IDs of the roles included in the new role.
+    pub fn with_included_roles(mut self, included_roles: Vec<String>) -> Self {
+        self.included_roles = included_roles;
+        self
+    }
+
     /// Builds the new Role.
     ///
     /// # Errors
@@ -104,14 +153,9 @@ impl RoleBuilder {
     /// Returns an [`InvalidStateError`] under the following conditions:
     /// * no ID or an empty ID was provided
     /// * no display name or an empty display name was provided
-    /// * empty permissions were provided
+    /// * neither permissions nor included roles were provided
+    /// * the role includes itself
     pub fn build(self) -> Result<Role, InvalidStateError> {
-        if self.permissions.is_empty() {
-            return Err(InvalidStateError::with_message(
-                "A role requires at least one permission".into(),
-            ));
-        }
-
         let id = self
             .id
             .ok_or_else(|| InvalidStateError::with_message("A role requires an id field".into()))?;
@@ -120,6 +164,8 @@ impl RoleBuilder {
                 "A role requires a non-empty id field".into(),
             ));
         }
+
+        validate_role_contents(&id, &self.permissions, &self.included_roles)?;
         let display_name = self.display_name.ok_or_else(|| {
             InvalidStateError::with_message("A role requires a display_name field".into())
         })?;
@@ -134,6 +180,7 @@ impl RoleBuilder {
             id,
             display_name,
             permissions: self.permissions,
+            included_roles: self.included_roles,
         })
     }
 }
@@ -145,6 +192,7 @@ pub struct RoleUpdateBuilder {
     id: String,
     display_name: Option<String>,
     permissions: Vec<String>,
+    included_roles: Vec<String>,
 }
 
 impl RoleUpdateBuilder {
@@ -160,19 +208,22 @@ impl RoleUpdateBuilder {
         self
     }
 
+    /// Updates the IDs of the roles included in the role.
+    pub fn with_included_roles(mut self, included_roles: Vec<String>) -> Self {
+        self.included_roles = included_roles;
+        self
+    }
+
     /// Builds the updated Role.
     ///
     /// # Errors
     ///
     /// Returns an [`InvalidStateError`] under the following conditions:
     /// * an empty display name was provided
-    /// * empty permissions were provided
+    /// * neither permissions nor included roles were provided
+    /// * the role includes itself
     pub fn build(self) -> Result<Role, InvalidStateError> {
-        if self.permissions.is_empty() {
-            return Err(InvalidStateError::with_message(
-                "A role requires at least one permission".into(),
-            ));
-        }
+        validate_role_contents(&self.id, &self.permissions, &self.included_roles)?;
 
         let display_name = self.display_name.ok_or_else(|| {
             InvalidStateError::with_message("A role requires a display_name field".into())
@@ -188,6 +239,7 @@ impl RoleUpdateBuilder {
             id: self.id,
             display_name,
             permissions: self.permissions,
+            included_roles: self.included_roles,
         })
     }
 }
@@ -199,13 +251,304 @@ pub enum Identity {
     Key(String),
     /// A user ID-based identity.
     User(String),
+    /// A group of identities, identified by the group's ID. The roles assigned to a group apply
+    /// to each of its members.
+    Group(String),
+}
+
+/// Checks that including the given roles in the role with the given ID would not create a cycle
+/// of inclusions, and that each of the included roles exists.
+///
+/// The `get_included_roles` function returns the IDs of the roles included in the role with the
+/// given ID, or `None` if the role does not exist.
+fn check_role_inclusions<F>(
+    role_id: &str,
+    included_roles: &[String],
+    mut get_included_roles: F,
+) -> Result<(), RoleBasedAuthorizationStoreError>
+where
+    F: FnMut(&str) -> Result<Option<Vec<String>>, RoleBasedAuthorizationStoreError>,
+{
+    let mut visited = HashSet::new();
+    let mut to_visit = included_roles.to_vec();
+
+    while let Some(included_role_id) = to_visit.pop() {
+        if included_role_id == role_id {
+            return Err(InvalidStateError::with_message(format!(
+                "Role {} cannot include itself, directly or through other roles",
+                role_id
+            ))
+            .into());
+        }
+
+        if !visited.insert(included_role_id.clone()) {
+            continue;
+        }
+
+        let transitive_roles = get_included_roles(&included_role_id)?.ok_or_else(|| {
+            InvalidStateError::with_message(format!(
+                "Included role {} does not exist",
+                included_role_id
+            ))
+        })?;
+        to_visit.extend(transitive_roles);
+    }
+
+    Ok(())
+}
+
+/// A Group is a named set of identities that may be assigned roles as a whole, using
+/// [`Identity::Group`].
+#[derive(Clone)]
+pub struct Group {
+    id: String,
+    display_name: String,
+    members: Vec<Identity>,
+}
+
+impl Group {
+    /// Returns the group's id.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the group's display name.
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    /// Returns the identities that are members of the group.
+    pub fn members(&self) -> &[Identity] {
+        &self.members
+    }
+
+    /// Convert this group back into a builder, in order to update its values.
+    pub fn into_update_builder(self) -> GroupUpdateBuilder {
+        GroupUpdateBuilder {
+            id: self.id,
+            display_name: Some(self.display_name),
+            members: self.members,
+        }
+    }
+
+    /// Converts this group into it's constituent parts.  These parts are in the tuple:
+    /// `(id, display_name, members)`.
+    pub fn into_parts(self) -> (String, String, Vec<Identity>) {
+        (self.id, self.display_name, self.members)
+    }
+}
+
+/// Checks that a group has a display name, and that none of its members are groups.
+fn validate_group_contents(
+    display_name: Option<String>,
+    members: &[Identity],
+) -> Result<String, InvalidStateError> {
+    let display_name = display_name.ok_or_else(|| {
+        InvalidStateError::with_message("A group requires a display_name field".into())
+    })?;
+
+    if display_name.is_empty() {
+        return Err(InvalidStateError::with_message(
+            "A group requires a non-empty display_name field".into(),
+        ));
+    }
+
+    if members
+        .iter()
+        .any(|member| matches!(member, Identity::Group(_)))
+    {
+        return Err(InvalidStateError::with_message(
+            "A group cannot have another group as a member".into(),
+        ));
+    }
+
+    Ok(display_name)
+}
+
+/// A builder to create new groups.
+#[derive(Default)]
+pub struct GroupBuilder {
+    id: Option<String>,
+    display_name: Option<String>,
+    members: Vec<Identity>,
+}
+
+impl GroupBuilder {
+    /// Constructs a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID for the new group.
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets the display name for the new group.
+    pub fn with_display_name(mut self, display_name: String) -> Self {
+        self.display_name = Some(display_name);
+        self
+    }
+
+    /// Sets the members of the new group.
+    pub fn with_members(mut self, members: Vec<Identity>) -> Self {
+        self.members = members;
+        self
+    }
+
+    /// Builds the new Group.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidStateError`] under the following conditions:
+    /// * no ID or an empty ID was provided
+    /// * no display name or an empty display name was provided
+    /// * a group was provided as a member
+    pub fn build(self) -> Result<Group, InvalidStateError> {
+        let id = self.id.ok_or_else(|| {
+            InvalidStateError::with_message("A group requires an id field".into())
+        })?;
+        if id.is_empty() {
+            return Err(InvalidStateError::with_message(
+                "A group requires a non-empty id field".into(),
+            ));
+        }
+
+        let display_name = validate_group_contents(self.display_name, &self.members)?;
+
+        Ok(Group {
+            id,
+            display_name,
+            members: self.members,
+        })
+    }
+}
+
+/// Updates an existing group.
+///
+/// This builder only allows the updatable fields to be modified.
+pub struct GroupUpdateBuilder {
+    id: String,
+    display_name: Option<String>,
+    members: Vec<Identity>,
+}
+
+impl GroupUpdateBuilder {
+    /// Updates the display name for the group.
+    pub fn with_display_name(mut self, display_name: String) -> Self {
+        self.display_name = Some(display_name);
+        self
+    }
+
+    /// Updates the members of the group.
+    pub fn with_members(mut self, members: Vec<Identity>) -> Self {
+        self.members = members;
+        self
+    }
+
+    /// Builds the updated Group.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidStateError`] under the following conditions:
+    /// * an empty display name was provided
+    /// * a group was provided as a member
+    pub fn build(self) -> Result<Group, InvalidStateError> {
+        let display_name = validate_group_contents(self.display_name, &self.members)?;
+
+        Ok(Group {
+            id: self.id,
+            display_name,
+            members: self.members,
+        })
+    }
+}
+
+/// The resource that a scoped assignment of roles applies to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Scope {
+    /// The circuit with the given ID, including all of its services.
+    Circuit(String),
+    /// A single service on a circuit.
+    Service {
+        circuit_id: String,
+        service_id: String,
+    },
+}
+
+impl Scope {
+    /// Returns whether or not the given scope is within this scope. A circuit scope contains
+    /// itself and the scopes of the circuit's services; a service scope only contains itself.
+    pub fn contains(&self, other: &Scope) -> bool {
+        match (self, other) {
+            (Scope::Circuit(circuit_id), Scope::Circuit(other_circuit_id))
+            | (
+                Scope::Circuit(circuit_id),
+                Scope::Service {
+                    circuit_id: other_circuit_id,
+                    ..
+                },
+            ) => circuit_id == other_circuit_id,
+            (Scope::Service { .. }, _) => self == other,
+        }
+    }
+}
+
+/// Displays a scope as either `<circuit_id>` or `<circuit_id>::<service_id>`.
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Scope::Circuit(circuit_id) => f.write_str(circuit_id),
+            Scope::Service {
+                circuit_id,
+                service_id,
+            } => write!(f, "{}::{}", circuit_id, service_id),
+        }
+    }
+}
+
+/// Parses a scope from either `<circuit_id>` or `<circuit_id>::<service_id>`.
+impl FromStr for Scope {
+    type Err = InvalidArgumentError;
+
+    fn from_str(scope: &str) -> Result<Self, Self::Err> {
+        let mut parts = scope.splitn(2, "::");
+        match (parts.next(), parts.next()) {
+            (Some(circuit_id), None) if !circuit_id.is_empty() => {
+                Ok(Scope::Circuit(circuit_id.to_string()))
+            }
+            (Some(circuit_id), Some(service_id))
+                if !circuit_id.is_empty()
+                    && !service_id.is_empty()
+                    && !service_id.contains("::") =>
+            {
+                Ok(Scope::Service {
+                    circuit_id: circuit_id.to_string(),
+                    service_id: service_id.to_string(),
+                })
+            }
+            _ => Err(InvalidArgumentError::new(
+                "scope".into(),
+                format!(
+                    "\"{}\" is not of the form <circuit_id> or <circuit_id>::<service_id>",
+                    scope
+                ),
+            )),
+        }
+    }
 }
 
 /// An assignment of roles to a particular identity.
+///
+/// Roles may be assigned globally, in which case their permissions apply to every resource, or
+/// to a [`Scope`], in which case their permissions only apply to requests for that circuit or
+/// service.
 #[derive(Clone)]
 pub struct Assignment {
     identity: Identity,
     roles: Vec<String>,
+    scoped_roles: BTreeMap<Scope, Vec<String>>,
 }
 
 impl Assignment {
@@ -214,29 +557,64 @@ impl Assignment {
         &self.identity
     }
 
-    /// Returns the assigned roles IDs.
+    /// Returns the globally assigned roles IDs.
     pub fn roles(&self) -> &[String] {
         &self.roles
     }
 
+    /// Returns the role IDs assigned to specific scopes.
+    pub fn scoped_roles(&self) -> &BTreeMap<Scope, Vec<String>> {
+        &self.scoped_roles
+    }
+
     /// Convert this assignment back into a builder, in order to update its values.
     pub fn into_update_builder(self) -> AssignmentUpdateBuilder {
-        let Assignment { identity, roles } = self;
-        AssignmentUpdateBuilder { identity, roles }
+        let Assignment {
+            identity,
+            roles,
+            scoped_roles,
+        } = self;
+        AssignmentUpdateBuilder {
+            identity,
+            roles,
+            scoped_roles,
+        }
     }
 
     /// Converts this assignment into it's constituent parts.  These parts are in the tuple:
-    /// `(identity, roles)`.
-    pub fn into_parts(self) -> (Identity, Vec<String>) {
-        (self.identity, self.roles)
+    /// `(identity, roles, scoped_roles)`.
+    pub fn into_parts(self) -> (Identity, Vec<String>, BTreeMap<Scope, Vec<String>>) {
+        (self.identity, self.roles, self.scoped_roles)
     }
 }
 
+/// Checks that an assignment has at least one role, and that each scope has at least one role.
+fn validate_assignment_roles(
+    roles: &[String],
+    scoped_roles: &BTreeMap<Scope, Vec<String>>,
+) -> Result<(), InvalidStateError> {
+    if roles.is_empty() && scoped_roles.is_empty() {
+        return Err(InvalidStateError::with_message(
+            "An assignment requires at least one role".into(),
+        ));
+    }
+
+    if let Some((scope, _)) = scoped_roles.iter().find(|(_, roles)| roles.is_empty()) {
+        return Err(InvalidStateError::with_message(format!(
+            "An assignment requires at least one role for scope {}",
+            scope
+        )));
+    }
+
+    Ok(())
+}
+
 /// Constructs new Assignments.
 #[derive(Default)]
 pub struct AssignmentBuilder {
     identity: Option<Identity>,
     roles: Vec<String>,
+    scoped_roles: BTreeMap<Scope, Vec<String>>,
 }
 
 impl AssignmentBuilder {
@@ -251,31 +629,35 @@ impl AssignmentBuilder {
         self
     }
 
-    /// Sets the assigned roles.
+    /// Sets the globally assigned roles.
     pub fn with_roles(mut self, roles: Vec<String>) -> Self {
         self.roles = roles;
         self
     }
 
+    /// Sets the roles assigned to specific scopes.
+    pub fn with_scoped_roles(mut self, scoped_roles: BTreeMap<Scope, Vec<String>>) -> Self {
+        self.scoped_roles = scoped_roles;
+        self
+    }
+
     /// Builds a new assignment.
     ///
     /// # Errors
     ///
     /// Returns an [`InvalidStateError`] under the following conditions:
     /// * no identity was provided
-    /// * no roles were provided
+    /// * no roles or scoped roles were provided
+    /// * a scope was provided without any roles
     pub fn build(self) -> Result<Assignment, InvalidStateError> {
-        if self.roles.is_empty() {
-            return Err(InvalidStateError::with_message(
-                "An assignment requires at least one role".into(),
-            ));
-        }
+        validate_assignment_roles(&self.roles, &self.scoped_roles)?;
 
         Ok(Assignment {
             identity: self.identity.ok_or_else(|| {
                 InvalidStateError::with_message("An assignment requires an identity field".into())
             })?,
             roles: self.roles,
+            scoped_roles: self.scoped_roles,
         })
     }
 }
@@ -286,31 +668,36 @@ impl AssignmentBuilder {
 pub struct AssignmentUpdateBuilder {
     identity: Identity,
     roles: Vec<String>,
+    scoped_roles: BTreeMap<Scope, Vec<String>>,
 }
 
 impl AssignmentUpdateBuilder {
-    /// Updates the assigned roles.
+    /// Updates the globally assigned roles.
     pub fn with_roles(mut self, roles: Vec<String>) -> Self {
         self.roles = roles;
         self
     }
 
+    /// Updates the roles assigned to specific scopes.
+    pub fn with_scoped_roles(mut self, scoped_roles: BTreeMap<Scope, Vec<String>>) -> Self {
+        self.scoped_roles = scoped_roles;
+        self
+    }
+
     /// Builds the updated assignment.
     ///
     /// # Errors
     ///
     /// Returns an [`InvalidStateError`] under the following conditions:
-    /// * no roles were provided
+    /// * no roles or scoped roles were provided
+    /// * a scope was provided without any roles
     pub fn build(self) -> Result<Assignment, InvalidStateError> {
-        if self.roles.is_empty() {
-            return Err(InvalidStateError::with_message(
-                "An assignment requires at least one role".into(),
-            ));
-        }
+        validate_assignment_roles(&self.roles, &self.scoped_roles)?;
 
         Ok(Assignment {
             identity: self.identity,
             roles: self.roles,
+            scoped_roles: self.scoped_roles,
         })
     }
 }
@@ -330,13 +717,16 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
     /// # Errors
     ///
     /// Returns a `ConstraintViolation` error if a duplicate role ID is added.
+    ///
+    /// Returns a `InvalidState` error if an included role does not exist.
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError>;
 
     /// Updates a role.
     ///
     /// # Errors
     ///
-    /// Returns a `InvalidState` error if the role does not exist.
+    /// Returns a `InvalidState` error if the role does not exist, if an included role does not
+    /// exist, or if the included roles would create a cycle of inclusions.
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError>;
 
     /// Removes a role.
@@ -352,12 +742,21 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
         identity: &Identity,
     ) -> Result<Option<Assignment>, RoleBasedAuthorizationStoreError>;
 
-    /// Returns the assigned roles for the given Identity.
+    /// Returns the globally assigned roles for the given Identity.
     fn get_assigned_roles(
         &self,
         identity: &Identity,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
 
+    /// Returns the roles that apply to the given Identity within the given scope. These are the
+    /// globally assigned roles, along with the roles assigned to any scope that contains the
+    /// given scope.
+    fn get_assigned_roles_in_scope(
+        &self,
+        identity: &Identity,
+        scope: &Scope,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
+
     /// Lists all assignments.
     fn list_assignments(
         &self,
@@ -394,6 +793,37 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
         identity: &Identity,
     ) -> Result<(), RoleBasedAuthorizationStoreError>;
 
+    /// Returns the group for the given ID, if one exists.
+    fn get_group(&self, id: &str) -> Result<Option<Group>, RoleBasedAuthorizationStoreError>;
+
+    /// Lists all groups.
+    fn list_groups(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError>;
+
+    /// Lists the groups that the given Identity is a member of.
+    fn list_groups_for_identity(
+        &self,
+        identity: &Identity,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Group>>, RoleBasedAuthorizationStoreError>;
+
+    /// Adds a group.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConstraintViolation` error if a duplicate group ID is added.
+    fn add_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError>;
+
+    /// Updates a group.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `InvalidState` error if the group does not exist.
+    fn update_group(&self, group: Group) -> Result<(), RoleBasedAuthorizationStoreError>;
+
+    /// Removes a group, along with the group's assignment.
+    fn remove_group(&self, group_id: &str) -> Result<(), RoleBasedAuthorizationStoreError>;
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore>;
 }
@@ -403,3 +833,980 @@ impl Clone for Box<dyn RoleBasedAuthorizationStore> {
         self.clone_box()
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// This tests verifies the following:
+    /// 1. Adds a role via the store API
+    /// 2. Verifies it has been added by getting the role via the store API
+    pub fn test_add_and_get_role(store: &dyn RoleBasedAuthorizationStore) {
+        let stored_role = store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id");
+        assert!(stored_role.is_none());
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let stored_role = store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the added role");
+
+        assert_eq!("test-role", stored_role.id());
+        assert_eq!("Test Role", stored_role.display_name());
+        assert_eq!(
+            &["a".to_string(), "b".to_string(), "c".to_string()],
+            stored_role.permissions()
+        );
+    }
+
+    /// This tests verifies the following:
+    /// 1. Adds two roles via the store API
+    /// 2. Verifies they have been added by listing the roles via the store API
+    pub fn test_list_roles(store: &dyn RoleBasedAuthorizationStore) {
+        let role = RoleBuilder::new()
+            .with_id("test-role-1".into())
+            .with_display_name("Test Role 1".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let role = RoleBuilder::new()
+            .with_id("test-role-2".into())
+            .with_display_name("Test Role 2".into())
+            .with_permissions(vec!["x".to_string(), "y".to_string(), "z".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let mut stored_role_iter = store.list_roles().expect("Unable to lookup role by id");
+
+        assert_eq!(2, stored_role_iter.len());
+
+        let stored_role = stored_role_iter
+            .next()
+            .expect("has 2 items, but returned None");
+        assert_eq!("test-role-1", stored_role.id());
+        assert_eq!("Test Role 1", stored_role.display_name());
+        assert_eq!(
+            &["a".to_string(), "b".to_string(), "c".to_string()],
+            stored_role.permissions()
+        );
+
+        let stored_role = stored_role_iter
+            .next()
+            .expect("has 2 items, but returned None");
+        assert_eq!("test-role-2", stored_role.id());
+        assert_eq!("Test Role 2", stored_role.display_name());
+        assert_eq!(
+            &["x".to_string(), "y".to_string(), "z".to_string()],
+            stored_role.permissions()
+        );
+    }
+
+    /// This tests verifies the following:
+    /// 1. Adds a role and verifies that it has been inserted
+    /// 2. Update the role and verifies that it has been changed, via the store API
+    pub fn test_update_role(store: &dyn RoleBasedAuthorizationStore) {
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let stored_role = store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the added role");
+
+        assert_eq!("test-role", stored_role.id());
+        assert_eq!("Test Role", stored_role.display_name());
+        assert_eq!(
+            &["a".to_string(), "b".to_string(), "c".to_string()],
+            stored_role.permissions()
+        );
+
+        let updated_role = stored_role
+            .into_update_builder()
+            .with_display_name("Updated Test Role".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string()])
+            .build()
+            .expect("Unable to build updated role");
+
+        store
+            .update_role(updated_role)
+            .expect("Unable to update role");
+
+        let stored_role = store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the added role");
+
+        assert_eq!("test-role", stored_role.id());
+        assert_eq!("Updated Test Role", stored_role.display_name());
+        assert_eq!(
+            &["a".to_string(), "b".to_string()],
+            stored_role.permissions()
+        );
+    }
+
+    /// This tests verifies the following:
+    /// 1. Adds a role and verifies that it has been inserted
+    /// 2. Removes a role and verifies that it has been removed, via the store API
+    /// 3. Verifies that the remove is idempotent
+    pub fn test_remove_role(store: &dyn RoleBasedAuthorizationStore) {
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let stored_role = store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the added role");
+
+        assert_eq!("test-role", stored_role.id());
+        assert_eq!("Test Role", stored_role.display_name());
+        assert_eq!(
+            &["a".to_string(), "b".to_string(), "c".to_string()],
+            stored_role.permissions()
+        );
+
+        store
+            .remove_role(stored_role.id())
+            .expect("Unable to remove role");
+
+        let stored_role = store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id");
+        assert!(stored_role.is_none());
+
+        // verify that the remove is idempotent
+        store
+            .remove_role("test-role")
+            .expect("Unable to remove role");
+    }
+
+    /// This test verifies the following:
+    /// 1. Adds a role.
+    /// 2. Adds an assignment for that role
+    /// 3. Verifies the assignment was added via the store API
+    pub fn test_add_and_get_assignment(store: &dyn RoleBasedAuthorizationStore) {
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let stored_assignment = store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment was not found");
+
+        assert_eq!(
+            &Identity::User("some-user-id".into()),
+            stored_assignment.identity()
+        );
+        assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
+    }
+
+    /// This test verifies the following:
+    /// 1. Adds two roles
+    /// 2. Adds an assignment for those roles
+    /// 3. Verifies the roles are returned via the get_assigned_roles API
+    pub fn test_get_assigned_roles(store: &dyn RoleBasedAuthorizationStore) {
+        let role = RoleBuilder::new()
+            .with_id("test-role-1".into())
+            .with_display_name("Test Role 1".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let role = RoleBuilder::new()
+            .with_id("test-role-2".into())
+            .with_display_name("Test Role 2".into())
+            .with_permissions(vec!["x".to_string(), "y".to_string(), "z".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role-1".to_string(), "test-role-2".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let mut assigned_roles = store
+            .get_assigned_roles(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assigned roles");
+
+        assert_eq!(2, assigned_roles.len());
+
+        let stored_role = assigned_roles
+            .next()
+            .expect("has 2 items, but returned None");
+        assert_eq!("test-role-1", stored_role.id());
+        assert_eq!("Test Role 1", stored_role.display_name());
+        assert_eq!(
+            &["a".to_string(), "b".to_string(), "c".to_string()],
+            stored_role.permissions()
+        );
+
+        let stored_role = assigned_roles
+            .next()
+            .expect("has 2 items, but returned None");
+        assert_eq!("test-role-2", stored_role.id());
+        assert_eq!("Test Role 2", stored_role.display_name());
+        assert_eq!(
+            &["x".to_string(), "y".to_string(), "z".to_string()],
+            stored_role.permissions()
+        );
+    }
+
+    /// This test verifies the following:
+    /// 1. Adds a role.
+    /// 2. Add two assignments for that role
+    /// 3. Verifies the assignments were added via the store's list API
+    pub fn test_list_assignments(store: &dyn RoleBasedAuthorizationStore) {
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id-1".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::Key("some-key-1".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let mut stored_assignment_iter =
+            store.list_assignments().expect("Unable to get assignment");
+
+        assert_eq!(2, stored_assignment_iter.len());
+
+        let stored_assignment = stored_assignment_iter
+            .next()
+            .expect("has 2 items, but returned None");
+        assert_eq!(
+            &Identity::User("some-user-id-1".into()),
+            stored_assignment.identity()
+        );
+        assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
+
+        let stored_assignment = stored_assignment_iter
+            .next()
+            .expect("has 2 items, but returned None");
+        assert_eq!(
+            &Identity::Key("some-key-1".into()),
+            stored_assignment.identity()
+        );
+        assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
+    }
+
+    /// This test verifies the following:
+    /// 1. Add two roles
+    /// 2. Add an assignment to one of the roles
+    /// 3. Update the assignment to have both roles and verify via the store API
+    /// 4. Update the assignment to only have the other role, and verify via the store API
+    pub fn test_update_assignment(store: &dyn RoleBasedAuthorizationStore) {
+        let role = RoleBuilder::new()
+            .with_id("test-role-1".into())
+            .with_display_name("Test Role 1".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let role = RoleBuilder::new()
+            .with_id("test-role-2".into())
+            .with_display_name("Test Role 2".into())
+            .with_permissions(vec!["x".to_string(), "y".to_string(), "z".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role-1".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let stored_assignment = store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment was not found");
+
+        assert_eq!(
+            &Identity::User("some-user-id".into()),
+            stored_assignment.identity()
+        );
+        assert_eq!(&vec!["test-role-1".to_string()], stored_assignment.roles());
+
+        let updated_assignment = stored_assignment
+            .into_update_builder()
+            .with_roles(vec!["test-role-1".to_string(), "test-role-2".to_string()])
+            .build()
+            .expect("Unable to build updated assignment");
+
+        store
+            .update_assignment(updated_assignment)
+            .expect("Unable to update assignment");
+
+        let stored_assignment = store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment was not found");
+
+        assert_eq!(
+            &Identity::User("some-user-id".into()),
+            stored_assignment.identity()
+        );
+        assert_eq!(
+            &vec!["test-role-1".to_string(), "test-role-2".to_string()],
+            stored_assignment.roles()
+        );
+
+        let updated_assignment = stored_assignment
+            .into_update_builder()
+            .with_roles(vec!["test-role-2".to_string()])
+            .build()
+            .expect("Unable to build updated assignment");
+
+        store
+            .update_assignment(updated_assignment)
+            .expect("Unable to update assignment");
+
+        let stored_assignment = store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment was not found");
+
+        assert_eq!(
+            &Identity::User("some-user-id".into()),
+            stored_assignment.identity()
+        );
+        assert_eq!(&vec!["test-role-2".to_string()], stored_assignment.roles());
+    }
+
+    /// This test verifies the following:
+    /// 1. Add a role
+    /// 2. Add an assignment for the role and verify with the store API
+    /// 3. Remove the assignment and verify its removal with the API
+    /// 4. Verify that the removal is idempotent
+    pub fn test_remove_assignment(store: &dyn RoleBasedAuthorizationStore) {
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let stored_assignment = store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment was not found");
+
+        assert_eq!(
+            &Identity::User("some-user-id".into()),
+            stored_assignment.identity()
+        );
+        assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
+
+        store
+            .remove_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to remove assignment");
+
+        let stored_assignment = store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment");
+
+        assert!(stored_assignment.is_none());
+
+        // verify that the removal is idempotent
+        store
+            .remove_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to remove assignment");
+    }
+
+    /// Verifies that scopes are parsed from and displayed as `<circuit_id>` or
+    /// `<circuit_id>::<service_id>`, and that invalid scopes are rejected.
+    #[test]
+    fn scope_parse_and_display() {
+        let scope: Scope = "abcde-01234"
+            .parse()
+            .expect("Unable to parse circuit scope");
+        assert_eq!(scope, Scope::Circuit("abcde-01234".into()));
+        assert_eq!(scope.to_string(), "abcde-01234");
+
+        let scope: Scope = "abcde-01234::a000"
+            .parse()
+            .expect("Unable to parse service scope");
+        assert_eq!(
+            scope,
+            Scope::Service {
+                circuit_id: "abcde-01234".into(),
+                service_id: "a000".into(),
+            }
+        );
+        assert_eq!(scope.to_string(), "abcde-01234::a000");
+
+        assert!("".parse::<Scope>().is_err());
+        assert!("::a000".parse::<Scope>().is_err());
+        assert!("abcde-01234::".parse::<Scope>().is_err());
+        assert!("abcde-01234::a000::b000".parse::<Scope>().is_err());
+    }
+
+    /// Verifies that a circuit scope contains the scopes of its services, and that a service scope
+    /// only contains itself.
+    #[test]
+    fn scope_contains() {
+        let circuit = Scope::Circuit("abcde-01234".into());
+        let service = Scope::Service {
+            circuit_id: "abcde-01234".into(),
+            service_id: "a000".into(),
+        };
+        let other_service = Scope::Service {
+            circuit_id: "abcde-01234".into(),
+            service_id: "b000".into(),
+        };
+
+        assert!(circuit.contains(&circuit));
+        assert!(circuit.contains(&service));
+        assert!(!circuit.contains(&Scope::Circuit("fghij-56789".into())));
+        assert!(service.contains(&service));
+        assert!(!service.contains(&circuit));
+        assert!(!service.contains(&other_service));
+    }
+
+    /// This test verifies the following:
+    /// 1. Adds two roles
+    /// 2. Adds an assignment with one global role and one role scoped to a circuit's service
+    /// 3. Verifies the scoped roles are returned with the assignment via the store API
+    /// 4. Updates the assignment to scope the role to the circuit instead and verifies the update
+    pub fn test_scoped_assignment(store: &dyn RoleBasedAuthorizationStore) {
+        add_scoped_test_roles(store);
+
+        let service_scope = Scope::Service {
+            circuit_id: "abcde-01234".into(),
+            service_id: "a000".into(),
+        };
+        let mut scoped_roles = BTreeMap::new();
+        scoped_roles.insert(service_scope.clone(), vec!["test-role-2".to_string()]);
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role-1".to_string()])
+            .with_scoped_roles(scoped_roles.clone())
+            .build()
+            .expect("Unable to build assignment");
+
+        store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let stored_assignment = store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment was not found");
+
+        assert_eq!(&vec!["test-role-1".to_string()], stored_assignment.roles());
+        assert_eq!(&scoped_roles, stored_assignment.scoped_roles());
+
+        let mut scoped_roles = BTreeMap::new();
+        scoped_roles.insert(
+            Scope::Circuit("abcde-01234".into()),
+            vec!["test-role-2".to_string()],
+        );
+
+        let updated_assignment = stored_assignment
+            .into_update_builder()
+            .with_roles(vec![])
+            .with_scoped_roles(scoped_roles.clone())
+            .build()
+            .expect("Unable to build updated assignment");
+
+        store
+            .update_assignment(updated_assignment)
+            .expect("Unable to update assignment");
+
+        let stored_assignment = store
+            .list_assignments()
+            .expect("Unable to list assignments")
+            .next()
+            .expect("Assignment was not found");
+
+        assert!(stored_assignment.roles().is_empty());
+        assert_eq!(&scoped_roles, stored_assignment.scoped_roles());
+    }
+
+    /// This test verifies the following:
+    /// 1. Adds two roles
+    /// 2. Adds an assignment with one global role and one role scoped to a circuit
+    /// 3. Verifies that only the global role is returned via the get_assigned_roles API
+    /// 4. Verifies that both roles are returned for the circuit and its services, and that only
+    ///    the global role is returned for another circuit, via the get_assigned_roles_in_scope API
+    pub fn test_get_assigned_roles_in_scope(store: &dyn RoleBasedAuthorizationStore) {
+        add_scoped_test_roles(store);
+
+        let mut scoped_roles = BTreeMap::new();
+        scoped_roles.insert(
+            Scope::Circuit("abcde-01234".into()),
+            vec!["test-role-2".to_string()],
+        );
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::Key("some-key".into()))
+            .with_roles(vec!["test-role-1".to_string()])
+            .with_scoped_roles(scoped_roles)
+            .build()
+            .expect("Unable to build assignment");
+
+        store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let identity = Identity::Key("some-key".into());
+        let role_ids = |roles: Box<dyn ExactSizeIterator<Item = Role>>| {
+            let mut role_ids = roles.map(|role| role.id().to_string()).collect::<Vec<_>>();
+            role_ids.sort();
+            role_ids
+        };
+
+        assert_eq!(
+            vec!["test-role-1".to_string()],
+            role_ids(
+                store
+                    .get_assigned_roles(&identity)
+                    .expect("Unable to get assigned roles")
+            )
+        );
+
+        assert_eq!(
+            vec!["test-role-1".to_string(), "test-role-2".to_string()],
+            role_ids(
+                store
+                    .get_assigned_roles_in_scope(&identity, &Scope::Circuit("abcde-01234".into()))
+                    .expect("Unable to get assigned roles")
+            )
+        );
+
+        assert_eq!(
+            vec!["test-role-1".to_string(), "test-role-2".to_string()],
+            role_ids(
+                store
+                    .get_assigned_roles_in_scope(
+                        &identity,
+                        &Scope::Service {
+                            circuit_id: "abcde-01234".into(),
+                            service_id: "a000".into(),
+                        }
+                    )
+                    .expect("Unable to get assigned roles")
+            )
+        );
+
+        assert_eq!(
+            vec!["test-role-1".to_string()],
+            role_ids(
+                store
+                    .get_assigned_roles_in_scope(&identity, &Scope::Circuit("fghij-56789".into()))
+                    .expect("Unable to get assigned roles")
+            )
+        );
+    }
+
+    fn add_scoped_test_roles(store: &dyn RoleBasedAuthorizationStore) {
+        let role = RoleBuilder::new()
+            .with_id("test-role-1".into())
+            .with_display_name("Test Role 1".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let role = RoleBuilder::new()
+            .with_id("test-role-2".into())
+            .with_display_name("Test Role 2".into())
+            .with_permissions(vec!["x".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+    }
+
+    /// This tests verifies the following:
+    /// 1. Adds a role that includes another role via the store API
+    /// 2. Verifies that the included roles have been stored
+    /// 3. Verifies that a role that includes an unknown role is rejected
+    /// 4. Verifies that an update that would create a cycle of inclusions is rejected
+    /// 5. Verifies that removing a role removes it from the roles that include it
+    pub fn test_role_inclusions(store: &dyn RoleBasedAuthorizationStore) {
+        add_scoped_test_roles(store);
+
+        let role = RoleBuilder::new()
+            .with_id("test-role-3".into())
+            .with_display_name("Test Role 3".into())
+            .with_included_roles(vec!["test-role-2".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        store.add_role(role).expect("Unable to add role");
+
+        let stored_role = store
+            .get_role("test-role-3")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the added role");
+        assert!(stored_role.permissions().is_empty());
+        assert_eq!(&["test-role-2".to_string()], stored_role.included_roles());
+
+        let role = RoleBuilder::new()
+            .with_id("test-role-4".into())
+            .with_display_name("Test Role 4".into())
+            .with_included_roles(vec!["unknown-role".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        assert!(matches!(
+            store.add_role(role),
+            Err(RoleBasedAuthorizationStoreError::InvalidState(_))
+        ));
+        assert!(store
+            .get_role("test-role-4")
+            .expect("Unable to lookup role by id")
+            .is_none());
+
+        // test-role-3 includes test-role-2, so test-role-2 cannot include test-role-3
+        let updated_role = store
+            .get_role("test-role-2")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the role")
+            .into_update_builder()
+            .with_included_roles(vec!["test-role-1".to_string(), "test-role-3".to_string()])
+            .build()
+            .expect("Unable to build updated role");
+
+        assert!(matches!(
+            store.update_role(updated_role),
+            Err(RoleBasedAuthorizationStoreError::InvalidState(_))
+        ));
+
+        let stored_role = store
+            .get_role("test-role-2")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the role");
+        assert!(stored_role.included_roles().is_empty());
+
+        let updated_role = stored_role
+            .into_update_builder()
+            .with_included_roles(vec!["test-role-1".to_string()])
+            .build()
+            .expect("Unable to build updated role");
+
+        store
+            .update_role(updated_role)
+            .expect("Unable to update role");
+
+        store
+            .remove_role("test-role-1")
+            .expect("Unable to remove role");
+
+        let stored_role = store
+            .get_role("test-role-2")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the role");
+        assert!(stored_role.included_roles().is_empty());
+    }
+
+    /// Verifies that a group cannot have another group as a member.
+    #[test]
+    fn group_builder_rejects_nested_groups() {
+        assert!(GroupBuilder::new()
+            .with_id("test-group".into())
+            .with_display_name("Test Group".into())
+            .with_members(vec![Identity::Group("other-group".into())])
+            .build()
+            .is_err());
+    }
+
+    /// This tests verifies the following:
+    /// 1. Adds a group via the store API
+    /// 2. Verifies it has been added by getting and listing the groups via the store API
+    /// 3. Verifies that a duplicate group is rejected
+    pub fn test_add_and_get_group(store: &dyn RoleBasedAuthorizationStore) {
+        let stored_group = store
+            .get_group("test-group")
+            .expect("Unable to lookup group by id");
+        assert!(stored_group.is_none());
+
+        let group = GroupBuilder::new()
+            .with_id("test-group".into())
+            .with_display_name("Test Group".into())
+            .with_members(vec![
+                Identity::Key("abc123".into()),
+                Identity::User("some-user-id".into()),
+            ])
+            .build()
+            .expect("Unable to build group");
+
+        store.add_group(group.clone()).expect("Unable to add group");
+
+        let stored_group = store
+            .get_group("test-group")
+            .expect("Unable to lookup group by id")
+            .expect("Did not find the added group");
+
+        assert_eq!("test-group", stored_group.id());
+        assert_eq!("Test Group", stored_group.display_name());
+        assert_eq!(
+            &[
+                Identity::Key("abc123".into()),
+                Identity::User("some-user-id".into()),
+            ],
+            stored_group.members()
+        );
+
+        let groups = store.list_groups().expect("Unable to list groups");
+        assert_eq!(1, groups.len());
+
+        assert!(matches!(
+            store.add_group(group),
+            Err(RoleBasedAuthorizationStoreError::ConstraintViolation(_))
+        ));
+    }
+
+    /// This tests verifies the following:
+    /// 1. Adds two groups with overlapping members via the store API
+    /// 2. Verifies that the groups for each member are listed via the store API
+    pub fn test_list_groups_for_identity(store: &dyn RoleBasedAuthorizationStore) {
+        let group = GroupBuilder::new()
+            .with_id("test-group-1".into())
+            .with_display_name("Test Group 1".into())
+            .with_members(vec![
+                Identity::Key("abc123".into()),
+                Identity::User("some-user-id".into()),
+            ])
+            .build()
+            .expect("Unable to build group");
+
+        store.add_group(group).expect("Unable to add group");
+
+        let group = GroupBuilder::new()
+            .with_id("test-group-2".into())
+            .with_display_name("Test Group 2".into())
+            .with_members(vec![Identity::User("some-user-id".into())])
+            .build()
+            .expect("Unable to build group");
+
+        store.add_group(group).expect("Unable to add group");
+
+        let group_ids = |groups: Box<dyn ExactSizeIterator<Item = Group>>| {
+            let mut group_ids = groups
+                .map(|group| group.id().to_string())
+                .collect::<Vec<_>>();
+            group_ids.sort();
+            group_ids
+        };
+
+        assert_eq!(
+            vec!["test-group-1".to_string(), "test-group-2".to_string()],
+            group_ids(
+                store
+                    .list_groups_for_identity(&Identity::User("some-user-id".into()))
+                    .expect("Unable to list groups")
+            )
+        );
+        assert_eq!(
+            vec!["test-group-1".to_string()],
+            group_ids(
+                store
+                    .list_groups_for_identity(&Identity::Key("abc123".into()))
+                    .expect("Unable to list groups")
+            )
+        );
+        assert!(group_ids(
+            store
+                .list_groups_for_identity(&Identity::User("other-user-id".into()))
+                .expect("Unable to list groups")
+        )
+        .is_empty());
+    }
+
+    /// This tests verifies the following:
+    /// 1. Adds a group via the store API
+    /// 2. Updates the group's display name and members via the store API
+    /// 3. Verifies the group has been updated
+    /// 4. Verifies that updating an unknown group is rejected
+    pub fn test_update_group(store: &dyn RoleBasedAuthorizationStore) {
+        let group = GroupBuilder::new()
+            .with_id("test-group".into())
+            .with_display_name("Test Group".into())
+            .with_members(vec![Identity::Key("abc123".into())])
+            .build()
+            .expect("Unable to build group");
+
+        store.add_group(group.clone()).expect("Unable to add group");
+
+        let updated_group = group
+            .into_update_builder()
+            .with_display_name("Updated Group".into())
+            .with_members(vec![Identity::User("some-user-id".into())])
+            .build()
+            .expect("Unable to build updated group");
+
+        store
+            .update_group(updated_group)
+            .expect("Unable to update group");
+
+        let stored_group = store
+            .get_group("test-group")
+            .expect("Unable to lookup group by id")
+            .expect("Did not find the group");
+
+        assert_eq!("Updated Group", stored_group.display_name());
+        assert_eq!(
+            &[Identity::User("some-user-id".into())],
+            stored_group.members()
+        );
+
+        let unknown_group = GroupBuilder::new()
+            .with_id("unknown-group".into())
+            .with_display_name("Unknown Group".into())
+            .build()
+            .expect("Unable to build group");
+
+        assert!(matches!(
+            store.update_group(unknown_group),
+            Err(RoleBasedAuthorizationStoreError::InvalidState(_))
+        ));
+    }
+
+    /// This tests verifies the following:
+    /// 1. Adds a group and assigns it a role via the store API
+    /// 2. Removes the group via the store API
+    /// 3. Verifies the group and its assignment have been removed
+    pub fn test_remove_group(store: &dyn RoleBasedAuthorizationStore) {
+        add_scoped_test_roles(store);
+
+        let group = GroupBuilder::new()
+            .with_id("test-group".into())
+            .with_display_name("Test Group".into())
+            .with_members(vec![Identity::Key("abc123".into())])
+            .build()
+            .expect("Unable to build group");
+
+        store.add_group(group).expect("Unable to add group");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::Group("test-group".into()))
+            .with_roles(vec!["test-role-1".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let stored_assignment = store
+            .get_assignment(&Identity::Group("test-group".into()))
+            .expect("Unable to get assignment")
+            .expect("Did not find the assignment");
+        assert_eq!(
+            &Identity::Group("test-group".into()),
+            stored_assignment.identity()
+        );
+
+        store
+            .remove_group("test-group")
+            .expect("Unable to remove group");
+
+        assert!(store
+            .get_group("test-group")
+            .expect("Unable to lookup group by id")
+            .is_none());
+        assert!(store
+            .get_assignment(&Identity::Group("test-group".into()))
+            .expect("Unable to get assignment")
+            .is_none());
+
+        // verify that the remove is idempotent
+        store
+            .remove_group("test-group")
+            .expect("Unable to remove group");
+    }
+}
@@ -26,6 +26,8 @@ use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
 use crate::channel;
+#[cfg(feature = "circuit-durability")]
+use crate::circuit::durable::DuplicateFilter;
 use crate::mesh::{Envelope, Mesh, RecvTimeoutError as MeshRecvTimeoutError};
 use crate::network::reply::InboundRouter;
 use crate::protos::circuit::{
@@ -484,6 +486,8 @@ fn run_service_loop(
         service.service_id()
     ))?;
 
+    #[cfg(feature = "circuit-durability")]
+    let mut duplicate_filter = DuplicateFilter::new();
     loop {
         let service_message: ServiceMessage = match service_recv.recv() {
             Ok(ProcessorMessage::ServiceMessage(message)) => Ok(message),
@@ -514,17 +518,47 @@ fn run_service_loop(
                     error!("unable to handle admin direct message: {}", err);
                 }
             }
-            ServiceMessage::CircuitDirectMessage(mut direct_message) => {
+            ServiceMessage::CircuitDirectMessage(direct_message) => {
+                #[cfg(feature = "circuit-durability")]
+                match duplicate_filter.is_duplicate(&direct_message) {
+                    Ok(true) => {
+                        debug!(
+                            "Dropping duplicate durable message {} from {} to {}",
+                            direct_message.get_durable_sequence_number(),
+                            direct_message.get_sender(),
+                            direct_message.get_recipient(),
+                        );
+                        continue;
+                    }
+                    Ok(false) => (),
+                    Err(err) => error!(
+                        "Unable to check durable message {} for duplicates: {}",
+                        direct_message.get_durable_sequence_number(),
+                        err
+                    ),
+                }
+
                 let msg_context = ServiceMessageContext {
-                    sender: direct_message.take_sender(),
-                    circuit: direct_message.take_circuit(),
-                    correlation_id: direct_message.take_correlation_id(),
+                    sender: direct_message.get_sender().to_string(),
+                    circuit: direct_message.get_circuit().to_string(),
+                    correlation_id: direct_message.get_correlation_id().to_string(),
                 };
 
                 if let Err(err) = service.handle_message(direct_message.get_payload(), &msg_context)
                 {
                     error!("unable to handle circuit direct message: {}", err);
                 }
+
+                #[cfg(feature = "circuit-durability")]
+                {
+                    if let Err(err) = duplicate_filter.record_handled(&direct_message) {
+                        error!(
+                            "Unable to record durable message {} as handled: {}",
+                            direct_message.get_durable_sequence_number(),
+                            err
+                        );
+                    }
+                }
             }
         }
     }
@@ -14,14 +14,10 @@
 
 //! Implementation of a `StoreFactory` for in memory
 
-#[cfg(feature = "sqlite")]
-use diesel::{
-    r2d2::{ConnectionManager, Pool},
-    sqlite::SqliteConnection,
-};
-
 #[cfg(feature = "admin-service-event-store")]
 use crate::admin::service::event::store::memory::MemoryAdminServiceEventStore;
+#[cfg(feature = "admin-service")]
+use crate::admin::store::memory::MemoryAdminServiceStore;
 #[cfg(feature = "biome-oauth")]
 use crate::biome::MemoryOAuthUserSessionStore;
 #[cfg(feature = "biome-credentials")]
@@ -34,6 +30,10 @@ use crate::biome::{KeyStore, MemoryKeyStore};
 use crate::biome::{MemoryUserProfileStore, UserProfileStore};
 #[cfg(feature = "oauth")]
 use crate::oauth::store::MemoryInflightOAuthRequestStore;
+#[cfg(feature = "registry-database")]
+use crate::registry::MemoryRegistry;
+#[cfg(feature = "authorization")]
+use crate::rest_api::auth::rbac::store::MemoryRoleBasedAuthorizationStore;
 
 use super::StoreFactory;
 
@@ -43,6 +43,8 @@ pub(in crate::store) const DEFAULT_IN_MEMORY_EVENT_LIMIT: usize = 100;
 /// A `StoryFactory` backed by memory.
 #[derive(Default)]
 pub struct MemoryStoreFactory {
+    #[cfg(feature = "admin-service")]
+    admin_service_store: MemoryAdminServiceStore,
     #[cfg(feature = "biome-credentials")]
     biome_credentials_store: MemoryCredentialsStore,
     #[cfg(feature = "biome-key-management")]
@@ -55,6 +57,10 @@ pub struct MemoryStoreFactory {
     inflight_request_store: MemoryInflightOAuthRequestStore,
     #[cfg(feature = "biome-profile")]
     biome_profile_store: MemoryUserProfileStore,
+    #[cfg(feature = "registry-database")]
+    registry_store: MemoryRegistry,
+    #[cfg(feature = "authorization")]
+    role_based_authorization_store: MemoryRoleBasedAuthorizationStore,
 }
 
 impl MemoryStoreFactory {
@@ -77,6 +83,8 @@ impl MemoryStoreFactory {
         let biome_profile_store = MemoryUserProfileStore::new();
 
         Self {
+            #[cfg(feature = "admin-service")]
+            admin_service_store: MemoryAdminServiceStore::new(),
             #[cfg(feature = "biome-credentials")]
             biome_credentials_store,
             #[cfg(feature = "biome-key-management")]
@@ -89,6 +97,10 @@ impl MemoryStoreFactory {
             inflight_request_store,
             #[cfg(feature = "biome-profile")]
             biome_profile_store,
+            #[cfg(feature = "registry-database")]
+            registry_store: MemoryRegistry::new(),
+            #[cfg(feature = "authorization")]
+            role_based_authorization_store: MemoryRoleBasedAuthorizationStore::new(),
         }
     }
 }
@@ -114,27 +126,9 @@ impl StoreFactory for MemoryStoreFactory {
         Box::new(self.biome_oauth_user_session_store.clone())
     }
 
-    #[cfg(all(feature = "admin-service", feature = "sqlite"))]
+    #[cfg(feature = "admin-service")]
     fn get_admin_service_store(&self) -> Box<dyn crate::admin::store::AdminServiceStore> {
-        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
-        let pool = Pool::builder()
-            .max_size(1)
-            .build(connection_manager)
-            .expect("Failed to build connection pool");
-
-        crate::migrations::run_sqlite_migrations(
-            &*pool.get().expect("Failed to get connection for migrations"),
-        )
-        .expect("Failed to run migrations");
-
-        Box::new(crate::admin::store::diesel::DieselAdminServiceStore::new(
-            pool,
-        ))
-    }
-
-    #[cfg(all(feature = "admin-service", not(feature = "sqlite")))]
-    fn get_admin_service_store(&self) -> Box<dyn crate::admin::store::AdminServiceStore> {
-        unimplemented!()
+        Box::new(self.admin_service_store.clone())
     }
 
     #[cfg(feature = "oauth")]
@@ -144,50 +138,16 @@ impl StoreFactory for MemoryStoreFactory {
         Box::new(self.inflight_request_store.clone())
     }
 
-    #[cfg(all(feature = "registry-database", feature = "sqlite"))]
+    #[cfg(feature = "registry-database")]
     fn get_registry_store(&self) -> Box<dyn crate::registry::RwRegistry> {
-        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
-        let pool = Pool::builder()
-            .max_size(1)
-            .build(connection_manager)
-            .expect("Failed to build connection pool");
-
-        crate::migrations::run_sqlite_migrations(
-            &*pool.get().expect("Failed to get connection for migrations"),
-        )
-        .expect("Failed to run migrations");
-
-        Box::new(crate::registry::DieselRegistry::new(pool))
+        Box::new(self.registry_store.clone())
     }
 
-    #[cfg(all(feature = "registry-database", not(feature = "sqlite")))]
-    fn get_registry_store(&self) -> Box<dyn crate::registry::RwRegistry> {
-        unimplemented!()
-    }
-
-    #[cfg(all(feature = "authorization", feature = "sqlite"))]
+    #[cfg(feature = "authorization")]
     fn get_role_based_authorization_store(
         &self,
     ) -> Box<dyn crate::rest_api::auth::rbac::store::RoleBasedAuthorizationStore> {
-        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
-        let pool = Pool::builder()
-            .max_size(1)
-            .build(connection_manager)
-            .expect("Failed to build connection pool");
-
-        crate::migrations::run_sqlite_migrations(
-            &*pool.get().expect("Failed to get connection for migrations"),
-        )
-        .expect("Failed to run migrations");
-
-        Box::new(crate::rest_api::auth::rbac::store::DieselRoleBasedAuthorizationStore::new(pool))
-    }
-
-    #[cfg(all(feature = "authorization", not(feature = "sqlite")))]
-    fn get_role_based_authorization_store(
-        &self,
-    ) -> Box<dyn crate::rest_api::auth::roles::store::RoleBasedAuthorizationStore> {
-        unimplemented!()
+        Box::new(self.role_based_authorization_store.clone())
     }
 
     #[cfg(feature = "biome-profile")]
@@ -203,4 +163,11 @@ impl StoreFactory for MemoryStoreFactory {
             std::num::NonZeroUsize::new(DEFAULT_IN_MEMORY_EVENT_LIMIT).unwrap(),
         )
     }
+
+    #[cfg(feature = "circuit-durability")]
+    fn get_durable_message_store(
+        &self,
+    ) -> Box<dyn crate::circuit::durable::store::DurableMessageStore> {
+        Box::new(crate::circuit::durable::store::MemoryDurableMessageStore::new())
+    }
 }
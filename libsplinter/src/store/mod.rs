@@ -71,6 +71,11 @@ pub trait StoreFactory {
     fn get_admin_service_event_store(
         &self,
     ) -> Box<dyn crate::admin::service::event::store::AdminServiceEventStore>;
+
+    #[cfg(feature = "circuit-durability")]
+    fn get_durable_message_store(
+        &self,
+    ) -> Box<dyn crate::circuit::durable::store::DurableMessageStore>;
 }
 
 /// Creates a `StoreFactory` backed by the given connection
@@ -113,7 +118,7 @@ pub fn create_store_factory(
                     "Failed to build connection pool".to_string(),
                 )
             })?;
-            Ok(Box::new(sqlite::SqliteStoreFactory::new(pool)))
+            Ok(Box::new(sqlite::SqliteStoreFactory::new(pool)?))
         }
     }
 }
@@ -141,4 +141,11 @@ impl StoreFactory for PgStoreFactory {
     ) -> Box<dyn crate::admin::service::event::store::AdminServiceEventStore> {
         unimplemented!()
     }
+
+    #[cfg(feature = "circuit-durability")]
+    fn get_durable_message_store(
+        &self,
+    ) -> Box<dyn crate::circuit::durable::store::DurableMessageStore> {
+        Box::new(crate::circuit::durable::store::DieselDurableMessageStore::new(self.pool.clone()))
+    }
 }
@@ -20,6 +20,8 @@ use diesel::{
     sqlite::SqliteConnection,
 };
 
+use crate::error::InternalError;
+
 use super::StoreFactory;
 
 /// A `StoreFactory` backed by a SQLite database.
@@ -29,8 +31,38 @@ pub struct SqliteStoreFactory {
 
 impl SqliteStoreFactory {
     /// Create a new `SqliteStoreFactory`.
-    pub fn new(pool: Pool<ConnectionManager<SqliteConnection>>) -> Self {
-        Self { pool }
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if a store that this factory provides is enabled without its
+    /// SQLite implementation.
+    pub fn new(pool: Pool<ConnectionManager<SqliteConnection>>) -> Result<Self, InternalError> {
+        #[allow(unused_mut)]
+        let mut unsupported: Vec<&str> = vec![];
+        #[cfg(all(
+            feature = "biome-oauth",
+            not(feature = "biome-oauth-user-store-sqlite")
+        ))]
+        unsupported.push("biome OAuth user session store");
+        #[cfg(all(
+            feature = "oauth",
+            not(feature = "oauth-inflight-request-store-sqlite")
+        ))]
+        unsupported.push("OAuth inflight request store");
+        #[cfg(all(
+            feature = "authorization",
+            not(feature = "role-based-authorization-store-sqlite")
+        ))]
+        unsupported.push("role-based authorization store");
+
+        if !unsupported.is_empty() {
+            return Err(InternalError::with_message(format!(
+                "SQLite is not supported by the following stores: {}",
+                unsupported.join(", ")
+            )));
+        }
+
+        Ok(Self { pool })
     }
 }
 
@@ -52,13 +84,23 @@ impl StoreFactory for SqliteStoreFactory {
         ))
     }
 
-    #[cfg(feature = "biome-oauth")]
+    #[cfg(feature = "biome-oauth-user-store-sqlite")]
     fn get_biome_oauth_user_session_store(&self) -> Box<dyn crate::biome::OAuthUserSessionStore> {
         Box::new(crate::biome::DieselOAuthUserSessionStore::new(
             self.pool.clone(),
         ))
     }
 
+    #[cfg(all(
+        feature = "biome-oauth",
+        not(feature = "biome-oauth-user-store-sqlite")
+    ))]
+    fn get_biome_oauth_user_session_store(&self) -> Box<dyn crate::biome::OAuthUserSessionStore> {
+        // This configuration cannot be reached, as `SqliteStoreFactory::new` returns an error if
+        // this store is enabled without SQLite support. It merely satisfies the compiler.
+        unreachable!()
+    }
+
     #[cfg(feature = "admin-service")]
     fn get_admin_service_store(&self) -> Box<dyn crate::admin::store::AdminServiceStore> {
         Box::new(crate::admin::store::diesel::DieselAdminServiceStore::new(
@@ -66,7 +108,7 @@ impl StoreFactory for SqliteStoreFactory {
         ))
     }
 
-    #[cfg(feature = "oauth")]
+    #[cfg(feature = "oauth-inflight-request-store-sqlite")]
     fn get_oauth_inflight_request_store(
         &self,
     ) -> Box<dyn crate::oauth::store::InflightOAuthRequestStore> {
@@ -75,12 +117,24 @@ impl StoreFactory for SqliteStoreFactory {
         ))
     }
 
+    #[cfg(all(
+        feature = "oauth",
+        not(feature = "oauth-inflight-request-store-sqlite")
+    ))]
+    fn get_oauth_inflight_request_store(
+        &self,
+    ) -> Box<dyn crate::oauth::store::InflightOAuthRequestStore> {
+        // This configuration cannot be reached, as `SqliteStoreFactory::new` returns an error if
+        // this store is enabled without SQLite support. It merely satisfies the compiler.
+        unreachable!()
+    }
+
     #[cfg(feature = "registry-database")]
     fn get_registry_store(&self) -> Box<dyn crate::registry::RwRegistry> {
         Box::new(crate::registry::DieselRegistry::new(self.pool.clone()))
     }
 
-    #[cfg(feature = "authorization")]
+    #[cfg(feature = "role-based-authorization-store-sqlite")]
     fn get_role_based_authorization_store(
         &self,
     ) -> Box<dyn crate::rest_api::auth::rbac::store::RoleBasedAuthorizationStore> {
@@ -91,6 +145,18 @@ impl StoreFactory for SqliteStoreFactory {
         )
     }
 
+    #[cfg(all(
+        feature = "authorization",
+        not(feature = "role-based-authorization-store-sqlite")
+    ))]
+    fn get_role_based_authorization_store(
+        &self,
+    ) -> Box<dyn crate::rest_api::auth::rbac::store::RoleBasedAuthorizationStore> {
+        // This configuration cannot be reached, as `SqliteStoreFactory::new` returns an error if
+        // this store is enabled without SQLite support. It merely satisfies the compiler.
+        unreachable!()
+    }
+
     #[cfg(feature = "biome-profile")]
     fn get_biome_user_profile_store(&self) -> Box<dyn crate::biome::UserProfileStore> {
         Box::new(crate::biome::DieselUserProfileStore::new(self.pool.clone()))
@@ -116,6 +182,13 @@ impl StoreFactory for SqliteStoreFactory {
     ) -> Box<dyn crate::admin::service::event::store::AdminServiceEventStore> {
         unimplemented!()
     }
+
+    #[cfg(feature = "circuit-durability")]
+    fn get_durable_message_store(
+        &self,
+    ) -> Box<dyn crate::circuit::durable::store::DurableMessageStore> {
+        Box::new(crate::circuit::durable::store::DieselDurableMessageStore::new(self.pool.clone()))
+    }
 }
 
 #[derive(Default, Debug)]
@@ -39,6 +39,8 @@ mod error;
 pub mod inproc;
 pub(crate) mod matrix;
 pub mod multi;
+#[cfg(feature = "quic-transport")]
+pub mod quic;
 #[deprecated(since = "0.3.14", note = "please use splinter::transport::socket")]
 pub mod raw;
 pub mod socket;
@@ -187,6 +189,39 @@ pub mod tests {
         handle.join().unwrap();
     }
 
+    /// Tests that many messages of varying sizes sent on a connection are received in the order
+    /// they were sent.
+    pub fn test_message_ordering<T: Transport + Send + 'static>(mut transport: T, bind: &str) {
+        const MESSAGE_COUNT: u32 = 500;
+
+        let mut listener = assert_ok(transport.listen(bind));
+        let endpoint = listener.endpoint();
+
+        let handle = thread::spawn(move || {
+            let mut client = assert_ok(transport.connect(&endpoint));
+            for i in 0..MESSAGE_COUNT {
+                let mut message = i.to_be_bytes().to_vec();
+                message.resize(4 + (i as usize % 10) * 7000, i as u8);
+                assert_ok(block!(client.send(&message), SendError));
+            }
+
+            // Wait for the server to receive every message before disconnecting
+            assert_eq!(vec![0], assert_ok(block!(client.recv(), RecvError)));
+        });
+
+        let mut server = assert_ok(listener.incoming().next().unwrap());
+
+        for i in 0..MESSAGE_COUNT {
+            let message = assert_ok(block!(server.recv(), RecvError));
+            assert_eq!(&i.to_be_bytes()[..], &message[..4]);
+            assert_eq!(4 + (i as usize % 10) * 7000, message.len());
+        }
+
+        assert_ok(block!(server.send(&[0]), SendError));
+
+        handle.join().unwrap();
+    }
+
     /// Tests that we can create connections which exhibit normal polling behavior.
     ///
     /// We expect connections to initially be writable, and we expect them to be readable once the
@@ -0,0 +1,113 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::mpsc::TryRecvError;
+#[cfg(feature = "tls-crl")]
+use std::sync::{Arc, Mutex};
+
+use mio::Evented;
+use mio_extras::channel as mio_channel;
+
+#[cfg(feature = "tls-crl")]
+use crate::transport::tls::TrackedConnection;
+use crate::transport::{Connection, DisconnectError, RecvError, SendError};
+
+use super::peer::Command;
+use super::stream::stream_for_message;
+
+/// A `Connection` whose messages are exchanged with the thread that drives the QUIC connection.
+pub(super) struct QuicConnection {
+    commands: mio_channel::Sender<Command>,
+    inbound: mio_channel::Receiver<Vec<u8>>,
+    remote_endpoint: String,
+    local_endpoint: String,
+    /// Keeps the connection tracked for revocation checks while it is open
+    #[cfg(feature = "tls-crl")]
+    _tracked: Option<Arc<TrackedConnection>>,
+}
+
+impl QuicConnection {
+    pub fn new(
+        commands: mio_channel::Sender<Command>,
+        inbound: mio_channel::Receiver<Vec<u8>>,
+        remote_endpoint: String,
+        local_endpoint: String,
+    ) -> Self {
+        QuicConnection {
+            commands,
+            inbound,
+            remote_endpoint,
+            local_endpoint,
+            #[cfg(feature = "tls-crl")]
+            _tracked: None,
+        }
+    }
+
+    /// Returns a function that closes the connection from another thread.
+    #[cfg(feature = "tls-crl")]
+    pub fn closer(&self) -> Box<dyn Fn() + Send + Sync> {
+        let commands = Mutex::new(self.commands.clone());
+        Box::new(move || {
+            if let Ok(commands) = commands.lock() {
+                let _ = commands.send(Command::Disconnect);
+            }
+        })
+    }
+
+    /// Keeps the connection tracked for revocation checks until it is dropped.
+    #[cfg(feature = "tls-crl")]
+    pub fn set_tracked(&mut self, tracked: Arc<TrackedConnection>) {
+        self._tracked = Some(tracked);
+    }
+}
+
+impl Connection for QuicConnection {
+    fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
+        self.commands
+            .send(Command::Send {
+                stream: stream_for_message(message),
+                message: message.to_vec(),
+            })
+            .map_err(|err| match err {
+                mio_channel::SendError::Io(err) => SendError::IoError(err),
+                mio_channel::SendError::Disconnected(_) => SendError::Disconnected,
+            })
+    }
+
+    fn recv(&mut self) -> Result<Vec<u8>, RecvError> {
+        self.inbound.try_recv().map_err(|err| match err {
+            TryRecvError::Empty => RecvError::WouldBlock,
+            TryRecvError::Disconnected => RecvError::Disconnected,
+        })
+    }
+
+    fn remote_endpoint(&self) -> String {
+        self.remote_endpoint.clone()
+    }
+
+    fn local_endpoint(&self) -> String {
+        self.local_endpoint.clone()
+    }
+
+    fn disconnect(&mut self) -> Result<(), DisconnectError> {
+        match self.commands.send(Command::Disconnect) {
+            Ok(()) | Err(mio_channel::SendError::Disconnected(_)) => Ok(()),
+            Err(mio_channel::SendError::Io(err)) => Err(DisconnectError::IoError(err)),
+        }
+    }
+
+    fn evented(&self) -> &dyn Evented {
+        &self.inbound
+    }
+}
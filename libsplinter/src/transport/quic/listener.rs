@@ -0,0 +1,378 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use mio::net::UdpSocket;
+use mio::{Events, Poll, PollOpt, Ready, Token};
+use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
+use openssl::memcmp;
+use openssl::pkey::{PKey, Private};
+use openssl::sign::Signer;
+use rand::Rng;
+
+use crate::transport::{AcceptError, Connection, Listener};
+
+use super::connection::QuicConnection;
+use super::peer::{new_connection_id, QuicPeer, MAX_DATAGRAM_SIZE, SOCKET_TOKEN};
+use super::transport::{QuicTls, PROTOCOL_PREFIX};
+
+/// How often the listener thread checks whether the listener has been dropped
+const SHUTDOWN_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// The number of connections whose handshake may be in progress at the same time; further
+/// connection attempts are dropped until a handshake completes or fails
+const MAX_PENDING_HANDSHAKES: usize = 128;
+/// How long an address validation token is accepted after it has been sent to a client, in seconds
+const TOKEN_LIFETIME_SECS: u64 = 10;
+/// The number of bytes in the HMAC of an address validation token
+const TOKEN_HMAC_SIZE: usize = 32;
+
+pub(super) struct QuicListener {
+    local_endpoint: String,
+    connections: Receiver<QuicConnection>,
+    running: Arc<AtomicBool>,
+}
+
+impl QuicListener {
+    pub fn new(
+        local_endpoint: String,
+        connections: Receiver<QuicConnection>,
+        running: Arc<AtomicBool>,
+    ) -> Self {
+        QuicListener {
+            local_endpoint,
+            connections,
+            running,
+        }
+    }
+}
+
+impl Listener for QuicListener {
+    fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
+        self.connections
+            .recv()
+            .map(|connection| Box::new(connection) as Box<dyn Connection>)
+            .map_err(|_| AcceptError::ProtocolError("QUIC listener has stopped".into()))
+    }
+
+    fn endpoint(&self) -> String {
+        self.local_endpoint.clone()
+    }
+}
+
+impl Drop for QuicListener {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Mints and validates the address validation tokens sent to clients in Retry packets.
+///
+/// A client must echo a valid token before the listener keeps any state for its connection, which
+/// proves that it can receive packets at the address it sends from, so spoofed source addresses
+/// can't be used to start handshakes. A token is the time it was minted and the connection ID
+/// originally chosen by the client, followed by an HMAC of both and the client's address, keyed
+/// with a secret that is generated for each listener.
+struct AddressValidator {
+    key: PKey<Private>,
+}
+
+impl AddressValidator {
+    fn new() -> Result<Self, ErrorStack> {
+        let mut key = [0; TOKEN_HMAC_SIZE];
+        rand::thread_rng().fill(&mut key[..]);
+        Ok(AddressValidator {
+            key: PKey::hmac(&key)?,
+        })
+    }
+
+    /// Returns a token for the client at the given address that chose the given connection ID.
+    fn mint(&self, from: &SocketAddr, odcid: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+        let mut token = now_secs().to_be_bytes().to_vec();
+        token.extend_from_slice(odcid);
+        let hmac = self.hmac(from, &token)?;
+        token.extend_from_slice(&hmac);
+        Ok(token)
+    }
+
+    /// Returns the connection ID originally chosen by the client if the token was minted by this
+    /// validator for the client's address and has not expired.
+    fn validate(&self, from: &SocketAddr, token: &[u8]) -> Option<Vec<u8>> {
+        if token.len() < 8 + TOKEN_HMAC_SIZE {
+            return None;
+        }
+
+        let (data, hmac) = token.split_at(token.len() - TOKEN_HMAC_SIZE);
+        let expected = self.hmac(from, data).ok()?;
+        if !memcmp::eq(&expected, hmac) {
+            return None;
+        }
+
+        let minted = u64::from_be_bytes(<[u8; 8]>::try_from(&data[..8]).ok()?);
+        if now_secs().saturating_sub(minted) > TOKEN_LIFETIME_SECS {
+            return None;
+        }
+
+        Some(data[8..].to_vec())
+    }
+
+    fn hmac(&self, from: &SocketAddr, data: &[u8]) -> Result<Vec<u8>, ErrorStack> {
+        let mut signer = Signer::new(MessageDigest::sha256(), &self.key)?;
+        signer.update(from.to_string().as_bytes())?;
+        signer.update(data)?;
+        signer.sign_to_vec()
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// Drives the connections accepted on the listener's socket.
+///
+/// A client's address is validated with a Retry packet before a connection is created for it, and
+/// at most `MAX_PENDING_HANDSHAKES` handshakes are in progress at a time. Each connection is sent
+/// on `new_connections` once its handshake is complete and the certificate of its remote endpoint
+/// has been checked. The thread stops once the listener has been dropped and all of its
+/// connections are closed.
+pub(super) fn run_listener(
+    socket: UdpSocket,
+    mut config: quiche::Config,
+    tls: QuicTls,
+    local_endpoint: String,
+    new_connections: Sender<QuicConnection>,
+    running: Arc<AtomicBool>,
+) -> io::Result<()> {
+    let poll = Poll::new()?;
+    poll.register(&socket, SOCKET_TOKEN, Ready::readable(), PollOpt::edge())?;
+
+    let mut events = Events::with_capacity(1024);
+    let mut buf = [0; MAX_DATAGRAM_SIZE];
+    let mut out = [0; MAX_DATAGRAM_SIZE];
+    let address_validator =
+        AddressValidator::new().map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+
+    let mut peers: HashMap<usize, QuicPeer> = HashMap::new();
+    // Connections whose handshake is not yet complete
+    let mut pending: HashMap<usize, QuicConnection> = HashMap::new();
+    // The ID chosen by this side in the retry packet identifies a connection
+    let mut connection_ids: HashMap<Vec<u8>, usize> = HashMap::new();
+    let mut next_token = SOCKET_TOKEN.0 + 1;
+    #[cfg(feature = "tls-reload")]
+    let mut generation = tls.generation();
+
+    loop {
+        let timeout = peers
+            .values()
+            .filter_map(QuicPeer::timeout)
+            .min()
+            .map_or(SHUTDOWN_CHECK_INTERVAL, |timeout| {
+                timeout.min(SHUTDOWN_CHECK_INTERVAL)
+            });
+        poll.poll(&mut events, Some(timeout))?;
+
+        let listening = running.load(Ordering::SeqCst);
+
+        // Use the reloaded certificates for the connections accepted from now on
+        #[cfg(feature = "tls-reload")]
+        {
+            if generation != tls.generation() {
+                generation = tls.generation();
+                match tls.build_config(true) {
+                    Ok(reloaded) => config = reloaded,
+                    Err(err) => error!(
+                        "Unable to reload QUIC certificates for {}: {}",
+                        local_endpoint, err
+                    ),
+                }
+            }
+        }
+
+        for peer in peers.values_mut() {
+            peer.on_timeout();
+        }
+
+        loop {
+            let (len, from) = match socket.recv_from(&mut buf) {
+                Ok(result) => result,
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => return Err(err),
+            };
+            let packet = &mut buf[..len];
+
+            let header = match quiche::Header::from_slice(packet, quiche::MAX_CONN_ID_LEN) {
+                Ok(header) => header,
+                Err(err) => {
+                    debug!("Dropping invalid QUIC packet from {}: {}", from, err);
+                    continue;
+                }
+            };
+
+            let token = match connection_ids.get(&header.dcid) {
+                Some(token) => *token,
+                None => {
+                    if header.ty != quiche::Type::Initial || !listening {
+                        continue;
+                    }
+
+                    if !quiche::version_is_supported(header.version) {
+                        if let Ok(len) =
+                            quiche::negotiate_version(&header.scid, &header.dcid, &mut out)
+                        {
+                            let _ = socket.send_to(&out[..len], &from);
+                        }
+                        continue;
+                    }
+
+                    if pending.len() >= MAX_PENDING_HANDSHAKES {
+                        debug!(
+                            "Dropping QUIC connection attempt from {}: too many pending handshakes",
+                            from
+                        );
+                        continue;
+                    }
+
+                    // Ask the client to prove it owns its address before keeping any state for it
+                    let address_token = match &header.token {
+                        Some(address_token) if !address_token.is_empty() => address_token,
+                        _ => {
+                            let scid = new_connection_id();
+                            let sent = address_validator
+                                .mint(&from, &header.dcid)
+                                .map_err(|err| err.to_string())
+                                .and_then(|address_token| {
+                                    quiche::retry(
+                                        &header.scid,
+                                        &header.dcid,
+                                        &scid,
+                                        &address_token,
+                                        header.version,
+                                        &mut out,
+                                    )
+                                    .map_err(|err| err.to_string())
+                                });
+                            match sent {
+                                Ok(len) => {
+                                    let _ = socket.send_to(&out[..len], &from);
+                                }
+                                Err(err) => {
+                                    debug!("Unable to send QUIC retry to {}: {}", from, err)
+                                }
+                            }
+                            continue;
+                        }
+                    };
+
+                    let odcid = match address_validator.validate(&from, address_token) {
+                        Some(odcid) => odcid,
+                        None => {
+                            debug!("Dropping QUIC packet from {}: invalid address token", from);
+                            continue;
+                        }
+                    };
+                    // The client uses the connection ID chosen in the retry packet
+                    if header.dcid.len() != quiche::MAX_CONN_ID_LEN {
+                        debug!("Dropping QUIC packet from {}: invalid connection ID", from);
+                        continue;
+                    }
+                    let scid = header.dcid.clone();
+
+                    let conn = match quiche::accept(&scid, Some(&odcid[..]), &mut config) {
+                        Ok(conn) => conn,
+                        Err(err) => {
+                            debug!("Unable to accept QUIC connection from {}: {}", from, err);
+                            continue;
+                        }
+                    };
+
+                    let token = next_token;
+                    next_token += 1;
+
+                    let (peer, connection) = QuicPeer::new(
+                        conn,
+                        from,
+                        true,
+                        format!("{}{}", PROTOCOL_PREFIX, from),
+                        local_endpoint.clone(),
+                    );
+                    poll.register(
+                        peer.commands(),
+                        Token(token),
+                        Ready::readable(),
+                        PollOpt::edge(),
+                    )?;
+
+                    connection_ids.insert(scid, token);
+                    peers.insert(token, peer);
+                    pending.insert(token, connection);
+
+                    token
+                }
+            };
+
+            if let Some(peer) = peers.get_mut(&token) {
+                peer.recv_packet(packet);
+            }
+        }
+
+        for (token, peer) in peers.iter_mut() {
+            if peer.is_established() {
+                if let Some(mut connection) = pending.remove(token) {
+                    if let Err(msg) = tls.check_peer(peer.peer_cert(), &mut connection) {
+                        debug!(
+                            "Rejecting QUIC connection from {}: {}",
+                            connection.remote_endpoint(),
+                            msg
+                        );
+                        peer.reject(b"certificate rejected");
+                        peer.send_packets(&socket);
+                        continue;
+                    }
+                    // If the listener has been dropped, the connection is dropped and closed
+                    let _ = new_connections.send(connection);
+                }
+                peer.process();
+            }
+            peer.send_packets(&socket);
+        }
+
+        let closed = peers
+            .iter()
+            .filter(|(_, peer)| peer.is_closed())
+            .map(|(token, _)| *token)
+            .collect::<Vec<_>>();
+        for token in closed {
+            if let Some(peer) = peers.remove(&token) {
+                let _ = poll.deregister(peer.commands());
+            }
+            pending.remove(&token);
+            connection_ids.retain(|_, id_token| *id_token != token);
+        }
+
+        if !listening && peers.is_empty() {
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,272 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A QUIC-based transport implementation.
+//!
+//! The `splinter::transport::quic` module provides a `Transport` implementation on top of QUIC,
+//! using the same certificate configuration as the TLS transports. Supports endpoints of the
+//! format `quic://ip_or_host:port`.
+//!
+//! Each side of a connection sends its messages on several QUIC streams: the messages of a
+//! circuit's services are all sent on the same stream, and all other messages on a control stream,
+//! so the messages of a circuit are received in the order they were sent without waiting on the
+//! messages of other circuits.
+
+mod connection;
+mod listener;
+mod peer;
+mod stream;
+mod transport;
+
+pub use transport::{QuicInitError, QuicTransport};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::protos::circuit::{CircuitDirectMessage, CircuitMessage, CircuitMessageType};
+    use crate::protos::network::{NetworkMessage, NetworkMessageType};
+    use crate::transport::tests;
+    use crate::transport::tls::tests::{make_ca_cert, make_ca_signed_cert};
+    use crate::transport::tls::{TlsConfig, TlsConfigBuilder};
+    use crate::transport::{RecvError, Transport};
+
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::Duration;
+
+    use protobuf::Message;
+    use tempdir::TempDir;
+
+    use stream::{stream_for_message, CONTROL_STREAM};
+
+    fn write_file(mut temp_dir: PathBuf, file_name: &str, bytes: &[u8]) -> String {
+        temp_dir.push(file_name);
+        let path = temp_dir.to_str().unwrap().to_string();
+        let mut file = File::create(path.to_string()).unwrap();
+        file.write_all(bytes).unwrap();
+
+        path
+    }
+
+    fn create_test_tls_config(temp_dir: &TempDir, insecure: bool) -> TlsConfig {
+        let mut builder = TlsConfigBuilder::new();
+
+        // Generate Certificate Authority keys and certificate
+        let (ca_key, ca_cert) = make_ca_cert();
+
+        let temp_dir_path = temp_dir.path();
+
+        if !insecure {
+            let ca_path_file = write_file(
+                temp_dir_path.to_path_buf(),
+                "ca.cert",
+                &ca_cert.to_pem().unwrap(),
+            );
+            builder = builder.with_ca_certs_file(ca_path_file);
+        }
+
+        // Generate client and server keys and certificates
+        let (client_key, client_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+        let (server_key, server_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+
+        let client_cert_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "client.cert",
+            &client_cert.to_pem().unwrap(),
+        );
+        let client_key_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "client.key",
+            &client_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+        let server_cert_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "server.cert",
+            &server_cert.to_pem().unwrap(),
+        );
+        let server_key_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "server.key",
+            &server_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+
+        builder
+            .with_server_cert_file(server_cert_file)
+            .with_server_private_key_file(server_key_file)
+            .with_client_cert_file(client_cert_file)
+            .with_client_private_key_file(client_key_file)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_quic_accepts() {
+        let temp_dir = TempDir::new("test-quic-accepts").unwrap();
+        let config = create_test_tls_config(&temp_dir, true);
+        let transport = QuicTransport::new_insecure(&config).unwrap();
+        assert!(transport.accepts("quic://127.0.0.1:18090"));
+        assert!(transport.accepts("quic://somewhere.example.com:18090"));
+        assert!(!transport.accepts("tcps://127.0.0.1:18090"));
+        assert!(!transport.accepts("127.0.0.1:18090"));
+    }
+
+    #[test]
+    fn test_quic_transport() {
+        let temp_dir = TempDir::new("test-quic-transport").unwrap();
+        let config = create_test_tls_config(&temp_dir, true);
+        let transport = QuicTransport::new_insecure(&config).unwrap();
+        tests::test_transport(transport, "quic://127.0.0.1:18091");
+    }
+
+    // Test that a transport that verifies remote endpoints can't be created without a CA
+    // certificate file
+    #[test]
+    fn test_quic_requires_ca() {
+        let temp_dir = TempDir::new("test-quic-requires-ca").unwrap();
+        let config = create_test_tls_config(&temp_dir, true);
+        assert!(QuicTransport::new(&config).is_err());
+    }
+
+    #[test]
+    fn test_quic_poll() {
+        let temp_dir = TempDir::new("test-quic-poll").unwrap();
+        let config = create_test_tls_config(&temp_dir, false);
+        let transport = QuicTransport::new(&config).unwrap();
+        tests::test_poll(transport, "quic://127.0.0.1:18092");
+    }
+
+    #[test]
+    fn test_quic_message_ordering() {
+        let temp_dir = TempDir::new("test-quic-message-ordering").unwrap();
+        let config = create_test_tls_config(&temp_dir, false);
+        let transport = QuicTransport::new(&config).unwrap();
+        tests::test_message_ordering(transport, "quic://127.0.0.1:18093");
+    }
+
+    // Returns a serialized network message containing a circuit direct message for the circuit
+    fn circuit_direct_message(circuit: &str, payload: &[u8]) -> Vec<u8> {
+        let mut direct_msg = CircuitDirectMessage::new();
+        direct_msg.set_circuit(circuit.into());
+        direct_msg.set_payload(payload.to_vec());
+
+        let mut circuit_msg = CircuitMessage::new();
+        circuit_msg.set_message_type(CircuitMessageType::CIRCUIT_DIRECT_MESSAGE);
+        circuit_msg.set_payload(direct_msg.write_to_bytes().unwrap());
+
+        let mut network_msg = NetworkMessage::new();
+        network_msg.set_message_type(NetworkMessageType::CIRCUIT);
+        network_msg.set_payload(circuit_msg.write_to_bytes().unwrap());
+        network_msg.write_to_bytes().unwrap()
+    }
+
+    // Test that the messages of a circuit are always assigned to the same stream, which is not the
+    // control stream, and that other messages are assigned to the control stream
+    #[test]
+    fn test_quic_stream_for_message() {
+        let stream = stream_for_message(&circuit_direct_message("alpha-00000", b"a"));
+        assert_ne!(CONTROL_STREAM, stream);
+        assert_eq!(
+            stream,
+            stream_for_message(&circuit_direct_message("alpha-00000", b"b"))
+        );
+
+        let mut heartbeat = NetworkMessage::new();
+        heartbeat.set_message_type(NetworkMessageType::NETWORK_HEARTBEAT);
+        assert_eq!(
+            CONTROL_STREAM,
+            stream_for_message(&heartbeat.write_to_bytes().unwrap())
+        );
+        assert_eq!(CONTROL_STREAM, stream_for_message(&[0xff, 0xff, 0xff]));
+    }
+
+    // Test that the interleaved messages of several circuits, sent with control messages, are
+    // received in the order they were sent on each circuit
+    #[test]
+    fn test_quic_circuit_message_ordering() {
+        const MESSAGE_COUNT: u32 = 200;
+        const CIRCUITS: &[&str] = &["alpha-00000", "bravo-00000", "charl-00000"];
+
+        let temp_dir = TempDir::new("test-quic-circuit-message-ordering").unwrap();
+        let config = create_test_tls_config(&temp_dir, false);
+        let mut transport = QuicTransport::new(&config).unwrap();
+        let mut listener = transport.listen("quic://127.0.0.1:18094").unwrap();
+        let endpoint = listener.endpoint();
+
+        let handle = thread::spawn(move || {
+            let mut client = transport.connect(&endpoint).unwrap();
+            for i in 0..MESSAGE_COUNT {
+                for circuit in CIRCUITS {
+                    client
+                        .send(&circuit_direct_message(circuit, &i.to_be_bytes()))
+                        .unwrap();
+                }
+                client.send(&i.to_be_bytes()).unwrap();
+            }
+
+            // Wait for the server to receive every message before disconnecting
+            loop {
+                match client.recv() {
+                    Err(RecvError::WouldBlock) => thread::sleep(Duration::from_millis(10)),
+                    res => {
+                        assert_eq!(vec![0], res.unwrap());
+                        break;
+                    }
+                }
+            }
+        });
+
+        let mut server = listener.accept().unwrap();
+        let mut next: HashMap<String, u32> = HashMap::new();
+        let mut received = 0;
+        while received < MESSAGE_COUNT * (CIRCUITS.len() as u32 + 1) {
+            let message = match server.recv() {
+                Ok(message) => message,
+                Err(RecvError::WouldBlock) => {
+                    thread::sleep(Duration::from_millis(10));
+                    continue;
+                }
+                Err(err) => panic!("Unable to receive message: {}", err),
+            };
+
+            let (circuit, payload) = match NetworkMessage::parse_from_bytes(&message)
+                .ok()
+                .filter(|msg| msg.get_message_type() == NetworkMessageType::CIRCUIT)
+            {
+                Some(network_msg) => {
+                    let circuit_msg: CircuitMessage =
+                        Message::parse_from_bytes(network_msg.get_payload()).unwrap();
+                    let direct_msg: CircuitDirectMessage =
+                        Message::parse_from_bytes(circuit_msg.get_payload()).unwrap();
+                    (
+                        direct_msg.get_circuit().to_string(),
+                        direct_msg.get_payload().to_vec(),
+                    )
+                }
+                None => (String::new(), message),
+            };
+
+            let expected = next.entry(circuit).or_insert(0);
+            assert_eq!(&expected.to_be_bytes()[..], &payload[..]);
+            *expected += 1;
+            received += 1;
+        }
+
+        server.send(&[0]).unwrap();
+        handle.join().unwrap();
+    }
+}
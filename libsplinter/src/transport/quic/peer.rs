@@ -0,0 +1,381 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The state of a QUIC connection, which is driven by a background thread.
+//!
+//! A `QuicPeer` is owned by the thread that reads the connection's UDP socket. It exchanges
+//! messages with its `QuicConnection` over channels: the connection sends commands to the peer,
+//! and the peer sends each message it receives to the connection.
+//!
+//! Each side of a connection sends its messages on a fixed set of unidirectional streams, chosen
+//! by the connection as described in the `stream` module; messages sent on the same stream are
+//! received in the order they were sent. Each message is prefixed with its length as a 4-byte
+//! big-endian integer.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::mpsc::{Sender, TryRecvError};
+use std::time::Duration;
+
+use mio::net::UdpSocket;
+use mio::{Events, Poll, PollOpt, Ready, Token};
+use mio_extras::channel as mio_channel;
+use rand::Rng;
+
+use super::connection::QuicConnection;
+use super::stream::MESSAGE_STREAMS;
+
+/// The largest UDP datagram that may be received
+pub(super) const MAX_DATAGRAM_SIZE: usize = 65535;
+/// The largest UDP datagram that is sent
+const MAX_SEND_DATAGRAM_SIZE: usize = 1350;
+
+pub(super) const SOCKET_TOKEN: Token = Token(0);
+const COMMANDS_TOKEN: Token = Token(1);
+
+/// Commands sent from a `QuicConnection` to its peer
+pub(super) enum Command {
+    /// Sends the message on the stream with the given index
+    Send {
+        stream: usize,
+        message: Vec<u8>,
+    },
+    Disconnect,
+}
+
+/// The number of bytes in the length prefix of a message
+const LENGTH_PREFIX_SIZE: usize = 4;
+
+/// The two least significant bits of the IDs of the unidirectional streams opened by a client; the
+/// IDs of the streams opened by a server end with `0b11`
+const CLIENT_UNI_STREAM_TYPE: u64 = 0x2;
+const SERVER_UNI_STREAM_TYPE: u64 = 0x3;
+
+pub(super) struct QuicPeer {
+    conn: Pin<Box<quiche::Connection>>,
+    remote_addr: SocketAddr,
+    commands: mio_channel::Receiver<Command>,
+    inbound: mio_channel::Sender<Vec<u8>>,
+    /// The type bits of the IDs of the streams this side of the connection sends messages on
+    outbound_stream_type: u64,
+    /// The type bits of the IDs of the streams the remote endpoint sends messages on
+    inbound_stream_type: u64,
+    /// The framed messages that have not been written to each outbound stream yet
+    outbound: Vec<Vec<u8>>,
+    /// The bytes received on each inbound stream that do not form a complete message yet
+    inbound_buffers: HashMap<u64, Vec<u8>>,
+}
+
+impl QuicPeer {
+    /// Creates the peer for the given QUIC connection, and the `QuicConnection` used to exchange
+    /// messages with it.
+    pub fn new(
+        conn: Pin<Box<quiche::Connection>>,
+        remote_addr: SocketAddr,
+        server: bool,
+        remote_endpoint: String,
+        local_endpoint: String,
+    ) -> (Self, QuicConnection) {
+        let (command_sender, command_receiver) = mio_channel::channel();
+        let (inbound_sender, inbound_receiver) = mio_channel::channel();
+
+        let (outbound_stream_type, inbound_stream_type) = if server {
+            (SERVER_UNI_STREAM_TYPE, CLIENT_UNI_STREAM_TYPE)
+        } else {
+            (CLIENT_UNI_STREAM_TYPE, SERVER_UNI_STREAM_TYPE)
+        };
+
+        let peer = QuicPeer {
+            conn,
+            remote_addr,
+            commands: command_receiver,
+            inbound: inbound_sender,
+            outbound_stream_type,
+            inbound_stream_type,
+            outbound: vec![Vec::new(); MESSAGE_STREAMS],
+            inbound_buffers: HashMap::new(),
+        };
+        let connection = QuicConnection::new(
+            command_sender,
+            inbound_receiver,
+            remote_endpoint,
+            local_endpoint,
+        );
+
+        (peer, connection)
+    }
+
+    pub fn commands(&self) -> &mio_channel::Receiver<Command> {
+        &self.commands
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.conn.timeout()
+    }
+
+    pub fn on_timeout(&mut self) {
+        self.conn.on_timeout();
+    }
+
+    pub fn is_established(&self) -> bool {
+        self.conn.is_established()
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.conn.is_closed()
+    }
+
+    /// Returns the DER-encoded certificate of the remote endpoint, if it presented one.
+    pub fn peer_cert(&self) -> Option<Vec<u8>> {
+        self.conn.peer_cert().map(|cert| cert.to_vec())
+    }
+
+    /// Closes the connection because the remote endpoint was rejected.
+    pub fn reject(&mut self, reason: &[u8]) {
+        let _ = self.conn.close(false, 0x1, reason);
+    }
+
+    /// Processes a packet received from the remote endpoint.
+    pub fn recv_packet(&mut self, packet: &mut [u8]) {
+        if let Err(err) = self.conn.recv(packet) {
+            if err != quiche::Error::Done {
+                debug!(
+                    "Unable to process QUIC packet from {}: {}",
+                    self.remote_addr, err
+                );
+            }
+        }
+    }
+
+    /// Handles the pending commands, delivers the messages that have been received, and writes
+    /// outbound messages to the outbound streams.
+    pub fn process(&mut self) {
+        if self.conn.is_closed() {
+            return;
+        }
+
+        if let Err(err) = self
+            .process_commands()
+            .and_then(|_| self.read_streams())
+            .and_then(|_| self.write_streams())
+        {
+            debug!("Closing QUIC connection to {}: {}", self.remote_addr, err);
+            let _ = self.conn.close(false, 0x1, b"internal error");
+        }
+    }
+
+    /// Sends the packets that are ready to be sent to the remote endpoint.
+    pub fn send_packets(&mut self, socket: &UdpSocket) {
+        let mut out = [0; MAX_SEND_DATAGRAM_SIZE];
+        loop {
+            let len = match self.conn.send(&mut out) {
+                Ok(len) => len,
+                Err(quiche::Error::Done) => break,
+                Err(err) => {
+                    debug!(
+                        "Unable to create QUIC packet for {}: {}",
+                        self.remote_addr, err
+                    );
+                    let _ = self.conn.close(false, 0x1, b"internal error");
+                    break;
+                }
+            };
+
+            // A packet that cannot be sent is recovered like a lost packet
+            if let Err(err) = socket.send_to(&out[..len], &self.remote_addr) {
+                if err.kind() != io::ErrorKind::WouldBlock {
+                    debug!(
+                        "Unable to send QUIC packet to {}: {}",
+                        self.remote_addr, err
+                    );
+                }
+                break;
+            }
+        }
+    }
+
+    fn process_commands(&mut self) -> Result<(), quiche::Error> {
+        loop {
+            match self.commands.try_recv() {
+                Ok(Command::Send { stream, message }) => {
+                    let len = match u32::try_from(message.len()) {
+                        Ok(len) => len,
+                        Err(_) => {
+                            debug!(
+                                "Unable to send {} byte message to {}: message is too large",
+                                message.len(),
+                                self.remote_addr
+                            );
+                            continue;
+                        }
+                    };
+                    let outbound = &mut self.outbound[stream % MESSAGE_STREAMS];
+                    outbound.extend_from_slice(&len.to_be_bytes());
+                    outbound.extend_from_slice(&message);
+                }
+                Ok(Command::Disconnect) | Err(TryRecvError::Disconnected) => {
+                    return self.disconnect();
+                }
+                Err(TryRecvError::Empty) => return Ok(()),
+            }
+        }
+    }
+
+    fn read_streams(&mut self) -> Result<(), quiche::Error> {
+        let mut buf = [0; MAX_DATAGRAM_SIZE];
+        for stream_id in self.conn.readable() {
+            if stream_id & 0x3 != self.inbound_stream_type
+                || stream_id >> 2 >= MESSAGE_STREAMS as u64
+            {
+                debug!(
+                    "Closing QUIC connection to {}: unexpected stream {}",
+                    self.remote_addr, stream_id
+                );
+                return match self.conn.close(true, 0x1, b"unexpected stream") {
+                    Ok(()) | Err(quiche::Error::Done) => Ok(()),
+                    Err(err) => Err(err),
+                };
+            }
+
+            let inbound_buffer = self.inbound_buffers.entry(stream_id).or_default();
+            loop {
+                let len = match self.conn.stream_recv(stream_id, &mut buf) {
+                    Ok((len, _)) => len,
+                    Err(quiche::Error::Done) => break,
+                    Err(err) => return Err(err),
+                };
+                inbound_buffer.extend_from_slice(&buf[..len]);
+            }
+
+            while let Some(message) = next_inbound_message(inbound_buffer) {
+                if self.inbound.send(message).is_err() {
+                    // The connection has been dropped
+                    return self.disconnect();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn disconnect(&mut self) -> Result<(), quiche::Error> {
+        match self.conn.close(true, 0x0, b"disconnect") {
+            Ok(()) | Err(quiche::Error::Done) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn write_streams(&mut self) -> Result<(), quiche::Error> {
+        for (index, outbound) in self.outbound.iter_mut().enumerate() {
+            if outbound.is_empty() {
+                continue;
+            }
+
+            let stream_id = ((index as u64) << 2) | self.outbound_stream_type;
+            match self.conn.stream_send(stream_id, outbound, false) {
+                Ok(written) => {
+                    outbound.drain(..written);
+                }
+                // The stream or connection is out of flow control credit
+                Err(quiche::Error::Done) => (),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Removes the next complete message from the buffer of an inbound stream, if it has been
+/// received.
+fn next_inbound_message(inbound_buffer: &mut Vec<u8>) -> Option<Vec<u8>> {
+    if inbound_buffer.len() < LENGTH_PREFIX_SIZE {
+        return None;
+    }
+
+    let mut len_bytes = [0; LENGTH_PREFIX_SIZE];
+    len_bytes.copy_from_slice(&inbound_buffer[..LENGTH_PREFIX_SIZE]);
+    let end = LENGTH_PREFIX_SIZE + u32::from_be_bytes(len_bytes) as usize;
+    if inbound_buffer.len() < end {
+        return None;
+    }
+
+    let message = inbound_buffer[LENGTH_PREFIX_SIZE..end].to_vec();
+    inbound_buffer.drain(..end);
+    Some(message)
+}
+
+/// Creates a random connection ID.
+pub(super) fn new_connection_id() -> [u8; quiche::MAX_CONN_ID_LEN] {
+    let mut connection_id = [0; quiche::MAX_CONN_ID_LEN];
+    rand::thread_rng().fill(&mut connection_id[..]);
+    connection_id
+}
+
+/// Drives the client side of a connection until it is closed.
+///
+/// The result of the handshake, with the certificate of the remote endpoint, is sent on
+/// `handshake_sender` as soon as it is known.
+pub(super) fn run_client(
+    socket: UdpSocket,
+    mut peer: QuicPeer,
+    handshake_sender: Sender<Result<Option<Vec<u8>>, String>>,
+) -> io::Result<()> {
+    let poll = Poll::new()?;
+    poll.register(&socket, SOCKET_TOKEN, Ready::readable(), PollOpt::edge())?;
+    poll.register(
+        peer.commands(),
+        COMMANDS_TOKEN,
+        Ready::readable(),
+        PollOpt::edge(),
+    )?;
+
+    let mut events = Events::with_capacity(1024);
+    let mut buf = [0; MAX_DATAGRAM_SIZE];
+    let mut handshake_sender = Some(handshake_sender);
+
+    // Send the initial packet
+    peer.send_packets(&socket);
+
+    loop {
+        poll.poll(&mut events, peer.timeout())?;
+        peer.on_timeout();
+
+        loop {
+            match socket.recv_from(&mut buf) {
+                Ok((len, _)) => peer.recv_packet(&mut buf[..len]),
+                Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        if peer.is_established() {
+            if let Some(sender) = handshake_sender.take() {
+                let _ = sender.send(Ok(peer.peer_cert()));
+            }
+            peer.process();
+        }
+        peer.send_packets(&socket);
+
+        if peer.is_closed() {
+            if let Some(sender) = handshake_sender.take() {
+                let _ = sender.send(Err("Handshake failed".into()));
+            }
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Assigns the messages sent on a connection to QUIC streams.
+//!
+//! Each side of a connection opens `MESSAGE_STREAMS` unidirectional streams. Messages sent on the
+//! same stream are received in the order they were sent, while a message that is delayed on one
+//! stream, by a lost packet or by flow control, does not hold up the messages on the others.
+//!
+//! The messages of a circuit's services are all sent on the same stream, chosen from the circuit
+//! ID, so they stay in order. All other messages, such as authorization, heartbeat and admin
+//! messages, are sent in order on the control stream.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use protobuf::Message;
+
+use crate::protos::circuit::{
+    CircuitDirectMessage, CircuitDurableMessage, CircuitDurableMessageAck, CircuitMessage,
+    CircuitMessageType,
+};
+use crate::protos::network::{NetworkMessage, NetworkMessageType};
+
+/// The number of unidirectional streams each side of a connection sends messages on
+pub(super) const MESSAGE_STREAMS: usize = 8;
+
+/// The stream used for messages that don't belong to a circuit's services
+pub(super) const CONTROL_STREAM: usize = 0;
+
+/// Returns the index of the stream the message is sent on, from `0` to `MESSAGE_STREAMS - 1`.
+///
+/// Messages that can't be parsed are sent on the control stream.
+pub(super) fn stream_for_message(message: &[u8]) -> usize {
+    match circuit_id(message) {
+        Some(circuit_id) => {
+            let mut hasher = DefaultHasher::new();
+            circuit_id.hash(&mut hasher);
+            1 + (hasher.finish() % (MESSAGE_STREAMS as u64 - 1)) as usize
+        }
+        None => CONTROL_STREAM,
+    }
+}
+
+/// Returns the ID of the circuit the message is sent on, if it is a message between the services
+/// of a circuit.
+fn circuit_id(message: &[u8]) -> Option<String> {
+    let network_msg: NetworkMessage = Message::parse_from_bytes(message).ok()?;
+    if network_msg.get_message_type() != NetworkMessageType::CIRCUIT {
+        return None;
+    }
+
+    let circuit_msg: CircuitMessage = Message::parse_from_bytes(network_msg.get_payload()).ok()?;
+    let payload = circuit_msg.get_payload();
+    match circuit_msg.get_message_type() {
+        CircuitMessageType::CIRCUIT_DIRECT_MESSAGE => {
+            let msg: CircuitDirectMessage = Message::parse_from_bytes(payload).ok()?;
+            Some(msg.get_circuit().to_string())
+        }
+        CircuitMessageType::CIRCUIT_DURABLE_MESSAGE => {
+            let msg: CircuitDurableMessage = Message::parse_from_bytes(payload).ok()?;
+            Some(msg.get_circuit_id().to_string())
+        }
+        CircuitMessageType::CIRCUIT_DURABLE_MESSAGE_ACK => {
+            let msg: CircuitDurableMessageAck = Message::parse_from_bytes(payload).ok()?;
+            Some(msg.get_circuit_id().to_string())
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,484 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+use std::sync::atomic::AtomicBool;
+#[cfg(feature = "tls-reload")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use mio::net::UdpSocket;
+#[cfg(feature = "tls-crl")]
+use openssl::x509::X509;
+use url::{ParseError, Url};
+
+#[cfg(feature = "tls-reload")]
+use crate::error::InternalError;
+use crate::transport::tls::TlsConfig;
+#[cfg(feature = "tls-reload")]
+use crate::transport::tls::TlsReloader;
+#[cfg(feature = "tls-crl")]
+use crate::transport::tls::{is_revoked, read_crl, TrackedConnections};
+use crate::transport::{ConnectError, Connection, ListenError, Listener, Transport};
+
+use super::connection::QuicConnection;
+use super::listener::{run_listener, QuicListener};
+use super::peer::{new_connection_id, run_client, QuicPeer};
+use super::stream::MESSAGE_STREAMS;
+
+pub(super) const PROTOCOL_PREFIX: &str = "quic://";
+
+/// The ALPN protocol negotiated by both sides of a connection
+const APPLICATION_PROTOCOL: &[u8] = b"\x08splinter";
+/// How long a connection may be idle before it is closed, in milliseconds
+const IDLE_TIMEOUT_MS: u64 = 60_000;
+/// The number of bytes that may be in flight on a connection
+const MAX_DATA: u64 = 16 * 1024 * 1024;
+/// The number of bytes that may be in flight on a single stream. A stream may use the whole
+/// window of the connection when the other streams are idle.
+const MAX_STREAM_DATA: u64 = MAX_DATA;
+/// The number of streams the remote endpoint may open on a connection
+const MAX_STREAMS: u64 = MESSAGE_STREAMS as u64;
+/// How long to wait for the handshake with a remote endpoint to complete
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The certificate and key files used to configure connections, and the state shared by the
+/// transport and its listeners
+#[derive(Clone)]
+pub(super) struct QuicTls {
+    ca_certs_file: Option<String>,
+    server_cert_file: String,
+    server_private_key_file: String,
+    client_cert_file: String,
+    client_private_key_file: String,
+    #[cfg(feature = "tls-crl")]
+    crl_file: Option<String>,
+    insecure: bool,
+    /// Incremented each time the files are reloaded, so that listeners rebuild their
+    /// configuration
+    #[cfg(feature = "tls-reload")]
+    generation: Arc<AtomicUsize>,
+    /// The connections to check when the certificate revocation list is reloaded
+    #[cfg(feature = "tls-crl")]
+    connections: TrackedConnections,
+}
+
+impl QuicTls {
+    pub fn build_config(&self, server: bool) -> Result<quiche::Config, quiche::Error> {
+        let mut config = quiche::Config::new(quiche::PROTOCOL_VERSION)?;
+
+        if server {
+            config.load_cert_chain_from_pem_file(&self.server_cert_file)?;
+            config.load_priv_key_from_pem_file(&self.server_private_key_file)?;
+        } else {
+            config.load_cert_chain_from_pem_file(&self.client_cert_file)?;
+            config.load_priv_key_from_pem_file(&self.client_private_key_file)?;
+        }
+
+        match &self.ca_certs_file {
+            Some(ca_certs_file) if !self.insecure => {
+                config.load_verify_locations_from_file(ca_certs_file)?;
+                config.verify_peer(true);
+            }
+            _ => config.verify_peer(false),
+        }
+
+        config.set_application_protos(APPLICATION_PROTOCOL)?;
+        config.set_max_idle_timeout(IDLE_TIMEOUT_MS);
+        config.set_initial_max_data(MAX_DATA);
+        config.set_initial_max_stream_data_uni(MAX_STREAM_DATA);
+        config.set_initial_max_streams_uni(MAX_STREAMS);
+        config.set_disable_active_migration(true);
+
+        Ok(config)
+    }
+
+    /// Returns the number of times the files have been reloaded.
+    #[cfg(feature = "tls-reload")]
+    pub fn generation(&self) -> usize {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Checks the certificate of the remote endpoint of a newly established connection against
+    /// the certificate revocation list, if one is configured, and tracks the connection so that it
+    /// is closed if a reloaded list revokes the certificate. Returns an error describing why the
+    /// connection must be closed otherwise.
+    ///
+    /// quiche can't check a revocation list during the handshake, so the check is made as soon as
+    /// the handshake is complete, before the connection is used.
+    pub fn check_peer(
+        &self,
+        peer_cert: Option<Vec<u8>>,
+        connection: &mut QuicConnection,
+    ) -> Result<(), String> {
+        #[cfg(feature = "tls-crl")]
+        {
+            let crl_file = match &self.crl_file {
+                Some(crl_file) => crl_file,
+                None => return Ok(()),
+            };
+
+            let peer_cert = peer_cert
+                .ok_or_else(|| "remote endpoint did not present a certificate".to_string())
+                .and_then(|der| {
+                    X509::from_der(&der).map_err(|err| format!("invalid certificate: {}", err))
+                })?;
+            let crl = read_crl(crl_file).map_err(|err| err.to_string())?;
+            if is_revoked(&crl, &peer_cert) {
+                return Err("certificate is revoked".into());
+            }
+
+            let tracked = self
+                .connections
+                .track(peer_cert, connection.closer())
+                .map_err(|err| format!("unable to track connection: {}", err))?;
+            connection.set_tracked(tracked);
+        }
+
+        #[cfg(not(feature = "tls-crl"))]
+        let _ = (peer_cert, connection);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "tls-reload")]
+    fn paths(&self) -> Vec<String> {
+        let mut paths = vec![
+            self.client_private_key_file.clone(),
+            self.client_cert_file.clone(),
+            self.server_private_key_file.clone(),
+            self.server_cert_file.clone(),
+        ];
+        paths.extend(self.ca_certs_file.iter().cloned());
+        #[cfg(feature = "tls-crl")]
+        paths.extend(self.crl_file.iter().cloned());
+        paths
+    }
+}
+
+/// A QUIC-based `Transport`.
+///
+/// Supports endpoints of the format `quic://ip_or_host:port`. QUIC always uses TLS, so the
+/// transport requires the same certificate configuration as the TLS transports. Both sides of a
+/// connection verify the other's certificate against the configured CA certificate file, unless
+/// the transport is constructed with [`new_insecure`]. If the configuration includes a certificate
+/// revocation list, the certificate of each remote endpoint is also checked against it once the
+/// handshake is complete.
+///
+/// [`new_insecure`]: #method.new_insecure
+///
+/// # Examples
+///
+/// To connect to the a remote endpoint, send a message, and receive a reply message:
+///
+/// ```rust,no_run
+/// use splinter::transport::Transport as _;
+/// use splinter::transport::quic::QuicTransport;
+/// use splinter::transport::tls::TlsConfigBuilder;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let config = TlsConfigBuilder::new()
+///         .with_ca_certs_file("ca.cert".into())
+///         .with_client_cert_file("client.crt".into())
+///         .with_client_private_key_file("client.key".into())
+///         .with_server_cert_file("server.crt".into())
+///         .with_server_private_key_file("server.key".into())
+///         .build()?;
+///     let mut transport = QuicTransport::new(&config)?;
+///
+///     // Connect to a remote endpoint starting with `quic://`.
+///     let mut connection = transport.connect("quic://127.0.0.1:5555")?;
+///
+///     // Send some bytes
+///     connection.send(b"hello world")?;
+///
+///     // Receive a response
+///     let msg = connection.recv()?;
+///
+///     // Disconnect
+///     connection.disconnect()?;
+///
+///     Ok(())
+/// }
+/// ```
+pub struct QuicTransport {
+    tls: QuicTls,
+}
+
+impl QuicTransport {
+    /// Constructs a new `QuicTransport` from the given TLS configuration.
+    ///
+    /// Returns an error if the configuration does not include a CA certificate file, or if the
+    /// certificates or keys cannot be loaded.
+    pub fn new(config: &TlsConfig) -> Result<Self, QuicInitError> {
+        if config.ca_certs_file().is_none() {
+            return Err(QuicInitError::ProtocolError(
+                "A CA certificate file is required to verify remote endpoints".into(),
+            ));
+        }
+
+        Self::with_verification(config, false)
+    }
+
+    /// Constructs a new `QuicTransport` from the given TLS configuration that does not verify the
+    /// certificates of remote endpoints. Any CA certificate file in the configuration is ignored.
+    ///
+    /// Returns an error if the certificates or keys cannot be loaded, or if the configuration
+    /// includes a certificate revocation list, which can't be checked without verifying the
+    /// certificates.
+    pub fn new_insecure(config: &TlsConfig) -> Result<Self, QuicInitError> {
+        #[cfg(feature = "tls-crl")]
+        {
+            if config.crl_file().is_some() {
+                return Err(QuicInitError::ProtocolError(
+                    "A certificate revocation list can't be checked when remote endpoints are \
+                     not verified"
+                        .into(),
+                ));
+            }
+        }
+
+        Self::with_verification(config, true)
+    }
+
+    fn with_verification(config: &TlsConfig, insecure: bool) -> Result<Self, QuicInitError> {
+        let tls = QuicTls {
+            ca_certs_file: config.ca_certs_file().clone(),
+            server_cert_file: config.server_cert_file().to_string(),
+            server_private_key_file: config.server_private_key_file().to_string(),
+            client_cert_file: config.client_cert_file().to_string(),
+            client_private_key_file: config.client_private_key_file().to_string(),
+            #[cfg(feature = "tls-crl")]
+            crl_file: config.crl_file().map(String::from),
+            insecure,
+            #[cfg(feature = "tls-reload")]
+            generation: Arc::new(AtomicUsize::new(0)),
+            #[cfg(feature = "tls-crl")]
+            connections: TrackedConnections::default(),
+        };
+
+        // Load both configurations up front, so that bad certificates are reported at startup
+        tls.build_config(true)?;
+        tls.build_config(false)?;
+        #[cfg(feature = "tls-crl")]
+        {
+            if let Some(crl_file) = &tls.crl_file {
+                read_crl(crl_file).map_err(|err| QuicInitError::ProtocolError(err.to_string()))?;
+            }
+        }
+
+        Ok(QuicTransport { tls })
+    }
+
+    /// Returns a `TlsReloader` that reloads the certificate and key files of this transport.
+    ///
+    /// New connections use the reloaded files, including the connections accepted by listeners
+    /// that were created before the reload. When a certificate revocation list is configured, the
+    /// established connections are checked against the reloaded list, and the connections whose
+    /// remote certificates it revokes are closed.
+    #[cfg(feature = "tls-reload")]
+    pub fn reloader(&self) -> TlsReloader {
+        let tls = self.tls.clone();
+        TlsReloader::new(
+            self.tls.paths(),
+            Arc::new(move || {
+                // Make sure the files are valid before the listeners load them
+                tls.build_config(true)
+                    .and_then(|_| tls.build_config(false))
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                tls.generation.fetch_add(1, Ordering::SeqCst);
+
+                #[cfg(feature = "tls-crl")]
+                {
+                    if let Some(crl) = &tls.crl_file {
+                        let closed = tls.connections.close_revoked(crl)?;
+                        if closed > 0 {
+                            info!(
+                                "Closed {} QUIC connections with revoked certificates",
+                                closed
+                            );
+                        }
+                    }
+                }
+
+                Ok(())
+            }),
+        )
+    }
+}
+
+fn endpoint_to_dns_name(endpoint: &str) -> Result<String, ParseError> {
+    let mut address = String::from("quic://");
+    address.push_str(endpoint);
+    let url = Url::parse(&address)?;
+    let dns_name = match url.domain() {
+        Some(d) if d.parse::<Ipv4Addr>().is_ok() => "localhost",
+        Some(d) if d.parse::<Ipv6Addr>().is_ok() => "localhost",
+        Some(d) => d,
+        None => "localhost",
+    };
+    Ok(String::from(dns_name))
+}
+
+impl Transport for QuicTransport {
+    fn accepts(&self, address: &str) -> bool {
+        address.starts_with(PROTOCOL_PREFIX)
+    }
+
+    fn connect(&mut self, endpoint: &str) -> Result<Box<dyn Connection>, ConnectError> {
+        let address = endpoint.strip_prefix(PROTOCOL_PREFIX).ok_or_else(|| {
+            ConnectError::ProtocolError(format!("Invalid protocol \"{}\"", endpoint))
+        })?;
+
+        let remote_addr = address.to_socket_addrs()?.next().ok_or_else(|| {
+            ConnectError::ParseError(format!("Unable to resolve address: {}", address))
+        })?;
+        let bind_addr: SocketAddr = if remote_addr.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(&bind_addr)?;
+        socket.connect(remote_addr)?;
+
+        let remote_endpoint = format!("{}{}", PROTOCOL_PREFIX, remote_addr);
+        let local_endpoint = format!("{}{}", PROTOCOL_PREFIX, socket.local_addr()?);
+
+        let dns_name = endpoint_to_dns_name(address)?;
+        let mut config = self.tls.build_config(false).map_err(|err| {
+            ConnectError::ProtocolError(format!("Unable to configure connection: {}", err))
+        })?;
+        let conn =
+            quiche::connect(Some(&dns_name), &new_connection_id(), &mut config).map_err(|err| {
+                ConnectError::ProtocolError(format!("Unable to create connection: {}", err))
+            })?;
+
+        let (peer, mut connection) = QuicPeer::new(
+            conn,
+            remote_addr,
+            false,
+            remote_endpoint.clone(),
+            local_endpoint,
+        );
+
+        let (handshake_sender, handshake_receiver) = channel();
+        thread::Builder::new()
+            .name(format!("QuicConnection-{}", remote_endpoint))
+            .spawn(move || {
+                if let Err(err) = run_client(socket, peer, handshake_sender) {
+                    error!("QUIC connection to {} failed: {}", remote_endpoint, err);
+                }
+            })?;
+
+        match handshake_receiver.recv_timeout(HANDSHAKE_TIMEOUT) {
+            Ok(Ok(peer_cert)) => match self.tls.check_peer(peer_cert, &mut connection) {
+                Ok(()) => Ok(Box::new(connection)),
+                Err(msg) => {
+                    let _ = connection.disconnect();
+                    Err(ConnectError::ProtocolError(format!(
+                        "Rejected connection to {}: {}",
+                        endpoint, msg
+                    )))
+                }
+            },
+            Ok(Err(msg)) => Err(ConnectError::ProtocolError(msg)),
+            Err(RecvTimeoutError::Timeout) => Err(ConnectError::ProtocolError(format!(
+                "Handshake with {} timed out",
+                endpoint
+            ))),
+            Err(RecvTimeoutError::Disconnected) => Err(ConnectError::ProtocolError(format!(
+                "Handshake with {} failed",
+                endpoint
+            ))),
+        }
+    }
+
+    fn listen(&mut self, bind: &str) -> Result<Box<dyn Listener>, ListenError> {
+        let address = bind
+            .strip_prefix(PROTOCOL_PREFIX)
+            .ok_or_else(|| ListenError::ProtocolError(format!("Invalid protocol \"{}\"", bind)))?;
+
+        let bind_addr = address
+            .to_socket_addrs()
+            .map_err(|err| ListenError::IoError(format!("Failed to resolve {}", address), err))?
+            .next()
+            .ok_or_else(|| {
+                ListenError::ProtocolError(format!("Unable to resolve address: {}", address))
+            })?;
+        let socket = UdpSocket::bind(&bind_addr)
+            .map_err(|err| ListenError::IoError(format!("Failed to bind to {}", address), err))?;
+        let local_endpoint = format!(
+            "{}{}",
+            PROTOCOL_PREFIX,
+            socket
+                .local_addr()
+                .map_err(|err| ListenError::IoError("Failed to get local address".into(), err))?
+        );
+
+        let config = self.tls.build_config(true).map_err(|err| {
+            ListenError::ProtocolError(format!("Unable to configure listener: {}", err))
+        })?;
+        let tls = self.tls.clone();
+
+        let (connection_sender, connection_receiver) = channel();
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = running.clone();
+        let thread_endpoint = local_endpoint.clone();
+        thread::Builder::new()
+            .name(format!("QuicListener-{}", local_endpoint))
+            .spawn(move || {
+                if let Err(err) = run_listener(
+                    socket,
+                    config,
+                    tls,
+                    thread_endpoint.clone(),
+                    connection_sender,
+                    thread_running,
+                ) {
+                    error!("QUIC listener on {} failed: {}", thread_endpoint, err);
+                }
+            })
+            .map_err(|err| ListenError::IoError("Failed to start listener thread".into(), err))?;
+
+        Ok(Box::new(QuicListener::new(
+            local_endpoint,
+            connection_receiver,
+            running,
+        )))
+    }
+}
+
+#[derive(Debug)]
+pub enum QuicInitError {
+    ProtocolError(String),
+}
+
+impl std::error::Error for QuicInitError {}
+
+impl std::fmt::Display for QuicInitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            QuicInitError::ProtocolError(msg) => write!(f, "Unable to initialize QUIC: {}", msg),
+        }
+    }
+}
+
+impl From<quiche::Error> for QuicInitError {
+    fn from(error: quiche::Error) -> Self {
+        QuicInitError::ProtocolError(format!("QUIC error: {}", error))
+    }
+}
@@ -26,7 +26,14 @@ use std::io;
 use std::net::{Ipv4Addr, Ipv6Addr, TcpListener, TcpStream};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::Path;
-
+use std::sync::{Arc, RwLock};
+
+#[cfg(feature = "tls-reload")]
+use crate::error::InternalError;
+#[cfg(feature = "tls-reload")]
+use crate::transport::tls::TlsReloader;
+#[cfg(feature = "tls-crl")]
+use crate::transport::tls::{load_crl, TrackedConnection, TrackedConnections};
 use crate::transport::{
     AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
     SendError, Transport,
@@ -39,8 +46,12 @@ const DEPRECATED_PROTOCOL_PREFIX: &str = "tls://";
 const PROTOCOL_PREFIX: &str = "tcps://";
 
 pub struct TlsTransport {
-    connector: SslConnector,
-    acceptor: SslAcceptor,
+    context: Arc<RwLock<TlsContext>>,
+    #[cfg(any(feature = "tls-crl", feature = "tls-reload"))]
+    files: TlsFiles,
+    /// The connections to check when a certificate revocation list is reloaded
+    #[cfg(feature = "tls-crl")]
+    connections: TrackedConnections,
 }
 
 impl TlsTransport {
@@ -51,10 +62,153 @@ impl TlsTransport {
         server_key: String,
         server_cert: String,
     ) -> Result<Self, TlsInitError> {
-        let client_cert_path = Path::new(&client_cert);
-        let client_key_path = Path::new(&client_key);
-        let server_cert_path = Path::new(&server_cert);
-        let server_key_path = Path::new(&server_key);
+        let files = TlsFiles {
+            ca_cert,
+            client_key,
+            client_cert,
+            server_key,
+            server_cert,
+            #[cfg(feature = "tls-crl")]
+            crl: None,
+        };
+        let context = TlsContext::build(&files)?;
+
+        Ok(TlsTransport {
+            context: Arc::new(RwLock::new(context)),
+            #[cfg(any(feature = "tls-crl", feature = "tls-reload"))]
+            files,
+            #[cfg(feature = "tls-crl")]
+            connections: TrackedConnections::default(),
+        })
+    }
+
+    /// Checks the certificates of remote nodes against the certificate revocation list in the
+    /// given PEM file during the TLS handshake, rejecting any certificate it revokes.
+    ///
+    /// A CA certificate must have been provided, because the certificates of remote nodes are not
+    /// verified otherwise.
+    #[cfg(feature = "tls-crl")]
+    pub fn with_crl_file(mut self, crl_file: String) -> Result<Self, TlsInitError> {
+        if self.files.ca_cert.is_none() {
+            return Err(TlsInitError::ProtocolError(
+                "a CA certificate is required to check a certificate revocation list".into(),
+            ));
+        }
+
+        self.files.crl = Some(crl_file);
+        replace_context(&self.context, TlsContext::build(&self.files)?)?;
+
+        Ok(self)
+    }
+
+    /// Returns a `TlsReloader` that reloads the certificate and key files of this transport.
+    ///
+    /// When a certificate revocation list is configured, the established connections are checked
+    /// against the reloaded list, and the connections whose remote certificates it revokes are
+    /// closed.
+    ///
+    /// The reloader only reloads the files the transport has been configured with so far, so it
+    /// should be created after the transport is fully configured.
+    #[cfg(feature = "tls-reload")]
+    pub fn reloader(&self) -> TlsReloader {
+        let context = self.context.clone();
+        let files = self.files.clone();
+        #[cfg(feature = "tls-crl")]
+        let connections = self.connections.clone();
+
+        TlsReloader::new(
+            self.files.paths(),
+            Arc::new(move || {
+                let new_context = TlsContext::build(&files)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                replace_context(&context, new_context)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+                #[cfg(feature = "tls-crl")]
+                {
+                    if let Some(crl) = &files.crl {
+                        let closed = connections.close_revoked(crl)?;
+                        if closed > 0 {
+                            info!(
+                                "Closed {} tcps:// connections with revoked certificates",
+                                closed
+                            );
+                        }
+                    }
+                }
+
+                Ok(())
+            }),
+        )
+    }
+
+    /// Starts tracking a connection, if revocation checks are enabled, so that it is closed if
+    /// the certificate of its remote endpoint is revoked by a reloaded revocation list.
+    #[cfg(feature = "tls-crl")]
+    fn track(
+        &self,
+        stream: &SslStream<TcpStream>,
+    ) -> Result<Option<Arc<TrackedConnection>>, String> {
+        if self.files.crl.is_none() {
+            return Ok(None);
+        }
+        self.connections
+            .track_stream(stream)
+            .map_err(|err| format!("Unable to track connection: {}", err))
+    }
+
+    fn connector(&self) -> Result<SslConnector, ConnectError> {
+        self.context
+            .read()
+            .map(|context| context.connector.clone())
+            .map_err(|_| ConnectError::ProtocolError("TLS context lock was poisoned".into()))
+    }
+}
+
+/// The paths of the files a `TlsTransport` is built from
+#[derive(Clone)]
+struct TlsFiles {
+    ca_cert: Option<String>,
+    client_key: String,
+    client_cert: String,
+    server_key: String,
+    server_cert: String,
+    #[cfg(feature = "tls-crl")]
+    crl: Option<String>,
+}
+
+#[cfg(feature = "tls-reload")]
+impl TlsFiles {
+    /// Returns the paths of the files.
+    fn paths(&self) -> Vec<String> {
+        let mut paths = vec![
+            self.client_key.clone(),
+            self.client_cert.clone(),
+            self.server_key.clone(),
+            self.server_cert.clone(),
+        ];
+        paths.extend(self.ca_cert.iter().cloned());
+        #[cfg(feature = "tls-crl")]
+        paths.extend(self.crl.iter().cloned());
+        paths
+    }
+}
+
+/// The connector and acceptor used for new connections.
+///
+/// Established connections keep the SSL context they were created with, so replacing the
+/// `TlsContext` of a transport does not affect them.
+struct TlsContext {
+    connector: SslConnector,
+    acceptor: SslAcceptor,
+}
+
+impl TlsContext {
+    fn build(files: &TlsFiles) -> Result<Self, TlsInitError> {
+        let client_cert_path = Path::new(&files.client_cert);
+        let client_key_path = Path::new(&files.client_key);
+        let server_cert_path = Path::new(&files.server_cert);
+        let server_key_path = Path::new(&files.server_key);
 
         // Build TLS Connector
         let mut connector = SslConnector::builder(SslMethod::tls())?;
@@ -69,32 +223,41 @@ impl TlsTransport {
         acceptor.check_private_key()?;
 
         // if ca_cert is provided set as accept cert, otherwise set verify to none
-        let (acceptor, connector) = {
-            if let Some(ca_cert) = ca_cert {
-                let ca_cert_path = Path::new(&ca_cert);
-                acceptor.set_ca_file(ca_cert_path)?;
-                connector.set_ca_file(ca_cert_path)?;
-                connector.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
-                acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
-                let connector = connector.build();
-                let acceptor = acceptor.build();
-                (acceptor, connector)
-            } else {
-                connector.set_verify(SslVerifyMode::NONE);
-                acceptor.set_verify(SslVerifyMode::NONE);
-                let connector = connector.build();
-                let acceptor = acceptor.build();
-                (acceptor, connector)
+        if let Some(ca_cert) = &files.ca_cert {
+            let ca_cert_path = Path::new(ca_cert);
+            acceptor.set_ca_file(ca_cert_path)?;
+            connector.set_ca_file(ca_cert_path)?;
+            connector.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+            acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        } else {
+            connector.set_verify(SslVerifyMode::NONE);
+            acceptor.set_verify(SslVerifyMode::NONE);
+        }
+
+        #[cfg(feature = "tls-crl")]
+        {
+            if let Some(crl) = &files.crl {
+                load_crl(&mut connector, crl)?;
+                load_crl(&mut acceptor, crl)?;
             }
-        };
+        }
 
-        Ok(TlsTransport {
-            connector,
-            acceptor,
+        Ok(TlsContext {
+            connector: connector.build(),
+            acceptor: acceptor.build(),
         })
     }
 }
 
+#[cfg(any(feature = "tls-crl", feature = "tls-reload"))]
+fn replace_context(lock: &RwLock<TlsContext>, context: TlsContext) -> Result<(), TlsInitError> {
+    *lock
+        .write()
+        .map_err(|_| TlsInitError::ProtocolError("TLS context lock was poisoned".into()))? =
+        context;
+    Ok(())
+}
+
 fn endpoint_to_dns_name(endpoint: &str) -> Result<String, ParseError> {
     let mut address = String::from("tcp://");
     address.push_str(endpoint);
@@ -134,7 +297,11 @@ impl Transport for TlsTransport {
         let dns_name = endpoint_to_dns_name(address)?;
 
         let stream = TcpStream::connect(address)?;
-        let mut tls_stream = self.connector.connect(&dns_name, stream)?;
+        let mut tls_stream = self.connector()?.connect(&dns_name, stream)?;
+        #[cfg(feature = "tls-crl")]
+        let tracked = self
+            .track(&tls_stream)
+            .map_err(ConnectError::ProtocolError)?;
 
         let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
             .negotiate(&mut tls_stream)
@@ -150,6 +317,8 @@ impl Transport for TlsTransport {
         let connection = TlsConnection {
             frame_version,
             stream: tls_stream,
+            #[cfg(feature = "tls-crl")]
+            _tracked: tracked,
         };
         Ok(Box::new(connection))
     }
@@ -174,20 +343,41 @@ impl Transport for TlsTransport {
             listener: TcpListener::bind(address).map_err(|err| {
                 ListenError::IoError(format!("Failed to bind to {}", address), err)
             })?,
-            acceptor: self.acceptor.clone(),
+            context: self.context.clone(),
+            #[cfg(feature = "tls-crl")]
+            crl_enabled: self.files.crl.is_some(),
+            #[cfg(feature = "tls-crl")]
+            connections: self.connections.clone(),
         }))
     }
 }
 
 pub struct TlsListener {
     listener: TcpListener,
-    acceptor: SslAcceptor,
+    context: Arc<RwLock<TlsContext>>,
+    #[cfg(feature = "tls-crl")]
+    crl_enabled: bool,
+    #[cfg(feature = "tls-crl")]
+    connections: TrackedConnections,
 }
 
 impl Listener for TlsListener {
     fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
         let (stream, _) = self.listener.accept()?;
-        let mut tls_stream = self.acceptor.accept(stream)?;
+        let acceptor = self
+            .context
+            .read()
+            .map(|context| context.acceptor.clone())
+            .map_err(|_| AcceptError::ProtocolError("TLS context lock was poisoned".into()))?;
+        let mut tls_stream = acceptor.accept(stream)?;
+        #[cfg(feature = "tls-crl")]
+        let tracked = if self.crl_enabled {
+            self.connections.track_stream(&tls_stream).map_err(|err| {
+                AcceptError::ProtocolError(format!("Unable to track connection: {}", err))
+            })?
+        } else {
+            None
+        };
 
         let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
             .negotiate(&mut tls_stream)
@@ -205,6 +395,8 @@ impl Listener for TlsListener {
         let connection = TlsConnection {
             frame_version,
             stream: tls_stream,
+            #[cfg(feature = "tls-crl")]
+            _tracked: tracked,
         };
         Ok(Box::new(connection))
     }
@@ -217,6 +409,9 @@ impl Listener for TlsListener {
 pub struct TlsConnection {
     frame_version: FrameVersion,
     stream: SslStream<TcpStream>,
+    /// Keeps the connection tracked for revocation checks while it is open
+    #[cfg(feature = "tls-crl")]
+    _tracked: Option<Arc<TrackedConnection>>,
 }
 
 impl Connection for TlsConnection {
@@ -265,6 +460,8 @@ impl TlsConnection {
         TlsConnection {
             frame_version: FrameVersion::V1,
             stream,
+            #[cfg(feature = "tls-crl")]
+            _tracked: None,
         }
     }
 }
@@ -351,6 +548,8 @@ pub(crate) mod tests {
     use super::*;
 
     use crate::transport::tests;
+    #[cfg(feature = "tls-crl")]
+    use crate::transport::tls::tests::make_crl;
     use crate::transport::tls::tests::{make_ca_cert, make_ca_signed_cert};
 
     use std::fs::File;
@@ -426,6 +625,339 @@ pub(crate) mod tests {
         .unwrap()
     }
 
+    /// Writes the keys and certificates of a client and a server that are signed by a new CA to
+    /// the directory, replacing any existing files.
+    #[cfg(any(feature = "tls-crl", feature = "tls-reload"))]
+    fn write_test_files(dir: &Path) -> TlsFiles {
+        let (ca_key, ca_cert) = make_ca_cert();
+        let (client_key, client_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+        let (server_key, server_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+
+        TlsFiles {
+            ca_cert: Some(write_file(
+                dir.to_path_buf(),
+                "ca.cert",
+                &ca_cert.to_pem().unwrap(),
+            )),
+            client_key: write_file(
+                dir.to_path_buf(),
+                "client.key",
+                &client_key.private_key_to_pem_pkcs8().unwrap(),
+            ),
+            client_cert: write_file(
+                dir.to_path_buf(),
+                "client.cert",
+                &client_cert.to_pem().unwrap(),
+            ),
+            server_key: write_file(
+                dir.to_path_buf(),
+                "server.key",
+                &server_key.private_key_to_pem_pkcs8().unwrap(),
+            ),
+            server_cert: write_file(
+                dir.to_path_buf(),
+                "server.cert",
+                &server_cert.to_pem().unwrap(),
+            ),
+            #[cfg(feature = "tls-crl")]
+            crl: None,
+        }
+    }
+
+    #[cfg(any(feature = "tls-crl", feature = "tls-reload"))]
+    fn transport_from_files(files: &TlsFiles) -> TlsTransport {
+        TlsTransport::new(
+            files.ca_cert.clone(),
+            files.client_key.clone(),
+            files.client_cert.clone(),
+            files.server_key.clone(),
+            files.server_cert.clone(),
+        )
+        .expect("Unable to create transport")
+    }
+
+    #[cfg(feature = "tls-reload")]
+    fn recv_blocking(connection: &mut Box<dyn Connection>) -> Vec<u8> {
+        loop {
+            match connection.recv() {
+                Err(RecvError::WouldBlock) => {
+                    std::thread::sleep(std::time::Duration::from_millis(100))
+                }
+                res => return res.expect("Unable to receive message"),
+            }
+        }
+    }
+
+    /// Test that reloading the files of a transport changes the certificates used for new
+    /// connections, and leaves established connections open:
+    /// 1. Connect a client to a server, using certificates signed by the same CA
+    /// 2. Replace the files of the server with certificates signed by a new CA and reload them
+    /// 3. Verify the established connection can still be used in both directions
+    /// 4. Verify the client can no longer connect, because it does not trust the new CA
+    /// 5. Verify a client that uses the new files can connect
+    #[cfg(feature = "tls-reload")]
+    #[test]
+    fn test_reload() {
+        let temp_dir = TempDir::new("tls-reload-test").unwrap();
+        let files = write_test_files(temp_dir.path());
+
+        let mut server = transport_from_files(&files);
+        let reloader = server.reloader();
+        let mut listener = server.listen("127.0.0.1:0").expect("Unable to listen");
+        let endpoint = listener.endpoint();
+
+        let mut client = transport_from_files(&files);
+        let connect_endpoint = endpoint.clone();
+        let handle = std::thread::spawn(move || {
+            let connection = client.connect(&connect_endpoint);
+            (client, connection)
+        });
+        let mut server_connection = listener.accept().expect("Unable to accept connection");
+        let (mut client, client_connection) = handle.join().unwrap();
+        let mut client_connection = client_connection.expect("Unable to connect");
+
+        assert!(!reloader.reload_if_changed().expect("Unable to check files"));
+        write_test_files(temp_dir.path());
+        assert!(reloader
+            .reload_if_changed()
+            .expect("Unable to reload files"));
+        assert!(!reloader.reload_if_changed().expect("Unable to check files"));
+
+        client_connection.send(b"hello").expect("Unable to send");
+        assert_eq!(recv_blocking(&mut server_connection), b"hello".to_vec());
+        server_connection.send(b"world").expect("Unable to send");
+        assert_eq!(recv_blocking(&mut client_connection), b"world".to_vec());
+
+        let connect_endpoint = endpoint.clone();
+        let handle = std::thread::spawn(move || client.connect(&connect_endpoint).is_err());
+        assert!(listener.accept().is_err());
+        assert!(handle.join().unwrap());
+
+        let mut new_client = transport_from_files(&files);
+        let handle = std::thread::spawn(move || new_client.connect(&endpoint).is_ok());
+        assert!(listener.accept().is_ok());
+        assert!(handle.join().unwrap());
+    }
+
+    /// Test that reloading invalid files returns an error and keeps the certificates that were
+    /// in use before.
+    #[cfg(feature = "tls-reload")]
+    #[test]
+    fn test_reload_invalid_files() {
+        let temp_dir = TempDir::new("tls-reload-test").unwrap();
+        let files = write_test_files(temp_dir.path());
+
+        let mut server = transport_from_files(&files);
+        let mut client = transport_from_files(&files);
+        let reloader = server.reloader();
+
+        write_file(temp_dir.path().to_path_buf(), "server.cert", b"invalid");
+        assert!(reloader.reload().is_err());
+
+        let mut listener = server.listen("127.0.0.1:0").expect("Unable to listen");
+        let endpoint = listener.endpoint();
+        let handle = std::thread::spawn(move || client.connect(&endpoint).is_ok());
+        assert!(listener.accept().is_ok());
+        assert!(handle.join().unwrap());
+    }
+
+    /// Test that a certificate revocation list can only be used when a CA certificate is
+    /// provided, and that an invalid list is rejected.
+    #[cfg(feature = "tls-crl")]
+    #[test]
+    fn test_crl_file() {
+        let temp_dir = TempDir::new("tls-crl-test").unwrap();
+        let mut files = write_test_files(temp_dir.path());
+        let crl = write_file(temp_dir.path().to_path_buf(), "ca.crl", b"invalid");
+
+        assert!(transport_from_files(&files)
+            .with_crl_file(crl.clone())
+            .is_err());
+
+        files.ca_cert = None;
+        assert!(transport_from_files(&files).with_crl_file(crl).is_err());
+    }
+
+    /// Test that a server with a certificate revocation list rejects the handshake of a client
+    /// whose certificate is revoked, and accepts a client whose certificate is not:
+    /// 1. Create a server that checks a list revoking one of two client certificates
+    /// 2. Verify the server rejects the client with the revoked certificate
+    /// 3. Verify the server accepts the client with the other certificate
+    #[cfg(feature = "tls-crl")]
+    #[test]
+    fn test_crl_rejects_revoked_certificate() {
+        let temp_dir = TempDir::new("tls-crl-test").unwrap();
+        let dir = temp_dir.path();
+
+        let (ca_key, ca_cert) = make_ca_cert();
+        let (server_key, server_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+        let (client_key, client_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+        let (revoked_key, revoked_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+
+        let ca_file = write_file(dir.to_path_buf(), "ca.cert", &ca_cert.to_pem().unwrap());
+        let crl_file = write_file(
+            dir.to_path_buf(),
+            "ca.crl",
+            &make_crl(&ca_cert, &ca_key, &[&revoked_cert]),
+        );
+        let server_key_file = write_file(
+            dir.to_path_buf(),
+            "server.key",
+            &server_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+        let server_cert_file = write_file(
+            dir.to_path_buf(),
+            "server.cert",
+            &server_cert.to_pem().unwrap(),
+        );
+        let client_key_file = write_file(
+            dir.to_path_buf(),
+            "client.key",
+            &client_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+        let client_cert_file = write_file(
+            dir.to_path_buf(),
+            "client.cert",
+            &client_cert.to_pem().unwrap(),
+        );
+        let revoked_key_file = write_file(
+            dir.to_path_buf(),
+            "revoked.key",
+            &revoked_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+        let revoked_cert_file = write_file(
+            dir.to_path_buf(),
+            "revoked.cert",
+            &revoked_cert.to_pem().unwrap(),
+        );
+
+        let mut server = TlsTransport::new(
+            Some(ca_file.clone()),
+            server_key_file.clone(),
+            server_cert_file.clone(),
+            server_key_file.clone(),
+            server_cert_file.clone(),
+        )
+        .expect("Unable to create server transport")
+        .with_crl_file(crl_file)
+        .expect("Unable to load certificate revocation list");
+        let mut listener = server.listen("127.0.0.1:0").expect("Unable to listen");
+        let endpoint = listener.endpoint();
+
+        let mut revoked_client = TlsTransport::new(
+            Some(ca_file.clone()),
+            revoked_key_file,
+            revoked_cert_file,
+            server_key_file.clone(),
+            server_cert_file.clone(),
+        )
+        .expect("Unable to create revoked client transport");
+        let connect_endpoint = endpoint.clone();
+        let handle = std::thread::spawn(move || revoked_client.connect(&connect_endpoint).is_err());
+        assert!(listener.accept().is_err());
+        assert!(handle.join().unwrap());
+
+        let mut client = TlsTransport::new(
+            Some(ca_file),
+            client_key_file,
+            client_cert_file,
+            server_key_file,
+            server_cert_file,
+        )
+        .expect("Unable to create client transport");
+        let handle = std::thread::spawn(move || client.connect(&endpoint).is_ok());
+        assert!(listener.accept().is_ok());
+        assert!(handle.join().unwrap());
+    }
+
+    /// Test that reloading a certificate revocation list closes the established connections whose
+    /// remote certificates it revokes:
+    /// 1. Connect a client to a server that checks a list revoking no certificates
+    /// 2. Replace the list with one revoking the certificate of the client and reload it
+    /// 3. Verify the server's end of the established connection is closed
+    #[cfg(all(feature = "tls-crl", feature = "tls-reload"))]
+    #[test]
+    fn test_reload_crl_closes_revoked_connections() {
+        let temp_dir = TempDir::new("tls-crl-test").unwrap();
+        let dir = temp_dir.path();
+
+        let (ca_key, ca_cert) = make_ca_cert();
+        let (server_key, server_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+        let (client_key, client_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+
+        let ca_file = write_file(dir.to_path_buf(), "ca.cert", &ca_cert.to_pem().unwrap());
+        let crl_file = write_file(
+            dir.to_path_buf(),
+            "ca.crl",
+            &make_crl(&ca_cert, &ca_key, &[]),
+        );
+        let server_key_file = write_file(
+            dir.to_path_buf(),
+            "server.key",
+            &server_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+        let server_cert_file = write_file(
+            dir.to_path_buf(),
+            "server.cert",
+            &server_cert.to_pem().unwrap(),
+        );
+        let client_key_file = write_file(
+            dir.to_path_buf(),
+            "client.key",
+            &client_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+        let client_cert_file = write_file(
+            dir.to_path_buf(),
+            "client.cert",
+            &client_cert.to_pem().unwrap(),
+        );
+
+        let mut server = TlsTransport::new(
+            Some(ca_file.clone()),
+            server_key_file.clone(),
+            server_cert_file.clone(),
+            server_key_file.clone(),
+            server_cert_file.clone(),
+        )
+        .expect("Unable to create server transport")
+        .with_crl_file(crl_file)
+        .expect("Unable to load certificate revocation list");
+        let reloader = server.reloader();
+        let mut listener = server.listen("127.0.0.1:0").expect("Unable to listen");
+        let endpoint = listener.endpoint();
+
+        let mut client = TlsTransport::new(
+            Some(ca_file),
+            client_key_file,
+            client_cert_file,
+            server_key_file,
+            server_cert_file,
+        )
+        .expect("Unable to create client transport");
+        let handle = std::thread::spawn(move || client.connect(&endpoint));
+        let mut server_connection = listener.accept().expect("Unable to accept connection");
+        let _client_connection = handle.join().unwrap().expect("Unable to connect");
+
+        write_file(
+            dir.to_path_buf(),
+            "ca.crl",
+            &make_crl(&ca_cert, &ca_key, &[&client_cert]),
+        );
+        reloader.reload().expect("Unable to reload files");
+
+        loop {
+            match server_connection.recv() {
+                Err(RecvError::WouldBlock) => {
+                    std::thread::sleep(std::time::Duration::from_millis(100))
+                }
+                res => {
+                    assert!(res.is_err());
+                    break;
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_transport() {
         let transport = create_test_tls_transport(true);
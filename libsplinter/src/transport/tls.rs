@@ -12,20 +12,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "tls-crl")]
+use std::net::{Shutdown, TcpStream};
 #[cfg(feature = "ws-transport")]
 use std::path::Path;
-
-#[cfg(feature = "ws-transport")]
+#[cfg(feature = "tls-crl")]
+use std::sync::Weak;
+#[cfg(any(feature = "tls-crl", feature = "tls-reload"))]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "tls-reload")]
+use std::time::SystemTime;
+
+#[cfg(any(feature = "tls-crl", feature = "ws-transport"))]
 use openssl::error::ErrorStack;
+#[cfg(any(feature = "tls-crl", feature = "ws-transport"))]
+use openssl::ssl::SslFiletype;
 #[cfg(feature = "ws-transport")]
-use openssl::ssl::{SslAcceptor, SslConnector, SslFiletype, SslMethod, SslVerifyMode};
-
+use openssl::ssl::{SslAcceptor, SslConnector, SslMethod, SslVerifyMode};
+#[cfg(feature = "tls-crl")]
+use openssl::ssl::{SslContextBuilder, SslStream};
+#[cfg(feature = "tls-crl")]
+use openssl::x509::{
+    store::X509Lookup, verify::X509VerifyFlags, CrlStatus, X509Crl, X509CrlRef, X509Ref, X509,
+};
+
+#[cfg(any(feature = "tls-crl", feature = "tls-reload"))]
+use crate::error::InternalError;
+
+#[derive(Clone)]
 pub struct TlsConfig {
     ca_certs_file: Option<String>,
     server_cert_file: String,
     server_private_key_file: String,
     client_cert_file: String,
     client_private_key_file: String,
+    #[cfg(feature = "tls-crl")]
+    crl_file: Option<String>,
 }
 
 impl TlsConfig {
@@ -48,6 +70,13 @@ impl TlsConfig {
     pub fn client_private_key_file(&self) -> &str {
         &self.client_private_key_file
     }
+
+    /// Returns the certificate revocation list file that the certificates of remote endpoints are
+    /// checked against, if one is configured.
+    #[cfg(feature = "tls-crl")]
+    pub fn crl_file(&self) -> Option<&str> {
+        self.crl_file.as_deref()
+    }
 }
 
 #[derive(Default)]
@@ -57,6 +86,8 @@ pub struct TlsConfigBuilder {
     server_private_key_file: Option<String>,
     client_cert_file: Option<String>,
     client_private_key_file: Option<String>,
+    #[cfg(feature = "tls-crl")]
+    crl_file: Option<String>,
 }
 
 impl TlsConfigBuilder {
@@ -67,6 +98,8 @@ impl TlsConfigBuilder {
             server_private_key_file: None,
             client_cert_file: None,
             client_private_key_file: None,
+            #[cfg(feature = "tls-crl")]
+            crl_file: None,
         }
     }
 
@@ -95,6 +128,14 @@ impl TlsConfigBuilder {
         self
     }
 
+    /// Sets a PEM file containing a certificate revocation list, which the certificates of remote
+    /// endpoints are checked against.
+    #[cfg(feature = "tls-crl")]
+    pub fn with_crl_file(mut self, crl_file: String) -> Self {
+        self.crl_file = Some(crl_file);
+        self
+    }
+
     pub fn build(self) -> Result<TlsConfig, TlsConfigBuilderError> {
         let ca_certs_file = self.ca_certs_file;
         let server_cert_file = self
@@ -110,12 +151,24 @@ impl TlsConfigBuilder {
             TlsConfigBuilderError::MissingField("client_private_key_file".to_string())
         })?;
 
+        #[cfg(feature = "tls-crl")]
+        {
+            if self.crl_file.is_some() && ca_certs_file.is_none() {
+                return Err(TlsConfigBuilderError::InvalidField(
+                    "a CA certificate file is required to check a certificate revocation list"
+                        .to_string(),
+                ));
+            }
+        }
+
         Ok(TlsConfig {
             ca_certs_file,
             server_cert_file,
             server_private_key_file,
             client_cert_file,
             client_private_key_file,
+            #[cfg(feature = "tls-crl")]
+            crl_file: self.crl_file,
         })
     }
 }
@@ -123,6 +176,7 @@ impl TlsConfigBuilder {
 #[derive(Debug)]
 pub enum TlsConfigBuilderError {
     MissingField(String),
+    InvalidField(String),
 }
 
 impl std::error::Error for TlsConfigBuilderError {}
@@ -133,6 +187,9 @@ impl std::fmt::Display for TlsConfigBuilderError {
             TlsConfigBuilderError::MissingField(ref s) => {
                 write!(f, "Missing required field '{}' in TLS configuration", s)
             }
+            TlsConfigBuilderError::InvalidField(ref s) => {
+                write!(f, "Invalid TLS configuration: {}", s)
+            }
         }
     }
 }
@@ -154,6 +211,13 @@ pub(super) fn build_connector(config: &TlsConfig) -> Result<SslConnector, ErrorS
         builder.set_verify(SslVerifyMode::NONE);
     }
 
+    #[cfg(feature = "tls-crl")]
+    {
+        if let Some(crl_file) = config.crl_file() {
+            load_crl(&mut builder, crl_file)?;
+        }
+    }
+
     Ok(builder.build())
 }
 
@@ -174,16 +238,251 @@ pub(super) fn build_acceptor(config: &TlsConfig) -> Result<SslAcceptor, ErrorSta
         builder.set_verify(SslVerifyMode::NONE);
     }
 
+    // Revocation can only be checked if the client is required to present its certificate
+    #[cfg(feature = "tls-crl")]
+    {
+        if let Some(crl_file) = config.crl_file() {
+            builder.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+            load_crl(&mut builder, crl_file)?;
+        }
+    }
+
     Ok(builder.build())
 }
 
+/// Loads the certificate revocation list in the given PEM file and enables revocation checks of
+/// the peer certificate.
+#[cfg(feature = "tls-crl")]
+pub(crate) fn load_crl(builder: &mut SslContextBuilder, crl_file: &str) -> Result<(), ErrorStack> {
+    builder
+        .cert_store_mut()
+        .add_lookup(X509Lookup::file())?
+        .load_crl_file(crl_file, SslFiletype::PEM)?;
+    builder
+        .verify_param_mut()
+        .set_flags(X509VerifyFlags::CRL_CHECK)?;
+    Ok(())
+}
+
+/// Reads the certificate revocation list in the given PEM file.
+#[cfg(feature = "tls-crl")]
+pub(crate) fn read_crl(crl_file: &str) -> Result<X509Crl, InternalError> {
+    let pem = std::fs::read(crl_file).map_err(|err| {
+        InternalError::from_source_with_prefix(
+            Box::new(err),
+            format!("Unable to read certificate revocation list {}", crl_file),
+        )
+    })?;
+    X509Crl::from_pem(&pem).map_err(|err| {
+        InternalError::from_source_with_prefix(
+            Box::new(err),
+            format!("Unable to parse certificate revocation list {}", crl_file),
+        )
+    })
+}
+
+/// Returns true if the certificate is revoked by the certificate revocation list.
+#[cfg(feature = "tls-crl")]
+pub(crate) fn is_revoked(crl: &X509CrlRef, cert: &X509Ref) -> bool {
+    match crl.get_by_cert(cert) {
+        CrlStatus::Revoked(_) => true,
+        CrlStatus::NotRevoked | CrlStatus::RemoveFromCrl(_) => false,
+    }
+}
+
+/// The connections a transport has established, along with the certificates of their remote
+/// endpoints, so that they can be closed when a certificate revocation list is reloaded.
+///
+/// A connection stays tracked for as long as the `TrackedConnection` returned by [`track`] is
+/// kept alive, so it should be owned by the connection.
+///
+/// [`track`]: #method.track
+#[cfg(feature = "tls-crl")]
+#[derive(Clone, Default)]
+pub(crate) struct TrackedConnections {
+    connections: Arc<Mutex<Vec<Weak<TrackedConnection>>>>,
+}
+
+/// A connection that can be closed if the certificate of its remote endpoint is revoked
+#[cfg(feature = "tls-crl")]
+pub(crate) struct TrackedConnection {
+    peer_cert: X509,
+    close: Box<dyn Fn() + Send + Sync>,
+}
+
+#[cfg(feature = "tls-crl")]
+impl TrackedConnections {
+    /// Tracks a connection to a remote endpoint with the given certificate; `close` is called if
+    /// the certificate is revoked.
+    pub fn track(
+        &self,
+        peer_cert: X509,
+        close: Box<dyn Fn() + Send + Sync>,
+    ) -> Result<Arc<TrackedConnection>, InternalError> {
+        let connection = Arc::new(TrackedConnection { peer_cert, close });
+        let mut connections = self
+            .connections
+            .lock()
+            .map_err(|_| InternalError::with_message("tracked connections lock poisoned".into()))?;
+        connections.retain(|connection| connection.strong_count() > 0);
+        connections.push(Arc::downgrade(&connection));
+        Ok(connection)
+    }
+
+    /// Tracks a connection over the given TLS stream, which is closed by shutting down the
+    /// underlying TCP stream. Returns `None` if the remote endpoint did not present a certificate.
+    pub fn track_stream(
+        &self,
+        stream: &SslStream<TcpStream>,
+    ) -> Result<Option<Arc<TrackedConnection>>, InternalError> {
+        let peer_cert = match stream.ssl().peer_certificate() {
+            Some(peer_cert) => peer_cert,
+            None => return Ok(None),
+        };
+        let tcp_stream = stream
+            .get_ref()
+            .try_clone()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        self.track(
+            peer_cert,
+            Box::new(move || {
+                let _ = tcp_stream.shutdown(Shutdown::Both);
+            }),
+        )
+        .map(Some)
+    }
+
+    /// Closes the tracked connections whose remote endpoints' certificates are revoked by the
+    /// certificate revocation list in the given PEM file, returning how many were closed.
+    pub fn close_revoked(&self, crl_file: &str) -> Result<usize, InternalError> {
+        let crl = read_crl(crl_file)?;
+        let mut connections = self
+            .connections
+            .lock()
+            .map_err(|_| InternalError::with_message("tracked connections lock poisoned".into()))?;
+
+        let mut closed = 0;
+        connections.retain(|connection| match connection.upgrade() {
+            Some(connection) if is_revoked(&crl, &connection.peer_cert) => {
+                (connection.close)();
+                closed += 1;
+                false
+            }
+            Some(_) => true,
+            // The connection has been dropped
+            None => false,
+        });
+
+        Ok(closed)
+    }
+}
+
+/// Reloads the certificate and key files of a transport, and of the listeners it created.
+///
+/// Only connections that are established after a reload use the new certificates. Established
+/// connections are left open, unless the reloaded certificate revocation list revokes the
+/// certificate of their remote endpoint, in which case they are closed.
+#[cfg(feature = "tls-reload")]
+#[derive(Clone)]
+pub struct TlsReloader {
+    paths: Vec<String>,
+    last_modified: Arc<Mutex<Vec<Option<SystemTime>>>>,
+    reload: Arc<dyn Fn() -> Result<(), InternalError> + Send + Sync>,
+}
+
+#[cfg(feature = "tls-reload")]
+impl TlsReloader {
+    /// Creates a reloader for a transport built from the given files; `reload` rebuilds the
+    /// transport from the files.
+    pub(crate) fn new(
+        paths: Vec<String>,
+        reload: Arc<dyn Fn() -> Result<(), InternalError> + Send + Sync>,
+    ) -> Self {
+        let last_modified = Arc::new(Mutex::new(modified(&paths)));
+        TlsReloader {
+            paths,
+            last_modified,
+            reload,
+        }
+    }
+
+    /// Reloads the files. If any of the files is invalid, the certificates that were in use
+    /// before are kept and an error is returned.
+    pub fn reload(&self) -> Result<(), InternalError> {
+        let mut last_modified = self
+            .last_modified
+            .lock()
+            .map_err(|_| InternalError::with_message("TLS reloader lock was poisoned".into()))?;
+        self.reload_files(&mut last_modified)
+    }
+
+    /// Reloads the files if any of them has been modified since they were last loaded, returning
+    /// whether they were reloaded.
+    pub fn reload_if_changed(&self) -> Result<bool, InternalError> {
+        let mut last_modified = self
+            .last_modified
+            .lock()
+            .map_err(|_| InternalError::with_message("TLS reloader lock was poisoned".into()))?;
+        if *last_modified == modified(&self.paths) {
+            return Ok(false);
+        }
+        self.reload_files(&mut last_modified)?;
+        Ok(true)
+    }
+
+    fn reload_files(
+        &self,
+        last_modified: &mut Vec<Option<SystemTime>>,
+    ) -> Result<(), InternalError> {
+        // Record the modification times first, so a file that is replaced while it is being
+        // loaded is loaded again on the next check
+        *last_modified = modified(&self.paths);
+        (self.reload)()
+    }
+}
+
+/// Returns the modification time of each file, or `None` for a file that cannot be read.
+#[cfg(feature = "tls-reload")]
+fn modified(paths: &[String]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .collect()
+}
+
+#[cfg(feature = "tls-reload")]
+impl TlsConfig {
+    /// Returns the paths of the files in the configuration.
+    pub(crate) fn paths(&self) -> Vec<String> {
+        let mut paths = vec![
+            self.client_private_key_file.clone(),
+            self.client_cert_file.clone(),
+            self.server_private_key_file.clone(),
+            self.server_cert_file.clone(),
+        ];
+        paths.extend(self.ca_certs_file.iter().cloned());
+        #[cfg(feature = "tls-crl")]
+        paths.extend(self.crl_file.iter().cloned());
+        paths
+    }
+}
+
 #[cfg(test)]
 pub(super) mod tests {
     use openssl::asn1::Asn1Time;
+    #[cfg(feature = "tls-crl")]
+    use openssl::base64;
     use openssl::bn::{BigNum, MsbOption};
     use openssl::hash::MessageDigest;
     use openssl::pkey::{PKey, PKeyRef, Private};
     use openssl::rsa::Rsa;
+    #[cfg(feature = "tls-crl")]
+    use openssl::sign::Signer;
     use openssl::x509::extension::{BasicConstraints, ExtendedKeyUsage, KeyUsage};
     use openssl::x509::{X509NameBuilder, X509Ref, X509};
 
@@ -211,7 +510,7 @@ pub(super) mod tests {
             .append_extension(BasicConstraints::new().critical().ca().build().unwrap())
             .unwrap();
         cert_builder
-            .append_extension(KeyUsage::new().key_cert_sign().build().unwrap())
+            .append_extension(KeyUsage::new().key_cert_sign().crl_sign().build().unwrap())
             .unwrap();
 
         cert_builder
@@ -269,4 +568,114 @@ pub(super) mod tests {
 
         (privkey, cert)
     }
+
+    // Make a PEM-encoded certificate revocation list, signed by the given CA cert and private key,
+    // that revokes the given certificates
+    #[cfg(feature = "tls-crl")]
+    pub fn make_crl(
+        ca_cert: &X509Ref,
+        ca_privkey: &PKeyRef<Private>,
+        revoked: &[&X509Ref],
+    ) -> Vec<u8> {
+        // sha256WithRSAEncryption
+        let algorithm = der(
+            0x30,
+            &[
+                &[
+                    0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b,
+                ][..],
+                &[0x05, 0x00][..],
+            ]
+            .concat(),
+        );
+        let revoked_certificates = revoked
+            .iter()
+            .map(|cert| {
+                let serial = cert.serial_number().to_bn().unwrap().to_vec();
+                der(
+                    0x30,
+                    &[der_integer(&serial), der(0x17, b"200101000000Z")].concat(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .concat();
+
+        // The list of revoked certificates is omitted when it is empty
+        let revoked_certificates = if revoked_certificates.is_empty() {
+            vec![]
+        } else {
+            der(0x30, &revoked_certificates)
+        };
+
+        let tbs_cert_list = der(
+            0x30,
+            &[
+                der_integer(&[1]),
+                algorithm.clone(),
+                ca_cert.subject_name().to_der().unwrap(),
+                der(0x17, b"200101000000Z"),
+                der(0x17, b"491231235959Z"),
+                revoked_certificates,
+            ]
+            .concat(),
+        );
+
+        let mut signer = Signer::new(MessageDigest::sha256(), ca_privkey).unwrap();
+        signer.update(&tbs_cert_list).unwrap();
+        let signature = signer.sign_to_vec().unwrap();
+
+        let crl = der(
+            0x30,
+            &[
+                tbs_cert_list,
+                algorithm,
+                der(0x03, &[&[0][..], &signature[..]].concat()),
+            ]
+            .concat(),
+        );
+
+        let encoded = base64::encode_block(&crl);
+        let mut pem = b"-----BEGIN X509 CRL-----\n".to_vec();
+        for line in encoded.as_bytes().chunks(64) {
+            pem.extend_from_slice(line);
+            pem.push(b'\n');
+        }
+        pem.extend_from_slice(b"-----END X509 CRL-----\n");
+        pem
+    }
+
+    // Encode a DER value with the given tag
+    #[cfg(feature = "tls-crl")]
+    fn der(tag: u8, content: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![tag];
+        if content.len() < 0x80 {
+            encoded.push(content.len() as u8);
+        } else {
+            let len_bytes = content
+                .len()
+                .to_be_bytes()
+                .iter()
+                .copied()
+                .skip_while(|byte| *byte == 0)
+                .collect::<Vec<_>>();
+            encoded.push(0x80 | len_bytes.len() as u8);
+            encoded.extend(len_bytes);
+        }
+        encoded.extend_from_slice(content);
+        encoded
+    }
+
+    // Encode a DER integer from its unsigned big-endian bytes
+    #[cfg(feature = "tls-crl")]
+    fn der_integer(bytes: &[u8]) -> Vec<u8> {
+        let mut content = bytes
+            .iter()
+            .copied()
+            .skip_while(|byte| *byte == 0)
+            .collect::<Vec<_>>();
+        if content.first().map(|byte| byte & 0x80 != 0).unwrap_or(true) {
+            content.insert(0, 0);
+        }
+        der(0x02, &content)
+    }
 }
@@ -15,11 +15,15 @@
 use std::io::{Read, Write};
 use std::net::TcpStream;
 use std::os::unix::io::{AsRawFd, RawFd};
+#[cfg(feature = "tls-crl")]
+use std::sync::Arc;
 
 use mio::{unix::EventedFd, Evented, Poll, PollOpt, Ready, Token};
 use openssl::ssl::SslStream;
 use tungstenite::{protocol::WebSocket, Message};
 
+#[cfg(feature = "tls-crl")]
+use crate::transport::tls::TrackedConnection;
 use crate::transport::{Connection, DisconnectError, RecvError, SendError};
 
 pub(super) struct WsConnection<S>
@@ -29,6 +33,9 @@ where
     websocket: WebSocket<S>,
     remote_endpoint: String,
     local_endpoint: String,
+    /// Keeps the connection tracked for revocation checks while it is open
+    #[cfg(feature = "tls-crl")]
+    _tracked: Option<Arc<TrackedConnection>>,
 }
 
 impl<S> WsConnection<S>
@@ -40,8 +47,17 @@ where
             websocket,
             remote_endpoint,
             local_endpoint,
+            #[cfg(feature = "tls-crl")]
+            _tracked: None,
         }
     }
+
+    /// Keeps the connection tracked for revocation checks until it is dropped.
+    #[cfg(feature = "tls-crl")]
+    pub fn with_tracked(mut self, tracked: Option<Arc<TrackedConnection>>) -> Self {
+        self._tracked = tracked;
+        self
+    }
 }
 
 impl<S> Connection for WsConnection<S>
@@ -13,46 +13,75 @@
 // limitations under the License.
 
 use std::net::TcpListener;
+use std::sync::{Arc, RwLock};
 use std::thread;
 use std::time::Duration;
 
-use openssl::ssl::SslAcceptor;
 use tungstenite::{accept, handshake::HandshakeError};
 
+#[cfg(feature = "tls-crl")]
+use crate::transport::tls::TrackedConnections;
 use crate::transport::{AcceptError, Connection, Listener};
 
 use super::connection::WsConnection;
+use super::transport::TlsInner;
 use super::transport::WSS_PROTOCOL_PREFIX;
 use super::transport::WS_PROTOCOL_PREFIX;
 
 pub(super) struct WsListener {
     listener: TcpListener,
     local_endpoint: String,
-    acceptor: Option<SslAcceptor>,
+    tls_inner: Option<Arc<RwLock<TlsInner>>>,
+    #[cfg(feature = "tls-crl")]
+    connections: Option<TrackedConnections>,
 }
 
 impl WsListener {
     pub fn new(
         listener: TcpListener,
         local_endpoint: String,
-        acceptor: Option<SslAcceptor>,
+        tls_inner: Option<Arc<RwLock<TlsInner>>>,
     ) -> Self {
         WsListener {
             listener,
             local_endpoint,
-            acceptor,
+            tls_inner,
+            #[cfg(feature = "tls-crl")]
+            connections: None,
         }
     }
+
+    /// Tracks the accepted connections for revocation checks.
+    #[cfg(feature = "tls-crl")]
+    pub fn with_tracked_connections(mut self, connections: Option<TrackedConnections>) -> Self {
+        self.connections = connections;
+        self
+    }
 }
 
 impl Listener for WsListener {
     fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
         let (stream, _) = self.listener.accept()?;
 
-        if let Some(acceptor) = &self.acceptor {
+        if let Some(tls_inner) = &self.tls_inner {
             let remote_endpoint = format!("{}{}", WSS_PROTOCOL_PREFIX, stream.peer_addr()?);
 
-            let websocket = accept(acceptor.accept(stream)?).map_or_else(
+            let acceptor = tls_inner
+                .read()
+                .map_err(|_| AcceptError::ProtocolError("WebSocket TLS lock was poisoned".into()))?
+                .acceptor
+                .clone();
+            let tls_stream = acceptor.accept(stream)?;
+
+            #[cfg(feature = "tls-crl")]
+            let tracked = match &self.connections {
+                Some(connections) => connections.track_stream(&tls_stream).map_err(|err| {
+                    AcceptError::ProtocolError(format!("Unable to track connection: {}", err))
+                })?,
+                None => None,
+            };
+
+            let websocket = accept(tls_stream).map_or_else(
                 {
                     |mut handshake_err| loop {
                         match handshake_err {
@@ -70,11 +99,12 @@ impl Listener for WsListener {
                 Ok,
             )?;
 
-            Ok(Box::new(WsConnection::new(
-                websocket,
-                remote_endpoint,
-                self.local_endpoint.clone(),
-            )))
+            let connection =
+                WsConnection::new(websocket, remote_endpoint, self.local_endpoint.clone());
+            #[cfg(feature = "tls-crl")]
+            let connection = connection.with_tracked(tracked);
+
+            Ok(Box::new(connection))
         } else {
             let remote_endpoint = format!("{}{}", WS_PROTOCOL_PREFIX, stream.peer_addr()?);
 
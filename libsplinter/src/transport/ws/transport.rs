@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::net::{Ipv4Addr, Ipv6Addr, TcpListener, TcpStream};
+use std::sync::{Arc, RwLock};
 use std::thread;
 use std::time::Duration;
 
@@ -21,6 +22,12 @@ use openssl::ssl::{SslAcceptor, SslConnector};
 use tungstenite::{client, handshake::HandshakeError};
 use url::{ParseError, Url};
 
+#[cfg(feature = "tls-reload")]
+use crate::error::InternalError;
+#[cfg(feature = "tls-reload")]
+use crate::transport::tls::TlsReloader;
+#[cfg(feature = "tls-crl")]
+use crate::transport::tls::TrackedConnections;
 use crate::transport::tls::{build_acceptor, build_connector, TlsConfig};
 use crate::transport::{ConnectError, Connection, ListenError, Listener, Transport};
 
@@ -30,11 +37,24 @@ use super::listener::WsListener;
 pub(super) const WS_PROTOCOL_PREFIX: &str = "ws://";
 pub(super) const WSS_PROTOCOL_PREFIX: &str = "wss://";
 
-struct TlsInner {
-    acceptor: SslAcceptor,
+/// The acceptor and connector used for new wss:// connections.
+///
+/// Established connections keep the SSL context they were created with, so replacing the
+/// `TlsInner` of a transport does not affect them.
+pub(super) struct TlsInner {
+    pub(super) acceptor: SslAcceptor,
     connector: SslConnector,
 }
 
+impl TlsInner {
+    fn build(config: &TlsConfig) -> Result<Self, ErrorStack> {
+        Ok(TlsInner {
+            acceptor: build_acceptor(config)?,
+            connector: build_connector(config)?,
+        })
+    }
+}
+
 /// A WebSocket-based `Transport`.
 ///
 /// Supports endpoints of the format `ws://ip_or_host:port`.
@@ -95,22 +115,79 @@ struct TlsInner {
 /// ```
 #[derive(Default)]
 pub struct WsTransport {
-    tls_inner: Option<TlsInner>,
+    tls_inner: Option<Arc<RwLock<TlsInner>>>,
+    #[cfg(any(feature = "tls-crl", feature = "tls-reload"))]
+    tls_config: Option<TlsConfig>,
+    /// The wss:// connections to check when a certificate revocation list is reloaded
+    #[cfg(feature = "tls-crl")]
+    connections: TrackedConnections,
 }
 
 impl WsTransport {
     pub fn new(config: Option<&TlsConfig>) -> Result<Self, WsInitError> {
         if let Some(conf) = config {
             Ok(WsTransport {
-                tls_inner: Some(TlsInner {
-                    acceptor: build_acceptor(&conf)?,
-                    connector: build_connector(&conf)?,
-                }),
+                tls_inner: Some(Arc::new(RwLock::new(TlsInner::build(conf)?))),
+                #[cfg(any(feature = "tls-crl", feature = "tls-reload"))]
+                tls_config: Some(conf.clone()),
+                #[cfg(feature = "tls-crl")]
+                connections: TrackedConnections::default(),
             })
         } else {
-            Ok(WsTransport { tls_inner: None })
+            Ok(WsTransport::default())
         }
     }
+
+    /// Returns a `TlsReloader` that reloads the certificate and key files of this transport's
+    /// wss:// connections, or `None` if the transport was created without TLS.
+    ///
+    /// When a certificate revocation list is configured, the established wss:// connections are
+    /// checked against the reloaded list, and the connections whose remote certificates it
+    /// revokes are closed.
+    #[cfg(feature = "tls-reload")]
+    pub fn reloader(&self) -> Option<TlsReloader> {
+        let tls_inner = self.tls_inner.clone()?;
+        let config = self.tls_config.clone()?;
+        let paths = config.paths();
+        #[cfg(feature = "tls-crl")]
+        let connections = self.connections.clone();
+
+        Some(TlsReloader::new(
+            paths,
+            Arc::new(move || {
+                let new_inner = TlsInner::build(&config)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                *tls_inner.write().map_err(|_| {
+                    InternalError::with_message("WebSocket TLS lock was poisoned".into())
+                })? = new_inner;
+
+                #[cfg(feature = "tls-crl")]
+                {
+                    if let Some(crl) = config.crl_file() {
+                        let closed = connections.close_revoked(crl)?;
+                        if closed > 0 {
+                            info!(
+                                "Closed {} wss:// connections with revoked certificates",
+                                closed
+                            );
+                        }
+                    }
+                }
+
+                Ok(())
+            }),
+        ))
+    }
+
+    /// Returns the connections to track for revocation checks, if a certificate revocation list
+    /// is configured.
+    #[cfg(feature = "tls-crl")]
+    fn tracked_connections(&self) -> Option<TrackedConnections> {
+        self.tls_config
+            .as_ref()
+            .and_then(TlsConfig::crl_file)
+            .map(|_| self.connections.clone())
+    }
 }
 
 fn endpoint_to_dns_name(endpoint: &str) -> Result<String, ParseError> {
@@ -169,7 +246,7 @@ impl Transport for WsTransport {
             let remote_endpoint = format!("{}{}", WSS_PROTOCOL_PREFIX, stream.peer_addr()?);
             let local_endpoint = format!("{}{}", WSS_PROTOCOL_PREFIX, stream.local_addr()?);
 
-            let tls_stream = self
+            let connector = self
                 .tls_inner
                 .as_ref()
                 .ok_or_else(|| {
@@ -178,8 +255,19 @@ impl Transport for WsTransport {
                         WSS_PROTOCOL_PREFIX
                     ))
                 })?
+                .read()
+                .map_err(|_| ConnectError::ProtocolError("WebSocket TLS lock was poisoned".into()))?
                 .connector
-                .connect(&dns_name, stream)?;
+                .clone();
+            let tls_stream = connector.connect(&dns_name, stream)?;
+
+            #[cfg(feature = "tls-crl")]
+            let tracked = match self.tracked_connections() {
+                Some(connections) => connections.track_stream(&tls_stream).map_err(|err| {
+                    ConnectError::ProtocolError(format!("Unable to track connection: {}", err))
+                })?,
+                None => None,
+            };
 
             let (websocket, _) = client(endpoint, tls_stream).map_or_else(
                 {
@@ -199,11 +287,11 @@ impl Transport for WsTransport {
                 Ok,
             )?;
 
-            Ok(Box::new(WsConnection::new(
-                websocket,
-                remote_endpoint,
-                local_endpoint,
-            )))
+            let connection = WsConnection::new(websocket, remote_endpoint, local_endpoint);
+            #[cfg(feature = "tls-crl")]
+            let connection = connection.with_tracked(tracked);
+
+            Ok(Box::new(connection))
         } else {
             Err(ConnectError::ProtocolError(format!(
                 "Invalid protocol: {}",
@@ -248,11 +336,11 @@ impl Transport for WsTransport {
                 })?
             );
 
-            Ok(Box::new(WsListener::new(
-                tcp_listener,
-                local_endpoint,
-                Some(inner.acceptor.clone()),
-            )))
+            let listener = WsListener::new(tcp_listener, local_endpoint, Some(inner.clone()));
+            #[cfg(feature = "tls-crl")]
+            let listener = listener.with_tracked_connections(self.tracked_connections());
+
+            Ok(Box::new(listener))
         } else {
             Err(ListenError::ProtocolError(format!(
                 "Invalid protocol: {}",
@@ -0,0 +1,266 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Health checks for the components a splinter node depends on.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use splinter::admin::store::{AdminServiceStore, CircuitStatus};
+use splinter::orchestrator::{ServiceDefinition, ServiceLister};
+use splinter::peer::{PeerManagerConnector, PeerStatus};
+use splinter::registry::RemoteYamlRefreshStatusHandle;
+use splinter::store::StoreFactory;
+
+use crate::report::{CheckReport, HealthCheck};
+
+/// Checks that the node's database is reachable.
+pub struct DatabaseCheck {
+    store: Box<dyn AdminServiceStore>,
+}
+
+impl DatabaseCheck {
+    pub fn new(store_factory: &dyn StoreFactory) -> Self {
+        Self {
+            store: store_factory.get_admin_service_store(),
+        }
+    }
+}
+
+impl HealthCheck for DatabaseCheck {
+    fn check(&self) -> Vec<CheckReport> {
+        // Any query requires a connection to the database; looking up a node that cannot exist
+        // keeps the query cheap.
+        let report = match self.store.get_node("") {
+            Ok(_) => CheckReport::pass("database", "The database is reachable"),
+            Err(err) => CheckReport::fail(
+                "database",
+                &format!("Unable to query the database: {}", err),
+            ),
+        };
+
+        vec![report]
+    }
+}
+
+/// Checks that the node is connected to its peers.
+pub struct PeerConnectivityCheck {
+    // The connector is not `Sync`, so it must be guarded for the check to be shared between the
+    // REST API's threads
+    connector: Mutex<PeerManagerConnector>,
+}
+
+impl PeerConnectivityCheck {
+    pub fn new(connector: PeerManagerConnector) -> Self {
+        Self {
+            connector: Mutex::new(connector),
+        }
+    }
+}
+
+impl HealthCheck for PeerConnectivityCheck {
+    fn check(&self) -> Vec<CheckReport> {
+        let peer_statuses = match self.connector.lock() {
+            Ok(connector) => connector.list_peer_statuses(),
+            Err(_) => return vec![CheckReport::fail("peers", "Peer connector lock poisoned")],
+        };
+
+        let peer_statuses = match peer_statuses {
+            Ok(peer_statuses) => peer_statuses,
+            Err(err) => {
+                return vec![CheckReport::fail(
+                    "peers",
+                    &format!("Unable to list peers: {}", err),
+                )]
+            }
+        };
+
+        let mut unconnected_peers = peer_statuses
+            .iter()
+            .filter(|(_, status)| *status != PeerStatus::Connected)
+            .map(|(peer_id, _)| peer_id.as_str())
+            .collect::<Vec<_>>();
+        unconnected_peers.sort();
+
+        let total = peer_statuses.len();
+        let connected = total - unconnected_peers.len();
+        let details = if unconnected_peers.is_empty() {
+            format!("{} of {} peers connected", connected, total)
+        } else {
+            format!(
+                "{} of {} peers connected; not connected: {}",
+                connected,
+                total,
+                unconnected_peers.join(", ")
+            )
+        };
+
+        let report = if unconnected_peers.is_empty() {
+            CheckReport::pass("peers", &details)
+        } else if connected > 0 {
+            CheckReport::warn("peers", &details)
+        } else {
+            CheckReport::fail("peers", &details)
+        };
+
+        vec![report]
+    }
+}
+
+/// Checks that the node's remote registries are being refreshed.
+pub struct RemoteRegistryCheck {
+    handles: Vec<RemoteYamlRefreshStatusHandle>,
+}
+
+impl RemoteRegistryCheck {
+    pub fn new(handles: Vec<RemoteYamlRefreshStatusHandle>) -> Self {
+        Self { handles }
+    }
+}
+
+impl HealthCheck for RemoteRegistryCheck {
+    fn check(&self) -> Vec<CheckReport> {
+        self.handles
+            .iter()
+            .map(|handle| {
+                let status = match handle.refresh_status() {
+                    Ok(status) => status,
+                    Err(err) => {
+                        return CheckReport::fail(
+                            "registry",
+                            &format!("Unable to get remote registry status: {}", err),
+                        )
+                    }
+                };
+
+                let name = format!("registry {}", status.url);
+                match (
+                    status.last_refresh_successful,
+                    status.last_successful_refresh,
+                ) {
+                    (true, _) => CheckReport::pass(&name, "The registry was refreshed"),
+                    (false, Some(last_successful_refresh)) => CheckReport::warn(
+                        &name,
+                        &format!(
+                            "The last refresh failed; the registry was last refreshed {}s ago",
+                            SystemTime::now()
+                                .duration_since(last_successful_refresh)
+                                .map(|elapsed| elapsed.as_secs())
+                                .unwrap_or(0)
+                        ),
+                    ),
+                    (false, None) => CheckReport::fail(
+                        &name,
+                        "The registry has not been refreshed since the node started",
+                    ),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Checks that the services this node runs for its circuits are running.
+pub struct ServiceCheck {
+    node_id: String,
+    service_lister: ServiceLister,
+    store: Box<dyn AdminServiceStore>,
+}
+
+impl ServiceCheck {
+    pub fn new(
+        node_id: &str,
+        service_lister: ServiceLister,
+        store: Box<dyn AdminServiceStore>,
+    ) -> Self {
+        Self {
+            node_id: node_id.into(),
+            service_lister,
+            store,
+        }
+    }
+}
+
+impl HealthCheck for ServiceCheck {
+    fn check(&self) -> Vec<CheckReport> {
+        let running_services = match self.service_lister.list_services(vec![], vec![]) {
+            Ok(services) => services.into_iter().collect::<HashSet<_>>(),
+            Err(err) => {
+                return vec![CheckReport::fail(
+                    "services",
+                    &format!("Unable to list running services: {}", err),
+                )]
+            }
+        };
+
+        let circuits = match self.store.list_circuits(&[]) {
+            Ok(circuits) => circuits,
+            Err(err) => {
+                return vec![CheckReport::fail(
+                    "services",
+                    &format!("Unable to list circuits: {}", err),
+                )]
+            }
+        };
+
+        let expected_services = circuits
+            .filter(|circuit| circuit.circuit_status() == &CircuitStatus::Active)
+            .flat_map(|circuit| {
+                let circuit_id = circuit.circuit_id().to_string();
+                circuit
+                    .roster()
+                    .iter()
+                    .filter(|service| service.node_id() == self.node_id)
+                    .map(|service| ServiceDefinition {
+                        circuit: circuit_id.clone(),
+                        service_id: service.service_id().into(),
+                        service_type: service.service_type().into(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<HashSet<_>>();
+
+        let mut reports = expected_services
+            .iter()
+            .map(|service| {
+                let name = service_name(service);
+                if running_services.contains(service) {
+                    CheckReport::pass(&name, "The service is running")
+                } else {
+                    CheckReport::fail(&name, "The service is not running")
+                }
+            })
+            .chain(
+                running_services
+                    .difference(&expected_services)
+                    .map(|service| {
+                        CheckReport::warn(
+                            &service_name(service),
+                            "The service is running, but is not part of an active circuit",
+                        )
+                    }),
+            )
+            .collect::<Vec<_>>();
+        reports.sort_by(|a, b| a.name.cmp(&b.name));
+
+        reports
+    }
+}
+
+fn service_name(service: &ServiceDefinition) -> String {
+    format!(
+        "service {}::{} ({})",
+        service.circuit, service.service_id, service.service_type
+    )
+}
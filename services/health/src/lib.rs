@@ -14,33 +14,50 @@
 
 #[macro_use]
 extern crate log;
+#[macro_use]
+extern crate serde_derive;
+
+pub mod checks;
+mod report;
+
+use std::any::Any;
+use std::sync::Arc;
 
 #[cfg(feature = "authorization")]
 use splinter::rest_api::auth::Permission;
 use splinter::{
-    actix_web::HttpResponse,
-    futures::IntoFuture,
+    actix_web::{web, Error, HttpResponse},
+    futures::Future,
     rest_api::{Method, Resource, RestResourceProvider},
     service::{
         error::{ServiceDestroyError, ServiceError, ServiceStartError, ServiceStopError},
         Service, ServiceMessageContext, ServiceNetworkRegistry,
     },
 };
-use std::any::Any;
+
+pub use report::{CheckReport, HealthCheck, HealthReport, HealthStatus};
 
 #[cfg(feature = "authorization")]
 pub const HEALTH_READ_PERMISSION: Permission = Permission::Check("health.read");
 
 pub struct HealthService {
     service_id: String,
+    checks: Vec<Arc<dyn HealthCheck>>,
 }
 
 impl HealthService {
     pub fn new(node_id: &str) -> Self {
         Self {
             service_id: format!("health::{}", node_id),
+            checks: vec![],
         }
     }
+
+    /// Adds a check to the readiness checks run for each status request.
+    pub fn with_check(mut self, check: Box<dyn HealthCheck>) -> Self {
+        self.checks.push(Arc::from(check));
+        self
+    }
 }
 
 impl Service for HealthService {
@@ -89,23 +106,49 @@ impl Service for HealthService {
 
 impl RestResourceProvider for HealthService {
     fn resources(&self) -> Vec<Resource> {
-        vec![make_status_resource()]
+        vec![make_status_resource(self.checks.clone())]
     }
 }
 
-fn make_status_resource() -> Resource {
+fn make_status_resource(checks: Vec<Arc<dyn HealthCheck>>) -> Resource {
     let resource = Resource::build("/health/status");
 
     #[cfg(feature = "authorization")]
     {
         resource.add_method(Method::Get, HEALTH_READ_PERMISSION, move |_, _| {
-            Box::new(HttpResponse::Ok().finish().into_future())
+            get_status(checks.clone())
         })
     }
     #[cfg(not(feature = "authorization"))]
     {
-        resource.add_method(Method::Get, move |_, _| {
-            Box::new(HttpResponse::Ok().finish().into_future())
-        })
+        resource.add_method(Method::Get, move |_, _| get_status(checks.clone()))
     }
 }
+
+fn get_status(
+    checks: Vec<Arc<dyn HealthCheck>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        web::block(move || {
+            let readiness = checks
+                .iter()
+                .flat_map(|check| check.check())
+                .collect::<Vec<_>>();
+
+            Ok::<_, ()>(HealthReport::new(
+                CheckReport::pass("liveness", "The node is running"),
+                readiness,
+            ))
+        })
+        .then(|res| match res {
+            Ok(report) if report.status == HealthStatus::Fail => {
+                Ok(HttpResponse::ServiceUnavailable().json(report))
+            }
+            Ok(report) => Ok(HttpResponse::Ok().json(report)),
+            Err(err) => {
+                error!("Unable to run health checks: {:?}", err);
+                Ok(HttpResponse::InternalServerError().finish())
+            }
+        }),
+    )
+}
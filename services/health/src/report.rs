@@ -0,0 +1,136 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Data structures for reporting the health of a node.
+
+/// The outcome of a health check.
+///
+/// Statuses are ordered from healthiest to least healthy, so the overall status of a set of checks
+/// is the maximum of their statuses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// The checked component is working as expected
+    Pass,
+    /// The checked component is working, but is degraded
+    Warn,
+    /// The checked component is not working
+    Fail,
+}
+
+/// The result of checking a single component of the node.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CheckReport {
+    pub name: String,
+    pub status: HealthStatus,
+    pub details: String,
+}
+
+impl CheckReport {
+    pub fn pass(name: &str, details: &str) -> Self {
+        Self::new(name, HealthStatus::Pass, details)
+    }
+
+    pub fn warn(name: &str, details: &str) -> Self {
+        Self::new(name, HealthStatus::Warn, details)
+    }
+
+    pub fn fail(name: &str, details: &str) -> Self {
+        Self::new(name, HealthStatus::Fail, details)
+    }
+
+    fn new(name: &str, status: HealthStatus, details: &str) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            details: details.into(),
+        }
+    }
+}
+
+/// A check of whether a component the node depends on is ready.
+pub trait HealthCheck: Send + Sync {
+    /// Checks the component, returning a report for each of the parts of the component that were
+    /// checked.
+    fn check(&self) -> Vec<CheckReport>;
+}
+
+/// The health of a node.
+///
+/// The liveness check reports whether the node is running at all, while the readiness checks
+/// report whether the components the node depends on are available.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    pub liveness: CheckReport,
+    pub readiness: Vec<CheckReport>,
+}
+
+impl HealthReport {
+    /// Creates a report from the results of the checks; the status of the report is that of the
+    /// least healthy check.
+    pub fn new(liveness: CheckReport, readiness: Vec<CheckReport>) -> Self {
+        let status = readiness
+            .iter()
+            .map(|check| check.status)
+            .fold(liveness.status, std::cmp::max);
+
+        Self {
+            status,
+            liveness,
+            readiness,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the status of a report is the least healthy status of its checks.
+    #[test]
+    fn report_status_is_least_healthy_check() {
+        let liveness = CheckReport::pass("liveness", "running");
+
+        let report = HealthReport::new(liveness.clone(), vec![]);
+        assert_eq!(report.status, HealthStatus::Pass);
+
+        let report = HealthReport::new(
+            liveness.clone(),
+            vec![
+                CheckReport::pass("database", "connected"),
+                CheckReport::warn("peers", "1 of 2 peers connected"),
+            ],
+        );
+        assert_eq!(report.status, HealthStatus::Warn);
+
+        let report = HealthReport::new(
+            liveness,
+            vec![
+                CheckReport::fail("database", "unreachable"),
+                CheckReport::warn("peers", "1 of 2 peers connected"),
+            ],
+        );
+        assert_eq!(report.status, HealthStatus::Fail);
+    }
+
+    /// Verify that statuses are serialized in lowercase.
+    #[test]
+    fn status_serialization() {
+        let check = CheckReport::warn("peers", "1 of 2 peers connected");
+        let value = serde_json::to_value(&check).expect("Unable to serialize check");
+
+        assert_eq!(value["status"], "warn");
+    }
+}
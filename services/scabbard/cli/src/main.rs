@@ -19,6 +19,8 @@ mod error;
 mod key;
 
 use std::fs::File;
+#[cfg(feature = "snapshot")]
+use std::io::BufWriter;
 use std::io::{BufReader, Read};
 use std::path::PathBuf;
 use std::time::Duration;
@@ -45,6 +47,8 @@ use sabre_sdk::{
     protos::FromBytes,
 };
 use scabbard::client::{ScabbardClientBuilder, ServiceId};
+#[cfg(feature = "snapshot")]
+use scabbard::snapshot::StateSnapshot;
 use transact::contract::archive::{default_scar_path, SmartContractArchive};
 
 use error::CliError;
@@ -563,38 +567,110 @@ fn run() -> Result<(), CliError> {
                                 .default_value("300"),
                         ]),
                 ),
-        )
+        );
+
+    let mut state_subcommand = SubCommand::with_name("state")
+        .about("Get scabbard state information")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
         .subcommand(
-            SubCommand::with_name("state")
-                .about("Get scabbard state information")
-                .setting(AppSettings::SubcommandRequiredElseHelp)
-                .subcommand(
-                    SubCommand::with_name("root")
-                        .about("Get the current state root hash")
-                        .args(&[
-                            Arg::with_name("url")
-                                .help("URL to the scabbard REST API")
-                                .short("U")
-                                .long("url")
-                                .takes_value(true)
-                                .default_value("http://localhost:8080"),
-                            Arg::with_name("service-id")
-                                .long_help(
-                                    "Fully-qualified service ID of the scabbard service (must be \
-                                     of the form 'circuit_id::service_id')",
-                                )
-                                .long("service-id")
-                                .takes_value(true)
-                                .required(true),
-                            Arg::with_name("key")
-                                .short("k")
-                                .long("key")
-                                .takes_value(true)
-                                .help("Name or path of private key"),
-                        ]),
-                ),
+            SubCommand::with_name("root")
+                .about("Get the current state root hash")
+                .args(&[
+                    Arg::with_name("url")
+                        .help("URL to the scabbard REST API")
+                        .short("U")
+                        .long("url")
+                        .takes_value(true)
+                        .default_value("http://localhost:8080"),
+                    Arg::with_name("service-id")
+                        .long_help(
+                            "Fully-qualified service ID of the scabbard service (must be \
+                             of the form 'circuit_id::service_id')",
+                        )
+                        .long("service-id")
+                        .takes_value(true)
+                        .required(true),
+                    Arg::with_name("key")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                ]),
         );
 
+    #[cfg(feature = "snapshot")]
+    {
+        state_subcommand = state_subcommand
+            .subcommand(
+                SubCommand::with_name("export")
+                    .about("Export a snapshot of scabbard state to a file")
+                    .args(&[
+                        Arg::with_name("file")
+                            .help("Path of the file to write the snapshot to")
+                            .required(true),
+                        Arg::with_name("state-root")
+                            .help(
+                                "State root hash to take the snapshot at; defaults to the \
+                                 current state root",
+                            )
+                            .long("state-root")
+                            .takes_value(true),
+                        Arg::with_name("url")
+                            .help("URL to the scabbard REST API")
+                            .short("U")
+                            .long("url")
+                            .takes_value(true)
+                            .default_value("http://localhost:8080"),
+                        Arg::with_name("service-id")
+                            .long_help(
+                                "Fully-qualified service ID of the scabbard service (must be \
+                                 of the form 'circuit_id::service_id')",
+                            )
+                            .long("service-id")
+                            .takes_value(true)
+                            .required(true),
+                        Arg::with_name("key")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    ]),
+            )
+            .subcommand(
+                SubCommand::with_name("import")
+                    .about(
+                        "Import a snapshot of scabbard state from a file into a service that \
+                         has not committed any transactions",
+                    )
+                    .args(&[
+                        Arg::with_name("file")
+                            .help("Path of the snapshot file to import")
+                            .required(true),
+                        Arg::with_name("url")
+                            .help("URL to the scabbard REST API")
+                            .short("U")
+                            .long("url")
+                            .takes_value(true)
+                            .default_value("http://localhost:8080"),
+                        Arg::with_name("service-id")
+                            .long_help(
+                                "Fully-qualified service ID of the scabbard service (must be \
+                                 of the form 'circuit_id::service_id')",
+                            )
+                            .long("service-id")
+                            .takes_value(true)
+                            .required(true),
+                        Arg::with_name("key")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    ]),
+            );
+    }
+
+    app = app.subcommand(state_subcommand);
+
     #[cfg(feature = "smart-permissions")]
     {
         app = app.subcommand(
@@ -1460,6 +1536,84 @@ fn run() -> Result<(), CliError> {
 
                 Ok(())
             }
+            #[cfg(feature = "snapshot")]
+            ("export", Some(matches)) => {
+                let url = matches.value_of("url").expect("default not set for --url");
+                let key = matches
+                    .value_of("key")
+                    .ok_or_else(|| CliError::MissingArgument("key".into()))?;
+
+                let client = ScabbardClientBuilder::new()
+                    .with_url(url)
+                    .with_auth(&create_cylinder_jwt_auth(Some(key))?)
+                    .build()?;
+
+                let full_service_id = matches
+                    .value_of("service-id")
+                    .ok_or_else(|| CliError::MissingArgument("service-id".into()))?;
+                let service_id = ServiceId::from_string(full_service_id)?;
+
+                let file = matches
+                    .value_of("file")
+                    .ok_or_else(|| CliError::MissingArgument("file".into()))?;
+
+                let snapshot =
+                    client.export_snapshot(&service_id, matches.value_of("state-root"))?;
+
+                let writer = BufWriter::new(File::create(file).map_err(|err| {
+                    CliError::action_error_with_source("failed to create file", err.into())
+                })?);
+                snapshot.write_to(writer).map_err(|err| {
+                    CliError::action_error_with_source("failed to write snapshot", err.into())
+                })?;
+
+                info!(
+                    "Exported {} entries at state root {} to {}",
+                    snapshot.entries().len(),
+                    snapshot.state_root(),
+                    file
+                );
+
+                Ok(())
+            }
+            #[cfg(feature = "snapshot")]
+            ("import", Some(matches)) => {
+                let url = matches.value_of("url").expect("default not set for --url");
+                let key = matches
+                    .value_of("key")
+                    .ok_or_else(|| CliError::MissingArgument("key".into()))?;
+
+                let client = ScabbardClientBuilder::new()
+                    .with_url(url)
+                    .with_auth(&create_cylinder_jwt_auth(Some(key))?)
+                    .build()?;
+
+                let full_service_id = matches
+                    .value_of("service-id")
+                    .ok_or_else(|| CliError::MissingArgument("service-id".into()))?;
+                let service_id = ServiceId::from_string(full_service_id)?;
+
+                let file = matches
+                    .value_of("file")
+                    .ok_or_else(|| CliError::MissingArgument("file".into()))?;
+
+                let reader = BufReader::new(File::open(file).map_err(|err| {
+                    CliError::action_error_with_source("failed to open file", err.into())
+                })?);
+                let snapshot = StateSnapshot::read_from(reader).map_err(|err| {
+                    CliError::action_error_with_source("failed to read snapshot", err.into())
+                })?;
+
+                client.import_snapshot(&service_id, &snapshot)?;
+
+                info!(
+                    "Imported {} entries at state root {}",
+                    snapshot.entries().len(),
+                    snapshot.state_root()
+                );
+
+                Ok(())
+            }
             _ => Err(CliError::InvalidSubcommand),
         },
         _ => Err(CliError::InvalidSubcommand),
@@ -27,6 +27,8 @@ use transact::{protocol::batch::Batch, protos::IntoBytes};
 
 use super::hex::parse_hex;
 use super::protocol::SCABBARD_PROTOCOL_VERSION;
+#[cfg(feature = "snapshot")]
+use super::snapshot::StateSnapshot;
 
 pub use builder::ScabbardClientBuilder;
 pub use error::ScabbardClientError;
@@ -247,6 +249,122 @@ impl ScabbardClient {
             )))
         }
     }
+
+    /// Export a snapshot of the state of the scabbard instance with the given `service_id`. If a
+    /// `state_root` is provided, the snapshot is taken at that state root; otherwise it is taken
+    /// at the current state root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The client's URL was invalid
+    /// * The given `state_root` is not a valid hex string
+    /// * The REST API request failed
+    /// * The given `state_root` does not exist
+    /// * An internal server error occurred in the scabbard service
+    #[cfg(feature = "snapshot")]
+    pub fn export_snapshot(
+        &self,
+        service_id: &ServiceId,
+        state_root: Option<&str>,
+    ) -> Result<StateSnapshot, ScabbardClientError> {
+        let mut url = Url::parse(&format!(
+            "{}/scabbard/{}/{}/snapshot/export",
+            &self.url,
+            service_id.circuit(),
+            service_id.service_id()
+        ))
+        .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
+        if let Some(state_root) = state_root {
+            parse_hex(state_root).map_err(|err| {
+                ScabbardClientError::new_with_source("invalid state root", err.into())
+            })?;
+            url.set_query(Some(&format!("state_root={}", state_root)))
+        }
+
+        let response = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| ScabbardClientError::new_with_source("request failed", err.into()))?;
+
+        if response.status().is_success() {
+            StateSnapshot::read_from(response).map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize response body",
+                    err.into(),
+                )
+            })
+        } else {
+            let status = response.status();
+            let msg: ErrorResponse = response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize error response body",
+                    err.into(),
+                )
+            })?;
+            Err(ScabbardClientError::new(&format!(
+                "failed to export snapshot: {}: {}",
+                status, msg
+            )))
+        }
+    }
+
+    /// Import the given `snapshot` into the state of the scabbard instance with the given
+    /// `service_id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The client's URL was invalid
+    /// * The REST API request failed
+    /// * The scabbard instance rejected the snapshot, because it has already committed
+    ///   transactions or the snapshot's entries do not match its state root
+    /// * An internal server error occurred in the scabbard service
+    #[cfg(feature = "snapshot")]
+    pub fn import_snapshot(
+        &self,
+        service_id: &ServiceId,
+        snapshot: &StateSnapshot,
+    ) -> Result<(), ScabbardClientError> {
+        let url = Url::parse(&format!(
+            "{}/scabbard/{}/{}/snapshot/import",
+            &self.url,
+            service_id.circuit(),
+            service_id.service_id()
+        ))
+        .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
+
+        let mut body = vec![];
+        snapshot.write_to(&mut body).map_err(|err| {
+            ScabbardClientError::new_with_source("failed to serialize snapshot", err.into())
+        })?;
+
+        let response = Client::new()
+            .post(url)
+            .body(body)
+            .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| ScabbardClientError::new_with_source("request failed", err.into()))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            let status = response.status();
+            let msg: ErrorResponse = response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize error response body",
+                    err.into(),
+                )
+            })?;
+            Err(ScabbardClientError::new(&format!(
+                "failed to import snapshot: {}: {}",
+                status, msg
+            )))
+        }
+    }
 }
 
 /// Using the given `base_url` and `batch_link` to check batch statuses, `wait` the given duration
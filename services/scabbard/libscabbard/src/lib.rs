@@ -27,3 +27,5 @@ mod hex;
 pub mod protocol;
 pub mod protos;
 pub mod service;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
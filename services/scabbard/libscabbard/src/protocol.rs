@@ -26,3 +26,5 @@ pub(crate) const SCABBARD_GET_STATE_PROTOCOL_MIN: u32 = 1;
 pub(crate) const SCABBARD_LIST_STATE_PROTOCOL_MIN: u32 = 1;
 #[cfg(all(feature = "rest-api", feature = "rest-api-actix"))]
 pub(crate) const SCABBARD_STATE_ROOT_PROTOCOL_MIN: u32 = 1;
+#[cfg(all(feature = "rest-api", feature = "rest-api-actix", feature = "snapshot"))]
+pub(crate) const SCABBARD_SNAPSHOT_PROTOCOL_MIN: u32 = 1;
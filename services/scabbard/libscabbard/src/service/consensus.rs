@@ -12,26 +12,122 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(any(feature = "coordinator-selection", feature = "pbft"))]
+use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
+#[cfg(feature = "consensus-log")]
+use std::path::Path;
+#[cfg(feature = "pbft")]
+use std::str::FromStr;
 use std::sync::mpsc::{channel, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread::{Builder, JoinHandle};
 use std::time::Duration;
 
+#[cfg(feature = "pbft")]
+use cylinder::{secp256k1::Secp256k1Context, Context, PublicKey, Signer};
 use protobuf::Message;
+#[cfg(feature = "pbft")]
+use splinter::consensus::pbft::PbftEngine;
+#[cfg(feature = "coordinator-selection")]
+use splinter::consensus::two_phase::coordinator::{
+    CoordinatorPolicy, LowestIdPolicy, RoundRobinPolicy, WeightedPolicy,
+};
+#[cfg(feature = "consensus-log")]
+use splinter::consensus::two_phase::log::FileTwoPhaseLog;
 use splinter::consensus::{
     error::{ConsensusSendError, ProposalManagerError},
     two_phase::TwoPhaseEngine,
     ConsensusEngine, ConsensusMessage, ConsensusNetworkSender, PeerId, Proposal, ProposalId,
     ProposalManager, ProposalUpdate, StartupState,
 };
+use transact::protocol::batch::BatchPair;
 use transact::protos::IntoBytes;
 
 use crate::protos::scabbard::{ProposedBatch, ScabbardMessage, ScabbardMessage_Type};
 
+#[cfg(feature = "consensus-log")]
+use super::error::ScabbardStateError;
 use super::error::{ScabbardConsensusManagerError, ScabbardError};
 use super::shared::ScabbardShared;
 use super::state::ScabbardState;
+#[cfg(feature = "state-sync")]
+use super::sync;
+
+/// The consensus algorithms that a scabbard service can use to agree on batches with its peers
+#[cfg(feature = "pbft")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConsensusAlgorithm {
+    /// Two-phase commit, which requires every service to be available and trusts all of them
+    TwoPhaseCommit,
+    /// Practical Byzantine Fault Tolerance, which tolerates `f` faulty services out of `3f + 1`
+    Pbft,
+}
+
+#[cfg(feature = "pbft")]
+impl FromStr for ConsensusAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "two-phase" => Ok(ConsensusAlgorithm::TwoPhaseCommit),
+            "pbft" => Ok(ConsensusAlgorithm::Pbft),
+            _ => Err(format!(
+                "unknown consensus algorithm {}; expected \"two-phase\" or \"pbft\"",
+                s
+            )),
+        }
+    }
+}
+
+/// The keys that scabbard services sign their PBFT consensus messages with and check each
+/// other's messages against
+#[cfg(feature = "pbft")]
+pub struct PbftKeys {
+    /// Signs this service's consensus messages
+    pub signer: Box<dyn Signer>,
+    /// The public key of each service on the circuit, including this one, keyed by service ID
+    pub public_keys: HashMap<String, PublicKey>,
+}
+
+#[cfg(feature = "pbft")]
+impl Clone for PbftKeys {
+    fn clone(&self) -> Self {
+        PbftKeys {
+            signer: self.signer.clone_box(),
+            public_keys: self.public_keys.clone(),
+        }
+    }
+}
+
+/// How the coordinator of each proposal is chosen when scabbard services use two-phase commit
+#[cfg(feature = "coordinator-selection")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum CoordinatorSelection {
+    /// The service with the lowest ID coordinates every proposal
+    LowestId,
+    /// The services take turns coordinating proposals
+    RoundRobin,
+    /// The services coordinate proposals in proportion to their weights, which are keyed by
+    /// service ID; services without a weight have a weight of 1
+    Weighted(HashMap<String, u32>),
+}
+
+#[cfg(feature = "coordinator-selection")]
+impl CoordinatorSelection {
+    fn into_policy(self) -> Box<dyn CoordinatorPolicy> {
+        match self {
+            CoordinatorSelection::LowestId => Box::new(LowestIdPolicy),
+            CoordinatorSelection::RoundRobin => Box::new(RoundRobinPolicy),
+            CoordinatorSelection::Weighted(weights) => Box::new(WeightedPolicy::new(
+                weights
+                    .into_iter()
+                    .map(|(service_id, weight)| (service_id.as_bytes().into(), weight))
+                    .collect(),
+            )),
+        }
+    }
+}
 
 /// Component used by the service to manage and interact with consenus
 pub struct ScabbardConsensusManager {
@@ -49,6 +145,14 @@ impl ScabbardConsensusManager {
         state: Arc<Mutex<ScabbardState>>,
         // The coordinator timeout for the two-phase commit consensus engine
         coordinator_timeout: Duration,
+        // The file in which the two-phase commit consensus engine records its progress, so it can
+        // recover any in-progress proposal after a restart
+        #[cfg(feature = "consensus-log")] consensus_log_path: &Path,
+        #[cfg(feature = "pbft")] consensus_algorithm: ConsensusAlgorithm,
+        // The keys the PBFT consensus engine signs and checks messages with; required with PBFT
+        #[cfg(feature = "pbft")] pbft_keys: Option<PbftKeys>,
+        // How the two-phase commit consensus engine chooses the coordinator of each proposal
+        #[cfg(feature = "coordinator-selection")] coordinator_selection: CoordinatorSelection,
     ) -> Result<Self, ScabbardConsensusManagerError> {
         let peer_ids = shared
             .lock()
@@ -75,18 +179,61 @@ impl ScabbardConsensusManager {
             last_proposal: None,
         };
 
+        #[cfg(feature = "consensus-log")]
+        let consensus_log = FileTwoPhaseLog::new(consensus_log_path)
+            .map_err(|err| ScabbardConsensusManagerError(Box::new(err)))?;
+
+        // The coordinator timeout doubles as the view change timeout
+        #[cfg(feature = "pbft")]
+        let pbft_engine = match (consensus_algorithm, pbft_keys) {
+            (ConsensusAlgorithm::TwoPhaseCommit, _) => None,
+            (ConsensusAlgorithm::Pbft, Some(pbft_keys)) => Some(PbftEngine::new(
+                coordinator_timeout,
+                pbft_keys.signer,
+                Secp256k1Context::new().new_verifier(),
+                pbft_keys
+                    .public_keys
+                    .into_iter()
+                    .map(|(service_id, public_key)| (service_id.as_bytes().into(), public_key))
+                    .collect(),
+            )),
+            (ConsensusAlgorithm::Pbft, None) => {
+                let err: Box<dyn std::error::Error + Send + Sync> =
+                    "PBFT consensus requires signing keys".into();
+                return Err(ScabbardConsensusManagerError(Box::new(
+                    ScabbardError::InitializationFailed(err),
+                )));
+            }
+        };
+
         let thread_handle = Builder::new()
             .name(format!("consensus-{}", service_id))
             .spawn(move || {
-                let mut two_phase_engine = TwoPhaseEngine::new(coordinator_timeout);
-                if let Err(err) = two_phase_engine.run(
+                #[cfg(feature = "consensus-log")]
+                let two_phase_engine =
+                    TwoPhaseEngine::new(coordinator_timeout).with_log(Box::new(consensus_log));
+                #[cfg(not(feature = "consensus-log"))]
+                let two_phase_engine = TwoPhaseEngine::new(coordinator_timeout);
+                #[cfg(feature = "coordinator-selection")]
+                let two_phase_engine =
+                    two_phase_engine.with_coordinator_policy(coordinator_selection.into_policy());
+
+                #[cfg(feature = "pbft")]
+                let mut engine: Box<dyn ConsensusEngine> = match pbft_engine {
+                    Some(pbft_engine) => Box::new(pbft_engine),
+                    None => Box::new(two_phase_engine),
+                };
+                #[cfg(not(feature = "pbft"))]
+                let mut engine = two_phase_engine;
+
+                if let Err(err) = engine.run(
                     consensus_msg_rx,
                     proposal_update_rx,
                     Box::new(consensus_network_sender),
                     Box::new(proposal_manager),
                     startup_state,
                 ) {
-                    error!("two phase consensus exited with an error: {}", err)
+                    error!("{} consensus exited with an error: {}", engine.name(), err)
                 }
             })
             .map_err(|err| ScabbardConsensusManagerError(Box::new(err)))?;
@@ -151,6 +298,27 @@ impl ScabbardProposalManager {
             state,
         }
     }
+
+    /// Discard the changes from a proposal that this service voted against, and ask all peers for
+    /// the changes that this service is missing.
+    #[cfg(feature = "state-sync")]
+    fn request_sync(&self) {
+        let result = match (self.shared.lock(), self.state.lock()) {
+            (Ok(mut shared), Ok(mut state)) => {
+                // The proposal can't be accepted now that this service has voted against it, and
+                // pending changes would prevent this service from catching up
+                state.rollback().map_err(ScabbardError::from).and_then(|_| {
+                    let peers = shared.peer_services().iter().cloned().collect::<Vec<_>>();
+                    sync::request_sync(&self.service_id, &peers, &mut shared, &state, false)
+                })
+            }
+            _ => Err(ScabbardError::LockPoisoned),
+        };
+
+        if let Err(err) = result {
+            error!("Unable to request sync from peers: {}", err);
+        }
+    }
 }
 
 impl ProposalManager for ScabbardProposalManager {
@@ -166,88 +334,177 @@ impl ProposalManager for ScabbardProposalManager {
             .lock()
             .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?;
 
-        if let Some(batch) = shared.pop_batch_from_queue() {
-            let expected_hash = self
-                .state
-                .lock()
-                .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?
-                .prepare_change(batch.clone())
-                .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
+        // Batches stay in the queue until this service has caught up with its peers
+        #[cfg(feature = "state-sync")]
+        {
+            if shared.is_syncing() {
+                debug!("Not proposing a batch while catching up with peers");
+                self.proposal_update_sender
+                    .send(ProposalUpdate::ProposalCreated(None))?;
+                return Ok(());
+            }
+        }
 
-            // Intentionally leaving out the previous_id and proposal_height fields, since this
-            // service and two phase consensus don't use them. This means the proposal ID can just
-            // be the summary.
-            let proposal = Proposal {
-                id: expected_hash.as_bytes().into(),
-                summary: expected_hash.as_bytes().into(),
-                ..Default::default()
-            };
-
-            shared.add_proposed_batch(proposal.id.clone(), batch.clone());
-
-            // Send the proposal to the other services
-            let mut proposed_batch = ProposedBatch::new();
-            proposed_batch.set_proposal(
-                proposal
-                    .clone()
-                    .try_into()
-                    .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?,
-            );
-            proposed_batch.set_batch(
-                batch
-                    .into_bytes()
-                    .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?,
-            );
-            proposed_batch.set_service_id(self.service_id.clone());
+        #[cfg(feature = "multi-batch-proposals")]
+        let batches = shared.pop_proposal_batches();
+        #[cfg(not(feature = "multi-batch-proposals"))]
+        let batches = shared
+            .pop_batch_from_queue()
+            .into_iter()
+            .collect::<Vec<_>>();
 
-            let mut msg = ScabbardMessage::new();
-            msg.set_message_type(ScabbardMessage_Type::PROPOSED_BATCH);
-            msg.set_proposed_batch(proposed_batch);
-            let msg_bytes = msg
-                .write_to_bytes()
-                .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
+        if batches.is_empty() {
+            self.proposal_update_sender
+                .send(ProposalUpdate::ProposalCreated(None))?;
+            return Ok(());
+        }
+
+        #[cfg(feature = "coordinator-selection")]
+        let proposal_height;
+        let (expected_hash, batches) = {
+            let mut state = self.state.lock().map_err(|_| {
+                ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned))
+            })?;
+
+            // The proposal height is derived from the committed proposals, which are persisted
+            // with the state, so that turns to coordinate continue across restarts
+            #[cfg(feature = "coordinator-selection")]
+            {
+                proposal_height = state.committed_proposals() + 1;
+            }
 
-            let sender = shared
-                .network_sender()
-                .ok_or(ProposalManagerError::NotReady)?;
+            let (expected_hash, valid_batches) = state
+                .prepare_changes(batches)
+                .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
 
-            for service in shared.peer_services() {
-                sender
-                    .send(service, msg_bytes.as_slice())
+            // Invalid batches are not proposed; their results are reported in the batch history
+            if valid_batches.is_empty() {
+                state
+                    .rollback()
                     .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
+                self.proposal_update_sender
+                    .send(ProposalUpdate::ProposalCreated(None))?;
+                return Ok(());
             }
 
-            self.proposal_update_sender
-                .send(ProposalUpdate::ProposalCreated(Some(proposal)))?;
+            (expected_hash, valid_batches)
+        };
+
+        // Intentionally leaving out the previous_id field, since this service and two phase
+        // consensus don't use it. This means the proposal ID can just be the summary. The
+        // proposal height is only set when two phase consensus uses it to choose the coordinator.
+        let proposal = Proposal {
+            id: expected_hash.as_bytes().into(),
+            summary: expected_hash.as_bytes().into(),
+            #[cfg(feature = "coordinator-selection")]
+            proposal_height,
+            ..Default::default()
+        };
+
+        // Send the proposal to the other services
+        let mut proposed_batch = ProposedBatch::new();
+        proposed_batch.set_proposal(
+            proposal
+                .clone()
+                .try_into()
+                .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?,
+        );
+        let mut batches_bytes = batches
+            .iter()
+            .cloned()
+            .map(BatchPair::into_bytes)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
+        // Single-batch proposals use the `batch` field, so services that only support single-batch
+        // proposals can still evaluate them
+        if batches_bytes.len() == 1 {
+            proposed_batch.set_batch(batches_bytes.remove(0));
         } else {
-            self.proposal_update_sender
-                .send(ProposalUpdate::ProposalCreated(None))?;
+            proposed_batch.set_batches(batches_bytes.into());
+        }
+        proposed_batch.set_service_id(self.service_id.clone());
+
+        shared.add_proposed_batches(proposal.id.clone(), batches);
+
+        let mut msg = ScabbardMessage::new();
+        msg.set_message_type(ScabbardMessage_Type::PROPOSED_BATCH);
+        msg.set_proposed_batch(proposed_batch);
+        let msg_bytes = msg
+            .write_to_bytes()
+            .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
+
+        let sender = shared
+            .network_sender()
+            .ok_or(ProposalManagerError::NotReady)?;
+
+        for service in shared.peer_services() {
+            sender
+                .send(service, msg_bytes.as_slice())
+                .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
         }
 
+        self.proposal_update_sender
+            .send(ProposalUpdate::ProposalCreated(Some(proposal)))?;
+
         Ok(())
     }
 
     fn check_proposal(&self, id: &ProposalId) -> Result<(), ProposalManagerError> {
-        let batch = self
-            .shared
-            .lock()
-            .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?
-            .get_proposed_batch(id)
-            .ok_or_else(|| ProposalManagerError::UnknownProposal(id.clone()))?
-            .clone();
+        let batches = {
+            let shared = self.shared.lock().map_err(|_| {
+                ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned))
+            })?;
+
+            // The proposal can't be verified until this service has caught up with its peers
+            #[cfg(feature = "state-sync")]
+            {
+                if shared.is_syncing() {
+                    warn!(
+                        "Unable to verify proposal {} while catching up with peers",
+                        id
+                    );
+                    self.proposal_update_sender
+                        .send(ProposalUpdate::ProposalInvalid(id.clone()))?;
+                    return Ok(());
+                }
+            }
+
+            shared
+                .get_proposed_batches(id)
+                .ok_or_else(|| ProposalManagerError::UnknownProposal(id.clone()))?
+                .to_vec()
+        };
+        let batch_count = batches.len();
 
-        let hash = self
+        let (hash, valid_batches) = self
             .state
             .lock()
             .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?
-            .prepare_change(batch)
+            .prepare_changes(batches)
             .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
 
-        if hash.as_bytes() != id.as_ref() {
+        let valid = if valid_batches.len() != batch_count {
+            warn!(
+                "Proposal {} contains {} invalid batch(es)",
+                id,
+                batch_count - valid_batches.len()
+            );
+            false
+        } else if hash.as_bytes() != id.as_ref() {
             warn!("Hash mismatch: expected {} but was {}", id, hash);
+            false
+        } else {
+            true
+        };
 
+        if !valid {
             self.proposal_update_sender
                 .send(ProposalUpdate::ProposalInvalid(id.clone()))?;
+
+            // An invalid proposal is usually caused by this service missing changes that its peers
+            // have committed
+            #[cfg(feature = "state-sync")]
+            self.request_sync();
         } else {
             self.proposal_update_sender
                 .send(ProposalUpdate::ProposalValid(id.clone()))?;
@@ -269,7 +526,7 @@ impl ProposalManager for ScabbardProposalManager {
             .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?;
 
         shared
-            .remove_proposed_batch(id)
+            .remove_proposed_batches(id)
             .ok_or_else(|| ProposalManagerError::UnknownProposal(id.clone()))?;
 
         self.state
@@ -286,6 +543,76 @@ impl ProposalManager for ScabbardProposalManager {
         Ok(())
     }
 
+    /// The proposed batches are recorded when the consensus log is enabled, so they are executed
+    /// again and committed. If the batches are not available, this service catches up with its
+    /// peers instead when state sync is enabled.
+    #[cfg(feature = "consensus-log")]
+    fn reapply_proposal(&self, id: &ProposalId) -> Result<(), ProposalManagerError> {
+        let batches = {
+            let mut shared = self.shared.lock().map_err(|_| {
+                ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned))
+            })?;
+
+            // The proposal ID is the state root that results from committing it, so the proposal
+            // was already committed if that is the current state root
+            let committed = self
+                .state
+                .lock()
+                .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?
+                .current_state_root()
+                .as_bytes()
+                == id.as_ref();
+            if committed {
+                shared.remove_proposed_batches(id);
+                info!("Proposal {} was committed before restart", id);
+                return Ok(());
+            }
+
+            shared.get_proposed_batches(id).map(<[BatchPair]>::to_vec)
+        };
+
+        let batches = match batches {
+            Some(batches) => batches,
+            #[cfg(feature = "state-sync")]
+            None => {
+                warn!(
+                    "Batches of proposal {} are not available; catching up with peers",
+                    id
+                );
+                self.request_sync();
+                return Ok(());
+            }
+            #[cfg(not(feature = "state-sync"))]
+            None => return Err(ProposalManagerError::UnknownProposal(id.clone())),
+        };
+
+        let hash = {
+            let mut state = self.state.lock().map_err(|_| {
+                ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned))
+            })?;
+            // Discard any changes prepared since the restart, since the proposal must be applied
+            // on top of the current state root
+            state
+                .rollback()
+                .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
+            state
+                .prepare_changes(batches)
+                .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?
+                .0
+        };
+
+        if hash.as_bytes() != id.as_ref() {
+            return Err(ProposalManagerError::Internal(Box::new(
+                ScabbardStateError(format!(
+                    "re-applying proposal {} resulted in state root {}",
+                    id, hash
+                )),
+            )));
+        }
+
+        self.accept_proposal(id, None)
+    }
+
     fn reject_proposal(&self, id: &ProposalId) -> Result<(), ProposalManagerError> {
         let mut shared = self
             .shared
@@ -293,7 +620,7 @@ impl ProposalManager for ScabbardProposalManager {
             .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?;
 
         shared
-            .remove_proposed_batch(id)
+            .remove_proposed_batches(id)
             .ok_or_else(|| ProposalManagerError::UnknownProposal(id.clone()))?;
 
         self.state
@@ -306,6 +633,17 @@ impl ProposalManager for ScabbardProposalManager {
 
         Ok(())
     }
+
+    #[cfg(all(feature = "pbft", feature = "state-sync"))]
+    fn sync_state(&self) -> Result<bool, ProposalManagerError> {
+        self.shared
+            .lock()
+            .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?
+            .notify_when_synced(self.proposal_update_sender.clone());
+        self.request_sync();
+
+        Ok(true)
+    }
 }
 
 pub struct ScabbardConsensusNetworkSender {
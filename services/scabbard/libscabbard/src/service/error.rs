@@ -30,7 +30,11 @@ pub enum ScabbardError {
     LockPoisoned,
     MessageTypeUnset,
     NotConnected,
+    #[cfg(feature = "snapshot")]
+    SnapshotRejected(String),
     StateInteractionFailed(ScabbardStateError),
+    #[cfg(feature = "state-sync")]
+    SyncFailed(Box<dyn Error + Send>),
 }
 
 impl Error for ScabbardError {
@@ -42,7 +46,11 @@ impl Error for ScabbardError {
             ScabbardError::LockPoisoned => None,
             ScabbardError::MessageTypeUnset => None,
             ScabbardError::NotConnected => None,
+            #[cfg(feature = "snapshot")]
+            ScabbardError::SnapshotRejected(_) => None,
             ScabbardError::StateInteractionFailed(err) => Some(err),
+            #[cfg(feature = "state-sync")]
+            ScabbardError::SyncFailed(err) => Some(&**err),
         }
     }
 }
@@ -62,9 +70,13 @@ impl std::fmt::Display for ScabbardError {
             ScabbardError::NotConnected => {
                 write!(f, "attempted to send message, but service isn't connected")
             }
+            #[cfg(feature = "snapshot")]
+            ScabbardError::SnapshotRejected(msg) => write!(f, "snapshot rejected: {}", msg),
             ScabbardError::StateInteractionFailed(err) => {
                 write!(f, "interaction with scabbard state failed: {}", err)
             }
+            #[cfg(feature = "state-sync")]
+            ScabbardError::SyncFailed(err) => write!(f, "failed to sync with peers: {}", err),
         }
     }
 }
@@ -13,17 +13,31 @@
 // limitations under the License.
 
 use std::collections::{HashMap, HashSet};
+#[cfg(feature = "pbft")]
+use std::error::Error;
+#[cfg(feature = "pbft")]
+use std::iter::once;
 use std::path::Path;
 use std::time::Duration;
 
 use cylinder::VerifierFactory;
+#[cfg(feature = "pbft")]
+use cylinder::{PublicKey, Signer};
+#[cfg(feature = "metrics")]
+use splinter::metrics::MetricsRegistry;
 #[cfg(feature = "service-arg-validation")]
 use splinter::service::validation::{ServiceArgValidationError, ServiceArgValidator};
 use splinter::service::{FactoryCreateError, Service, ServiceFactory};
 
-#[cfg(feature = "service-arg-validation")]
+#[cfg(any(feature = "pbft", feature = "service-arg-validation"))]
 use crate::hex::parse_hex;
 
+#[cfg(feature = "multi-batch-proposals")]
+use super::shared::{DEFAULT_MAX_PROPOSAL_AGE, DEFAULT_MAX_PROPOSAL_BATCHES};
+#[cfg(feature = "coordinator-selection")]
+use super::CoordinatorSelection;
+#[cfg(feature = "pbft")]
+use super::{ConsensusAlgorithm, PbftKeys};
 use super::{Scabbard, SERVICE_TYPE};
 
 const DEFAULT_STATE_DB_DIR: &str = "/var/lib/splinter";
@@ -38,6 +52,10 @@ pub struct ScabbardFactory {
     receipt_db_dir: String,
     receipt_db_size: usize,
     signature_verifier_factory: Box<dyn VerifierFactory>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<MetricsRegistry>,
+    #[cfg(feature = "pbft")]
+    signer: Option<Box<dyn Signer>>,
 }
 
 impl ScabbardFactory {
@@ -55,8 +73,27 @@ impl ScabbardFactory {
             receipt_db_dir: receipt_db_dir.unwrap_or_else(|| DEFAULT_RECEIPT_DB_DIR.into()),
             receipt_db_size: receipt_db_size.unwrap_or(DEFAULT_RECEIPT_DB_SIZE),
             signature_verifier_factory,
+            #[cfg(feature = "metrics")]
+            metrics: None,
+            #[cfg(feature = "pbft")]
+            signer: None,
         }
     }
+
+    /// Set the metrics registry that the services created by this factory will report to.
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: MetricsRegistry) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Set the node's signer, which the services created by this factory sign their PBFT
+    /// consensus messages with; required to create services that use PBFT.
+    #[cfg(feature = "pbft")]
+    pub fn with_signer(mut self, signer: Box<dyn Signer>) -> Self {
+        self.signer = Some(signer);
+        self
+    }
 }
 
 #[cfg(feature = "service-arg-validation")]
@@ -96,6 +133,26 @@ impl ServiceArgValidator for ScabbardArgValidator {
             }
         }
 
+        #[cfg(feature = "pbft")]
+        {
+            if let Some(consensus) = args.get("consensus") {
+                consensus
+                    .parse::<ConsensusAlgorithm>()
+                    .map_err(ServiceArgValidationError)?;
+            }
+            parse_pbft_public_keys(args).map_err(ServiceArgValidationError)?;
+        }
+
+        #[cfg(feature = "multi-batch-proposals")]
+        {
+            parse_proposal_limits(args).map_err(ServiceArgValidationError)?;
+        }
+
+        #[cfg(feature = "coordinator-selection")]
+        {
+            parse_coordinator_selection(args).map_err(ServiceArgValidationError)?;
+        }
+
         Ok(())
     }
 }
@@ -115,6 +172,27 @@ impl ServiceFactory for ScabbardFactory {
     /// - `coordinator_timeout`: the length of time (in milliseconds) that the network has to
     ///   commit a proposal before the coordinator rejects it (if not provided, default is 30
     ///   seconds)
+    /// - `consensus`: the consensus algorithm to use, either `two-phase` or `pbft` (if not
+    ///   provided, default is `two-phase`); with `pbft`, `coordinator_timeout` is the length of
+    ///   time the network has to make progress before a new primary is chosen (requires the `pbft`
+    ///   feature)
+    /// - `pbft_public_keys`: the public key of each service on the circuit, including this one,
+    ///   formatted as a serialized JSON object of service IDs to hex-encoded public keys; each
+    ///   service signs its consensus messages with its node's key, so this service's key must be
+    ///   the public key of the factory's signer (required with `pbft` consensus; requires the
+    ///   `pbft` feature)
+    /// - `max_proposal_batches`: the maximum number of queued batches to propose at once (if not
+    ///   provided, default is 100; requires the `multi-batch-proposals` feature)
+    /// - `max_proposal_age`: the length of time (in milliseconds) that a queued batch waits for
+    ///   more batches to be queued before it is proposed (if not provided, default is 0; requires
+    ///   the `multi-batch-proposals` feature)
+    /// - `coordinator_selection`: how the two-phase commit coordinator of each proposal is chosen,
+    ///   either `lowest-id`, `round-robin`, or `weighted` (if not provided, default is
+    ///   `lowest-id`); if a coordinator times out, the next service chosen takes over (requires
+    ///   the `coordinator-selection` feature)
+    /// - `coordinator_weights`: the weight of each service with `weighted` coordinator selection,
+    ///   formatted as a serialized JSON object of service IDs to integers; services that are not
+    ///   listed have a weight of 1 (requires the `coordinator-selection` feature)
     fn create(
         &self,
         service_id: String,
@@ -157,6 +235,67 @@ impl ServiceFactory for ScabbardFactory {
             })
             .transpose()?;
 
+        #[cfg(feature = "pbft")]
+        let consensus_algorithm = args
+            .get("consensus")
+            .map(|consensus| {
+                consensus
+                    .parse::<ConsensusAlgorithm>()
+                    .map_err(FactoryCreateError::InvalidArguments)
+            })
+            .transpose()?;
+
+        #[cfg(feature = "pbft")]
+        let pbft_keys =
+            match parse_pbft_public_keys(&args).map_err(FactoryCreateError::InvalidArguments)? {
+                Some(public_keys) => {
+                    if let Some(service) = peer_services
+                        .iter()
+                        .chain(once(&service_id))
+                        .find(|service| !public_keys.contains_key(*service))
+                    {
+                        return Err(FactoryCreateError::InvalidArguments(format!(
+                            "pbft_public_keys has no key for service {}",
+                            service
+                        )));
+                    }
+
+                    let signer = match self.signer.as_ref() {
+                        Some(signer) => signer.clone_box(),
+                        None => {
+                            let err: Box<dyn Error + Send + Sync> =
+                                "no signer is available for PBFT consensus".into();
+                            return Err(FactoryCreateError::CreationFailed(err));
+                        }
+                    };
+                    let signer_key = signer
+                        .public_key()
+                        .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))?;
+                    if Some(signer_key.as_slice())
+                        != public_keys.get(&service_id).map(PublicKey::as_slice)
+                    {
+                        return Err(FactoryCreateError::InvalidArguments(format!(
+                            "pbft_public_keys does not have this node's key for service {}",
+                            service_id
+                        )));
+                    }
+
+                    Some(PbftKeys {
+                        signer,
+                        public_keys,
+                    })
+                }
+                None => None,
+            };
+
+        #[cfg(feature = "coordinator-selection")]
+        let coordinator_selection =
+            parse_coordinator_selection(&args).map_err(FactoryCreateError::InvalidArguments)?;
+
+        #[cfg(feature = "multi-batch-proposals")]
+        let (max_proposal_batches, max_proposal_age) =
+            parse_proposal_limits(&args).map_err(FactoryCreateError::InvalidArguments)?;
+
         let service = Scabbard::new(
             service_id,
             circuit_id,
@@ -171,6 +310,39 @@ impl ServiceFactory for ScabbardFactory {
         )
         .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))?;
 
+        #[cfg(feature = "pbft")]
+        let service = match consensus_algorithm {
+            Some(consensus_algorithm) => service.with_consensus_algorithm(consensus_algorithm),
+            None => service,
+        };
+
+        #[cfg(feature = "pbft")]
+        let service = match pbft_keys {
+            Some(pbft_keys) => service.with_pbft_keys(pbft_keys),
+            None => service,
+        };
+
+        #[cfg(feature = "coordinator-selection")]
+        let service = match coordinator_selection {
+            Some(coordinator_selection) => {
+                service.with_coordinator_selection(coordinator_selection)
+            }
+            None => service,
+        };
+
+        #[cfg(feature = "multi-batch-proposals")]
+        let service = service
+            .with_proposal_limits(max_proposal_batches, max_proposal_age)
+            .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))?;
+
+        #[cfg(feature = "metrics")]
+        let service = match self.metrics.as_ref() {
+            Some(metrics) => service
+                .with_metrics(metrics.clone())
+                .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))?,
+            None => service,
+        };
+
         Ok(Box::new(service))
     }
 
@@ -184,6 +356,10 @@ impl ServiceFactory for ScabbardFactory {
     /// * `GET /state/{address}` - Get a value from scabbard's state
     /// * `GET /state` - Get multiple scabbard state entries
     /// * `GET /state_root` - Get the current state root hash of scabbard's state
+    /// * `GET /snapshot/export` - Export a snapshot of scabbard's state (requires the `snapshot`
+    ///   feature)
+    /// * `POST /snapshot/import` - Import a snapshot into scabbard's state (requires the `snapshot`
+    ///   feature)
     ///
     /// These endpoints are only available if the following REST API backend feature is enabled:
     ///
@@ -206,18 +382,123 @@ impl ServiceFactory for ScabbardFactory {
                 actix::state_address::make_get_state_at_address_endpoint(),
                 actix::state::make_get_state_with_prefix_endpoint(),
                 actix::state_root::make_get_state_root_endpoint(),
-            ])
+            ]);
+
+            #[cfg(feature = "snapshot")]
+            endpoints.append(&mut vec![
+                actix::snapshot::make_export_snapshot_endpoint(),
+                actix::snapshot::make_import_snapshot_endpoint(),
+            ]);
         }
 
         endpoints
     }
 }
 
+/// Parse the optional `max_proposal_batches` and `max_proposal_age` service arguments, using the
+/// default limits for any that aren't provided.
+#[cfg(feature = "multi-batch-proposals")]
+fn parse_proposal_limits(args: &HashMap<String, String>) -> Result<(usize, Duration), String> {
+    let max_batches = match args.get("max_proposal_batches") {
+        Some(max_batches) => match max_batches.parse::<usize>() {
+            Ok(0) => return Err("invalid max_proposal_batches: must be at least 1".into()),
+            Ok(max_batches) => max_batches,
+            Err(err) => return Err(format!("invalid max_proposal_batches: {}", err)),
+        },
+        None => DEFAULT_MAX_PROPOSAL_BATCHES,
+    };
+
+    let max_age = match args.get("max_proposal_age") {
+        Some(max_age) => Duration::from_millis(
+            max_age
+                .parse::<u64>()
+                .map_err(|err| format!("invalid max_proposal_age: {}", err))?,
+        ),
+        None => DEFAULT_MAX_PROPOSAL_AGE,
+    };
+
+    Ok((max_batches, max_age))
+}
+
+/// Parse the `pbft_public_keys` service argument, which must be provided with `pbft` consensus
+/// and only then.
+#[cfg(feature = "pbft")]
+fn parse_pbft_public_keys(
+    args: &HashMap<String, String>,
+) -> Result<Option<HashMap<String, PublicKey>>, String> {
+    let public_keys = match (
+        args.get("consensus").map(String::as_str),
+        args.get("pbft_public_keys"),
+    ) {
+        (Some("pbft"), Some(public_keys)) => public_keys,
+        (Some("pbft"), None) => return Err("pbft_public_keys argument not provided".into()),
+        (_, Some(_)) => {
+            return Err("pbft_public_keys can only be provided with \"pbft\" consensus".into())
+        }
+        (_, None) => return Ok(None),
+    };
+
+    serde_json::from_str::<HashMap<String, String>>(public_keys)
+        .map_err(|err| format!("failed to parse pbft_public_keys: {}", err))?
+        .into_iter()
+        .map(|(service_id, key)| {
+            let key_bytes = parse_hex(&key)
+                .map_err(|_| format!("{} is not a valid hex-formatted public key", key))?;
+            if key_bytes.len() != 33 {
+                return Err(format!("{} is not a valid public key: invalid length", key));
+            }
+            Ok((service_id, PublicKey::new(key_bytes)))
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+}
+
+/// Parse the optional `coordinator_selection` and `coordinator_weights` service arguments.
+/// Weights may only be given with `weighted` coordinator selection.
+#[cfg(feature = "coordinator-selection")]
+fn parse_coordinator_selection(
+    args: &HashMap<String, String>,
+) -> Result<Option<CoordinatorSelection>, String> {
+    let weights = args
+        .get("coordinator_weights")
+        .map(|weights| {
+            serde_json::from_str::<HashMap<String, u32>>(weights)
+                .map_err(|err| format!("failed to parse coordinator_weights: {}", err))
+        })
+        .transpose()?;
+
+    match (
+        args.get("coordinator_selection").map(String::as_str),
+        weights,
+    ) {
+        (None, None) => Ok(None),
+        (Some("lowest-id"), None) => Ok(Some(CoordinatorSelection::LowestId)),
+        (Some("round-robin"), None) => Ok(Some(CoordinatorSelection::RoundRobin)),
+        (Some("weighted"), weights) => Ok(Some(CoordinatorSelection::Weighted(
+            weights.unwrap_or_default(),
+        ))),
+        (Some(selection), None) => Err(format!(
+            "unknown coordinator_selection {}; expected \"lowest-id\", \"round-robin\", or \
+             \"weighted\"",
+            selection
+        )),
+        (_, Some(_)) => Err(
+            "coordinator_weights can only be provided with \"weighted\" coordinator_selection"
+                .into(),
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use cylinder::secp256k1::Secp256k1Context;
+    #[cfg(feature = "pbft")]
+    use cylinder::Context;
+
+    #[cfg(feature = "pbft")]
+    use crate::hex::to_hex;
 
     /// Verify that the scabbard factory produces a valid `Scabbard` instance.
     #[test]
@@ -256,6 +537,149 @@ mod tests {
         assert_eq!(scabbard.coordinator_timeout, Duration::from_millis(123));
     }
 
+    /// Verify that the `consensus` service argument is properly set for a new `Scabbard` instance,
+    /// that PBFT requires a signer and the public key of every service, and that creation fails
+    /// when the consensus algorithm is unknown.
+    #[cfg(feature = "pbft")]
+    #[test]
+    fn create_with_consensus() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let node_key = to_hex(
+            signer
+                .public_key()
+                .expect("failed to get public key")
+                .as_slice(),
+        );
+        let peer_key = to_hex(
+            context
+                .new_signer(context.new_random_private_key())
+                .public_key()
+                .expect("failed to get public key")
+                .as_slice(),
+        );
+        let mut public_keys = HashMap::new();
+        public_keys.insert("".to_string(), node_key);
+        public_keys.insert("1".to_string(), peer_key.clone());
+        public_keys.insert("2".to_string(), peer_key.clone());
+        let pbft_public_keys = |keys: &HashMap<String, String>| {
+            serde_json::to_string(keys).expect("failed to serialize pbft_public_keys")
+        };
+
+        // The key of service "3" is missing
+        let factory = get_factory().with_signer(signer);
+        let mut args = get_mock_args();
+        args.insert("consensus".into(), "pbft".into());
+        args.insert("pbft_public_keys".into(), pbft_public_keys(&public_keys));
+        assert!(
+            factory.create("".into(), "", "", args).is_err(),
+            "Creating service without the public key of every peer did not fail"
+        );
+
+        public_keys.insert("3".to_string(), peer_key);
+        let mut args = get_mock_args();
+        args.insert("consensus".into(), "pbft".into());
+        args.insert("pbft_public_keys".into(), pbft_public_keys(&public_keys));
+        assert!(
+            get_factory().create("".into(), "", "", args).is_err(),
+            "Creating PBFT service without a signer did not fail"
+        );
+
+        let mut args = get_mock_args();
+        args.insert("consensus".into(), "pbft".into());
+        args.insert("pbft_public_keys".into(), pbft_public_keys(&public_keys));
+
+        let service = factory
+            .create("".into(), "", "", args)
+            .expect("failed to create service");
+        let scabbard = (&*service)
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .expect("failed to downcast Service to Scabbard");
+
+        assert_eq!(scabbard.consensus_algorithm, ConsensusAlgorithm::Pbft);
+
+        let mut args = get_mock_args();
+        args.insert("consensus".into(), "raft".into());
+
+        assert!(
+            factory.create("".into(), "", "", args).is_err(),
+            "Creating factory with an unknown consensus algorithm did not fail"
+        );
+    }
+
+    /// Verify that a `Scabbard` instance is created with valid `max_proposal_batches` and
+    /// `max_proposal_age` service arguments, and that creation fails when either is invalid.
+    #[cfg(feature = "multi-batch-proposals")]
+    #[test]
+    fn create_with_proposal_limits() {
+        let factory = get_factory();
+
+        let mut args = get_mock_args();
+        args.insert("max_proposal_batches".into(), "500".into());
+        args.insert("max_proposal_age".into(), "250".into());
+        assert!(
+            factory.create("".into(), "", "", args).is_ok(),
+            "Creating service with valid proposal limits failed"
+        );
+
+        let mut args = get_mock_args();
+        args.insert("max_proposal_batches".into(), "0".into());
+        assert!(
+            factory.create("".into(), "", "", args).is_err(),
+            "Creating service with a max_proposal_batches of 0 did not fail"
+        );
+
+        let mut args = get_mock_args();
+        args.insert("max_proposal_age".into(), "soon".into());
+        assert!(
+            factory.create("".into(), "", "", args).is_err(),
+            "Creating service with an invalid max_proposal_age did not fail"
+        );
+    }
+
+    /// Verify that the `coordinator_selection` and `coordinator_weights` service arguments are
+    /// properly set for a new `Scabbard` instance, and that creation fails when they are invalid.
+    #[cfg(feature = "coordinator-selection")]
+    #[test]
+    fn create_with_coordinator_selection() {
+        let factory = get_factory();
+
+        let mut args = get_mock_args();
+        args.insert("coordinator_selection".into(), "weighted".into());
+        args.insert("coordinator_weights".into(), r#"{"0": 3, "1": 0}"#.into());
+        let service = factory
+            .create("".into(), "", "", args)
+            .expect("failed to create service");
+        let scabbard = (&*service)
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .expect("failed to downcast Service to Scabbard");
+        assert_eq!(
+            scabbard.coordinator_selection,
+            CoordinatorSelection::Weighted(
+                vec![("0".to_string(), 3), ("1".to_string(), 0)]
+                    .into_iter()
+                    .collect()
+            )
+        );
+
+        let mut args = get_mock_args();
+        args.insert("coordinator_selection".into(), "random".into());
+        assert!(
+            factory.create("".into(), "", "", args).is_err(),
+            "Creating service with an unknown coordinator_selection did not fail"
+        );
+
+        let mut args = get_mock_args();
+        args.insert("coordinator_selection".into(), "round-robin".into());
+        args.insert("coordinator_weights".into(), r#"{"0": 3}"#.into());
+        assert!(
+            factory.create("".into(), "", "", args).is_err(),
+            "Creating service with weights for round-robin coordinator_selection did not fail"
+        );
+    }
+
     /// Verify that `Scabbard` creation fails when the `peer_services` argument isn't specified.
     #[test]
     fn create_without_peer_services() {
@@ -23,6 +23,8 @@ mod factory;
 mod rest_api;
 mod shared;
 mod state;
+#[cfg(feature = "state-sync")]
+mod sync;
 
 use std::any::Any;
 use std::collections::{HashSet, VecDeque};
@@ -34,6 +36,8 @@ use std::time::Duration;
 use cylinder::Verifier as SignatureVerifier;
 use openssl::hash::{hash, MessageDigest};
 use protobuf::Message;
+#[cfg(feature = "metrics")]
+use splinter::metrics::MetricsRegistry;
 use splinter::{
     consensus::{Proposal, ProposalUpdate},
     service::{
@@ -45,8 +49,14 @@ use transact::{protocol::batch::BatchPair, protos::FromBytes};
 
 use super::hex::to_hex;
 use super::protos::scabbard::{ScabbardMessage, ScabbardMessage_Type};
+#[cfg(feature = "snapshot")]
+use super::snapshot::StateSnapshot;
 
+#[cfg(feature = "coordinator-selection")]
+pub use consensus::CoordinatorSelection;
 use consensus::ScabbardConsensusManager;
+#[cfg(feature = "pbft")]
+pub use consensus::{ConsensusAlgorithm, PbftKeys};
 use error::ScabbardError;
 #[cfg(feature = "service-arg-validation")]
 pub use factory::ScabbardArgValidator;
@@ -61,6 +71,11 @@ const SERVICE_TYPE: &str = "scabbard";
 
 const DEFAULT_COORDINATOR_TIMEOUT: u64 = 30; // 30 seconds
 
+#[cfg(feature = "metrics")]
+const BATCH_QUEUE_LENGTH_METRIC: &str = "scabbard_batch_queue_length";
+#[cfg(feature = "metrics")]
+const BATCH_COMMIT_LATENCY_METRIC: &str = "scabbard_batch_commit_latency_seconds";
+
 /// A service for running Sawtooth Sabre smart contracts with two-phase commit consensus.
 #[derive(Clone)]
 pub struct Scabbard {
@@ -70,7 +85,21 @@ pub struct Scabbard {
     state: Arc<Mutex<ScabbardState>>,
     /// The coordinator timeout for the two-phase commit consensus engine
     coordinator_timeout: Duration,
+    /// The file in which the two-phase commit consensus engine records its progress
+    #[cfg(feature = "consensus-log")]
+    consensus_log_path: PathBuf,
+    /// The consensus algorithm used to agree on batches with peer services
+    #[cfg(feature = "pbft")]
+    consensus_algorithm: ConsensusAlgorithm,
+    /// The keys PBFT consensus signs and checks messages with
+    #[cfg(feature = "pbft")]
+    pbft_keys: Option<PbftKeys>,
+    /// How the coordinator of each proposal is chosen with two-phase commit
+    #[cfg(feature = "coordinator-selection")]
+    coordinator_selection: CoordinatorSelection,
     consensus: Arc<Mutex<Option<ScabbardConsensusManager>>>,
+    #[cfg(feature = "metrics")]
+    metrics: Option<MetricsRegistry>,
 }
 
 impl Scabbard {
@@ -96,7 +125,9 @@ impl Scabbard {
         // default value will be used (30 seconds).
         coordinator_timeout: Option<Duration>,
     ) -> Result<Self, ScabbardError> {
-        let shared = ScabbardShared::new(VecDeque::new(), None, peer_services, signature_verifier);
+        #[allow(unused_mut)]
+        let mut shared =
+            ScabbardShared::new(VecDeque::new(), None, peer_services, signature_verifier);
 
         let (state_db_path, receipt_db_path) =
             compute_db_paths(&service_id, circuit_id, state_db_dir, receipt_db_dir)?;
@@ -109,6 +140,15 @@ impl Scabbard {
         )
         .map_err(|err| ScabbardError::InitializationFailed(Box::new(err)))?;
 
+        #[cfg(feature = "consensus-log")]
+        let consensus_log_path = compute_consensus_log_path(&service_id, circuit_id, state_db_dir)?;
+        #[cfg(feature = "consensus-log")]
+        shared.load_proposed_batches(compute_proposed_batches_path(
+            &service_id,
+            circuit_id,
+            state_db_dir,
+        )?)?;
+
         let coordinator_timeout =
             coordinator_timeout.unwrap_or_else(|| Duration::from_secs(DEFAULT_COORDINATOR_TIMEOUT));
 
@@ -118,10 +158,93 @@ impl Scabbard {
             shared: Arc::new(Mutex::new(shared)),
             state: Arc::new(Mutex::new(state)),
             coordinator_timeout,
+            #[cfg(feature = "consensus-log")]
+            consensus_log_path,
+            #[cfg(feature = "pbft")]
+            consensus_algorithm: ConsensusAlgorithm::TwoPhaseCommit,
+            #[cfg(feature = "pbft")]
+            pbft_keys: None,
+            #[cfg(feature = "coordinator-selection")]
+            coordinator_selection: CoordinatorSelection::LowestId,
             consensus: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "metrics")]
+            metrics: None,
         })
     }
 
+    /// Use the given consensus algorithm, rather than two-phase commit, to agree on batches with
+    /// peer services. With PBFT, the coordinator timeout is used as the view change timeout.
+    #[cfg(feature = "pbft")]
+    pub fn with_consensus_algorithm(mut self, consensus_algorithm: ConsensusAlgorithm) -> Self {
+        self.consensus_algorithm = consensus_algorithm;
+        self
+    }
+
+    /// Sign PBFT consensus messages with the given keys' signer, and check the peer services'
+    /// messages against their public keys; required to use PBFT.
+    #[cfg(feature = "pbft")]
+    pub fn with_pbft_keys(mut self, pbft_keys: PbftKeys) -> Self {
+        self.pbft_keys = Some(pbft_keys);
+        self
+    }
+
+    /// Choose the coordinator of each proposal as given, rather than always choosing the service
+    /// with the lowest ID. If a coordinator times out, the next service chosen takes over.
+    #[cfg(feature = "coordinator-selection")]
+    pub fn with_coordinator_selection(
+        mut self,
+        coordinator_selection: CoordinatorSelection,
+    ) -> Self {
+        self.coordinator_selection = coordinator_selection;
+        self
+    }
+
+    /// Propose up to `max_batches` queued batches at a time. Queued batches wait for up to
+    /// `max_age` for more batches to be queued before they are proposed.
+    #[cfg(feature = "multi-batch-proposals")]
+    pub fn with_proposal_limits(
+        self,
+        max_batches: usize,
+        max_age: Duration,
+    ) -> Result<Self, ScabbardError> {
+        self.shared
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .set_proposal_limits(max_batches, max_age);
+        Ok(self)
+    }
+
+    /// Report the length of this service's batch queue and the latency of its batch commits to
+    /// the given metrics registry. The metrics are labeled with the circuit and service IDs.
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: MetricsRegistry) -> Result<Self, ScabbardError> {
+        let labels = [
+            ("circuit", self.circuit_id.as_str()),
+            ("service", self.service_id.as_str()),
+        ];
+
+        self.shared
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .set_batch_queue_length_gauge(metrics.gauge(
+                BATCH_QUEUE_LENGTH_METRIC,
+                "Number of batches submitted to a scabbard service that have not been proposed",
+                &labels,
+            ));
+        self.state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .batch_history()
+            .set_commit_latency_histogram(metrics.histogram(
+                BATCH_COMMIT_LATENCY_METRIC,
+                "Time between a scabbard service receiving a batch and committing it",
+                &labels,
+            ));
+
+        self.metrics = Some(metrics);
+        Ok(self)
+    }
+
     /// Fetch the value at the given `address` in the scabbard service's state. Returns `None` if
     /// the `address` is not set.
     pub fn get_state_at_address(&self, address: &str) -> Result<Option<Vec<u8>>, ScabbardError> {
@@ -153,6 +276,48 @@ impl Scabbard {
             .to_string())
     }
 
+    /// Take a snapshot of all entries in the scabbard service's state at the given `state_root`,
+    /// or at the current state root if none is provided. Returns `None` if the state root does
+    /// not exist.
+    #[cfg(feature = "snapshot")]
+    pub fn export_snapshot(
+        &self,
+        state_root: Option<&str>,
+    ) -> Result<Option<StateSnapshot>, ScabbardError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .export_snapshot(state_root)?)
+    }
+
+    /// Replace the scabbard service's state with the contents of the given snapshot.
+    ///
+    /// The snapshot is rejected if the scabbard service has already committed transactions, or if
+    /// the state root computed from the snapshot's entries does not match the snapshot's state
+    /// root.
+    #[cfg(feature = "snapshot")]
+    pub fn import_snapshot(&self, snapshot: &StateSnapshot) -> Result<(), ScabbardError> {
+        let mut state = self.state.lock().map_err(|_| ScabbardError::LockPoisoned)?;
+
+        if state.has_committed_transactions()? {
+            return Err(ScabbardError::SnapshotRejected(
+                "service has already committed transactions".into(),
+            ));
+        }
+
+        let computed_state_root = state.compute_snapshot_state_root(snapshot)?;
+        if computed_state_root != snapshot.state_root() {
+            return Err(ScabbardError::SnapshotRejected(format!(
+                "computed state root {} does not match snapshot state root {}",
+                computed_state_root,
+                snapshot.state_root()
+            )));
+        }
+
+        Ok(state.import_snapshot(snapshot)?)
+    }
+
     pub fn add_batches(&self, batches: Vec<BatchPair>) -> Result<Option<String>, ScabbardError> {
         let mut shared = self
             .shared
@@ -253,6 +418,26 @@ impl Service for Scabbard {
             .map_err(|_| ServiceStartError::PoisonedLock("shared lock poisoned".into()))?
             .set_network_sender(service_registry.connect(self.service_id())?);
 
+        // Check whether any changes were committed by peers while this service was stopped
+        #[cfg(feature = "state-sync")]
+        {
+            let shared = self
+                .shared
+                .lock()
+                .map_err(|_| ServiceStartError::PoisonedLock("shared lock poisoned".into()))?;
+            let state = self
+                .state
+                .lock()
+                .map_err(|_| ServiceStartError::PoisonedLock("state lock poisoned".into()))?;
+            let peers = shared.peer_services().iter().cloned().collect::<Vec<_>>();
+
+            if let Err(err) =
+                sync::send_sync_request(self.service_id(), &peers, &shared, &state, false)
+            {
+                warn!("Unable to request sync from peers: {}", err);
+            }
+        }
+
         // Setup consensus
         consensus.replace(
             ScabbardConsensusManager::new(
@@ -260,6 +445,14 @@ impl Service for Scabbard {
                 self.shared.clone(),
                 self.state.clone(),
                 self.coordinator_timeout,
+                #[cfg(feature = "consensus-log")]
+                &self.consensus_log_path,
+                #[cfg(feature = "pbft")]
+                self.consensus_algorithm,
+                #[cfg(feature = "pbft")]
+                self.pbft_keys.clone(),
+                #[cfg(feature = "coordinator-selection")]
+                self.coordinator_selection.clone(),
             )
             .map_err(|err| {
                 ServiceStartError::Internal(format!("Unable to start consensus: {}", err))
@@ -309,6 +502,18 @@ impl Service for Scabbard {
         {
             Err(ServiceDestroyError::NotStopped)
         } else {
+            #[cfg(feature = "metrics")]
+            {
+                if let Some(metrics) = self.metrics.as_ref() {
+                    let labels = [
+                        ("circuit", self.circuit_id.as_str()),
+                        ("service", self.service_id.as_str()),
+                    ];
+                    metrics.remove(BATCH_QUEUE_LENGTH_METRIC, &labels);
+                    metrics.remove(BATCH_COMMIT_LATENCY_METRIC, &labels);
+                }
+            }
+
             Ok(())
         }
     }
@@ -333,13 +538,25 @@ impl Service for Scabbard {
                 let proposed_batch = message.get_proposed_batch();
 
                 let proposal = Proposal::try_from(proposed_batch.get_proposal())?;
-                let batch = BatchPair::from_bytes(proposed_batch.get_batch())
+                let batches_bytes = if proposed_batch.get_batches().is_empty() {
+                    vec![proposed_batch.get_batch()]
+                } else {
+                    proposed_batch
+                        .get_batches()
+                        .iter()
+                        .map(Vec::as_slice)
+                        .collect()
+                };
+                let batches = batches_bytes
+                    .into_iter()
+                    .map(BatchPair::from_bytes)
+                    .collect::<Result<Vec<_>, _>>()
                     .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
 
                 self.shared
                     .lock()
                     .map_err(|_| ServiceError::PoisonedLock("shared lock poisoned".into()))?
-                    .add_proposed_batch(proposal.id.clone(), batch);
+                    .add_proposed_batches(proposal.id.clone(), batches);
 
                 self.consensus
                     .lock()
@@ -352,6 +569,49 @@ impl Service for Scabbard {
                     ))
                     .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
             }
+            #[cfg(feature = "state-sync")]
+            ScabbardMessage_Type::SYNC_REQUEST => {
+                let mut shared = self
+                    .shared
+                    .lock()
+                    .map_err(|_| ServiceError::PoisonedLock("shared lock poisoned".into()))?;
+                let state = self
+                    .state
+                    .lock()
+                    .map_err(|_| ServiceError::PoisonedLock("state lock poisoned".into()))?;
+
+                sync::handle_sync_request(
+                    &self.service_id,
+                    message.get_sync_request(),
+                    &mut shared,
+                    &state,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+            }
+            #[cfg(feature = "state-sync")]
+            ScabbardMessage_Type::SYNC_RESPONSE => {
+                let mut shared = self
+                    .shared
+                    .lock()
+                    .map_err(|_| ServiceError::PoisonedLock("shared lock poisoned".into()))?;
+                let mut state = self
+                    .state
+                    .lock()
+                    .map_err(|_| ServiceError::PoisonedLock("state lock poisoned".into()))?;
+
+                sync::handle_sync_response(
+                    &self.service_id,
+                    message.get_sync_response(),
+                    &mut shared,
+                    &mut state,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))
+            }
+            #[cfg(not(feature = "state-sync"))]
+            ScabbardMessage_Type::SYNC_REQUEST | ScabbardMessage_Type::SYNC_RESPONSE => {
+                debug!("Ignoring sync message; state sync is not enabled");
+                Ok(())
+            }
             ScabbardMessage_Type::UNSET => Err(ServiceError::InvalidMessageFormat(Box::new(
                 ScabbardError::MessageTypeUnset,
             ))),
@@ -369,15 +629,39 @@ fn compute_db_paths(
     state_db_dir: &Path,
     receipt_db_dir: &Path,
 ) -> Result<(PathBuf, PathBuf), ScabbardError> {
-    let hash = hash(
+    let hash = compute_service_hash(service_id, circuit_id)?;
+    let state_db_path = state_db_dir.join(format!("{}-state.lmdb", hash));
+    let receipt_db_path = receipt_db_dir.join(format!("{}-receipts.lmdb", hash));
+    Ok((state_db_path, receipt_db_path))
+}
+
+#[cfg(feature = "consensus-log")]
+fn compute_consensus_log_path(
+    service_id: &str,
+    circuit_id: &str,
+    state_db_dir: &Path,
+) -> Result<PathBuf, ScabbardError> {
+    let hash = compute_service_hash(service_id, circuit_id)?;
+    Ok(state_db_dir.join(format!("{}-consensus.log", hash)))
+}
+
+#[cfg(feature = "consensus-log")]
+fn compute_proposed_batches_path(
+    service_id: &str,
+    circuit_id: &str,
+    state_db_dir: &Path,
+) -> Result<PathBuf, ScabbardError> {
+    let hash = compute_service_hash(service_id, circuit_id)?;
+    Ok(state_db_dir.join(format!("{}-proposals.json", hash)))
+}
+
+fn compute_service_hash(service_id: &str, circuit_id: &str) -> Result<String, ScabbardError> {
+    hash(
         MessageDigest::sha256(),
         format!("{}::{}", service_id, circuit_id).as_bytes(),
     )
     .map(|digest| to_hex(&*digest))
-    .map_err(|err| ScabbardError::InitializationFailed(Box::new(err)))?;
-    let state_db_path = state_db_dir.join(format!("{}-state.lmdb", hash));
-    let receipt_db_path = receipt_db_dir.join(format!("{}-receipts.lmdb", hash));
-    Ok((state_db_path, receipt_db_path))
+    .map_err(|err| ScabbardError::InitializationFailed(Box::new(err)))
 }
 
 #[cfg(test)]
@@ -14,6 +14,8 @@
 
 pub mod batch_statuses;
 pub mod batches;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
 pub mod state;
 pub mod state_address;
 pub mod state_root;
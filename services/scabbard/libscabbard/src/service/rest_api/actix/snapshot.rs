@@ -0,0 +1,149 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix_web::{web, Error as ActixError, HttpResponse};
+use futures::{stream::Stream, Future, IntoFuture};
+use splinter::{
+    rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard},
+    service::rest_api::ServiceEndpoint,
+};
+
+use crate::protocol;
+#[cfg(feature = "authorization")]
+use crate::service::rest_api::{SCABBARD_READ_PERMISSION, SCABBARD_WRITE_PERMISSION};
+use crate::service::{error::ScabbardError, Scabbard, SERVICE_TYPE};
+use crate::snapshot::StateSnapshot;
+
+pub fn make_export_snapshot_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/snapshot/export".into(),
+        method: Method::Get,
+        handler: Arc::new(move |request, _, service| {
+            let scabbard = match service.as_any().downcast_ref::<Scabbard>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to scabbard service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            let query: web::Query<HashMap<String, String>> =
+                if let Ok(q) = web::Query::from_query(request.query_string()) {
+                    q
+                } else {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request("Invalid query"))
+                            .into_future(),
+                    );
+                };
+
+            let state_root = query.get("state_root").map(String::as_str);
+
+            Box::new(match scabbard.export_snapshot(state_root) {
+                Ok(Some(snapshot)) => HttpResponse::Ok().json(snapshot).into_future(),
+                Ok(None) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found("State root not found"))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to export snapshot: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![Box::new(ProtocolVersionRangeGuard::new(
+            protocol::SCABBARD_SNAPSHOT_PROTOCOL_MIN,
+            protocol::SCABBARD_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: SCABBARD_READ_PERMISSION,
+    }
+}
+
+pub fn make_import_snapshot_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/snapshot/import".into(),
+        method: Method::Post,
+        handler: Arc::new(move |_, payload, service| {
+            let scabbard = match service.as_any().downcast_ref::<Scabbard>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to scabbard service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            }
+            .clone();
+
+            Box::new(
+                payload
+                    .from_err::<ActixError>()
+                    .fold(web::BytesMut::new(), move |mut body, chunk| {
+                        body.extend_from_slice(&chunk);
+                        Ok::<_, ActixError>(body)
+                    })
+                    .into_future()
+                    .and_then(move |body| {
+                        let snapshot = match StateSnapshot::read_from(&body[..]) {
+                            Ok(snapshot) => snapshot,
+                            Err(err) => {
+                                return HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request(&format!(
+                                        "Invalid body: {}",
+                                        err
+                                    )))
+                                    .into_future()
+                            }
+                        };
+
+                        match scabbard.import_snapshot(&snapshot) {
+                            Ok(()) => HttpResponse::Ok().finish().into_future(),
+                            Err(ScabbardError::SnapshotRejected(msg)) => HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Snapshot rejected: {}",
+                                    msg
+                                )))
+                                .into_future(),
+                            Err(err) => {
+                                error!("Failed to import snapshot: {}", err);
+                                HttpResponse::InternalServerError()
+                                    .json(ErrorResponse::internal_error())
+                                    .into_future()
+                            }
+                        }
+                    }),
+            )
+        }),
+        request_guards: vec![Box::new(ProtocolVersionRangeGuard::new(
+            protocol::SCABBARD_SNAPSHOT_PROTOCOL_MIN,
+            protocol::SCABBARD_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: SCABBARD_WRITE_PERMISSION,
+    }
+}
@@ -13,32 +13,79 @@
 // limitations under the License.
 
 use std::collections::{HashMap, HashSet, VecDeque};
+#[cfg(feature = "consensus-log")]
+use std::fs::{rename, File};
+#[cfg(feature = "consensus-log")]
+use std::io::{self, ErrorKind, Write};
+#[cfg(feature = "consensus-log")]
+use std::path::PathBuf;
+#[cfg(all(feature = "pbft", feature = "state-sync"))]
+use std::sync::mpsc::Sender;
+#[cfg(any(feature = "multi-batch-proposals", feature = "state-sync"))]
+use std::time::{Duration, Instant};
 
 use cylinder::{PublicKey, Signature, Verifier as SignatureVerifier};
 use openssl::hash::{hash, MessageDigest};
 use transact::protocol::batch::BatchPair;
 use transact::protocol::transaction::{HashMethod, TransactionHeader};
 use transact::protos::FromBytes;
+#[cfg(feature = "consensus-log")]
+use transact::protos::IntoBytes;
 
+#[cfg(all(feature = "pbft", feature = "state-sync"))]
+use splinter::consensus::ProposalUpdate;
+#[cfg(feature = "metrics")]
+use splinter::metrics::Gauge;
 use splinter::{consensus::ProposalId, service::ServiceNetworkSender};
 
 use crate::hex::parse_hex;
 
 use super::error::ScabbardError;
 
+/// The default maximum number of batches in a proposal
+#[cfg(feature = "multi-batch-proposals")]
+pub const DEFAULT_MAX_PROPOSAL_BATCHES: usize = 100;
+/// By default, queued batches are proposed without waiting for more batches to be queued
+#[cfg(feature = "multi-batch-proposals")]
+pub const DEFAULT_MAX_PROPOSAL_AGE: Duration = Duration::from_millis(0);
+
 /// Data structure used to store information that's shared between components in this service
 pub struct ScabbardShared {
     /// Queue of batches that have been submitted locally via the REST API, but have not yet been
     /// proposed.
     batch_queue: VecDeque<BatchPair>,
+    /// When each batch in the queue was added to it
+    #[cfg(feature = "multi-batch-proposals")]
+    batch_queue_times: VecDeque<Instant>,
+    /// The maximum number of batches in a proposal
+    #[cfg(feature = "multi-batch-proposals")]
+    max_proposal_batches: usize,
+    /// The longest time a batch waits in the queue for more batches to be queued before it is
+    /// proposed
+    #[cfg(feature = "multi-batch-proposals")]
+    max_proposal_age: Duration,
     /// Used to send messages to other services; set when the service is started and unset when the
     /// service is stopped.
     network_sender: Option<Box<dyn ServiceNetworkSender>>,
     /// List of service IDs that this service is configured to communicate and share state with.
     peer_services: HashSet<String>,
     /// Tracks which batches are currently being evaluated, indexed by corresponding proposal IDs.
-    proposed_batches: HashMap<ProposalId, BatchPair>,
+    proposed_batches: HashMap<ProposalId, Vec<BatchPair>>,
+    /// The file in which the proposed batches are recorded, so that a proposal consensus approved
+    /// can still be applied if the service restarts before applying it.
+    #[cfg(feature = "consensus-log")]
+    proposed_batches_path: Option<PathBuf>,
     signature_verifier: Box<dyn SignatureVerifier>,
+    /// Reports the length of the batch queue, if metrics are enabled for this service.
+    #[cfg(feature = "metrics")]
+    batch_queue_length: Option<Gauge>,
+    /// Set while this service is catching up with its peers; the service does not take part in
+    /// consensus until it has caught up or the deadline has passed.
+    #[cfg(feature = "state-sync")]
+    sync_deadline: Option<Instant>,
+    /// Told when this service has caught up with its peers, if consensus asked it to catch up
+    #[cfg(all(feature = "pbft", feature = "state-sync"))]
+    sync_listener: Option<Sender<ProposalUpdate>>,
 }
 
 impl ScabbardShared {
@@ -49,20 +96,83 @@ impl ScabbardShared {
         signature_verifier: Box<dyn SignatureVerifier>,
     ) -> Self {
         ScabbardShared {
+            #[cfg(feature = "multi-batch-proposals")]
+            batch_queue_times: batch_queue.iter().map(|_| Instant::now()).collect(),
+            #[cfg(feature = "multi-batch-proposals")]
+            max_proposal_batches: DEFAULT_MAX_PROPOSAL_BATCHES,
+            #[cfg(feature = "multi-batch-proposals")]
+            max_proposal_age: DEFAULT_MAX_PROPOSAL_AGE,
             batch_queue,
             network_sender,
             peer_services,
             proposed_batches: HashMap::new(),
+            #[cfg(feature = "consensus-log")]
+            proposed_batches_path: None,
             signature_verifier,
+            #[cfg(feature = "metrics")]
+            batch_queue_length: None,
+            #[cfg(feature = "state-sync")]
+            sync_deadline: None,
+            #[cfg(all(feature = "pbft", feature = "state-sync"))]
+            sync_listener: None,
         }
     }
 
+    #[cfg(feature = "metrics")]
+    pub fn set_batch_queue_length_gauge(&mut self, gauge: Gauge) {
+        gauge.set(self.batch_queue.len() as i64);
+        self.batch_queue_length = Some(gauge);
+    }
+
+    #[cfg(feature = "multi-batch-proposals")]
+    pub fn set_proposal_limits(&mut self, max_batches: usize, max_age: Duration) {
+        self.max_proposal_batches = max_batches;
+        self.max_proposal_age = max_age;
+    }
+
     pub fn add_batch_to_queue(&mut self, batch: BatchPair) {
-        self.batch_queue.push_back(batch)
+        self.batch_queue.push_back(batch);
+        #[cfg(feature = "multi-batch-proposals")]
+        self.batch_queue_times.push_back(Instant::now());
+        #[cfg(feature = "metrics")]
+        self.update_batch_queue_length();
     }
 
+    #[cfg(not(feature = "multi-batch-proposals"))]
     pub fn pop_batch_from_queue(&mut self) -> Option<BatchPair> {
-        self.batch_queue.pop_front()
+        let batch = self.batch_queue.pop_front();
+        #[cfg(feature = "metrics")]
+        self.update_batch_queue_length();
+        batch
+    }
+
+    /// Take the batches for the next proposal from the front of the queue. Batches are only taken
+    /// once the queue holds the maximum number of batches for a proposal, or the oldest batch in
+    /// the queue has reached the maximum proposal age.
+    #[cfg(feature = "multi-batch-proposals")]
+    pub fn pop_proposal_batches(&mut self) -> Vec<BatchPair> {
+        let oldest_batch_expired = self
+            .batch_queue_times
+            .front()
+            .map(|queued_at| queued_at.elapsed() >= self.max_proposal_age)
+            .unwrap_or(false);
+        if self.batch_queue.len() < self.max_proposal_batches && !oldest_batch_expired {
+            return vec![];
+        }
+
+        let batch_count = self.batch_queue.len().min(self.max_proposal_batches);
+        let batches = self.batch_queue.drain(..batch_count).collect();
+        self.batch_queue_times.drain(..batch_count);
+        #[cfg(feature = "metrics")]
+        self.update_batch_queue_length();
+        batches
+    }
+
+    #[cfg(feature = "metrics")]
+    fn update_batch_queue_length(&self) {
+        if let Some(gauge) = self.batch_queue_length.as_ref() {
+            gauge.set(self.batch_queue.len() as i64);
+        }
     }
 
     pub fn network_sender(&self) -> Option<&dyn ServiceNetworkSender> {
@@ -81,20 +191,134 @@ impl ScabbardShared {
         &self.peer_services
     }
 
-    pub fn add_proposed_batch(
+    #[cfg(feature = "state-sync")]
+    pub fn start_sync(&mut self, timeout: Duration) {
+        self.sync_deadline = Some(Instant::now() + timeout);
+    }
+
+    #[cfg(feature = "state-sync")]
+    pub fn finish_sync(&mut self) {
+        self.sync_deadline = None;
+
+        #[cfg(feature = "pbft")]
+        {
+            if let Some(sync_listener) = self.sync_listener.take() {
+                if sync_listener.send(ProposalUpdate::StateSynced).is_err() {
+                    warn!("Unable to tell consensus that this service has caught up");
+                }
+            }
+        }
+    }
+
+    /// Send `ProposalUpdate::StateSynced` with the given sender once this service has caught up
+    /// with its peers.
+    #[cfg(all(feature = "pbft", feature = "state-sync"))]
+    pub fn notify_when_synced(&mut self, sync_listener: Sender<ProposalUpdate>) {
+        self.sync_listener = Some(sync_listener);
+    }
+
+    #[cfg(feature = "state-sync")]
+    pub fn is_syncing(&self) -> bool {
+        self.sync_deadline
+            .map(|deadline| Instant::now() < deadline)
+            .unwrap_or(false)
+    }
+
+    /// Record the proposed batches in the given file from now on, loading any batches that were
+    /// recorded in it before the service restarted.
+    #[cfg(feature = "consensus-log")]
+    pub fn load_proposed_batches(&mut self, path: PathBuf) -> Result<(), ScabbardError> {
+        let file = match File::open(&path) {
+            Ok(file) => Some(file),
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            Err(err) => return Err(ScabbardError::InitializationFailed(Box::new(err))),
+        };
+
+        if let Some(file) = file {
+            let recorded: Vec<(Vec<u8>, Vec<Vec<u8>>)> = serde_json::from_reader(file)
+                .map_err(|err| ScabbardError::InitializationFailed(Box::new(err)))?;
+            for (proposal_id, batches) in recorded {
+                let batches = batches
+                    .iter()
+                    .map(|bytes| BatchPair::from_bytes(bytes))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| ScabbardError::InitializationFailed(Box::new(err)))?;
+                self.proposed_batches.insert(proposal_id.into(), batches);
+            }
+        }
+
+        self.proposed_batches_path = Some(path);
+
+        Ok(())
+    }
+
+    pub fn add_proposed_batches(
         &mut self,
         proposal_id: ProposalId,
-        batch: BatchPair,
-    ) -> Option<BatchPair> {
-        self.proposed_batches.insert(proposal_id, batch)
+        batches: Vec<BatchPair>,
+    ) -> Option<Vec<BatchPair>> {
+        let replaced = self.proposed_batches.insert(proposal_id, batches);
+        #[cfg(feature = "consensus-log")]
+        self.write_proposed_batches();
+        replaced
     }
 
-    pub fn get_proposed_batch(&self, proposal_id: &ProposalId) -> Option<&BatchPair> {
-        self.proposed_batches.get(proposal_id)
+    pub fn get_proposed_batches(&self, proposal_id: &ProposalId) -> Option<&[BatchPair]> {
+        self.proposed_batches
+            .get(proposal_id)
+            .map(|batches| batches.as_slice())
     }
 
-    pub fn remove_proposed_batch(&mut self, proposal_id: &ProposalId) -> Option<BatchPair> {
-        self.proposed_batches.remove(&proposal_id)
+    pub fn remove_proposed_batches(&mut self, proposal_id: &ProposalId) -> Option<Vec<BatchPair>> {
+        let removed = self.proposed_batches.remove(&proposal_id);
+        #[cfg(feature = "consensus-log")]
+        {
+            if removed.is_some() {
+                self.write_proposed_batches();
+            }
+        }
+        removed
+    }
+
+    /// Write the proposed batches to the file they are recorded in, if any. The batches are
+    /// written to a temporary file that is then moved into place, so a crash while writing never
+    /// leaves the file partially written.
+    #[cfg(feature = "consensus-log")]
+    fn write_proposed_batches(&self) {
+        let path = match self.proposed_batches_path.as_ref() {
+            Some(path) => path,
+            None => return,
+        };
+
+        let result = self
+            .proposed_batches
+            .iter()
+            .map(|(proposal_id, batches)| {
+                let batches = batches
+                    .iter()
+                    .cloned()
+                    .map(BatchPair::into_bytes)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| io::Error::new(ErrorKind::Other, err))?;
+                Ok((proposal_id.as_ref().to_vec(), batches))
+            })
+            .collect::<Result<Vec<_>, io::Error>>()
+            .and_then(|recorded| serde_json::to_vec(&recorded).map_err(io::Error::from))
+            .and_then(|bytes| {
+                let temp_path = path.with_extension("temp");
+                let mut temp_file = File::create(&temp_path)?;
+                temp_file.write_all(&bytes)?;
+                temp_file.sync_all()?;
+                rename(&temp_path, path)
+            });
+
+        if let Err(err) = result {
+            error!(
+                "Unable to record proposed batches in {}: {}",
+                path.display(),
+                err
+            );
+        }
     }
 
     pub fn verify_batches(&self, batches: &[BatchPair]) -> Result<bool, ScabbardError> {
@@ -196,3 +420,152 @@ impl ScabbardShared {
         Ok(true)
     }
 }
+
+#[cfg(all(test, feature = "multi-batch-proposals"))]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context, Signer, VerifierFactory};
+    use transact::{
+        families::command::make_command_transaction,
+        protocol::{
+            batch::BatchBuilder,
+            command::{BytesEntry, Command, SetState},
+        },
+    };
+
+    /// Verify that batches are not taken until the queue holds the maximum number of batches for
+    /// a proposal, that no more than the maximum are taken, and that the remaining batches are
+    /// taken in order with later batches.
+    ///
+    /// 1. Queue 2 batches with a maximum of 3 batches and verify that none are taken
+    /// 2. Queue 3 more batches and verify that the first 3 batches are taken
+    /// 3. Verify that the 2 remaining batches are not taken on their own
+    /// 4. Queue 1 more batch and verify that the 2 remaining batches are taken with it
+    #[test]
+    fn pop_proposal_batches_max_batches() {
+        let signer = new_signer();
+        let batches = (0..6).map(|i| make_batch(&*signer, i)).collect::<Vec<_>>();
+
+        let mut shared = new_shared();
+        shared.set_proposal_limits(3, Duration::from_secs(60));
+
+        shared.add_batch_to_queue(batches[0].clone());
+        shared.add_batch_to_queue(batches[1].clone());
+        assert!(shared.pop_proposal_batches().is_empty());
+
+        shared.add_batch_to_queue(batches[2].clone());
+        shared.add_batch_to_queue(batches[3].clone());
+        shared.add_batch_to_queue(batches[4].clone());
+        assert_eq!(
+            batch_ids(&shared.pop_proposal_batches()),
+            batch_ids(&batches[0..3])
+        );
+
+        assert!(shared.pop_proposal_batches().is_empty());
+
+        shared.add_batch_to_queue(batches[5].clone());
+        assert_eq!(
+            batch_ids(&shared.pop_proposal_batches()),
+            batch_ids(&batches[3..6])
+        );
+        assert!(shared.pop_proposal_batches().is_empty());
+    }
+
+    /// Verify that the queued batches are taken once the oldest batch has reached the maximum
+    /// proposal age, even though the queue does not hold the maximum number of batches.
+    ///
+    /// 1. Queue 2 batches with a maximum of 10 batches and verify that none are taken
+    /// 2. Wait for the maximum proposal age and verify that both batches are taken
+    #[test]
+    fn pop_proposal_batches_max_age() {
+        let signer = new_signer();
+        let batches = (0..2).map(|i| make_batch(&*signer, i)).collect::<Vec<_>>();
+
+        let mut shared = new_shared();
+        shared.set_proposal_limits(10, Duration::from_millis(200));
+
+        shared.add_batch_to_queue(batches[0].clone());
+        shared.add_batch_to_queue(batches[1].clone());
+        assert!(shared.pop_proposal_batches().is_empty());
+
+        thread::sleep(Duration::from_millis(250));
+        assert_eq!(
+            batch_ids(&shared.pop_proposal_batches()),
+            batch_ids(&batches)
+        );
+        assert!(shared.pop_proposal_batches().is_empty());
+    }
+
+    /// Verify that batches left over after an expired queue is cut to the maximum number of
+    /// batches keep their age, so they are taken without waiting again.
+    ///
+    /// 1. Queue 3 batches with a maximum of 2 batches
+    /// 2. Wait for the maximum proposal age and verify that the first 2 batches are taken
+    /// 3. Verify that the remaining batch is taken immediately
+    #[test]
+    fn pop_proposal_batches_expired_leftovers() {
+        let signer = new_signer();
+        let batches = (0..3).map(|i| make_batch(&*signer, i)).collect::<Vec<_>>();
+
+        let mut shared = new_shared();
+        shared.set_proposal_limits(2, Duration::from_millis(200));
+
+        for batch in batches.iter() {
+            shared.add_batch_to_queue(batch.clone());
+        }
+
+        thread::sleep(Duration::from_millis(250));
+        assert_eq!(
+            batch_ids(&shared.pop_proposal_batches()),
+            batch_ids(&batches[0..2])
+        );
+        assert_eq!(
+            batch_ids(&shared.pop_proposal_batches()),
+            batch_ids(&batches[2..3])
+        );
+        assert!(shared.pop_proposal_batches().is_empty());
+    }
+
+    fn new_shared() -> ScabbardShared {
+        ScabbardShared::new(
+            VecDeque::new(),
+            None,
+            HashSet::new(),
+            Secp256k1Context::new().new_verifier(),
+        )
+    }
+
+    fn new_signer() -> Box<dyn Signer> {
+        let context = Secp256k1Context::new();
+        context.new_signer(context.new_random_private_key())
+    }
+
+    /// Build a batch that sets a value at an address derived from the given index, so each batch
+    /// is distinct.
+    fn make_batch(signer: &dyn Signer, index: u8) -> BatchPair {
+        BatchBuilder::new()
+            .with_transactions(vec![
+                make_command_transaction(
+                    &[Command::SetState(SetState::new(vec![BytesEntry::new(
+                        format!("abcdef{:02x}", index),
+                        vec![index],
+                    )]))],
+                    signer,
+                )
+                .take()
+                .0,
+            ])
+            .build_pair(signer)
+            .expect("Failed to build batch")
+    }
+
+    fn batch_ids(batches: &[BatchPair]) -> Vec<String> {
+        batches
+            .iter()
+            .map(|batch| batch.batch().header_signature().to_string())
+            .collect()
+    }
+}
@@ -29,6 +29,8 @@ use sawtooth_sabre::{
 };
 #[cfg(feature = "events")]
 use splinter::events::{ParseBytes, ParseError};
+#[cfg(feature = "metrics")]
+use splinter::metrics::Histogram;
 #[cfg(test)]
 use transact::families::command::CommandTransactionHandler;
 use transact::{
@@ -52,11 +54,16 @@ use transact::{
 
 use crate::hex;
 use crate::protos::scabbard::{Setting, Setting_Entry};
+#[cfg(feature = "snapshot")]
+use crate::snapshot::StateSnapshot;
 
 use super::error::{ScabbardStateError, StateSubscriberError};
 
 const EXECUTION_TIMEOUT: u64 = 300; // five minutes
 const CURRENT_STATE_ROOT_INDEX: &str = "current_state_root";
+/// The key of the number of committed proposals, which is stored with the current state root
+#[cfg(feature = "coordinator-selection")]
+const COMMITTED_PROPOSALS_KEY: &[u8] = b"COMMITTED_PROPOSALS";
 const ITER_CACHE_SIZE: usize = 64;
 const COMPLETED_BATCH_INFO_ITER_RETRY_MILLIS: u64 = 100;
 const DEFAULT_BATCH_HISTORY_SIZE: usize = 100;
@@ -69,8 +76,11 @@ pub struct ScabbardState {
     context_manager: ContextManager,
     executor: Executor,
     current_state_root: String,
+    /// The number of proposals committed to state, which is persisted with the current state root
+    #[cfg(feature = "coordinator-selection")]
+    committed_proposals: u64,
     transaction_receipt_store: Arc<RwLock<TransactionReceiptStore>>,
-    pending_changes: Option<(String, Vec<TransactionReceipt>)>,
+    pending_changes: Option<(Vec<String>, Vec<TransactionReceipt>)>,
     event_subscribers: Vec<Box<dyn StateSubscriber>>,
     batch_history: BatchHistory,
 }
@@ -121,6 +131,9 @@ impl ScabbardState {
             )?
         };
 
+        #[cfg(feature = "coordinator-selection")]
+        let committed_proposals = Self::read_committed_proposals(&*db)?;
+
         // Initialize transact
         let context_manager = ContextManager::new(Box::new(MerkleState::new(db.clone())));
         let mut executor = Executor::new(vec![Box::new(StaticExecutionAdapter::new_adapter(
@@ -142,6 +155,8 @@ impl ScabbardState {
             context_manager,
             executor,
             current_state_root,
+            #[cfg(feature = "coordinator-selection")]
+            committed_proposals,
             transaction_receipt_store: Arc::new(RwLock::new(TransactionReceiptStore::new(
                 Box::new(
                     LmdbOrderedStore::new(receipt_db_path, Some(receipt_db_size))
@@ -161,6 +176,25 @@ impl ScabbardState {
             .map_err(|e| ScabbardStateError(format!("Unable to read HEAD entry: {}", e)))
     }
 
+    #[cfg(feature = "coordinator-selection")]
+    fn read_committed_proposals(db: &dyn Database) -> Result<u64, ScabbardStateError> {
+        let bytes = db
+            .get_reader()
+            .and_then(|reader| reader.index_get(CURRENT_STATE_ROOT_INDEX, COMMITTED_PROPOSALS_KEY))
+            .map_err(|e| {
+                ScabbardStateError(format!("Unable to read committed proposals entry: {}", e))
+            })?;
+
+        match bytes {
+            Some(bytes) => <[u8; 8]>::try_from(bytes.as_slice())
+                .map(u64::from_be_bytes)
+                .map_err(|_| ScabbardStateError("Committed proposals entry is invalid".into())),
+            None => Ok(0),
+        }
+    }
+
+    /// Write the current state root, and the number of committed proposals in the same
+    /// transaction, to the database.
     fn write_current_state_root(&self) -> Result<(), ScabbardStateError> {
         let current_root_bytes = hex::parse_hex(&self.current_state_root).map_err(|e| {
             ScabbardStateError(format!(
@@ -180,6 +214,17 @@ impl ScabbardState {
             .index_put(CURRENT_STATE_ROOT_INDEX, b"HEAD", &current_root_bytes)
             .map_err(|e| ScabbardStateError(format!("Unable to write HEAD entry: {}", e)))?;
 
+        #[cfg(feature = "coordinator-selection")]
+        writer
+            .index_put(
+                CURRENT_STATE_ROOT_INDEX,
+                COMMITTED_PROPOSALS_KEY,
+                &self.committed_proposals.to_be_bytes(),
+            )
+            .map_err(|e| {
+                ScabbardStateError(format!("Unable to write committed proposals entry: {}", e))
+            })?;
+
         writer
             .commit()
             .map_err(|e| ScabbardStateError(format!("Unable to commit HEAD entry: {}", e)))?;
@@ -221,34 +266,232 @@ impl ScabbardState {
         &self.current_state_root
     }
 
-    pub fn prepare_change(&mut self, batch: BatchPair) -> Result<String, ScabbardStateError> {
-        // Setup the transact scheduler
-        let (result_tx, result_rx) = std::sync::mpsc::channel();
-        let mut scheduler = SerialScheduler::new(
-            Box::new(self.context_manager.clone()),
-            self.current_state_root.clone(),
-        )?;
-        scheduler.set_result_callback(Box::new(move |batch_result| {
-            if result_tx.send(batch_result).is_err() {
-                error!("Unable to send batch result; receiver must have dropped");
+    /// Take a snapshot of all entries in state at the given `state_root`, or at the current state
+    /// root if none is provided. Returns `None` if the state root does not exist.
+    #[cfg(feature = "snapshot")]
+    pub fn export_snapshot(
+        &self,
+        state_root: Option<&str>,
+    ) -> Result<Option<StateSnapshot>, ScabbardStateError> {
+        let state_root = state_root.unwrap_or(&self.current_state_root);
+
+        let entries = match MerkleRadixTree::new(self.db.clone(), Some(state_root))?.leaves(None) {
+            Ok(leaves) => leaves.collect::<Result<Vec<_>, _>>()?,
+            // Every existing state root has a root node, so the state root must not exist
+            Err(StateDatabaseError::NotFound(_)) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Some(StateSnapshot::new(state_root, entries)))
+    }
+
+    /// Determine whether any transactions have been committed to state; snapshots may only be
+    /// imported if none have been.
+    #[cfg(feature = "snapshot")]
+    pub fn has_committed_transactions(&self) -> Result<bool, ScabbardStateError> {
+        let transaction_receipt_store = self.transaction_receipt_store.read().map_err(|err| {
+            ScabbardStateError(format!("transaction receipt store lock poisoned: {}", err))
+        })?;
+
+        let has_receipts = transaction_receipt_store
+            .iter()
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to get transaction receipts from store: {}",
+                    err
+                ))
+            })?
+            .next()
+            .is_some();
+
+        Ok(has_receipts)
+    }
+
+    /// Compute the state root that results from setting the snapshot's entries in empty state.
+    #[cfg(feature = "snapshot")]
+    pub fn compute_snapshot_state_root(
+        &self,
+        snapshot: &StateSnapshot,
+    ) -> Result<String, ScabbardStateError> {
+        Ok(MerkleState::new(self.db.clone())
+            .compute_state_id(&self.empty_state_root()?, &snapshot_state_changes(snapshot))?)
+    }
+
+    /// Replace the contents of state with the snapshot's entries and make the resulting state root
+    /// the current state root.
+    ///
+    /// The receipts of any transactions committed before the import are removed, since they no
+    /// longer lead to the current state root; a service that has imported a snapshot reports that
+    /// it has not committed any transactions until it commits new ones.
+    ///
+    /// The snapshot is not verified; callers should check the result of
+    /// `compute_snapshot_state_root` before importing a snapshot.
+    #[cfg(feature = "snapshot")]
+    pub fn import_snapshot(&mut self, snapshot: &StateSnapshot) -> Result<(), ScabbardStateError> {
+        if self.pending_changes.is_some() {
+            return Err(ScabbardStateError(
+                "cannot import snapshot while changes are pending".into(),
+            ));
+        }
+
+        self.current_state_root = MerkleState::new(self.db.clone())
+            .commit(&self.empty_state_root()?, &snapshot_state_changes(snapshot))?;
+
+        self.write_current_state_root()?;
+
+        let mut transaction_receipt_store =
+            self.transaction_receipt_store.write().map_err(|err| {
+                ScabbardStateError(format!("transaction receipt store lock poisoned: {}", err))
+            })?;
+        let mut removed_receipts = 0;
+        while transaction_receipt_store
+            .pop()
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to remove transaction receipt from store: {}",
+                    err
+                ))
+            })?
+            .is_some()
+        {
+            removed_receipts += 1;
+        }
+        if removed_receipts > 0 {
+            debug!(
+                "removed {} transaction receipt(s) committed before the snapshot",
+                removed_receipts
+            );
+        }
+
+        info!(
+            "imported {} state entries for new state root {}",
+            snapshot.entries().len(),
+            self.current_state_root,
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "snapshot")]
+    fn empty_state_root(&self) -> Result<String, ScabbardStateError> {
+        Ok(MerkleRadixTree::new(self.db.clone(), None)?.get_merkle_root())
+    }
+
+    /// Determine whether a batch has been prepared but not yet committed or rolled back.
+    #[cfg(feature = "state-sync")]
+    pub fn has_pending_changes(&self) -> bool {
+        self.pending_changes.is_some()
+    }
+
+    /// Get the ID of the last transaction that was committed to state, if any.
+    #[cfg(feature = "state-sync")]
+    pub fn last_transaction_id(&self) -> Result<Option<String>, ScabbardStateError> {
+        let transaction_receipt_store = self.transaction_receipt_store.read().map_err(|err| {
+            ScabbardStateError(format!("transaction receipt store lock poisoned: {}", err))
+        })?;
+
+        let last_receipt = transaction_receipt_store
+            .iter()
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to get transaction receipts from store: {}",
+                    err
+                ))
+            })?
+            .last();
+
+        Ok(last_receipt.map(|receipt| receipt.transaction_id))
+    }
+
+    /// Determine whether the transaction with the given ID has been committed to state.
+    #[cfg(feature = "state-sync")]
+    pub fn has_transaction(&self, transaction_id: &str) -> Result<bool, ScabbardStateError> {
+        let transaction_receipt_store = self.transaction_receipt_store.read().map_err(|err| {
+            ScabbardStateError(format!("transaction receipt store lock poisoned: {}", err))
+        })?;
+
+        let receipt = transaction_receipt_store
+            .get_by_id(transaction_id.into())
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to get transaction receipt from store: {}",
+                    err
+                ))
+            })?;
+
+        Ok(receipt.is_some())
+    }
+
+    /// Get the receipts of all transactions committed after the transaction with the given ID, or
+    /// of all committed transactions if no ID is provided. Returns `None` if the transaction has
+    /// not been committed to state.
+    #[cfg(feature = "state-sync")]
+    pub fn transaction_receipts_since(
+        &self,
+        transaction_id: Option<&str>,
+    ) -> Result<Option<Vec<TransactionReceipt>>, ScabbardStateError> {
+        if let Some(transaction_id) = transaction_id {
+            if !self.has_transaction(transaction_id)? {
+                return Ok(None);
             }
-        }))?;
+        }
 
-        // Add the batch to, finalize, and execute the scheduler
-        scheduler.add_batch(batch.clone())?;
-        scheduler.finalize()?;
-        self.executor
-            .execute(scheduler.take_task_iterator()?, scheduler.new_notifier()?)?;
+        let transaction_receipt_store = self.transaction_receipt_store.read().map_err(|err| {
+            ScabbardStateError(format!("transaction receipt store lock poisoned: {}", err))
+        })?;
 
-        // Get the results and shutdown the scheduler
-        let recv_result = result_rx.recv_timeout(Duration::from_secs(EXECUTION_TIMEOUT));
+        let receipts = if let Some(transaction_id) = transaction_id {
+            transaction_receipt_store.iter_since_id(transaction_id.into())
+        } else {
+            transaction_receipt_store.iter()
+        }
+        .map_err(|err| {
+            ScabbardStateError(format!(
+                "failed to get transaction receipts from store: {}",
+                err
+            ))
+        })?
+        .collect();
+
+        Ok(Some(receipts))
+    }
+
+    /// Commit the changes from transactions that were committed by another service. The changes
+    /// are only committed if they produce the `expected_state_root`; returns whether or not they
+    /// were committed.
+    #[cfg(feature = "state-sync")]
+    pub fn apply_transaction_receipts(
+        &mut self,
+        txn_receipts: Vec<TransactionReceipt>,
+        expected_state_root: &str,
+    ) -> Result<bool, ScabbardStateError> {
+        if self.pending_changes.is_some() {
+            return Err(ScabbardStateError(
+                "cannot apply transaction receipts while changes are pending".into(),
+            ));
+        }
+
+        let state_root = MerkleState::new(self.db.clone()).compute_state_id(
+            &self.current_state_root,
+            &receipts_into_transact_state_changes(&txn_receipts)?,
+        )?;
+        if state_root != expected_state_root {
+            return Ok(false);
+        }
+
+        self.commit_receipts(txn_receipts)?;
 
-        let batch_result = recv_result
-            .map_err(|_| ScabbardStateError("failed to receive result in reasonable time".into()))?
+        Ok(true)
+    }
+
+    pub fn prepare_change(&mut self, batch: BatchPair) -> Result<String, ScabbardStateError> {
+        let batch_result = self
+            .execute_batches(vec![batch])?
+            .pop()
             .ok_or_else(|| ScabbardStateError("no result returned from executor".into()))?;
 
         let batch_status = batch_result.clone().into();
-        let signature = batch.batch().header_signature();
+        let signature = batch_result.batch.batch().header_signature().to_string();
         self.batch_history
             .update_batch_status(&signature, batch_status);
 
@@ -268,61 +511,121 @@ impl ScabbardState {
             &self.current_state_root,
             &receipts_into_transact_state_changes(&txn_receipts)?,
         )?;
-        self.pending_changes = Some((signature.to_string(), txn_receipts));
+        self.pending_changes = Some((vec![signature], txn_receipts));
         Ok(state_root)
     }
 
-    pub fn commit(&mut self) -> Result<(), ScabbardStateError> {
-        match self.pending_changes.take() {
-            Some((signature, txn_receipts)) => {
-                let state_changes = receipts_into_transact_state_changes(&txn_receipts)?;
-                self.current_state_root = MerkleState::new(self.db.clone())
-                    .commit(&self.current_state_root, &state_changes)?;
+    /// Execute the batches in order and prepare to commit the changes of all the valid ones at
+    /// once. The status of each batch is recorded in the batch history; invalid batches make no
+    /// changes.
+    ///
+    /// Returns the state root that results from the changes, along with the valid batches.
+    pub fn prepare_changes(
+        &mut self,
+        batches: Vec<BatchPair>,
+    ) -> Result<(String, Vec<BatchPair>), ScabbardStateError> {
+        let mut valid_batches = vec![];
+        let mut signatures = vec![];
+        let mut txn_receipts = vec![];
+
+        for batch_result in self.execute_batches(batches)? {
+            let batch_status: BatchStatus = batch_result.clone().into();
+            let signature = batch_result.batch.batch().header_signature().to_string();
+            self.batch_history
+                .update_batch_status(&signature, batch_status.clone());
+
+            if let BatchStatus::Valid(_) = batch_status {
+                valid_batches.push(batch_result.batch);
+                signatures.push(signature);
+                txn_receipts.extend(batch_result.receipts);
+            }
+        }
 
-                self.write_current_state_root()?;
+        // Save the results and compute the resulting state root
+        let state_root = MerkleState::new(self.db.clone()).compute_state_id(
+            &self.current_state_root,
+            &receipts_into_transact_state_changes(&txn_receipts)?,
+        )?;
+        self.pending_changes = Some((signatures, txn_receipts));
+        Ok((state_root, valid_batches))
+    }
 
-                info!(
-                    "committed {} change(s) for new state root {}",
-                    state_changes.len(),
-                    self.current_state_root,
-                );
+    /// Execute the batches in order on top of the current state root, returning one result per
+    /// batch.
+    fn execute_batches(
+        &mut self,
+        batches: Vec<BatchPair>,
+    ) -> Result<Vec<BatchExecutionResult>, ScabbardStateError> {
+        let batch_count = batches.len();
+        if batch_count == 0 {
+            return Ok(vec![]);
+        }
 
-                let events = txn_receipts
-                    .iter()
-                    .cloned()
-                    .map(StateChangeEvent::try_from)
-                    .collect::<Result<Vec<_>, _>>()?;
+        // Setup the transact scheduler
+        let (result_tx, result_rx) = std::sync::mpsc::channel();
+        let mut scheduler = SerialScheduler::new(
+            Box::new(self.context_manager.clone()),
+            self.current_state_root.clone(),
+        )?;
+        scheduler.set_result_callback(Box::new(move |batch_result| {
+            if result_tx.send(batch_result).is_err() {
+                error!("Unable to send batch result; receiver must have dropped");
+            }
+        }))?;
 
-                self.transaction_receipt_store
-                    .write()
-                    .map_err(|err| {
-                        ScabbardStateError(format!(
-                            "transaction receipt store lock poisoned: {}",
-                            err
-                        ))
-                    })?
-                    .append(txn_receipts)
-                    .map_err(|err| {
-                        ScabbardStateError(format!(
-                            "failed to add transaction receipts to store: {}",
-                            err
-                        ))
-                    })?;
+        // Add the batches to, finalize, and execute the scheduler
+        for batch in batches {
+            scheduler.add_batch(batch)?;
+        }
+        scheduler.finalize()?;
+        self.executor
+            .execute(scheduler.take_task_iterator()?, scheduler.new_notifier()?)?;
 
-                for event in events {
-                    self.event_subscribers.retain(|subscriber| {
-                        match subscriber.handle_event(event.clone()) {
-                            Ok(()) => true,
-                            Err(StateSubscriberError::Unsubscribe) => false,
-                            Err(err @ StateSubscriberError::UnableToHandleEvent(_)) => {
-                                error!("{}", err);
-                                true
-                            }
-                        }
-                    });
+        // Get the results; the scheduler sends `None` once all batches have been executed
+        let mut batch_results = Vec::with_capacity(batch_count);
+        while batch_results.len() < batch_count {
+            match result_rx
+                .recv_timeout(Duration::from_secs(EXECUTION_TIMEOUT))
+                .map_err(|_| {
+                    ScabbardStateError("failed to receive result in reasonable time".into())
+                })? {
+                Some(batch_result) => batch_results.push(batch_result),
+                None => break,
+            }
+        }
+
+        if batch_results.len() != batch_count {
+            return Err(ScabbardStateError(format!(
+                "executor returned {} result(s) for {} batch(es)",
+                batch_results.len(),
+                batch_count
+            )));
+        }
+
+        Ok(batch_results)
+    }
+
+    /// The number of proposals committed to state; it survives restarts, and determines the
+    /// height of the next proposal.
+    #[cfg(feature = "coordinator-selection")]
+    pub fn committed_proposals(&self) -> u64 {
+        self.committed_proposals
+    }
+
+    pub fn commit(&mut self) -> Result<(), ScabbardStateError> {
+        match self.pending_changes.take() {
+            Some((signatures, txn_receipts)) => {
+                // Counted before the receipts are committed, so that the count is written with
+                // the new state root
+                #[cfg(feature = "coordinator-selection")]
+                {
+                    self.committed_proposals += 1;
                 }
+                self.commit_receipts(txn_receipts)?;
 
-                self.batch_history.commit(&signature);
+                for signature in signatures {
+                    self.batch_history.commit(&signature);
+                }
 
                 Ok(())
             }
@@ -330,6 +633,59 @@ impl ScabbardState {
         }
     }
 
+    /// Apply the state changes in the receipts to the current state root, store the receipts and
+    /// notify subscribers of the changes.
+    fn commit_receipts(
+        &mut self,
+        txn_receipts: Vec<TransactionReceipt>,
+    ) -> Result<(), ScabbardStateError> {
+        let state_changes = receipts_into_transact_state_changes(&txn_receipts)?;
+        self.current_state_root =
+            MerkleState::new(self.db.clone()).commit(&self.current_state_root, &state_changes)?;
+
+        self.write_current_state_root()?;
+
+        info!(
+            "committed {} change(s) for new state root {}",
+            state_changes.len(),
+            self.current_state_root,
+        );
+
+        let events = txn_receipts
+            .iter()
+            .cloned()
+            .map(StateChangeEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.transaction_receipt_store
+            .write()
+            .map_err(|err| {
+                ScabbardStateError(format!("transaction receipt store lock poisoned: {}", err))
+            })?
+            .append(txn_receipts)
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to add transaction receipts to store: {}",
+                    err
+                ))
+            })?;
+
+        for event in events {
+            self.event_subscribers.retain(|subscriber| {
+                match subscriber.handle_event(event.clone()) {
+                    Ok(()) => true,
+                    Err(StateSubscriberError::Unsubscribe) => false,
+                    Err(err @ StateSubscriberError::UnableToHandleEvent(_)) => {
+                        error!("{}", err);
+                        true
+                    }
+                }
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn rollback(&mut self) -> Result<(), ScabbardStateError> {
         match self.pending_changes.take() {
             Some((_, txn_receipts)) => info!(
@@ -373,6 +729,18 @@ fn receipts_into_transact_state_changes(
         .collect())
 }
 
+#[cfg(feature = "snapshot")]
+fn snapshot_state_changes(snapshot: &StateSnapshot) -> Vec<TransactStateChange> {
+    snapshot
+        .entries()
+        .iter()
+        .map(|entry| TransactStateChange::Set {
+            key: entry.address().into(),
+            value: entry.value().to_vec(),
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StateChangeEvent {
     pub id: String,
@@ -617,6 +985,10 @@ pub struct BatchHistory {
     history: HashMap<String, BatchInfo>,
     limit: usize,
     batch_subscribers: Vec<(HashSet<String>, Sender<BatchInfo>)>,
+    /// Records the time between a batch being added to the history and being committed, if
+    /// metrics are enabled for this service.
+    #[cfg(feature = "metrics")]
+    commit_latency: Option<Histogram>,
 }
 
 impl BatchHistory {
@@ -624,6 +996,11 @@ impl BatchHistory {
         Self::default()
     }
 
+    #[cfg(feature = "metrics")]
+    pub fn set_commit_latency_histogram(&mut self, histogram: Histogram) {
+        self.commit_latency = Some(histogram);
+    }
+
     pub fn add_batch(&mut self, signature: &str) {
         self.upsert_batch(signature.into(), BatchStatus::Pending);
     }
@@ -644,6 +1021,16 @@ impl BatchHistory {
             Some(info) => match info.status.clone() {
                 BatchStatus::Valid(txns) => {
                     info.set_status(BatchStatus::Committed(txns));
+
+                    #[cfg(feature = "metrics")]
+                    {
+                        if let (Some(histogram), Ok(latency)) = (
+                            self.commit_latency.as_ref(),
+                            SystemTime::now().duration_since(info.timestamp),
+                        ) {
+                            histogram.observe_duration(latency);
+                        }
+                    }
                 }
                 _ => {
                     error!(
@@ -775,6 +1162,8 @@ impl Default for BatchHistory {
             history: HashMap::new(),
             limit: DEFAULT_BATCH_HISTORY_SIZE,
             batch_subscribers: vec![],
+            #[cfg(feature = "metrics")]
+            commit_latency: None,
         }
     }
 }
@@ -848,7 +1237,7 @@ mod tests {
         families::command::make_command_transaction,
         protocol::{
             batch::BatchBuilder,
-            command::{BytesEntry, Command, SetState},
+            command::{BytesEntry, Command, ReturnInvalid, SetState},
         },
     };
 
@@ -1068,6 +1457,291 @@ mod tests {
         assert!(no_entries.is_empty());
     }
 
+    /// Verify that a snapshot exported from one `ScabbardState` can be imported into another.
+    ///
+    /// 1. Initialize a `ScabbardState`, set a value in state, and export a snapshot.
+    /// 2. Verify that the original state has committed transactions.
+    /// 3. Initialize a second `ScabbardState` and verify that it has no committed transactions.
+    /// 4. Verify that the state root computed from the snapshot matches the snapshot's root.
+    /// 5. Import the snapshot and verify that the second state's root and values match the
+    ///    original state.
+    #[cfg(feature = "snapshot")]
+    #[test]
+    fn snapshot_export_and_import() {
+        let source_paths = StatePaths::new("snapshot_export");
+        let mut source_state = ScabbardState::new(
+            &source_paths.state_db_path,
+            TEMP_DB_SIZE,
+            &source_paths.receipt_db_path,
+            TEMP_DB_SIZE,
+            vec![],
+        )
+        .expect("Failed to initialize state");
+
+        let address = "abcdef".to_string();
+        let value = b"value".to_vec();
+
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let batch = BatchBuilder::new()
+            .with_transactions(vec![
+                make_command_transaction(
+                    &[Command::SetState(SetState::new(vec![BytesEntry::new(
+                        address.clone(),
+                        value.clone(),
+                    )]))],
+                    &*signer,
+                )
+                .take()
+                .0,
+            ])
+            .build_pair(&*signer)
+            .expect("Failed to build batch");
+        source_state
+            .prepare_change(batch)
+            .expect("Failed to prepare change");
+        source_state.commit().expect("Failed to commit change");
+
+        let snapshot = source_state
+            .export_snapshot(None)
+            .expect("Failed to export snapshot")
+            .expect("Current state root not found");
+        assert_eq!(snapshot.state_root(), source_state.current_state_root());
+        assert!(source_state
+            .has_committed_transactions()
+            .expect("Failed to check for committed transactions"));
+
+        let target_paths = StatePaths::new("snapshot_import");
+        let mut target_state = ScabbardState::new(
+            &target_paths.state_db_path,
+            TEMP_DB_SIZE,
+            &target_paths.receipt_db_path,
+            TEMP_DB_SIZE,
+            vec!["admin".into()],
+        )
+        .expect("Failed to initialize state");
+        assert!(!target_state
+            .has_committed_transactions()
+            .expect("Failed to check for committed transactions"));
+
+        assert_eq!(
+            target_state
+                .compute_snapshot_state_root(&snapshot)
+                .expect("Failed to compute snapshot state root"),
+            snapshot.state_root(),
+        );
+
+        target_state
+            .import_snapshot(&snapshot)
+            .expect("Failed to import snapshot");
+        assert_eq!(target_state.current_state_root(), snapshot.state_root());
+        assert_eq!(
+            target_state
+                .get_state_at_address(&address)
+                .expect("Failed to get state for set address"),
+            Some(value),
+        );
+
+        // An unknown state root cannot be exported
+        assert!(source_state
+            .export_snapshot(Some("0123456789abcdef"))
+            .expect("Failed to export snapshot")
+            .is_none());
+    }
+
+    /// Verify that importing a snapshot into a `ScabbardState` that has already committed
+    /// transactions removes the receipts of those transactions, as happens when a service catches
+    /// up with a peer's snapshot.
+    ///
+    /// 1. Initialize two `ScabbardState`s and commit a different batch to each.
+    /// 2. Export a snapshot of the first state and import it into the second.
+    /// 3. Verify that the second state's root matches the first's, that the value set by its own
+    ///    batch is gone, and that it no longer has any committed transactions.
+    #[cfg(feature = "snapshot")]
+    #[test]
+    fn snapshot_import_removes_receipts() {
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let make_batch = |address: &str| {
+            BatchBuilder::new()
+                .with_transactions(vec![
+                    make_command_transaction(
+                        &[Command::SetState(SetState::new(vec![BytesEntry::new(
+                            address.to_string(),
+                            b"value".to_vec(),
+                        )]))],
+                        &*signer,
+                    )
+                    .take()
+                    .0,
+                ])
+                .build_pair(&*signer)
+                .expect("Failed to build batch")
+        };
+
+        let source_paths = StatePaths::new("snapshot_receipts_source");
+        let mut source_state = ScabbardState::new(
+            &source_paths.state_db_path,
+            TEMP_DB_SIZE,
+            &source_paths.receipt_db_path,
+            TEMP_DB_SIZE,
+            vec![],
+        )
+        .expect("Failed to initialize state");
+        source_state
+            .prepare_change(make_batch("abcdef"))
+            .expect("Failed to prepare change");
+        source_state.commit().expect("Failed to commit change");
+
+        let target_paths = StatePaths::new("snapshot_receipts_target");
+        let mut target_state = ScabbardState::new(
+            &target_paths.state_db_path,
+            TEMP_DB_SIZE,
+            &target_paths.receipt_db_path,
+            TEMP_DB_SIZE,
+            vec![],
+        )
+        .expect("Failed to initialize state");
+        target_state
+            .prepare_change(make_batch("012345"))
+            .expect("Failed to prepare change");
+        target_state.commit().expect("Failed to commit change");
+        assert!(target_state
+            .has_committed_transactions()
+            .expect("Failed to check for committed transactions"));
+
+        let snapshot = source_state
+            .export_snapshot(None)
+            .expect("Failed to export snapshot")
+            .expect("Current state root not found");
+        target_state
+            .import_snapshot(&snapshot)
+            .expect("Failed to import snapshot");
+
+        assert_eq!(
+            target_state.current_state_root(),
+            source_state.current_state_root()
+        );
+        assert_eq!(
+            target_state
+                .get_state_at_address("012345")
+                .expect("Failed to get state for unset address"),
+            None,
+        );
+        assert!(!target_state
+            .has_committed_transactions()
+            .expect("Failed to check for committed transactions"));
+    }
+
+    /// Verify that a `ScabbardState` that is missing committed transactions can catch up by
+    /// applying the receipts of those transactions.
+    ///
+    /// 1. Initialize two `ScabbardState`s with the same admin keys and commit a batch to the
+    ///    first.
+    /// 2. Verify that the first state's receipts are only applied to the second state if they
+    ///    produce the expected state root.
+    /// 3. Apply the receipts with the first state's root and verify that the states' roots,
+    ///    values, and last transaction IDs match.
+    /// 4. Verify that the second state has no receipts since its last transaction, and no
+    ///    receipts since a transaction it has not committed.
+    #[cfg(feature = "state-sync")]
+    #[test]
+    fn state_sync_apply_transaction_receipts() {
+        let ahead_paths = StatePaths::new("state_sync_ahead");
+        let mut ahead_state = ScabbardState::new(
+            &ahead_paths.state_db_path,
+            TEMP_DB_SIZE,
+            &ahead_paths.receipt_db_path,
+            TEMP_DB_SIZE,
+            vec![],
+        )
+        .expect("Failed to initialize state");
+
+        let behind_paths = StatePaths::new("state_sync_behind");
+        let mut behind_state = ScabbardState::new(
+            &behind_paths.state_db_path,
+            TEMP_DB_SIZE,
+            &behind_paths.receipt_db_path,
+            TEMP_DB_SIZE,
+            vec![],
+        )
+        .expect("Failed to initialize state");
+
+        let address = "abcdef".to_string();
+        let value = b"value".to_vec();
+
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let batch = BatchBuilder::new()
+            .with_transactions(vec![
+                make_command_transaction(
+                    &[Command::SetState(SetState::new(vec![BytesEntry::new(
+                        address.clone(),
+                        value.clone(),
+                    )]))],
+                    &*signer,
+                )
+                .take()
+                .0,
+            ])
+            .build_pair(&*signer)
+            .expect("Failed to build batch");
+        ahead_state
+            .prepare_change(batch)
+            .expect("Failed to prepare change");
+        ahead_state.commit().expect("Failed to commit change");
+
+        let receipts = ahead_state
+            .transaction_receipts_since(None)
+            .expect("Failed to get receipts")
+            .expect("Receipts not found");
+        assert_eq!(receipts.len(), 1);
+
+        assert!(!behind_state
+            .apply_transaction_receipts(receipts.clone(), "0123456789abcdef")
+            .expect("Failed to apply receipts"));
+        assert_ne!(
+            behind_state.current_state_root(),
+            ahead_state.current_state_root()
+        );
+
+        assert!(behind_state
+            .apply_transaction_receipts(receipts, ahead_state.current_state_root())
+            .expect("Failed to apply receipts"));
+        assert_eq!(
+            behind_state.current_state_root(),
+            ahead_state.current_state_root()
+        );
+        assert_eq!(
+            behind_state
+                .get_state_at_address(&address)
+                .expect("Failed to get state for set address"),
+            Some(value),
+        );
+
+        let last_transaction_id = behind_state
+            .last_transaction_id()
+            .expect("Failed to get last transaction ID")
+            .expect("No last transaction ID");
+        assert_eq!(
+            ahead_state
+                .last_transaction_id()
+                .expect("Failed to get last transaction ID"),
+            Some(last_transaction_id.clone()),
+        );
+
+        assert_eq!(
+            behind_state
+                .transaction_receipts_since(Some(&last_transaction_id))
+                .expect("Failed to get receipts"),
+            Some(vec![]),
+        );
+        assert!(behind_state
+            .transaction_receipts_since(Some("unknown"))
+            .expect("Failed to get receipts")
+            .is_none());
+    }
+
     struct StatePaths {
         _temp_dir_handle: TempDir,
         pub state_db_path: PathBuf,
@@ -1087,6 +1761,101 @@ mod tests {
         }
     }
 
+    /// Verify that the `ScabbardState::prepare_changes` method executes several batches and
+    /// commits the changes of the valid ones together.
+    ///
+    /// 1. Initialize a new, empty `ScabbardState` and add three batches to its batch history;
+    ///    the second batch contains an invalid transaction.
+    /// 2. Prepare the changes of all three batches and verify that only the first and third are
+    ///    returned as valid, and that the resulting state root is not yet the current one.
+    /// 3. Commit the changes and verify that the state root matches the prepared one, that the
+    ///    values set by the valid batches are in state, and that the batch history reports the
+    ///    valid batches as committed and the invalid batch as invalid.
+    #[test]
+    fn prepare_changes() {
+        let paths = StatePaths::new("prepare_changes");
+        let mut state = ScabbardState::new(
+            &paths.state_db_path,
+            TEMP_DB_SIZE,
+            &paths.receipt_db_path,
+            TEMP_DB_SIZE,
+            vec![],
+        )
+        .expect("Failed to initialize state");
+
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let build_batch = |command| {
+            BatchBuilder::new()
+                .with_transactions(vec![
+                    make_command_transaction(&[command], &*signer).take().0,
+                ])
+                .build_pair(&*signer)
+                .expect("Failed to build batch")
+        };
+
+        let batches = vec![
+            build_batch(Command::SetState(SetState::new(vec![BytesEntry::new(
+                "abcdef01".into(),
+                b"value1".to_vec(),
+            )]))),
+            build_batch(Command::ReturnInvalid(ReturnInvalid::new("invalid".into()))),
+            build_batch(Command::SetState(SetState::new(vec![BytesEntry::new(
+                "abcdef02".into(),
+                b"value2".to_vec(),
+            )]))),
+        ];
+        let signatures = batches
+            .iter()
+            .map(|batch| batch.batch().header_signature().to_string())
+            .collect::<Vec<_>>();
+        for signature in &signatures {
+            state.batch_history().add_batch(signature);
+        }
+
+        let initial_state_root = state.current_state_root().to_string();
+        let (state_root, valid_batches) = state
+            .prepare_changes(batches)
+            .expect("Failed to prepare changes");
+        assert_eq!(
+            valid_batches
+                .iter()
+                .map(|batch| batch.batch().header_signature())
+                .collect::<Vec<_>>(),
+            vec![signatures[0].as_str(), signatures[2].as_str()],
+        );
+        assert_eq!(state.current_state_root(), initial_state_root);
+
+        state.commit().expect("Failed to commit changes");
+        assert_eq!(state.current_state_root(), state_root);
+        assert_eq!(
+            state
+                .get_state_at_address("abcdef01")
+                .expect("Failed to get state"),
+            Some(b"value1".to_vec()),
+        );
+        assert_eq!(
+            state
+                .get_state_at_address("abcdef02")
+                .expect("Failed to get state"),
+            Some(b"value2".to_vec()),
+        );
+
+        let batch_infos = state
+            .batch_history()
+            .get_batch_info(signatures.iter().cloned().collect(), None)
+            .expect("Failed to get batch info")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("Failed to collect batch info");
+        for info in batch_infos {
+            match info.status {
+                BatchStatus::Committed(_) => assert_ne!(info.id, signatures[1]),
+                BatchStatus::Invalid(_) => assert_eq!(info.id, signatures[1]),
+                status => panic!("Unexpected status for batch {}: {:?}", info.id, status),
+            }
+        }
+    }
+
     fn mock_transaction_receipt(id: &str) -> TransactionReceipt {
         TransactionReceipt {
             transaction_id: id.into(),
@@ -0,0 +1,273 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Catch-up synchronization between scabbard services.
+//!
+//! A service that misses a commit (for instance, because it was offline when the batch was
+//! committed) can no longer verify proposals, because its state root no longer matches its
+//! peers'. When a service detects this, it sends its current state root and the ID of its last
+//! committed transaction to its peers. A peer that has committed that transaction responds with
+//! the receipts of every transaction it has committed since; the lagging service commits the
+//! changes in those receipts if they produce the peer's state root. If they do not, or if the peer
+//! does not know the requester's last transaction, the peer responds with a snapshot of its state
+//! instead.
+//!
+//! Until the service has caught up (or `SYNC_TIMEOUT` has elapsed), it does not propose batches
+//! and votes against every proposal it receives.
+
+use std::time::Duration;
+
+use protobuf::Message;
+use transact::protocol::receipt::TransactionReceipt;
+use transact::protos::{FromBytes, IntoBytes};
+
+use crate::protos::scabbard::{ScabbardMessage, ScabbardMessage_Type, SyncRequest, SyncResponse};
+use crate::snapshot::StateSnapshot;
+
+use super::error::ScabbardError;
+use super::shared::ScabbardShared;
+use super::state::ScabbardState;
+
+/// How long a service waits to catch up with its peers before taking part in consensus again
+const SYNC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Ask the given peers for any changes that this service is missing, without waiting for them to
+/// respond before taking part in consensus.
+pub fn send_sync_request(
+    service_id: &str,
+    peers: &[String],
+    shared: &ScabbardShared,
+    state: &ScabbardState,
+    snapshot_requested: bool,
+) -> Result<(), ScabbardError> {
+    let mut request = SyncRequest::new();
+    request.set_service_id(service_id.into());
+    request.set_state_root(state.current_state_root().into());
+    request.set_last_transaction_id(state.last_transaction_id()?.unwrap_or_default());
+    request.set_snapshot_requested(snapshot_requested);
+
+    let mut msg = ScabbardMessage::new();
+    msg.set_message_type(ScabbardMessage_Type::SYNC_REQUEST);
+    msg.set_sync_request(request);
+    let msg_bytes = msg
+        .write_to_bytes()
+        .map_err(|err| ScabbardError::SyncFailed(Box::new(err)))?;
+
+    let sender = shared.network_sender().ok_or(ScabbardError::NotConnected)?;
+
+    for peer in peers {
+        debug!("Requesting sync from {}", peer);
+        sender
+            .send(peer, msg_bytes.as_slice())
+            .map_err(|err| ScabbardError::SyncFailed(Box::new(err)))?;
+    }
+
+    Ok(())
+}
+
+/// Ask the given peers for any changes that this service is missing, and stop taking part in
+/// consensus until this service has caught up.
+pub fn request_sync(
+    service_id: &str,
+    peers: &[String],
+    shared: &mut ScabbardShared,
+    state: &ScabbardState,
+    snapshot_requested: bool,
+) -> Result<(), ScabbardError> {
+    if peers.is_empty() {
+        return Ok(());
+    }
+
+    send_sync_request(service_id, peers, shared, state, snapshot_requested)?;
+    shared.start_sync(SYNC_TIMEOUT);
+
+    Ok(())
+}
+
+/// Respond to a peer's request with the changes it is missing.
+///
+/// If the requester has committed a transaction that this service has not, this service may be
+/// the one that is behind, so it requests a sync from the requester as well.
+pub fn handle_sync_request(
+    service_id: &str,
+    request: &SyncRequest,
+    shared: &mut ScabbardShared,
+    state: &ScabbardState,
+) -> Result<(), ScabbardError> {
+    let requester = request.get_service_id();
+    if !shared.peer_services().contains(requester) {
+        warn!("Ignoring sync request from unknown service {}", requester);
+        return Ok(());
+    }
+
+    let mut response = SyncResponse::new();
+    response.set_service_id(service_id.into());
+    response.set_state_root(state.current_state_root().into());
+    response.set_last_transaction_id(state.last_transaction_id()?.unwrap_or_default());
+
+    let mut requester_may_be_ahead = false;
+
+    if request.get_state_root() != state.current_state_root() {
+        let receipts = if request.get_snapshot_requested() {
+            None
+        } else {
+            let requester_last_transaction_id = Some(request.get_last_transaction_id())
+                .filter(|transaction_id| !transaction_id.is_empty());
+            let receipts = state.transaction_receipts_since(requester_last_transaction_id)?;
+            requester_may_be_ahead = receipts.is_none();
+            receipts
+        };
+
+        match receipts {
+            Some(receipts) if !receipts.is_empty() => {
+                debug!(
+                    "Sending {} transaction receipt(s) to {}",
+                    receipts.len(),
+                    requester
+                );
+                response.set_transaction_receipts(
+                    receipts
+                        .into_iter()
+                        .map(TransactionReceipt::into_bytes)
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|err| ScabbardError::SyncFailed(Box::new(err)))?
+                        .into(),
+                );
+            }
+            _ => {
+                if let Some(snapshot) = state.export_snapshot(None)? {
+                    debug!(
+                        "Sending snapshot of state root {} to {}",
+                        snapshot.state_root(),
+                        requester
+                    );
+                    let mut snapshot_bytes = vec![];
+                    snapshot
+                        .write_to(&mut snapshot_bytes)
+                        .map_err(|err| ScabbardError::SyncFailed(Box::new(err)))?;
+                    response.set_snapshot(snapshot_bytes);
+                }
+            }
+        }
+    }
+
+    let mut msg = ScabbardMessage::new();
+    msg.set_message_type(ScabbardMessage_Type::SYNC_RESPONSE);
+    msg.set_sync_response(response);
+    let msg_bytes = msg
+        .write_to_bytes()
+        .map_err(|err| ScabbardError::SyncFailed(Box::new(err)))?;
+
+    shared
+        .network_sender()
+        .ok_or(ScabbardError::NotConnected)?
+        .send(requester, msg_bytes.as_slice())
+        .map_err(|err| ScabbardError::SyncFailed(Box::new(err)))?;
+
+    // Only one sync is requested at a time, so two services that have diverged don't request
+    // syncs from each other indefinitely
+    if requester_may_be_ahead && !shared.is_syncing() {
+        request_sync(service_id, &[requester.to_string()], shared, state, false)?;
+    }
+
+    Ok(())
+}
+
+/// Catch up with a peer using the changes it sent in response to this service's request.
+pub fn handle_sync_response(
+    service_id: &str,
+    response: &SyncResponse,
+    shared: &mut ScabbardShared,
+    state: &mut ScabbardState,
+) -> Result<(), ScabbardError> {
+    let responder = response.get_service_id();
+    if !shared.peer_services().contains(responder) {
+        warn!("Ignoring sync response from unknown service {}", responder);
+        return Ok(());
+    }
+
+    if response.get_state_root() == state.current_state_root() {
+        debug!("In sync with {}", responder);
+        shared.finish_sync();
+        return Ok(());
+    }
+
+    if state.has_pending_changes() {
+        debug!(
+            "Ignoring sync response from {} while a proposal is pending",
+            responder
+        );
+        return Ok(());
+    }
+
+    if !response.get_transaction_receipts().is_empty() {
+        let receipts = response
+            .get_transaction_receipts()
+            .iter()
+            .map(|bytes| TransactionReceipt::from_bytes(bytes))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| ScabbardError::SyncFailed(Box::new(err)))?;
+        let receipt_count = receipts.len();
+
+        if state.apply_transaction_receipts(receipts, response.get_state_root())? {
+            info!(
+                "Caught up with {} by committing {} transaction(s)",
+                responder, receipt_count
+            );
+            shared.finish_sync();
+        } else {
+            warn!(
+                "Transactions from {} do not produce its state root {}; requesting a snapshot",
+                responder,
+                response.get_state_root()
+            );
+            request_sync(service_id, &[responder.to_string()], shared, state, true)?;
+        }
+    } else if !response.get_snapshot().is_empty() {
+        // If this service has already committed the responder's last transaction, the responder
+        // is behind this service and its snapshot must not be imported
+        let responder_last_transaction_id = response.get_last_transaction_id();
+        if responder_last_transaction_id.is_empty()
+            || state.has_transaction(responder_last_transaction_id)?
+        {
+            debug!("Ignoring snapshot from {}, which is behind", responder);
+            return Ok(());
+        }
+
+        let snapshot = StateSnapshot::read_from(response.get_snapshot())
+            .map_err(|err| ScabbardError::SyncFailed(Box::new(err)))?;
+        if snapshot.state_root() != response.get_state_root()
+            || state.compute_snapshot_state_root(&snapshot)? != snapshot.state_root()
+        {
+            warn!(
+                "Ignoring snapshot from {} that does not match its state root {}",
+                responder,
+                response.get_state_root()
+            );
+            return Ok(());
+        }
+
+        state.import_snapshot(&snapshot)?;
+        info!(
+            "Caught up with {} by importing a snapshot of state root {}",
+            responder,
+            snapshot.state_root()
+        );
+        shared.finish_sync();
+    } else {
+        debug!("{} has no changes that this service is missing", responder);
+    }
+
+    Ok(())
+}
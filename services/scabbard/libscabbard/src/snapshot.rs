@@ -0,0 +1,173 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Portable snapshots of a scabbard service's state.
+//!
+//! A snapshot contains every entry in a scabbard service's state at a given state root, along with
+//! the state root hash itself. Snapshots may be imported into a scabbard service that has not yet
+//! committed any transactions; the state root is recomputed from the entries on import and the
+//! snapshot is rejected if it does not match.
+
+use std::io::{Read, Write};
+
+use crate::hex::{parse_hex, to_hex};
+
+/// The version of the snapshot format produced by this library
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// All entries in a scabbard service's state at a given state root.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    version: u32,
+    state_root: String,
+    entries: Vec<SnapshotEntry>,
+}
+
+impl StateSnapshot {
+    /// Create a new snapshot from the `entries` in state at the given `state_root`.
+    pub fn new(state_root: &str, entries: Vec<(String, Vec<u8>)>) -> Self {
+        Self {
+            version: SNAPSHOT_FORMAT_VERSION,
+            state_root: state_root.into(),
+            entries: entries
+                .into_iter()
+                .map(|(address, value)| SnapshotEntry { address, value })
+                .collect(),
+        }
+    }
+
+    /// Read a snapshot that was written with `write_to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot could not be read or parsed, or if it was written in an
+    /// unsupported format version.
+    pub fn read_from<R: Read>(reader: R) -> Result<Self, SnapshotError> {
+        let snapshot: Self = serde_json::from_reader(reader)
+            .map_err(|err| SnapshotError(format!("failed to read snapshot: {}", err)))?;
+
+        if snapshot.version != SNAPSHOT_FORMAT_VERSION {
+            return Err(SnapshotError(format!(
+                "unsupported snapshot format version: {}",
+                snapshot.version
+            )));
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Write the snapshot in a portable format.
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), SnapshotError> {
+        serde_json::to_writer(writer, self)
+            .map_err(|err| SnapshotError(format!("failed to write snapshot: {}", err)))
+    }
+
+    /// Get the format version of the snapshot.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Get the state root hash that the snapshot's entries were taken from.
+    pub fn state_root(&self) -> &str {
+        &self.state_root
+    }
+
+    /// Get the entries in the snapshot.
+    pub fn entries(&self) -> &[SnapshotEntry] {
+        &self.entries
+    }
+}
+
+/// An entry in a `StateSnapshot`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    address: String,
+    #[serde(with = "hex_value")]
+    value: Vec<u8>,
+}
+
+impl SnapshotEntry {
+    /// Get the address of the entry.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Get the value of the entry.
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+/// An error that occurred while reading or writing a `StateSnapshot`.
+#[derive(Debug)]
+pub struct SnapshotError(pub String);
+
+impl std::error::Error for SnapshotError {}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Values are stored as hex strings, which are considerably more compact than the JSON array that
+/// serde would produce for a `Vec<u8>`.
+mod hex_value {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use super::{parse_hex, to_hex};
+
+    pub fn serialize<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&to_hex(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        parse_hex(&hex).map_err(|err| D::Error::custom(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a snapshot can be written and read back.
+    #[test]
+    fn snapshot_round_trip() {
+        let snapshot = StateSnapshot::new(
+            "abcd",
+            vec![
+                ("012345".into(), b"value1".to_vec()),
+                ("abcdef".into(), b"value2".to_vec()),
+            ],
+        );
+
+        let mut bytes = vec![];
+        snapshot
+            .write_to(&mut bytes)
+            .expect("Failed to write snapshot");
+
+        let read = StateSnapshot::read_from(bytes.as_slice()).expect("Failed to read snapshot");
+        assert_eq!(read, snapshot);
+        assert_eq!(read.entries()[1].value(), b"value2");
+    }
+
+    /// Verify that a snapshot with an unsupported version is rejected.
+    #[test]
+    fn snapshot_unsupported_version() {
+        let bytes = br#"{"version": 2, "state_root": "abcd", "entries": []}"#;
+
+        assert!(StateSnapshot::read_from(&bytes[..]).is_err());
+    }
+}
@@ -390,6 +390,29 @@ impl ConfigBuilder {
                     None => None,
                 }
             }),
+            #[cfg(feature = "registry-signatures")]
+            registry_publisher_keys: self.partial_configs.iter().find_map(|p| {
+                match p.registry_publisher_keys() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }
+            }),
+            #[cfg(feature = "circuit-relay")]
+            relay_nodes: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.relay_nodes() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }),
+            #[cfg(feature = "circuit-relay")]
+            relay_routes: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.relay_routes() {
+                    Some(v) => Some((v, p.source())),
+                    None => None,
+                }),
             strict_ref_counts: self
                 .partial_configs
                 .iter()
@@ -398,6 +421,14 @@ impl ConfigBuilder {
                     None => None,
                 })
                 .ok_or_else(|| ConfigError::MissingValue("strict_ref_counts".to_string()))?,
+            #[cfg(feature = "tls-crl")]
+            tls_crl_file: self
+                .partial_configs
+                .iter()
+                .find_map(|p| match p.tls_crl_file() {
+                    Some(v) => Some((get_tls_file_path(&tls_cert_dir.0, &v), p.source())),
+                    None => None,
+                }),
         })
     }
 }
@@ -150,6 +150,36 @@ impl<'a> PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
                 .with_oauth_openid_url(self.matches.value_of("oauth_openid_url").map(String::from))
         }
 
+        #[cfg(feature = "registry-signatures")]
+        {
+            partial_config = partial_config.with_registry_publisher_keys(
+                self.matches
+                    .values_of("registry_publisher_keys")
+                    .map(|values| values.map(String::from).collect::<Vec<String>>()),
+            )
+        }
+
+        #[cfg(feature = "circuit-relay")]
+        {
+            partial_config = partial_config
+                .with_relay_nodes(
+                    self.matches
+                        .values_of("relay_nodes")
+                        .map(|values| values.map(String::from).collect::<Vec<String>>()),
+                )
+                .with_relay_routes(
+                    self.matches
+                        .values_of("relay_routes")
+                        .map(|values| values.map(String::from).collect::<Vec<String>>()),
+                )
+        }
+
+        #[cfg(feature = "tls-crl")]
+        {
+            partial_config = partial_config
+                .with_tls_crl_file(self.matches.value_of("tls_crl_file").map(String::from));
+        }
+
         Ok(partial_config)
     }
 }
@@ -84,7 +84,15 @@ pub struct Config {
     oauth_redirect_url: Option<(String, ConfigSource)>,
     #[cfg(feature = "oauth")]
     oauth_openid_url: Option<(String, ConfigSource)>,
+    #[cfg(feature = "registry-signatures")]
+    registry_publisher_keys: Option<(Vec<String>, ConfigSource)>,
+    #[cfg(feature = "circuit-relay")]
+    relay_nodes: Option<(Vec<String>, ConfigSource)>,
+    #[cfg(feature = "circuit-relay")]
+    relay_routes: Option<(Vec<String>, ConfigSource)>,
     strict_ref_counts: (bool, ConfigSource),
+    #[cfg(feature = "tls-crl")]
+    tls_crl_file: Option<(String, ConfigSource)>,
 }
 
 impl Config {
@@ -267,10 +275,46 @@ impl Config {
         }
     }
 
+    #[cfg(feature = "registry-signatures")]
+    pub fn registry_publisher_keys(&self) -> Option<&[String]> {
+        if let Some((list, _)) = &self.registry_publisher_keys {
+            Some(list)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    pub fn relay_nodes(&self) -> Option<&[String]> {
+        if let Some((list, _)) = &self.relay_nodes {
+            Some(list)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    pub fn relay_routes(&self) -> Option<&[String]> {
+        if let Some((list, _)) = &self.relay_routes {
+            Some(list)
+        } else {
+            None
+        }
+    }
+
     pub fn strict_ref_counts(&self) -> bool {
         self.strict_ref_counts.0
     }
 
+    #[cfg(feature = "tls-crl")]
+    pub fn tls_crl_file(&self) -> Option<&str> {
+        if let Some((file, _)) = &self.tls_crl_file {
+            Some(file)
+        } else {
+            None
+        }
+    }
+
     pub fn config_dir_source(&self) -> &ConfigSource {
         &self.config_dir.1
     }
@@ -450,10 +494,46 @@ impl Config {
         }
     }
 
+    #[cfg(feature = "registry-signatures")]
+    pub fn registry_publisher_keys_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.registry_publisher_keys {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    pub fn relay_nodes_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.relay_nodes {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    pub fn relay_routes_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.relay_routes {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
     fn strict_ref_counts_source(&self) -> &ConfigSource {
         &self.strict_ref_counts.1
     }
 
+    #[cfg(feature = "tls-crl")]
+    fn tls_crl_file_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.tls_crl_file {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
     #[allow(clippy::cognitive_complexity)]
     /// Displays the configuration value along with where the value was sourced from.
     pub fn log_as_debug(&self) {
@@ -638,11 +718,38 @@ impl Config {
                 );
             }
         }
+        #[cfg(feature = "registry-signatures")]
+        {
+            if let (Some(list), Some(source)) = (
+                self.registry_publisher_keys(),
+                self.registry_publisher_keys_source(),
+            ) {
+                debug!(
+                    "Config: registry_publisher_keys: {:?} (source: {:?})",
+                    list, source
+                );
+            }
+        }
+        #[cfg(feature = "circuit-relay")]
+        {
+            if let (Some(list), Some(source)) = (self.relay_nodes(), self.relay_nodes_source()) {
+                debug!("Config: relay_nodes: {:?} (source: {:?})", list, source);
+            }
+            if let (Some(list), Some(source)) = (self.relay_routes(), self.relay_routes_source()) {
+                debug!("Config: relay_routes: {:?} (source: {:?})", list, source);
+            }
+        }
         debug!(
             "Config: strict_ref_counts: {:?} (source: {:?})",
             self.strict_ref_counts(),
             self.strict_ref_counts_source()
         );
+        #[cfg(feature = "tls-crl")]
+        {
+            if let (Some(file), Some(source)) = (self.tls_crl_file(), self.tls_crl_file_source()) {
+                debug!("Config: tls_crl_file: {} (source: {:?})", file, source);
+            }
+        }
     }
 
     #[cfg(feature = "rest-api-cors")]
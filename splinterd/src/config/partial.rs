@@ -77,7 +77,15 @@ pub struct PartialConfig {
     oauth_redirect_url: Option<String>,
     #[cfg(feature = "oauth")]
     oauth_openid_url: Option<String>,
+    #[cfg(feature = "registry-signatures")]
+    registry_publisher_keys: Option<Vec<String>>,
+    #[cfg(feature = "circuit-relay")]
+    relay_nodes: Option<Vec<String>>,
+    #[cfg(feature = "circuit-relay")]
+    relay_routes: Option<Vec<String>>,
     strict_ref_counts: Option<bool>,
+    #[cfg(feature = "tls-crl")]
+    tls_crl_file: Option<String>,
 }
 
 impl PartialConfig {
@@ -129,7 +137,15 @@ impl PartialConfig {
             oauth_redirect_url: None,
             #[cfg(feature = "oauth")]
             oauth_openid_url: None,
+            #[cfg(feature = "registry-signatures")]
+            registry_publisher_keys: None,
+            #[cfg(feature = "circuit-relay")]
+            relay_nodes: None,
+            #[cfg(feature = "circuit-relay")]
+            relay_routes: None,
             strict_ref_counts: None,
+            #[cfg(feature = "tls-crl")]
+            tls_crl_file: None,
         }
     }
 
@@ -280,10 +296,30 @@ impl PartialConfig {
         self.oauth_openid_url.clone()
     }
 
+    #[cfg(feature = "registry-signatures")]
+    pub fn registry_publisher_keys(&self) -> Option<Vec<String>> {
+        self.registry_publisher_keys.clone()
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    pub fn relay_nodes(&self) -> Option<Vec<String>> {
+        self.relay_nodes.clone()
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    pub fn relay_routes(&self) -> Option<Vec<String>> {
+        self.relay_routes.clone()
+    }
+
     pub fn strict_ref_counts(&self) -> Option<bool> {
         self.strict_ref_counts
     }
 
+    #[cfg(feature = "tls-crl")]
+    pub fn tls_crl_file(&self) -> Option<String> {
+        self.tls_crl_file.clone()
+    }
+
     /// Adds a `config_dir` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -668,6 +704,47 @@ impl PartialConfig {
         self
     }
 
+    #[cfg(feature = "registry-signatures")]
+    /// Adds a `registry_publisher_keys` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_publisher_keys` - Hex-encoded public keys of the publishers whose signatures
+    ///   remote registry files must carry
+    ///
+    pub fn with_registry_publisher_keys(
+        mut self,
+        registry_publisher_keys: Option<Vec<String>>,
+    ) -> Self {
+        self.registry_publisher_keys = registry_publisher_keys;
+        self
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    /// Adds a `relay_nodes` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `relay_nodes` - The nodes allowed to relay messages for circuits they are not a member of
+    ///
+    pub fn with_relay_nodes(mut self, relay_nodes: Option<Vec<String>>) -> Self {
+        self.relay_nodes = relay_nodes;
+        self
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    /// Adds a `relay_routes` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `relay_routes` - Routes to nodes that are reached through another node, in the form
+    ///   `<destination node ID>=<next hop node ID>`
+    ///
+    pub fn with_relay_routes(mut self, relay_routes: Option<Vec<String>>) -> Self {
+        self.relay_routes = relay_routes;
+        self
+    }
+
     /// Adds a `strict_ref_counts` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -678,4 +755,17 @@ impl PartialConfig {
         self.strict_ref_counts = strict_ref_counts;
         self
     }
+
+    #[cfg(feature = "tls-crl")]
+    /// Adds a `tls_crl_file` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tls_crl_file` - Certificate revocation list used to reject the certificates of remote
+    ///   nodes (*.pem file)
+    ///
+    pub fn with_tls_crl_file(mut self, tls_crl_file: Option<String>) -> Self {
+        self.tls_crl_file = tls_crl_file;
+        self
+    }
 }
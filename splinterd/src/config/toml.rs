@@ -67,6 +67,14 @@ struct TomlConfig {
     oauth_redirect_url: Option<String>,
     #[cfg(feature = "oauth")]
     oauth_openid_url: Option<String>,
+    #[cfg(feature = "registry-signatures")]
+    registry_publisher_keys: Option<Vec<String>>,
+    #[cfg(feature = "circuit-relay")]
+    relay_nodes: Option<Vec<String>>,
+    #[cfg(feature = "circuit-relay")]
+    relay_routes: Option<Vec<String>>,
+    #[cfg(feature = "tls-crl")]
+    tls_crl_file: Option<String>,
 
     // Deprecated values
     cert_dir: Option<String>,
@@ -180,6 +188,24 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
                 .with_oauth_openid_url(self.toml_config.oauth_openid_url);
         }
 
+        #[cfg(feature = "registry-signatures")]
+        {
+            partial_config = partial_config
+                .with_registry_publisher_keys(self.toml_config.registry_publisher_keys);
+        }
+
+        #[cfg(feature = "circuit-relay")]
+        {
+            partial_config = partial_config
+                .with_relay_nodes(self.toml_config.relay_nodes)
+                .with_relay_routes(self.toml_config.relay_routes);
+        }
+
+        #[cfg(feature = "tls-crl")]
+        {
+            partial_config = partial_config.with_tls_crl_file(self.toml_config.tls_crl_file);
+        }
+
         // deprecated values, only set if the current value was not set
         if partial_config.tls_cert_dir().is_none() {
             partial_config = partial_config.with_tls_cert_dir(self.toml_config.cert_dir)
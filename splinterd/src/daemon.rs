@@ -18,28 +18,55 @@ use std::error::Error;
 use std::fmt;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "registry-notifications")]
+use std::sync::mpsc::{channel, Sender};
 use std::sync::Arc;
 use std::thread;
 use std::time::Duration;
 
+#[cfg(any(
+    feature = "challenge-authorization",
+    feature = "registry-signatures",
+    feature = "scabbard-pbft"
+))]
+use cylinder::Context;
+#[cfg(feature = "registry-signatures")]
+use cylinder::PublicKey;
+#[cfg(any(feature = "challenge-authorization", feature = "scabbard-pbft"))]
+use cylinder::{load_key, Signer};
 use cylinder::{secp256k1::Secp256k1Context, VerifierFactory};
+#[cfg(all(feature = "health", feature = "database"))]
+use health::checks::DatabaseCheck;
 #[cfg(feature = "health")]
-use health::HealthService;
+use health::{
+    checks::{PeerConnectivityCheck, RemoteRegistryCheck, ServiceCheck},
+    HealthService,
+};
 #[cfg(feature = "service-arg-validation")]
 use scabbard::service::ScabbardArgValidator;
 use scabbard::service::ScabbardFactory;
 use splinter::admin::rest_api::CircuitResourceProvider;
 use splinter::admin::service::{admin_service_id, AdminService};
 use splinter::admin::store::yaml::YamlAdminServiceStore;
+#[cfg(feature = "metrics")]
+use splinter::admin::store::AdminServiceStore;
 #[cfg(any(feature = "biome-credentials", feature = "biome-key-management"))]
 use splinter::biome::rest_api::{BiomeRestResourceManager, BiomeRestResourceManagerBuilder};
+#[cfg(feature = "circuit-durability")]
+use splinter::circuit::durable::DurableDelivery;
 use splinter::circuit::handlers::{
     AdminDirectMessageHandler, CircuitDirectMessageHandler, CircuitErrorHandler,
     CircuitMessageHandler, ServiceConnectRequestHandler, ServiceDisconnectRequestHandler,
 };
+#[cfg(feature = "circuit-durability")]
+use splinter::circuit::handlers::{CircuitDurableMessageAckHandler, CircuitDurableMessageHandler};
+#[cfg(feature = "circuit-relay")]
+use splinter::circuit::routing::relay::RelayTable;
 use splinter::circuit::routing::{memory::RoutingTable, RoutingTableReader, RoutingTableWriter};
 use splinter::keys::insecure::AllowAllKeyPermissionManager;
 use splinter::mesh::Mesh;
+#[cfg(feature = "metrics")]
+use splinter::metrics::MetricsRegistry;
 use splinter::network::auth::AuthorizationManager;
 use splinter::network::connection_manager::{
     authorizers::Authorizers, authorizers::InprocAuthorizer, ConnectionManager, Connector,
@@ -52,12 +79,18 @@ use splinter::orchestrator::{NewOrchestratorError, ServiceOrchestrator};
 use splinter::peer::interconnect::NetworkMessageSender;
 use splinter::peer::interconnect::PeerInterconnectBuilder;
 use splinter::peer::PeerManager;
+#[cfg(feature = "registry-notifications")]
+use splinter::peer::PeerManagerConnector;
 use splinter::protos::circuit::CircuitMessageType;
 use splinter::protos::network::NetworkMessageType;
+#[cfg(feature = "health")]
+use splinter::registry::RemoteYamlRefreshStatusHandle;
 use splinter::registry::{
     LocalYamlRegistry, RegistryReader, RemoteYamlRegistry, RemoteYamlShutdownHandle, RwRegistry,
     UnifiedRegistry,
 };
+#[cfg(feature = "registry-notifications")]
+use splinter::registry::{Node, RegistryNotification, RegistrySubscriber, RegistrySubscriberError};
 #[cfg(feature = "authorization-handler-allow-keys")]
 use splinter::rest_api::auth::allow_keys::AllowKeysAuthorizationHandler;
 #[cfg(feature = "authorization-handler-maintenance")]
@@ -75,7 +108,7 @@ use splinter::rest_api::{
 };
 #[cfg(feature = "service-arg-validation")]
 use splinter::service::validation::ServiceArgValidator;
-use splinter::service::{self, ServiceProcessor, ShutdownHandle};
+use splinter::service::{self, ServiceFactory, ServiceProcessor, ShutdownHandle};
 use splinter::transport::{
     inproc::InprocTransport, multi::MultiTransport, AcceptError, ConnectError, Connection,
     Incoming, ListenError, Listener, Transport,
@@ -134,6 +167,12 @@ pub struct SplinterDaemon {
     oauth_redirect_url: Option<String>,
     #[cfg(feature = "oauth")]
     oauth_openid_url: Option<String>,
+    #[cfg(feature = "registry-signatures")]
+    registry_publisher_keys: Vec<PublicKey>,
+    #[cfg(feature = "circuit-relay")]
+    relay_nodes: Vec<String>,
+    #[cfg(feature = "circuit-relay")]
+    relay_routes: Vec<(String, String)>,
     heartbeat: u64,
     strict_ref_counts: bool,
 }
@@ -146,6 +185,9 @@ impl SplinterDaemon {
         let mut service_transport = InprocTransport::default();
         transport.add_transport(Box::new(service_transport.clone()));
 
+        #[cfg(feature = "metrics")]
+        let metrics = MetricsRegistry::new();
+
         #[cfg(feature = "database")]
         let db_url = self.db_url.clone().ok_or_else(|| {
             StartError::StorageError(
@@ -276,11 +318,43 @@ impl SplinterDaemon {
         #[cfg(feature = "health")]
         internal_service_listeners.push(transport.listen("inproc://health_service")?);
 
+        let (registry, registry_shutdown) = create_registry(
+            &self.state_dir,
+            &self.registries,
+            self.registry_auto_refresh,
+            self.registry_forced_refresh,
+            #[cfg(feature = "registry-signatures")]
+            &self.registry_publisher_keys,
+            #[cfg(feature = "registry-database")]
+            &*store_factory,
+        )?;
+
         info!("Starting SpinterNode with ID {}", self.node_id);
+        #[cfg(not(feature = "challenge-authorization"))]
         let authorization_manager =
             AuthorizationManager::new(self.node_id.clone()).map_err(|err| {
                 StartError::NetworkError(format!("Unable to create authorization manager: {}", err))
             })?;
+        #[cfg(feature = "challenge-authorization")]
+        let authorization_manager = match load_node_signer(&self.state_dir)? {
+            Some(signer) => AuthorizationManager::new_with_challenge_authorization(
+                self.node_id.clone(),
+                signer,
+                Secp256k1Context::new().new_verifier(),
+                registry.clone_box_as_reader(),
+            ),
+            None => {
+                warn!(
+                    "No node key found in {}: challenge authorization is disabled and only \
+                     trust authorization will be used",
+                    Path::new(&self.state_dir).join("keys").display()
+                );
+                AuthorizationManager::new(self.node_id.clone())
+            }
+        }
+        .map_err(|err| {
+            StartError::NetworkError(format!("Unable to create authorization manager: {}", err))
+        })?;
 
         // Allowing unused_mut because inproc_ids must be mutable if feature health is enabled
         #[allow(unused_mut)]
@@ -320,18 +394,32 @@ impl SplinterDaemon {
         let connection_connector = connection_manager.connector();
         let connection_manager_shutdown = connection_manager.shutdown_signaler();
 
-        let peer_manager = PeerManager::builder()
+        let mut peer_manager_builder = PeerManager::builder()
             .with_connector(connection_connector.clone())
             .with_identity(self.node_id.to_string())
-            .with_strict_ref_counts(self.strict_ref_counts)
-            .start()
-            .map_err(|err| {
-                StartError::NetworkError(format!("Unable to start peer manager: {}", err))
-            })?;
+            .with_strict_ref_counts(self.strict_ref_counts);
+        #[cfg(feature = "metrics")]
+        {
+            peer_manager_builder = peer_manager_builder.with_metrics(metrics.clone());
+        }
+        let peer_manager = peer_manager_builder.start().map_err(|err| {
+            StartError::NetworkError(format!("Unable to start peer manager: {}", err))
+        })?;
 
         let peer_connector = peer_manager.connector();
         let peer_manager_shutdown = peer_manager.shutdown_signaler();
 
+        // Reconnect peers whose endpoints or keys are changed in the registry
+        #[cfg(feature = "registry-notifications")]
+        registry
+            .add_subscriber(Box::new(PeerRegistryUpdater::new(peer_connector.clone())?))
+            .map_err(|err| {
+                StartError::RegistryError(format!(
+                    "Unable to subscribe to registry notifications: {}",
+                    err
+                ))
+            })?;
+
         // Listen for services
         Self::listen_for_services(
             connection_connector.clone(),
@@ -383,12 +471,60 @@ impl SplinterDaemon {
 
         let network_sender = interconnect.new_network_sender();
 
+        #[cfg(feature = "circuit-relay")]
+        let relay_table = {
+            let relay_table = RelayTable::default();
+            for node_id in &self.relay_nodes {
+                relay_table
+                    .add_relay_node(node_id.to_string())
+                    .map_err(|err| {
+                        StartError::NetworkError(format!("Unable to add relay node: {}", err))
+                    })?;
+            }
+            for (destination, next_hop) in &self.relay_routes {
+                relay_table
+                    .add_route(destination.to_string(), next_hop.to_string())
+                    .map_err(|err| {
+                        StartError::NetworkError(format!("Unable to add relay route: {}", err))
+                    })?;
+            }
+            relay_table
+        };
+
+        #[cfg(feature = "circuit-durability")]
+        let durable_message_store = store_factory.get_durable_message_store();
+        #[cfg(feature = "circuit-durability")]
+        let durable_delivery = DurableDelivery::new(
+            durable_message_store.clone(),
+            Box::new(network_sender.clone()),
+            routing_reader.clone(),
+        );
+        #[cfg(feature = "circuit-durability")]
+        let durable_retry_join = durable_delivery
+            .start_retry_thread(&peer_connector)
+            .map_err(|err| {
+                StartError::NetworkError(format!(
+                    "Unable to start durable message delivery: {}",
+                    err
+                ))
+            })?;
+
         // Set up the Circuit dispatcher
         let circuit_dispatcher = set_up_circuit_dispatcher(
             network_sender.clone(),
             &self.node_id,
             routing_reader.clone(),
             routing_writer.clone(),
+            #[cfg(feature = "metrics")]
+            &metrics,
+            #[cfg(feature = "circuit-relay")]
+            relay_table,
+            #[cfg(feature = "circuit-durability")]
+            durable_message_store.clone(),
+            #[cfg(feature = "circuit-durability")]
+            durable_delivery,
+            #[cfg(feature = "challenge-authorization")]
+            &authorization_manager,
         );
         let circuit_dispatch_loop = DispatchLoopBuilder::new()
             .with_dispatcher(circuit_dispatcher)
@@ -399,6 +535,12 @@ impl SplinterDaemon {
             })?;
         let circuit_dispatch_sender = circuit_dispatch_loop.new_dispatcher_sender();
 
+        #[cfg(feature = "metrics")]
+        {
+            register_queue_depth_metric(&metrics, "circuit", circuit_dispatch_sender.clone());
+            register_queue_depth_metric(&metrics, "network", network_dispatcher_sender.clone());
+        }
+
         let circuit_dispatcher_shutdown = circuit_dispatch_loop.shutdown_signaler();
 
         // Set up the Network dispatcher
@@ -465,29 +607,54 @@ impl SplinterDaemon {
         let signing_context = Secp256k1Context::new();
         let admin_service_verifier = signing_context.new_verifier();
 
+        let service_factories: Vec<Box<dyn ServiceFactory>> = vec![Box::new({
+            let scabbard_factory =
+                ScabbardFactory::new(None, None, None, None, Box::new(signing_context));
+            #[cfg(feature = "metrics")]
+            let scabbard_factory = scabbard_factory.with_metrics(metrics.clone());
+            #[cfg(feature = "scabbard-pbft")]
+            let scabbard_factory = match load_node_signer(&self.state_dir) {
+                Ok(Some(signer)) => scabbard_factory.with_signer(signer),
+                Ok(None) => {
+                    warn!(
+                        "Scabbard services cannot use PBFT consensus: no node key found in {}",
+                        Path::new(&self.state_dir).join("keys").display()
+                    );
+                    scabbard_factory
+                }
+                Err(err) => {
+                    warn!("Scabbard services cannot use PBFT consensus: {}", err);
+                    scabbard_factory
+                }
+            };
+            scabbard_factory
+        })];
+
+        #[cfg(not(feature = "circuit-durability"))]
         let (orchestrator, orchestator_join_handles) = ServiceOrchestrator::new(
-            vec![Box::new(ScabbardFactory::new(
-                None,
-                None,
-                None,
-                None,
-                Box::new(signing_context),
-            ))],
+            service_factories,
             orchestrator_connection,
             ORCHESTRATOR_INCOMING_CAPACITY,
             ORCHESTRATOR_OUTGOING_CAPACITY,
             ORCHESTRATOR_CHANNEL_CAPACITY,
         )?;
+        #[cfg(feature = "circuit-durability")]
+        let (orchestrator, orchestator_join_handles) =
+            ServiceOrchestrator::with_durable_message_store(
+                service_factories,
+                orchestrator_connection,
+                ORCHESTRATOR_INCOMING_CAPACITY,
+                ORCHESTRATOR_OUTGOING_CAPACITY,
+                ORCHESTRATOR_CHANNEL_CAPACITY,
+                durable_message_store,
+            )?;
         let orchestrator_resources = orchestrator.resources();
-
-        let (registry, registry_shutdown) = create_registry(
-            &self.state_dir,
-            &self.registries,
-            self.registry_auto_refresh,
-            self.registry_forced_refresh,
-            #[cfg(feature = "registry-database")]
-            &*store_factory,
-        )?;
+        #[cfg(feature = "health")]
+        let service_lister = orchestrator.service_lister();
+        #[cfg(feature = "health")]
+        let health_peer_connector = peer_connector.clone();
+        #[cfg(feature = "health")]
+        let health_admin_service_store = admin_service_store.clone();
 
         let (admin_service, admin_notification_join) = AdminService::new(
             &self.node_id,
@@ -513,6 +680,30 @@ impl SplinterDaemon {
             StartError::AdminServiceError(format!("unable to create admin service: {}", err))
         })?;
 
+        #[cfg(feature = "challenge-authorization")]
+        admin_service
+            .set_authorization_inquisitor(authorization_manager.authorization_inquisitor())
+            .map_err(|err| {
+                StartError::AdminServiceError(format!("unable to create admin service: {}", err))
+            })?;
+
+        #[cfg(feature = "metrics")]
+        {
+            let admin_service_store = admin_service_store.clone();
+            metrics.register_gauge_fn(
+                "splinter_admin_pending_proposals",
+                "Number of circuit proposals that have not been accepted or rejected",
+                &[],
+                move || match admin_service_store.list_proposals(&[]) {
+                    Ok(proposals) => proposals.len() as f64,
+                    Err(err) => {
+                        error!("Unable to count pending proposals: {}", err);
+                        f64::NAN
+                    }
+                },
+            );
+        }
+
         let node_id = self.node_id.clone();
         let display_name = self.display_name.clone();
         #[cfg(feature = "service-endpoint")]
@@ -589,6 +780,17 @@ impl SplinterDaemon {
                     )
                     .resources(),
                 );
+
+            #[cfg(feature = "metrics")]
+            {
+                let metrics = metrics.clone();
+                rest_api_builder =
+                    rest_api_builder.add_resource(Resource::build("/metrics").add_method(
+                        Method::Get,
+                        routes::METRICS_READ_PERMISSION,
+                        move |_, _| routes::get_metrics(&metrics),
+                    ));
+            }
         }
         #[cfg(not(feature = "authorization"))]
         {
@@ -608,6 +810,15 @@ impl SplinterDaemon {
                         )
                     }),
                 );
+
+            #[cfg(feature = "metrics")]
+            {
+                let metrics = metrics.clone();
+                rest_api_builder = rest_api_builder.add_resource(
+                    Resource::build("/metrics")
+                        .add_method(Method::Get, move |_, _| routes::get_metrics(&metrics)),
+                );
+            }
         }
 
         #[cfg(feature = "rest-api-cors")]
@@ -721,7 +932,19 @@ impl SplinterDaemon {
         let mut health_service_processor_join_handle: Option<_> = None;
         #[cfg(feature = "health")]
         {
-            let health_service = HealthService::new(&self.node_id);
+            let health_service = HealthService::new(&self.node_id)
+                .with_check(Box::new(PeerConnectivityCheck::new(health_peer_connector)))
+                .with_check(Box::new(RemoteRegistryCheck::new(
+                    registry_shutdown.remote_yaml_refresh_status_handles(),
+                )))
+                .with_check(Box::new(ServiceCheck::new(
+                    &self.node_id,
+                    service_lister,
+                    health_admin_service_store,
+                )));
+            #[cfg(feature = "database")]
+            let health_service =
+                health_service.with_check(Box::new(DatabaseCheck::new(&*store_factory)));
             rest_api_builder = rest_api_builder.add_resources(health_service.resources());
 
             health_service_processor_join_handle.replace(start_health_service(
@@ -786,6 +1009,8 @@ impl SplinterDaemon {
         debug!("Shutting down admin service's peer manager notification receiver...");
         let _ = admin_notification_join.join();
         debug!("Shutting down admin service's peer manager notification receiver (complete)");
+        #[cfg(feature = "circuit-durability")]
+        let _ = durable_retry_join.join();
         connection_manager_shutdown.shutdown();
         connection_manager.await_shutdown();
         self.mesh.shutdown_signaler().shutdown();
@@ -1050,6 +1275,12 @@ pub struct SplinterDaemonBuilder {
     oauth_redirect_url: Option<String>,
     #[cfg(feature = "oauth")]
     oauth_openid_url: Option<String>,
+    #[cfg(feature = "registry-signatures")]
+    registry_publisher_keys: Vec<PublicKey>,
+    #[cfg(feature = "circuit-relay")]
+    relay_nodes: Vec<String>,
+    #[cfg(feature = "circuit-relay")]
+    relay_routes: Vec<(String, String)>,
     strict_ref_counts: Option<bool>,
 }
 
@@ -1189,6 +1420,24 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    #[cfg(feature = "registry-signatures")]
+    pub fn with_registry_publisher_keys(mut self, value: Vec<PublicKey>) -> Self {
+        self.registry_publisher_keys = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    pub fn with_relay_nodes(mut self, value: Vec<String>) -> Self {
+        self.relay_nodes = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    pub fn with_relay_routes(mut self, value: Vec<(String, String)>) -> Self {
+        self.relay_routes = value;
+        self
+    }
+
     pub fn with_strict_ref_counts(mut self, strict_ref_counts: bool) -> Self {
         self.strict_ref_counts = Some(strict_ref_counts);
         self
@@ -1305,6 +1554,12 @@ impl SplinterDaemonBuilder {
             oauth_redirect_url: self.oauth_redirect_url,
             #[cfg(feature = "oauth")]
             oauth_openid_url: self.oauth_openid_url,
+            #[cfg(feature = "registry-signatures")]
+            registry_publisher_keys: self.registry_publisher_keys,
+            #[cfg(feature = "circuit-relay")]
+            relay_nodes: self.relay_nodes,
+            #[cfg(feature = "circuit-relay")]
+            relay_routes: self.relay_routes,
             heartbeat,
             strict_ref_counts,
         })
@@ -1336,6 +1591,13 @@ fn set_up_circuit_dispatcher(
     node_id: &str,
     routing_reader: Box<dyn RoutingTableReader>,
     routing_writer: Box<dyn RoutingTableWriter>,
+    #[cfg(feature = "metrics")] metrics: &MetricsRegistry,
+    #[cfg(feature = "circuit-relay")] relay_table: RelayTable,
+    #[cfg(feature = "circuit-durability")] durable_message_store: Box<
+        dyn splinter::circuit::durable::store::DurableMessageStore,
+    >,
+    #[cfg(feature = "circuit-durability")] durable_delivery: DurableDelivery,
+    #[cfg(feature = "challenge-authorization")] authorization_manager: &AuthorizationManager,
 ) -> Dispatcher<CircuitMessageType> {
     let mut dispatcher = Dispatcher::<CircuitMessageType>::new(Box::new(network_sender));
 
@@ -1352,8 +1614,34 @@ fn set_up_circuit_dispatcher(
 
     let direct_message_handler =
         CircuitDirectMessageHandler::new(node_id.to_string(), routing_reader.clone());
+    #[cfg(feature = "metrics")]
+    let direct_message_handler = direct_message_handler.with_metrics(metrics.clone());
+    #[cfg(feature = "circuit-relay")]
+    let direct_message_handler = direct_message_handler.with_relay_table(relay_table);
+    #[cfg(feature = "circuit-durability")]
+    let direct_message_handler =
+        direct_message_handler.with_durable_delivery(durable_delivery.clone());
+    #[cfg(feature = "challenge-authorization")]
+    let direct_message_handler = direct_message_handler
+        .with_authorization_inquisitor(authorization_manager.authorization_inquisitor());
     dispatcher.set_handler(Box::new(direct_message_handler));
 
+    #[cfg(feature = "circuit-durability")]
+    {
+        let durable_message_handler = CircuitDurableMessageHandler::new(
+            node_id.to_string(),
+            routing_reader.clone(),
+            durable_message_store,
+        );
+        #[cfg(feature = "challenge-authorization")]
+        let durable_message_handler = durable_message_handler
+            .with_authorization_inquisitor(authorization_manager.authorization_inquisitor());
+        dispatcher.set_handler(Box::new(durable_message_handler));
+        dispatcher.set_handler(Box::new(CircuitDurableMessageAckHandler::new(
+            durable_delivery,
+        )));
+    }
+
     let circuit_error_handler =
         CircuitErrorHandler::new(node_id.to_string(), routing_reader.clone());
     dispatcher.set_handler(Box::new(circuit_error_handler));
@@ -1361,11 +1649,42 @@ fn set_up_circuit_dispatcher(
     // Circuit Admin handlers
     let admin_direct_message_handler =
         AdminDirectMessageHandler::new(node_id.to_string(), routing_reader);
+    #[cfg(feature = "metrics")]
+    let admin_direct_message_handler = admin_direct_message_handler.with_metrics(metrics.clone());
     dispatcher.set_handler(Box::new(admin_direct_message_handler));
 
     dispatcher
 }
 
+/// Reports the number of messages waiting in a dispatch loop's queue.
+#[cfg(feature = "metrics")]
+fn register_queue_depth_metric<MT>(
+    metrics: &MetricsRegistry,
+    dispatcher: &str,
+    sender: DispatchMessageSender<MT>,
+) where
+    MT: std::any::Any + std::hash::Hash + Eq + fmt::Debug + Clone + Send + 'static,
+{
+    metrics.register_gauge_fn(
+        "splinter_dispatch_queue_depth",
+        "Number of messages waiting to be dispatched, by dispatcher",
+        &[("dispatcher", dispatcher)],
+        move || sender.queue_depth() as f64,
+    );
+}
+
+/// Loads the node's `splinterd` private key from the `keys` directory in the state directory;
+/// this key is used to sign challenges when authorizing with other nodes and to sign PBFT
+/// consensus messages. Returns `None` if the node has no key.
+#[cfg(any(feature = "challenge-authorization", feature = "scabbard-pbft"))]
+fn load_node_signer(state_dir: &str) -> Result<Option<Box<dyn Signer>>, StartError> {
+    let key_dir = Path::new(state_dir).join("keys");
+    let private_key = load_key("splinterd", &[key_dir])
+        .map_err(|err| StartError::NetworkError(format!("Unable to load node key: {}", err)))?;
+
+    Ok(private_key.map(|private_key| Secp256k1Context::new().new_signer(private_key)))
+}
+
 #[cfg(not(feature = "registry-database"))]
 fn create_local_registry(state_dir: &str) -> Result<Box<dyn RwRegistry>, StartError> {
     let local_registry_path = Path::new(state_dir)
@@ -1394,6 +1713,7 @@ fn create_registry(
     registries: &[String],
     auto_refresh_interval: u64,
     forced_refresh_interval: u64,
+    #[cfg(feature = "registry-signatures")] publisher_keys: &[PublicKey],
     #[cfg(feature = "registry-database")] store_factory: &dyn splinter::store::StoreFactory,
 ) -> Result<(Box<dyn RwRegistry>, RegistryShutdownHandle), StartError> {
     let mut registry_shutdown_handle = RegistryShutdownHandle::new();
@@ -1441,15 +1761,39 @@ fn create_registry(
                 } else {
                     None
                 };
-                match RemoteYamlRegistry::new(
+                #[cfg(feature = "registry-signatures")]
+                let remote_registry = if publisher_keys.is_empty() {
+                    RemoteYamlRegistry::new(
+                        registry,
+                        state_dir,
+                        auto_refresh_interval,
+                        forced_refresh_interval,
+                    )
+                } else {
+                    RemoteYamlRegistry::new_with_publisher_keys(
+                        registry,
+                        state_dir,
+                        auto_refresh_interval,
+                        forced_refresh_interval,
+                        publisher_keys.to_vec(),
+                        Secp256k1Context::new().new_verifier(),
+                    )
+                };
+                #[cfg(not(feature = "registry-signatures"))]
+                let remote_registry = RemoteYamlRegistry::new(
                     registry,
                     state_dir,
                     auto_refresh_interval,
                     forced_refresh_interval,
-                ) {
+                );
+                match remote_registry {
                     Ok(registry) => {
                         registry_shutdown_handle
                             .add_remote_yaml_shutdown_handle(registry.shutdown_handle());
+                        #[cfg(feature = "health")]
+                        registry_shutdown_handle.add_remote_yaml_refresh_status_handle(
+                            registry.refresh_status_handle(),
+                        );
                         Some(Box::new(registry) as Box<dyn RegistryReader>)
                     }
                     Err(err) => {
@@ -1487,6 +1831,8 @@ fn parse_registry_arg(registry: &str) -> Result<(&str, &str), &str> {
 #[derive(Default)]
 struct RegistryShutdownHandle {
     remote_yaml_shutdown_handles: Vec<RemoteYamlShutdownHandle>,
+    #[cfg(feature = "health")]
+    remote_yaml_refresh_status_handles: Vec<RemoteYamlRefreshStatusHandle>,
 }
 
 impl RegistryShutdownHandle {
@@ -1498,6 +1844,16 @@ impl RegistryShutdownHandle {
         self.remote_yaml_shutdown_handles.push(handle);
     }
 
+    #[cfg(feature = "health")]
+    fn add_remote_yaml_refresh_status_handle(&mut self, handle: RemoteYamlRefreshStatusHandle) {
+        self.remote_yaml_refresh_status_handles.push(handle);
+    }
+
+    #[cfg(feature = "health")]
+    fn remote_yaml_refresh_status_handles(&self) -> Vec<RemoteYamlRefreshStatusHandle> {
+        self.remote_yaml_refresh_status_handles.clone()
+    }
+
     fn shutdown(&self) {
         self.remote_yaml_shutdown_handles
             .iter()
@@ -1505,6 +1861,78 @@ impl RegistryShutdownHandle {
     }
 }
 
+/// Updates peers when their node is updated in the registry.
+///
+/// A peer whose endpoints change is reconnected to its new endpoints. A peer whose keys change is
+/// reconnected so that it is authorized against its new keys; a connection authorized with a key
+/// that has been removed from the registry is not kept open. The admin service's key verifier
+/// reads the node's keys from the registry for each message it verifies, so the new keys take
+/// effect there as soon as the registry is updated.
+///
+/// The peers are updated on the updater's own thread, so the registry does not wait on the peer
+/// manager while it notifies its subscribers.
+#[cfg(feature = "registry-notifications")]
+struct PeerRegistryUpdater {
+    sender: Sender<(Node, Node)>,
+}
+
+#[cfg(feature = "registry-notifications")]
+impl PeerRegistryUpdater {
+    fn new(peer_connector: PeerManagerConnector) -> Result<Self, StartError> {
+        let (sender, receiver) = channel();
+        thread::Builder::new()
+            .name("PeerRegistryUpdater".into())
+            .spawn(move || {
+                for (previous, node) in receiver {
+                    update_peer(&peer_connector, &previous, &node);
+                }
+            })
+            .map_err(|err| {
+                StartError::RegistryError(format!("Unable to start peer registry updater: {}", err))
+            })?;
+
+        Ok(Self { sender })
+    }
+}
+
+#[cfg(feature = "registry-notifications")]
+impl RegistrySubscriber for PeerRegistryUpdater {
+    fn handle_notification(
+        &self,
+        notification: &RegistryNotification,
+    ) -> Result<(), RegistrySubscriberError> {
+        if let RegistryNotification::NodeUpdated { previous, node } = notification {
+            if previous.endpoints != node.endpoints || previous.keys != node.keys {
+                // The updater's thread only stops if it panicked
+                self.sender
+                    .send((previous.clone(), node.clone()))
+                    .map_err(|_| RegistrySubscriberError::Unsubscribe)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reconnects the peer for a node whose endpoints or keys have been updated in the registry.
+#[cfg(feature = "registry-notifications")]
+fn update_peer(peer_connector: &PeerManagerConnector, previous: &Node, node: &Node) {
+    if previous.endpoints != node.endpoints {
+        if let Err(err) =
+            peer_connector.update_peer_endpoints(node.identity.clone(), node.endpoints.clone())
+        {
+            error!(
+                "Unable to update the endpoints of peer {}: {}",
+                node.identity, err
+            );
+        }
+    }
+    if previous.keys != node.keys {
+        if let Err(err) = peer_connector.reconnect_peer(node.identity.clone()) {
+            error!("Unable to reconnect peer {}: {}", node.identity, err);
+        }
+    }
+}
+
 #[cfg(feature = "authorization-handler-allow-keys")]
 fn create_allow_keys_authorization_handler(
     state_dir: &str,
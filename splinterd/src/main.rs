@@ -36,6 +36,8 @@ use crate::config::{
 use crate::daemon::SplinterDaemonBuilder;
 use clap::{clap_app, crate_version};
 use clap::{Arg, ArgMatches};
+#[cfg(feature = "registry-signatures")]
+use cylinder::PublicKey;
 
 use std::env;
 use std::fs::{self, File};
@@ -342,6 +344,18 @@ fn main() {
             .alias("rest-api-key"),
     );
 
+    #[cfg(feature = "tls-crl")]
+    let app = app.arg(
+        Arg::with_name("tls_crl_file")
+            .long("tls-crl-file")
+            .help(
+                "File path to the certificate revocation list used to reject peer certificates; \
+                 only supported by tcps:// connections, so QUIC and secure WebSocket connections \
+                 are disabled when it is set",
+            )
+            .takes_value(true),
+    );
+
     #[cfg(feature = "rest-api-cors")]
     let app = app.arg(
         Arg::with_name("whitelist")
@@ -386,6 +400,41 @@ fn main() {
                 .takes_value(true),
         );
 
+    #[cfg(feature = "registry-signatures")]
+    let app = app.arg(
+        Arg::with_name("registry_publisher_keys")
+            .long("registry-publisher-key")
+            .long_help(
+                "Hex-encoded public key of a publisher whose signature remote registry files \
+                 must carry",
+            )
+            .takes_value(true)
+            .multiple(true),
+    );
+
+    #[cfg(feature = "circuit-relay")]
+    let app = app
+        .arg(
+            Arg::with_name("relay_nodes")
+                .long("relay-node")
+                .long_help(
+                    "ID of a node that is allowed to relay messages for circuits it is not a \
+                     member of",
+                )
+                .takes_value(true)
+                .multiple(true),
+        )
+        .arg(
+            Arg::with_name("relay_routes")
+                .long("relay-route")
+                .long_help(
+                    "Route to a node that is reached through another node, in the form \
+                     <destination node ID>=<next hop node ID>",
+                )
+                .takes_value(true)
+                .multiple(true),
+        );
+
     let matches = app.get_matches();
 
     let log_level = match matches.occurrences_of("verbose") {
@@ -430,7 +479,7 @@ fn start_daemon(matches: ArgMatches) -> Result<(), UserError> {
 
     if config.no_tls() {
         for network_endpoint in config.network_endpoints() {
-            if network_endpoint.starts_with("tcps://") {
+            if network_endpoint.starts_with("tcps://") || network_endpoint.starts_with("quic://") {
                 return Err(UserError::InvalidArgument(format!(
                     "TLS is disabled, thus endpoint {} is invalid",
                     network_endpoint,
@@ -521,9 +570,54 @@ fn start_daemon(matches: ArgMatches) -> Result<(), UserError> {
             .with_oauth_openid_url(config.oauth_openid_url().map(ToOwned::to_owned));
     }
 
+    #[cfg(feature = "registry-signatures")]
+    {
+        let publisher_keys = config
+            .registry_publisher_keys()
+            .unwrap_or(&[])
+            .iter()
+            .map(|key| {
+                PublicKey::new_from_hex(key).map_err(|err| {
+                    UserError::InvalidArgument(format!(
+                        "registry publisher key {} is not a valid public key: {}",
+                        key, err
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        daemon_builder = daemon_builder.with_registry_publisher_keys(publisher_keys);
+    }
+
+    #[cfg(feature = "circuit-relay")]
+    {
+        let relay_routes = config
+            .relay_routes()
+            .unwrap_or(&[])
+            .iter()
+            .map(|route| parse_relay_route(route))
+            .collect::<Result<Vec<_>, _>>()?;
+        daemon_builder = daemon_builder
+            .with_relay_nodes(config.relay_nodes().unwrap_or(&[]).to_vec())
+            .with_relay_routes(relay_routes);
+    }
+
     let mut node = daemon_builder.build().map_err(|err| {
         UserError::daemon_err_with_source("unable to build the Splinter daemon", Box::new(err))
     })?;
     node.start(transport)?;
     Ok(())
 }
+
+/// Parses a relay route of the form `<destination node ID>=<next hop node ID>`.
+#[cfg(feature = "circuit-relay")]
+fn parse_relay_route(route: &str) -> Result<(String, String), UserError> {
+    match route.splitn(2, '=').collect::<Vec<_>>().as_slice() {
+        [destination, next_hop] if !destination.is_empty() && !next_hop.is_empty() => {
+            Ok((destination.to_string(), next_hop.to_string()))
+        }
+        _ => Err(UserError::InvalidArgument(format!(
+            "relay route {} is not of the form <destination node ID>=<next hop node ID>",
+            route
+        ))),
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use splinter::actix_web::{Error, HttpResponse};
+use splinter::futures::{Future, IntoFuture};
+use splinter::metrics::MetricsRegistry;
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::Permission;
+
+#[cfg(feature = "authorization")]
+pub const METRICS_READ_PERMISSION: Permission = Permission::Check("metrics.read");
+
+/// The content type of the Prometheus text exposition format
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+pub fn get_metrics(
+    metrics: &MetricsRegistry,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        HttpResponse::Ok()
+            .content_type(PROMETHEUS_CONTENT_TYPE)
+            .body(metrics.render())
+            .into_future(),
+    )
+}
@@ -13,6 +13,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "metrics")]
+mod metrics;
 mod status;
 
+#[cfg(feature = "metrics")]
+pub use metrics::*;
 pub use status::*;
@@ -14,10 +14,20 @@
 
 use std::fs;
 use std::path::Path;
+#[cfg(feature = "tls-reload")]
+use std::thread;
+#[cfg(feature = "tls-reload")]
+use std::time::{Duration, Instant};
 
+#[cfg(feature = "tls-reload")]
+use signal_hook::{consts::SIGHUP, iterator::Signals};
 use splinter::transport::multi::MultiTransport;
+#[cfg(feature = "quic-transport")]
+use splinter::transport::quic::QuicTransport;
 use splinter::transport::socket::TcpTransport;
 use splinter::transport::socket::TlsTransport;
+#[cfg(feature = "tls-reload")]
+use splinter::transport::tls::TlsReloader;
 use splinter::transport::tls::{TlsConfig, TlsConfigBuilder};
 #[cfg(feature = "ws-transport")]
 use splinter::transport::ws::WsTransport;
@@ -28,6 +38,10 @@ use crate::error::GetTransportError;
 
 type SendableTransport = Box<dyn Transport + Send>;
 
+/// How often the TLS certificate and key files are checked for changes
+#[cfg(feature = "tls-reload")]
+const TLS_RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportError> {
     let mut transports: Vec<SendableTransport> = vec![];
 
@@ -43,20 +57,53 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
         validate_tls_config(&tls_config)?;
         print_tls_config(&tls_config)?;
 
-        transports.push(Box::new(TlsTransport::new(
+        let tls_transport = TlsTransport::new(
             tls_config.ca_certs_file().to_owned(),
             tls_config.client_private_key_file().to_string(),
             tls_config.client_cert_file().to_string(),
             tls_config.server_private_key_file().to_string(),
             tls_config.server_cert_file().to_string(),
-        )?));
+        )?;
+
+        #[cfg(feature = "tls-crl")]
+        let tls_transport = match tls_config.crl_file() {
+            Some(crl_file) => tls_transport.with_crl_file(crl_file.to_string())?,
+            None => tls_transport,
+        };
+
+        #[cfg(feature = "tls-reload")]
+        let mut reloaders = vec![tls_transport.reloader()];
+
+        transports.push(Box::new(tls_transport));
 
         #[cfg(feature = "ws-transport")]
-        transports.push(Box::new(WsTransport::new(Some(&tls_config)).map_err(
-            |e| {
+        {
+            let ws_transport = WsTransport::new(Some(&tls_config)).map_err(|e| {
                 GetTransportError::CertError(format!("Failed to create WebSocket transport: {}", e))
-            },
-        )?));
+            })?;
+            #[cfg(feature = "tls-reload")]
+            reloaders.extend(ws_transport.reloader());
+            transports.push(Box::new(ws_transport));
+        }
+
+        #[cfg(feature = "quic-transport")]
+        {
+            let quic_transport = if config.tls_insecure() {
+                warn!("Starting QuicTransport in insecure mode");
+                QuicTransport::new_insecure(&tls_config)
+            } else {
+                QuicTransport::new(&tls_config)
+            }
+            .map_err(|e| {
+                GetTransportError::CertError(format!("Failed to create QUIC transport: {}", e))
+            })?;
+            #[cfg(feature = "tls-reload")]
+            reloaders.push(quic_transport.reloader());
+            transports.push(Box::new(quic_transport));
+        }
+
+        #[cfg(feature = "tls-reload")]
+        start_tls_reload_thread(reloaders)?;
     } else {
         #[cfg(feature = "ws-transport")]
         transports.push(Box::new(WsTransport::default()));
@@ -78,6 +125,18 @@ fn build_tls_config(config: &Config) -> Result<TlsConfig, GetTransportError> {
         builder = builder.with_ca_certs_file(config.tls_ca_file().to_string());
     }
 
+    #[cfg(feature = "tls-crl")]
+    {
+        if let Some(crl_file) = config.tls_crl_file() {
+            if config.tls_insecure() {
+                return Err(GetTransportError::CertError(
+                    "A certificate revocation list can't be used in insecure mode".into(),
+                ));
+            }
+            builder = builder.with_crl_file(crl_file.to_string());
+        }
+    }
+
     builder
         .build()
         .map_err(|e| GetTransportError::CertError(format!("TLS config error: {}", e)))
@@ -148,6 +207,50 @@ fn print_tls_config(tls_config: &TlsConfig) -> Result<(), GetTransportError> {
     if let Some(ca_path) = tls_config.ca_certs_file() {
         debug!("Using ca certs file: {:?}", ca_path);
     }
+    #[cfg(feature = "tls-crl")]
+    {
+        if let Some(crl_path) = tls_config.crl_file() {
+            debug!("Using certificate revocation list file: {:?}", crl_path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Starts a thread that reloads the TLS certificate and key files of the transports when splinterd
+/// receives a SIGHUP, or when the files are modified.
+///
+/// Connections that are already established are kept, unless a reloaded certificate revocation
+/// list revokes the certificate of their remote endpoint.
+#[cfg(feature = "tls-reload")]
+fn start_tls_reload_thread(reloaders: Vec<TlsReloader>) -> Result<(), GetTransportError> {
+    let mut signals = Signals::new(&[SIGHUP])?;
+
+    thread::Builder::new()
+        .name("TlsReloader".into())
+        .spawn(move || {
+            let mut last_check = Instant::now();
+            loop {
+                if signals.pending().next().is_some() {
+                    for reloader in &reloaders {
+                        match reloader.reload() {
+                            Ok(()) => info!("Reloaded TLS certificates after SIGHUP"),
+                            Err(err) => error!("Unable to reload TLS certificates: {}", err),
+                        }
+                    }
+                } else if last_check.elapsed() >= TLS_RELOAD_CHECK_INTERVAL {
+                    last_check = Instant::now();
+                    for reloader in &reloaders {
+                        match reloader.reload_if_changed() {
+                            Ok(true) => info!("Reloaded modified TLS certificates"),
+                            Ok(false) => (),
+                            Err(err) => error!("Unable to reload TLS certificates: {}", err),
+                        }
+                    }
+                }
+                thread::sleep(Duration::from_secs(1));
+            }
+        })?;
 
     Ok(())
 }